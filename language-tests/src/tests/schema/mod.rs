@@ -165,6 +165,8 @@ pub enum NewPlannerStrategyConfig {
 	BestEffortRo,
 	/// Require new planner for all read-only statements (hard fail on Unimplemented).
 	AllRo,
+	/// Require new planner for read-only statements and plan supported writes.
+	All,
 	/// Skip new planner entirely; always use legacy compute.
 	ComputeOnly,
 }
@@ -179,6 +181,7 @@ impl fmt::Display for NewPlannerStrategyConfig {
 		match self {
 			Self::BestEffortRo => f.write_str("best-effort-ro"),
 			Self::AllRo => f.write_str("all-ro"),
+			Self::All => f.write_str("all"),
 			Self::ComputeOnly => f.write_str("compute-only"),
 		}
 	}
@@ -190,6 +193,7 @@ impl From<NewPlannerStrategy> for NewPlannerStrategyConfig {
 			NewPlannerStrategy::BestEffortReadOnlyStatements => Self::BestEffortRo,
 			NewPlannerStrategy::ComputeOnly => Self::ComputeOnly,
 			NewPlannerStrategy::AllReadOnlyStatements => Self::AllRo,
+			NewPlannerStrategy::AllStatements => Self::All,
 		}
 	}
 }
@@ -202,6 +206,7 @@ impl From<NewPlannerStrategyConfig> for NewPlannerStrategy {
 			}
 			NewPlannerStrategyConfig::ComputeOnly => NewPlannerStrategy::ComputeOnly,
 			NewPlannerStrategyConfig::AllRo => NewPlannerStrategy::AllReadOnlyStatements,
+			NewPlannerStrategyConfig::All => NewPlannerStrategy::AllStatements,
 		}
 	}
 }
@@ -84,9 +84,18 @@ pub enum ApiError {
 	#[error("Request body must be binary data")]
 	RequestBodyNotBinary,
 
+	#[error("Invalid request body: {0}")]
+	InvalidRequestBodySchema(String),
+
+	#[error("Authentication required: You must be signed in to access this resource")]
+	Unauthorized,
+
 	#[error("Permission denied: You are not allowed to access this resource")]
 	PermissionDenied,
 
+	#[error("Too many requests: The rate limit for this resource has been exceeded")]
+	TooManyRequests,
+
 	#[error("Not found")]
 	NotFound,
 }
@@ -123,7 +132,10 @@ impl ApiError {
 			} => StatusCode::INTERNAL_SERVER_ERROR,
 			Self::FinalActionRequestParseFailure => StatusCode::BAD_REQUEST,
 			Self::RequestBodyNotBinary => StatusCode::BAD_REQUEST,
+			Self::InvalidRequestBodySchema(_) => StatusCode::BAD_REQUEST,
+			Self::Unauthorized => StatusCode::UNAUTHORIZED,
 			Self::PermissionDenied => StatusCode::FORBIDDEN,
+			Self::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
 			Self::NotFound => StatusCode::NOT_FOUND,
 		}
 	}
@@ -132,7 +144,9 @@ impl ApiError {
 		let msg = self.to_string();
 		match &self {
 			Self::NotFound => TypesError::not_found(msg, None),
-			Self::PermissionDenied => TypesError::not_allowed(msg, None),
+			Self::Unauthorized | Self::PermissionDenied | Self::TooManyRequests => {
+				TypesError::not_allowed(msg, None)
+			}
 			Self::BodyDecodeFailure | Self::InvalidApiResponse(_) => {
 				TypesError::serialization(msg, SerializationError::Deserialization)
 			}
@@ -160,6 +174,7 @@ impl ApiError {
 			| Self::InvalidRequestBodyType {
 				..
 			}
+			| Self::InvalidRequestBodySchema(_)
 			| Self::RequestBodyNotBinary
 			| Self::RequestBodyTooLarge(_)
 			| Self::NoOutputStrategy
@@ -0,0 +1,91 @@
+use http::header::{
+	ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+	ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE, VARY,
+};
+use http::{HeaderMap, HeaderName, HeaderValue};
+use surrealdb_types::SurrealValue;
+
+use crate::api::err::ApiError;
+
+/// Options accepted by the `api::cors` middleware function.
+#[derive(Clone, Debug, SurrealValue)]
+#[surreal(crate = "surrealdb_types")]
+#[surreal(default)]
+pub struct CorsOptions {
+	/// The origins which are allowed, where `*` allows any origin
+	pub origins: Vec<String>,
+	/// The methods which are advertised as allowed
+	pub methods: Vec<String>,
+	/// The request headers which are advertised as allowed
+	pub headers: Vec<String>,
+	/// The response headers which the browser may expose to scripts
+	pub expose: Vec<String>,
+	/// Whether the browser may send credentials with the request
+	pub credentials: bool,
+	/// How long the browser may cache the CORS response
+	pub max_age: Option<std::time::Duration>,
+}
+
+impl Default for CorsOptions {
+	fn default() -> Self {
+		Self {
+			origins: vec!["*".to_string()],
+			methods: ["GET", "POST", "PUT", "PATCH", "DELETE"].map(String::from).to_vec(),
+			headers: ["Accept", "Authorization", "Content-Type"].map(String::from).to_vec(),
+			expose: Vec::new(),
+			credentials: false,
+			max_age: None,
+		}
+	}
+}
+
+impl CorsOptions {
+	/// Checks whether the request origin is allowed by these options.
+	pub fn allows(&self, origin: &str) -> bool {
+		self.origins.iter().any(|x| x == "*" || x.eq_ignore_ascii_case(origin))
+	}
+
+	/// Applies the CORS response headers for an allowed request origin.
+	pub fn apply(&self, origin: &HeaderValue, headers: &mut HeaderMap) -> Result<(), ApiError> {
+		// A wildcard can not be combined with credentials, so echo the origin instead
+		if self.origins.iter().any(|x| x == "*") && !self.credentials {
+			headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
+		} else {
+			headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+			headers.append(VARY, HeaderValue::from_static("Origin"));
+		}
+
+		if self.credentials {
+			headers.insert(ACCESS_CONTROL_ALLOW_CREDENTIALS, HeaderValue::from_static("true"));
+		}
+
+		insert_list(headers, ACCESS_CONTROL_ALLOW_METHODS, &self.methods)?;
+		insert_list(headers, ACCESS_CONTROL_ALLOW_HEADERS, &self.headers)?;
+		insert_list(headers, ACCESS_CONTROL_EXPOSE_HEADERS, &self.expose)?;
+
+		if let Some(max_age) = self.max_age {
+			headers.insert(ACCESS_CONTROL_MAX_AGE, HeaderValue::from(max_age.as_secs()));
+		}
+
+		Ok(())
+	}
+}
+
+fn insert_list(
+	headers: &mut HeaderMap,
+	name: HeaderName,
+	values: &[String],
+) -> Result<(), ApiError> {
+	if values.is_empty() {
+		return Ok(());
+	}
+
+	let value = values.join(", ");
+	let value = HeaderValue::from_str(&value).map_err(|e| ApiError::InvalidHeaderValue {
+		name: name.to_string(),
+		value: format!("{}: {}", value, e),
+	})?;
+
+	headers.insert(name, value);
+	Ok(())
+}
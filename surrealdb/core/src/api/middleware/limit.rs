@@ -0,0 +1,99 @@
+use std::convert::Infallible;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use quick_cache::sync::Cache;
+use web_time::Instant;
+
+use crate::cnf::API_RATE_LIMIT_CACHE_SIZE;
+
+/// The fixed windows tracked by the `api::rate_limit` middleware.
+///
+/// Counters are held in memory on each node, so when running a cluster the
+/// effective limit for a client is the configured limit multiplied by the
+/// number of nodes serving the endpoint. Counters which are evicted from the
+/// cache simply start a new window.
+static RATE_LIMITS: LazyLock<Cache<String, Arc<Mutex<Window>>>> =
+	LazyLock::new(|| Cache::new(API_RATE_LIMIT_CACHE_SIZE.max(10)));
+
+struct Window {
+	start: Instant,
+	count: u64,
+}
+
+/// The result of checking a request against a rate limit.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RateLimit {
+	/// The request is allowed, with the number of requests remaining in the window
+	Allowed {
+		remaining: u64,
+	},
+	/// The request is denied until the current window resets
+	Exceeded {
+		retry_after: Duration,
+	},
+}
+
+/// Counts a request against the fixed window identified by `key`.
+pub fn check(key: String, limit: u64, window: Duration) -> RateLimit {
+	let entry = RATE_LIMITS.get_or_insert_with(&key, || {
+		Ok::<_, Infallible>(Arc::new(Mutex::new(Window {
+			start: Instant::now(),
+			count: 0,
+		})))
+	});
+	// The error type is infallible
+	let Ok(entry) = entry;
+	let mut entry = entry.lock();
+	// Start a new window once the current one has elapsed
+	let elapsed = entry.start.elapsed();
+	if elapsed >= window {
+		entry.start = Instant::now();
+		entry.count = 0;
+	}
+	// Check whether the limit has been reached
+	if entry.count >= limit {
+		return RateLimit::Exceeded {
+			retry_after: window.saturating_sub(entry.start.elapsed()),
+		};
+	}
+	entry.count += 1;
+	RateLimit::Allowed {
+		remaining: limit - entry.count,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fixed_window_limits_requests() {
+		let key = "test/test/fixed_window_limits_requests".to_string();
+		let window = Duration::from_secs(60);
+		assert_eq!(
+			check(key.clone(), 2, window),
+			RateLimit::Allowed {
+				remaining: 1
+			}
+		);
+		assert_eq!(
+			check(key.clone(), 2, window),
+			RateLimit::Allowed {
+				remaining: 0
+			}
+		);
+		assert!(matches!(check(key, 2, window), RateLimit::Exceeded { .. }));
+	}
+
+	#[test]
+	fn window_resets_after_elapsing() {
+		let key = "test/test/window_resets_after_elapsing".to_string();
+		let window = Duration::from_millis(10);
+		assert!(matches!(check(key.clone(), 1, window), RateLimit::Allowed { .. }));
+		assert!(matches!(check(key.clone(), 1, window), RateLimit::Exceeded { .. }));
+		std::thread::sleep(Duration::from_millis(20));
+		assert!(matches!(check(key, 1, window), RateLimit::Allowed { .. }));
+	}
+}
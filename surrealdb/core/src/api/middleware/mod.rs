@@ -1,3 +1,5 @@
 pub mod common;
+pub mod cors;
+pub mod limit;
 pub mod req;
 pub mod res;
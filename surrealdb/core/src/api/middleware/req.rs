@@ -1,5 +1,5 @@
 use anyhow::Result;
-use headers::{ContentType, HeaderMapExt};
+use headers::{ContentLength, ContentType, HeaderMapExt};
 use mime::{APPLICATION_JSON, APPLICATION_OCTET_STREAM, Mime, TEXT_PLAIN};
 use surrealdb_types::Value;
 
@@ -7,14 +7,46 @@ use super::common::{APPLICATION_CBOR, APPLICATION_SDB_FB};
 use crate::api::err::ApiError;
 use crate::api::middleware::common::{APPLICATION_SDB_NATIVE, BodyStrategy};
 use crate::api::request::ApiRequest;
+use crate::expr::{Bytesize, Kind};
 use crate::kvs::IntoBytes;
 use crate::rpc::format;
+use crate::sql::expression::convert_public_value_to_internal;
+use crate::val::convert_value_to_public_value;
 
 pub async fn body(req: &mut ApiRequest, strategy: BodyStrategy) -> Result<()> {
 	let mut parser = BodyParser::from((req, strategy));
 	parser.process().await
 }
 
+/// Checks that neither the declared nor the received request body exceeds `max`.
+pub(crate) fn ensure_max_size(req: &ApiRequest, max: Bytesize) -> Result<()> {
+	// Reject early based on the declared length
+	if let Some(ContentLength(len)) = req.headers.typed_get::<ContentLength>()
+		&& len > max.0
+	{
+		return Err(ApiError::RequestBodyTooLarge(max).into());
+	}
+	// Check the length of the body as received
+	let len = match &req.body {
+		Value::Bytes(bytes) => bytes.len() as u64,
+		Value::String(text) => text.len() as u64,
+		_ => 0,
+	};
+	if len > max.0 {
+		return Err(ApiError::RequestBodyTooLarge(max).into());
+	}
+	Ok(())
+}
+
+/// Coerces the parsed request body to `kind`, rejecting bodies which do not match.
+pub(crate) fn validate_body(req: &mut ApiRequest, kind: &Kind) -> Result<()> {
+	let body = convert_public_value_to_internal(std::mem::take(&mut req.body));
+	let body =
+		body.coerce_to_kind(kind).map_err(|e| ApiError::InvalidRequestBodySchema(e.to_string()))?;
+	req.body = convert_value_to_public_value(body)?;
+	Ok(())
+}
+
 pub struct BodyParser<'a> {
 	mime: Option<Mime>,
 	req: &'a mut ApiRequest,
@@ -5,6 +5,7 @@ use anyhow::Result;
 use http::header::{ACCEPT, CONTENT_TYPE};
 use http::{HeaderMap, HeaderValue};
 use mime::{APPLICATION_JSON, APPLICATION_OCTET_STREAM, Mime, Name, TEXT_PLAIN};
+use surrealdb_types::ToSql;
 
 use crate::api::err::ApiError;
 use crate::api::format as api_format;
@@ -108,6 +109,30 @@ pub fn convert_response_value(response: &mut ApiResponse, strategy: BodyStrategy
 	Ok(())
 }
 
+/// Computes a strong entity tag for a response body.
+///
+/// Serialized bodies are hashed as they are sent, while native bodies are
+/// hashed through their SurrealQL representation, which is deterministic.
+pub fn entity_tag(body: &PublicValue) -> String {
+	let hash = match body {
+		PublicValue::Bytes(bytes) => blake3::hash(bytes.as_slice()),
+		PublicValue::String(text) => blake3::hash(text.as_bytes()),
+		body => blake3::hash(body.to_sql().as_bytes()),
+	};
+	format!("\"{}\"", &hash.to_hex()[..32])
+}
+
+/// Checks whether an `If-None-Match` request header matches an entity tag.
+pub fn matches_entity_tag(value: &HeaderValue, etag: &str) -> bool {
+	let Ok(value) = value.to_str() else {
+		return false;
+	};
+	value.split(',').map(str::trim).any(|x| {
+		// Weak comparison ignores the weak validator prefix
+		x == "*" || x.strip_prefix("W/").unwrap_or(x) == etag
+	})
+}
+
 fn parse_accept(value: &HeaderValue) -> Vec<AcceptRange> {
 	// Validate that the header value is not empty
 	let s = value.to_str().unwrap_or("").trim();
@@ -214,11 +239,12 @@ impl AcceptRange {
 
 #[cfg(test)]
 mod tests {
-	use http::HeaderMap;
 	use http::header::ACCEPT;
+	use http::{HeaderMap, HeaderValue};
 
 	use crate::api::middleware::common::BodyStrategy;
-	use crate::api::middleware::res::output_body_strategy;
+	use crate::api::middleware::res::{entity_tag, matches_entity_tag, output_body_strategy};
+	use crate::types::PublicValue;
 
 	macro_rules! case {
 		($in:ident => None, $header:expr) => {{
@@ -286,4 +312,18 @@ mod tests {
 		// 15) Only q<=0 entries (all ignored)
 		case!(Auto => None, "application/json;q=0, */*;q=0");
 	}
+
+	#[test]
+	fn entity_tag_matching() {
+		let etag = entity_tag(&PublicValue::String("hello".to_string()));
+		assert_eq!(etag, entity_tag(&PublicValue::String("hello".to_string())));
+		assert_ne!(etag, entity_tag(&PublicValue::String("world".to_string())));
+
+		let header = |x: &str| -> HeaderValue { x.parse().unwrap() };
+		assert!(matches_entity_tag(&header(&etag), &etag));
+		assert!(matches_entity_tag(&header(&format!("W/{etag}")), &etag));
+		assert!(matches_entity_tag(&header(&format!("\"other\", {etag}")), &etag));
+		assert!(matches_entity_tag(&header("*"), &etag));
+		assert!(!matches_entity_tag(&header("\"other\""), &etag));
+	}
 }
@@ -1,6 +1,7 @@
 pub mod err;
 pub mod invocation;
 pub mod middleware;
+pub mod openapi;
 pub mod path;
 pub mod request;
 pub mod response;
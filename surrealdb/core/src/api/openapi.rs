@@ -0,0 +1,323 @@
+//! OpenAPI document generation for `DEFINE API` endpoints.
+//!
+//! The document is derived from the defined paths, methods and path
+//! parameters. Request bodies are described by the `api::req::body` and
+//! `api::req::validate` middleware, and the error responses of the built-in
+//! middleware are listed alongside the default response.
+
+use anyhow::Result;
+
+use crate::api::format;
+use crate::api::path::Segment;
+use crate::catalog::providers::{ApiProvider, DatabaseProvider};
+use crate::catalog::{ApiConfigDefinition, ApiDefinition, ApiMethod, MiddlewareDefinition};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::expr::{Base, Kind, KindLiteral};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{Array, Object, Value};
+
+/// The version of the OpenAPI specification which is generated.
+const OPENAPI_VERSION: &str = "3.1.0";
+
+/// The path, relative to the API root of a database, at which the OpenAPI
+/// document is served when no API is defined on that path.
+pub const OPENAPI_PATH: &str = "openapi.json";
+
+/// The methods which a fallback action responds to.
+const METHODS: [ApiMethod; 6] = [
+	ApiMethod::Get,
+	ApiMethod::Put,
+	ApiMethod::Post,
+	ApiMethod::Delete,
+	ApiMethod::Patch,
+	ApiMethod::Trace,
+];
+
+/// Generates the OpenAPI document for the APIs defined in the current database.
+pub(crate) async fn generate(ctx: &FrozenContext, opt: &Options) -> Result<Value> {
+	// Allowed to run?
+	opt.is_allowed(Action::View, ResourceKind::Api, &Base::Db)?;
+	// Get the database definition
+	let (ns, db) = opt.ns_db()?;
+	let txn = ctx.tx();
+	let Some(def) = txn.get_db_by_name(ns, db, None).await? else {
+		return Ok(document(ns, db, None, &[], None));
+	};
+	// Fetch the API definitions and global config
+	let apis = txn.all_db_apis(def.namespace_id, def.database_id, None).await?;
+	let global = txn.get_db_config(def.namespace_id, def.database_id, "api", None).await?;
+	let global = global.as_ref().map(|v| v.try_as_api()).transpose()?;
+	// Build the document
+	Ok(document(ns, db, def.comment.as_deref(), &apis, global))
+}
+
+fn document(
+	ns: &str,
+	db: &str,
+	comment: Option<&str>,
+	apis: &[ApiDefinition],
+	global: Option<&ApiConfigDefinition>,
+) -> Value {
+	let mut paths = Object::default();
+	for api in apis {
+		let (path, parameters) = path_item_parts(api);
+		let mut item = Object::default();
+		if let Some(comment) = &api.comment {
+			item.insert("description".to_string(), comment.clone().into());
+		}
+		if !parameters.is_empty() {
+			item.insert("parameters".to_string(), Value::Array(Array(parameters)));
+		}
+		// Operations for the explicitly defined methods
+		for action in api.actions.iter() {
+			let middleware = middleware(global, &api.config, Some(&action.config));
+			for method in action.methods.iter() {
+				item.insert(method.to_string(), operation(&middleware));
+			}
+		}
+		// Any other method is handled by the fallback action
+		if api.fallback.is_some() {
+			let middleware = middleware(global, &api.config, None);
+			for method in METHODS.iter() {
+				if !item.contains_key(&method.to_string()) {
+					item.insert(method.to_string(), operation(&middleware));
+				}
+			}
+		}
+		paths.insert(path, item.into());
+	}
+
+	let mut info = Object::default();
+	info.insert("title".to_string(), db.into());
+	info.insert("version".to_string(), "1.0.0".into());
+	if let Some(comment) = comment {
+		info.insert("description".to_string(), comment.into());
+	}
+
+	Value::from(map! {
+		"openapi".to_string() => OPENAPI_VERSION.into(),
+		"info".to_string() => info.into(),
+		"servers".to_string() => Value::Array(Array(vec![
+			Value::from(map! {
+				"url".to_string() => format!("/api/{ns}/{db}").into(),
+			}),
+		])),
+		"paths".to_string() => paths.into(),
+	})
+}
+
+/// Returns the OpenAPI path template and path parameters of an API.
+fn path_item_parts(api: &ApiDefinition) -> (String, Vec<Value>) {
+	let mut path = String::new();
+	let mut parameters = Vec::new();
+	for segment in api.path.iter() {
+		path.push('/');
+		match segment {
+			Segment::Fixed(x) => path.push_str(x),
+			Segment::Dynamic(name, kind) => {
+				path.push_str(&format!("{{{name}}}"));
+				let kind = kind.as_ref().unwrap_or(&Kind::String);
+				parameters.push(parameter(name, schema(kind), None));
+			}
+			Segment::Rest(name) => {
+				path.push_str(&format!("{{{name}}}"));
+				let description = "Matches all remaining path segments";
+				parameters.push(parameter(name, schema(&Kind::String), Some(description)));
+			}
+		}
+	}
+	if path.is_empty() {
+		path.push('/');
+	}
+	(path, parameters)
+}
+
+fn parameter(name: &str, schema: Value, description: Option<&str>) -> Value {
+	Value::from(map! {
+		"name".to_string() => name.into(),
+		"in".to_string() => "path".into(),
+		"required".to_string() => true.into(),
+		"schema".to_string() => schema,
+		"description".to_string(), if let Some(description) = description => description.into(),
+	})
+}
+
+/// Collects the middleware which applies to an operation, in execution order.
+fn middleware<'a>(
+	global: Option<&'a ApiConfigDefinition>,
+	api: &'a ApiConfigDefinition,
+	method: Option<&'a ApiConfigDefinition>,
+) -> Vec<&'a MiddlewareDefinition> {
+	global
+		.into_iter()
+		.chain(std::iter::once(api))
+		.chain(method)
+		.flat_map(|x| x.middleware.iter())
+		.collect()
+}
+
+/// Finds the first string argument of a built-in middleware function.
+fn middleware_arg<'a>(middleware: &[&'a MiddlewareDefinition], name: &str) -> Option<&'a str> {
+	middleware.iter().find(|x| x.name.eq_ignore_ascii_case(name)).and_then(|x| {
+		match x.args.first() {
+			Some(Value::String(v)) => Some(v.as_str()),
+			_ => None,
+		}
+	})
+}
+
+fn has_middleware(middleware: &[&MiddlewareDefinition], name: &str) -> bool {
+	middleware.iter().any(|x| x.name.eq_ignore_ascii_case(name))
+}
+
+fn operation(middleware: &[&MiddlewareDefinition]) -> Value {
+	let mut operation = Object::default();
+	// Describe the request body
+	let parses = has_middleware(middleware, "api::req::body");
+	let validates = middleware_arg(middleware, "api::req::validate");
+	if parses || validates.is_some() {
+		let kind = validates.and_then(|x| crate::syn::kind(x).ok()).map(Kind::from);
+		let required = !kind.as_ref().is_some_and(Kind::can_be_none);
+		let strategy = middleware_arg(middleware, "api::req::body").unwrap_or("auto");
+		operation.insert(
+			"requestBody".to_string(),
+			Value::from(map! {
+				"required".to_string() => required.into(),
+				"content".to_string() => content(strategy, schema(kind.as_ref().unwrap_or(&Kind::Any))),
+			}),
+		);
+	}
+	// Describe the responses
+	let strategy = middleware_arg(middleware, "api::res::body").unwrap_or("auto");
+	let mut responses = Object::default();
+	responses.insert(
+		"default".to_string(),
+		Value::from(map! {
+			"description".to_string() => "The API response".into(),
+			"content".to_string() => content(strategy, schema(&Kind::Any)),
+		}),
+	);
+	let errors = [
+		("api::res::cache", "304", "The response has not been modified"),
+		("api::req::validate", "400", "The request body is invalid"),
+		("api::auth::required", "401", "Authentication is required"),
+		("api::auth::role", "401", "Authentication is required"),
+		("api::auth::role", "403", "The authenticated user does not have the required role"),
+		("api::req::max_size", "413", "The request body is too large"),
+		("api::rate_limit", "429", "The rate limit has been exceeded"),
+	];
+	for (name, status, description) in errors {
+		if has_middleware(middleware, name) {
+			responses.insert(
+				status.to_string(),
+				Value::from(map! {
+					"description".to_string() => description.into(),
+				}),
+			);
+		}
+	}
+	operation.insert("responses".to_string(), responses.into());
+	operation.into()
+}
+
+/// Returns the media types for a body strategy of the `api::req::body` and
+/// `api::res::body` middleware.
+fn content(strategy: &str, schema: Value) -> Value {
+	let types: &[&str] = match strategy.to_ascii_lowercase().as_str() {
+		"json" => &[format::JSON],
+		"cbor" => &[format::CBOR],
+		"flatbuffers" => &[format::FLATBUFFERS],
+		"plain" => &[format::PLAIN],
+		"bytes" => &[format::OCTET_STREAM],
+		"native" => &[format::NATIVE],
+		_ => &[format::JSON, format::CBOR],
+	};
+	let mut content = Object::default();
+	for x in types {
+		content.insert(
+			x.to_string(),
+			Value::from(map! {
+				"schema".to_string() => schema.clone(),
+			}),
+		);
+	}
+	content.into()
+}
+
+fn typed(name: &str) -> Value {
+	Value::from(map! {
+		"type".to_string() => name.into(),
+	})
+}
+
+fn formatted(name: &str, format: &str) -> Value {
+	Value::from(map! {
+		"type".to_string() => name.into(),
+		"format".to_string() => format.into(),
+	})
+}
+
+/// Converts a SurrealQL type into a JSON Schema, as used by OpenAPI 3.1.
+pub(crate) fn schema(kind: &Kind) -> Value {
+	match kind {
+		Kind::Any | Kind::Function(_, _) | Kind::Range => Value::from(Object::default()),
+		Kind::None | Kind::Null => typed("null"),
+		Kind::Bool => typed("boolean"),
+		Kind::Bytes => Value::from(map! {
+			"type".to_string() => "string".into(),
+			"contentEncoding".to_string() => "base64".into(),
+		}),
+		Kind::Datetime => formatted("string", "date-time"),
+		Kind::Decimal | Kind::Float | Kind::Number => typed("number"),
+		Kind::Duration => formatted("string", "duration"),
+		Kind::Int => typed("integer"),
+		Kind::Object | Kind::Geometry(_) => typed("object"),
+		Kind::String | Kind::Table(_) | Kind::Record(_) | Kind::File(_) => typed("string"),
+		Kind::Uuid => formatted("string", "uuid"),
+		Kind::Regex => formatted("string", "regex"),
+		Kind::Either(kinds) => Value::from(map! {
+			"anyOf".to_string() => Value::Array(Array(kinds.iter().map(schema).collect())),
+		}),
+		Kind::Set(inner, max) | Kind::Array(inner, max) => Value::from(map! {
+			"type".to_string() => "array".into(),
+			"items".to_string() => schema(inner),
+			"maxItems".to_string(), if let Some(max) = max => Value::from(*max as i64),
+			"uniqueItems".to_string(), if matches!(kind, Kind::Set(_, _)) => true.into(),
+		}),
+		Kind::Literal(literal) => match literal {
+			KindLiteral::String(v) => constant(v.clone().into()),
+			KindLiteral::Integer(v) => constant((*v).into()),
+			KindLiteral::Float(v) => constant((*v).into()),
+			KindLiteral::Decimal(v) => constant((*v).into()),
+			KindLiteral::Duration(v) => constant(v.to_string().into()),
+			KindLiteral::Bool(v) => constant((*v).into()),
+			KindLiteral::Array(kinds) => Value::from(map! {
+				"type".to_string() => "array".into(),
+				"prefixItems".to_string() => Value::Array(Array(kinds.iter().map(schema).collect())),
+				"items".to_string() => false.into(),
+			}),
+			KindLiteral::Object(fields) => {
+				let properties =
+					Object(fields.iter().map(|(k, v)| (k.clone(), schema(v))).collect());
+				let required: Vec<Value> = fields
+					.iter()
+					.filter(|(_, v)| !v.can_be_none())
+					.map(|(k, _)| k.clone().into())
+					.collect();
+				Value::from(map! {
+					"type".to_string() => "object".into(),
+					"properties".to_string() => properties.into(),
+					"required".to_string(), if !required.is_empty() => Value::Array(Array(required)),
+					"additionalProperties".to_string() => false.into(),
+				})
+			}
+		},
+	}
+}
+
+fn constant(value: Value) -> Value {
+	Value::from(map! {
+		"const".to_string() => value,
+	})
+}
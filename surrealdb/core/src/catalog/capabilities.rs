@@ -0,0 +1,165 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::Result;
+use revision::revisioned;
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::dbs::capabilities::{ExperimentalTarget, FuncTarget, NetTarget};
+use crate::err::Error;
+use crate::expr::statements::info::InfoStructure;
+use crate::fmt::QuoteStr;
+use crate::val::{Array, Value};
+
+/// The capabilities of a namespace or database, overriding those of the
+/// server.
+///
+/// Overrides can only ever restrict the capabilities of the server. A
+/// capability is allowed within a database when it is allowed by the server,
+/// by the namespace, and by the database. Any capability which is not
+/// overridden is inherited unchanged.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CapabilitiesDefinition {
+	/// Whether embedded scripting functions can be run
+	pub(crate) scripting: Option<bool>,
+	/// The functions which can be run
+	pub(crate) allow_funcs: Option<CapabilityTargets>,
+	/// The functions which can not be run
+	pub(crate) deny_funcs: Option<CapabilityTargets>,
+	/// The network targets which can be connected to
+	pub(crate) allow_net: Option<CapabilityTargets>,
+	/// The network targets which can not be connected to
+	pub(crate) deny_net: Option<CapabilityTargets>,
+	/// The experimental features which can be used
+	pub(crate) allow_experimental: Option<CapabilityTargets>,
+	/// The experimental features which can not be used
+	pub(crate) deny_experimental: Option<CapabilityTargets>,
+}
+
+/// The targets of an overridden capability
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum CapabilityTargets {
+	/// No targets
+	None,
+	/// The listed targets
+	Some(Vec<String>),
+	/// Every target
+	All,
+}
+
+impl CapabilitiesDefinition {
+	/// Whether any capability is overridden
+	pub(crate) fn is_empty(&self) -> bool {
+		self == &Self::default()
+	}
+
+	/// The overridden target capabilities, along with their names
+	fn targets(&self) -> [(&'static str, &Option<CapabilityTargets>); 6] {
+		[
+			("allow_funcs", &self.allow_funcs),
+			("deny_funcs", &self.deny_funcs),
+			("allow_net", &self.allow_net),
+			("deny_net", &self.deny_net),
+			("allow_experimental", &self.allow_experimental),
+			("deny_experimental", &self.deny_experimental),
+		]
+	}
+
+	/// Checks that every listed target is a valid target for its capability
+	pub(crate) fn validate(&self) -> Result<()> {
+		for (name, targets) in self.targets() {
+			let Some(CapabilityTargets::Some(targets)) = targets else {
+				continue;
+			};
+			for target in targets {
+				let res = match name {
+					"allow_funcs" | "deny_funcs" => check::<FuncTarget>(target),
+					"allow_net" | "deny_net" => check::<NetTarget>(target),
+					_ => check::<ExperimentalTarget>(target),
+				};
+				if let Err(message) = res {
+					return Err(Error::InvalidCapability {
+						name: name.to_owned(),
+						target: target.clone(),
+						message,
+					}
+					.into());
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Parses a capability target, returning the reason it is invalid
+fn check<T>(target: &str) -> Result<(), String>
+where
+	T: FromStr,
+	T::Err: Display,
+{
+	T::from_str(target).map(|_| ()).map_err(|e| e.to_string())
+}
+
+impl ToSql for CapabilityTargets {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			CapabilityTargets::None => f.push_str("false"),
+			CapabilityTargets::All => f.push_str("true"),
+			CapabilityTargets::Some(v) => {
+				f.push('[');
+				for (i, target) in v.iter().enumerate() {
+					if i > 0 {
+						f.push_str(", ");
+					}
+					write_sql!(f, fmt, "{}", QuoteStr(target));
+				}
+				f.push(']');
+			}
+		}
+	}
+}
+
+impl ToSql for CapabilitiesDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let mut fields = Vec::new();
+		if let Some(v) = self.scripting {
+			fields.push(format!("scripting: {v}"));
+		}
+		for (name, targets) in self.targets() {
+			if let Some(v) = targets {
+				fields.push(format!("{name}: {}", v.to_sql()));
+			}
+		}
+		write_sql!(f, fmt, "{{ {} }}", fields.join(", "));
+	}
+}
+
+impl InfoStructure for CapabilityTargets {
+	fn structure(self) -> Value {
+		match self {
+			CapabilityTargets::None => Value::Bool(false),
+			CapabilityTargets::All => Value::Bool(true),
+			CapabilityTargets::Some(v) => {
+				Value::Array(Array(v.into_iter().map(Value::from).collect()))
+			}
+		}
+	}
+}
+
+impl InfoStructure for CapabilitiesDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"scripting".to_string(), if let Some(v) = self.scripting => v.into(),
+			"allow_funcs".to_string(), if let Some(v) = self.allow_funcs => v.structure(),
+			"deny_funcs".to_string(), if let Some(v) = self.deny_funcs => v.structure(),
+			"allow_net".to_string(), if let Some(v) = self.allow_net => v.structure(),
+			"deny_net".to_string(), if let Some(v) = self.deny_net => v.structure(),
+			"allow_experimental".to_string(), if let Some(v) = self.allow_experimental => v.structure(),
+			"deny_experimental".to_string(), if let Some(v) = self.deny_experimental => v.structure(),
+		})
+	}
+}
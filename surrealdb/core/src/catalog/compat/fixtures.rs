@@ -56,6 +56,8 @@ pub fn namespace_basic() -> NamespaceDefinition {
 		namespace_id: NamespaceId(1),
 		name: "test".to_string(),
 		comment: None,
+		capabilities: None,
+		quota: None,
 	}
 }
 
@@ -65,6 +67,8 @@ pub fn namespace_with_comment() -> NamespaceDefinition {
 		namespace_id: NamespaceId(123),
 		name: "production".to_string(),
 		comment: Some("Production namespace".to_string()),
+		capabilities: None,
+		quota: None,
 	}
 }
 
@@ -81,6 +85,8 @@ pub fn database_basic() -> DatabaseDefinition {
 		strict: false,
 		comment: None,
 		changefeed: None,
+		capabilities: None,
+		quota: None,
 	}
 }
 
@@ -96,6 +102,8 @@ pub fn database_with_changefeed() -> DatabaseDefinition {
 			expiry: Duration::from_secs(3600),
 			store_diff: true,
 		}),
+		capabilities: None,
+		quota: None,
 	}
 }
 
@@ -108,6 +116,8 @@ pub fn database_strict() -> DatabaseDefinition {
 		strict: true,
 		comment: Some("Strict mode database".to_string()),
 		changefeed: None,
+		capabilities: None,
+		quota: None,
 	}
 }
 
@@ -129,6 +139,7 @@ pub fn table_basic() -> TableDefinition {
 		changefeed: None,
 		comment: None,
 		table_type: TableType::Normal,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -161,6 +172,7 @@ pub fn table_with_view() -> TableDefinition {
 		changefeed: None,
 		comment: Some("User statistics view".to_string()),
 		table_type: TableType::Normal,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -185,6 +197,7 @@ pub fn table_schemafull() -> TableDefinition {
 		}),
 		comment: Some("Order records".to_string()),
 		table_type: TableType::Normal,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -217,6 +230,7 @@ pub fn table_relation() -> TableDefinition {
 			to: vec!["posts".to_string(), "comments".to_string()],
 			enforced: true,
 		}),
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -242,6 +256,7 @@ pub fn table_with_materialized_view() -> TableDefinition {
 		changefeed: None,
 		comment: Some("Materialized view of active users".to_string()),
 		table_type: TableType::Normal,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -263,6 +278,7 @@ pub fn table_any_type() -> TableDefinition {
 		changefeed: None,
 		comment: None,
 		table_type: TableType::Any,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -1125,6 +1141,8 @@ pub fn user_basic() -> UserDefinition {
 		session_duration: None,
 		comment: None,
 		base: Base::Root,
+		quota: None,
+		totp: None,
 	}
 }
 
@@ -1139,6 +1157,8 @@ pub fn user_with_durations() -> UserDefinition {
 		session_duration: Some(Duration::from_secs(86400)),
 		comment: Some("API service account".to_string()),
 		base: Base::Ns,
+		quota: None,
+		totp: None,
 	}
 }
 
@@ -1153,6 +1173,8 @@ pub fn user_db_base() -> UserDefinition {
 		session_duration: None,
 		comment: Some("Database-level user".to_string()),
 		base: Base::Db,
+		quota: None,
+		totp: None,
 	}
 }
 
@@ -1583,9 +1605,10 @@ pub fn node_archived() -> Node {
 // TermDocument fixtures
 // ===========================================================================
 
-/// Term document - basic default
+/// Term document - basic default (offsets serialized before word positions
+/// were stored decode without a position)
 pub fn term_document_basic() -> TermDocument {
-	TermDocument::new(123, vec![Offset::new(1, 2, 3, 4)])
+	TermDocument::new(123, vec![Offset::new(1, 2, 3, 4, u32::MAX)])
 }
 
 // ===========================================================================
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use storekey::{BorrowDecode, Encode};
 use surrealdb_types::{SqlFormat, ToSql};
 
-use crate::catalog::NamespaceId;
+use crate::catalog::{CapabilitiesDefinition, NamespaceId, QuotaDefinition};
 use crate::expr::ChangeFeed;
 use crate::expr::statements::info::InfoStructure;
 use crate::kvs::impl_kv_value_revisioned;
@@ -67,7 +67,7 @@ impl From<u32> for DatabaseId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct DatabaseDefinition {
 	pub(crate) namespace_id: NamespaceId,
@@ -76,6 +76,12 @@ pub struct DatabaseDefinition {
 	pub(crate) comment: Option<String>,
 	pub(crate) changefeed: Option<ChangeFeed>,
 	pub(crate) strict: bool,
+	/// The capabilities of the database, restricting those of the namespace
+	#[revision(start = 2)]
+	pub(crate) capabilities: Option<CapabilitiesDefinition>,
+	/// The resource quotas of the database
+	#[revision(start = 3)]
+	pub(crate) quota: Option<QuotaDefinition>,
 }
 impl_kv_value_revisioned!(DatabaseDefinition);
 
@@ -89,6 +95,8 @@ impl DatabaseDefinition {
 				.map(|v| Expr::Literal(Literal::String(v)))
 				.unwrap_or(Expr::Literal(Literal::None)),
 			changefeed: self.changefeed.map(|v| v.into()),
+			capabilities: self.capabilities.clone(),
+			quota: self.quota.clone(),
 			..Default::default()
 		}
 	}
@@ -105,6 +113,8 @@ impl InfoStructure for DatabaseDefinition {
 		Value::from(map! {
 			"name".to_string() => self.name.into(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"capabilities".to_string(), if let Some(v) = self.capabilities => v.structure(),
+			"quota".to_string(), if let Some(v) = self.quota => v.structure(),
 			"id".to_string() => self.database_id.0.into(),
 		})
 	}
@@ -9,10 +9,12 @@
 mod access;
 pub(crate) mod aggregation;
 mod auth;
+mod capabilities;
 mod database;
 mod module;
 mod namespace;
 pub(crate) mod providers;
+mod quota;
 mod record;
 mod schema;
 mod subscription;
@@ -25,13 +27,15 @@ mod compat;
 mod test;
 
 pub(crate) use access::*;
+pub(crate) use capabilities::*;
 pub(crate) use database::*;
 pub(crate) use module::*;
 pub(crate) use namespace::*;
+pub(crate) use quota::*;
 pub(crate) use record::*;
 pub use schema::ApiMethod;
 pub(crate) use schema::{
-	ApiDefinition, Distance, FullTextParams, HnswParams, Scoring, VectorType, *,
+	ApiDefinition, Distance, FullTextParams, HnswParams, IvfPqParams, Scoring, VectorType, *,
 };
 pub(crate) use subscription::*;
 pub(crate) use table::*;
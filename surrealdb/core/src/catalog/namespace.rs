@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use storekey::{BorrowDecode, Encode};
 use surrealdb_types::{SqlFormat, ToSql};
 
+use crate::catalog::{CapabilitiesDefinition, QuotaDefinition};
 use crate::expr::statements::info::InfoStructure;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql::statements::DefineNamespaceStatement;
@@ -66,12 +67,18 @@ impl From<u32> for NamespaceId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
 pub struct NamespaceDefinition {
 	pub namespace_id: NamespaceId,
 	pub name: String,
 	pub comment: Option<String>,
+	/// The capabilities of the namespace, restricting those of the server
+	#[revision(start = 2)]
+	pub capabilities: Option<CapabilitiesDefinition>,
+	/// The resource quotas of the namespace
+	#[revision(start = 3)]
+	pub quota: Option<QuotaDefinition>,
 }
 impl_kv_value_revisioned!(NamespaceDefinition);
 
@@ -84,6 +91,8 @@ impl NamespaceDefinition {
 				.clone()
 				.map(|v| Expr::Literal(Literal::String(v)))
 				.unwrap_or(Expr::Literal(Literal::None)),
+			capabilities: self.capabilities.clone(),
+			quota: self.quota.clone(),
 			..Default::default()
 		}
 	}
@@ -100,6 +109,8 @@ impl InfoStructure for NamespaceDefinition {
 		Value::from(map! {
 			"name".to_string() => self.name.into(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"capabilities".to_string(), if let Some(v) = self.capabilities => v.structure(),
+			"quota".to_string(), if let Some(v) = self.quota => v.structure(),
 			"id".to_string() => self.namespace_id.0.into(),
 		})
 	}
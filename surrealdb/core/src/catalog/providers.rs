@@ -79,6 +79,8 @@ pub(crate) trait NamespaceProvider {
 					namespace_id: self.get_next_ns_id(ctx).await?,
 					name: ns.to_owned(),
 					comment: None,
+					capabilities: None,
+					quota: None,
 				};
 				self.put_ns(ns).await
 			}
@@ -173,6 +175,30 @@ pub(crate) trait DatabaseProvider: NamespaceProvider {
 		version: Option<u64>,
 	) -> Result<Arc<[catalog::SequenceDefinition]>>;
 
+	/// Retrieve all job definitions for a specific database.
+	async fn all_db_jobs(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		version: Option<u64>,
+	) -> Result<Arc<[catalog::JobDefinition]>>;
+
+	/// Retrieve all consumer group definitions for a specific database.
+	async fn all_db_consumers(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		version: Option<u64>,
+	) -> Result<Arc<[catalog::ConsumerDefinition]>>;
+
+	/// Retrieve all sink definitions for a specific database.
+	async fn all_db_sinks(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		version: Option<u64>,
+	) -> Result<Arc<[catalog::SinkDefinition]>>;
+
 	/// Retrieve all function definitions for a specific database.
 	async fn all_db_functions(
 		&self,
@@ -240,6 +266,33 @@ pub(crate) trait DatabaseProvider: NamespaceProvider {
 		version: Option<u64>,
 	) -> Result<Arc<catalog::SequenceDefinition>>;
 
+	/// Retrieve a specific job definition from a database.
+	async fn get_db_job(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		jb: &str,
+		version: Option<u64>,
+	) -> Result<Arc<catalog::JobDefinition>>;
+
+	/// Retrieve a specific consumer group definition from a database.
+	async fn get_db_consumer(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		cu: &str,
+		version: Option<u64>,
+	) -> Result<Arc<catalog::ConsumerDefinition>>;
+
+	/// Retrieve a specific sink definition from a database.
+	async fn get_db_sink(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		sk: &str,
+		version: Option<u64>,
+	) -> Result<Arc<catalog::SinkDefinition>>;
+
 	/// Retrieve a specific function definition from a database.
 	async fn get_db_function(
 		&self,
@@ -538,6 +591,23 @@ pub(crate) trait TableProvider {
 		ix: &str,
 	) -> Result<()>;
 
+	/// Retrieve the statistics collected for a table.
+	async fn get_tb_statistics(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+	) -> Result<Option<Arc<catalog::TableStatistics>>>;
+
+	/// Put the statistics collected for a table.
+	async fn put_tb_statistics(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		st: &catalog::TableStatistics,
+	) -> Result<()>;
+
 	/// Fetch a specific record value.
 	async fn get_record(
 		&self,
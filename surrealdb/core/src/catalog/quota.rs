@@ -0,0 +1,80 @@
+use revision::revisioned;
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::expr::statements::info::InfoStructure;
+use crate::val::Value;
+
+/// The resource quotas of a namespace, database, or user.
+///
+/// Quotas apply in addition to the quotas of every enclosing level, so a
+/// statement run by a user within a database is limited by the lowest of the
+/// quotas of the namespace, the database, and the user.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct QuotaDefinition {
+	/// The maximum number of records a single statement can scan
+	pub(crate) max_scanned: Option<u64>,
+	/// The maximum number of bytes a single statement can buffer in memory
+	/// when sorting, shuffling, or grouping records
+	pub(crate) max_memory: Option<u64>,
+	/// The maximum number of statements which can run at the same time
+	pub(crate) max_concurrent: Option<u64>,
+	/// The maximum number of bytes which can be stored
+	pub(crate) max_storage: Option<u64>,
+}
+
+impl QuotaDefinition {
+	/// Whether any quota is set
+	pub(crate) fn is_empty(&self) -> bool {
+		self == &Self::default()
+	}
+
+	/// The quotas, along with their names
+	pub(crate) fn limits(&self) -> [(&'static str, Option<u64>); 4] {
+		[
+			("max_scanned", self.max_scanned),
+			("max_memory", self.max_memory),
+			("max_concurrent", self.max_concurrent),
+			("max_storage", self.max_storage),
+		]
+	}
+
+	/// Combines two sets of quotas, keeping the lowest of each quota
+	pub(crate) fn merge(&self, other: &Self) -> Self {
+		fn min(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+			match (a, b) {
+				(Some(a), Some(b)) => Some(a.min(b)),
+				(a, b) => a.or(b),
+			}
+		}
+		Self {
+			max_scanned: min(self.max_scanned, other.max_scanned),
+			max_memory: min(self.max_memory, other.max_memory),
+			max_concurrent: min(self.max_concurrent, other.max_concurrent),
+			max_storage: min(self.max_storage, other.max_storage),
+		}
+	}
+}
+
+impl ToSql for QuotaDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let fields: Vec<String> = self
+			.limits()
+			.into_iter()
+			.filter_map(|(name, v)| v.map(|v| format!("{name}: {v}")))
+			.collect();
+		write_sql!(f, fmt, "{{ {} }}", fields.join(", "));
+	}
+}
+
+impl InfoStructure for QuotaDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"max_scanned".to_string(), if let Some(v) = self.max_scanned => v.into(),
+			"max_memory".to_string(), if let Some(v) = self.max_memory => v.into(),
+			"max_concurrent".to_string(), if let Some(v) = self.max_concurrent => v.into(),
+			"max_storage".to_string(), if let Some(v) = self.max_storage => v.into(),
+		})
+	}
+}
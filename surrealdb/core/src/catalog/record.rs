@@ -9,7 +9,7 @@ use revision::revisioned;
 
 use crate::catalog::aggregation::AggregationStat;
 use crate::kvs::impl_kv_value_revisioned;
-use crate::val::Value;
+use crate::val::{Datetime, Value};
 
 /// Represents a record stored in the database
 ///
@@ -75,6 +75,28 @@ impl Record {
 				*metadata = Some(Metadata {
 					record_type: rtype,
 					aggregation_stats: Vec::new(),
+					expires: None,
+				});
+			}
+		}
+	}
+
+	/// Returns the time at which this record expires, if any
+	pub(crate) fn expires(&self) -> Option<&Datetime> {
+		self.metadata.as_ref().and_then(|m| m.expires.as_ref())
+	}
+
+	/// Sets the time at which this record expires in the metadata
+	pub(crate) fn set_expires(&mut self, expires: Datetime) {
+		match &mut self.metadata {
+			Some(metadata) => {
+				metadata.expires = Some(expires);
+			}
+			metadata => {
+				*metadata = Some(Metadata {
+					record_type: RecordType::Table,
+					aggregation_stats: Vec::new(),
+					expires: Some(expires),
 				});
 			}
 		}
@@ -102,7 +124,7 @@ pub(crate) enum RecordType {
 /// aggregation statistics for materialized view records.
 /// The metadata is revisioned to ensure compatibility across different versions
 /// of the database.
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct Metadata {
 	/// The type of the record (e.g., Edge for graph edges)
@@ -111,4 +133,8 @@ pub(crate) struct Metadata {
 	/// These do not directly correspond to a feild but must be used in conjunction with the table
 	/// definition to calculate the final value for this record.
 	pub(crate) aggregation_stats: Vec<AggregationStat>,
+	/// The time at which this record expires, for tables with a duration
+	/// time-to-live.
+	#[revision(start = 2)]
+	pub(crate) expires: Option<Datetime>,
 }
@@ -0,0 +1,70 @@
+use revision::revisioned;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql;
+use crate::sql::statements::define::{DefineConsumerStatement, DefineKind};
+use crate::val::{Datetime, Value};
+
+/// A named changefeed consumer group.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct ConsumerDefinition {
+	pub(crate) name: String,
+	pub(crate) comment: Option<String>,
+}
+
+impl_kv_value_revisioned!(ConsumerDefinition);
+
+impl ConsumerDefinition {
+	pub fn to_sql_definition(&self) -> DefineConsumerStatement {
+		DefineConsumerStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+
+	/// The structure of the consumer group, together with its checkpoint
+	pub(crate) fn structure_with_checkpoint(self, checkpoint: Option<ConsumerCheckpoint>) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"versionstamp".to_string(), if let Some(v) = &checkpoint => v.versionstamp.into(),
+			"acknowledged".to_string(), if let Some(v) = checkpoint => v.acknowledged.into(),
+		})
+	}
+}
+
+impl InfoStructure for ConsumerDefinition {
+	fn structure(self) -> Value {
+		self.structure_with_checkpoint(None)
+	}
+}
+
+impl ToSql for ConsumerDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
+
+/// The position up to which a consumer group has acknowledged the changefeed.
+///
+/// This is stored apart from the [`ConsumerDefinition`], so that acknowledging
+/// changes does not invalidate the cached catalog definitions.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ConsumerCheckpoint {
+	/// The last versionstamp which the group has acknowledged
+	pub(crate) versionstamp: u64,
+	/// When the checkpoint was last moved
+	pub(crate) acknowledged: Datetime,
+}
+
+impl_kv_value_revisioned!(ConsumerCheckpoint);
@@ -124,7 +124,7 @@ impl ToSql for IndexDefinition {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub(crate) enum Index {
 	/// (Basic) non unique
@@ -138,6 +138,12 @@ pub(crate) enum Index {
 	FullText(FullTextParams),
 	/// Count index
 	Count(Option<Cond>),
+	/// Geospatial index over geometry values
+	#[revision(start = 2)]
+	Spatial,
+	/// IVF-PQ index for approximate distance-based metrics
+	#[revision(start = 3)]
+	IvfPq(IvfPqParams),
 }
 
 impl Index {
@@ -148,6 +154,8 @@ impl Index {
 			Self::Hnsw(params) => sql::index::Index::Hnsw(params.clone().into()),
 			Self::FullText(params) => sql::index::Index::FullText(params.clone().into()),
 			Self::Count(cond) => sql::index::Index::Count(cond.clone().map(Into::into)),
+			Self::Spatial => sql::index::Index::Spatial,
+			Self::IvfPq(params) => sql::index::Index::IvfPq(params.clone().into()),
 		}
 	}
 
@@ -372,3 +380,27 @@ pub(crate) struct HnswParams {
 	#[revision(start = 2)]
 	pub use_hashed_vector: bool,
 }
+
+/// IVF-PQ index parameters.
+///
+/// Vectors are partitioned into `lists` inverted lists around coarse
+/// centroids, and each vector is compressed into `subvectors` product
+/// quantization codes of `bits` bits each.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct IvfPqParams {
+	/// The dimension of the index.
+	pub dimension: u16,
+	/// The distance metric to use.
+	pub distance: Distance,
+	/// The vector type to use.
+	pub vector_type: VectorType,
+	/// The number of inverted lists (coarse centroids).
+	pub lists: u16,
+	/// The number of sub-vectors each vector is split into.
+	pub subvectors: u16,
+	/// The number of bits of each sub-vector code (1 to 8).
+	pub bits: u8,
+	/// The default number of inverted lists probed on search.
+	pub probes: u16,
+}
@@ -0,0 +1,115 @@
+use revision::revisioned;
+use surrealdb_types::{SqlFormat, ToSql};
+use uuid::Uuid;
+
+use crate::expr::Expr;
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql;
+use crate::sql::statements::define::{DefineJobStatement, DefineKind};
+use crate::val::{Datetime, Value};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct JobDefinition {
+	pub(crate) name: String,
+	/// The cron expression the job is scheduled with
+	pub(crate) schedule: String,
+	pub(crate) then: Expr,
+	/// Whether the scheduler skips this job
+	pub(crate) paused: bool,
+	pub(crate) comment: Option<String>,
+}
+
+impl_kv_value_revisioned!(JobDefinition);
+
+impl JobDefinition {
+	pub fn to_sql_definition(&self) -> DefineJobStatement {
+		DefineJobStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			schedule: self.schedule.clone(),
+			paused: self.paused,
+			then: self.then.clone().into(),
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+
+	/// The structure of the job, together with its schedule and run history
+	pub(crate) fn structure_with_state(self, state: Option<JobState>) -> Value {
+		let (next, runs) = match state {
+			Some(state) => (state.next, state.runs),
+			// The job has not been scheduled yet
+			None => (None, Vec::new()),
+		};
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"schedule".to_string() => self.schedule.into(),
+			"then".to_string() => self.then.structure(),
+			"paused".to_string() => self.paused.into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"next".to_string(), if let Some(v) = next => v.into(),
+			"runs".to_string() => runs.into_iter().map(InfoStructure::structure).collect(),
+		})
+	}
+}
+
+impl InfoStructure for JobDefinition {
+	fn structure(self) -> Value {
+		self.structure_with_state(None)
+	}
+}
+
+impl ToSql for JobDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
+
+/// The schedule and recent runs of a job.
+///
+/// This is stored apart from the [`JobDefinition`], so that recording a run
+/// does not invalidate the cached catalog definitions.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct JobState {
+	/// When the job is next due to run
+	pub(crate) next: Option<Datetime>,
+	/// The most recent runs of the job, newest first
+	pub(crate) runs: Vec<JobRun>,
+}
+
+impl_kv_value_revisioned!(JobState);
+
+/// A single run of a job
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct JobRun {
+	/// When the run was due
+	pub(crate) scheduled: Datetime,
+	/// When the run started
+	pub(crate) started: Datetime,
+	/// When the run finished
+	pub(crate) finished: Datetime,
+	/// The node which ran the job
+	pub(crate) node: Uuid,
+	/// The error the run failed with, if any
+	pub(crate) error: Option<String>,
+}
+
+impl InfoStructure for JobRun {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"scheduled".to_string() => self.scheduled.into(),
+			"started".to_string() => self.started.into(),
+			"finished".to_string() => self.finished.into(),
+			"node".to_string() => Value::Uuid(self.node.into()),
+			"error".to_string(), if let Some(v) = self.error => v.into(),
+		})
+	}
+}
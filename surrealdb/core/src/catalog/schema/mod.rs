@@ -7,14 +7,18 @@ mod api;
 pub(crate) mod base;
 mod bucket;
 mod config;
+mod consumer;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
 mod ml;
 mod module;
 mod param;
 mod sequence;
+mod sink;
+mod statistics;
 mod user;
 use std::fmt::{Display, Formatter};
 
@@ -23,14 +27,18 @@ pub use analyzer::*;
 pub use api::*;
 pub use bucket::*;
 pub use config::*;
+pub use consumer::*;
 pub use event::*;
 pub use field::*;
 pub use function::*;
 pub use index::*;
+pub use job::*;
 pub use ml::*;
 pub use module::*;
 pub(crate) use param::*;
 pub use sequence::*;
+pub use sink::*;
+pub use statistics::*;
 pub use user::*;
 
 use crate::expr::Expr;
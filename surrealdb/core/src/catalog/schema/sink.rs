@@ -0,0 +1,140 @@
+use revision::revisioned;
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::expr::statements::info::InfoStructure;
+use crate::fmt::QuoteStr;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql;
+use crate::sql::statements::define::{DefineKind, DefineSinkStatement};
+use crate::val::{Datetime, TableName, Value};
+
+/// A sink which pushes the changefeed of a table to an external target.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct SinkDefinition {
+	pub(crate) name: String,
+	/// The table whose changefeed is delivered
+	pub(crate) target_table: TableName,
+	pub(crate) target: SinkTarget,
+	pub(crate) comment: Option<String>,
+}
+
+impl_kv_value_revisioned!(SinkDefinition);
+
+/// Where the changes of a sink are delivered to
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum SinkTarget {
+	/// Each batch of changes is sent in a POST request to the url
+	Webhook(String),
+	/// Changes are appended to a rolling NDJSON file at the path
+	File(String),
+	/// Changes are read by a client of the HTTP streaming endpoint
+	Stream,
+}
+
+impl SinkTarget {
+	/// Whether changes are pushed by the datastore, rather than pulled by a client
+	pub(crate) fn is_pushed(&self) -> bool {
+		!matches!(self, SinkTarget::Stream)
+	}
+}
+
+impl ToSql for SinkTarget {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			SinkTarget::Webhook(v) => write_sql!(f, fmt, "WEBHOOK {}", QuoteStr(v)),
+			SinkTarget::File(v) => write_sql!(f, fmt, "FILE {}", QuoteStr(v)),
+			SinkTarget::Stream => f.push_str("STREAM"),
+		}
+	}
+}
+
+impl InfoStructure for SinkTarget {
+	fn structure(self) -> Value {
+		match self {
+			SinkTarget::Webhook(v) => Value::from(map! {
+				"kind".to_string() => "webhook".into(),
+				"url".to_string() => v.into(),
+			}),
+			SinkTarget::File(v) => Value::from(map! {
+				"kind".to_string() => "file".into(),
+				"path".to_string() => v.into(),
+			}),
+			SinkTarget::Stream => Value::from(map! {
+				"kind".to_string() => "stream".into(),
+			}),
+		}
+	}
+}
+
+impl SinkDefinition {
+	pub fn to_sql_definition(&self) -> DefineSinkStatement {
+		DefineSinkStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			target_table: sql::Expr::Table(self.target_table.clone().into_string()),
+			target: self.target.clone(),
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+
+	/// The structure of the sink, together with its delivery state
+	pub(crate) fn structure_with_state(self, state: Option<SinkState>) -> Value {
+		let (versionstamp, delivered, attempts, error, retry) = match state {
+			Some(s) => (Some(s.versionstamp), s.delivered, Some(s.attempts), s.error, s.retry),
+			// Without a state, nothing is known about the deliveries
+			None => (None, None, None, None, None),
+		};
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"what".to_string() => self.target_table.into(),
+			"target".to_string() => self.target.structure(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"versionstamp".to_string(), if let Some(v) = versionstamp => v.into(),
+			"delivered".to_string(), if let Some(v) = delivered => v.into(),
+			"attempts".to_string(), if let Some(v) = attempts => v.into(),
+			"error".to_string(), if let Some(v) = error => v.into(),
+			"retry".to_string(), if let Some(v) = retry => v.into(),
+		})
+	}
+}
+
+impl InfoStructure for SinkDefinition {
+	fn structure(self) -> Value {
+		self.structure_with_state(None)
+	}
+}
+
+impl ToSql for SinkDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
+
+/// The delivery progress of a sink.
+///
+/// This is stored apart from the [`SinkDefinition`], so that recording a
+/// delivery does not invalidate the cached catalog definitions.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SinkState {
+	/// The last versionstamp which has been delivered
+	pub(crate) versionstamp: u64,
+	/// When changes were last delivered
+	pub(crate) delivered: Option<Datetime>,
+	/// The number of failed attempts since the last delivery
+	pub(crate) attempts: u32,
+	/// The error the last attempt failed with, if any
+	pub(crate) error: Option<String>,
+	/// When the delivery is next attempted after a failure
+	pub(crate) retry: Option<Datetime>,
+}
+
+impl_kv_value_revisioned!(SinkState);
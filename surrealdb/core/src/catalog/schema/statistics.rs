@@ -0,0 +1,140 @@
+use revision::revisioned;
+
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::val::{Array, Datetime, Value};
+
+/// The maximum number of most common values kept for each field
+const MAX_COMMON_VALUES: usize = 10;
+/// The number of buckets in the histogram of each field
+const HISTOGRAM_BUCKETS: usize = 32;
+
+/// The statistics of a table, as collected by `ANALYZE TABLE`
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TableStatistics {
+	/// The number of records in the table when it was analyzed
+	pub(crate) records: u64,
+	/// The number of records which were sampled
+	pub(crate) sampled: u64,
+	/// The time at which the table was analyzed
+	pub(crate) analyzed_at: Datetime,
+	/// The statistics of each analyzed field
+	pub(crate) fields: Vec<FieldStatistics>,
+}
+
+impl_kv_value_revisioned!(TableStatistics);
+
+impl TableStatistics {
+	/// Returns the statistics of a field, if it was analyzed
+	pub(crate) fn field(&self, field: &str) -> Option<&FieldStatistics> {
+		self.fields.iter().find(|f| f.field == field)
+	}
+}
+
+/// The statistics of a single field, computed from the sampled records
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FieldStatistics {
+	/// The field, as a SurrealQL idiom
+	pub(crate) field: String,
+	/// The number of sampled records where the field is NONE or NULL
+	pub(crate) nulls: u64,
+	/// The estimated number of distinct values of the field in the table
+	pub(crate) distinct: u64,
+	/// The most common values, with the number of sampled records holding them
+	pub(crate) common: Vec<(Value, u64)>,
+	/// The bounds of an equi-depth histogram over the remaining sampled values
+	pub(crate) histogram: Vec<Value>,
+}
+
+impl FieldStatistics {
+	/// Computes the statistics of a field from the values of the sampled
+	/// records, given the total number of records in the table.
+	pub(crate) fn from_sample(field: String, mut values: Vec<Value>, records: u64) -> Self {
+		let sampled = values.len() as u64;
+		values.retain(|v| !v.is_nullish());
+		let nulls = sampled - values.len() as u64;
+		values.sort_unstable();
+		// Group the sorted values into runs of equal values
+		let mut runs: Vec<(Value, u64)> = Vec::new();
+		for v in values {
+			match runs.last_mut() {
+				Some((last, count)) if *last == v => *count += 1,
+				_ => runs.push((v, 1)),
+			}
+		}
+		let n = (sampled - nulls) as f64;
+		let d = runs.len() as f64;
+		let f1 = runs.iter().filter(|(_, c)| *c == 1).count() as f64;
+		// The number of non-null records in the whole table
+		let total = if sampled == 0 {
+			0.0
+		} else {
+			records as f64 * n / sampled as f64
+		};
+		// Estimate the number of distinct values in the table with the
+		// Duj1 estimator of Haas and Stokes, which scales the distinct values
+		// seen in the sample by how many of them were only seen once
+		let distinct = if n == 0.0 {
+			0.0
+		} else if f1 == n {
+			total
+		} else {
+			(n * d / (n - f1 + f1 * n / total)).clamp(d, total.max(d))
+		};
+		// The values seen more than once, with the most frequent first
+		let mut common: Vec<(Value, u64)> = runs.iter().filter(|(_, c)| *c > 1).cloned().collect();
+		common.sort_by(|a, b| b.1.cmp(&a.1));
+		common.truncate(MAX_COMMON_VALUES);
+		// Build an equi-depth histogram over the remaining values
+		let rest: Vec<&Value> = runs
+			.iter()
+			.filter(|(v, _)| !common.iter().any(|(c, _)| c == v))
+			.flat_map(|(v, c)| std::iter::repeat_n(v, *c as usize))
+			.collect();
+		let histogram = if rest.len() < 2 {
+			rest.into_iter().cloned().collect()
+		} else {
+			let buckets = HISTOGRAM_BUCKETS.min(rest.len() - 1);
+			(0..=buckets).map(|i| rest[i * (rest.len() - 1) / buckets].clone()).collect()
+		};
+		Self {
+			field,
+			nulls,
+			distinct: distinct.round() as u64,
+			common,
+			histogram,
+		}
+	}
+}
+
+impl InfoStructure for TableStatistics {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"records".to_string() => Value::from(self.records as i64),
+			"sampled".to_string() => Value::from(self.sampled as i64),
+			"analyzed_at".to_string() => Value::Datetime(self.analyzed_at),
+			"fields".to_string() => Value::Array(Array(
+				self.fields.into_iter().map(InfoStructure::structure).collect(),
+			)),
+		})
+	}
+}
+
+impl InfoStructure for FieldStatistics {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"field".to_string() => self.field.into(),
+			"nulls".to_string() => Value::from(self.nulls as i64),
+			"distinct".to_string() => Value::from(self.distinct as i64),
+			"common".to_string() => Value::Array(Array(
+				self.common
+					.into_iter()
+					.map(|(v, c)| Value::Array(Array(vec![v, Value::from(c as i64)])))
+					.collect(),
+			)),
+			"histogram".to_string() => Value::Array(Array(self.histogram)),
+		})
+	}
+}
@@ -3,13 +3,14 @@ use std::time::Duration;
 use revision::revisioned;
 use surrealdb_types::{SqlFormat, ToSql};
 
+use crate::catalog::QuotaDefinition;
 use crate::catalog::base::Base;
 use crate::expr::statements::info::InfoStructure;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
 use crate::val::{Array, Value};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct UserDefinition {
 	pub name: String,
@@ -22,6 +23,44 @@ pub struct UserDefinition {
 	pub session_duration: Option<Duration>,
 	pub comment: Option<String>,
 	pub base: Base,
+	/// The resource quotas of the user
+	#[revision(start = 2)]
+	pub quota: Option<QuotaDefinition>,
+	/// The TOTP second factor of the user, if enrolled
+	#[revision(start = 3)]
+	pub totp: Option<TotpDefinition>,
+}
+
+/// The TOTP second factor of a system user
+#[revisioned(revision = 2)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TotpDefinition {
+	/// The shared secret, encoded as base32
+	pub secret: String,
+	/// The salted hashes of the recovery codes which have not been used yet
+	pub recovery: Vec<String>,
+	/// The time step of the last code which was used, so it cannot be replayed
+	#[revision(start = 2)]
+	pub last_step: Option<u64>,
+	/// The number of consecutive failed attempts at completing signin challenges
+	#[revision(start = 2)]
+	pub failed_attempts: u32,
+	/// The unix time of the last failed attempt at completing a signin challenge
+	#[revision(start = 2)]
+	pub last_failure: Option<i64>,
+}
+
+impl TotpDefinition {
+	/// Creates a new TOTP second factor, which has not been used yet
+	pub fn new(secret: String, recovery: Vec<String>) -> Self {
+		Self {
+			secret,
+			recovery,
+			last_step: None,
+			failed_attempts: 0,
+			last_failure: None,
+		}
+	}
 }
 
 impl UserDefinition {
@@ -53,6 +92,9 @@ impl UserDefinition {
 				.clone()
 				.map(|c| sql::Expr::Literal(sql::Literal::String(c)))
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+			quota: self.quota.clone(),
+			// The TOTP secret and recovery hashes are never displayed
+			totp: sql::statements::define::user::TotpType::Unset,
 		}
 	}
 }
@@ -74,6 +116,10 @@ impl InfoStructure for UserDefinition {
 				"session".to_string() => self.session_duration.map(Value::from).unwrap_or(Value::None),
 			}),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"quota".to_string(), if let Some(v) = self.quota => v.structure(),
+			"totp".to_string(), if let Some(v) = self.totp => Value::from(map! {
+				"recovery".to_string() => Value::from(v.recovery.len() as i64),
+			}),
 		})
 	}
 }
@@ -4,7 +4,7 @@ use uuid::Uuid;
 
 use crate::catalog::{DatabaseId, NamespaceId, Permissions, ViewDefinition};
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{ChangeFeed, Kind};
+use crate::expr::{ChangeFeed, Kind, Ttl};
 use crate::fmt::EscapeKwFreeIdent;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
@@ -40,7 +40,7 @@ impl DeserializeRevisioned for TableId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TableDefinition {
 	pub(crate) namespace_id: NamespaceId,
@@ -54,6 +54,9 @@ pub struct TableDefinition {
 	pub(crate) changefeed: Option<ChangeFeed>,
 	pub(crate) comment: Option<String>,
 	pub(crate) table_type: TableType,
+	/// The time-to-live of the records in this table
+	#[revision(start = 2)]
+	pub(crate) ttl: Option<Ttl>,
 
 	/// The last time that a DEFINE FIELD was added to this table
 	pub(crate) cache_fields_ts: Uuid,
@@ -87,6 +90,7 @@ impl TableDefinition {
 			changefeed: None,
 			comment: None,
 			table_type: TableType::default(),
+			ttl: None,
 			cache_fields_ts: now,
 			cache_events_ts: now,
 			cache_tables_ts: now,
@@ -118,6 +122,7 @@ impl TableDefinition {
 				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
 			table_type: self.table_type.clone().into(),
+			ttl: self.ttl.clone().map(Into::into),
 			..Default::default()
 		}
 	}
@@ -138,6 +143,7 @@ impl InfoStructure for TableDefinition {
 			"kind".to_string() => self.table_type.structure(),
 			"view".to_string(), if let Some(v) = self.view => v.structure(),
 			"changefeed".to_string(), if let Some(v) = self.changefeed => v.structure(),
+			"ttl".to_string(), if let Some(v) = self.ttl => v.structure(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 			"id".to_string() => self.table_id.0.into(),
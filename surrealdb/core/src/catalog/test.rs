@@ -25,7 +25,9 @@ use crate::val::{Datetime, TableName, Value};
 	namespace_id: NamespaceId(123),
 	name: "test".to_string(),
 	comment: Some("comment".to_string()),
-}, 16)]
+	capabilities: None,
+	quota: None,
+}, 18)]
 #[case::database(DatabaseDefinition {
 	namespace_id: NamespaceId(123),
 	database_id: DatabaseId(456),
@@ -36,7 +38,9 @@ use crate::val::{Datetime, TableName, Value};
 		expiry: Duration::from_secs(123),
 		store_diff: false,
 	}),
-}, 25)]
+	capabilities: None,
+	quota: None,
+}, 27)]
 #[case::table(TableDefinition {
 	namespace_id: NamespaceId(123),
 	database_id: DatabaseId(456),
@@ -60,11 +64,12 @@ use crate::val::{Datetime, TableName, Value};
 	}),
 	comment: Some("comment".to_string()),
 	table_type: TableType::Normal,
+	ttl: None,
 	cache_fields_ts: Uuid::default(),
 	cache_events_ts: Uuid::default(),
 	cache_tables_ts: Uuid::default(),
 	cache_indexes_ts: Uuid::default(),
-}, 149)]
+}, 150)]
 #[case::subscription(SubscriptionDefinition {
 	id: Uuid::default(),
 	node: Uuid::default(),
@@ -237,7 +242,9 @@ use crate::val::{Datetime, TableName, Value};
 	session_duration: Some(Duration::from_secs(123)),
 	comment: Some("comment".to_string()),
 	base: crate::catalog::schema::base::Base::Root,
-}, 40)]
+	quota: None,
+	totp: None,
+}, 42)]
 #[case::record(Record::new(Value::Bool(true)), 5)]
 fn test_serialize_deserialize<T>(#[case] original: T, #[case] expected_encoded_size: usize)
 where
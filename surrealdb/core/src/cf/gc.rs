@@ -49,7 +49,9 @@ pub async fn gc_all_at(lh: &LeaseHandler, tx: &Transaction) -> Result<()> {
 			}
 
 			let ts = tx.timestamp().await?;
-			// Calculate the changefeed watermark cutoff time
+			// Calculate the changefeed watermark cutoff time. Consumer groups and
+			// sinks do not hold back the watermark, so that a stalled or abandoned
+			// reader can not keep changes past the changefeed expiry.
 			let watermark_ts = ts.sub_checked(cf_expiry).unwrap_or_else(|| ts_impl.earliest());
 			// Garbage collect all entries older than the watermark
 			gc_range(tx, db.namespace_id, db.database_id, &watermark_ts, &ts_impl).await?;
@@ -1,5 +1,6 @@
 use anyhow::Result;
 
+use crate::catalog::providers::DatabaseProvider;
 use crate::catalog::{DatabaseId, NamespaceId};
 use crate::cf::{ChangeSet, DatabaseMutation, TableMutations};
 use crate::err::Error;
@@ -18,6 +19,8 @@ use crate::val::TableName;
 //
 // You can use this to read the change feed in chunks.
 // The second call would start from the last timestamp/version + 1 of the first call.
+// When reading for a consumer group, the read starts after the last change
+// which the group has acknowledged.
 pub async fn read(
 	tx: &Transaction,
 	ns: NamespaceId,
@@ -44,6 +47,25 @@ pub async fn read(
 				),
 			})?
 		}
+		ShowSince::Consumer(name) => {
+			// Ensure the consumer group exists
+			tx.get_db_consumer(ns, db, &name, None).await?;
+			let key = crate::key::database::ck::new(ns, db, &name);
+			match tx.get(&key, None).await? {
+				// Start after the last acknowledged change
+				Some(checkpoint) => {
+					let x = checkpoint.versionstamp.saturating_add(1);
+					ts_impl.create_from_versionstamp(x as u128).ok_or_else(|| Error::Query {
+						message: format!(
+							"Invalid versionstamp `{x}`, outside of range for kv-store timestamps"
+						),
+					})?
+				}
+				// A group which has not acknowledged any change yet starts
+				// from the earliest change which is retained
+				None => ts_impl.earliest(),
+			}
+		}
 	};
 
 	let buf = &mut [0u8; _];
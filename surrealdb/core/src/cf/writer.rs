@@ -340,6 +340,8 @@ mod tests {
 			namespace_id,
 			name: NS.to_string(),
 			comment: None,
+			capabilities: None,
+			quota: None,
 		};
 		let db_def = DatabaseDefinition {
 			namespace_id,
@@ -351,6 +353,8 @@ mod tests {
 			}),
 			comment: None,
 			strict: false,
+			capabilities: None,
+			quota: None,
 		};
 		let mut tb_def = TableDefinition::new(
 			namespace_id,
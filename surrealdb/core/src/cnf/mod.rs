@@ -62,6 +62,11 @@ pub static REGEX_SIZE_LIMIT: LazyLock<usize> =
 pub static REGEX_CACHE_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_REGEX_CACHE_SIZE", usize, 1_000);
 
+/// Specifies the number of rate limit counters which are tracked by the
+/// `api::rate_limit` middleware on this node (default: 10,000)
+pub static API_RATE_LIMIT_CACHE_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_API_RATE_LIMIT_CACHE_SIZE", usize, 10_000);
+
 /// Specifies the number of items which can be cached within a single
 /// transaction (default: 10,000)
 pub static TRANSACTION_CACHE_SIZE: LazyLock<usize> =
@@ -168,11 +173,46 @@ pub static SURREALISM_LOG_LEVEL: LazyLock<String> = LazyLock::new(|| {
 pub static NORMAL_FETCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_NORMAL_FETCH_SIZE", u32, 500);
 
+/// The number of runs kept in the history of each scheduled job (default: 10)
+pub static JOB_HISTORY_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_JOB_HISTORY_SIZE", usize, 10);
+
+/// The maximum number of changesets delivered to a sink at once (default: 100)
+pub static SINK_BATCH_SIZE: LazyLock<u32> = lazy_env_parse!("SURREAL_SINK_BATCH_SIZE", u32, 100);
+
+/// The size in bytes at which a file sink is rolled over to a new file (default: 64 MiB)
+pub static SINK_FILE_MAX_SIZE: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_SINK_FILE_MAX_SIZE", u64, 64 * 1024 * 1024);
+
+/// The maximum delay in seconds between retries of a failed sink delivery (default: 300)
+pub static SINK_RETRY_MAX_DELAY: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_SINK_RETRY_MAX_DELAY", u64, 300);
+
+/// The number of seconds for which the quotas of a namespace, database, and
+/// user are cached before they are fetched again (default: 10)
+pub static QUOTA_DEFINITION_CACHE_SECS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_QUOTA_DEFINITION_CACHE_SECS", u64, 10);
+
+/// The number of namespace, database, and user combinations for which quotas
+/// are cached (default: 10,000)
+pub static QUOTA_DEFINITION_CACHE_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_QUOTA_DEFINITION_CACHE_SIZE", usize, 10_000);
+
+/// The number of records which are sampled by `ANALYZE TABLE` when no `SAMPLE`
+/// size is specified (default: 10,000)
+pub static ANALYZE_SAMPLE_SIZE: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_ANALYZE_SAMPLE_SIZE", u64, 10_000);
+
 /// The maximum number of keys that should be scanned at once for export queries
 /// (default: 1000)
 pub static EXPORT_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_EXPORT_BATCH_SIZE", u32, 1000);
 
+/// The maximum size in bytes of a single key or value which is accepted when
+/// restoring a backup (default: 64 MiB)
+pub static BACKUP_MAX_ENTRY_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_BACKUP_MAX_ENTRY_SIZE", usize, 64 * 1024 * 1024);
+
 /// The maximum number of keys that should be scanned at once for count queries
 /// (default: 50,000)
 pub static COUNT_BATCH_SIZE: LazyLock<u32> =
@@ -195,6 +235,11 @@ pub static OPERATOR_BUFFER_SIZE: LazyLock<usize> =
 pub static MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE", u32, 1000);
 
+/// The number of rows the build side of a hash join may hold before the join
+/// falls back to evaluating the subquery once per row (default: 100,000)
+pub static HASH_JOIN_BUILD_LIMIT: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_HASH_JOIN_BUILD_LIMIT", usize, 100_000);
+
 /// The maximum stack size of the JavaScript function runtime (default: 256 KiB)
 pub static SCRIPTING_MAX_STACK_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_SCRIPTING_MAX_STACK_SIZE", usize, 256 * 1024);
@@ -229,6 +274,11 @@ pub static HTTP_IDLE_TIMEOUT_SECS: LazyLock<u64> =
 pub static HTTP_CONNECT_TIMEOUT_SECS: LazyLock<u64> =
 	lazy_env_parse!("SURREAL_HTTP_CONNECT_TIMEOUT_SECS", u64, 30);
 
+/// The number of HTTP clients which are kept for the distinct network
+/// capabilities of namespaces and databases (default: 100)
+pub static HTTP_CLIENT_CACHE_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_HTTP_CLIENT_CACHE_SIZE", usize, 100);
+
 /// Forward all authentication errors to the client. Do not use in production
 /// (default: false)
 pub static INSECURE_FORWARD_ACCESS_ERRORS: LazyLock<bool> =
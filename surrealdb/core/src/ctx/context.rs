@@ -36,15 +36,18 @@ use crate::dbs::capabilities::NetTarget;
 use crate::dbs::capabilities::Targets;
 use crate::dbs::{Capabilities, NewPlannerStrategy, Options, Session, Variables};
 use crate::err::Error;
+use crate::exec::QueryUsage;
 use crate::exec::function::FunctionRegistry;
 #[cfg(feature = "http")]
 use crate::http::HttpClient;
+use crate::iam::Auth;
 use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
 use crate::idx::trees::store::IndexStores;
 use crate::kvs::Transaction;
 use crate::kvs::cache::ds::DatastoreCache;
 use crate::kvs::index::IndexBuilder;
+use crate::kvs::quota::Quotas;
 use crate::kvs::sequences::Sequences;
 use crate::kvs::slowlog::SlowLog;
 use crate::mem::ALLOC;
@@ -111,6 +114,10 @@ pub struct Context {
 	matches_context: Option<Arc<crate::exec::function::MatchesContext>>,
 	// KNN context for index functions (vector::distance::knn)
 	knn_context: Option<Arc<crate::exec::function::KnnContext>>,
+	// The registry of the resource quotas shared between statements
+	quotas: Option<Arc<Quotas>>,
+	// The resource usage of the running statement, when a quota applies
+	query_usage: Option<Arc<QueryUsage>>,
 	/// Client for making http requests.
 	#[cfg(feature = "http")]
 	http_client: Arc<HttpClient>,
@@ -157,6 +164,8 @@ impl Context {
 			redact_volatile_explain_attrs: false,
 			matches_context: None,
 			knn_context: None,
+			quotas: None,
+			query_usage: None,
 			#[cfg(feature = "http")]
 			http_client: parent.http_client.clone(),
 		}
@@ -207,6 +216,8 @@ impl Context {
 			redact_volatile_explain_attrs: parent.redact_volatile_explain_attrs,
 			matches_context: parent.matches_context.clone(),
 			knn_context: parent.knn_context.clone(),
+			quotas: parent.quotas.clone(),
+			query_usage: parent.query_usage.clone(),
 			#[cfg(feature = "http")]
 			http_client,
 		}
@@ -243,6 +254,8 @@ impl Context {
 			redact_volatile_explain_attrs: parent.redact_volatile_explain_attrs,
 			matches_context: parent.matches_context.clone(),
 			knn_context: parent.knn_context.clone(),
+			quotas: parent.quotas.clone(),
+			query_usage: parent.query_usage.clone(),
 			#[cfg(feature = "http")]
 			http_client: parent.http_client.clone(),
 		}
@@ -286,6 +299,8 @@ impl Context {
 			redact_volatile_explain_attrs: from.redact_volatile_explain_attrs,
 			matches_context: from.matches_context.clone(),
 			knn_context: from.knn_context.clone(),
+			quotas: from.quotas.clone(),
+			query_usage: from.query_usage.clone(),
 			#[cfg(feature = "http")]
 			http_client: from.http_client.clone(),
 		}
@@ -322,6 +337,8 @@ impl Context {
 			redact_volatile_explain_attrs: from.redact_volatile_explain_attrs,
 			matches_context: from.matches_context.clone(),
 			knn_context: from.knn_context.clone(),
+			quotas: from.quotas.clone(),
+			query_usage: from.query_usage.clone(),
 			#[cfg(feature = "http")]
 			http_client: from.http_client.clone(),
 		}
@@ -370,6 +387,8 @@ impl Context {
 			redact_volatile_explain_attrs: false,
 			matches_context: None,
 			knn_context: None,
+			quotas: None,
+			query_usage: None,
 			#[cfg(feature = "http")]
 			http_client,
 		};
@@ -409,6 +428,8 @@ impl Context {
 			redact_volatile_explain_attrs: false,
 			matches_context: None,
 			knn_context: None,
+			quotas: None,
+			query_usage: None,
 			#[cfg(feature = "http")]
 			http_client: Arc::new(
 				HttpClient::new(
@@ -852,6 +873,100 @@ impl Context {
 		self.capabilities.clone()
 	}
 
+	/// Set the capabilities for this context, switching to the shared http
+	/// client of the network targets if they have changed
+	pub(crate) fn set_capabilities(&mut self, capabilities: Arc<Capabilities>) -> Result<()> {
+		if Arc::ptr_eq(&capabilities, &self.capabilities) {
+			return Ok(());
+		}
+		#[cfg(feature = "http")]
+		if capabilities.allow_net != self.capabilities.allow_net
+			|| capabilities.deny_net != self.capabilities.deny_net
+		{
+			self.http_client = HttpClient::cached(&capabilities.allow_net, &capabilities.deny_net)?;
+		}
+		self.capabilities = capabilities;
+		Ok(())
+	}
+
+	/// Restrict the given capabilities by the capabilities defined on a
+	/// namespace and database, and set them for this context
+	pub(crate) async fn restrict_capabilities(
+		&mut self,
+		txn: &Transaction,
+		capabilities: &Arc<Capabilities>,
+		ns: Option<&str>,
+		db: Option<&str>,
+	) -> Result<()> {
+		let res = Self::restricted_capabilities(txn, capabilities, ns, db).await?;
+		self.set_capabilities(res)
+	}
+
+	/// Restrict the given capabilities by the capabilities defined on a
+	/// namespace and database
+	pub(crate) async fn restricted_capabilities(
+		txn: &Transaction,
+		capabilities: &Arc<Capabilities>,
+		ns: Option<&str>,
+		db: Option<&str>,
+	) -> Result<Arc<Capabilities>> {
+		let mut res = capabilities.clone();
+		if let Some(ns) = ns {
+			if let Some(def) = txn.get_ns_by_name(ns, None).await?
+				&& let Some(v) = &def.capabilities
+			{
+				res = Arc::new(res.restrict(v));
+			}
+			if let Some(db) = db
+				&& let Some(def) = txn.get_db_by_name(ns, db, None).await?
+				&& let Some(v) = &def.capabilities
+			{
+				res = Arc::new(res.restrict(v));
+			}
+		}
+		Ok(res)
+	}
+
+	/// Set the registry of the resource quotas shared between statements
+	pub(crate) fn set_quotas(&mut self, quotas: Arc<Quotas>) {
+		self.quotas = Some(quotas);
+	}
+
+	/// Start tracking the resource usage of a statement against the quotas
+	/// defined on the namespace, the database, and the authenticated user
+	pub(crate) async fn apply_quotas(
+		&mut self,
+		txn: &Transaction,
+		ns: Option<&str>,
+		db: Option<&str>,
+		auth: &Auth,
+		write: bool,
+	) -> Result<()> {
+		// Release the usage of any previous statement first
+		self.release_quotas();
+		if let Some(quotas) = &self.quotas {
+			self.query_usage = quotas.start(txn, ns, db, auth, write).await?.map(Arc::new);
+		}
+		Ok(())
+	}
+
+	/// Clear the cached quotas, after a quota has been defined or removed
+	pub(crate) fn clear_quota_cache(&self) {
+		if let Some(quotas) = &self.quotas {
+			quotas.clear();
+		}
+	}
+
+	/// Stop tracking the resource usage of the finished statement
+	pub(crate) fn release_quotas(&mut self) {
+		self.query_usage = None;
+	}
+
+	/// Get the resource usage of the running statement, if a quota applies
+	pub(crate) fn query_usage(&self) -> Option<&Arc<QueryUsage>> {
+		self.query_usage.as_ref()
+	}
+
 	/// Get the function registry for this context
 	pub(crate) fn function_registry(&self) -> &Arc<FunctionRegistry> {
 		&self.function_registry
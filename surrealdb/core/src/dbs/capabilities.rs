@@ -4,7 +4,6 @@ use std::hash::{Hash, Hasher};
 use std::net::IpAddr;
 #[cfg(all(target_family = "wasm", feature = "http"))]
 use std::net::ToSocketAddrs;
-#[cfg(feature = "surrealism")]
 use std::str::FromStr;
 
 #[cfg(feature = "surrealism")]
@@ -16,6 +15,7 @@ use surrealism_runtime::capabilities::SurrealismCapabilities;
 use tokio::net::lookup_host;
 use url::Url;
 
+use crate::catalog::{CapabilitiesDefinition, CapabilityTargets};
 use crate::dbs::session::NewPlannerStrategy;
 use crate::iam::{Auth, Level};
 use crate::rpc::Method;
@@ -378,6 +378,7 @@ pub enum RouteTarget {
 	Ml,
 	GraphQL,
 	Api,
+	Sink,
 }
 
 // impl display
@@ -397,6 +398,7 @@ impl fmt::Display for RouteTarget {
 			RouteTarget::Ml => write!(f, "ml"),
 			RouteTarget::GraphQL => write!(f, "graphql"),
 			RouteTarget::Api => write!(f, "api"),
+			RouteTarget::Sink => write!(f, "sink"),
 		}
 	}
 }
@@ -435,6 +437,7 @@ impl std::str::FromStr for RouteTarget {
 			"ml" => Ok(RouteTarget::Ml),
 			"graphql" => Ok(RouteTarget::GraphQL),
 			"api" => Ok(RouteTarget::Api),
+			"sink" => Ok(RouteTarget::Sink),
 			_ => Err(ParseRouteTargetError),
 		}
 	}
@@ -549,6 +552,54 @@ impl<T: Hash + Eq + PartialEq + Ord + fmt::Debug + fmt::Display> Targets<T> {
 	}
 }
 
+impl<T: Target + Clone + Hash + Eq + PartialEq + Ord> Targets<T> {
+	/// Returns the targets which are matched by both sets of targets.
+	pub(crate) fn intersect(&self, other: &Self) -> Self {
+		match (self, other) {
+			(Self::None, _) | (_, Self::None) => Self::None,
+			(Self::All, v) | (v, Self::All) => v.clone(),
+			(Self::Some(a), Self::Some(b)) => {
+				// Keep the narrower of any two overlapping targets
+				let set: HashSet<T> = a
+					.iter()
+					.filter(|t| b.iter().any(|x| x.matches(t)))
+					.chain(b.iter().filter(|t| a.iter().any(|x| x.matches(t))))
+					.cloned()
+					.collect();
+				match set.is_empty() {
+					true => Self::None,
+					false => Self::Some(set),
+				}
+			}
+		}
+	}
+
+	/// Returns the targets which are matched by either set of targets.
+	pub(crate) fn union(&self, other: &Self) -> Self {
+		match (self, other) {
+			(Self::All, _) | (_, Self::All) => Self::All,
+			(Self::None, v) | (v, Self::None) => v.clone(),
+			(Self::Some(a), Self::Some(b)) => Self::Some(a.union(b).cloned().collect()),
+		}
+	}
+}
+
+impl<T: FromStr> From<&CapabilityTargets> for Targets<T>
+where
+	T: Hash + Eq + PartialEq + Ord,
+{
+	fn from(v: &CapabilityTargets) -> Self {
+		match v {
+			CapabilityTargets::None => Self::None,
+			CapabilityTargets::All => Self::All,
+			// Targets are validated when they are defined
+			CapabilityTargets::Some(v) => {
+				Self::Some(v.iter().filter_map(|t| t.parse().ok()).collect())
+			}
+		}
+	}
+}
+
 impl<T: Target + Hash + Eq + PartialEq + Ord + fmt::Display> fmt::Display for Targets<T> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
@@ -810,6 +861,36 @@ impl Capabilities {
 		&self.planner_strategy
 	}
 
+	/// Restricts the capabilities by those defined on a namespace or database.
+	///
+	/// The overrides can only narrow the capabilities: the allowed targets are
+	/// intersected, and the denied targets are combined.
+	pub(crate) fn restrict(&self, def: &CapabilitiesDefinition) -> Self {
+		let mut cap = self.clone();
+		if let Some(v) = def.scripting {
+			cap.scripting &= v;
+		}
+		if let Some(v) = &def.allow_funcs {
+			cap.allow_funcs = cap.allow_funcs.intersect(&v.into());
+		}
+		if let Some(v) = &def.deny_funcs {
+			cap.deny_funcs = cap.deny_funcs.union(&v.into());
+		}
+		if let Some(v) = &def.allow_net {
+			cap.allow_net = cap.allow_net.intersect(&v.into());
+		}
+		if let Some(v) = &def.deny_net {
+			cap.deny_net = cap.deny_net.union(&v.into());
+		}
+		if let Some(v) = &def.allow_experimental {
+			cap.allow_experimental = cap.allow_experimental.intersect(&v.into());
+		}
+		if let Some(v) = &def.deny_experimental {
+			cap.deny_experimental = cap.deny_experimental.union(&v.into());
+		}
+		cap
+	}
+
 	pub fn allows_scripting(&self) -> bool {
 		self.scripting
 	}
@@ -1214,6 +1295,44 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_targets_intersect() {
+		let func = |v: &[&str]| {
+			Targets::<FuncTarget>::Some(
+				v.iter().map(|x| FuncTarget::from_str(x).unwrap()).collect(),
+			)
+		};
+		assert_eq!(Targets::All.intersect(&func(&["http"])), func(&["http"]));
+		assert_eq!(func(&["http"]).intersect(&Targets::None), Targets::None);
+		assert_eq!(func(&["http"]).intersect(&func(&["http::get"])), func(&["http::get"]));
+		assert_eq!(func(&["http::get"]).intersect(&func(&["string"])), Targets::None);
+		assert_eq!(func(&["http"]).union(&func(&["string"])), func(&["http", "string"]));
+		assert_eq!(func(&["http"]).union(&Targets::All), Targets::All);
+	}
+
+	#[test]
+	fn test_capabilities_restrict() {
+		let caps = Capabilities::default()
+			.with_scripting(true)
+			.with_functions(Targets::All)
+			.without_functions(Targets::None);
+		let def = CapabilitiesDefinition {
+			scripting: Some(false),
+			deny_funcs: Some(CapabilityTargets::Some(vec!["string::uppercase".to_string()])),
+			..Default::default()
+		};
+		let res = caps.restrict(&def);
+		assert!(!res.allows_scripting());
+		assert!(res.allows_function_name("string::lowercase"));
+		assert!(!res.allows_function_name("string::uppercase"));
+		// Overrides can never grant more than the server allows
+		let def = CapabilitiesDefinition {
+			scripting: Some(true),
+			..Default::default()
+		};
+		assert!(!Capabilities::default().with_scripting(false).restrict(&def).allows_scripting());
+	}
+
 	#[test]
 	fn test_capabilities() {
 		// When scripting is allowed
@@ -17,7 +17,7 @@ use crate::catalog::providers::{CatalogProvider, NamespaceProvider, RootProvider
 use crate::ctx::reason::Reason;
 use crate::ctx::{Context, FrozenContext};
 use crate::dbs::response::QueryResult;
-use crate::dbs::{Force, Options, QueryType};
+use crate::dbs::{Capabilities, Force, Options, QueryType};
 use crate::doc::DefaultBroker;
 use crate::err::Error;
 use crate::exec::planner::try_plan_expr;
@@ -42,6 +42,9 @@ pub struct Executor {
 	results: Vec<QueryResult>,
 	opt: Options,
 	ctx: FrozenContext,
+	/// The capabilities of the datastore, before any namespace or database
+	/// overrides are applied
+	capabilities: Arc<Capabilities>,
 	/// Cached session info to avoid re-extracting from context on every query.
 	/// Session values don't change between statements in the same executor batch.
 	cached_session: Option<Arc<crate::exec::context::SessionInfo>>,
@@ -68,6 +71,7 @@ impl Executor {
 			stack: TreeStack::new(),
 			results: Vec::new(),
 			opt,
+			capabilities: ctx.get_capabilities(),
 			ctx,
 			cached_session: None,
 		}
@@ -348,6 +352,13 @@ impl Executor {
 					.map_err(anyhow::Error::new)?
 			};
 		}
+		// Apply the capability overrides of the selected namespace and database
+		let capabilities = self.capabilities.clone();
+		let (ns, db) = (self.opt.ns.clone(), self.opt.db.clone());
+		ctx_mut!().restrict_capabilities(&txn, &capabilities, ns.as_deref(), db.as_deref()).await?;
+		// Apply the resource quotas of the selected namespace, database, and user
+		let (auth, write) = (self.opt.auth.clone(), !plan.read_only());
+		ctx_mut!().apply_quotas(&txn, ns.as_deref(), db.as_deref(), &auth, write).await?;
 		let res = match plan {
 			TopLevelExpr::Use(stmt) => {
 				let opt_ref = self.opt.clone();
@@ -497,6 +508,18 @@ impl Executor {
 					"Cannot CANCEL without starting a transaction".to_string(),
 				))));
 			}
+			TopLevelExpr::Savepoint(name) => {
+				txn.set_named_save_point(&name).await?;
+				Ok(Value::None)
+			}
+			TopLevelExpr::RollbackTo(name) => {
+				txn.rollback_to_named_save_point(&name).await?;
+				Ok(Value::None)
+			}
+			TopLevelExpr::Release(name) => {
+				txn.release_named_save_point(&name).await?;
+				Ok(Value::None)
+			}
 			TopLevelExpr::Kill(s) => {
 				Arc::get_mut(&mut self.ctx)
 					.ok_or_else(|| {
@@ -562,6 +585,10 @@ impl Executor {
 				}
 			}
 		};
+		// Release the concurrency quotas held by the statement
+		if let Some(ctx) = Arc::get_mut(&mut self.ctx) {
+			ctx.release_quotas();
+		}
 
 		// Catch cancellation during running.
 		match self.ctx.done(true)? {
@@ -592,6 +619,19 @@ impl Executor {
 				bail!(Error::QueryCancelled);
 			}
 		}
+		// Savepoints only exist within a transaction block
+		match stmt {
+			TopLevelExpr::Savepoint(_) => bail!(Error::InvalidStatement(
+				"Cannot SAVEPOINT without starting a transaction".to_string(),
+			)),
+			TopLevelExpr::RollbackTo(_) => bail!(Error::InvalidStatement(
+				"Cannot ROLLBACK TO a savepoint without starting a transaction".to_string(),
+			)),
+			TopLevelExpr::Release(_) => bail!(Error::InvalidStatement(
+				"Cannot RELEASE a savepoint without starting a transaction".to_string(),
+			)),
+			_ => {}
+		}
 
 		self.execute_plan_impl(kvs, start, stmt).await
 	}
@@ -754,6 +794,9 @@ impl Executor {
 		let receiver = self.prepare_broker();
 		let start_results = self.results.len();
 		let mut skip_remaining = false;
+		// The position of the result of a statement which failed within a named
+		// savepoint, until the transaction is rolled back to a savepoint.
+		let mut failed_result = None;
 
 		// loop over the statements until we hit a cancel or a commit statement.
 		while let Some(stmt) = stream.next().await {
@@ -843,6 +886,53 @@ impl Executor {
 				continue;
 			}
 
+			// After a failed statement, only a rollback to a savepoint can continue the
+			// transaction.
+			if let Some(failed) = failed_result {
+				match stmt {
+					TopLevelExpr::Cancel | TopLevelExpr::RollbackTo(_) => {}
+					TopLevelExpr::Commit => {
+						let _ = txn.cancel().await;
+
+						// The transaction was not rolled back to a savepoint, so it is aborted.
+						for res in &mut self.results[start_results..failed] {
+							res.query_type = QueryType::Other;
+							res.result = Err(TypesError::query(
+								"The query was not executed due to a failed transaction"
+									.to_string(),
+								Some(QueryError::NotExecuted),
+							));
+						}
+
+						self.opt.broker = None;
+
+						self.results.push(QueryResult {
+							time: Duration::ZERO,
+							result: Err(TypesError::query(
+								"Cannot COMMIT: the transaction was aborted due to a prior error"
+									.to_string(),
+								Some(QueryError::NotExecuted),
+							)),
+							query_type: QueryType::Other,
+						});
+
+						return Ok(());
+					}
+					_ => {
+						self.results.push(QueryResult {
+							time: Duration::ZERO,
+							result: Err(TypesError::query(
+								"The query was not executed due to a failed statement, ROLLBACK TO a savepoint to continue the transaction"
+									.to_string(),
+								Some(QueryError::NotExecuted),
+							)),
+							query_type: QueryType::Other,
+						});
+						continue;
+					}
+				}
+			}
+
 			trace!(target: TARGET, statement = %stmt.to_sql(), "Executing statement");
 
 			let query_type = match stmt {
@@ -1011,9 +1101,15 @@ impl Executor {
 				stmt => {
 					// reintroduce planner later.
 					let plan = stmt;
+					let rollback = matches!(plan, TopLevelExpr::RollbackTo(_));
 
 					let r = match self.execute_plan_in_transaction(txn.clone(), &before, plan).await
 					{
+						// The transaction continues after rolling back to a savepoint.
+						Ok(x) if rollback => {
+							failed_result = None;
+							Ok(x)
+						}
 						Ok(x) => Ok(x),
 						Err(ControlFlow::Return(value)) => {
 							skip_remaining = true;
@@ -1022,6 +1118,17 @@ impl Executor {
 						Err(ControlFlow::Break) | Err(ControlFlow::Continue) => {
 							Err(anyhow!(Error::InvalidControlFlow))
 						}
+						// Within a named savepoint, the failed statement can be reverted by
+						// rolling back to the savepoint.
+						Err(ControlFlow::Err(e)) if txn.has_named_save_point().await => {
+							failed_result.get_or_insert(self.results.len());
+							self.results.push(QueryResult {
+								time: before.elapsed(),
+								result: Err(types_error_from_anyhow(e)),
+								query_type,
+							});
+							continue;
+						}
 						Err(ControlFlow::Err(e)) => {
 							for res in &mut self.results[start_results..] {
 								res.query_type = QueryType::Other;
@@ -1624,4 +1731,51 @@ mod tests {
 			"Error should explain that import mode is locked, got: {err}"
 		);
 	}
+
+	#[tokio::test]
+	async fn import_stream_applies_capability_overrides() {
+		use bytes::Bytes;
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::default().with_ns("NS").with_db("DB");
+
+		let sql = "DEFINE NAMESPACE NS; USE NS NS; \
+			DEFINE DATABASE DB CAPABILITIES { deny_funcs: ['string::uppercase'] }";
+		ds.execute(sql, &sess, None).await.unwrap();
+
+		let sql = "OPTION IMPORT; INSERT INTO person { name: string::uppercase('a') };";
+		let body = futures::stream::once(async { Ok(Bytes::from(sql)) });
+		let results = ds.import_stream(&sess, body).await.unwrap();
+
+		assert!(
+			results.iter().any(|r| r.result.is_err()),
+			"Expected the denied function to fail the import"
+		);
+
+		let verify = ds.execute("SELECT * FROM person", &sess, None).await.unwrap();
+		let rows = verify[0].result.as_ref().unwrap();
+		assert_eq!(rows.as_array().unwrap().len(), 0, "No records should have been imported");
+	}
+
+	#[tokio::test]
+	async fn execute_with_transaction_applies_capability_overrides() {
+		use crate::kvs::LockType::Optimistic;
+		use crate::kvs::TransactionType::Write;
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::default().with_ns("NS").with_db("DB");
+
+		let sql = "DEFINE NAMESPACE NS; USE NS NS; \
+			DEFINE DATABASE DB CAPABILITIES { deny_funcs: ['string::uppercase'] }";
+		ds.execute(sql, &sess, None).await.unwrap();
+
+		let txn = ds.transaction(Write, Optimistic).await.unwrap().enclose();
+		let sql = "string::lowercase('A'); string::uppercase('a');";
+		let results = ds.execute_with_transaction(sql, &sess, None, txn.clone()).await.unwrap();
+		txn.cancel().await.unwrap();
+
+		assert!(results[0].result.is_ok(), "Allowed functions should run");
+		let err = results[1].result.as_ref().unwrap_err().to_string();
+		assert!(err.contains("is not allowed"), "Expected the function to be denied, got: {err}");
+	}
 }
@@ -188,6 +188,12 @@ impl Iterator {
 		self.entries.push(val)
 	}
 
+	/// Takes the prepared iterables out of the iterator, along with the
+	/// record to create when no other record is processed
+	pub(crate) fn take_entries(&mut self) -> (Vec<Iterable>, Option<Iterable>) {
+		(mem::take(&mut self.entries), self.guaranteed.take())
+	}
+
 	/// Prepares a value for processing
 	#[allow(clippy::too_many_arguments)]
 	pub(crate) async fn prepare(
@@ -134,18 +134,26 @@ impl ExplainItem {
 					details: vec![("from", Value::RecordId(from.clone()))],
 				},
 			},
-			Iterable::Table(_doc_ctx, t, rs, sc) => Self {
-				name: match rs {
-					RecordStrategy::Count => "Iterate Table Count",
-					RecordStrategy::KeysOnly => "Iterate Table Keys",
-					RecordStrategy::KeysAndValues => "Iterate Table",
-				}
-				.into(),
-				details: vec![
+			Iterable::Table(_doc_ctx, t, rs, sc) => {
+				let mut details = vec![
 					("table", Value::String(t.clone().into_string())),
 					("direction", sc.to_string().into()),
-				],
-			},
+				];
+				if let Some(qp) = ctx.get_query_planner()
+					&& let Some(rows) = qp.estimated_rows(t, None)
+				{
+					details.push(("estimated_rows", Value::from(rows as i64)));
+				}
+				Self {
+					name: match rs {
+						RecordStrategy::Count => "Iterate Table Count",
+						RecordStrategy::KeysOnly => "Iterate Table Keys",
+						RecordStrategy::KeysAndValues => "Iterate Table",
+					}
+					.into(),
+					details,
+				}
+			}
 			Iterable::Range(_doc_ctx, tb, r, rs, sc) => Self {
 				name: match rs {
 					RecordStrategy::Count => "Iterate Range Count",
@@ -197,6 +205,11 @@ impl ExplainItem {
 				{
 					details.push(("plan", exe.explain(*ir)));
 				}
+				if let Some(qp) = ctx.get_query_planner()
+					&& let Some(rows) = qp.estimated_rows(t, Some(*ir))
+				{
+					details.push(("estimated_rows", Value::from(rows as i64)));
+				}
 				Self {
 					name: match rs {
 						RecordStrategy::Count => "Iterate Index Count",
@@ -62,6 +62,52 @@ impl Iterable {
 		Ok(())
 	}
 
+	/// Whether this iterable targets individual records, rather than
+	/// scanning a table, a range, an index or graph edges.
+	pub(crate) fn is_record_target(&self) -> bool {
+		matches!(
+			self,
+			Iterable::Value(..)
+				| Iterable::GenerateRecordId(..)
+				| Iterable::RecordId(..)
+				| Iterable::Defer(..)
+				| Iterable::Mergeable(..)
+				| Iterable::Relatable(..)
+		)
+	}
+
+	/// Prepares an iterable which targets individual records for
+	/// processing, without collecting it through an [`Iterator`].
+	///
+	/// Returns `None` for values which the iterator would skip.
+	pub(crate) async fn prepare_record_target(
+		self,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Option<Processable>> {
+		let collectable = match self {
+			Iterable::Value(doc_ctx, v) => {
+				if v.is_nullish() {
+					return Ok(None);
+				}
+				Collectable::Value(doc_ctx, v)
+			}
+			Iterable::GenerateRecordId(doc_ctx, v) => Collectable::GenerateRecordId(doc_ctx, v),
+			Iterable::RecordId(doc_ctx, v) => Collectable::RecordId(doc_ctx, v),
+			Iterable::Defer(doc_ctx, v) => Collectable::Defer(doc_ctx, v),
+			Iterable::Mergeable(doc_ctx, tb, id, o) => Collectable::Mergeable(doc_ctx, tb, id, o),
+			Iterable::Relatable(doc_ctx, f, v, w, o) => Collectable::Relatable {
+				doc_ctx,
+				f,
+				v,
+				w,
+				o,
+			},
+			_ => bail!(Error::Internal("Expected an iterable targeting records".to_string())),
+		};
+		collectable.prepare(opt, txn, false).await.map(Some)
+	}
+
 	/// Check if the iteration stage is valid for the iterable.
 	///
 	/// This is only false if the iterable is a table or index and the iteration stage is building a
@@ -551,6 +597,10 @@ pub(super) struct ConcurrentCollector<'a> {
 impl Collector for ConcurrentCollector<'_> {
 	#[instrument(level = "trace", skip_all)]
 	async fn collect(&mut self, collectable: Collectable) -> Result<()> {
+		// Count the scanned record against the scan quota
+		if let Some(usage) = self.ctx.query_usage() {
+			usage.record_scanned(1)?;
+		}
 		// if it is skippable don't need to process the document
 		if self.ite.skippable() > 0 {
 			self.ite.skipped(1);
@@ -576,6 +626,10 @@ pub(super) struct ConcurrentDistinctCollector<'a> {
 impl Collector for ConcurrentDistinctCollector<'_> {
 	#[instrument(level = "trace", skip_all)]
 	async fn collect(&mut self, collectable: Collectable) -> Result<()> {
+		// Count the scanned record against the scan quota
+		if let Some(usage) = self.coll.ctx.query_usage() {
+			usage.record_scanned(1)?;
+		}
 		let skippable = self.coll.ite.skippable() > 0;
 		// If it is skippable, we just need to collect the record id (if any)
 		// to ensure that distinct can be checked.
@@ -81,6 +81,13 @@ impl Results {
 		rs: RecordStrategy,
 		val: Value,
 	) -> Result<()> {
+		// Count values buffered for sorting, shuffling, or grouping against
+		// the memory quota
+		if matches!(self, Self::MemoryOrdered(_) | Self::MemoryRandom(_) | Self::Groups(_))
+			&& let Some(usage) = ctx.query_usage()
+		{
+			usage.record_memory(std::slice::from_ref(&val))?;
+		}
 		match self {
 			Self::None => {}
 			Self::Memory(s) => {
@@ -46,7 +46,8 @@ pub struct Session {
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub enum NewPlannerStrategy {
-	/// Try the new planner for read-only statements, fall back to compute on Unimplemented.
+	/// Try the new planner for read-only statements and supported write
+	/// statements, fall back to compute on Unimplemented.
 	#[default]
 	BestEffortReadOnlyStatements,
 	/// Skip the new planner entirely; always use the compute executor.
@@ -54,6 +55,23 @@ pub enum NewPlannerStrategy {
 	/// Require the new planner for all read-only statements.
 	/// Promotes Error::PlannerUnimplemented to Error::Query (hard error) instead of falling back.
 	AllReadOnlyStatements,
+	/// Require the new planner for all read-only statements, and also plan
+	/// supported write statements (CREATE, UPDATE, UPSERT, DELETE, INSERT and
+	/// RELATE) through the streaming mutation operator. Unsupported write
+	/// statements fall back to compute.
+	AllStatements,
+}
+
+impl NewPlannerStrategy {
+	/// Whether `Error::PlannerUnimplemented` should be promoted to a hard error.
+	pub fn requires_planner(&self) -> bool {
+		matches!(self, Self::AllReadOnlyStatements | Self::AllStatements)
+	}
+
+	/// Whether write statements should be planned by the new planner.
+	pub fn plans_writes(&self) -> bool {
+		matches!(self, Self::BestEffortReadOnlyStatements | Self::AllStatements)
+	}
 }
 
 impl fmt::Display for NewPlannerStrategy {
@@ -62,6 +80,7 @@ impl fmt::Display for NewPlannerStrategy {
 			Self::BestEffortReadOnlyStatements => f.write_str("best-effort"),
 			Self::ComputeOnly => f.write_str("compute-only"),
 			Self::AllReadOnlyStatements => f.write_str("all-read-only"),
+			Self::AllStatements => f.write_str("all"),
 		}
 	}
 }
@@ -74,8 +93,9 @@ impl FromStr for NewPlannerStrategy {
 			"best-effort" => Ok(Self::BestEffortReadOnlyStatements),
 			"compute-only" => Ok(Self::ComputeOnly),
 			"all-read-only" => Ok(Self::AllReadOnlyStatements),
+			"all" => Ok(Self::AllStatements),
 			_ => Err(format!(
-				"unknown planner strategy: '{s}' (expected 'best-effort', 'compute-only', or 'all-read-only')"
+				"unknown planner strategy: '{s}' (expected 'best-effort', 'compute-only', 'all-read-only', or 'all')"
 			)),
 		}
 	}
@@ -437,12 +437,15 @@ impl AsyncEventContext {
 	}
 
 	async fn run_event(&mut self, stk: &mut Stk, mut ctx: Context, v: Val) -> Result<()> {
-		let tx = self.new_write_tx().await?;
-		ctx.set_transaction(Arc::new(tx));
+		let tx = Arc::new(self.new_write_tx().await?);
+		let eq = catch!(tx, EventQueue::decode_key(&self.k));
+		let mut ev = catch!(tx, AsyncEventRecord::kv_decode_value(v));
+		// Run the event with the capabilities of its namespace and database
+		let base = ctx.get_capabilities();
+		catch!(tx, ctx.restrict_capabilities(&tx, &base, Some(&ev.ns), Some(&ev.db)).await);
+		ctx.set_transaction(tx);
 		let ctx = ctx.freeze();
 		let tx = ctx.tx();
-		let eq = EventQueue::decode_key(&self.k)?;
-		let mut ev = AsyncEventRecord::kv_decode_value(v)?;
 		match Self::process_event(stk, &ctx, &self.opt, self.lh.as_ref(), &eq, &ev).await {
 			Ok(_) => {
 				// Event processed successfully, delete the event from the queue.
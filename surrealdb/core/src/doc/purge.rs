@@ -41,6 +41,11 @@ impl Document {
 			let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
 			// Purge the record data
 			txn.del_record(ns, db, &rid.table, &rid.key).await?;
+			// Purge the record expiry time
+			if let Some(ttl) = &self.tb().await?.ttl {
+				crate::kvs::ttl::index_record(&txn, ns, db, rid, ttl, &self.initial.doc, None)
+					.await?;
+			}
 			// Purge the record edges
 			self.purge_edges(stk, ctx, opt, rid.as_ref()).await?;
 			// Purge any record references
@@ -1,10 +1,12 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 
 use crate::catalog::providers::TableProvider;
 use crate::ctx::FrozenContext;
 use crate::dbs::{Options, Statement};
 use crate::doc::Document;
 use crate::err::Error;
+use crate::expr::Ttl;
 
 impl Document {
 	pub(super) async fn store_record_data(
@@ -18,9 +20,18 @@ impl Document {
 			return Ok(());
 		}
 		// Check if the table is a view
-		if self.tb().await?.drop {
+		let tb = self.tb().await?.clone();
+		if tb.drop {
 			return Ok(());
 		}
+		// Set the expiry time for a duration time-to-live
+		if let Some(Ttl::Duration(ttl)) = &tb.ttl {
+			let expires = chrono::Duration::from_std(*ttl)
+				.ok()
+				.and_then(|ttl| Utc::now().checked_add_signed(ttl))
+				.unwrap_or(DateTime::<Utc>::MAX_UTC);
+			self.current.doc.set_expires(expires.into());
+		}
 		// Get the record id
 		let rid = self.id()?;
 		// Get NS & DB
@@ -110,6 +121,12 @@ impl Document {
 			// Let's update the stored value for the specified key
 			_ => ctx.tx().set_record(ns, db, &rid.table, &rid.key, doc).await,
 		}?;
+		// Move the record to its new expiry time
+		if let Some(ttl) = &tb.ttl {
+			let (before, after) = (&*self.initial.doc, &*self.current.doc);
+			crate::kvs::ttl::index_record(&ctx.tx(), ns, db, &rid, ttl, before, Some(after))
+				.await?;
+		}
 		// Carry on
 		Ok(())
 	}
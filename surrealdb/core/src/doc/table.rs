@@ -332,6 +332,7 @@ impl Document {
 				metadata: Some(Metadata {
 					record_type: RecordType::Table,
 					aggregation_stats: aggr.aggregations.iter().map(|x| x.to_stat()).collect(),
+					expires: None,
 				}),
 			}
 		};
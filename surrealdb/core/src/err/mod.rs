@@ -181,7 +181,6 @@ pub(crate) enum Error {
 
 	/// The URL is invalid
 	#[error("The URL `{0}` is invalid")]
-	#[cfg_attr(not(any(feature = "http", feature = "jwks")), expect(dead_code))]
 	InvalidUrl(String),
 
 	/// The size of the vector is incorrect
@@ -233,6 +232,13 @@ pub(crate) enum Error {
 	#[error("The query was not executed due to the memory threshold being reached")]
 	QueryBeyondMemoryThreshold,
 
+	/// The query was stopped, because it exceeded a resource quota
+	#[error("The query exceeded the {name} quota of {limit}")]
+	QuotaExceeded {
+		name: String,
+		limit: u64,
+	},
+
 	/// The query did not execute, because the transaction has failed.
 	#[error("The query was not executed due to a failed transaction. {message}")]
 	QueryNotExecuted {
@@ -311,6 +317,30 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested job does not exist
+	#[error("The job '{name}' does not exist")]
+	JbNotFound {
+		name: String,
+	},
+
+	/// The requested consumer group does not exist
+	#[error("The consumer '{name}' does not exist")]
+	CuNotFound {
+		name: String,
+	},
+
+	/// The requested sink does not exist
+	#[error("The sink '{name}' does not exist")]
+	SkNotFound {
+		name: String,
+	},
+
+	/// The requested sink does not deliver its changes to a stream
+	#[error("The sink '{name}' does not deliver to a stream")]
+	SkNotStream {
+		name: String,
+	},
+
 	/// The requested config does not exist
 	#[error("The config for {name} does not exist")]
 	CgNotFound {
@@ -387,6 +417,12 @@ pub(crate) enum Error {
 	#[error("Invalid statement: {0}")]
 	InvalidStatement(String),
 
+	/// The requested savepoint does not exist in the current transaction
+	#[error("The savepoint '{name}' does not exist in this transaction")]
+	SavepointNotFound {
+		name: String,
+	},
+
 	/// Cannot execute statement using the specified value
 	#[error("Cannot execute statement using value: {value}")]
 	InvalidStatementTarget {
@@ -679,6 +715,10 @@ pub(crate) enum Error {
 	#[error("A value can't be highlighted: {0}")]
 	HighlightError(String),
 
+	/// The query string of a MATCHES operator is invalid
+	#[error("Invalid full-text search query: {0}")]
+	InvalidMatchesQuery(String),
+
 	/// Represents an underlying error with FST
 	#[error("FstError error: {0}")]
 	FstError(#[from] FstError),
@@ -742,6 +782,14 @@ pub(crate) enum Error {
 	#[cfg_attr(not(feature = "http"), expect(dead_code))]
 	NetTargetNotAllowed(String),
 
+	/// A capability override lists an invalid target
+	#[error("Invalid capability target '{target}' for '{name}': {message}")]
+	InvalidCapability {
+		name: String,
+		target: String,
+		message: String,
+	},
+
 	//
 	// Authentication / Signup
 	#[error("There was an error creating the token")]
@@ -777,6 +825,10 @@ pub(crate) enum Error {
 	#[error("There was a problem with signing up")]
 	InvalidSignup,
 
+	/// The TOTP secret of a user is not valid base32
+	#[error("The TOTP secret is not valid base32")]
+	InvalidTotpSecret,
+
 	// The cluster node already exists
 	#[error("The node '{id}' already exists")]
 	ClAlreadyExists {
@@ -867,6 +919,24 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested job already exists
+	#[error("The job '{name}' already exists")]
+	JbAlreadyExists {
+		name: String,
+	},
+
+	/// The requested consumer group already exists
+	#[error("The consumer '{name}' already exists")]
+	CuAlreadyExists {
+		name: String,
+	},
+
+	/// The requested sink already exists
+	#[error("The sink '{name}' already exists")]
+	SkAlreadyExists {
+		name: String,
+	},
+
 	/// The requested table already exists
 	#[error("The table '{name}' already exists")]
 	TbAlreadyExists {
@@ -1074,6 +1144,16 @@ pub(crate) enum Error {
 		actual: u16,
 	},
 
+	/// A datastore backup could not be decoded or restored
+	#[error("Invalid backup: {0}")]
+	InvalidBackup(String),
+
+	/// A backup was taken of a datastore which keeps historical versions
+	#[error(
+		"The datastore keeps historical versions, which can not be included in a backup. Back up only the latest version of each key instead"
+	)]
+	VersionedBackup,
+
 	#[error("Size of query script exceeded maximum supported size of 4,294,967,295 bytes.")]
 	QueryTooLarge,
 
@@ -55,6 +55,7 @@ pub fn into_types_error(error: Error) -> TypesError {
 			),
 		},
 		InvalidSignup => TypesError::not_allowed(message, AuthError::InvalidSignup),
+		InvalidTotpSecret => TypesError::validation(message, None),
 
 		// Validation
 		NsEmpty => TypesError::validation(message, ValidationError::NamespaceEmpty),
@@ -85,6 +86,7 @@ pub fn into_types_error(error: Error) -> TypesError {
 			},
 		),
 		InvalidPatch(_) => TypesError::validation(message, None),
+		InvalidMatchesQuery(_) => TypesError::validation(message, None),
 		Coerce(_) => TypesError::validation(message, None),
 		Cast(_) => TypesError::validation(message, None),
 		TryAdd(..) | TrySub(..) | TryMul(..) | TryDiv(..) | TryRem(..) | TryPow(..) | TryNeg(_)
@@ -129,6 +131,9 @@ pub fn into_types_error(error: Error) -> TypesError {
 			},
 		),
 		QueryCancelled => TypesError::query(message, QueryError::Cancelled),
+		QuotaExceeded {
+			..
+		} => TypesError::query(message, None),
 		QueryNotExecuted {
 			message,
 		} => TypesError::query(message, QueryError::NotExecuted),
@@ -250,6 +255,15 @@ pub fn into_types_error(error: Error) -> TypesError {
 		| SeqAlreadyExists {
 			..
 		}
+		| JbAlreadyExists {
+			..
+		}
+		| CuAlreadyExists {
+			..
+		}
+		| SkAlreadyExists {
+			..
+		}
 		| NtAlreadyExists {
 			..
 		}
@@ -310,6 +324,7 @@ pub fn into_types_error(error: Error) -> TypesError {
 			KvsError::Datastore(_)
 			| KvsError::Transaction(_)
 			| KvsError::TimestampInvalid(_)
+			| KvsError::Encryption(_)
 			| KvsError::Internal(_)
 			| KvsError::CompactionNotSupported => TypesError::internal(message),
 		},
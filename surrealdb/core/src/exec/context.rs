@@ -345,6 +345,15 @@ impl DatabaseContext {
 
 		Ok(result)
 	}
+
+	/// Look up the statistics collected by `ANALYZE TABLE` for a table.
+	pub(crate) async fn get_table_statistics(
+		&self,
+		table: &crate::val::TableName,
+	) -> anyhow::Result<Option<Arc<crate::catalog::TableStatistics>>> {
+		use crate::catalog::providers::TableProvider;
+		self.txn().get_tb_statistics(self.ns_ctx.ns.namespace_id, self.db.database_id, table).await
+	}
 }
 
 /// Unified execution context - a discriminated union of all context levels.
@@ -233,6 +233,9 @@ impl IndexFunction for SearchHighlight {
 /// Usage: `search::score(1)`
 ///
 /// The match_ref (1st argument, index 0) is extracted at plan time.
+///
+/// In a hybrid search (`WITH FUSION`), this returns the fused score of the
+/// KNN and full-text searches instead of the BM25 score.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SearchScore;
 
@@ -274,6 +277,14 @@ impl IndexFunction for SearchScore {
 			// Extract RecordId from the current row
 			let rid = extract_record_id(ctx)?;
 
+			// A hybrid search has already computed the fused scores
+			if let Some(fusion) = &match_ctx.fusion {
+				return Ok(match fusion.get(&rid).await {
+					Some(score) => Value::Number(Number::Float(score)),
+					None => Value::None,
+				});
+			}
+
 			// Get the full-text index resources (lazy init)
 			let (fti, qt, scorer) = match_ctx.ft_resources(ctx).await?;
 
@@ -17,6 +17,10 @@
 //! - **Knn**: A [`KnnContext`] is created from the KNN operator in the WHERE clause. The KNN scan
 //!   operator populates it with per-row distances at execution time.
 //!
+//! When a `SELECT` fuses a KNN search with a full-text search (`WITH FUSION`), a
+//! [`FusionContext`] is attached to the MATCHES context, and the hybrid scan operator
+//! populates it with the fused per-row scores returned by `search::score`.
+//!
 //! Examples: search::highlight, search::score, search::offsets, vector::distance::knn
 
 use std::collections::HashMap;
@@ -30,6 +34,7 @@ use crate::exec::physical_expr::EvalContext;
 use crate::exec::{BoxFut, ContextLevel, SendSyncRequirement};
 use crate::expr::Kind;
 use crate::expr::idiom::Idiom;
+use crate::expr::operator::MatchesOperator;
 use crate::idx::ft::MatchRef;
 use crate::idx::ft::fulltext::{FullTextIndex, QueryTerms, Scorer};
 use crate::val::{Number, RecordId, TableName, Value};
@@ -162,23 +167,35 @@ pub struct MatchContext {
 	pub idiom: Idiom,
 	/// The search query string from the right side of the MATCHES operator.
 	pub query: String,
+	/// The MATCHES operator, along with its options.
+	pub operator: MatchesOperator,
 	/// The table name for index lookup.
 	pub table: TableName,
+	/// The fused scores, when the query is a hybrid search.
+	pub fusion: Option<Arc<FusionContext>>,
 	/// Lazily initialized full-text index resources.
 	ft_cache: tokio::sync::OnceCell<(FullTextIndex, QueryTerms, Option<Scorer>)>,
 }
 
 impl MatchContext {
 	/// Create a new MatchContext from resolved MATCHES clause info.
-	pub fn new(idiom: Idiom, query: String, table: TableName) -> Self {
+	pub fn new(idiom: Idiom, query: String, operator: MatchesOperator, table: TableName) -> Self {
 		Self {
 			idiom,
 			query,
+			operator,
 			table,
+			fusion: None,
 			ft_cache: tokio::sync::OnceCell::new(),
 		}
 	}
 
+	/// Attach the fused scores of a hybrid search.
+	pub fn with_fusion(mut self, fusion: Option<Arc<FusionContext>>) -> Self {
+		self.fusion = fusion;
+		self
+	}
+
 	/// Get or lazily initialize the full-text index resources.
 	///
 	/// On first call, this looks up the full-text index definition for the
@@ -247,7 +264,13 @@ impl MatchContext {
 					let mut stack = reblessive::TreeStack::new();
 					stack
 						.enter(|stk| {
-							fti.extract_querying_terms(stk, frozen, opt, self.query.clone())
+							fti.extract_querying_terms(
+								stk,
+								frozen,
+								opt,
+								self.query.clone(),
+								&self.operator,
+							)
 						})
 						.finish()
 						.await?
@@ -328,6 +351,55 @@ impl Debug for KnnContext {
 	}
 }
 
+// =========================================================================
+// FusionContext - fused score context populated by the hybrid scan operator
+// =========================================================================
+
+/// Fused score context, populated by HybridScan at execution time.
+///
+/// Created at plan time when the `SELECT` has a `WITH FUSION` clause, and
+/// shared (via `Arc`) between the hybrid scan operator and `search::score()`.
+/// The scan operator writes the per-row fused scores once both searches are
+/// complete; the function reads them during projection evaluation.
+pub struct FusionContext {
+	/// Per-row fused scores keyed by RecordId, populated by the hybrid scan operator.
+	scores: tokio::sync::RwLock<HashMap<RecordId, f64>>,
+}
+
+impl FusionContext {
+	/// Create a new empty FusionContext.
+	pub fn new() -> Self {
+		Self {
+			scores: tokio::sync::RwLock::new(HashMap::new()),
+		}
+	}
+
+	/// Record the fused scores. Called by HybridScan after the fusion.
+	pub async fn extend(&self, scores: impl IntoIterator<Item = (RecordId, f64)>) {
+		self.scores.write().await.extend(scores);
+	}
+
+	/// Look up the fused score for a record. Called by search::score().
+	pub async fn get(&self, rid: &RecordId) -> Option<f64> {
+		self.scores.read().await.get(rid).copied()
+	}
+}
+
+impl Default for FusionContext {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Debug for FusionContext {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.scores.try_read() {
+			Ok(guard) => f.debug_struct("FusionContext").field("entries", &guard.len()).finish(),
+			Err(_) => f.debug_struct("FusionContext").field("entries", &"<locked>").finish(),
+		}
+	}
+}
+
 // =========================================================================
 // MatchesContext - planning-time map of all MATCHES clauses
 // =========================================================================
@@ -339,6 +411,8 @@ pub struct MatchInfo {
 	pub idiom: Idiom,
 	/// The search query string from the right side of the MATCHES operator.
 	pub query: String,
+	/// The MATCHES operator, along with its options.
+	pub operator: MatchesOperator,
 }
 
 /// Planning-time context mapping match_ref numbers to MATCHES clause info.
@@ -351,6 +425,8 @@ pub struct MatchesContext {
 	matches: HashMap<MatchRef, MatchInfo>,
 	/// The table name from the FROM clause, set during planning.
 	table: Option<TableName>,
+	/// The fused scores, when the query is a hybrid search.
+	fusion: Option<Arc<FusionContext>>,
 }
 
 impl MatchesContext {
@@ -359,6 +435,7 @@ impl MatchesContext {
 		Self {
 			matches: HashMap::new(),
 			table: None,
+			fusion: None,
 		}
 	}
 
@@ -372,6 +449,16 @@ impl MatchesContext {
 		self.table.as_ref()
 	}
 
+	/// Set the fused score context of a hybrid search.
+	pub fn set_fusion(&mut self, fusion: Arc<FusionContext>) {
+		self.fusion = Some(fusion);
+	}
+
+	/// Get the fused score context of a hybrid search.
+	pub fn fusion(&self) -> Option<&Arc<FusionContext>> {
+		self.fusion.as_ref()
+	}
+
 	/// Insert a MATCHES clause entry.
 	pub fn insert(&mut self, match_ref: MatchRef, info: MatchInfo) {
 		self.matches.insert(match_ref, info);
@@ -408,9 +495,15 @@ impl MatchesContext {
 		});
 
 		match info {
-			Some(info) => {
-				Ok(Arc::new(MatchContext::new(info.idiom.clone(), info.query.clone(), table)))
-			}
+			Some(info) => Ok(Arc::new(
+				MatchContext::new(
+					info.idiom.clone(),
+					info.query.clone(),
+					info.operator.clone(),
+					table,
+				)
+				.with_fusion(self.fusion.clone()),
+			)),
 			None => {
 				// If there are no MATCHES clauses at all, provide a clear error
 				if self.matches.is_empty() {
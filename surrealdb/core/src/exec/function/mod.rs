@@ -24,7 +24,8 @@ use std::fmt::Debug;
 pub use aggregate::{Accumulator, AggregateFunction};
 use anyhow::Result;
 pub use index::{
-	IndexContext, IndexContextKind, IndexFunction, KnnContext, MatchInfo, MatchesContext,
+	FusionContext, IndexContext, IndexContextKind, IndexFunction, KnnContext, MatchContext,
+	MatchInfo, MatchesContext,
 };
 pub use method::MethodDescriptor;
 pub use projection::ProjectionFunction;
@@ -6,6 +6,7 @@
 //! - B-tree indexes (Idx, Uniq) with equality, range, and compound access patterns
 //! - Full-text search indexes (FullText) with MATCHES operator
 //! - Vector similarity indexes (Hnsw) with KNN search
+//! - Geospatial indexes (Spatial) with geometry operators and `geo::distance`
 //! - Count indexes for COUNT(*) optimization
 //!
 //! ## Architecture
@@ -3,14 +3,17 @@
 //! An [`AccessPath`] represents a specific way to retrieve records from a table,
 //! whether through a full table scan, point lookup, or index scan.
 
+use std::ops::Bound;
 use std::sync::Arc;
 
 use super::IndexCandidate;
-use crate::catalog::IndexDefinition;
+use crate::catalog::{IndexDefinition, TableStatistics};
 use crate::expr::BinaryOperator;
 use crate::expr::operator::MatchesOperator;
-use crate::expr::with::With;
+use crate::expr::with::{Fusion, With};
 use crate::idx::planner::ScanDirection;
+use crate::idx::planner::cost::{CostModel, Estimate};
+use crate::idx::spatial::SpatialRegion;
 use crate::val::{Number, Value};
 
 /// A reference to an index definition with its position in the schema.
@@ -102,6 +105,29 @@ pub enum AccessPath {
 		ef: u32,
 	},
 
+	/// Geospatial search using a SPATIAL index.
+	///
+	/// Returns the records whose geometry may intersect the region; the
+	/// original condition is still evaluated against each record.
+	SpatialSearch {
+		index_ref: IndexRef,
+		/// The bounding box of the query predicate
+		region: SpatialRegion,
+	},
+
+	/// Hybrid search fusing a KNN search with a full-text search.
+	///
+	/// Both searches run concurrently, and their results are merged into a
+	/// single ranking according to the `WITH FUSION` clause.
+	HybridSearch {
+		/// The KNN search (a `KnnSearch` path)
+		knn: Box<AccessPath>,
+		/// The full-text search (a `FullTextSearch` path)
+		fulltext: Box<AccessPath>,
+		/// How the scores of both searches are fused
+		fusion: Fusion,
+	},
+
 	/// Union of multiple index scans for OR conditions.
 	///
 	/// Each sub-path handles one branch of the OR; results are
@@ -125,6 +151,25 @@ impl AccessPath {
 			}
 		)
 	}
+
+	/// Estimate the records read by this access path, and its cost.
+	///
+	/// Returns `None` for access paths which the cost model cannot estimate,
+	/// such as full-text, KNN and spatial searches.
+	pub(crate) fn estimate(&self, cost: &CostModel) -> Option<Estimate> {
+		match self {
+			AccessPath::TableScan => Some(cost.table_scan()),
+			AccessPath::BTreeScan {
+				index_ref,
+				access,
+				..
+			} => access.estimate(cost, index_ref),
+			AccessPath::Union(paths) => {
+				paths.iter().try_fold(Estimate::default(), |acc, p| Some(acc + p.estimate(cost)?))
+			}
+			_ => None,
+		}
+	}
 }
 
 /// How to access an index.
@@ -167,6 +212,59 @@ pub enum BTreeAccess {
 		/// HNSW search expansion factor
 		ef: u32,
 	},
+
+	/// Geospatial access via a SPATIAL index.
+	Spatial {
+		/// The bounding box of the query predicate
+		region: SpatialRegion,
+	},
+}
+
+impl BTreeAccess {
+	/// Estimate the records read by this access on an index, and its cost.
+	pub(crate) fn estimate(&self, cost: &CostModel, ix: &IndexDefinition) -> Option<Estimate> {
+		match self {
+			BTreeAccess::Equality(v) => Some(cost.index_scan(ix, std::slice::from_ref(v), None)),
+			BTreeAccess::Range {
+				from,
+				to,
+			} => {
+				let from = match from {
+					Some(b) if b.inclusive => Bound::Included(&b.value),
+					Some(b) => Bound::Excluded(&b.value),
+					None => Bound::Unbounded,
+				};
+				let to = match to {
+					Some(b) if b.inclusive => Bound::Included(&b.value),
+					Some(b) => Bound::Excluded(&b.value),
+					None => Bound::Unbounded,
+				};
+				Some(cost.index_scan(ix, &[], Some((from, to))))
+			}
+			BTreeAccess::Compound {
+				prefix,
+				range,
+			} => {
+				let range = range.as_ref().and_then(|(op, v)| match op {
+					BinaryOperator::LessThan => Some((Bound::Unbounded, Bound::Excluded(v))),
+					BinaryOperator::LessThanEqual => Some((Bound::Unbounded, Bound::Included(v))),
+					BinaryOperator::MoreThan => Some((Bound::Excluded(v), Bound::Unbounded)),
+					BinaryOperator::MoreThanEqual => Some((Bound::Included(v), Bound::Unbounded)),
+					_ => None,
+				});
+				Some(cost.index_scan(ix, prefix, range))
+			}
+			BTreeAccess::FullText {
+				..
+			}
+			| BTreeAccess::Knn {
+				..
+			}
+			| BTreeAccess::Spatial {
+				..
+			} => None,
+		}
+	}
 }
 
 /// A bound for a range scan.
@@ -201,7 +299,11 @@ impl RangeBound {
 /// Selection priority:
 /// 1. WITH NOINDEX - always use table scan
 /// 2. WITH INDEX names - use specified index(es)
-/// 3. Best effort heuristics:
+/// 3. WITH FUSION - combine the best KNN and full-text candidates
+/// 4. When the table has statistics, and no candidate covers ORDER BY, the
+///    candidate with the lowest estimated cost, or a table scan when it is
+///    cheaper than every candidate
+/// 5. Best effort heuristics:
 ///    - Prefer unique index for equality (returns 1 row)
 ///    - Prefer compound index that matches more columns
 ///    - Prefer index that covers ORDER BY
@@ -210,6 +312,7 @@ pub fn select_access_path(
 	candidates: Vec<IndexCandidate>,
 	with_hints: Option<&With>,
 	direction: ScanDirection,
+	statistics: Option<&TableStatistics>,
 ) -> AccessPath {
 	// WITH NOINDEX forces table scan
 	if matches!(with_hints, Some(With::NoIndex)) {
@@ -225,11 +328,28 @@ pub fn select_access_path(
 	// If hinted index not found, fall through to best effort
 	// (could also error here, but being lenient)
 
+	// WITH FUSION - fuse a KNN search with a full-text search
+	if let Some(With::Fusion(fusion)) = with_hints
+		&& let Some(path) = select_hybrid_access_path(&candidates, fusion, direction)
+	{
+		return path;
+	}
+
 	// No candidates - table scan
 	if candidates.is_empty() {
 		return AccessPath::TableScan;
 	}
 
+	// Cost-based: pick the cheapest candidate, or a table scan. Candidates
+	// covering ORDER BY avoid a sort, which the cost model does not account
+	// for, so they are left to the heuristics.
+	if let Some(stats) = statistics
+		&& !candidates.iter().any(|c| c.covers_order)
+		&& let Some(path) = select_cheapest_access_path(&candidates, with_hints, direction, stats)
+	{
+		return path;
+	}
+
 	// Best effort: score and pick the best candidate
 	candidates
 		.into_iter()
@@ -238,6 +358,56 @@ pub fn select_access_path(
 		.unwrap_or(AccessPath::TableScan)
 }
 
+/// Pick the candidate with the lowest estimated cost, or a table scan when it
+/// is cheaper than every candidate.
+///
+/// Returns `None` when a candidate cannot be estimated.
+fn select_cheapest_access_path(
+	candidates: &[IndexCandidate],
+	with_hints: Option<&With>,
+	direction: ScanDirection,
+	stats: &TableStatistics,
+) -> Option<AccessPath> {
+	let cost = CostModel::new(stats);
+	let mut best: Option<(&IndexCandidate, Estimate)> = None;
+	for candidate in candidates {
+		let estimate = candidate.access.estimate(&cost, &candidate.index_ref)?;
+		if best.is_none_or(|(_, b)| estimate.cost < b.cost) {
+			best = Some((candidate, estimate));
+		}
+	}
+	let (candidate, estimate) = best?;
+	// An index hint is never overridden by a table scan
+	if estimate.cost > cost.table_scan().cost && !matches!(with_hints, Some(With::Index(_))) {
+		return Some(AccessPath::TableScan);
+	}
+	Some(candidate.to_access_path(direction))
+}
+
+/// Build a hybrid access path from the best KNN and full-text candidates.
+///
+/// Returns `None` unless the condition can be served by both a KNN index
+/// and a full-text index.
+pub fn select_hybrid_access_path(
+	candidates: &[IndexCandidate],
+	fusion: &Fusion,
+	direction: ScanDirection,
+) -> Option<AccessPath> {
+	let knn = candidates
+		.iter()
+		.filter(|c| matches!(c.access, BTreeAccess::Knn { .. }))
+		.max_by_key(|c| c.score())?;
+	let fulltext = candidates
+		.iter()
+		.filter(|c| matches!(c.access, BTreeAccess::FullText { .. }))
+		.max_by_key(|c| c.score())?;
+	Some(AccessPath::HybridSearch {
+		knn: Box::new(knn.to_access_path(direction)),
+		fulltext: Box::new(fulltext.to_access_path(direction)),
+		fusion: fusion.clone(),
+	})
+}
+
 /// Find a candidate matching one of the hinted index names.
 fn find_hinted_index<'a>(
 	candidates: &'a [IndexCandidate],
@@ -6,14 +6,17 @@
 use std::sync::Arc;
 
 use super::access_path::{AccessPath, BTreeAccess, IndexRef, RangeBound, select_access_path};
-use crate::catalog::{Index, IndexDefinition};
+use crate::catalog::{Index, IndexDefinition, TableStatistics};
 use crate::exec::planner::util::try_literal_to_value;
+use crate::expr::literal::Literal;
 use crate::expr::operator::{MatchesOperator, NearestNeighbor, PrefixOperator};
 use crate::expr::order::Ordering;
 use crate::expr::with::With;
-use crate::expr::{BinaryOperator, Cond, Expr, Idiom};
+use crate::expr::{BinaryOperator, Cond, Expr, Function, Idiom};
 use crate::idx::planner::ScanDirection;
-use crate::val::{Number, Value};
+use crate::idx::planner::cost::CostModel;
+use crate::idx::spatial::SpatialRegion;
+use crate::val::{Geometry, Number, Value};
 
 /// Analyzes query conditions to find matching indexes.
 pub struct IndexAnalyzer<'a> {
@@ -21,6 +24,8 @@ pub struct IndexAnalyzer<'a> {
 	pub indexes: Arc<[IndexDefinition]>,
 	/// Optional WITH INDEX/NOINDEX hints
 	pub with_hints: Option<&'a With>,
+	/// The statistics of the table, if it was analyzed
+	pub statistics: Option<Arc<TableStatistics>>,
 }
 
 impl<'a> IndexAnalyzer<'a> {
@@ -29,9 +34,30 @@ impl<'a> IndexAnalyzer<'a> {
 		Self {
 			indexes,
 			with_hints,
+			statistics: None,
 		}
 	}
 
+	/// Use the statistics of the table to choose between access paths.
+	pub fn with_statistics(mut self, statistics: Option<Arc<TableStatistics>>) -> Self {
+		self.statistics = statistics;
+		self
+	}
+
+	/// Returns `true` unless the table statistics estimate that a union of
+	/// index scans reads more than a table scan. Index hints are always
+	/// followed.
+	fn cheaper_than_table_scan(&self, path: &AccessPath) -> bool {
+		let Some(stats) = self.statistics.as_deref() else {
+			return true;
+		};
+		if matches!(self.with_hints, Some(With::Index(_))) {
+			return true;
+		}
+		let cost = CostModel::new(stats);
+		path.estimate(&cost).is_none_or(|e| e.cost <= cost.table_scan().cost)
+	}
+
 	/// Analyze conditions and ORDER BY to find candidate access paths.
 	///
 	/// Returns a list of index candidates that could be used for this query.
@@ -113,7 +139,12 @@ impl<'a> IndexAnalyzer<'a> {
 				// This branch has no index — cannot use union
 				return None;
 			}
-			let path = select_access_path(candidates, self.with_hints, direction);
+			let path = select_access_path(
+				candidates,
+				self.with_hints,
+				direction,
+				self.statistics.as_deref(),
+			);
 			if matches!(path, AccessPath::TableScan) {
 				// WITH hints rejected all candidates for this branch
 				return None;
@@ -121,7 +152,8 @@ impl<'a> IndexAnalyzer<'a> {
 			branch_paths.push(path);
 		}
 
-		Some(AccessPath::Union(branch_paths))
+		let path = AccessPath::Union(branch_paths);
+		self.cheaper_than_table_scan(&path).then_some(path)
 	}
 
 	/// Maximum number of array elements to expand for `field IN [...]`.
@@ -228,7 +260,10 @@ impl<'a> IndexAnalyzer<'a> {
 						})
 						.collect()
 				};
-				return Some(AccessPath::Union(paths));
+				let path = AccessPath::Union(paths);
+				if self.cheaper_than_table_scan(&path) {
+					return Some(path);
+				}
 			}
 		}
 
@@ -291,7 +326,10 @@ impl<'a> IndexAnalyzer<'a> {
 							direction,
 						})
 						.collect();
-					return Some(AccessPath::Union(paths));
+					let path = AccessPath::Union(paths);
+					if self.cheaper_than_table_scan(&path) {
+						return Some(path);
+					}
 				}
 			}
 		}
@@ -693,9 +731,15 @@ impl<'a> IndexAnalyzer<'a> {
 					}
 					BinaryOperator::Contain | BinaryOperator::Inside => {
 						self.try_match_containment(left, op, right, candidates);
+						self.try_match_spatial(left, op, right, candidates);
 						self.try_match_comparison(left, op, right, candidates);
 					}
+					// Geometry operators for spatial search
+					BinaryOperator::Intersects => {
+						self.try_match_spatial(left, op, right, candidates);
+					}
 					_ => {
+						self.try_match_spatial(left, op, right, candidates);
 						self.try_match_comparison(left, op, right, candidates);
 					}
 				}
@@ -990,7 +1034,7 @@ impl<'a> IndexAnalyzer<'a> {
 		}
 	}
 
-	/// Try to match a KNN expression to an HNSW index.
+	/// Try to match a KNN expression to an HNSW or IVF-PQ index.
 	fn try_match_knn(
 		&self,
 		left: &Expr,
@@ -998,11 +1042,12 @@ impl<'a> IndexAnalyzer<'a> {
 		nn: &NearestNeighbor,
 		candidates: &mut Vec<IndexCandidate>,
 	) {
-		// Approximate always uses HNSW; K(k,d) uses HNSW when distance matches
-		let (k, user_ef, required_distance) = match nn {
-			NearestNeighbor::Approximate(k, ef) => (*k, Some(*ef), None),
-			NearestNeighbor::K(k, d) => (*k, None, Some(d)),
-			_ => return,
+		// Approximate always uses a vector index; K(k,d) uses one when distance matches;
+		// KTree is only served by IVF-PQ indexes
+		let (k, user_ef, required_distance, tree_only) = match nn {
+			NearestNeighbor::Approximate(k, ef) => (*k, Some(*ef), None, false),
+			NearestNeighbor::K(k, d) => (*k, None, Some(d), false),
+			NearestNeighbor::KTree(k) => (*k, None, None, true),
 		};
 
 		// Extract idiom from left side
@@ -1034,18 +1079,23 @@ impl<'a> IndexAnalyzer<'a> {
 			_ => return,
 		};
 
-		// Find HNSW indexes that match this idiom
+		// Find vector indexes that match this idiom
 		for (idx, ix_def) in self.indexes.iter().enumerate() {
 			if ix_def.prepare_remove {
 				continue;
 			}
 
-			let Index::Hnsw(ref hnsw) = ix_def.index else {
-				continue;
+			// For IVF-PQ, the second parameter is the number of probed lists
+			let (distance, default_ef) = match ix_def.index {
+				Index::Hnsw(ref hnsw) if !tree_only => {
+					(&hnsw.distance, k.max(hnsw.ef_construction as u32))
+				}
+				Index::IvfPq(ref ivfpq) => (&ivfpq.distance, ivfpq.probes as u32),
+				_ => continue,
 			};
 
 			if let Some(d) = required_distance
-				&& *d != hnsw.distance
+				&& d != distance
 			{
 				continue;
 			}
@@ -1053,7 +1103,7 @@ impl<'a> IndexAnalyzer<'a> {
 			if let Some(first_col) = ix_def.cols.first()
 				&& idiom_matches(idiom, first_col)
 			{
-				let ef = user_ef.unwrap_or_else(|| k.max(hnsw.ef_construction as u32));
+				let ef = user_ef.unwrap_or(default_ef);
 				let index_ref = IndexRef::new(self.indexes.clone(), idx);
 				let candidate = IndexCandidate {
 					index_ref,
@@ -1069,6 +1119,97 @@ impl<'a> IndexAnalyzer<'a> {
 		}
 	}
 
+	/// Try to match a geometry predicate to a spatial index.
+	///
+	/// Handles:
+	/// - `field INSIDE geometry`, `field CONTAINS geometry` and
+	///   `field INTERSECTS geometry`, with the field on either side
+	/// - `geo::distance(field, point) < radius`, and its mirrored forms
+	///
+	/// A spatial index only narrows down the candidate records, so the
+	/// original condition must still be evaluated.
+	fn try_match_spatial(
+		&self,
+		left: &Expr,
+		op: &BinaryOperator,
+		right: &Expr,
+		candidates: &mut Vec<IndexCandidate>,
+	) {
+		let (idiom, region) = match op {
+			BinaryOperator::Inside | BinaryOperator::Contain | BinaryOperator::Intersects => {
+				match (left, right) {
+					(Expr::Idiom(idiom), Expr::Literal(Literal::Geometry(g)))
+					| (Expr::Literal(Literal::Geometry(g)), Expr::Idiom(idiom)) => {
+						let Some(region) = SpatialRegion::from_geometry(g) else {
+							return;
+						};
+						(idiom, region)
+					}
+					_ => return,
+				}
+			}
+			BinaryOperator::LessThan | BinaryOperator::LessThanEqual => {
+				let Some(m) = Self::match_geo_distance(left, right) else {
+					return;
+				};
+				m
+			}
+			BinaryOperator::MoreThan | BinaryOperator::MoreThanEqual => {
+				let Some(m) = Self::match_geo_distance(right, left) else {
+					return;
+				};
+				m
+			}
+			_ => return,
+		};
+
+		for (idx, ix_def) in self.indexes.iter().enumerate() {
+			if ix_def.prepare_remove {
+				continue;
+			}
+			if !matches!(ix_def.index, Index::Spatial) {
+				continue;
+			}
+			if let Some(first_col) = ix_def.cols.first()
+				&& idiom_matches(idiom, first_col)
+			{
+				let index_ref = IndexRef::new(self.indexes.clone(), idx);
+				candidates.push(IndexCandidate {
+					index_ref,
+					access: BTreeAccess::Spatial {
+						region: region.clone(),
+					},
+					covers_order: false,
+				});
+			}
+		}
+	}
+
+	/// Extract the field and the search region from
+	/// `geo::distance(field, point)` compared with a radius.
+	fn match_geo_distance<'e>(call: &'e Expr, radius: &Expr) -> Option<(&'e Idiom, SpatialRegion)> {
+		let Expr::FunctionCall(f) = call else {
+			return None;
+		};
+		if !matches!(&f.receiver, Function::Normal(name) if name == "geo::distance")
+			|| f.arguments.len() != 2
+		{
+			return None;
+		}
+		let (idiom, point) = match (&f.arguments[0], &f.arguments[1]) {
+			(Expr::Idiom(idiom), Expr::Literal(Literal::Geometry(Geometry::Point(p))))
+			| (Expr::Literal(Literal::Geometry(Geometry::Point(p))), Expr::Idiom(idiom)) => (idiom, *p),
+			_ => return None,
+		};
+		let Expr::Literal(lit) = radius else {
+			return None;
+		};
+		let Some(Value::Number(distance)) = try_literal_to_value(lit) else {
+			return None;
+		};
+		SpatialRegion::from_radius(point, distance.to_float()).map(|region| (idiom, region))
+	}
+
 	/// Analyze ORDER BY for index-ordered scan opportunities.
 	fn analyze_order(&self, ordering: &Ordering, candidates: &mut Vec<IndexCandidate>) {
 		let Ordering::Order(order_list) = ordering else {
@@ -1239,6 +1380,7 @@ impl IndexCandidate {
 	/// - Full scan with order: low
 	/// - Order coverage: bonus points
 	/// - FullText and KNN: high (specialized search)
+	/// - Spatial: above range scans (approximate, rechecked)
 	pub fn score(&self) -> u32 {
 		let mut score = 0u32;
 
@@ -1287,6 +1429,13 @@ impl IndexCandidate {
 				// when the query uses nearest neighbor operators
 				score += 800;
 			}
+			BTreeAccess::Spatial {
+				..
+			} => {
+				// Spatial search narrows down the candidates, but returns
+				// a superset which still has to be filtered
+				score += 600;
+			}
 		}
 
 		// Bonus for covering ORDER BY
@@ -1318,6 +1467,12 @@ impl IndexCandidate {
 				k: *k,
 				ef: *ef,
 			},
+			BTreeAccess::Spatial {
+				region,
+			} => AccessPath::SpatialSearch {
+				index_ref: self.index_ref.clone(),
+				region: region.clone(),
+			},
 			_ => AccessPath::BTreeScan {
 				index_ref: self.index_ref.clone(),
 				access: self.access.clone(),
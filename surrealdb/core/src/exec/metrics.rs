@@ -13,9 +13,13 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::task::{Context, Poll};
 
+use anyhow::Result;
 use futures::Stream;
 
+use crate::catalog::QuotaDefinition;
 use crate::exec::{FlowResult, ValueBatch, ValueBatchStream};
+use crate::kvs::quota::{QuotaSlot, Quotas};
+use crate::val::Value;
 
 // ---------------------------------------------------------------------------
 // WASM-compatible timing helper
@@ -112,6 +116,75 @@ impl OperatorMetrics {
 	}
 }
 
+// ---------------------------------------------------------------------------
+// QueryUsage
+// ---------------------------------------------------------------------------
+
+/// Per-statement resource usage, checked against the statement's quotas.
+///
+/// Created by [`Quotas::start`] when at least one quota applies to the
+/// statement, and shared with every operator through the context. Scanned
+/// records are counted by the scan operators, and buffered memory by the
+/// operators which collect their whole input (sorting, shuffling, grouping).
+/// The concurrency slots held by the statement are released on drop.
+#[derive(Debug)]
+pub(crate) struct QueryUsage {
+	/// The lowest of the quotas which apply to the statement.
+	quota: QuotaDefinition,
+	/// Total number of records scanned.
+	scanned: AtomicU64,
+	/// Estimated number of bytes buffered in memory.
+	memory: AtomicU64,
+	/// The concurrency slots held until the statement finishes.
+	_slots: Vec<QuotaSlot>,
+	/// The registry the usage is reported to.
+	quotas: Arc<Quotas>,
+}
+
+impl QueryUsage {
+	pub(crate) fn new(quota: QuotaDefinition, slots: Vec<QuotaSlot>, quotas: Arc<Quotas>) -> Self {
+		Self {
+			quota,
+			scanned: AtomicU64::new(0),
+			memory: AtomicU64::new(0),
+			_slots: slots,
+			quotas,
+		}
+	}
+
+	/// Record `count` scanned records, failing once `max_scanned` is exceeded.
+	pub(crate) fn record_scanned(&self, count: u64) -> Result<()> {
+		self.quotas.scanned(count);
+		let total = self.scanned.fetch_add(count, Ordering::Relaxed) + count;
+		match self.quota.max_scanned {
+			Some(limit) if total > limit => Err(self.quotas.exceeded("max_scanned", limit).into()),
+			_ => Ok(()),
+		}
+	}
+
+	/// Record buffered `values`, failing once `max_memory` is exceeded.
+	///
+	/// Only the estimated size is accumulated when no memory quota is set,
+	/// so this is cheap to call on every buffered batch.
+	pub(crate) fn record_memory(&self, values: &[Value]) -> Result<()> {
+		let Some(limit) = self.quota.max_memory else {
+			return Ok(());
+		};
+		let size: usize = values.iter().map(Value::estimated_size).sum();
+		let total = self.memory.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+		if total > limit {
+			return Err(self.quotas.exceeded("max_memory", limit).into());
+		}
+		Ok(())
+	}
+}
+
+impl Drop for QueryUsage {
+	fn drop(&mut self) {
+		self.quotas.finished();
+	}
+}
+
 // ---------------------------------------------------------------------------
 // MetricsStream
 // ---------------------------------------------------------------------------
@@ -96,7 +96,7 @@ pub(crate) use cardinality::CardinalityHint;
 // Re-export context types
 pub(crate) use context::{ContextLevel, DatabaseContext, ExecutionContext};
 // Re-export metrics types
-pub(crate) use metrics::{OperatorMetrics, monitor_stream};
+pub(crate) use metrics::{OperatorMetrics, QueryUsage, monitor_stream};
 // Re-export ordering types
 pub(crate) use ordering::OutputOrdering;
 // Re-export physical expression types
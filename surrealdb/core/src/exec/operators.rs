@@ -8,9 +8,11 @@ mod filter;
 mod foreach;
 mod ifelse;
 mod info;
+mod join;
 mod knn_topk;
 mod let_plan;
 mod limit;
+mod mutate;
 mod project;
 mod project_value;
 pub(crate) mod recursion;
@@ -38,11 +40,16 @@ pub use filter::Filter;
 pub use foreach::ForeachPlan;
 pub use ifelse::IfElsePlan;
 pub use info::{
-	DatabaseInfoPlan, IndexInfoPlan, NamespaceInfoPlan, RootInfoPlan, TableInfoPlan, UserInfoPlan,
+	ApiInfoPlan, DatabaseInfoPlan, IndexInfoPlan, NamespaceInfoPlan, RootInfoPlan, TableInfoPlan,
+	UserInfoPlan,
 };
+pub use join::{HashJoin, MergeJoin};
+pub(crate) use join::{JOIN_KEY_FIELD, JOIN_VALUE_FIELD};
 pub use knn_topk::KnnTopK;
 pub use let_plan::LetPlan;
 pub use limit::Limit;
+pub use mutate::Mutate;
+pub(crate) use mutate::{MutationKind, MutationSource};
 pub use project::{FieldSelection, Project, Projection, SelectProject};
 pub use project_value::ProjectValue;
 pub use recursion::RecursionOp;
@@ -50,8 +57,9 @@ pub use r#return::ReturnPlan;
 // Scan operators (storage I/O)
 pub use scan::CountScan;
 pub use scan::{
-	DynamicScan, EdgeTableSpec, FullTextScan, GraphEdgeScan, GraphScanOutput, IndexScan, KnnScan,
-	RecordIdScan, ReferenceScan, ReferenceScanOutput, TableScan, UnionIndexScan,
+	DynamicScan, EdgeTableSpec, FullTextScan, GraphEdgeScan, GraphScanOutput, HybridScan,
+	IndexScan, KnnScan, RecordIdScan, ReferenceScan, ReferenceScanOutput, SpatialScan, TableScan,
+	UnionIndexScan,
 };
 pub use sequence::SequencePlan;
 pub use sleep::SleepPlan;
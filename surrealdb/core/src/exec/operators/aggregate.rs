@@ -351,6 +351,10 @@ impl ExecOperator for Aggregate {
 					))?;
 				}
 				let batch = batch_result?;
+				// Count the buffered values against the memory quota
+				if let Some(usage) = ctx.ctx().query_usage() {
+					usage.record_memory(&batch.values)?;
+				}
 				let eval_ctx = EvalContext::from_exec_ctx(&ctx);
 
 				// Phase 1: Batch evaluate group-by key expressions across all rows
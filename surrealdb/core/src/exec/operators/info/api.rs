@@ -0,0 +1,81 @@
+//! API INFO operator - returns the OpenAPI document for the defined APIs.
+//!
+//! Implements INFO FOR API which derives an OpenAPI 3.1 document from the
+//! `DEFINE API` endpoints of the current database.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::stream;
+
+use crate::exec::context::{ContextLevel, ExecutionContext};
+use crate::exec::{
+	AccessMode, CardinalityHint, ExecOperator, FlowResult, OperatorMetrics, ValueBatch,
+	ValueBatchStream,
+};
+use crate::val::Value;
+
+/// API INFO operator.
+///
+/// Returns the OpenAPI document for the APIs defined in the current database.
+#[derive(Debug)]
+pub struct ApiInfoPlan {
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl ApiInfoPlan {
+	pub(crate) fn new() -> Self {
+		Self {
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl ExecOperator for ApiInfoPlan {
+	fn name(&self) -> &'static str {
+		"InfoApi"
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		ContextLevel::Database
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		AccessMode::ReadOnly
+	}
+
+	fn cardinality_hint(&self) -> CardinalityHint {
+		CardinalityHint::AtMostOne
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(self.metrics.as_ref())
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let ctx = ctx.clone();
+
+		Ok(Box::pin(stream::once(async move {
+			let value = execute_api_info(&ctx).await?;
+			Ok(ValueBatch {
+				values: vec![value],
+			})
+		})))
+	}
+
+	fn is_scalar(&self) -> bool {
+		true
+	}
+}
+
+async fn execute_api_info(ctx: &ExecutionContext) -> crate::expr::FlowResult<Value> {
+	let opt = ctx
+		.root()
+		.options
+		.as_ref()
+		.ok_or_else(|| anyhow::anyhow!("Options not available in execution context"))?;
+
+	Ok(crate::api::openapi::generate(ctx.ctx(), opt).await?)
+}
@@ -6,6 +6,7 @@
 //! - Analyzers
 //! - Buckets
 //! - Functions
+//! - Jobs
 //! - Modules
 //! - Models
 //! - Params
@@ -164,7 +165,9 @@ async fn execute_database_info(
 			"apis".to_string() => process(txn.all_db_apis(ns, db, version).await?),
 			"analyzers".to_string() => process(txn.all_db_analyzers(ns, db, version).await?),
 			"buckets".to_string() => process(txn.all_db_buckets(ns, db, version).await?),
+			"consumers".to_string() => crate::expr::statements::info::process_consumers(ctx.ctx(), ns, db, version, txn.all_db_consumers(ns, db, version).await?).await?,
 			"functions".to_string() => process(txn.all_db_functions(ns, db, version).await?),
+			"jobs".to_string() => crate::expr::statements::info::process_jobs(ctx.ctx(), ns, db, version, txn.all_db_jobs(ns, db, version).await?).await?,
 			"modules".to_string() => crate::expr::statements::info::process_modules(ctx.ctx(), ns, db, txn.all_db_modules(ns, db, version).await?).await,
 			"models".to_string() => process(txn.all_db_models(ns, db, version).await?),
 			"params".to_string() => process(txn.all_db_params(ns, db, version).await?),
@@ -172,6 +175,7 @@ async fn execute_database_info(
 			"users".to_string() => process(txn.all_db_users(ns, db, version).await?),
 			"configs".to_string() => process(txn.all_db_configs(ns, db, version).await?),
 			"sequences".to_string() => process(txn.all_db_sequences(ns, db, version).await?),
+			"sinks".to_string() => crate::expr::statements::info::process_sinks(ctx.ctx(), ns, db, version, txn.all_db_sinks(ns, db, version).await?).await?,
 		};
 		Ok(Value::Object(Object(object)))
 	} else {
@@ -204,6 +208,13 @@ async fn execute_database_info(
 				}
 				out.into()
 			},
+			"consumers".to_string() => {
+				let mut out = Object::default();
+				for v in txn.all_db_consumers(ns, db, version).await?.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
 			"functions".to_string() => {
 				let mut out = Object::default();
 				for v in txn.all_db_functions(ns, db, version).await?.iter() {
@@ -211,6 +222,13 @@ async fn execute_database_info(
 				}
 				out.into()
 			},
+			"jobs".to_string() => {
+				let mut out = Object::default();
+				for v in txn.all_db_jobs(ns, db, version).await?.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
 			"modules".to_string() => {
 				let mut out = Object::default();
 				for v in txn.all_db_modules(ns, db, version).await?.iter() {
@@ -260,6 +278,13 @@ async fn execute_database_info(
 				}
 				out.into()
 			},
+			"sinks".to_string() => {
+				let mut out = Object::default();
+				for v in txn.all_db_sinks(ns, db, version).await?.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
 		};
 		Ok(Value::Object(Object(object)))
 	}
@@ -9,7 +9,9 @@
 //! - `TableInfoPlan`: INFO FOR TABLE - returns table metadata
 //! - `UserInfoPlan`: INFO FOR USER - returns user information
 //! - `IndexInfoPlan`: INFO FOR INDEX - returns index building status
+//! - `ApiInfoPlan`: INFO FOR API - returns the OpenAPI document for the defined APIs
 
+mod api;
 mod database;
 mod index;
 mod namespace;
@@ -17,6 +19,7 @@ mod root;
 mod table;
 mod user;
 
+pub use api::ApiInfoPlan;
 pub use database::DatabaseInfoPlan;
 pub use index::IndexInfoPlan;
 pub use namespace::NamespaceInfoPlan;
@@ -0,0 +1,663 @@
+//! Join operators for decorrelated subqueries.
+//!
+//! A correlated subquery such as
+//! `SELECT *, (SELECT * FROM b WHERE x = $parent.y) AS bs FROM a` would
+//! otherwise run its inner plan once per outer row. The planner rewrites it
+//! into a join: the inner plan (with the correlated equality removed) runs
+//! once, and each outer row is matched with the inner rows sharing its key.
+//!
+//! A [`HashJoin`] groups the inner rows by key in memory. When both sides
+//! are already sorted by their keys, a [`MergeJoin`] streams the inner rows
+//! alongside the outer rows instead, holding only the current group.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use surrealdb_types::ToSql;
+use tracing::instrument;
+
+use crate::exec::{
+	AccessMode, CardinalityHint, ContextLevel, EvalContext, ExecOperator, ExecutionContext,
+	FlowResult, OperatorMetrics, PhysicalExpr, ValueBatch, ValueBatchStream, buffer_stream,
+	monitor_stream,
+};
+use crate::val::{Array, Object, Value};
+
+/// Field holding the join key in the rows of a decorrelated inner plan.
+pub(crate) const JOIN_KEY_FIELD: &str = "_jk";
+
+/// Field holding the projected value in the rows of a decorrelated
+/// `SELECT VALUE` inner plan.
+pub(crate) const JOIN_VALUE_FIELD: &str = "_jv";
+
+/// Joins each input row with the rows of a decorrelated subquery.
+///
+/// The build side is executed once, on the first input batch. Every build
+/// row carries its join key in the `build_key` field, which is removed from
+/// the row when it is the synthetic [`JOIN_KEY_FIELD`]. For each input
+/// row, the probe key is evaluated and the array of matching build rows is
+/// written to the `output` field, in build order, exactly as the original
+/// subquery would have returned them.
+///
+/// The build side holds at most `build_limit` rows. Beyond that, the join
+/// gives up on the hash table and evaluates the original correlated
+/// expression (`fallback`) for every input row instead.
+#[derive(Debug, Clone)]
+pub struct HashJoin {
+	/// The outer rows (probe side)
+	pub(crate) input: Arc<dyn ExecOperator>,
+	/// The decorrelated subquery (build side)
+	pub(crate) build: Arc<dyn ExecOperator>,
+	/// The field holding the join key of a build row
+	pub(crate) build_key: String,
+	/// The join key of an outer row
+	pub(crate) probe_key: Arc<dyn PhysicalExpr>,
+	/// Whether the build rows hold their output in [`JOIN_VALUE_FIELD`]
+	/// (`SELECT VALUE` subqueries) rather than being the output themselves
+	pub(crate) value_only: bool,
+	/// The field the matching build rows are written to
+	pub(crate) output: String,
+	/// The original correlated expression, evaluated per row when the build
+	/// side exceeds `build_limit`
+	pub(crate) fallback: Arc<dyn PhysicalExpr>,
+	/// The maximum number of rows held by the build side
+	pub(crate) build_limit: usize,
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl HashJoin {
+	/// Create a new HashJoin operator with fresh metrics.
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn new(
+		input: Arc<dyn ExecOperator>,
+		build: Arc<dyn ExecOperator>,
+		build_key: String,
+		probe_key: Arc<dyn PhysicalExpr>,
+		value_only: bool,
+		output: String,
+		fallback: Arc<dyn PhysicalExpr>,
+		build_limit: usize,
+	) -> Self {
+		Self {
+			input,
+			build,
+			build_key,
+			probe_key,
+			value_only,
+			output,
+			fallback,
+			build_limit,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl ExecOperator for HashJoin {
+	fn name(&self) -> &'static str {
+		"HashJoin"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		vec![
+			("output".to_string(), self.output.clone()),
+			("build_key".to_string(), self.build_key.clone()),
+			("probe_key".to_string(), self.probe_key.to_sql()),
+		]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		self.input
+			.required_context()
+			.max(self.build.required_context())
+			.max(self.probe_key.required_context())
+			.max(self.fallback.required_context())
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		self.input
+			.access_mode()
+			.combine(self.build.access_mode())
+			.combine(self.probe_key.access_mode())
+			.combine(self.fallback.access_mode())
+	}
+
+	fn cardinality_hint(&self) -> CardinalityHint {
+		self.input.cardinality_hint()
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		vec![&self.input, &self.build]
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn expressions(&self) -> Vec<(&str, &Arc<dyn PhysicalExpr>)> {
+		vec![("probe_key", &self.probe_key)]
+	}
+
+	fn output_ordering(&self) -> crate::exec::OutputOrdering {
+		self.input.output_ordering()
+	}
+
+	#[instrument(name = "HashJoin::execute", level = "trace", skip_all)]
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let mut input_stream = buffer_stream(
+			self.input.execute(ctx)?,
+			self.input.access_mode(),
+			self.input.cardinality_hint(),
+		);
+		let build = self.build.clone();
+		let build_key = self.build_key.clone();
+		let probe_key = self.probe_key.clone();
+		let value_only = self.value_only;
+		let output = self.output.clone();
+		let fallback = self.fallback.clone();
+		let build_limit = self.build_limit;
+		let ctx = ctx.clone();
+
+		let stream: ValueBatchStream = Box::pin(async_stream::try_stream! {
+			// `None` until the first rows arrive, then `Some(None)` if the
+			// build side was too large to be held in memory
+			let mut table: Option<Option<JoinTable>> = None;
+			while let Some(batch_result) = input_stream.next().await {
+				let batch: ValueBatch = batch_result?;
+				if batch.values.is_empty() {
+					continue;
+				}
+
+				// Build the hash table once, when the first rows arrive
+				if table.is_none() {
+					let mut build_stream = build.execute(&ctx)?;
+					let mut rows = Vec::new();
+					let mut overflow = false;
+					while let Some(build_batch) = build_stream.next().await {
+						rows.extend(build_batch?.values);
+						if rows.len() > build_limit {
+							overflow = true;
+							break;
+						}
+					}
+					table = Some(if overflow {
+						None
+					} else {
+						Some(JoinTable::new(rows, &build_key, value_only))
+					});
+				}
+
+				// Probe the hash table with the key of every row, or evaluate
+				// the subquery for every row if there is no hash table
+				let eval_ctx = EvalContext::from_exec_ctx(&ctx);
+				let matches = match table.as_ref() {
+					Some(Some(table)) => probe_key
+						.evaluate_batch(eval_ctx, &batch.values)
+						.await?
+						.iter()
+						.map(|key| table.probe(key))
+						.collect(),
+					_ => fallback.evaluate_batch(eval_ctx, &batch.values).await?,
+				};
+				let mut values = Vec::with_capacity(batch.values.len());
+				for (value, matches) in batch.values.into_iter().zip(matches) {
+					let mut obj = into_object(&ctx, value).await?;
+					obj.insert(output.clone(), matches);
+					values.push(Value::Object(obj));
+				}
+				yield ValueBatch { values };
+			}
+		});
+
+		Ok(monitor_stream(stream, "HashJoin", &self.metrics))
+	}
+}
+
+/// Joins each input row with the rows of a decorrelated subquery, when both
+/// are sorted by their join keys.
+///
+/// The input is sorted by record id, and the build side is sorted by its
+/// join key through an index, so both are read once, side by side, and only
+/// the build rows of the current key are held in memory. Build rows whose
+/// key is not a record id can never match, and are skipped. Within a key,
+/// the build rows keep their index order, which is their record id order,
+/// exactly as the original subquery would have returned them.
+#[derive(Debug, Clone)]
+pub struct MergeJoin {
+	/// The outer rows, sorted by record id
+	pub(crate) input: Arc<dyn ExecOperator>,
+	/// The decorrelated subquery, sorted by its join key
+	pub(crate) build: Arc<dyn ExecOperator>,
+	/// The field holding the join key of a build row
+	pub(crate) build_key: String,
+	/// The join key of an outer row
+	pub(crate) probe_key: Arc<dyn PhysicalExpr>,
+	/// Whether the build rows hold their output in [`JOIN_VALUE_FIELD`]
+	pub(crate) value_only: bool,
+	/// The field the matching build rows are written to
+	pub(crate) output: String,
+	/// The original correlated expression, evaluated for the rows whose key
+	/// is not a record id
+	pub(crate) fallback: Arc<dyn PhysicalExpr>,
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl MergeJoin {
+	/// Create a new MergeJoin operator with fresh metrics.
+	pub(crate) fn new(
+		input: Arc<dyn ExecOperator>,
+		build: Arc<dyn ExecOperator>,
+		build_key: String,
+		probe_key: Arc<dyn PhysicalExpr>,
+		value_only: bool,
+		output: String,
+		fallback: Arc<dyn PhysicalExpr>,
+	) -> Self {
+		Self {
+			input,
+			build,
+			build_key,
+			probe_key,
+			value_only,
+			output,
+			fallback,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl ExecOperator for MergeJoin {
+	fn name(&self) -> &'static str {
+		"MergeJoin"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		vec![
+			("output".to_string(), self.output.clone()),
+			("build_key".to_string(), self.build_key.clone()),
+			("probe_key".to_string(), self.probe_key.to_sql()),
+		]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		self.input
+			.required_context()
+			.max(self.build.required_context())
+			.max(self.probe_key.required_context())
+			.max(self.fallback.required_context())
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		self.input
+			.access_mode()
+			.combine(self.build.access_mode())
+			.combine(self.probe_key.access_mode())
+			.combine(self.fallback.access_mode())
+	}
+
+	fn cardinality_hint(&self) -> CardinalityHint {
+		self.input.cardinality_hint()
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		vec![&self.input, &self.build]
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn expressions(&self) -> Vec<(&str, &Arc<dyn PhysicalExpr>)> {
+		vec![("probe_key", &self.probe_key)]
+	}
+
+	fn output_ordering(&self) -> crate::exec::OutputOrdering {
+		self.input.output_ordering()
+	}
+
+	#[instrument(name = "MergeJoin::execute", level = "trace", skip_all)]
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let mut input_stream = buffer_stream(
+			self.input.execute(ctx)?,
+			self.input.access_mode(),
+			self.input.cardinality_hint(),
+		);
+		let build = self.build.clone();
+		let build_key = self.build_key.clone();
+		let probe_key = self.probe_key.clone();
+		let value_only = self.value_only;
+		let output = self.output.clone();
+		let fallback = self.fallback.clone();
+		let ctx = ctx.clone();
+
+		let stream: ValueBatchStream = Box::pin(async_stream::try_stream! {
+			let mut build_stream: Option<ValueBatchStream> = None;
+			let mut cursor = MergeCursor::default();
+			while let Some(batch_result) = input_stream.next().await {
+				let batch: ValueBatch = batch_result?;
+				if batch.values.is_empty() {
+					continue;
+				}
+				// Start the build side when the first rows arrive
+				if build_stream.is_none() {
+					build_stream = Some(build.execute(&ctx)?);
+				}
+				let Some(build_stream) = build_stream.as_mut() else {
+					continue;
+				};
+
+				let keys =
+					probe_key.evaluate_batch(EvalContext::from_exec_ctx(&ctx), &batch.values).await?;
+				let eval_ctx = EvalContext::from_exec_ctx(&ctx);
+				let mut values = Vec::with_capacity(batch.values.len());
+				for (value, key) in batch.values.into_iter().zip(keys) {
+					let matches = if matches!(key, Value::RecordId(_)) {
+						// Read the build side until it passes the key
+						while cursor.needs_rows(&key) {
+							match build_stream.next().await {
+								Some(build_batch) => {
+									cursor.push(build_batch?.values, &build_key, value_only)
+								}
+								None => cursor.finish(),
+							}
+						}
+						cursor.matches(key)
+					} else {
+						fallback.evaluate(eval_ctx.with_value_and_doc(&value)).await?
+					};
+					let mut obj = into_object(&ctx, value).await?;
+					obj.insert(output.clone(), matches);
+					values.push(Value::Object(obj));
+				}
+				yield ValueBatch { values };
+			}
+		});
+
+		Ok(monitor_stream(stream, "MergeJoin", &self.metrics))
+	}
+}
+
+/// The build rows of a merge join, read in key order.
+#[derive(Default)]
+struct MergeCursor {
+	/// The build rows read but not matched yet, as (key, output) pairs
+	rows: VecDeque<(Value, Value)>,
+	/// Whether the build side has no more rows
+	exhausted: bool,
+	/// The last probe key with its matches, reused when a key repeats
+	last: Option<(Value, Value)>,
+}
+
+impl MergeCursor {
+	/// Queue a batch of build rows, dropping the ones which can never match.
+	fn push(&mut self, rows: Vec<Value>, key_field: &str, value_only: bool) {
+		for row in rows {
+			let (key, value) = split_build_row(row, key_field, value_only);
+			if matches!(key, Value::RecordId(_)) {
+				self.rows.push_back((key, value));
+			}
+		}
+	}
+
+	/// Mark the build side as exhausted.
+	fn finish(&mut self) {
+		self.exhausted = true;
+	}
+
+	/// Whether more build rows must be read before matching `key`.
+	fn needs_rows(&self, key: &Value) -> bool {
+		if self.exhausted || self.last.as_ref().is_some_and(|(k, _)| k == key) {
+			return false;
+		}
+		// Every row of the key has been read once a greater key is queued
+		self.rows.back().is_none_or(|(k, _)| k <= key)
+	}
+
+	/// Take the build rows matching `key`. Probe keys must be increasing.
+	fn matches(&mut self, key: Value) -> Value {
+		if let Some((k, matches)) = &self.last
+			&& *k == key
+		{
+			return matches.clone();
+		}
+		while self.rows.front().is_some_and(|(k, _)| *k < key) {
+			self.rows.pop_front();
+		}
+		let mut matches = Vec::new();
+		while self.rows.front().is_some_and(|(k, _)| *k == key) {
+			if let Some((_, value)) = self.rows.pop_front() {
+				matches.push(value);
+			}
+		}
+		let matches = Value::Array(Array(matches));
+		self.last = Some((key, matches.clone()));
+		matches
+	}
+}
+
+/// Turn an input row into the object the joined values are written to.
+async fn into_object(ctx: &ExecutionContext, value: Value) -> FlowResult<Object> {
+	Ok(match value {
+		Value::Object(obj) => obj,
+		Value::Geometry(geo) => geo.as_object(),
+		Value::RecordId(rid) => match super::fetch::fetch_record(ctx, &rid).await? {
+			Value::Object(obj) => obj,
+			_ => Object::default(),
+		},
+		_ => Object::default(),
+	})
+}
+
+/// Split a build row into its join key and its output value.
+///
+/// The synthetic [`JOIN_KEY_FIELD`] is removed from the row, while a key read
+/// from a regular field is left in place.
+fn split_build_row(row: Value, key_field: &str, value_only: bool) -> (Value, Value) {
+	match row {
+		Value::Object(mut obj) => {
+			let key = if key_field == JOIN_KEY_FIELD {
+				obj.remove(JOIN_KEY_FIELD)
+			} else {
+				obj.get(key_field).cloned()
+			}
+			.unwrap_or(Value::None);
+			let value = if value_only {
+				obj.remove(JOIN_VALUE_FIELD).unwrap_or(Value::None)
+			} else {
+				Value::Object(obj)
+			};
+			(key, value)
+		}
+		other => (Value::None, other),
+	}
+}
+
+/// The build side of a hash join, grouped by join key.
+struct JoinTable {
+	/// The output value of every build row, in build order
+	values: Vec<Value>,
+	/// The join key of every build row, in build order
+	keys: Vec<Value>,
+	/// Build rows by join key, for keys compared by value
+	index: HashMap<Value, Vec<usize>>,
+	/// Build rows whose join key is a regex, which also equals the strings it matches
+	patterns: Vec<usize>,
+}
+
+impl JoinTable {
+	fn new(rows: Vec<Value>, key_field: &str, value_only: bool) -> Self {
+		let mut values = Vec::with_capacity(rows.len());
+		let mut keys = Vec::with_capacity(rows.len());
+		let mut index: HashMap<Value, Vec<usize>> = HashMap::new();
+		let mut patterns = Vec::new();
+		for (i, row) in rows.into_iter().enumerate() {
+			let (key, value) = split_build_row(row, key_field, value_only);
+			if matches!(key, Value::Regex(_)) {
+				patterns.push(i);
+			} else {
+				index.entry(key.clone()).or_default().push(i);
+			}
+			values.push(value);
+			keys.push(key);
+		}
+		Self {
+			values,
+			keys,
+			index,
+			patterns,
+		}
+	}
+
+	/// Collect the output values of the build rows whose key equals `key`.
+	fn probe(&self, key: &Value) -> Value {
+		let rows: Vec<usize> = if matches!(key, Value::Regex(_)) {
+			// A regex probe key matches build keys by pattern
+			(0..self.keys.len()).filter(|&i| self.keys[i].equal(key)).collect()
+		} else {
+			let mut rows = match self.index.get(key) {
+				Some(rows) => rows.clone(),
+				None => Vec::new(),
+			};
+			if matches!(key, Value::String(_)) && !self.patterns.is_empty() {
+				rows.extend(self.patterns.iter().copied().filter(|&i| self.keys[i].equal(key)));
+				rows.sort_unstable();
+			}
+			rows
+		};
+		Value::Array(Array(rows.into_iter().map(|i| self.values[i].clone()).collect()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::exec::operators::SourceExpr;
+	use crate::exec::physical_expr::Literal;
+	use crate::val::{Number, RecordId, Regex};
+
+	fn int(v: i64) -> Value {
+		Value::Number(Number::Int(v))
+	}
+
+	fn row(key: Value, value: Value) -> Value {
+		Value::Object(Object::from(vec![
+			(JOIN_KEY_FIELD.to_string(), key),
+			(JOIN_VALUE_FIELD.to_string(), value),
+		]))
+	}
+
+	#[test]
+	fn test_hash_join_attrs() {
+		let literal: Arc<dyn PhysicalExpr> = Arc::new(Literal(int(1)));
+		let join = HashJoin::new(
+			Arc::new(SourceExpr::new(literal.clone())),
+			Arc::new(SourceExpr::new(literal.clone())),
+			JOIN_KEY_FIELD.to_string(),
+			literal.clone(),
+			false,
+			"_j0".to_string(),
+			literal,
+			100,
+		);
+
+		assert_eq!(join.name(), "HashJoin");
+		assert_eq!(join.children().len(), 2);
+		assert_eq!(join.attrs()[0], ("output".to_string(), "_j0".to_string()));
+	}
+
+	#[test]
+	fn test_join_table_probe_keeps_build_order() {
+		let table = JoinTable::new(
+			vec![row(int(1), int(10)), row(int(2), int(20)), row(int(1), int(30))],
+			JOIN_KEY_FIELD,
+			true,
+		);
+
+		assert_eq!(table.probe(&int(1)), Value::Array(Array(vec![int(10), int(30)])));
+		assert_eq!(table.probe(&int(2)), Value::Array(Array(vec![int(20)])));
+		assert_eq!(table.probe(&int(3)), Value::Array(Array(vec![])));
+		// Numbers compare equal across their representations
+		assert_eq!(
+			table.probe(&Value::Number(Number::Float(1.0))),
+			Value::Array(Array(vec![int(10), int(30)]))
+		);
+	}
+
+	#[test]
+	fn test_join_table_probe_regex() {
+		let regex = Value::Regex("^a".parse::<Regex>().unwrap());
+		let table = JoinTable::new(
+			vec![
+				row(Value::String("abc".into()), int(1)),
+				row(regex.clone(), int(2)),
+				row(Value::String("bcd".into()), int(3)),
+			],
+			JOIN_KEY_FIELD,
+			true,
+		);
+
+		assert_eq!(
+			table.probe(&Value::String("abc".into())),
+			Value::Array(Array(vec![int(1), int(2)]))
+		);
+		assert_eq!(table.probe(&regex), Value::Array(Array(vec![int(1), int(2)])));
+	}
+
+	#[test]
+	fn test_join_table_strips_key() {
+		let table = JoinTable::new(vec![row(int(1), int(10))], JOIN_KEY_FIELD, false);
+
+		let Value::Array(Array(rows)) = table.probe(&int(1)) else {
+			panic!("expected an array");
+		};
+		let Value::Object(obj) = &rows[0] else {
+			panic!("expected an object");
+		};
+		assert!(obj.get(JOIN_KEY_FIELD).is_none());
+		assert_eq!(obj.get(JOIN_VALUE_FIELD), Some(&int(10)));
+
+		// A key read from a regular field is left in place
+		let table = JoinTable::new(vec![row(int(1), int(10))], JOIN_VALUE_FIELD, false);
+		let Value::Array(Array(rows)) = table.probe(&int(10)) else {
+			panic!("expected an array");
+		};
+		assert_eq!(rows.len(), 1);
+		let Value::Object(obj) = &rows[0] else {
+			panic!("expected an object");
+		};
+		assert_eq!(obj.get(JOIN_VALUE_FIELD), Some(&int(10)));
+	}
+
+	#[test]
+	fn test_merge_cursor_matches_in_key_order() {
+		let rid = |id: i64| Value::RecordId(RecordId::new("post".into(), id));
+		let mut cursor = MergeCursor::default();
+		assert!(cursor.needs_rows(&rid(1)));
+		cursor.push(
+			vec![row(rid(1), int(10)), row(int(7), int(0)), row(rid(1), int(11))],
+			JOIN_KEY_FIELD,
+			true,
+		);
+		// The rows of a key may continue in the next batch
+		assert!(cursor.needs_rows(&rid(1)));
+		cursor.push(vec![row(rid(3), int(30)), row(rid(4), int(40))], JOIN_KEY_FIELD, true);
+		assert!(!cursor.needs_rows(&rid(1)));
+		assert_eq!(cursor.matches(rid(1)), Value::Array(Array(vec![int(10), int(11)])));
+		// A repeated key reuses its matches
+		assert!(!cursor.needs_rows(&rid(1)));
+		assert_eq!(cursor.matches(rid(1)), Value::Array(Array(vec![int(10), int(11)])));
+		assert_eq!(cursor.matches(rid(2)), Value::Array(Array(vec![])));
+		assert_eq!(cursor.matches(rid(3)), Value::Array(Array(vec![int(30)])));
+		// The last key needs the end of the build side
+		assert!(cursor.needs_rows(&rid(4)));
+		cursor.finish();
+		assert!(!cursor.needs_rows(&rid(4)));
+		assert_eq!(cursor.matches(rid(4)), Value::Array(Array(vec![int(40)])));
+		assert_eq!(cursor.matches(rid(5)), Value::Array(Array(vec![])));
+	}
+}
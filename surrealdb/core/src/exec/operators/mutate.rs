@@ -0,0 +1,472 @@
+//! Mutation sink operator for write statements.
+//!
+//! UPDATE and DELETE statements over tables are fed by the same scan
+//! operators used for SELECT (table scans, B-tree and spatial index scans,
+//! index unions), so write statements share the access paths picked by index
+//! analysis. These scans only output record ids, and the sink reads each
+//! batch of records once before mutating them.
+//!
+//! Every other target (record ids, tables to create records in, the values of
+//! an INSERT statement, the relations of a RELATE statement) is prepared by
+//! the statement when the sink is executed, and each targeted record is then
+//! processed in turn.
+//!
+//! Every record is passed through the document pipeline, which checks the
+//! WHERE clause and table permissions, writes the record and its indexes,
+//! and runs events, views, live queries and changefeeds.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::StreamExt;
+use reblessive::TreeStack;
+
+use super::scan::common::BATCH_SIZE;
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{DatabaseDefinition, NamespaceDefinition};
+use crate::ctx::FrozenContext;
+use crate::dbs::{Iterable, Iterator, Operable, Options, Processable, Statement};
+use crate::doc::{Document, DocumentContext, IgnoreError, NsDbCtx, NsDbTbCtx};
+use crate::err::Error;
+use crate::exec::plan_or_compute::get_legacy_context;
+use crate::exec::{
+	AccessMode, ContextLevel, ExecOperator, ExecutionContext, FlowResult, OperatorMetrics,
+	ValueBatch, ValueBatchStream, monitor_stream,
+};
+use crate::expr::statements::{
+	CreateStatement, DeleteStatement, InsertStatement, RelateStatement, UpdateStatement,
+	UpsertStatement,
+};
+use crate::expr::{ControlFlow, ControlFlowExt, Expr};
+use crate::idx::planner::{QueryPlanner, RecordStrategy, StatementContext};
+use crate::kvs::{CachePolicy, Transaction};
+use crate::val::{TableName, Value};
+
+/// The write statement applied by a [`Mutate`] operator.
+#[derive(Debug, Clone)]
+pub(crate) enum MutationKind {
+	Create(Arc<CreateStatement>),
+	Update(Arc<UpdateStatement>),
+	Upsert(Arc<UpsertStatement>),
+	Delete(Arc<DeleteStatement>),
+	Insert(Arc<InsertStatement>),
+	Relate(Arc<RelateStatement>),
+}
+
+impl MutationKind {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Create(_) => "CREATE",
+			Self::Update(_) => "UPDATE",
+			Self::Upsert(_) => "UPSERT",
+			Self::Delete(_) => "DELETE",
+			Self::Insert(_) => "INSERT",
+			Self::Relate(_) => "RELATE",
+		}
+	}
+
+	fn statement(&self) -> Statement<'_> {
+		match self {
+			Self::Create(stmt) => Statement::from(stmt.as_ref()),
+			Self::Update(stmt) => Statement::from(stmt.as_ref()),
+			Self::Upsert(stmt) => Statement::from(stmt.as_ref()),
+			Self::Delete(stmt) => Statement::from(stmt.as_ref()),
+			Self::Insert(stmt) => Statement::from(stmt.as_ref()),
+			Self::Relate(stmt) => Statement::from(stmt.as_ref()),
+		}
+	}
+
+	/// The error raised when a statement target is not a table or a record.
+	fn invalid_target(&self, value: String) -> Error {
+		match self {
+			Self::Create(_) => Error::CreateStatement {
+				value,
+			},
+			Self::Update(_) => Error::UpdateStatement {
+				value,
+			},
+			Self::Upsert(_) => Error::UpsertStatement {
+				value,
+			},
+			Self::Delete(_) => Error::DeleteStatement {
+				value,
+			},
+			Self::Insert(_) | Self::Relate(_) => Error::InvalidStatementTarget {
+				value,
+			},
+		}
+	}
+}
+
+/// The records fed to a [`Mutate`] operator.
+#[derive(Debug, Clone)]
+pub(crate) enum MutationSource {
+	/// The records of a table, produced by a scan which outputs their ids
+	Scan {
+		/// The table being mutated
+		table: TableName,
+		/// The scan producing the candidate record ids
+		input: Arc<dyn ExecOperator>,
+	},
+	/// A statement target, evaluated when the operator is executed
+	Target(Expr),
+	/// The records of an INSERT statement, or the relations of a RELATE
+	/// statement
+	Statement,
+}
+
+/// Mutation sink operator.
+///
+/// Consumes the records produced by each source, applies the write
+/// statement to them in batches, and yields the statement output for every
+/// record which was mutated.
+#[derive(Debug)]
+pub struct Mutate {
+	pub(crate) kind: MutationKind,
+	pub(crate) sources: Vec<MutationSource>,
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl Mutate {
+	pub(crate) fn new(kind: MutationKind, sources: Vec<MutationSource>) -> Self {
+		Self {
+			kind,
+			sources,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl ExecOperator for Mutate {
+	fn name(&self) -> &'static str {
+		"Mutate"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		let mut attrs = vec![("statement".to_string(), self.kind.as_str().to_string())];
+		let tables = self
+			.sources
+			.iter()
+			.filter_map(|s| match s {
+				MutationSource::Scan {
+					table,
+					..
+				} => Some(table.to_string()),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		if !tables.is_empty() {
+			attrs.push(("table".to_string(), tables.join(", ")));
+		}
+		attrs
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		ContextLevel::Database
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		AccessMode::ReadWrite
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		self.sources
+			.iter()
+			.filter_map(|s| match s {
+				MutationSource::Scan {
+					input,
+					..
+				} => Some(input),
+				_ => None,
+			})
+			.collect()
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		// Start every scan up front, so that any error is raised before
+		// the first record is written
+		let mut inputs = Vec::with_capacity(self.sources.len());
+		for source in self.sources.iter() {
+			let input = match source {
+				MutationSource::Scan {
+					input,
+					..
+				} => Some(input.execute(ctx)?),
+				_ => None,
+			};
+			inputs.push((source.clone(), input));
+		}
+
+		let kind = self.kind.clone();
+		let ctx = ctx.clone();
+
+		let stream = async_stream::try_stream! {
+			let db_ctx = ctx.database().context("Mutate requires database context")?;
+			let ns = Arc::clone(&db_ctx.ns_ctx.ns);
+			let db = Arc::clone(&db_ctx.db);
+			let txn = ctx.txn();
+			let (opt, frozen) =
+				get_legacy_context(&ctx).context("Mutate requires legacy options")?;
+			// Prevent deep recursion, as the document iterator does
+			let opt = &opt.dive(4)?;
+			let stm = kind.statement();
+			let mut stack = TreeStack::new();
+
+			// Prepare the statement targets before any record is written,
+			// as the compute path does
+			let mut planner = QueryPlanner::new();
+			let mut prepared = Vec::with_capacity(inputs.len());
+			let mut guaranteed = None;
+			{
+				let stm_ctx = StatementContext::new(&frozen, opt, &stm)?;
+				let doc_ctx = NsDbCtx {
+					ns: Arc::clone(&ns),
+					db: Arc::clone(&db),
+				};
+				for (source, _) in inputs.iter() {
+					let mut iterator = Iterator::new();
+					match source {
+						MutationSource::Scan { .. } => {}
+						MutationSource::Target(expr) => {
+							stack
+								.enter(|stk| {
+									iterator.prepare(
+										stk, &frozen, opt, None, &mut planner, &stm_ctx, &doc_ctx,
+										expr,
+									)
+								})
+								.finish()
+								.await
+								.map_err(|e| rename_target_error(&kind, e))?;
+						}
+						MutationSource::Statement => {
+							stack
+								.enter(|stk| prepare_statement(stk, &kind, &frozen, opt, &mut iterator))
+								.finish()
+								.await?;
+						}
+					}
+					let (entries, record) = iterator.take_entries();
+					if record.is_some() {
+						guaranteed = record;
+					}
+					prepared.push(entries);
+				}
+			}
+			// Attach the query planner, used by any prepared table scan
+			let frozen = stm.setup_query_planner(planner, Cow::Owned(frozen)).into_owned();
+
+			let mut produced = false;
+			let mut values = Vec::new();
+			for ((source, input), entries) in inputs.into_iter().zip(prepared) {
+				if let (MutationSource::Scan { table, .. }, Some(mut input)) = (source, input) {
+					let doc_ctx = table_context(&txn, &ns, &db, &table, opt).await?;
+					while let Some(batch) = input.next().await {
+						let batch = batch?;
+						let mut rids = Vec::with_capacity(batch.values.len());
+						for value in batch.values {
+							let Some(Value::RecordId(rid)) =
+								value.as_object().and_then(|o| o.get("id"))
+							else {
+								Err(ControlFlow::Err(anyhow::Error::new(Error::Internal(format!(
+									"Expected a record id from the scan of table '{table}'"
+								)))))?
+							};
+							rids.push(rid.clone());
+						}
+						// Read the records of the batch once, keeping their metadata
+						let records = txn
+							.getm_records(
+								ns.namespace_id,
+								db.database_id,
+								&rids,
+								opt.version,
+								CachePolicy::ReadOnly,
+							)
+							.await
+							.context("Failed to fetch records")?;
+						for (rid, record) in rids.into_iter().zip(records) {
+							let pro = Processable {
+								doc_ctx: DocumentContext::NsDbTbCtx(doc_ctx.clone()),
+								record_strategy: RecordStrategy::KeysAndValues,
+								generate: None,
+								rid: Some(Arc::new(rid)),
+								ir: None,
+								val: Operable::Value(record),
+							};
+							let res = stack
+								.enter(|stk| Document::process(stk, &frozen, opt, &stm, pro))
+								.finish()
+								.await;
+							if let Some(v) = accept(res)? {
+								produced = true;
+								values.push(v);
+							}
+						}
+						if values.len() >= BATCH_SIZE {
+							yield ValueBatch { values: std::mem::take(&mut values) };
+						}
+					}
+					continue;
+				}
+				// Consecutive table, range, index and graph edge scans are
+				// collected by a single document iterator, which removes
+				// any records matched by more than one index
+				let mut scans = Iterator::new();
+				let mut pending = false;
+				for iterable in entries {
+					if !iterable.is_record_target() {
+						scans.ingest(iterable);
+						pending = true;
+						continue;
+					}
+					if pending {
+						let out = collect_scans(&mut stack, &mut scans, &frozen, opt, &stm).await?;
+						produced |= !out.is_empty();
+						values.extend(out);
+						pending = false;
+					}
+					if let Some(v) = process_record(&mut stack, iterable, &txn, &frozen, opt, &stm).await? {
+						produced = true;
+						values.push(v);
+					}
+					if values.len() >= BATCH_SIZE {
+						yield ValueBatch { values: std::mem::take(&mut values) };
+					}
+				}
+				if pending {
+					let out = collect_scans(&mut stack, &mut scans, &frozen, opt, &stm).await?;
+					produced |= !out.is_empty();
+					values.extend(out);
+				}
+				if values.len() >= BATCH_SIZE {
+					yield ValueBatch { values: std::mem::take(&mut values) };
+				}
+			}
+			// An UPSERT with a WHERE clause creates a record when no
+			// record was matched
+			if !produced && !opt.import && let Some(record) = guaranteed {
+				if let Some(v) = process_record(&mut stack, record, &txn, &frozen, opt, &stm).await? {
+					values.push(v);
+				}
+			}
+			if !values.is_empty() {
+				yield ValueBatch { values };
+			}
+		};
+
+		Ok(monitor_stream(Box::pin(stream), "Mutate", &self.metrics))
+	}
+}
+
+/// Report an invalid statement target with the error of the statement.
+fn rename_target_error(kind: &MutationKind, e: anyhow::Error) -> anyhow::Error {
+	match e.downcast::<Error>() {
+		Ok(Error::InvalidStatementTarget {
+			value,
+		}) => anyhow::Error::new(kind.invalid_target(value)),
+		Ok(e) => anyhow::Error::new(e),
+		Err(e) => e,
+	}
+}
+
+/// Prepare the records of an INSERT statement, or the relations of a RELATE
+/// statement.
+async fn prepare_statement(
+	stk: &mut reblessive::tree::Stk,
+	kind: &MutationKind,
+	ctx: &FrozenContext,
+	opt: &Options,
+	iterator: &mut Iterator,
+) -> Result<()> {
+	match kind {
+		MutationKind::Insert(stmt) => stmt.prepare(stk, ctx, opt, None, iterator).await,
+		MutationKind::Relate(stmt) => stmt.prepare(stk, ctx, opt, None, iterator).await,
+		_ => Err(anyhow::Error::new(Error::Internal(format!(
+			"{} statements have no statement source",
+			kind.as_str()
+		)))),
+	}
+}
+
+/// Fetch the definitions needed to process the records of a table.
+async fn table_context(
+	txn: &Transaction,
+	ns: &Arc<NamespaceDefinition>,
+	db: &Arc<DatabaseDefinition>,
+	table: &TableName,
+	opt: &Options,
+) -> Result<NsDbTbCtx, ControlFlow> {
+	// The table must exist for UPDATE and DELETE statements
+	let tb = txn
+		.get_tb(ns.namespace_id, db.database_id, table, opt.version)
+		.await
+		.context("Failed to get table")?
+		.ok_or_else(|| {
+			ControlFlow::Err(anyhow::Error::new(Error::TbNotFound {
+				name: table.clone(),
+			}))
+		})?;
+	let fields = txn
+		.all_tb_fields(ns.namespace_id, db.database_id, table, opt.version)
+		.await
+		.context("Failed to get table fields")?;
+	Ok(NsDbTbCtx {
+		ns: Arc::clone(ns),
+		db: Arc::clone(db),
+		tb,
+		fields,
+	})
+}
+
+/// Process a single targeted record through the document pipeline.
+async fn process_record(
+	stack: &mut TreeStack,
+	iterable: Iterable,
+	txn: &Transaction,
+	ctx: &FrozenContext,
+	opt: &Options,
+	stm: &Statement<'_>,
+) -> Result<Option<Value>, ControlFlow> {
+	let Some(pro) = iterable.prepare_record_target(opt, txn).await? else {
+		return Ok(None);
+	};
+	let res = stack.enter(|stk| Document::process(stk, ctx, opt, stm, pro)).finish().await;
+	accept(res)
+}
+
+/// Collect the records of the scans ingested into a document iterator.
+async fn collect_scans(
+	stack: &mut TreeStack,
+	scans: &mut Iterator,
+	ctx: &FrozenContext,
+	opt: &Options,
+	stm: &Statement<'_>,
+) -> Result<Vec<Value>, ControlFlow> {
+	let out = stack
+		.enter(|stk| scans.output(stk, ctx, opt, stm, RecordStrategy::KeysAndValues))
+		.finish()
+		.await?;
+	*scans = Iterator::new();
+	match out {
+		Value::Array(a) => Ok(a.0),
+		v => Ok(vec![v]),
+	}
+}
+
+/// Keep the output of a processed record, skipping ignored records.
+fn accept(res: Result<Value, IgnoreError>) -> Result<Option<Value>, ControlFlow> {
+	match res {
+		Ok(v) => Ok(Some(v)),
+		Err(IgnoreError::Ignore) => Ok(None),
+		Err(IgnoreError::Error(e)) => Err(ControlFlow::Err(e)),
+	}
+}
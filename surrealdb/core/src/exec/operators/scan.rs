@@ -9,6 +9,7 @@ mod count;
 mod dynamic;
 mod fulltext;
 mod graph;
+mod hybrid;
 mod index;
 pub(crate) mod index_count;
 mod knn;
@@ -16,6 +17,7 @@ pub(crate) mod pipeline;
 mod record_id;
 mod reference;
 pub(crate) mod resolved;
+mod spatial;
 mod table;
 mod union_index;
 
@@ -23,10 +25,12 @@ pub use count::CountScan;
 pub use dynamic::DynamicScan;
 pub use fulltext::FullTextScan;
 pub use graph::{EdgeTableSpec, GraphEdgeScan, GraphScanOutput};
+pub use hybrid::HybridScan;
 pub use index::IndexScan;
 pub use knn::KnnScan;
 pub(crate) use pipeline::determine_scan_direction;
 pub use record_id::RecordIdScan;
 pub use reference::{ReferenceScan, ReferenceScanOutput};
+pub use spatial::SpatialScan;
 pub use table::TableScan;
 pub use union_index::UnionIndexScan;
@@ -9,7 +9,7 @@ use crate::catalog::{DatabaseId, NamespaceId};
 use crate::exec::{ControlFlowExt, EvalContext, ExecutionContext, PhysicalExpr};
 use crate::expr::ControlFlow;
 use crate::kvs::{CachePolicy, Transaction};
-use crate::val::{RecordId, RecordIdKey, Value};
+use crate::val::{Object, RecordId, RecordIdKey, Value};
 
 /// Default batch size for collecting records before yielding downstream.
 pub(crate) const BATCH_SIZE: usize = 1000;
@@ -193,3 +193,49 @@ pub(crate) async fn fetch_and_filter_records_batch(
 	}
 	Ok(values)
 }
+
+/// Wrap a [`RecordId`] as an `{ id }` object.
+///
+/// Scans which only output record ids yield these objects, so that
+/// operators de-duplicating on the `id` field handle them unchanged.
+pub(crate) fn record_id_object(rid: RecordId) -> Value {
+	let mut obj = Object::default();
+	obj.insert("id".to_string(), Value::RecordId(rid));
+	Value::Object(obj)
+}
+
+/// Resolve a batch of [`RecordId`]s read from an index into output values.
+///
+/// When `ids_only` is set, the records are not read, and each id is
+/// returned as an `{ id }` object for a consumer which fetches the records
+/// itself. Otherwise the records are fetched and filtered with
+/// [`fetch_and_filter_records_batch`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn fetch_or_identify_records_batch(
+	ids_only: bool,
+	ctx: &ExecutionContext,
+	txn: &Transaction,
+	ns_id: NamespaceId,
+	db_id: DatabaseId,
+	rids: &[RecordId],
+	select_permission: &crate::exec::permission::PhysicalPermission,
+	check_perms: bool,
+	version: Option<u64>,
+	cache_policy: CachePolicy,
+) -> Result<Vec<Value>, ControlFlow> {
+	if ids_only {
+		return Ok(rids.iter().cloned().map(record_id_object).collect());
+	}
+	fetch_and_filter_records_batch(
+		ctx,
+		txn,
+		ns_id,
+		db_id,
+		rids,
+		select_permission,
+		check_perms,
+		version,
+		cache_policy,
+	)
+	.await
+}
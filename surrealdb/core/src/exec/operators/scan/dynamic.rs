@@ -7,7 +7,7 @@ use tracing::instrument;
 use super::pipeline::{
 	build_field_state, determine_scan_direction, eval_limit_expr, kv_scan_stream,
 };
-use super::{FullTextScan, IndexScan, KnnScan};
+use super::{FullTextScan, IndexScan, KnnScan, SpatialScan};
 use crate::catalog::{DatabaseId, NamespaceId, Permission};
 use crate::err::Error;
 use crate::exec::index::access_path::{AccessPath, select_access_path};
@@ -579,7 +579,12 @@ async fn resolve_table_scan_stream(
 			.await
 			.context("Failed to fetch indexes")?;
 
-		let analyzer = IndexAnalyzer::new(indexes, cfg.with.as_ref());
+		let statistics = db_ctx
+			.get_table_statistics(&cfg.table_name)
+			.await
+			.context("Failed to fetch table statistics")?;
+
+		let analyzer = IndexAnalyzer::new(indexes, cfg.with.as_ref()).with_statistics(statistics);
 		let candidates = analyzer.analyze(resolved_cond.as_ref(), cfg.order.as_ref());
 		if candidates.is_empty() {
 			// No single-index candidates -- try multi-index union for OR conditions
@@ -592,7 +597,12 @@ async fn resolve_table_scan_stream(
 					analyzer.try_containment_expansion(resolved_cond.as_ref(), cfg.direction)
 				})
 		} else {
-			let path = select_access_path(candidates, cfg.with.as_ref(), cfg.direction);
+			let path = select_access_path(
+				candidates,
+				cfg.with.as_ref(),
+				cfg.direction,
+				analyzer.statistics.as_deref(),
+			);
 			// When the best single-index path is a full-range scan (ORDER BY
 			// only), prefer a multi-index union for OR conditions if available.
 			if path.is_full_range_scan() {
@@ -670,6 +680,16 @@ async fn resolve_table_scan_stream(
 			Ok((stream, 0))
 		}
 
+		// Geospatial search via a SPATIAL index
+		Some(AccessPath::SpatialSearch {
+			index_ref,
+			region,
+		}) => {
+			let spatial_op = SpatialScan::new(index_ref, region, cfg.table_name, cfg.version, None);
+			let stream = spatial_op.execute(ctx)?;
+			Ok((stream, 0))
+		}
+
 		// Multi-index union for OR conditions — delegate to UnionIndexScan.
 		// Permission handling is done by DynamicScan's ScanPipeline above.
 		Some(AccessPath::Union(paths)) => {
@@ -761,10 +781,24 @@ fn create_index_operator(
 				None,
 			))
 		}
-		// TableScan and nested Union should not appear as sub-paths.
-		// Fall back to a table scan operator which will produce all
-		// records (safe but sub-optimal).
-		AccessPath::TableScan | AccessPath::Union(_) => Arc::new(super::TableScan::new(
+		AccessPath::SpatialSearch {
+			index_ref,
+			region,
+		} => Arc::new(SpatialScan::new(
+			index_ref.clone(),
+			region.clone(),
+			cfg.table_name.clone(),
+			cfg.version.clone(),
+			None,
+		)),
+		// TableScan, nested Union and HybridSearch should not appear as
+		// sub-paths. Fall back to a table scan operator which will produce
+		// all records (safe but sub-optimal).
+		AccessPath::TableScan
+		| AccessPath::Union(_)
+		| AccessPath::HybridSearch {
+			..
+		} => Arc::new(super::TableScan::new(
 			cfg.table_name.clone(),
 			cfg.direction,
 			None,
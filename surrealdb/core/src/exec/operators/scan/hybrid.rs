@@ -0,0 +1,410 @@
+//! Hybrid scan operator fusing a KNN search with a full-text search.
+//!
+//! Created by the planner when the access path is `AccessPath::HybridSearch`,
+//! meaning the `SELECT` has a `WITH FUSION` clause and its WHERE clause holds
+//! both a KNN operator served by a vector index and a MATCHES operator served
+//! by a full-text index. Both searches run concurrently; their results are
+//! merged into a single ranking, and the fused score of each record is made
+//! available to `search::score()` through the [`FusionContext`].
+//!
+//! Follows the same permission pattern as [`super::UnionIndexScan`]: the
+//! sub-operators return raw records, and the full
+//! [`ScanPipeline`](super::pipeline::ScanPipeline) is applied here.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use tracing::instrument;
+
+use super::pipeline::{ScanPipeline, build_field_state};
+use super::resolved::ResolvedTableContext;
+use crate::exec::function::{FusionContext, KnnContext, MatchContext};
+use crate::exec::permission::{
+	PhysicalPermission, convert_permission_to_physical, should_check_perms,
+	validate_record_user_access,
+};
+use crate::exec::physical_expr::EvalContext;
+use crate::exec::{
+	AccessMode, CombineAccessModes, ContextLevel, ExecOperator, ExecutionContext, FlowResult,
+	OperatorMetrics, ValueBatch, ValueBatchStream, buffer_stream, monitor_stream,
+};
+use crate::expr::with::{Fusion, FusionNorm};
+use crate::expr::{ControlFlow, ControlFlowExt};
+use crate::iam::Action;
+use crate::val::{RecordId, TableName, Value};
+
+/// Hybrid scan operator for `WITH FUSION` queries.
+///
+/// Executes a pre-planned [`super::KnnScan`] and a pre-planned
+/// [`super::FullTextScan`] in parallel, then fuses the scores of both
+/// searches. Records returned by either search are output once, ordered by
+/// their fused score (highest first).
+#[derive(Debug)]
+pub struct HybridScan {
+	pub(crate) table_name: TableName,
+	/// The KNN search sub-operator
+	pub(crate) knn: Arc<dyn ExecOperator>,
+	/// The full-text search sub-operator
+	pub(crate) fulltext: Arc<dyn ExecOperator>,
+	/// How the scores of both searches are fused
+	pub(crate) fusion: Fusion,
+	/// The MATCHES clause, used to compute the BM25 scores
+	pub(crate) match_ctx: Arc<MatchContext>,
+	/// The KNN distances, populated by the KNN sub-operator
+	pub(crate) knn_context: Arc<KnnContext>,
+	/// The fused scores, shared with search::score()
+	pub(crate) fusion_context: Arc<FusionContext>,
+	pub(crate) needed_fields: Option<HashSet<String>>,
+	/// Plan-time resolved table context. When present, `execute()` skips
+	/// runtime table def + permission lookup and uses pre-built field state.
+	pub(crate) resolved: Option<ResolvedTableContext>,
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl HybridScan {
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn new(
+		table_name: TableName,
+		knn: Arc<dyn ExecOperator>,
+		fulltext: Arc<dyn ExecOperator>,
+		fusion: Fusion,
+		match_ctx: Arc<MatchContext>,
+		knn_context: Arc<KnnContext>,
+		fusion_context: Arc<FusionContext>,
+		needed_fields: Option<HashSet<String>>,
+	) -> Self {
+		Self {
+			table_name,
+			knn,
+			fulltext,
+			fusion,
+			match_ctx,
+			knn_context,
+			fusion_context,
+			needed_fields,
+			resolved: None,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+
+	/// Set the plan-time resolved table context.
+	pub(crate) fn with_resolved(mut self, resolved: ResolvedTableContext) -> Self {
+		self.resolved = Some(resolved);
+		self
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl ExecOperator for HybridScan {
+	fn name(&self) -> &'static str {
+		"HybridScan"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		vec![
+			("table".to_string(), self.table_name.to_string()),
+			("fusion".to_string(), surrealdb_types::ToSql::to_sql(&self.fusion)),
+		]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		self.knn.required_context().max(self.fulltext.required_context())
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		[self.knn.access_mode(), self.fulltext.access_mode()].into_iter().combine_all()
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		vec![&self.knn, &self.fulltext]
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	#[instrument(name = "HybridScan::execute", level = "trace", skip_all)]
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let db_ctx = ctx.database()?.clone();
+
+		// Validate record user has access to this namespace/database
+		validate_record_user_access(&db_ctx)?;
+
+		// Check if we need to enforce permissions
+		let check_perms = should_check_perms(&db_ctx, Action::View)?;
+
+		// Execute both sub-operators eagerly so that any setup errors
+		// surface immediately.
+		let knn_stream = buffer_stream(
+			self.knn.execute(ctx)?,
+			self.knn.access_mode(),
+			self.knn.cardinality_hint(),
+		);
+		let fulltext_stream = buffer_stream(
+			self.fulltext.execute(ctx)?,
+			self.fulltext.access_mode(),
+			self.fulltext.cardinality_hint(),
+		);
+
+		// Clone for the async block
+		let table_name = self.table_name.clone();
+		let fusion = self.fusion.clone();
+		let match_ctx = self.match_ctx.clone();
+		let knn_context = self.knn_context.clone();
+		let fusion_context = self.fusion_context.clone();
+		let needed_fields = self.needed_fields.clone();
+		let resolved = self.resolved.clone();
+		let ctx = ctx.clone();
+
+		let stream: ValueBatchStream = Box::pin(async_stream::try_stream! {
+			let db_ctx = ctx.database().context("HybridScan requires database context")?;
+			let version = ctx.version_stamp();
+
+			// Resolve table permissions and field state: plan-time fast path or runtime fallback
+			let (select_permission, field_state) = if let Some(ref res) = resolved {
+				let perm = res.select_permission(check_perms);
+				let fs = res.field_state_for_projection(needed_fields.as_ref());
+				(perm, fs)
+			} else {
+				// Check table existence and resolve SELECT permission
+				let table_def = db_ctx
+					.get_table_def(&table_name, version)
+					.await
+					.context("Failed to get table")?;
+
+				if table_def.is_none() {
+					Err(ControlFlow::Err(anyhow::Error::new(crate::err::Error::TbNotFound {
+						name: table_name.clone(),
+					})))?;
+				}
+
+				let select_permission = if check_perms {
+					let catalog_perm = match &table_def {
+						Some(def) => def.permissions.select.clone(),
+						None => crate::catalog::Permission::None,
+					};
+					convert_permission_to_physical(&catalog_perm, ctx.ctx()).await
+						.context("Failed to convert permission")?
+				} else {
+					PhysicalPermission::Allow
+				};
+
+				let field_state = build_field_state(
+					&ctx, &table_name, check_perms, needed_fields.as_ref(),
+				).await?;
+				(select_permission, field_state)
+			};
+
+			// Early exit if denied
+			if matches!(select_permission, PhysicalPermission::Deny) {
+				return;
+			}
+
+			// Run both searches to completion, concurrently
+			let (knn_values, fulltext_values) =
+				futures::try_join!(collect_values(knn_stream), collect_values(fulltext_stream))?;
+
+			// Check for cancellation once both searches are complete
+			if ctx.cancellation().is_cancelled() {
+				Err(ControlFlow::Err(
+					anyhow::anyhow!(crate::err::Error::QueryCancelled),
+				))?;
+			}
+
+			// Score the KNN results: lower distance = higher score
+			let mut records: HashMap<RecordId, Value> = HashMap::new();
+			let mut knn_scores = Vec::with_capacity(knn_values.len());
+			for (rank, value) in knn_values.into_iter().enumerate() {
+				let Some(rid) = record_id(&value) else {
+					continue;
+				};
+				let score = match knn_context.get(&rid).await {
+					Some(distance) => 1.0 / (1.0 + distance.as_float()),
+					None => 1.0 / (1.0 + rank as f64),
+				};
+				knn_scores.push((rid.clone(), score));
+				records.entry(rid).or_insert(value);
+			}
+
+			// Score the full-text results with BM25, when the index has a scorer
+			let eval_ctx = EvalContext::from_exec_ctx(&ctx);
+			let (fti, qt, scorer) = match_ctx.ft_resources(&eval_ctx).await?;
+			let tx = eval_ctx.txn();
+			let mut fulltext_scores = Vec::with_capacity(fulltext_values.len());
+			for (rank, value) in fulltext_values.into_iter().enumerate() {
+				let Some(rid) = record_id(&value) else {
+					continue;
+				};
+				let bm25 = match (scorer, fti.get_doc_id(&tx, &rid).await?) {
+					(Some(scorer), Some(doc_id)) => {
+						Some(scorer.score(fti, &tx, qt, doc_id).await? as f64)
+					}
+					_ => None,
+				};
+				let score = bm25.unwrap_or(1.0 / (1.0 + rank as f64));
+				fulltext_scores.push((rid.clone(), score));
+				records.entry(rid).or_insert(value);
+			}
+
+			// Fuse the scores, and order the records by fused score
+			let scores = fuse_scores(&fusion, knn_scores, fulltext_scores);
+			let mut ranked: Vec<(RecordId, f64)> =
+				scores.iter().map(|(rid, score)| (rid.clone(), *score)).collect();
+			ranked.sort_by(|(a_rid, a), (b_rid, b)| b.total_cmp(a).then_with(|| a_rid.cmp(b_rid)));
+			fusion_context.extend(scores).await;
+
+			let mut values: Vec<Value> =
+				ranked.into_iter().filter_map(|(rid, _)| records.remove(&rid)).collect();
+			if values.is_empty() {
+				return;
+			}
+
+			// Build the pipeline (no predicate/limit/start — outer operators handle those)
+			let mut pipeline = ScanPipeline::new(
+				select_permission, None, field_state,
+				check_perms, None, 0,
+			);
+			pipeline.process_batch(&mut values, &ctx).await?;
+			if !values.is_empty() {
+				yield ValueBatch { values };
+			}
+		});
+
+		Ok(monitor_stream(stream, "HybridScan", &self.metrics))
+	}
+}
+
+/// Drain a sub-operator stream into a list of values.
+async fn collect_values(mut stream: ValueBatchStream) -> FlowResult<Vec<Value>> {
+	let mut values = Vec::new();
+	while let Some(batch) = stream.next().await {
+		values.extend(batch?.values);
+	}
+	Ok(values)
+}
+
+/// Extract the record id of a record returned by a sub-operator.
+fn record_id(value: &Value) -> Option<RecordId> {
+	match value {
+		Value::Object(obj) => match obj.get("id") {
+			Some(Value::RecordId(rid)) => Some(rid.clone()),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+/// Fuse the scores of the KNN search and of the full-text search.
+///
+/// Each list holds the score of every record returned by one search, where
+/// a higher score is a better match. A record missing from one of the lists
+/// gets no contribution from that list.
+///
+/// - **RRF**: each list contributes `1 / (k + rank)`, with a 1-based rank.
+/// - **Linear**: each list contributes its normalized score, multiplied by the weight of the list.
+fn fuse_scores(
+	fusion: &Fusion,
+	knn: Vec<(RecordId, f64)>,
+	fulltext: Vec<(RecordId, f64)>,
+) -> HashMap<RecordId, f64> {
+	let mut fused: HashMap<RecordId, f64> = HashMap::new();
+	match fusion {
+		Fusion::Rrf(k) => {
+			for mut list in [knn, fulltext] {
+				list.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+				for (rank, (rid, _)) in list.into_iter().enumerate() {
+					*fused.entry(rid).or_default() += 1.0 / (*k as f64 + (rank + 1) as f64);
+				}
+			}
+		}
+		Fusion::Linear {
+			vector,
+			text,
+			norm,
+		} => {
+			for (list, weight) in [(knn, *vector), (fulltext, *text)] {
+				let (offset, scale) = normalization(&list, *norm);
+				for (rid, score) in list {
+					*fused.entry(rid).or_default() += weight as f64 * (score - offset) / scale;
+				}
+			}
+		}
+	}
+	fused
+}
+
+/// Compute the normalization parameters of a list of scores.
+///
+/// Returns `(min, range)` for MinMax and `(mean, std_dev)` for ZScore,
+/// falling back to a unit scale when all the scores are equal.
+fn normalization(list: &[(RecordId, f64)], norm: FusionNorm) -> (f64, f64) {
+	if list.is_empty() {
+		return (0.0, 1.0);
+	}
+	let (offset, scale) = match norm {
+		FusionNorm::MinMax => {
+			let min = list.iter().fold(f64::INFINITY, |a, (_, b)| a.min(*b));
+			let max = list.iter().fold(f64::NEG_INFINITY, |a, (_, b)| a.max(*b));
+			(min, max - min)
+		}
+		FusionNorm::ZScore => {
+			let len = list.len() as f64;
+			let mean = list.iter().map(|(_, s)| s).sum::<f64>() / len;
+			let variance = list.iter().map(|(_, s)| (s - mean).powi(2)).sum::<f64>() / len;
+			(mean, variance.sqrt())
+		}
+	};
+	if scale > 0.0 {
+		(offset, scale)
+	} else {
+		(offset, 1.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::val::RecordIdKey;
+
+	fn rid(id: i64) -> RecordId {
+		RecordId::new("t".into(), RecordIdKey::Number(id))
+	}
+
+	#[test]
+	fn rrf_fusion() {
+		let knn = vec![(rid(1), 0.9), (rid(2), 0.5)];
+		let fulltext = vec![(rid(2), 3.0), (rid(3), 1.0)];
+		let fused = fuse_scores(&Fusion::Rrf(60), knn, fulltext);
+		assert_eq!(fused.len(), 3);
+		assert_eq!(fused[&rid(1)], 1.0 / 61.0);
+		assert_eq!(fused[&rid(2)], 1.0 / 62.0 + 1.0 / 61.0);
+		assert_eq!(fused[&rid(3)], 1.0 / 62.0);
+	}
+
+	#[test]
+	fn linear_fusion() {
+		let knn = vec![(rid(1), 0.5), (rid(2), 0.25)];
+		let fulltext = vec![(rid(2), 4.0), (rid(3), 2.0)];
+		let fusion = Fusion::Linear {
+			vector: 2.0,
+			text: 1.0,
+			norm: FusionNorm::MinMax,
+		};
+		let fused = fuse_scores(&fusion, knn, fulltext);
+		assert_eq!(fused[&rid(1)], 2.0);
+		assert_eq!(fused[&rid(2)], 1.0);
+		assert_eq!(fused[&rid(3)], 0.0);
+	}
+
+	#[test]
+	fn zscore_normalization() {
+		let list = vec![(rid(1), 1.0), (rid(2), 3.0)];
+		assert_eq!(normalization(&list, FusionNorm::ZScore), (2.0, 1.0));
+		let list = vec![(rid(1), 2.0), (rid(2), 2.0)];
+		assert_eq!(normalization(&list, FusionNorm::ZScore), (2.0, 1.0));
+		assert_eq!(normalization(&[], FusionNorm::MinMax), (0.0, 1.0));
+	}
+}
@@ -9,7 +9,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use surrealdb_types::ToSql;
 
-use super::common::fetch_and_filter_records_batch;
+use super::common::fetch_or_identify_records_batch;
 use super::pipeline::{build_field_state, eval_limit_expr};
 use super::resolved::ResolvedTableContext;
 use crate::err::Error;
@@ -69,6 +69,12 @@ pub struct IndexScan {
 	/// continues until either the range is exhausted or the consumer
 	/// drops the stream.
 	pub(crate) batch_ceiling: Option<Arc<dyn PhysicalExpr>>,
+	/// Whether the scan only outputs `{ id }` objects, leaving the records
+	/// to be fetched by the consumer (used by the mutation sink).
+	pub(crate) ids_only: bool,
+	/// The number of records the planner estimated this scan would read,
+	/// when the table has statistics.
+	pub(crate) estimated_rows: Option<u64>,
 	/// Per-operator runtime metrics for EXPLAIN ANALYZE.
 	pub(crate) metrics: Arc<OperatorMetrics>,
 }
@@ -96,6 +102,8 @@ impl IndexScan {
 			resolved: None,
 			needed_fields,
 			batch_ceiling: None,
+			ids_only: false,
+			estimated_rows: None,
 			metrics: Arc::new(OperatorMetrics::new()),
 		}
 	}
@@ -117,6 +125,19 @@ impl IndexScan {
 		self.batch_ceiling = ceiling;
 		self
 	}
+
+	/// Only output the ids of the matching records, as `{ id }` objects,
+	/// without reading the records themselves.
+	pub(crate) fn with_ids_only(mut self) -> Self {
+		self.ids_only = true;
+		self
+	}
+
+	/// Set the number of records the planner estimated this scan would read.
+	pub(crate) fn with_estimated_rows(mut self, estimated_rows: Option<u64>) -> Self {
+		self.estimated_rows = estimated_rows;
+		self
+	}
 }
 
 #[cfg_attr(target_family = "wasm", async_trait(?Send))]
@@ -170,14 +191,17 @@ impl ExecOperator for IndexScan {
 					format!("[{}]", prefix_str)
 				}
 			}
-			// FullText and KNN should use dedicated operators
+			// FullText, KNN and Spatial should use dedicated operators
 			BTreeAccess::FullText {
 				..
 			}
 			| BTreeAccess::Knn {
 				..
+			}
+			| BTreeAccess::Spatial {
+				..
 			} => {
-				unreachable!("IndexScan does not support FullText or KNN access")
+				unreachable!("IndexScan does not support FullText, KNN or Spatial access")
 			}
 		};
 		let mut attrs = vec![
@@ -191,6 +215,9 @@ impl ExecOperator for IndexScan {
 		if let Some(ref start) = self.start {
 			attrs.push(("offset".to_string(), start.to_sql()));
 		}
+		if let Some(rows) = self.estimated_rows {
+			attrs.push(("estimated_rows".to_string(), rows.to_string()));
+		}
 		attrs
 	}
 
@@ -330,6 +357,7 @@ impl ExecOperator for IndexScan {
 		let version_expr = self.version.clone();
 		let resolved = self.resolved.clone();
 		let needed_fields = self.needed_fields.clone();
+		let ids_only = self.ids_only;
 		let ctx = ctx.clone();
 
 		let stream = async_stream::try_stream! {
@@ -464,8 +492,8 @@ impl ExecOperator for IndexScan {
 							break;
 						}
 
-						let mut values = fetch_and_filter_records_batch(
-							&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+						let mut values = fetch_or_identify_records_batch(
+							ids_only, &ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
 							CachePolicy::ReadOnly,
 						).await?;
 
@@ -498,8 +526,8 @@ impl ExecOperator for IndexScan {
 							break;
 						}
 
-						let mut values = fetch_and_filter_records_batch(
-							&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+						let mut values = fetch_or_identify_records_batch(
+							ids_only, &ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
 							CachePolicy::ReadOnly,
 						).await?;
 
@@ -534,8 +562,8 @@ impl ExecOperator for IndexScan {
 							.context("Failed to iterate index")?;
 						if rids.is_empty() { break; }
 
-						let mut values = fetch_and_filter_records_batch(
-							&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+						let mut values = fetch_or_identify_records_batch(
+							ids_only, &ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
 							CachePolicy::ReadOnly,
 						).await?;
 
@@ -563,8 +591,8 @@ impl ExecOperator for IndexScan {
 							.context("Failed to iterate index")?;
 						if rids.is_empty() { break; }
 
-						let mut values = fetch_and_filter_records_batch(
-							&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+						let mut values = fetch_or_identify_records_batch(
+							ids_only, &ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
 							CachePolicy::ReadOnly,
 						).await?;
 
@@ -614,8 +642,8 @@ impl ExecOperator for IndexScan {
 						// scanning the next batch of index entries concurrently.
 						// This halves serial latency on TiKV.
 						let (values_result, next_rids_result) = if remaining > 0 {
-							let fetch_fut = fetch_and_filter_records_batch(
-								&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+							let fetch_fut = fetch_or_identify_records_batch(
+								ids_only, &ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
 								CachePolicy::ReadOnly,
 							);
 							let scan_fut = iter.next_batch(&txn, remaining.min(batch_max));
@@ -623,8 +651,8 @@ impl ExecOperator for IndexScan {
 							(v, Some(n))
 						} else {
 							// No more entries needed; skip the prefetch.
-							let v = fetch_and_filter_records_batch(
-								&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+							let v = fetch_or_identify_records_batch(
+								ids_only, &ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
 								CachePolicy::ReadOnly,
 							).await;
 							(v, None)
@@ -677,16 +705,16 @@ impl ExecOperator for IndexScan {
 						// Overlap: fetch records for the current batch while
 						// scanning the next batch of index entries concurrently.
 						let (values_result, next_rids_result) = if remaining > 0 {
-							let fetch_fut = fetch_and_filter_records_batch(
-								&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+							let fetch_fut = fetch_or_identify_records_batch(
+								ids_only, &ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
 								CachePolicy::ReadOnly,
 							);
 							let scan_fut = iter.next_batch(&txn, remaining.min(batch_max));
 							let (v, n) = futures::join!(fetch_fut, scan_fut);
 							(v, Some(n))
 						} else {
-							let v = fetch_and_filter_records_batch(
-								&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+							let v = fetch_or_identify_records_batch(
+								ids_only, &ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
 								CachePolicy::ReadOnly,
 							).await;
 							(v, None)
@@ -710,10 +738,12 @@ impl ExecOperator for IndexScan {
 					}
 				}
 
-				// FullText and KNN should use dedicated operators
-				(BTreeAccess::FullText { .. }, _) | (BTreeAccess::Knn { .. }, _) => {
+				// FullText, KNN and Spatial should use dedicated operators
+				(BTreeAccess::FullText { .. }, _)
+				| (BTreeAccess::Knn { .. }, _)
+				| (BTreeAccess::Spatial { .. }, _) => {
 					Err(ControlFlow::Err(anyhow::anyhow!(
-						"IndexScan does not support FullText or KNN access - use dedicated operators"
+						"IndexScan does not support FullText, KNN or Spatial access - use dedicated operators"
 					)))?
 				}
 			}
@@ -134,6 +134,11 @@ impl ScanPipeline {
 		batch: &mut Vec<Value>,
 		ctx: &ExecutionContext,
 	) -> Result<bool, ControlFlow> {
+		// Phase 0: count the scanned records against the scan quota
+		if let Some(usage) = ctx.ctx().query_usage() {
+			usage.record_scanned(batch.len() as u64)?;
+		}
+
 		// Phase 1: filter + process (parallel per-record via try_join_all_buffered)
 		if self.needs_processing {
 			filter_and_process_batch(
@@ -237,6 +242,43 @@ pub(crate) fn kv_scan_stream(
 	Box::pin(stream)
 }
 
+/// Produce a `ValueBatchStream` of `{ id }` objects from a raw KV range scan
+/// over record keys, without reading the record values.
+///
+/// Used by scans feeding a consumer which fetches the records itself.
+pub(crate) fn kv_key_scan_stream(
+	txn: Arc<Transaction>,
+	beg: crate::kvs::Key,
+	end: crate::kvs::Key,
+	version: Option<u64>,
+	storage_limit: Option<usize>,
+	direction: ScanDirection,
+	pre_skip: usize,
+) -> ValueBatchStream {
+	let skip = pre_skip.min(u32::MAX as usize) as u32;
+	let stream = async_stream::try_stream! {
+		let kv_stream = txn.stream_keys(beg..end, version, storage_limit, skip, direction);
+		futures::pin_mut!(kv_stream);
+
+		while let Some(result) = kv_stream.next().await {
+			let keys = result.context("Failed to scan record keys")?;
+			let mut batch = Vec::with_capacity(keys.len());
+			for key in keys {
+				let decoded_key = crate::key::record::RecordKey::decode_key(&key)
+					.context("Failed to decode record key")?;
+				batch.push(super::common::record_id_object(crate::val::RecordId {
+					table: decoded_key.tb.into_owned(),
+					key: decoded_key.id,
+				}));
+			}
+			if !batch.is_empty() {
+				yield ValueBatch { values: batch };
+			}
+		}
+	};
+	Box::pin(stream)
+}
+
 /// Decode a record from its key and value bytes.
 #[inline]
 pub(crate) fn decode_record(key: &[u8], val: Vec<u8>) -> Result<Value, ControlFlow> {
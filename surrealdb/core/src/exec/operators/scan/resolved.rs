@@ -39,6 +39,19 @@ pub(crate) struct ResolvedTableContext {
 }
 
 impl ResolvedTableContext {
+	/// Create a context which grants SELECT access and skips field processing.
+	///
+	/// Used for the scans feeding a mutation sink, where the document
+	/// pipeline applies the statement's own permission checks to every
+	/// record, and must see the records as they are stored.
+	pub fn unrestricted(table_def: Arc<TableDefinition>) -> Self {
+		Self {
+			table_def,
+			select_permission: PhysicalPermission::Allow,
+			field_state: Arc::new(FieldState::empty()),
+		}
+	}
+
 	/// Get a field state filtered for a specific projection.
 	pub fn field_state_for_projection(
 		&self,
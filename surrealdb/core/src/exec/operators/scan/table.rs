@@ -10,7 +10,9 @@ use async_trait::async_trait;
 use futures::StreamExt;
 use tracing::instrument;
 
-use super::pipeline::{ScanPipeline, build_field_state, eval_limit_expr, kv_scan_stream};
+use super::pipeline::{
+	ScanPipeline, build_field_state, eval_limit_expr, kv_key_scan_stream, kv_scan_stream,
+};
 use super::resolved::ResolvedTableContext;
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical, should_check_perms,
@@ -47,6 +49,12 @@ pub struct TableScan {
 	/// Plan-time resolved table context. When present, `execute()` skips
 	/// all runtime metadata lookups (table def, permissions, field state).
 	pub(crate) resolved: Option<ResolvedTableContext>,
+	/// Whether the scan only outputs `{ id }` objects, leaving the records
+	/// to be fetched by the consumer (used by the mutation sink).
+	pub(crate) ids_only: bool,
+	/// The number of records the planner estimated this scan would read,
+	/// when the table has statistics.
+	pub(crate) estimated_rows: Option<u64>,
 	pub(crate) metrics: Arc<OperatorMetrics>,
 }
 
@@ -69,6 +77,8 @@ impl TableScan {
 			start,
 			needed_fields,
 			resolved: None,
+			ids_only: false,
+			estimated_rows: None,
 			metrics: Arc::new(OperatorMetrics::new()),
 		}
 	}
@@ -78,6 +88,19 @@ impl TableScan {
 		self.resolved = Some(resolved);
 		self
 	}
+
+	/// Only output the ids of the records, as `{ id }` objects, scanning
+	/// the record keys without reading the record values.
+	pub(crate) fn with_ids_only(mut self) -> Self {
+		self.ids_only = true;
+		self
+	}
+
+	/// Set the number of records the planner estimated this scan would read.
+	pub(crate) fn with_estimated_rows(mut self, estimated_rows: Option<u64>) -> Self {
+		self.estimated_rows = estimated_rows;
+		self
+	}
 }
 
 #[cfg_attr(target_family = "wasm", async_trait(?Send))]
@@ -99,6 +122,9 @@ impl ExecOperator for TableScan {
 		if let Some(ref start) = self.start {
 			attrs.push(("offset".to_string(), start.to_sql()));
 		}
+		if let Some(rows) = self.estimated_rows {
+			attrs.push(("estimated_rows".to_string(), rows.to_string()));
+		}
 		attrs
 	}
 
@@ -154,6 +180,7 @@ impl ExecOperator for TableScan {
 		let limit_expr = self.limit.clone();
 		let start_expr = self.start.clone();
 		let needed_fields = self.needed_fields.clone();
+		let ids_only = self.ids_only;
 		let ctx = ctx.clone();
 
 		let stream = async_stream::try_stream! {
@@ -244,10 +271,17 @@ impl ExecOperator for TableScan {
 			let beg = record::prefix(ns.namespace_id, db.database_id, &table_name)?;
 			let end = record::suffix(ns.namespace_id, db.database_id, &table_name)?;
 			let prefetch = effective_storage_limit.is_none();
-			let mut source = kv_scan_stream(
-				Arc::clone(&txn), beg, end, version,
-				effective_storage_limit, direction, pre_skip, prefetch,
-			);
+			let mut source = if ids_only {
+				kv_key_scan_stream(
+					Arc::clone(&txn), beg, end, version,
+					effective_storage_limit, direction, pre_skip,
+				)
+			} else {
+				kv_scan_stream(
+					Arc::clone(&txn), beg, end, version,
+					effective_storage_limit, direction, pre_skip, prefetch,
+				)
+			};
 
 			let mut pipeline = ScanPipeline::new(
 				select_permission, predicate, field_state,
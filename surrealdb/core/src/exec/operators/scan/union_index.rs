@@ -59,6 +59,9 @@ pub struct UnionIndexScan {
 	/// The merge produces globally record-ID-sorted output, enabling sort
 	/// elimination and early termination for ORDER BY id queries.
 	pub(crate) merge_by_id: Option<SortDirection>,
+	/// The number of records the planner estimated the branches would read
+	/// in total, when the table has statistics.
+	pub(crate) estimated_rows: Option<u64>,
 	pub(crate) metrics: Arc<OperatorMetrics>,
 }
 
@@ -74,6 +77,7 @@ impl UnionIndexScan {
 			needed_fields,
 			resolved: None,
 			merge_by_id: None,
+			estimated_rows: None,
 			metrics: Arc::new(OperatorMetrics::new()),
 		}
 	}
@@ -94,6 +98,13 @@ impl UnionIndexScan {
 		self.merge_by_id = Some(direction);
 		self
 	}
+
+	/// Set the number of records the planner estimated the branches would
+	/// read in total.
+	pub(crate) fn with_estimated_rows(mut self, estimated_rows: Option<u64>) -> Self {
+		self.estimated_rows = estimated_rows;
+		self
+	}
 }
 
 #[cfg_attr(target_family = "wasm", async_trait(?Send))]
@@ -111,6 +122,9 @@ impl ExecOperator for UnionIndexScan {
 		if let Some(dir) = &self.merge_by_id {
 			attrs.push(("merge_by_id".to_string(), format!("{dir:?}")));
 		}
+		if let Some(rows) = self.estimated_rows {
+			attrs.push(("estimated_rows".to_string(), rows.to_string()));
+		}
 		attrs
 	}
 
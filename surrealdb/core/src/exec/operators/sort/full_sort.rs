@@ -140,6 +140,7 @@ impl ExecOperator for Sort {
 			self.input.cardinality_hint(),
 		);
 		let order_by = self.order_by.clone();
+		let usage = ctx.ctx().query_usage().cloned();
 		let ctx = ctx.clone();
 
 		// Sort requires collecting all input first, then sorting, then emitting
@@ -155,7 +156,13 @@ impl ExecOperator for Sort {
 					)));
 				}
 				match batch_result {
-					Ok(batch) => all_values.extend(batch.values),
+					Ok(batch) => {
+						// Count the buffered values against the memory quota
+						if let Some(usage) = &usage {
+							usage.record_memory(&batch.values)?;
+						}
+						all_values.extend(batch.values)
+					}
 					Err(e) => return Err(e),
 				}
 			}
@@ -338,6 +345,7 @@ impl ExecOperator for SortByKey {
 		);
 		let sort_keys = self.sort_keys.clone();
 		let cancellation = ctx.cancellation().clone();
+		let usage = ctx.ctx().query_usage().cloned();
 
 		// Sort requires collecting all input first, then sorting, then emitting
 		let sorted_stream = futures::stream::once(async move {
@@ -352,7 +360,13 @@ impl ExecOperator for SortByKey {
 					)));
 				}
 				match batch_result {
-					Ok(batch) => all_values.extend(batch.values),
+					Ok(batch) => {
+						// Count the buffered values against the memory quota
+						if let Some(usage) = &usage {
+							usage.record_memory(&batch.values)?;
+						}
+						all_values.extend(batch.values)
+					}
 					Err(e) => return Err(e),
 				}
 			}
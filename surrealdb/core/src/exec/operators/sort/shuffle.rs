@@ -93,6 +93,7 @@ impl ExecOperator for RandomShuffle {
 		);
 		let limit = self.limit;
 		let cancellation = ctx.cancellation().clone();
+		let usage = ctx.ctx().query_usage().cloned();
 
 		let shuffled_stream = futures::stream::once(async move {
 			// Collect all values from input
@@ -106,7 +107,13 @@ impl ExecOperator for RandomShuffle {
 					)));
 				}
 				match batch_result {
-					Ok(batch) => all_values.extend(batch.values),
+					Ok(batch) => {
+						// Count the buffered values against the memory quota
+						if let Some(usage) = &usage {
+							usage.record_memory(&batch.values)?;
+						}
+						all_values.extend(batch.values)
+					}
 					Err(e) => return Err(e),
 				}
 			}
@@ -17,7 +17,6 @@ use surrealdb_types::{SqlFormat, ToSql};
 
 use crate::cnf::PROTECTED_PARAM_NAMES;
 use crate::ctx::FrozenContext;
-use crate::dbs::NewPlannerStrategy;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::exec::AccessMode;
@@ -197,8 +196,7 @@ impl BlockPhysicalExpr {
 						phys_expr.evaluate(eval_ctx).await?
 					}
 					Err(Error::PlannerUnimplemented(ref msg))
-						if *frozen_ctx.new_planner_strategy()
-							== NewPlannerStrategy::AllReadOnlyStatements =>
+						if frozen_ctx.new_planner_strategy().requires_planner() =>
 					{
 						return Err(ControlFlow::Err(anyhow::anyhow!(Error::Query {
 							message: format!("New executor does not support: {msg}"),
@@ -265,8 +263,7 @@ impl BlockPhysicalExpr {
 						phys_expr.evaluate(eval_ctx).await
 					}
 					Err(Error::PlannerUnimplemented(ref msg))
-						if *frozen_ctx.new_planner_strategy()
-							== NewPlannerStrategy::AllReadOnlyStatements =>
+						if frozen_ctx.new_planner_strategy().requires_planner() =>
 					{
 						Err(ControlFlow::Err(anyhow::anyhow!(Error::Query {
 							message: format!("New executor does not support: {msg}"),
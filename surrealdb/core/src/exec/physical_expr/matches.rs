@@ -153,7 +153,13 @@ impl MatchesOp {
 					let mut stack = reblessive::TreeStack::new();
 					stack
 						.enter(|stk| {
-							fti.extract_querying_terms(stk, frozen, opt, self.query.clone())
+							fti.extract_querying_terms(
+								stk,
+								frozen,
+								opt,
+								self.query.clone(),
+								&self.operator,
+							)
 						})
 						.finish()
 						.await?
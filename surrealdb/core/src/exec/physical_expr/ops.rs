@@ -151,7 +151,7 @@ impl PhysicalExpr for BinaryOp {
 			}
 
 			// Records reaching this point have already been selected by
-			// KnnScan (HNSW) or KnnTopK (brute-force). KNN operators are
+			// KnnScan (HNSW, IVF-PQ) or KnnTopK (brute-force). KNN operators are
 			// stripped via strip_knn_from_condition before physical expression
 			// compilation, so this is a defensive fallback only.
 			BinaryOperator::NearestNeighbor(_) => Value::Bool(true),
@@ -300,9 +300,11 @@ pub(crate) fn expr_required_context(expr: &Expr) -> ContextLevel {
 		| Expr::Insert(_) => ContextLevel::Database,
 
 		// DDL statements need a database
-		Expr::Define(_) | Expr::Remove(_) | Expr::Alter(_) | Expr::Rebuild(_) => {
-			ContextLevel::Database
-		}
+		Expr::Define(_)
+		| Expr::Remove(_)
+		| Expr::Alter(_)
+		| Expr::Rebuild(_)
+		| Expr::Analyze(_) => ContextLevel::Database,
 
 		// Info: depends on the level
 		Expr::Info(info) => info_stmt_required_context(info),
@@ -333,9 +335,10 @@ fn info_stmt_required_context(info: &InfoStatement) -> ContextLevel {
 	match info {
 		InfoStatement::Root(_, _) => ContextLevel::Root,
 		InfoStatement::Ns(_, _) => ContextLevel::Namespace,
-		InfoStatement::Db(_, _) | InfoStatement::Tb(_, _, _) | InfoStatement::Index(_, _, _) => {
-			ContextLevel::Database
-		}
+		InfoStatement::Db(_, _)
+		| InfoStatement::Tb(_, _, _)
+		| InfoStatement::Index(_, _, _)
+		| InfoStatement::Api => ContextLevel::Database,
 		InfoStatement::User(user_expr, base, _) => {
 			let base_ctx = match base {
 				Some(Base::Root) | None => ContextLevel::Root,
@@ -44,6 +44,9 @@
 //! Aggregate (GROUP BY)
 //!     │
 //!     ▼
+//! HashJoin/MergeJoin (correlated subqueries and graph lookups in SELECT fields)
+//!     │
+//!     ▼
 //! Sort (ORDER BY)
 //!     │
 //!     ▼
@@ -61,6 +64,8 @@
 
 mod aggregate;
 mod idiom;
+mod join;
+mod mutation;
 mod select;
 mod source;
 pub(crate) mod util;
@@ -71,14 +76,13 @@ use std::sync::Arc;
 use self::util::literal_to_value;
 use crate::cnf::MAX_COMPUTATION_DEPTH;
 use crate::ctx::FrozenContext;
-use crate::dbs::NewPlannerStrategy;
 use crate::err::Error;
 use crate::exec::ExecOperator;
 use crate::exec::function::FunctionRegistry;
 use crate::exec::operators::{
-	AnalyzePlan, DatabaseInfoPlan, ExplainPlan, ExprPlan, Fetch, ForeachPlan, IfElsePlan,
-	IndexInfoPlan, NamespaceInfoPlan, ReturnPlan, RootInfoPlan, SequencePlan, SleepPlan,
-	TableInfoPlan, UserInfoPlan,
+	AnalyzePlan, ApiInfoPlan, DatabaseInfoPlan, ExplainPlan, ExprPlan, Fetch, ForeachPlan,
+	IfElsePlan, IndexInfoPlan, NamespaceInfoPlan, ReturnPlan, RootInfoPlan, SequencePlan,
+	SleepPlan, TableInfoPlan, UserInfoPlan,
 };
 use crate::exec::physical_expr::{
 	ArrayLiteral, BinaryOp, BlockPhysicalExpr, BuiltinFunctionExec, ClosureCallExec, ClosureExec,
@@ -99,6 +103,8 @@ use crate::expr::{Expr, Function, FunctionCall};
 /// - [`select`] — SELECT pipeline planning
 /// - [`aggregate`] — GROUP BY and aggregate extraction
 /// - [`idiom`] — Idiom-to-physical-part conversion
+/// - [`join`] — Decorrelation of subqueries into hash joins
+/// - [`mutation`] — UPDATE and DELETE mutation sink planning
 /// - [`source`] — Lookup, index function, and source planning
 /// - [`util`] — Pure utility functions
 pub struct Planner<'ctx> {
@@ -305,25 +311,14 @@ impl<'ctx> Planner<'ctx> {
 	/// available, performs plan-time index resolution and sort elimination.
 	pub async fn plan(&self, expr: &Expr) -> Result<Arc<dyn ExecOperator>, Error> {
 		match expr {
-			// DML/DDL — same as sync plan, always fall back to old executor
-			Expr::Create(_) => Err(Error::PlannerUnsupported(
-				"CREATE statements not yet supported in execution plans".to_string(),
-			)),
-			Expr::Update(_) => Err(Error::PlannerUnsupported(
-				"UPDATE statements not yet supported in execution plans".to_string(),
-			)),
-			Expr::Upsert(_) => Err(Error::PlannerUnsupported(
-				"UPSERT statements not yet supported in execution plans".to_string(),
-			)),
-			Expr::Delete(_) => Err(Error::PlannerUnsupported(
-				"DELETE statements not yet supported in execution plans".to_string(),
-			)),
-			Expr::Insert(_) => Err(Error::PlannerUnsupported(
-				"INSERT statements not yet supported in execution plans".to_string(),
-			)),
-			Expr::Relate(_) => Err(Error::PlannerUnsupported(
-				"RELATE statements not yet supported in execution plans".to_string(),
-			)),
+			// DML/DDL — fall back to the old executor, except for the write
+			// statements planned as mutation sinks
+			Expr::Create(stmt) => self.plan_create_statement(stmt.as_ref().clone()).await,
+			Expr::Update(stmt) => self.plan_update_statement(stmt.as_ref().clone()).await,
+			Expr::Upsert(stmt) => self.plan_upsert_statement(stmt.as_ref().clone()).await,
+			Expr::Delete(stmt) => self.plan_delete_statement(stmt.as_ref().clone()).await,
+			Expr::Insert(stmt) => self.plan_insert_statement(stmt.as_ref().clone()).await,
+			Expr::Relate(stmt) => self.plan_relate_statement(stmt.as_ref().clone()).await,
 			Expr::Define(_) => Err(Error::PlannerUnsupported(
 				"DEFINE statements not yet supported in execution plans".to_string(),
 			)),
@@ -333,6 +328,9 @@ impl<'ctx> Planner<'ctx> {
 			Expr::Rebuild(_) => Err(Error::PlannerUnsupported(
 				"REBUILD statements not yet supported in execution plans".to_string(),
 			)),
+			Expr::Analyze(_) => Err(Error::PlannerUnsupported(
+				"ANALYZE statements not yet supported in execution plans".to_string(),
+			)),
 			Expr::Alter(_) => Err(Error::PlannerUnsupported(
 				"ALTER statements not yet supported in execution plans".to_string(),
 			)),
@@ -435,11 +433,13 @@ impl<'ctx> Planner<'ctx> {
 			}),
 
 			// DDL — cannot be used in expression context
-			Expr::Define(_) | Expr::Remove(_) | Expr::Rebuild(_) | Expr::Alter(_) => {
-				Err(Error::PlannerUnsupported(
-					"DDL statements cannot be used in expression context".to_string(),
-				))
-			}
+			Expr::Define(_)
+			| Expr::Remove(_)
+			| Expr::Rebuild(_)
+			| Expr::Analyze(_)
+			| Expr::Alter(_) => Err(Error::PlannerUnsupported(
+				"DDL statements cannot be used in expression context".to_string(),
+			)),
 
 			// DML subqueries — not yet implemented
 			Expr::Create(_)
@@ -932,15 +932,15 @@ impl<'ctx> Planner<'ctx> {
 	// Internal Planning
 	// ========================================================================
 
-	/// When `AllReadOnlyStatements` strategy is active, convert `Error::PlannerUnimplemented`
-	/// into `Error::Query` so it becomes a hard error instead of a silent fallback.
+	/// When the `AllReadOnlyStatements` or `AllStatements` strategy is active, convert
+	/// `Error::PlannerUnimplemented` into `Error::Query` so it becomes a hard error instead
+	/// of a silent fallback.
 	///
 	/// `PlannerUnsupported` (DML/DDL) is left untouched — those always fall back to compute.
 	fn require_planned<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
 		match result {
 			Err(Error::PlannerUnimplemented(msg))
-				if *self.ctx.new_planner_strategy()
-					== NewPlannerStrategy::AllReadOnlyStatements =>
+				if self.ctx.new_planner_strategy().requires_planner() =>
 			{
 				Err(Error::Query {
 					message: format!("New executor does not support: {msg}"),
@@ -993,19 +993,19 @@ impl<'ctx> Planner<'ctx> {
 				| Expr::Break
 				| Expr::Continue) => self.plan_expr_as_operator(expr).await,
 
-				Expr::Create(_)
-				| Expr::Update(_)
-				| Expr::Upsert(_)
-				| Expr::Delete(_)
-				| Expr::Insert(_)
-				| Expr::Relate(_) => Err(Error::PlannerUnsupported(
-					"DML statements not yet supported in execution plans".to_string(),
+				Expr::Create(stmt) => self.plan_create_statement(*stmt).await,
+				Expr::Update(stmt) => self.plan_update_statement(*stmt).await,
+				Expr::Upsert(stmt) => self.plan_upsert_statement(*stmt).await,
+				Expr::Delete(stmt) => self.plan_delete_statement(*stmt).await,
+				Expr::Insert(stmt) => self.plan_insert_statement(*stmt).await,
+				Expr::Relate(stmt) => self.plan_relate_statement(*stmt).await,
+				Expr::Define(_)
+				| Expr::Remove(_)
+				| Expr::Rebuild(_)
+				| Expr::Analyze(_)
+				| Expr::Alter(_) => Err(Error::PlannerUnsupported(
+					"DDL statements not yet supported in execution plans".to_string(),
 				)),
-				Expr::Define(_) | Expr::Remove(_) | Expr::Rebuild(_) | Expr::Alter(_) => {
-					Err(Error::PlannerUnsupported(
-						"DDL statements not yet supported in execution plans".to_string(),
-					))
-				}
 			}
 		})
 	}
@@ -1163,6 +1163,7 @@ impl<'ctx> Planner<'ctx> {
 				let table = self.physical_expr_as_name(table).await?;
 				Ok(Arc::new(IndexInfoPlan::new(index, table, structured)) as Arc<dyn ExecOperator>)
 			}
+			InfoStatement::Api => Ok(Arc::new(ApiInfoPlan::new()) as Arc<dyn ExecOperator>),
 		}
 	}
 
@@ -1222,9 +1223,15 @@ macro_rules! try_plan_expr {
 				| $crate::expr::Expr::Delete(_)
 				| $crate::expr::Expr::Insert(_)
 				| $crate::expr::Expr::Relate(_)
-				| $crate::expr::Expr::Define(_)
+		) && !$ctx.new_planner_strategy().plans_writes()
+		{
+			Err($crate::err::Error::PlannerUnsupported(String::new()))
+		} else if matches!(
+			__expr,
+			$crate::expr::Expr::Define(_)
 				| $crate::expr::Expr::Remove(_)
 				| $crate::expr::Expr::Rebuild(_)
+				| $crate::expr::Expr::Analyze(_)
 				| $crate::expr::Expr::Alter(_)
 		) {
 			Err($crate::err::Error::PlannerUnsupported(String::new()))
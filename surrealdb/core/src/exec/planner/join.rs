@@ -0,0 +1,571 @@
+//! Subquery decorrelation for the planner.
+//!
+//! A subquery in the SELECT fields which is correlated with the outer row
+//! through a single equality, such as
+//! `(SELECT * FROM comment WHERE post = $parent.id)`, or a two-step graph
+//! lookup such as `->wrote->post`, can be rewritten into a join: the inner
+//! query runs once without the correlated predicate, and each outer row
+//! picks up its matches by key. Everything else about the subquery
+//! (residual WHERE, ORDER BY, projections) is planned as usual.
+//!
+//! The rewrite is only applied when it is estimated to be cheaper than the
+//! per-row evaluation: a handful of outer rows, each finding its matches
+//! through an index, is better served by the original query. When both
+//! sides are sorted by their keys, the rows are merged as they stream, and
+//! otherwise the inner rows are grouped in a bounded hash table.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::Planner;
+use super::select::collect_field_names;
+use super::util::{
+	derive_field_name, extract_correlated_equality, get_effective_limit_literal,
+	references_outer_document,
+};
+use crate::catalog::providers::{DatabaseProvider, TableProvider};
+use crate::catalog::{Index, Permission, TableType};
+use crate::cnf::HASH_JOIN_BUILD_LIMIT;
+use crate::err::Error;
+use crate::exec::field_path::FieldPath;
+use crate::exec::operators::{
+	HashJoin, JOIN_KEY_FIELD, JOIN_VALUE_FIELD, MergeJoin, SortDirection,
+};
+use crate::exec::ordering::SortProperty;
+use crate::exec::{CardinalityHint, ExecOperator};
+use crate::expr::field::{Field, Fields, Selector};
+use crate::expr::lookup::{Lookup, LookupKind, LookupSubject};
+use crate::expr::order::{Order, OrderList, Ordering};
+use crate::expr::part::Part;
+use crate::expr::statements::SelectStatement;
+use crate::expr::{
+	BinaryOperator, Cond, Dir, Expr, Function, FunctionCall, Idiom, Limit, Literal, Start,
+};
+use crate::idx::planner::cost::CostModel;
+use crate::val::TableName;
+
+/// The number of outer rows up to which looking up the matches of every row
+/// through an index is assumed to be cheaper than a join, when the inner
+/// table has no statistics to estimate the join with.
+const INDEXED_LOOKUP_ROWS: u64 = 1000;
+
+/// A correlated subquery rewritten so that it can run once for all rows.
+struct DecorrelatedSubquery {
+	/// The subquery without the correlated predicate, projecting its join key
+	inner: SelectStatement,
+	/// The field holding the join key in the rows of `inner`
+	build_key: String,
+	/// The join key of the outer row (the `$parent` path)
+	probe_key: Idiom,
+	/// Whether `inner` was a `SELECT VALUE` subquery
+	value_only: bool,
+	/// The table the subquery reads
+	table: TableName,
+	/// How the original expression finds the matches of a single outer row
+	lookup: PerRowLookup,
+}
+
+/// How the original expression finds the matches of a single outer row.
+enum PerRowLookup {
+	/// The subquery filters the inner table on this field, through an
+	/// index if there is one
+	Field(Idiom),
+	/// The graph lookup scans the edges of the outer record, which are the
+	/// edges holding the outer record in this field
+	Graph(&'static str),
+}
+
+// ============================================================================
+// impl Planner — Subquery decorrelation
+// ============================================================================
+
+impl<'ctx> Planner<'ctx> {
+	/// Decorrelate eligible subqueries in the SELECT fields into joins.
+	///
+	/// `outer_rows` is the estimated number of input rows, if it is known.
+	/// Returns the input wrapped in one join per decorrelated subquery,
+	/// together with the fields and OMIT list rewritten to read the joined
+	/// values in place of the subqueries.
+	pub(super) async fn plan_subquery_joins(
+		&self,
+		input: Arc<dyn ExecOperator>,
+		fields: Fields,
+		mut omit: Vec<Expr>,
+		outer_rows: Option<u64>,
+	) -> Result<(Arc<dyn ExecOperator>, Fields, Vec<Expr>), Error> {
+		let mut reserved: HashSet<String> = collect_field_names(&fields).into_iter().collect();
+		let has_wildcard = fields.has_all_selection();
+		let mut plan = input;
+		let fields = match fields {
+			Fields::Value(mut selector) => {
+				if let Some(subquery) = self.decorrelate(&selector.expr, true, outer_rows).await {
+					let output = next_join_field(&mut reserved);
+					let fallback = selector.expr.clone();
+					plan = self.plan_join(plan, subquery, output.clone(), fallback).await?;
+					selector.expr = Expr::Idiom(Idiom::field(output));
+				}
+				Fields::Value(selector)
+			}
+			Fields::Select(list) => {
+				let mut rewritten = Vec::with_capacity(list.len());
+				for field in list {
+					let Field::Single(selector) = field else {
+						rewritten.push(field);
+						continue;
+					};
+					// A graph lookup without an alias is output as a nested
+					// path, so only aliased lookups are rewritten
+					let simple_alias = selector
+						.alias
+						.as_ref()
+						.is_some_and(|a| a.len() == 1 && matches!(a.first(), Some(Part::Field(_))));
+					let subquery = self.decorrelate(&selector.expr, simple_alias, outer_rows).await;
+					let Some(subquery) = subquery else {
+						rewritten.push(Field::Single(selector));
+						continue;
+					};
+					let output = next_join_field(&mut reserved);
+					let fallback = selector.expr.clone();
+					plan = self.plan_join(plan, subquery, output.clone(), fallback).await?;
+					// Keep the original output name of the subquery field
+					let alias = selector
+						.alias
+						.unwrap_or_else(|| Idiom::field(derive_field_name(&selector.expr)));
+					rewritten.push(Field::Single(Selector {
+						expr: Expr::Idiom(Idiom::field(output.clone())),
+						alias: Some(alias),
+					}));
+					// The joined field must not leak through a wildcard
+					if has_wildcard {
+						omit.push(Expr::Idiom(Idiom::field(output)));
+					}
+				}
+				Fields::Select(rewritten)
+			}
+		};
+		Ok((plan, fields, omit))
+	}
+
+	/// Decorrelate a SELECT field, if it is eligible and a join is estimated
+	/// to be cheaper than evaluating it per row.
+	async fn decorrelate(
+		&self,
+		expr: &Expr,
+		lookups: bool,
+		outer_rows: Option<u64>,
+	) -> Option<DecorrelatedSubquery> {
+		let subquery = match expr {
+			Expr::Select(select) => decorrelate_subquery(select)?,
+			Expr::Idiom(idiom) if lookups => self.decorrelate_lookup(idiom).await?,
+			_ => return None,
+		};
+		if !self.join_pays_off(&subquery, outer_rows).await {
+			return None;
+		}
+		Some(subquery)
+	}
+
+	/// Estimate whether evaluating a subquery once for all rows is cheaper
+	/// than evaluating it once per outer row.
+	async fn join_pays_off(
+		&self,
+		subquery: &DecorrelatedSubquery,
+		outer_rows: Option<u64>,
+	) -> bool {
+		// A single outer row evaluates the subquery once either way
+		if outer_rows.is_some_and(|n| n <= 1) {
+			return false;
+		}
+		let (Some(txn), Some(ns), Some(db)) = (&self.txn, &self.ns, &self.db) else {
+			// Without the catalog nothing can be estimated, and the build
+			// side is bounded at runtime
+			return true;
+		};
+		let Ok(Some(db)) = txn.get_db_by_name(ns, db, None).await else {
+			return true;
+		};
+		let (ns, db) = (db.namespace_id, db.database_id);
+		// Find the index used by every per-row evaluation
+		let index = match &subquery.lookup {
+			PerRowLookup::Field(key) => {
+				let indexes = match txn.all_tb_indexes(ns, db, &subquery.table, None).await {
+					Ok(indexes) => indexes,
+					Err(_) => return true,
+				};
+				let index = indexes.iter().find(|ix| {
+					matches!(ix.index, Index::Idx | Index::Uniq) && ix.cols.first() == Some(key)
+				});
+				match index {
+					Some(ix) => Some(ix.clone()),
+					// Every per-row evaluation would scan the whole table
+					None => return true,
+				}
+			}
+			PerRowLookup::Graph(_) => None,
+		};
+		// The per-row lookups only read their matches, so the join needs a
+		// large or unknown number of outer rows to pay off
+		let Some(outer_rows) = outer_rows else {
+			return true;
+		};
+		let Ok(Some(statistics)) = txn.get_tb_statistics(ns, db, &subquery.table).await else {
+			return outer_rows > INDEXED_LOOKUP_ROWS;
+		};
+		let cost = CostModel::new(&statistics);
+		let lookup = match (&subquery.lookup, index) {
+			(_, Some(ix)) => cost.index_lookup(&ix),
+			(PerRowLookup::Graph(field), None) => cost.key_lookup(field),
+			(PerRowLookup::Field(_), None) => return true,
+		};
+		outer_rows as f64 * lookup.cost > cost.table_scan().cost
+	}
+
+	/// Rewrite an `->edge->table` (or `<-edge<-table`) lookup so that it can
+	/// be evaluated once for all outer rows, by reading the edge table.
+	///
+	/// The lookup returns the records at the other end of the edges of the
+	/// outer record, in edge order, which is what a scan of the edge table
+	/// filtered on the target tables returns, once grouped by the outer end.
+	/// Filters on the edges are kept, while filters on the target records
+	/// would need every target record to be read, and keep the per-row
+	/// evaluation. The edge table must only hold edges, and be readable
+	/// without permission checks, as the lookup reads the graph directly.
+	async fn decorrelate_lookup(&self, idiom: &Idiom) -> Option<DecorrelatedSubquery> {
+		let [Part::Lookup(edge), Part::Lookup(target)] = idiom.0.as_slice() else {
+			return None;
+		};
+		let (key, value) = match (&edge.kind, &target.kind) {
+			(LookupKind::Graph(Dir::Out), LookupKind::Graph(Dir::Out)) => ("in", "out"),
+			(LookupKind::Graph(Dir::In), LookupKind::Graph(Dir::In)) => ("out", "in"),
+			_ => return None,
+		};
+		if !is_plain_lookup(edge) || !is_plain_lookup(target) || target.cond.is_some() {
+			return None;
+		}
+		if edge.cond.as_ref().is_some_and(|c| references_outer_document(&c.0)) {
+			return None;
+		}
+		let [
+			LookupSubject::Table {
+				table,
+				referencing_field: None,
+			},
+		] = edge.what.as_slice()
+		else {
+			return None;
+		};
+		let mut targets = Vec::with_capacity(target.what.len());
+		for subject in &target.what {
+			let LookupSubject::Table {
+				table,
+				referencing_field: None,
+			} = subject
+			else {
+				return None;
+			};
+			targets.push(Expr::Literal(Literal::String(table.as_str().to_string())));
+		}
+		// Check that the edge table only holds edges, readable by anyone
+		let (Some(txn), Some(ns), Some(db)) = (&self.txn, &self.ns, &self.db) else {
+			return None;
+		};
+		let db = txn.get_db_by_name(ns, db, None).await.ok()??;
+		let tb = txn.get_tb(db.namespace_id, db.database_id, table, None).await.ok()??;
+		if !matches!(tb.table_type, TableType::Relation(_))
+			|| !matches!(tb.permissions.select, Permission::Full)
+		{
+			return None;
+		}
+		// Only keep the edges leading to the target tables
+		let on_targets = Expr::Binary {
+			left: Box::new(Expr::FunctionCall(Box::new(FunctionCall {
+				receiver: Function::Normal("record::tb".to_string()),
+				arguments: vec![Expr::Idiom(Idiom::field(value.to_string()))],
+			}))),
+			op: BinaryOperator::Inside,
+			right: Box::new(Expr::Literal(Literal::Array(targets))),
+		};
+		let cond = match &edge.cond {
+			Some(cond) => Expr::Binary {
+				left: Box::new(cond.0.clone()),
+				op: BinaryOperator::And,
+				right: Box::new(on_targets),
+			},
+			None => on_targets,
+		};
+		let field = |name: &str, alias: &str| {
+			Field::Single(Selector {
+				expr: Expr::Idiom(Idiom::field(name.to_string())),
+				alias: Some(Idiom::field(alias.to_string())),
+			})
+		};
+		let inner = SelectStatement {
+			fields: Fields::Select(vec![
+				field(value, JOIN_VALUE_FIELD),
+				field(key, JOIN_KEY_FIELD),
+			]),
+			omit: vec![],
+			only: false,
+			what: vec![Expr::Table(table.clone())],
+			with: None,
+			cond: Some(Cond(cond)),
+			split: None,
+			group: None,
+			order: None,
+			limit: None,
+			start: None,
+			fetch: None,
+			version: Expr::Literal(Literal::None),
+			timeout: Expr::Literal(Literal::None),
+			explain: None,
+			tempfiles: false,
+		};
+		Some(DecorrelatedSubquery {
+			inner,
+			build_key: JOIN_KEY_FIELD.to_string(),
+			probe_key: Idiom::field("id".to_string()),
+			value_only: true,
+			table: table.clone(),
+			lookup: PerRowLookup::Graph(key),
+		})
+	}
+
+	/// Plan the decorrelated subquery and join it onto the input.
+	///
+	/// `fallback` is the original correlated expression, which the join
+	/// evaluates per row when it can't match a row by key.
+	async fn plan_join(
+		&self,
+		input: Arc<dyn ExecOperator>,
+		subquery: DecorrelatedSubquery,
+		output: String,
+		fallback: Expr,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		let fallback = self.physical_expr(fallback).await?;
+		let probe_key = self.physical_expr(Expr::Idiom(subquery.probe_key.clone())).await?;
+		if let Some(build) = self.plan_merge_build(&input, &subquery).await? {
+			return Ok(Arc::new(MergeJoin::new(
+				input,
+				build,
+				subquery.build_key,
+				probe_key,
+				subquery.value_only,
+				output,
+				fallback,
+			)) as Arc<dyn ExecOperator>);
+		}
+		let DecorrelatedSubquery {
+			inner,
+			build_key,
+			value_only,
+			..
+		} = subquery;
+		let build = self.plan_select_statement(inner).await?;
+		Ok(Arc::new(HashJoin::new(
+			input,
+			build,
+			build_key,
+			probe_key,
+			value_only,
+			output,
+			fallback,
+			*HASH_JOIN_BUILD_LIMIT,
+		)) as Arc<dyn ExecOperator>)
+	}
+
+	/// Plan the build side of a merge join, if the input is sorted by record
+	/// id and an index returns the inner rows sorted by their join key.
+	async fn plan_merge_build(
+		&self,
+		input: &Arc<dyn ExecOperator>,
+		subquery: &DecorrelatedSubquery,
+	) -> Result<Option<Arc<dyn ExecOperator>>, Error> {
+		let PerRowLookup::Field(key) = &subquery.lookup else {
+			return Ok(None);
+		};
+		let by_id = SortProperty {
+			path: FieldPath::field("id"),
+			direction: SortDirection::Asc,
+			collate: false,
+			numeric: false,
+		};
+		if subquery.probe_key != Idiom::field("id".to_string())
+			|| subquery.inner.order.is_some()
+			|| !input.output_ordering().satisfies(&[by_id])
+		{
+			return Ok(None);
+		}
+		let mut inner = subquery.inner.clone();
+		inner.order = Some(Ordering::Order(OrderList(vec![Order {
+			value: key.clone(),
+			collate: false,
+			numeric: false,
+			direction: true,
+		}])));
+		let build = self.plan_select_statement(inner).await?;
+		// Without an index returning the rows in key order, the build side
+		// would be sorted in memory, so it's better off in a hash table
+		if contains_sort(&build) {
+			return Ok(None);
+		}
+		Ok(Some(build))
+	}
+}
+
+/// Estimate the number of rows a SELECT reads from its input, from the
+/// cardinality of the input and a literal LIMIT.
+///
+/// Without an ORDER BY, the LIMIT stops reading the input early, so the
+/// subqueries are only evaluated for the rows which are returned.
+pub(super) fn estimate_outer_rows(
+	input: &Arc<dyn ExecOperator>,
+	ordered: bool,
+	limit: &Option<Limit>,
+	start: &Option<Start>,
+) -> Option<u64> {
+	let rows = match input.cardinality_hint() {
+		CardinalityHint::AtMostOne => Some(1),
+		CardinalityHint::Bounded(n) => Some(n as u64),
+		CardinalityHint::Unbounded => None,
+	};
+	if ordered {
+		return rows;
+	}
+	match get_effective_limit_literal(start, limit) {
+		Some(limit) => Some(rows.map_or(limit as u64, |rows| rows.min(limit as u64))),
+		None => rows,
+	}
+}
+
+/// Whether a plan sorts its rows in memory.
+fn contains_sort(plan: &Arc<dyn ExecOperator>) -> bool {
+	matches!(
+		plan.name(),
+		"Sort" | "SortByKey" | "SortTopK" | "SortTopKByKey" | "ExternalSort" | "RandomShuffle"
+	) || plan.children().into_iter().any(contains_sort)
+}
+
+/// Whether a graph lookup step only names its subjects, and optionally
+/// filters them, without projecting, ordering or limiting them.
+fn is_plain_lookup(lookup: &Lookup) -> bool {
+	lookup.expr.is_none()
+		&& !lookup.only
+		&& lookup.split.is_none()
+		&& lookup.group.is_none()
+		&& lookup.order.is_none()
+		&& lookup.limit.is_none()
+		&& lookup.start.is_none()
+		&& lookup.alias.is_none()
+}
+
+/// Pick the next free `_jN` field name for a joined subquery.
+fn next_join_field(reserved: &mut HashSet<String>) -> String {
+	let mut n = 0;
+	loop {
+		let name = format!("_j{n}");
+		if reserved.insert(name.clone()) {
+			return name;
+		}
+		n += 1;
+	}
+}
+
+/// Rewrite a correlated subquery so that it can be evaluated once for all
+/// outer rows.
+///
+/// Returns `None` unless the subquery selects from a single table, is
+/// correlated through exactly one `field = $parent.path` predicate in its
+/// top-level WHERE AND chain, and has no clauses whose result depends on
+/// being evaluated per outer row (LIMIT, START, GROUP, SPLIT, FETCH, ONLY,
+/// VERSION, TIMEOUT, EXPLAIN).
+fn decorrelate_subquery(select: &SelectStatement) -> Option<DecorrelatedSubquery> {
+	if select.what.len() != 1
+		|| !matches!(select.what[0], Expr::Table(_))
+		|| select.only
+		|| select.split.is_some()
+		|| select.group.is_some()
+		|| select.limit.is_some()
+		|| select.start.is_some()
+		|| select.fetch.is_some()
+		|| select.explain.is_some()
+		|| !matches!(select.version, Expr::Literal(Literal::None))
+		|| !matches!(select.timeout, Expr::Literal(Literal::None))
+	{
+		return None;
+	}
+
+	let Expr::Table(table) = &select.what[0] else {
+		return None;
+	};
+	let (key, probe_key, cond) = extract_correlated_equality(select.cond.as_ref()?)?;
+
+	// Everything left must be independent of the outer row
+	if cond.as_ref().is_some_and(|c| references_outer_document(&c.0))
+		|| select.fields.iter_non_all_fields().any(|s| references_outer_document(&s.expr))
+		|| select.omit.iter().any(references_outer_document)
+	{
+		return None;
+	}
+	if let Some(Ordering::Order(list)) = &select.order
+		&& list.0.iter().any(|o| references_outer_document(&Expr::Idiom(o.value.clone())))
+	{
+		return None;
+	}
+
+	let mut inner = select.clone();
+	inner.cond = cond;
+	let key_field = |alias: &str| {
+		Field::Single(Selector {
+			expr: Expr::Idiom(key.clone()),
+			alias: Some(Idiom::field(alias.to_string())),
+		})
+	};
+
+	// Renaming a field under a wildcard drops the original, so a top-level
+	// key is read from the selected record as it is instead.
+	let wildcard_key = match key.first() {
+		Some(Part::Field(name)) if key.len() == 1 && inner.fields.has_all_selection() => {
+			Some(name.clone())
+		}
+		_ => None,
+	};
+	if let Some(name) = &wildcard_key {
+		let shadowed = collect_field_names(&inner.fields).contains(name)
+			|| inner.omit.iter().any(|o| match o {
+				Expr::Idiom(idiom) => idiom.first() == Some(&Part::Field(name.clone())),
+				_ => true,
+			});
+		if shadowed {
+			return None;
+		}
+	}
+
+	let (build_key, value_only) = match inner.fields {
+		Fields::Value(selector) => {
+			inner.fields = Fields::Select(vec![
+				Field::Single(Selector {
+					expr: selector.expr,
+					alias: Some(Idiom::field(JOIN_VALUE_FIELD.to_string())),
+				}),
+				key_field(JOIN_KEY_FIELD),
+			]);
+			(JOIN_KEY_FIELD.to_string(), true)
+		}
+		Fields::Select(ref mut list) => match wildcard_key {
+			Some(name) => (name, false),
+			None => {
+				list.push(key_field(JOIN_KEY_FIELD));
+				(JOIN_KEY_FIELD.to_string(), false)
+			}
+		},
+	};
+
+	Some(DecorrelatedSubquery {
+		inner,
+		build_key,
+		probe_key,
+		value_only,
+		table: table.clone(),
+		lookup: PerRowLookup::Field(key),
+	})
+}
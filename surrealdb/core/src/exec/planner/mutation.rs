@@ -0,0 +1,413 @@
+//! Write statement planning for the planner.
+//!
+//! CREATE, UPDATE, UPSERT, DELETE, INSERT and RELATE statements are planned
+//! as a [`Mutate`] sink. The tables targeted by UPDATE and DELETE statements
+//! are fed by the scan operators used for SELECT, so they stream their
+//! records in batches and use the access path picked by index analysis.
+//! Every other target is prepared by the statement when the sink runs. Any
+//! statement using a feature not supported here returns
+//! `Error::PlannerUnsupported` and falls back to compute.
+
+use std::sync::Arc;
+
+use super::Planner;
+use super::util::{has_knn_operator, has_matches_operator};
+use crate::catalog::providers::{DatabaseProvider, TableProvider};
+use crate::catalog::{DefineDefault, FieldDefinition, IndexDefinition};
+use crate::err::Error;
+use crate::exec::ExecOperator;
+use crate::exec::index::access_path::AccessPath;
+use crate::exec::operators::scan::resolved::ResolvedTableContext;
+use crate::exec::operators::{
+	IndexScan, Mutate, MutationKind, MutationSource, SpatialScan, TableScan, Timeout,
+	UnionIndexScan,
+};
+use crate::expr::statements::{
+	CreateStatement, DeleteStatement, InsertStatement, RelateStatement, UpdateStatement,
+	UpsertStatement,
+};
+use crate::expr::{Cond, Data, Expr, Idiom, Literal, Part, With};
+use crate::idx::planner::ScanDirection;
+use crate::val::TableName;
+
+impl<'ctx> Planner<'ctx> {
+	/// Plan a CREATE statement as a mutation sink.
+	pub(crate) async fn plan_create_statement(
+		&self,
+		stmt: CreateStatement,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		self.check_mutation_supported("CREATE", stmt.only, false).await?;
+		let sources = stmt.what.iter().cloned().map(MutationSource::Target).collect();
+		let timeout = stmt.timeout.clone();
+		let mutate = Mutate::new(MutationKind::Create(Arc::new(stmt)), sources);
+		self.plan_mutation_timeout(Arc::new(mutate), timeout).await
+	}
+
+	/// Plan an UPDATE statement as a mutation sink.
+	pub(crate) async fn plan_update_statement(
+		&self,
+		stmt: UpdateStatement,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		self.check_mutation_supported("UPDATE", stmt.only, stmt.explain.is_some()).await?;
+		let sources = self
+			.plan_mutation_sources(
+				&stmt.what,
+				stmt.cond.as_ref(),
+				stmt.with.as_ref(),
+				Some(stmt.data.as_ref()),
+			)
+			.await?;
+		let timeout = stmt.timeout.clone();
+		let mutate = Mutate::new(MutationKind::Update(Arc::new(stmt)), sources);
+		self.plan_mutation_timeout(Arc::new(mutate), timeout).await
+	}
+
+	/// Plan an UPSERT statement as a mutation sink.
+	pub(crate) async fn plan_upsert_statement(
+		&self,
+		stmt: UpsertStatement,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		self.check_mutation_supported("UPSERT", stmt.only, stmt.explain.is_some()).await?;
+		check_mutation_cond(stmt.cond.as_ref())?;
+		let sources = stmt.what.iter().cloned().map(MutationSource::Target).collect();
+		let timeout = stmt.timeout.clone();
+		let mutate = Mutate::new(MutationKind::Upsert(Arc::new(stmt)), sources);
+		self.plan_mutation_timeout(Arc::new(mutate), timeout).await
+	}
+
+	/// Plan a DELETE statement as a mutation sink.
+	pub(crate) async fn plan_delete_statement(
+		&self,
+		stmt: DeleteStatement,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		self.check_mutation_supported("DELETE", stmt.only, stmt.explain.is_some()).await?;
+		let sources = self
+			.plan_mutation_sources(&stmt.what, stmt.cond.as_ref(), stmt.with.as_ref(), None)
+			.await?;
+		let timeout = stmt.timeout.clone();
+		let mutate = Mutate::new(MutationKind::Delete(Arc::new(stmt)), sources);
+		self.plan_mutation_timeout(Arc::new(mutate), timeout).await
+	}
+
+	/// Plan an INSERT statement as a mutation sink.
+	pub(crate) async fn plan_insert_statement(
+		&self,
+		stmt: InsertStatement,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		self.check_mutation_supported("INSERT", false, false).await?;
+		let timeout = stmt.timeout.clone();
+		let mutate =
+			Mutate::new(MutationKind::Insert(Arc::new(stmt)), vec![MutationSource::Statement]);
+		self.plan_mutation_timeout(Arc::new(mutate), timeout).await
+	}
+
+	/// Plan a RELATE statement as a mutation sink.
+	pub(crate) async fn plan_relate_statement(
+		&self,
+		stmt: RelateStatement,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		self.check_mutation_supported("RELATE", stmt.only, false).await?;
+		let timeout = stmt.timeout.clone();
+		let mutate =
+			Mutate::new(MutationKind::Relate(Arc::new(stmt)), vec![MutationSource::Statement]);
+		self.plan_mutation_timeout(Arc::new(mutate), timeout).await
+	}
+
+	/// Reject write statements which the mutation sink does not handle.
+	async fn check_mutation_supported(
+		&self,
+		kind: &str,
+		only: bool,
+		explain: bool,
+	) -> Result<(), Error> {
+		if !self.ctx.new_planner_strategy().plans_writes() {
+			return Err(Error::PlannerUnsupported(format!(
+				"{kind} statements not yet supported in execution plans"
+			)));
+		}
+		if only {
+			return Err(Error::PlannerUnsupported(format!(
+				"{kind} ONLY statements not yet supported in execution plans"
+			)));
+		}
+		if explain {
+			return Err(Error::PlannerUnsupported(format!(
+				"{kind} statements with an EXPLAIN clause not yet supported in execution plans"
+			)));
+		}
+		// The compute path creates a missing namespace or database
+		let (Some(txn), Some(ns), Some(db)) = (&self.txn, &self.ns, &self.db) else {
+			return Err(Error::PlannerUnsupported(
+				"Write statements require a transaction and database to be planned".to_string(),
+			));
+		};
+		if !matches!(txn.get_db_by_name(ns, db, None).await, Ok(Some(_))) {
+			return Err(Error::PlannerUnsupported(format!(
+				"Database '{db}' not found at plan time"
+			)));
+		}
+		Ok(())
+	}
+
+	/// Plan the source feeding the mutation sink for each statement target.
+	///
+	/// Table targets are fed by a scan, planned with an unrestricted table
+	/// context, as the document pipeline checks the WHERE clause and the
+	/// statement permissions for every record. Every other target is
+	/// prepared when the sink runs.
+	///
+	/// For an UPDATE statement, `data` holds the statement's data clause.
+	/// An index whose columns the statement may change is not used, as its
+	/// scan could then observe the records which were already updated.
+	async fn plan_mutation_sources(
+		&self,
+		what: &[Expr],
+		cond: Option<&Cond>,
+		with: Option<&With>,
+		data: Option<Option<&Data>>,
+	) -> Result<Vec<MutationSource>, Error> {
+		let (Some(txn), Some(ns), Some(db)) = (&self.txn, &self.ns, &self.db) else {
+			return Err(Error::PlannerUnsupported(
+				"Write statements require a transaction and database to be planned".to_string(),
+			));
+		};
+		check_mutation_cond(cond)?;
+
+		let mut sources = Vec::with_capacity(what.len());
+		for expr in what {
+			let Expr::Table(table) = expr else {
+				sources.push(MutationSource::Target(expr.clone()));
+				continue;
+			};
+			// A missing table is reported by the compute path
+			let Some(tc) = Self::try_resolve_table_ctx(txn, self.ctx, ns, db, table).await else {
+				return Err(Error::PlannerUnsupported(format!(
+					"Table '{table}' not found at plan time"
+				)));
+			};
+			let tb = Arc::clone(&tc.table_def);
+			let tc = ResolvedTableContext::unrestricted(tc.table_def);
+			let mut path = self.resolve_access_path(txn, ns, db, table, cond, None, with).await?;
+			if let (Some(data), Some((p, direction, _))) = (data, &path) {
+				let direction = *direction;
+				let fields = txn
+					.all_tb_fields(tb.namespace_id, tb.database_id, table, None)
+					.await
+					.map_err(|e| Error::Internal(e.to_string()))?;
+				if path_may_change(p, data, &fields) {
+					path = Some((AccessPath::TableScan, direction, None));
+				}
+			}
+			let input: Arc<dyn ExecOperator> = match path {
+				Some((
+					AccessPath::BTreeScan {
+						index_ref,
+						access,
+						direction,
+					},
+					_,
+					estimated_rows,
+				)) => {
+					let scan = IndexScan::new(
+						index_ref,
+						access,
+						direction,
+						table.clone(),
+						None,
+						None,
+						None,
+						None,
+					)
+					.with_resolved(tc)
+					.with_ids_only()
+					.with_estimated_rows(estimated_rows);
+					Arc::new(scan)
+				}
+				Some((
+					AccessPath::SpatialSearch {
+						index_ref,
+						region,
+					},
+					_,
+					_,
+				)) => {
+					let scan = SpatialScan::new(index_ref, region, table.clone(), None, None)
+						.with_resolved(tc)
+						.with_ids_only();
+					Arc::new(scan)
+				}
+				Some((AccessPath::Union(paths), direction, estimated_rows)) => {
+					let mut sub_operators: Vec<Arc<dyn ExecOperator>> =
+						Vec::with_capacity(paths.len());
+					for path in paths {
+						let sub_op: Arc<dyn ExecOperator> = match path {
+							AccessPath::BTreeScan {
+								index_ref,
+								access,
+								direction,
+							} => Arc::new(
+								IndexScan::new(
+									index_ref,
+									access,
+									direction,
+									table.clone(),
+									None,
+									None,
+									None,
+									None,
+								)
+								.with_resolved(tc.clone())
+								.with_ids_only(),
+							),
+							AccessPath::SpatialSearch {
+								index_ref,
+								region,
+							} => Arc::new(
+								SpatialScan::new(index_ref, region, table.clone(), None, None)
+									.with_resolved(tc.clone())
+									.with_ids_only(),
+							),
+							// Any other sub-path scans the whole table
+							_ => table_scan(table, direction, tc.clone(), None),
+						};
+						sub_operators.push(sub_op);
+					}
+					let scan = UnionIndexScan::new(table.clone(), sub_operators, None)
+						.with_resolved(tc)
+						.with_estimated_rows(estimated_rows);
+					Arc::new(scan)
+				}
+				Some((AccessPath::TableScan, direction, estimated_rows)) => {
+					table_scan(table, direction, tc, estimated_rows)
+				}
+				Some((
+					AccessPath::FullTextSearch {
+						..
+					}
+					| AccessPath::KnnSearch {
+						..
+					}
+					| AccessPath::HybridSearch {
+						..
+					},
+					_,
+					_,
+				)) => {
+					return Err(Error::PlannerUnsupported(
+						"Write statements using full-text or KNN indexes not yet supported in execution plans"
+							.to_string(),
+					));
+				}
+				None => {
+					return Err(Error::PlannerUnsupported(
+						"Write statements require a namespace and database to be planned"
+							.to_string(),
+					));
+				}
+			};
+			sources.push(MutationSource::Scan {
+				table: table.clone(),
+				input,
+			});
+		}
+		Ok(sources)
+	}
+
+	/// Wrap the mutation sink with a Timeout operator when the statement
+	/// specifies a TIMEOUT clause.
+	async fn plan_mutation_timeout(
+		&self,
+		mutate: Arc<dyn ExecOperator>,
+		timeout: Expr,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		match timeout {
+			Expr::Literal(Literal::None) => Ok(mutate),
+			te => {
+				let tp = self.physical_expr(te).await?;
+				Ok(Arc::new(Timeout::new(mutate, Some(tp))))
+			}
+		}
+	}
+}
+
+/// Full-text and KNN operators depend on the query executor set up by the
+/// compute path.
+fn check_mutation_cond(cond: Option<&Cond>) -> Result<(), Error> {
+	if cond.is_some_and(|c| has_matches_operator(&c.0) || has_knn_operator(&c.0)) {
+		return Err(Error::PlannerUnsupported(
+			"Write statements with MATCHES or KNN conditions not yet supported in execution plans"
+				.to_string(),
+		));
+	}
+	Ok(())
+}
+
+/// A scan outputting the ids of every record in a table.
+fn table_scan(
+	table: &TableName,
+	direction: ScanDirection,
+	tc: ResolvedTableContext,
+	estimated_rows: Option<u64>,
+) -> Arc<dyn ExecOperator> {
+	let scan = TableScan::new(table.clone(), direction, None, None, None, None, None)
+		.with_resolved(tc)
+		.with_ids_only()
+		.with_estimated_rows(estimated_rows);
+	Arc::new(scan)
+}
+
+/// Whether an UPDATE statement may change the columns of an index used by
+/// an access path.
+fn path_may_change(path: &AccessPath, data: Option<&Data>, fields: &[FieldDefinition]) -> bool {
+	match path {
+		AccessPath::BTreeScan {
+			index_ref,
+			..
+		}
+		| AccessPath::SpatialSearch {
+			index_ref,
+			..
+		} => index_may_change(index_ref, data, fields),
+		AccessPath::Union(paths) => paths.iter().any(|p| path_may_change(p, data, fields)),
+		_ => false,
+	}
+}
+
+/// Whether an UPDATE statement may change the columns of an index.
+///
+/// Columns are compared by their top-level field. Fields which are
+/// recomputed on every update, and data clauses which replace or merge
+/// whole documents, are assumed to change every column.
+fn index_may_change(ix: &IndexDefinition, data: Option<&Data>, fields: &[FieldDefinition]) -> bool {
+	let mut roots = Vec::with_capacity(ix.cols.len());
+	for col in ix.cols.iter() {
+		let Some(root) = idiom_root(col) else {
+			return true;
+		};
+		roots.push(root);
+	}
+	let touches = |idiom: &Idiom| idiom_root(idiom).is_none_or(|r| roots.contains(&r));
+	let recomputed = fields.iter().any(|f| {
+		(f.value.is_some() || f.computed.is_some() || matches!(f.default, DefineDefault::Always(_)))
+			&& touches(&f.name)
+	});
+	if recomputed {
+		return true;
+	}
+	match data {
+		None | Some(Data::EmptyExpression) => false,
+		Some(Data::SetExpression(assignments) | Data::UpdateExpression(assignments)) => {
+			assignments.iter().any(|a| touches(&a.place))
+		}
+		Some(Data::UnsetExpression(idioms)) => idioms.iter().any(touches),
+		Some(_) => true,
+	}
+}
+
+/// The top-level field of an idiom, if it starts with one.
+fn idiom_root(idiom: &Idiom) -> Option<&str> {
+	match idiom.0.first() {
+		Some(Part::Field(name)) => Some(name.as_str()),
+		_ => None,
+	}
+}
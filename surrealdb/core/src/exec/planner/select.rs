@@ -1,7 +1,7 @@
 //! SELECT statement planning for the planner.
 //!
 //! Handles the full SELECT pipeline: source → filter → split → aggregate →
-//! sort → limit → project → fetch → timeout.
+//! join → sort → limit → project → fetch → timeout.
 //!
 //! Projection uses a fast path that classifies SELECT fields at plan time:
 //! - **Simple field paths** (e.g. `name`, `age`): handled by `SelectProject` with synchronous field
@@ -19,6 +19,7 @@ use std::sync::Arc;
 use surrealdb_types::ToSql;
 
 use super::Planner;
+use super::join::estimate_outer_rows;
 use super::util::{
 	SELECT_ITERATION_PARAMS, all_value_sources, check_forbidden_group_by_params, derive_field_name,
 	extract_bruteforce_knn, extract_count_field_names, extract_matches_context,
@@ -249,6 +250,15 @@ impl<'ctx> Planner<'ctx> {
 			(split_op, false)
 		};
 
+		// Decorrelate subqueries in the projection into joins. This happens
+		// before sorting so that ORDER BY can refer to their aliases.
+		let (grouped, fields, omit) = if skip_projections {
+			(grouped, fields, omit)
+		} else {
+			let outer_rows = estimate_outer_rows(&grouped, order.is_some(), &limit, &start);
+			self.plan_subquery_joins(grouped, fields, omit, outer_rows).await?
+		};
+
 		// Shared expression registry for deduplication across sort and projection.
 		// Expressions computed for ORDER BY are reused by the projection step.
 		// Reserve the SELECT field names so that synthetic `_eN` names never
@@ -1433,6 +1443,7 @@ impl<'ctx> Planner<'ctx> {
 			})
 		});
 		let has_knn_early = cond.as_ref().is_some_and(|c| has_knn_operator(&c.0));
+		let has_fusion = matches!(with, Some(crate::expr::with::With::Fusion(_)));
 
 		let planning_ctx: std::borrow::Cow<'_, crate::ctx::FrozenContext> =
 			if let Some(ref c) = cond {
@@ -1445,6 +1456,9 @@ impl<'ctx> Planner<'ctx> {
 						if let Some(ref t) = primary_table {
 							mc.set_table(t.clone());
 						}
+						if has_fusion {
+							mc.set_fusion(Arc::new(crate::exec::function::FusionContext::new()));
+						}
 						child.set_matches_context(mc);
 					}
 					if has_knn_early {
@@ -1538,6 +1552,24 @@ impl<'ctx> Planner<'ctx> {
 			(c.clone(), c)
 		};
 
+		// A hybrid search fuses an indexed KNN search with a full-text search
+		if has_fusion {
+			if !has_knn || planning_ctx.get_matches_context().is_none() {
+				return Err(Error::Query {
+					message: "WITH FUSION requires both a KNN operator and a MATCHES operator \
+					 in the WHERE clause"
+						.to_string(),
+				});
+			}
+			if brute_force_knn.is_some() {
+				return Err(Error::Query {
+					message: "WITH FUSION requires an indexed KNN search, \
+					 brute-force KNN operators are not supported"
+						.to_string(),
+				});
+			}
+		}
+
 		let scan_predicate = if source_is_single_scan {
 			match cond_for_filter.as_ref() {
 				Some(c) => Some(pp.physical_expr(c.0.clone()).await?),
@@ -1770,9 +1802,9 @@ impl<'ctx> Planner<'ctx> {
 	///
 	/// When the planner has a transaction and the source is a table,
 	/// resolves the access path at plan time and creates the concrete
-	/// operator (IndexScan, FullTextScan, KnnScan) directly. This
-	/// avoids redundant index analysis at execution time and enables
-	/// sort elimination via `output_ordering()`.
+	/// operator (IndexScan, FullTextScan, KnnScan, SpatialScan, HybridScan)
+	/// directly. This avoids redundant index analysis at execution time and
+	/// enables sort elimination via `output_ordering()`.
 	///
 	/// Without a transaction, creates a generic `Scan` that resolves
 	/// its access path at execution time.
@@ -1789,7 +1821,8 @@ impl<'ctx> Planner<'ctx> {
 		scan_limit: Option<Arc<dyn crate::exec::PhysicalExpr>>,
 		scan_start: Option<Arc<dyn crate::exec::PhysicalExpr>>,
 	) -> Result<PlannedSource, Error> {
-		use crate::exec::operators::{FullTextScan, IndexScan, KnnScan};
+		use crate::exec::function::MatchContext;
+		use crate::exec::operators::{FullTextScan, HybridScan, IndexScan, KnnScan, SpatialScan};
 
 		// Optimisation: WHERE id = <RecordId> -> point lookup.
 		// Detects `id = <RecordId literal>` in the top-level AND chain and
@@ -1833,9 +1866,19 @@ impl<'ctx> Planner<'ctx> {
 
 			let resolved =
 				self.resolve_access_path(txn, ns, db, table_name, cond, order, with).await;
-			if let Ok(Some((access_path, direction))) = resolved {
+			if let Ok(Some((access_path, direction, estimated_rows))) = resolved {
 				let table = table_name.clone();
 				let knn_ctx = self.ctx.get_knn_context().cloned();
+				if matches!(with, Some(crate::expr::with::With::Fusion(_)))
+					&& !matches!(access_path, AccessPath::HybridSearch { .. })
+				{
+					return Err(Error::Query {
+						message: format!(
+							"WITH FUSION requires a vector index and a full-text index \
+							 matching the WHERE clause on table '{table}'"
+						),
+					});
+				}
 				match access_path {
 					AccessPath::BTreeScan {
 						index_ref,
@@ -1902,7 +1945,8 @@ impl<'ctx> Planner<'ctx> {
 							version.clone(),
 							Some(needed_fields),
 						)
-						.with_batch_ceiling(batch_ceiling);
+						.with_batch_ceiling(batch_ceiling)
+						.with_estimated_rows(estimated_rows);
 						if let Some(ref tc) = table_ctx {
 							scan = scan.with_resolved(tc.clone());
 						}
@@ -1972,6 +2016,126 @@ impl<'ctx> Planner<'ctx> {
 							limit_pushed: false,
 						});
 					}
+					AccessPath::SpatialSearch {
+						index_ref,
+						region,
+					} => {
+						// The spatial index returns a superset of the matching
+						// records, so the original condition is always applied
+						let mut scan = SpatialScan::new(
+							index_ref,
+							region,
+							table,
+							version.clone(),
+							Some(needed_fields),
+						);
+						if let Some(ref tc) = table_ctx {
+							scan = scan.with_resolved(tc.clone());
+						}
+						return Ok(PlannedSource {
+							operator: Arc::new(scan) as Arc<dyn ExecOperator>,
+							filter_action: FilterAction::UseOriginal,
+							limit_pushed: false,
+						});
+					}
+					AccessPath::HybridSearch {
+						knn,
+						fulltext,
+						fusion,
+					} => {
+						let (
+							AccessPath::KnnSearch {
+								index_ref: knn_index,
+								vector,
+								k,
+								ef,
+							},
+							AccessPath::FullTextSearch {
+								index_ref: ft_index,
+								query,
+								operator,
+							},
+						) = (*knn, *fulltext)
+						else {
+							return Err(Error::Query {
+								message: "Invalid hybrid search access path".to_string(),
+							});
+						};
+						// Both searches are planned in sub-operator mode;
+						// HybridScan handles permissions and computed fields.
+						// The KNN search only considers candidates matching
+						// the non-search predicates of the WHERE clause.
+						let residual_cond = cond
+							.and_then(strip_knn_from_condition)
+							.and_then(|c| strip_fts_condition(&c));
+						// The KNN and fusion contexts are set up whenever
+						// the condition holds a hybrid search.
+						let Some(knn_ctx) = knn_ctx else {
+							return Err(Error::Query {
+								message: "Missing KNN context for hybrid search".to_string(),
+							});
+						};
+						let mut knn_scan = KnnScan::new(
+							knn_index,
+							vector,
+							k,
+							ef,
+							table.clone(),
+							version.clone(),
+							Some(knn_ctx.clone()),
+							residual_cond.clone(),
+							None,
+						);
+						let match_ctx = Arc::new(MatchContext::new(
+							ft_index.cols[0].clone(),
+							query.clone(),
+							operator.clone(),
+							table.clone(),
+						));
+						let mut ft_scan = FullTextScan::new(
+							ft_index,
+							query,
+							operator,
+							table.clone(),
+							version.clone(),
+							None,
+						);
+						if let Some(ref tc) = table_ctx {
+							knn_scan = knn_scan.with_resolved(tc.clone());
+							ft_scan = ft_scan.with_resolved(tc.clone());
+						}
+						let Some(fusion_ctx) =
+							self.ctx.get_matches_context().and_then(|mc| mc.fusion().cloned())
+						else {
+							return Err(Error::Query {
+								message: "Missing fusion context for hybrid search".to_string(),
+							});
+						};
+						let mut scan = HybridScan::new(
+							table,
+							Arc::new(knn_scan),
+							Arc::new(ft_scan),
+							fusion,
+							match_ctx,
+							knn_ctx,
+							fusion_ctx,
+							needed_fields,
+						);
+						if let Some(ref tc) = table_ctx {
+							scan = scan.with_resolved(tc.clone());
+						}
+						// The full-text results are not filtered by the
+						// residual predicates, so they are applied here.
+						let filter_action = match residual_cond {
+							None => FilterAction::FullyConsumed,
+							Some(residual) => FilterAction::Residual(residual),
+						};
+						return Ok(PlannedSource {
+							operator: Arc::new(scan) as Arc<dyn ExecOperator>,
+							filter_action,
+							limit_pushed: false,
+						});
+					}
 					AccessPath::TableScan => {
 						let filter_action = filter_action_for_predicate(&scan_predicate);
 						// TableScan can only provide ordering for `id ASC/DESC`.
@@ -1991,7 +2155,8 @@ impl<'ctx> Planner<'ctx> {
 							tbl_limit,
 							tbl_start,
 							needed_fields,
-						);
+						)
+						.with_estimated_rows(estimated_rows);
 						if let Some(tc) = table_ctx.clone() {
 							scan = scan.with_resolved(tc);
 						}
@@ -2110,6 +2275,22 @@ impl<'ctx> Planner<'ctx> {
 									}
 									Arc::new(scan)
 								}
+								AccessPath::SpatialSearch {
+									index_ref,
+									region,
+								} => {
+									let mut scan = SpatialScan::new(
+										index_ref,
+										region,
+										table.clone(),
+										version.clone(),
+										None,
+									);
+									if let Some(ref tc) = table_ctx {
+										scan = scan.with_resolved(tc.clone());
+									}
+									Arc::new(scan)
+								}
 								// TableScan and nested Union should not
 								// appear as sub-paths; fall back safely.
 								_ => Arc::new(TableScan::new(
@@ -2129,7 +2310,8 @@ impl<'ctx> Planner<'ctx> {
 						// (same pattern as TableScan). The outer
 						// pipeline handles Filter, Sort, and Limit.
 						let mut union_scan =
-							UnionIndexScan::new(table, sub_operators, needed_fields);
+							UnionIndexScan::new(table, sub_operators, needed_fields)
+								.with_estimated_rows(estimated_rows);
 						if let Some(dir) = merge_dir {
 							union_scan = union_scan.with_merge_by_id(dir);
 						}
@@ -2146,6 +2328,13 @@ impl<'ctx> Planner<'ctx> {
 			}
 		}
 
+		// Hybrid searches need their access path resolved at plan time
+		if matches!(with, Some(crate::expr::with::With::Fusion(_))) {
+			return Err(Error::Query {
+				message: "WITH FUSION requires a table source".to_string(),
+			});
+		}
+
 		// Fallback: create the appropriate operator (index resolved at runtime)
 		let knn_ctx = self.ctx.get_knn_context().cloned();
 
@@ -2273,7 +2462,7 @@ impl<'ctx> Planner<'ctx> {
 	/// Returns `None` if namespace/database lookup fails or the table doesn't
 	/// exist. Errors in field state resolution are silently ignored (the
 	/// operator will fall back to runtime resolution).
-	async fn try_resolve_table_ctx(
+	pub(super) async fn try_resolve_table_ctx(
 		txn: &crate::kvs::Transaction,
 		ctx: &crate::ctx::FrozenContext,
 		ns: &str,
@@ -2392,10 +2581,11 @@ impl<'ctx> Planner<'ctx> {
 	/// Resolve the optimal access path for a table at plan time.
 	///
 	/// Performs index analysis using the WHERE condition and ORDER BY clause.
-	/// Returns the selected `AccessPath` and scan direction, or `None` if
-	/// the namespace/database cannot be resolved.
+	/// Returns the selected `AccessPath`, the scan direction and, when the
+	/// table has statistics, the estimated number of records read, or `None`
+	/// if the namespace/database cannot be resolved.
 	#[allow(clippy::too_many_arguments)]
-	async fn resolve_access_path(
+	pub(super) async fn resolve_access_path(
 		&self,
 		txn: &crate::kvs::Transaction,
 		ns_name: &str,
@@ -2404,11 +2594,11 @@ impl<'ctx> Planner<'ctx> {
 		cond: Option<&Cond>,
 		order: Option<&crate::expr::order::Ordering>,
 		with: Option<&crate::expr::with::With>,
-	) -> Result<Option<(AccessPath, crate::idx::planner::ScanDirection)>, Error> {
+	) -> Result<Option<(AccessPath, crate::idx::planner::ScanDirection, Option<u64>)>, Error> {
 		let direction = determine_scan_direction(order);
 
 		if matches!(with, Some(crate::expr::with::With::NoIndex)) {
-			return Ok(Some((AccessPath::TableScan, direction)));
+			return Ok(Some((AccessPath::TableScan, direction, None)));
 		}
 
 		// Look up namespace and database to get IDs
@@ -2431,9 +2621,20 @@ impl<'ctx> Planner<'ctx> {
 		};
 
 		if indexes.is_empty() {
-			return Ok(Some((AccessPath::TableScan, direction)));
+			return Ok(Some((AccessPath::TableScan, direction, None)));
 		}
 
+		// Fetch the statistics collected by ANALYZE TABLE, if any
+		let statistics = txn
+			.get_tb_statistics(ns_def.namespace_id, db_def.database_id, table_name)
+			.await
+			.ok()
+			.flatten();
+		let estimate = |path: &AccessPath| -> Option<u64> {
+			let cost = crate::idx::planner::cost::CostModel::new(statistics.as_deref()?);
+			path.estimate(&cost).map(|e| e.rows.round() as u64)
+		};
+
 		// Rewrite projection function calls (e.g. type::field("name")) →
 		// Idiom in a cloned condition so the index analyzer can match
 		// against indexed columns.
@@ -2444,25 +2645,22 @@ impl<'ctx> Planner<'ctx> {
 		});
 		let analysis_cond = rewritten_cond.as_ref();
 
-		let analyzer = IndexAnalyzer::new(indexes, with);
+		let analyzer = IndexAnalyzer::new(indexes, with).with_statistics(statistics.clone());
 		let candidates = analyzer.analyze(analysis_cond, order);
 
 		if candidates.is_empty() {
-			if let Some(path) = analyzer.try_or_union(analysis_cond, direction) {
-				return Ok(Some((path, direction)));
-			}
-			// Try expanding IN operators into union of equality lookups
-			if let Some(path) = analyzer.try_in_expansion(analysis_cond, direction) {
-				return Ok(Some((path, direction)));
-			}
-			// Try expanding CONTAINSALL/CONTAINSANY into union of equality lookups
-			if let Some(path) = analyzer.try_containment_expansion(analysis_cond, direction) {
-				return Ok(Some((path, direction)));
-			}
-			return Ok(Some((AccessPath::TableScan, direction)));
+			let path = analyzer
+				.try_or_union(analysis_cond, direction)
+				// Try expanding IN operators into union of equality lookups
+				.or_else(|| analyzer.try_in_expansion(analysis_cond, direction))
+				// Try expanding CONTAINSALL/CONTAINSANY into union of equality lookups
+				.or_else(|| analyzer.try_containment_expansion(analysis_cond, direction))
+				.unwrap_or(AccessPath::TableScan);
+			let rows = estimate(&path);
+			return Ok(Some((path, direction, rows)));
 		}
 
-		let path = select_access_path(candidates, with, direction);
+		let path = select_access_path(candidates, with, direction, statistics.as_deref());
 
 		// When the chosen index covers ORDER BY, derive the correct scan
 		// direction from the ORDER BY clause rather than the default
@@ -2480,7 +2678,8 @@ impl<'ctx> Planner<'ctx> {
 		if path.is_full_range_scan()
 			&& let Some(union_path) = analyzer.try_or_union(analysis_cond, direction)
 		{
-			return Ok(Some((union_path, direction)));
+			let rows = estimate(&union_path);
+			return Ok(Some((union_path, direction, rows)));
 		}
 		// NOTE: We intentionally do NOT try try_in_expansion() here.
 		// The full-range scan covers ORDER BY, enabling sort elimination
@@ -2491,7 +2690,8 @@ impl<'ctx> Planner<'ctx> {
 		// candidates.is_empty() fallback above when no index covers
 		// ORDER BY at all.
 
-		Ok(Some((path, direction)))
+		let rows = estimate(&path);
+		Ok(Some((path, direction, rows)))
 	}
 }
 
@@ -2644,7 +2844,7 @@ fn adjust_direction_for_order(
 /// These names are passed to `ExpressionRegistry::with_reserved_names` so that
 /// synthetic internal names (`_e0`, `_e1`, ...) do not collide with fields the
 /// user explicitly selected.
-fn collect_field_names(fields: &Fields) -> Vec<String> {
+pub(super) fn collect_field_names(fields: &Fields) -> Vec<String> {
 	match fields {
 		Fields::Value(_) => vec![], // SELECT VALUE has no object fields
 		Fields::Select(field_list) => {
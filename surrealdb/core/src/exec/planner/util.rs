@@ -303,6 +303,35 @@ pub(super) fn has_knn_operator(expr: &Expr) -> bool {
 	checker.found_any
 }
 
+/// Check if an expression contains any full-text MATCHES operators.
+pub(super) fn has_matches_operator(expr: &Expr) -> bool {
+	struct MatchesOperatorChecker {
+		found: bool,
+	}
+
+	impl Visitor for MatchesOperatorChecker {
+		type Error = std::convert::Infallible;
+
+		fn visit_expr(&mut self, expr: &Expr) -> Result<(), Self::Error> {
+			if let Expr::Binary {
+				op: BinaryOperator::Matches(_),
+				..
+			} = expr
+			{
+				self.found = true;
+			}
+			expr.visit(self)
+		}
+	}
+
+	let mut checker = MatchesOperatorChecker {
+		found: false,
+	};
+	// The checker is infallible, so there is no error to handle
+	let _ = checker.visit_expr(expr);
+	checker.found
+}
+
 /// Check if an expression contains a brute-force KNN operator (`NearestNeighbor::K`).
 ///
 /// Used to distinguish between brute-force KNN with parameter-based vectors
@@ -387,9 +416,10 @@ pub(crate) fn strip_fts_condition(cond: &Cond) -> Option<Cond> {
 
 /// Strip handled KNN operators from a WHERE clause, returning the residual condition.
 ///
-/// Both `NearestNeighbor::K` (consumed by `KnnTopK`) and `NearestNeighbor::Approximate`
-/// (consumed by `KnnScan` via HNSW index) are stripped. `KTree` is left in place --
-/// the caller should verify the residual contains no remaining KNN operators and
+/// `NearestNeighbor::K` (consumed by `KnnTopK`), `NearestNeighbor::Approximate`
+/// (consumed by `KnnScan` via an HNSW or IVF-PQ index) and `NearestNeighbor::KTree`
+/// (consumed by `KnnScan` via an IVF-PQ index) are stripped from AND chains. The
+/// caller should verify the residual contains no remaining KNN operators and
 /// return an error if it does.
 pub(crate) fn strip_knn_from_condition(cond: &Cond) -> Option<Cond> {
 	let mut expr = cond.0.clone();
@@ -402,6 +432,29 @@ pub(crate) fn strip_knn_from_condition(cond: &Cond) -> Option<Cond> {
 	}
 }
 
+/// Extract the correlated equality from the WHERE clause of a subquery.
+///
+/// Looks through the top-level AND chain for the first `field = $parent.path`
+/// (or `$parent.path = field`) predicate, where both sides are plain field
+/// paths. Returns the inner field idiom, the outer `path` idiom (without the
+/// `$parent` anchor), and the residual condition with that predicate removed.
+pub(super) fn extract_correlated_equality(cond: &Cond) -> Option<(Idiom, Idiom, Option<Cond>)> {
+	let mut expr = cond.0.clone();
+	let mut extractor = CorrelationExtractor {
+		found: None,
+	};
+	// Both visitors are infallible, so there is no error to handle
+	let _ = extractor.visit_mut_expr(&mut expr);
+	let (inner, outer) = extractor.found?;
+	let _ = BoolSimplifier.visit_mut_expr(&mut expr);
+	let residual = if matches!(expr, Expr::Literal(Literal::Bool(true))) {
+		None
+	} else {
+		Some(Cond(expr))
+	};
+	Some((inner, outer, residual))
+}
+
 // ---------------------------------------------------------------------------
 // Index condition stripping
 // ---------------------------------------------------------------------------
@@ -590,8 +643,8 @@ impl MutVisitor for IndexConditionStripper<'_> {
 // MutVisitors for KNN condition rewriting
 // ---------------------------------------------------------------------------
 
-/// Replaces handled KNN expressions (`NearestNeighbor::K` and
-/// `NearestNeighbor::Approximate`) with `Literal::Bool(true)`.
+/// Replaces KNN expressions (`NearestNeighbor::K`, `NearestNeighbor::Approximate`
+/// and `NearestNeighbor::KTree`) with `Literal::Bool(true)`.
 /// Run `BoolSimplifier` afterwards to collapse the resulting
 /// `true AND x` chains.
 struct KnnStripper;
@@ -602,12 +655,10 @@ impl MutVisitor for KnnStripper {
 	fn visit_mut_expr(&mut self, expr: &mut Expr) -> Result<(), Self::Error> {
 		// Replace handled KNN expressions with `true`.
 		if let Expr::Binary {
-			op: BinaryOperator::NearestNeighbor(nn),
+			op: BinaryOperator::NearestNeighbor(_),
 			..
-		} = expr && matches!(
-			nn.as_ref(),
-			NearestNeighbor::K(..) | NearestNeighbor::Approximate(..)
-		) {
+		} = expr
+		{
 			*expr = Expr::Literal(Literal::Bool(true));
 			return Ok(());
 		}
@@ -716,6 +767,97 @@ impl MutVisitor for BruteForceKnnExtractor {
 	}
 }
 
+/// Replaces the first `field = $parent.path` predicate in an AND chain with
+/// `Literal::Bool(true)`, recording both sides.
+/// Run `BoolSimplifier` afterwards to collapse the resulting
+/// `true AND x` chains.
+struct CorrelationExtractor {
+	found: Option<(Idiom, Idiom)>,
+}
+
+impl CorrelationExtractor {
+	/// Check whether an expression is a plain field path like `a.b.c`.
+	fn as_field_path(expr: &Expr) -> Option<&Idiom> {
+		use crate::expr::part::Part;
+
+		match expr {
+			Expr::Idiom(idiom)
+				if !idiom.is_empty() && idiom.iter().all(|p| matches!(p, Part::Field(_))) =>
+			{
+				Some(idiom)
+			}
+			_ => None,
+		}
+	}
+
+	/// Check whether an expression is `$parent` followed by a plain field
+	/// path, returning the path without the `$parent` anchor.
+	fn as_parent_path(expr: &Expr) -> Option<Idiom> {
+		use crate::expr::part::Part;
+
+		let Expr::Idiom(idiom) = expr else {
+			return None;
+		};
+		let (Part::Start(Expr::Param(param)), rest) = idiom.split_first()? else {
+			return None;
+		};
+		if param.as_str() != "parent"
+			|| rest.is_empty()
+			|| !rest.iter().all(|p| matches!(p, Part::Field(_)))
+		{
+			return None;
+		}
+		Some(Idiom(rest.to_vec()))
+	}
+}
+
+impl MutVisitor for CorrelationExtractor {
+	type Error = std::convert::Infallible;
+
+	fn visit_mut_expr(&mut self, expr: &mut Expr) -> Result<(), Self::Error> {
+		if self.found.is_some() {
+			return Ok(());
+		}
+		match expr {
+			Expr::Binary {
+				left,
+				op: BinaryOperator::And,
+				right,
+			} => {
+				self.visit_mut_expr(left)?;
+				self.visit_mut_expr(right)?;
+			}
+			Expr::Binary {
+				left,
+				op: BinaryOperator::Equal,
+				right,
+			} => {
+				let found = match (Self::as_field_path(left), Self::as_parent_path(right)) {
+					(Some(inner), Some(outer)) => Some((inner.clone(), outer)),
+					_ => match (Self::as_parent_path(left), Self::as_field_path(right)) {
+						(Some(outer), Some(inner)) => Some((inner.clone(), outer)),
+						_ => None,
+					},
+				};
+				if found.is_some() {
+					self.found = found;
+					*expr = Expr::Literal(Literal::Bool(true));
+				}
+			}
+			_ => {}
+		}
+		Ok(())
+	}
+
+	// Don't descend into subqueries.
+	fn visit_mut_select(
+		&mut self,
+		_: &mut crate::expr::SelectStatement,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
+
 /// Reusable postorder pass that collapses boolean-literal sentinels in AND
 /// chains: `true AND x → x`, `x AND true → x`, `true AND true → true`.
 ///
@@ -1137,6 +1279,7 @@ impl Visitor for MatchesCollector<'_> {
 					crate::exec::function::MatchInfo {
 						idiom: idiom.clone(),
 						query,
+						operator: matches_op.clone(),
 					},
 				);
 			}
@@ -1231,6 +1374,34 @@ impl Visitor for ForbiddenParamChecker {
 	}
 }
 
+/// Check if an expression refers to the document of an enclosing statement
+/// through `$this`, `$self`, or `$parent`.
+///
+/// Nested subqueries are not inspected, as they bind these parameters to
+/// their own documents.
+pub(super) fn references_outer_document(expr: &Expr) -> bool {
+	expr.visit(&mut OuterDocumentFinder).is_err()
+}
+
+/// Visitor that fails on the first `$this`, `$self`, or `$parent` parameter
+/// outside of a nested subquery.
+struct OuterDocumentFinder;
+
+impl Visitor for OuterDocumentFinder {
+	type Error = ();
+
+	fn visit_param(&mut self, param: &Param) -> Result<(), Self::Error> {
+		if matches!(param.as_str(), "this" | "self" | "parent") {
+			return Err(());
+		}
+		Ok(())
+	}
+
+	fn visit_select(&mut self, _: &crate::expr::SelectStatement) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
+
 // ============================================================================
 // Pushdown Eligibility
 // ============================================================================
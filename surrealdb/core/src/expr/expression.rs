@@ -13,10 +13,10 @@ use crate::err::Error;
 use crate::expr::closure::ClosureExpr;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::statements::{
-	AlterStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
-	IfelseStatement, InfoStatement, InsertStatement, OutputStatement, RebuildStatement,
-	RelateStatement, RemoveStatement, SelectStatement, SetStatement, UpdateStatement,
-	UpsertStatement,
+	AlterStatement, AnalyzeStatement, CreateStatement, DefineStatement, DeleteStatement,
+	ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, OutputStatement,
+	RebuildStatement, RelateStatement, RemoveStatement, SelectStatement, SetStatement,
+	UpdateStatement, UpsertStatement,
 };
 use crate::expr::{
 	BinaryOperator, Block, Constant, ControlFlow, FlowResult, FunctionCall, Idiom, Literal, Mock,
@@ -78,6 +78,7 @@ pub(crate) enum Expr {
 	Define(Box<DefineStatement>),
 	Remove(Box<RemoveStatement>),
 	Rebuild(Box<RebuildStatement>),
+	Analyze(Box<AnalyzeStatement>),
 	Alter(Box<AlterStatement>),
 	Info(Box<InfoStatement>),
 	Foreach(Box<ForeachStatement>),
@@ -139,6 +140,7 @@ impl Expr {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Analyze(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_) => false,
 		}
@@ -314,6 +316,7 @@ impl Expr {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Analyze(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_)
 			| Expr::Info(_)
@@ -437,6 +440,9 @@ impl Expr {
 			Expr::Rebuild(rebuild_statement) => {
 				rebuild_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
 			}
+			Expr::Analyze(analyze_statement) => {
+				analyze_statement.compute(ctx, &opt, doc).await.map_err(ControlFlow::Err)
+			}
 			Expr::Upsert(upsert_statement) => {
 				upsert_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
 			}
@@ -762,6 +768,7 @@ impl Expr {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Analyze(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_)
 			| Expr::Info(_)
@@ -1,9 +1,18 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
 use revision::revisioned;
 use surrealdb_types::{SqlFormat, ToSql};
 
+use crate::buc::BucketController;
+use crate::buc::store::ObjectKey;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
 use crate::expr::language::Language;
+use crate::val::File;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Filter {
 	Ascii,
@@ -13,6 +22,42 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	#[revision(start = 2)]
+	Stopwords(WordList),
+	#[revision(start = 2)]
+	Synonyms(WordList),
+}
+
+impl Filter {
+	/// Reads the content of the bucket files used by the filter, so that the
+	/// stored analyzer does not depend on the bucket anymore.
+	pub(crate) async fn resolve(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<()> {
+		if let Self::Stopwords(WordList::File(file, lines))
+		| Self::Synonyms(WordList::File(file, lines)) = self
+		{
+			let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+			let Some(bytes) = controller.get(&ObjectKey::new(file.key.clone())).await? else {
+				bail!(Error::AnalyzerError(format!(
+					"The file {} does not exist",
+					file.display_inner()
+				)));
+			};
+			let content = String::from_utf8(bytes.0.to_vec()).map_err(|_| {
+				Error::AnalyzerError(format!(
+					"The file {} is not a valid UTF-8 text file",
+					file.display_inner()
+				))
+			})?;
+			*lines = content.lines().map(str::to_owned).collect();
+		}
+		Ok(())
+	}
 }
 
 impl ToSql for Filter {
@@ -21,3 +66,16 @@ impl ToSql for Filter {
 		stmt.fmt_sql(f, fmt);
 	}
 }
+
+/// The words used by the `STOPWORDS` and `SYNONYMS` filters
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum WordList {
+	/// The built-in list of a language
+	Language(Language),
+	/// A list of words, or of synonym rules
+	List(Vec<String>),
+	/// A file stored in a bucket, along with the lines of the file, which are
+	/// read when the analyzer is defined
+	File(File, Vec<String>),
+}
@@ -47,6 +47,7 @@ pub(crate) mod script;
 pub(crate) mod split;
 pub(crate) mod start;
 pub(crate) mod tokenizer;
+pub(crate) mod ttl;
 pub(crate) mod user;
 pub(crate) mod view;
 pub(crate) mod with;
@@ -99,6 +100,7 @@ pub(crate) use self::split::{Split, Splits};
 pub(crate) use self::start::Start;
 pub(crate) use self::statements::{DefineAnalyzerStatement, SelectStatement, SleepStatement};
 pub(crate) use self::tokenizer::Tokenizer;
+pub(crate) use self::ttl::Ttl;
 pub(crate) use self::view::View;
 pub(crate) use self::with::With;
 
@@ -130,6 +130,12 @@ pub(crate) enum BinaryOperator {
 pub(crate) struct MatchesOperator {
 	pub rf: Option<MatchRef>,
 	pub operator: BooleanOperator,
+	/// Whether the query terms also match the terms of the index within an
+	/// edit distance
+	pub fuzzy: Option<Fuzziness>,
+	/// Whether the query terms also match the terms of the index they are a
+	/// prefix of
+	pub prefix: bool,
 }
 
 impl ToSql for MatchesOperator {
@@ -139,6 +145,29 @@ impl ToSql for MatchesOperator {
 	}
 }
 
+/// The maximum edit distance of a fuzzy full-text search
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Fuzziness {
+	/// The distance depends on the length of the term: none up to 2
+	/// characters, 1 up to 5 characters, 2 otherwise
+	Auto,
+	Distance(u8),
+}
+
+impl Fuzziness {
+	/// The maximum number of edits allowed for the given term
+	pub(crate) fn max_distance(&self, term: &str) -> usize {
+		match self {
+			Self::Auto => match term.chars().count() {
+				0..=2 => 0,
+				3..=5 => 1,
+				_ => 2,
+			},
+			Self::Distance(d) => *d as usize,
+		}
+	}
+}
+
 /// Boolean operation executed by the full-text index
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
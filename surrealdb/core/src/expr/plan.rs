@@ -13,6 +13,9 @@ pub(crate) enum TopLevelExpr {
 	Begin,
 	Cancel,
 	Commit,
+	Savepoint(String),
+	RollbackTo(String),
+	Release(String),
 	Access(Box<AccessStatement>),
 	Kill(KillStatement),
 	Live(Box<LiveStatement>),
@@ -29,6 +32,9 @@ impl TopLevelExpr {
 			TopLevelExpr::Begin
 			| TopLevelExpr::Cancel
 			| TopLevelExpr::Commit
+			| TopLevelExpr::Savepoint(_)
+			| TopLevelExpr::RollbackTo(_)
+			| TopLevelExpr::Release(_)
 			| TopLevelExpr::Show(_) => true,
 			TopLevelExpr::Kill(_)
 			| TopLevelExpr::Live(_)
@@ -0,0 +1,100 @@
+use std::ops::Deref;
+
+use anyhow::Result;
+use reblessive::tree::Stk;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use super::AlterKind;
+use crate::catalog::ConsumerCheckpoint;
+use crate::catalog::providers::DatabaseProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::{Base, Expr, FlowResultExt};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{Datetime, Value};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct AlterConsumerStatement {
+	pub name: String,
+	pub if_exists: bool,
+	pub ack: Option<Expr>,
+	pub comment: AlterKind<String>,
+}
+
+impl AlterConsumerStatement {
+	#[instrument(level = "trace", name = "AlterConsumerStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the consumer definition
+		let mut cu = match txn.get_db_consumer(ns, db, &self.name, None).await {
+			Ok(v) => v.deref().clone(),
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::CuNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Move the checkpoint forward, without touching the definition
+		if let Some(ack) = &self.ack {
+			let versionstamp = stk
+				.run(|stk| ack.compute(stk, ctx, opt, doc))
+				.await
+				.catch_return()?
+				.cast_to::<i64>()?;
+			let versionstamp = u64::try_from(versionstamp).map_err(|_| Error::Query {
+				message: format!(
+					"Invalid versionstamp `{versionstamp}`, expected a positive integer"
+				),
+			})?;
+			let key = crate::key::database::ck::new(ns, db, &self.name);
+			// Acknowledging an earlier change than before is a no-op
+			let advanced = match txn.get(&key, None).await? {
+				Some(previous) => versionstamp > previous.versionstamp,
+				None => true,
+			};
+			if advanced {
+				let checkpoint = ConsumerCheckpoint {
+					versionstamp,
+					acknowledged: Datetime::now(),
+				};
+				txn.set(&key, &checkpoint).await?;
+			}
+		}
+
+		// The definition only changes with the comment
+		match self.comment {
+			AlterKind::Set(ref v) => cu.comment = Some(v.clone()),
+			AlterKind::Drop => cu.comment = None,
+			AlterKind::None => return Ok(Value::None),
+		}
+		// Set the consumer definition
+		let key = crate::key::database::cu::new(ns, db, &self.name);
+		txn.set(&key, &cu).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl ToSql for AlterConsumerStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::alter::AlterConsumerStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
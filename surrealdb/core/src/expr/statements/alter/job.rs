@@ -0,0 +1,108 @@
+use std::ops::Deref;
+
+use anyhow::Result;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use super::AlterKind;
+use crate::catalog::JobState;
+use crate::catalog::providers::DatabaseProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::{Base, Expr};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{Datetime, Value};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct AlterJobStatement {
+	pub name: String,
+	pub if_exists: bool,
+	pub paused: Option<bool>,
+	pub schedule: Option<String>,
+	pub then: Option<Expr>,
+	pub comment: AlterKind<String>,
+}
+
+impl AlterJobStatement {
+	#[instrument(level = "trace", name = "AlterJobStatement::compute", skip_all)]
+	pub(crate) async fn compute(&self, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Job, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the job definition
+		let mut jb = match txn.get_db_job(ns, db, &self.name, None).await {
+			Ok(v) => v.deref().clone(),
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::JbNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// The next run only changes when the schedule or pause state does
+		let mut reschedule = false;
+
+		if let Some(ref v) = self.schedule {
+			crate::job::parse_schedule(v)?;
+			jb.schedule = v.clone();
+			reschedule = true;
+		}
+
+		if let Some(v) = self.paused
+			&& v != jb.paused
+		{
+			jb.paused = v;
+			reschedule = true;
+		}
+
+		if let Some(ref v) = self.then {
+			jb.then = v.clone();
+		}
+
+		match self.comment {
+			AlterKind::Set(ref v) => jb.comment = Some(v.clone()),
+			AlterKind::Drop => jb.comment = None,
+			AlterKind::None => {}
+		}
+
+		// Set the job definition
+		let key = crate::key::database::jb::new(ns, db, &self.name);
+		txn.set(&key, &jb).await?;
+		// Reschedule the job from now, keeping its run history
+		if reschedule {
+			let key = crate::key::database::js::new(ns, db, &self.name);
+			let state = match txn.get(&key, None).await? {
+				Some(state) => state,
+				// The job has never been scheduled, so it has no run history
+				None => JobState {
+					next: None,
+					runs: Vec::new(),
+				},
+			};
+			let state = if jb.paused {
+				JobState {
+					next: None,
+					..state
+				}
+			} else {
+				state.scheduled(&jb.schedule, &Datetime::now())?
+			};
+			txn.set(&key, &state).await?;
+		}
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl ToSql for AlterJobStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::alter::AlterJobStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
@@ -13,11 +13,13 @@ mod analyzer;
 mod api;
 mod bucket;
 mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
 mod module;
 mod namespace;
 mod param;
@@ -31,11 +33,13 @@ pub(crate) use analyzer::AlterAnalyzerStatement;
 pub(crate) use api::{AlterApiClause, AlterApiStatement};
 pub(crate) use bucket::AlterBucketStatement;
 pub(crate) use config::AlterConfigStatement;
+pub(crate) use consumer::AlterConsumerStatement;
 pub(crate) use database::AlterDatabaseStatement;
 pub(crate) use event::AlterEventStatement;
 pub(crate) use field::{AlterDefault, AlterFieldStatement};
 pub(crate) use function::AlterFunctionStatement;
 pub(crate) use index::AlterIndexStatement;
+pub(crate) use job::AlterJobStatement;
 pub(crate) use module::AlterModuleStatement;
 pub(crate) use namespace::AlterNamespaceStatement;
 pub(crate) use param::AlterParamStatement;
@@ -119,6 +123,8 @@ pub(crate) enum AlterStatement {
 	Event(AlterEventStatement),
 	Index(AlterIndexStatement),
 	Sequence(AlterSequenceStatement),
+	Job(AlterJobStatement),
+	Consumer(AlterConsumerStatement),
 	Field(AlterFieldStatement),
 	Param(AlterParamStatement),
 	Bucket(AlterBucketStatement),
@@ -152,11 +158,13 @@ impl AlterStatement {
 			Self::Event(v) => v.compute(ctx, opt).await,
 			Self::Index(v) => v.compute(ctx, opt).await,
 			Self::Sequence(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Job(v) => v.compute(ctx, opt).await,
+			Self::Consumer(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Field(v) => v.compute(ctx, opt).await,
 			Self::Param(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Bucket(v) => v.compute(ctx, opt).await,
 			Self::Config(v) => v.compute(stk, ctx, opt, doc).await,
-			Self::Analyzer(v) => v.compute(ctx, opt).await,
+			Self::Analyzer(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Function(v) => v.compute(ctx, opt).await,
 			Self::User(v) => v.compute(ctx, opt).await,
 			Self::Access(v) => v.compute(ctx, opt).await,
@@ -176,6 +184,8 @@ impl ToSql for AlterStatement {
 			Self::Event(v) => v.fmt_sql(f, fmt),
 			Self::Index(v) => v.fmt_sql(f, fmt),
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
+			Self::Job(v) => v.fmt_sql(f, fmt),
+			Self::Consumer(v) => v.fmt_sql(f, fmt),
 			Self::Field(v) => v.fmt_sql(f, fmt),
 			Self::Param(v) => v.fmt_sql(f, fmt),
 			Self::Bucket(v) => v.fmt_sql(f, fmt),
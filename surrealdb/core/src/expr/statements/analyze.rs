@@ -0,0 +1,108 @@
+use anyhow::{Result, bail};
+use futures::StreamExt;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{FieldStatistics, Record, TableStatistics};
+use crate::cnf::ANALYZE_SAMPLE_SIZE;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::Base;
+use crate::expr::statements::info::InfoStructure;
+use crate::iam::{Action, ResourceKind};
+use crate::idx::planner::ScanDirection;
+use crate::kvs::KVValue;
+use crate::val::{Datetime, TableName, Value};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct AnalyzeStatement {
+	pub(crate) table: TableName,
+	pub(crate) sample: Option<u64>,
+}
+
+impl AnalyzeStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "AnalyzeStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		ctx: &FrozenContext,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Get the table definition
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let txn = ctx.tx();
+		txn.expect_tb(ns, db, &self.table).await?;
+		// Analyze the indexed columns, and the defined fields
+		let mut fields = Vec::new();
+		for ix in txn.all_tb_indexes(ns, db, &self.table, None).await?.iter() {
+			for col in ix.cols.iter() {
+				if !fields.contains(col) {
+					fields.push(col.clone());
+				}
+			}
+		}
+		for fd in txn.all_tb_fields(ns, db, &self.table, None).await?.iter() {
+			if !fields.contains(&fd.name) {
+				fields.push(fd.name.clone());
+			}
+		}
+		// Sample the records uniformly with a reservoir, while counting them
+		let size = self.sample.unwrap_or(*ANALYZE_SAMPLE_SIZE).max(1) as usize;
+		let mut sample: Vec<Value> = Vec::new();
+		let mut records = 0u64;
+		let mut rng = StdRng::from_entropy();
+		let beg = crate::key::record::prefix(ns, db, &self.table)?;
+		let end = crate::key::record::suffix(ns, db, &self.table)?;
+		let stream = txn.stream_keys_vals(beg..end, None, None, 0, ScanDirection::Forward, false);
+		futures::pin_mut!(stream);
+		while let Some(batch) = stream.next().await {
+			for (_, val) in batch? {
+				// Check if the context is finished
+				if ctx.is_done(Some(records as usize)).await? {
+					bail!(Error::QueryCancelled);
+				}
+				records += 1;
+				if sample.len() < size {
+					sample.push(Record::kv_decode_value(val)?.data);
+				} else {
+					let i = rng.gen_range(0..records) as usize;
+					if i < size {
+						sample[i] = Record::kv_decode_value(val)?.data;
+					}
+				}
+			}
+		}
+		// Compute the statistics of each field
+		let fields = fields
+			.iter()
+			.map(|idiom| {
+				let values = sample.iter().map(|v| v.pick(idiom)).collect();
+				FieldStatistics::from_sample(idiom.to_raw_string(), values, records)
+			})
+			.collect();
+		let stats = TableStatistics {
+			records,
+			sampled: sample.len() as u64,
+			analyzed_at: Datetime::now(),
+			fields,
+		};
+		// Store the statistics
+		txn.put_tb_statistics(ns, db, &self.table, &stats).await?;
+		// Ok all good
+		Ok(stats.structure())
+	}
+}
+
+impl ToSql for AnalyzeStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::analyze::AnalyzeStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
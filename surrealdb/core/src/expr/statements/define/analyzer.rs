@@ -51,12 +51,17 @@ impl DefineAnalyzerStatement {
 			.await
 			.catch_return()?
 			.cast_to()?;
+		// Read the bucket files used by the filters
+		let mut filters = self.filters.clone();
+		for f in filters.iter_mut().flatten() {
+			f.resolve(stk, ctx, opt, doc).await?;
+		}
 
 		Ok(catalog::AnalyzerDefinition {
 			name: expr_to_ident(stk, ctx, opt, doc, &self.name, "analyzer name").await?,
 			function: self.function.clone(),
 			tokenizers: self.tokenizers.clone(),
-			filters: self.filters.clone(),
+			filters,
 			comment,
 		})
 	}
@@ -108,6 +113,7 @@ impl DefineAnalyzerStatement {
 		// Process the statement
 		let key = crate::key::database::az::new(ns, db, &definition.name);
 		ctx.get_index_stores().mappers().load(&definition).await?;
+		ctx.get_index_stores().dictionaries().load(&definition).await?;
 		txn.set(&key, &definition).await?;
 		// Clear the cache
 		txn.clear_cache();
@@ -0,0 +1,104 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use super::DefineKind;
+use crate::catalog::providers::{CatalogProvider, DatabaseProvider};
+use crate::catalog::{ConsumerCheckpoint, ConsumerDefinition};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::val::Datetime;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineConsumerStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub comment: Expr,
+}
+
+impl Default for DefineConsumerStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl DefineConsumerStatement {
+	#[instrument(level = "trace", name = "DefineConsumerStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Compute name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "consumer name").await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Check if the definition exists
+		if txn.get_db_consumer(ns, db, &name, None).await.is_ok() {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::CuAlreadyExists {
+							name: name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => {
+					return Ok(Value::None);
+				}
+			}
+		}
+
+		let db = {
+			let (ns, db) = opt.ns_db()?;
+			txn.get_or_add_db(Some(ctx), ns, db).await?
+		};
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		let cu = ConsumerDefinition {
+			name: name.clone(),
+			comment,
+		};
+		// Set the definition
+		let key = crate::key::database::cu::new(db.namespace_id, db.database_id, &name);
+		txn.set(&key, &cu).await?;
+		// A new group only consumes changes made after it was defined, while a
+		// redefined group keeps its checkpoint
+		let key = crate::key::database::ck::new(db.namespace_id, db.database_id, &name);
+		if txn.get(&key, None).await?.is_none() {
+			let checkpoint = ConsumerCheckpoint {
+				versionstamp: txn
+					.timestamp()
+					.await?
+					.as_versionstamp()
+					.try_into()
+					.unwrap_or(u64::MAX),
+				acknowledged: Datetime::now(),
+			};
+			txn.set(&key, &checkpoint).await?;
+		}
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
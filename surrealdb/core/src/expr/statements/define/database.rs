@@ -2,8 +2,8 @@ use anyhow::{Result, bail};
 use reblessive::tree::Stk;
 
 use super::DefineKind;
-use crate::catalog::DatabaseDefinition;
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider};
+use crate::catalog::{CapabilitiesDefinition, DatabaseDefinition, QuotaDefinition};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -23,6 +23,8 @@ pub(crate) struct DefineDatabaseStatement {
 	pub strict: bool,
 	pub comment: Expr,
 	pub changefeed: Option<ChangeFeed>,
+	pub capabilities: Option<CapabilitiesDefinition>,
+	pub quota: Option<QuotaDefinition>,
 }
 
 impl Default for DefineDatabaseStatement {
@@ -34,6 +36,8 @@ impl Default for DefineDatabaseStatement {
 			comment: Expr::Literal(Literal::None),
 			changefeed: None,
 			strict: false,
+			capabilities: None,
+			quota: None,
 		}
 	}
 }
@@ -51,6 +55,11 @@ impl DefineDatabaseStatement {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Database, &Base::Ns)?;
 
+		// Check the capability targets are valid
+		if let Some(capabilities) = &self.capabilities {
+			capabilities.validate()?;
+		}
+
 		// Get the NS
 		let ns = opt.ns()?;
 
@@ -96,6 +105,8 @@ impl DefineDatabaseStatement {
 			comment,
 			changefeed: self.changefeed,
 			strict: self.strict,
+			capabilities: self.capabilities.clone().filter(|v| !v.is_empty()),
+			quota: self.quota.clone().filter(|v| !v.is_empty()),
 		};
 		txn.put_db(&nsv.name, db_def).await?;
 
@@ -106,6 +117,8 @@ impl DefineDatabaseStatement {
 
 		// Clear the cache
 		txn.clear_cache();
+		// Clear the cached quotas
+		ctx.clear_quota_cache();
 		// Ok all good
 		Ok(Value::None)
 	}
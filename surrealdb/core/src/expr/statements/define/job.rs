@@ -0,0 +1,119 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use super::DefineKind;
+use crate::catalog::providers::{CatalogProvider, DatabaseProvider};
+use crate::catalog::{JobDefinition, JobState};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::val::Datetime;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineJobStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub schedule: String,
+	pub paused: bool,
+	pub then: Expr,
+	pub comment: Expr,
+}
+
+impl Default for DefineJobStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			schedule: String::new(),
+			paused: false,
+			then: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl DefineJobStatement {
+	#[instrument(level = "trace", name = "DefineJobStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Job, &Base::Db)?;
+		// Compute name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "job name").await?;
+		// Check the schedule
+		crate::job::parse_schedule(&self.schedule)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Check if the definition exists
+		if txn.get_db_job(ns, db, &name, None).await.is_ok() {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::JbAlreadyExists {
+							name: name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => {
+					return Ok(Value::None);
+				}
+			}
+		}
+
+		let db = {
+			let (ns, db) = opt.ns_db()?;
+			txn.get_or_add_db(Some(ctx), ns, db).await?
+		};
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		let jb = JobDefinition {
+			name: name.clone(),
+			schedule: self.schedule.clone(),
+			then: self.then.clone(),
+			paused: self.paused,
+			comment,
+		};
+		// Set the definition
+		let key = crate::key::database::jb::new(db.namespace_id, db.database_id, &name);
+		txn.set(&key, &jb).await?;
+		// Schedule the next run, keeping the history of a redefined job
+		let key = crate::key::database::js::new(db.namespace_id, db.database_id, &name);
+		let state = match txn.get(&key, None).await? {
+			Some(state) => state,
+			// A new job has no run history
+			None => JobState {
+				next: None,
+				runs: Vec::new(),
+			},
+		};
+		let state = if jb.paused {
+			JobState {
+				next: None,
+				..state
+			}
+		} else {
+			state.scheduled(&jb.schedule, &Datetime::now())?
+		};
+		txn.set(&key, &state).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
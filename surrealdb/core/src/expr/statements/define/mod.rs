@@ -3,16 +3,19 @@ mod analyzer;
 mod api;
 mod bucket;
 pub mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
 mod model;
 mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 mod user;
 
@@ -22,18 +25,21 @@ use anyhow::Result;
 pub(crate) use api::{ApiAction, DefineApiStatement};
 pub(crate) use bucket::DefineBucketStatement;
 pub(crate) use config::DefineConfigStatement;
+pub(crate) use consumer::DefineConsumerStatement;
 pub(crate) use database::DefineDatabaseStatement;
 pub(crate) use event::DefineEventStatement;
 pub(crate) use field::{DefineDefault, DefineFieldStatement};
 pub(crate) use function::DefineFunctionStatement;
 pub(crate) use index::DefineIndexStatement;
 pub(in crate::expr::statements) use index::run_indexing;
+pub(crate) use job::DefineJobStatement;
 pub(crate) use model::DefineModelStatement;
 pub(crate) use module::DefineModuleStatement;
 pub(crate) use namespace::DefineNamespaceStatement;
 pub(crate) use param::DefineParamStatement;
 use reblessive::tree::Stk;
 pub(crate) use sequence::DefineSequenceStatement;
+pub(crate) use sink::DefineSinkStatement;
 pub(crate) use table::DefineTableStatement;
 pub(crate) use user::DefineUserStatement;
 
@@ -68,6 +74,9 @@ pub(crate) enum DefineStatement {
 	Api(DefineApiStatement),
 	Bucket(DefineBucketStatement),
 	Sequence(DefineSequenceStatement),
+	Job(DefineJobStatement),
+	Consumer(DefineConsumerStatement),
+	Sink(DefineSinkStatement),
 	Module(DefineModuleStatement),
 }
 
@@ -98,6 +107,9 @@ impl DefineStatement {
 			Self::Api(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Bucket(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Sequence(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Job(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Consumer(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Sink(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Module(v) => v.compute(stk, ctx, opt, doc).await,
 		}
 	}
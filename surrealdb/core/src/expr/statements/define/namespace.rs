@@ -2,8 +2,8 @@ use anyhow::{Result, bail};
 use reblessive::tree::Stk;
 
 use super::DefineKind;
-use crate::catalog::NamespaceDefinition;
 use crate::catalog::providers::NamespaceProvider;
+use crate::catalog::{CapabilitiesDefinition, NamespaceDefinition, QuotaDefinition};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -19,6 +19,8 @@ pub(crate) struct DefineNamespaceStatement {
 	pub id: Option<u32>,
 	pub name: Expr,
 	pub comment: Expr,
+	pub capabilities: Option<CapabilitiesDefinition>,
+	pub quota: Option<QuotaDefinition>,
 }
 
 impl Default for DefineNamespaceStatement {
@@ -28,6 +30,8 @@ impl Default for DefineNamespaceStatement {
 			id: None,
 			name: Expr::Literal(Literal::String(String::new())),
 			comment: Expr::Literal(Literal::None),
+			capabilities: None,
+			quota: None,
 		}
 	}
 }
@@ -44,6 +48,10 @@ impl DefineNamespaceStatement {
 	) -> Result<Value> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Namespace, &Base::Root)?;
+		// Check the capability targets are valid
+		if let Some(capabilities) = &self.capabilities {
+			capabilities.validate()?;
+		}
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Process the name
@@ -77,10 +85,14 @@ impl DefineNamespaceStatement {
 			namespace_id,
 			name,
 			comment,
+			capabilities: self.capabilities.clone().filter(|v| !v.is_empty()),
+			quota: self.quota.clone().filter(|v| !v.is_empty()),
 		};
 		txn.put_ns(ns_def).await?;
 		// Clear the cache
 		txn.clear_cache();
+		// Clear the cached quotas
+		ctx.clear_quota_cache();
 		// Ok all good
 		Ok(Value::None)
 	}
@@ -0,0 +1,131 @@
+use anyhow::{Result, bail, ensure};
+use reblessive::tree::Stk;
+
+use super::DefineKind;
+use crate::catalog::providers::{CatalogProvider, DatabaseProvider};
+use crate::catalog::{SinkDefinition, SinkState, SinkTarget};
+use crate::cnf::FILE_ALLOWLIST;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineSinkStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub target: SinkTarget,
+	pub comment: Expr,
+}
+
+impl Default for DefineSinkStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			target_table: Expr::Literal(Literal::None),
+			target: SinkTarget::Stream,
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl DefineSinkStatement {
+	#[instrument(level = "trace", name = "DefineSinkStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Compute name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "sink name").await?;
+		// Compute the table
+		let target_table = TableName::new(
+			expr_to_ident(stk, ctx, opt, doc, &self.target_table, "target table").await?,
+		);
+		// Check the target
+		match &self.target {
+			SinkTarget::Webhook(url) => {
+				url::Url::parse(url).map_err(|_| Error::InvalidUrl(url.clone()))?;
+			}
+			// File sinks write to the filesystem of the server, so they may
+			// only be defined by owners, and only when an allowlist is set
+			SinkTarget::File(path) => {
+				opt.is_allowed(Action::Edit, ResourceKind::Sink, &Base::Db)?;
+				ensure!(!FILE_ALLOWLIST.is_empty(), Error::FileAccessDenied(path.clone()));
+			}
+			SinkTarget::Stream => {}
+		}
+		// Fetch the transaction
+		let txn = ctx.tx();
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Check if the definition exists
+		if txn.get_db_sink(ns, db, &name, None).await.is_ok() {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::SkAlreadyExists {
+							name: name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => {
+					return Ok(Value::None);
+				}
+			}
+		}
+
+		let db = {
+			let (ns, db) = opt.ns_db()?;
+			txn.get_or_add_db(Some(ctx), ns, db).await?
+		};
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		let sk = SinkDefinition {
+			name: name.clone(),
+			target_table,
+			target: self.target.clone(),
+			comment,
+		};
+		// Set the definition
+		let key = crate::key::database::sk::new(db.namespace_id, db.database_id, &name);
+		txn.set(&key, &sk).await?;
+		// A new sink only delivers changes made after it was defined, while a
+		// redefined sink continues from where it was
+		let key = crate::key::database::sd::new(db.namespace_id, db.database_id, &name);
+		if txn.get(&key, None).await?.is_none() {
+			let state = SinkState {
+				versionstamp: txn
+					.timestamp()
+					.await?
+					.as_versionstamp()
+					.try_into()
+					.unwrap_or(u64::MAX),
+				delivered: None,
+				attempts: 0,
+				error: None,
+				retry: None,
+			};
+			txn.set(&key, &state).await?;
+		}
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
@@ -24,7 +24,7 @@ use crate::expr::parameterize::expr_to_ident;
 use crate::expr::paths::{ID, IN, OUT};
 use crate::expr::{
 	Base, BinaryOperator, Cond, Expr, Field, Fields, FlowResultExt, Function, FunctionCall, Group,
-	Groups, Idiom, Kind, Literal, SelectStatement, View,
+	Groups, Idiom, Kind, Literal, SelectStatement, Ttl, View,
 };
 use crate::iam::{Action, ResourceKind};
 use crate::key;
@@ -41,6 +41,7 @@ pub(crate) struct DefineTableStatement {
 	pub view: Option<View>,
 	pub permissions: Permissions,
 	pub changefeed: Option<ChangeFeed>,
+	pub ttl: Option<Ttl>,
 	pub comment: Expr,
 	pub table_type: TableType,
 }
@@ -56,6 +57,7 @@ impl Default for DefineTableStatement {
 			view: None,
 			permissions: Permissions::default(),
 			changefeed: None,
+			ttl: None,
 			comment: Expr::Literal(Literal::None),
 			table_type: TableType::default(),
 		}
@@ -88,7 +90,7 @@ impl DefineTableStatement {
 		let db = txn.expect_db_by_name(ns_name, db_name).await?;
 
 		// Check if the definition exists
-		let table_id =
+		let (table_id, previous_ttl) =
 			if let Some(tb) = txn.get_tb(ns.namespace_id, db.database_id, &name, None).await? {
 				match self.kind {
 					DefineKind::Default => {
@@ -102,9 +104,9 @@ impl DefineTableStatement {
 					DefineKind::IfNotExists => return Ok(Value::None),
 				}
 
-				tb.table_id
+				(tb.table_id, tb.ttl.clone())
 			} else {
-				txn.get_next_tb_id(Some(ctx), ns.namespace_id, db.database_id).await?
+				(txn.get_next_tb_id(Some(ctx), ns.namespace_id, db.database_id).await?, None)
 			};
 
 		let comment = stk
@@ -127,6 +129,7 @@ impl DefineTableStatement {
 			permissions: self.permissions.clone(),
 			comment,
 			changefeed: self.changefeed,
+			ttl: self.ttl.clone(),
 
 			cache_fields_ts: cache_ts,
 			cache_events_ts: cache_ts,
@@ -146,6 +149,18 @@ impl DefineTableStatement {
 		let tb = txn.put_tb(ns_name, db_name, &tb_def).await?;
 		let fields = txn.all_tb_fields(ns.namespace_id, db.database_id, &name, opt.version).await?;
 
+		// Index the existing records by their new expiry time
+		if previous_ttl != self.ttl {
+			crate::kvs::ttl::reindex_table(
+				&txn,
+				ns.namespace_id,
+				db.database_id,
+				&name,
+				self.ttl.as_ref(),
+			)
+			.await?;
+		}
+
 		// Clear the cache
 		if let Some(cache) = ctx.get_cache() {
 			cache.clear_tb(ns.namespace_id, db.database_id, &name);
@@ -758,6 +773,7 @@ impl DefineTableStatement {
 				metadata: Some(Metadata {
 					record_type: RecordType::Table,
 					aggregation_stats: stats,
+					expires: None,
 				}),
 				data,
 			});
@@ -9,15 +9,16 @@ use surrealdb_types::{SqlFormat, ToSql};
 
 use super::DefineKind;
 use crate::catalog::providers::{CatalogProvider, NamespaceProvider, UserProvider};
-use crate::catalog::{self, UserDefinition};
+use crate::catalog::{self, QuotaDefinition, TotpDefinition, UserDefinition};
+use crate::cnf::SERVER_NAME;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
-use crate::expr::user::UserDuration;
+use crate::expr::user::{UserDuration, UserTotp};
 use crate::expr::{Base, Expr, FlowResultExt, Idiom, Literal};
-use crate::iam::{Action, ResourceKind};
+use crate::iam::{Action, ResourceKind, totp};
 use crate::val::{self, Duration, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -30,6 +31,8 @@ pub(crate) struct DefineUserStatement {
 	pub roles: Vec<String>,
 	pub duration: UserDuration,
 	pub comment: Expr,
+	pub quota: Option<QuotaDefinition>,
+	pub totp: UserTotp,
 }
 
 impl Default for DefineUserStatement {
@@ -43,6 +46,8 @@ impl Default for DefineUserStatement {
 			roles: vec![],
 			duration: UserDuration::default(),
 			comment: Expr::Literal(Literal::None),
+			quota: None,
+			totp: UserTotp::Unset,
 		}
 	}
 }
@@ -65,6 +70,8 @@ impl DefineUserStatement {
 			roles: vec![role],
 			duration: UserDuration::default(),
 			comment: Expr::Literal(Literal::None),
+			quota: None,
+			totp: UserTotp::Unset,
 		}
 	}
 
@@ -103,6 +110,18 @@ impl DefineUserStatement {
 			session_duration,
 			comment,
 			base: self.base.into(),
+			quota: self.quota.clone().filter(|v| !v.is_empty()),
+			totp: match &self.totp {
+				UserTotp::Secret {
+					secret,
+					recovery,
+				} => {
+					totp::validate_secret(secret)?;
+					Some(TotpDefinition::new(secret.clone(), recovery.clone()))
+				}
+				// Secrets are generated when the statement is run
+				UserTotp::Unset | UserTotp::Generate => None,
+			},
 		})
 	}
 
@@ -129,9 +148,23 @@ impl DefineUserStatement {
 				.as_ref()
 				.map(|x| Expr::Idiom(Idiom::field(x.clone())))
 				.unwrap_or(Expr::Literal(Literal::None)),
+			quota: def.quota.clone(),
+			totp: match &def.totp {
+				Some(v) => UserTotp::Secret {
+					secret: v.secret.clone(),
+					recovery: v.recovery.clone(),
+				},
+				None => UserTotp::Unset,
+			},
 		}
 	}
 
+	/// Remove the TOTP enrolment of the user, which should not be displayed.
+	pub fn redact(mut self) -> Self {
+		self.totp = UserTotp::Unset;
+		self
+	}
+
 	/// Process this type returning a computed simple Value
 	#[instrument(level = "trace", name = "DefineUserStatement::compute", skip_all)]
 	pub(crate) async fn compute(
@@ -144,7 +177,25 @@ impl DefineUserStatement {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
 		// Compute definition
-		let definition = self.to_definition(stk, ctx, opt, doc).await?;
+		let mut definition = self.to_definition(stk, ctx, opt, doc).await?;
+		// Enrol the user in TOTP, returning the secret and recovery codes once
+		let enrolment = match self.totp {
+			UserTotp::Generate => {
+				let secret = totp::generate_secret();
+				let codes = totp::generate_recovery_codes();
+				let uri = totp::provisioning_uri(&secret, &definition.name, SERVER_NAME);
+				definition.totp = Some(TotpDefinition::new(
+					secret.clone(),
+					codes.iter().map(|c| totp::hash_recovery_code(c)).collect(),
+				));
+				Value::from(map! {
+					"secret".to_string() => Value::from(secret),
+					"uri".to_string() => Value::from(uri),
+					"recovery".to_string() => Value::from(codes.into_iter().map(Value::from).collect::<Vec<_>>()),
+				})
+			}
+			_ => Value::None,
+		};
 		// Check the statement type
 		match self.base {
 			Base::Root => {
@@ -168,8 +219,10 @@ impl DefineUserStatement {
 				txn.put_root_user(&definition).await?;
 				// Clear the cache
 				txn.clear_cache();
+				// Clear the cached quotas
+				ctx.clear_quota_cache();
 				// Ok all good
-				Ok(Value::None)
+				Ok(enrolment)
 			}
 			Base::Ns => {
 				// Fetch the transaction
@@ -200,8 +253,10 @@ impl DefineUserStatement {
 				txn.put_ns_user(ns.namespace_id, &definition).await?;
 				// Clear the cache
 				txn.clear_cache();
+				// Clear the cached quotas
+				ctx.clear_quota_cache();
 				// Ok all good
-				Ok(Value::None)
+				Ok(enrolment)
 			}
 			Base::Db => {
 				// Fetch the transaction
@@ -233,8 +288,10 @@ impl DefineUserStatement {
 				txn.put_db_user(db.namespace_id, db.database_id, &definition).await?;
 				// Clear the cache
 				txn.clear_cache();
+				// Clear the cached quotas
+				ctx.clear_quota_cache();
 				// Ok all good
-				Ok(Value::None)
+				Ok(enrolment)
 			}
 		}
 	}
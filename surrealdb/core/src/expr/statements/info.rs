@@ -32,6 +32,8 @@ pub(crate) enum InfoStatement {
 	User(Expr, Option<Base>, bool),
 	/// Index information
 	Index(Expr, Expr, bool),
+	/// OpenAPI document for the defined APIs
+	Api,
 }
 
 impl InfoStatement {
@@ -194,7 +196,9 @@ impl InfoStatement {
 						"apis".to_string() => process(txn.all_db_apis(ns, db, version).await?),
 						"analyzers".to_string() => process(txn.all_db_analyzers(ns, db, version).await?),
 						"buckets".to_string() => process(txn.all_db_buckets(ns, db, version).await?),
+						"consumers".to_string() => process_consumers(ctx, ns, db, version, txn.all_db_consumers(ns, db, version).await?).await?,
 						"functions".to_string() => process(txn.all_db_functions(ns, db, version).await?),
+						"jobs".to_string() => process_jobs(ctx, ns, db, version, txn.all_db_jobs(ns, db, version).await?).await?,
 						"modules".to_string() => process_modules(ctx, ns, db, txn.all_db_modules(ns, db, version).await?).await,
 						"models".to_string() => process(txn.all_db_models(ns, db, version).await?),
 						"params".to_string() => process(txn.all_db_params(ns, db, version).await?),
@@ -202,6 +206,7 @@ impl InfoStatement {
 						"users".to_string() => process(txn.all_db_users(ns, db, version).await?),
 						"configs".to_string() => process(txn.all_db_configs(ns, db, version).await?),
 						"sequences".to_string() => process(txn.all_db_sequences(ns, db, version).await?),
+						"sinks".to_string() => process_sinks(ctx, ns, db, version, txn.all_db_sinks(ns, db, version).await?).await?,
 					};
 					Value::Object(Object(object))
 				} else {
@@ -234,6 +239,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"consumers".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_consumers(ns, db, version).await?.iter() {
+								out.insert(v.name.clone(), v.to_sql().into());
+							}
+							out.into()
+						},
 						"functions".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_db_functions(ns, db, version).await?.iter() {
@@ -241,6 +253,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"jobs".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_jobs(ns, db, version).await?.iter() {
+								out.insert(v.name.clone(), v.to_sql().into());
+							}
+							out.into()
+						},
 						"modules".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_db_modules(ns, db, version).await?.iter() {
@@ -290,6 +309,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"sinks".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_sinks(ns, db, version).await?.iter() {
+								out.insert(v.name.clone(), v.to_sql().into());
+							}
+							out.into()
+						},
 					};
 					Value::Object(Object(object))
 				};
@@ -436,6 +462,7 @@ impl InfoStatement {
 				}
 				Ok(Object::default().into())
 			}
+			InfoStatement::Api => crate::api::openapi::generate(ctx, opt).await,
 		}
 	}
 }
@@ -548,6 +575,63 @@ async fn get_module_exports(
 	}
 }
 
+/// Process job definitions into structured Values, including the next
+/// scheduled run and the run history of each job.
+pub(crate) async fn process_jobs(
+	ctx: &FrozenContext,
+	ns: crate::catalog::NamespaceId,
+	db: crate::catalog::DatabaseId,
+	version: Option<u64>,
+	jobs: Arc<[crate::catalog::JobDefinition]>,
+) -> Result<Value> {
+	let txn = ctx.tx();
+	let mut values = Vec::with_capacity(jobs.len());
+	for job in jobs.iter() {
+		let key = crate::key::database::js::new(ns, db, &job.name);
+		let state = txn.get(&key, version).await?;
+		values.push(job.clone().structure_with_state(state));
+	}
+	Ok(Value::Array(values.into()))
+}
+
+/// Process consumer group definitions into structured Values, including the
+/// checkpoint of each group.
+pub(crate) async fn process_consumers(
+	ctx: &FrozenContext,
+	ns: crate::catalog::NamespaceId,
+	db: crate::catalog::DatabaseId,
+	version: Option<u64>,
+	consumers: Arc<[crate::catalog::ConsumerDefinition]>,
+) -> Result<Value> {
+	let txn = ctx.tx();
+	let mut values = Vec::with_capacity(consumers.len());
+	for consumer in consumers.iter() {
+		let key = crate::key::database::ck::new(ns, db, &consumer.name);
+		let checkpoint = txn.get(&key, version).await?;
+		values.push(consumer.clone().structure_with_checkpoint(checkpoint));
+	}
+	Ok(Value::Array(values.into()))
+}
+
+/// Process sink definitions into structured Values, including the delivery
+/// state of each sink.
+pub(crate) async fn process_sinks(
+	ctx: &FrozenContext,
+	ns: crate::catalog::NamespaceId,
+	db: crate::catalog::DatabaseId,
+	version: Option<u64>,
+	sinks: Arc<[crate::catalog::SinkDefinition]>,
+) -> Result<Value> {
+	let txn = ctx.tx();
+	let mut values = Vec::with_capacity(sinks.len());
+	for sink in sinks.iter() {
+		let key = crate::key::database::sd::new(ns, db, &sink.name);
+		let state = txn.get(&key, version).await?;
+		values.push(sink.clone().structure_with_state(state));
+	}
+	Ok(Value::Array(values.into()))
+}
+
 /// Process module definitions into structured Values, enriching each with
 /// export signatures from the cached surrealism runtime when available.
 pub(crate) async fn process_modules(
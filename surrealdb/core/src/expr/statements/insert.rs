@@ -57,6 +57,34 @@ impl InsertStatement {
 			}
 			None => ctx,
 		};
+		// Prepare the records to insert
+		self.prepare(stk, ctx, opt, doc, &mut iterator).await?;
+		// Assign the statement
+		let stm = Statement::from(self);
+
+		// Ensure the database exists.
+		ctx.get_db(opt).await?;
+
+		CursorDoc::update_parent(ctx, doc, async |ctx| {
+			// Process the statement
+			let res = iterator.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await?;
+			// Catch statement timeout
+			ctx.expect_not_timedout().await?;
+			// Output the results
+			Ok(res)
+		})
+		.await
+	}
+
+	/// Prepares the records to insert, ingesting them into the iterator
+	pub(crate) async fn prepare(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		iterator: &mut Iterator,
+	) -> Result<()> {
 		// Parse the INTO expression
 		let tb = match &self.into {
 			Some(into) => {
@@ -233,21 +261,7 @@ impl InsertStatement {
 			}
 			v => fail!("Unknown data clause type in INSERT statement: {v:?}"),
 		}
-		// Assign the statement
-		let stm = Statement::from(self);
-
-		// Ensure the database exists.
-		ctx.get_db(opt).await?;
-
-		CursorDoc::update_parent(ctx, doc, async |ctx| {
-			// Process the statement
-			let res = iterator.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await?;
-			// Catch statement timeout
-			ctx.expect_not_timedout().await?;
-			// Output the results
-			Ok(res)
-		})
-		.await
+		Ok(())
 	}
 }
 
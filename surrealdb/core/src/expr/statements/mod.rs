@@ -1,5 +1,6 @@
 pub(crate) mod access;
 pub(crate) mod alter;
+pub(crate) mod analyze;
 pub(crate) mod create;
 // needs to be public because the RPC layer is accessing the kv store for api
 // definitions.
@@ -26,6 +27,7 @@ pub(crate) mod r#use;
 
 pub(crate) use self::access::AccessStatement;
 pub(crate) use self::alter::AlterStatement;
+pub(crate) use self::analyze::AnalyzeStatement;
 pub(crate) use self::create::CreateStatement;
 pub(crate) use self::define::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement,
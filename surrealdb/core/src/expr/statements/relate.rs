@@ -60,6 +60,47 @@ impl RelateStatement {
 			}
 			None => ctx,
 		};
+		// Prepare the relations to create
+		self.prepare(stk, ctx, opt, doc, &mut iterator).await?;
+
+		// Assign the statement
+		let stm = Statement::from(self);
+
+		CursorDoc::update_parent(ctx, doc, async |ctx| {
+			// Process the statement
+			let res = iterator
+				.output(stk, ctx.as_ref(), opt, &stm, RecordStrategy::KeysAndValues)
+				.await?;
+			// Catch statement timeout
+			ctx.expect_not_timedout().await?;
+			// Output the results
+			match res {
+				// This is a single record result
+				Value::Array(mut a) if self.only => match a.len() {
+					// There was exactly one result
+					1 => Ok(a.0.pop().expect("array has exactly one element")),
+					// No results (e.g. record did not exist): return None for backwards
+					// compatibility with clients that expect a single value.
+					0 => Ok(Value::None),
+					// There were no results
+					_ => Err(anyhow::Error::new(Error::SingleOnlyOutput)),
+				},
+				// This is standard query result
+				v => Ok(v),
+			}
+		})
+		.await
+	}
+
+	/// Prepares the relations to create, ingesting them into the iterator
+	pub(crate) async fn prepare(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		iterator: &mut Iterator,
+	) -> Result<()> {
 		// Loop over the from targets
 		let from = {
 			let mut out = Vec::new();
@@ -177,34 +218,7 @@ impl RelateStatement {
 				iterator.ingest(Iterable::Relatable(doc_ctx, f.clone(), through, t.clone(), None));
 			}
 		}
-
-		// Assign the statement
-		let stm = Statement::from(self);
-
-		CursorDoc::update_parent(ctx, doc, async |ctx| {
-			// Process the statement
-			let res = iterator
-				.output(stk, ctx.as_ref(), opt, &stm, RecordStrategy::KeysAndValues)
-				.await?;
-			// Catch statement timeout
-			ctx.expect_not_timedout().await?;
-			// Output the results
-			match res {
-				// This is a single record result
-				Value::Array(mut a) if self.only => match a.len() {
-					// There was exactly one result
-					1 => Ok(a.0.pop().expect("array has exactly one element")),
-					// No results (e.g. record did not exist): return None for backwards
-					// compatibility with clients that expect a single value.
-					0 => Ok(Value::None),
-					// There were no results
-					_ => Err(anyhow::Error::new(Error::SingleOnlyOutput)),
-				},
-				// This is standard query result
-				v => Ok(v),
-			}
-		})
-		.await
+		Ok(())
 	}
 }
 
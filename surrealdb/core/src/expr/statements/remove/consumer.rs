@@ -0,0 +1,65 @@
+use anyhow::Result;
+use reblessive::tree::Stk;
+
+use crate::catalog::providers::DatabaseProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RemoveConsumerStatement {
+	pub name: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveConsumerStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl RemoveConsumerStatement {
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Compute the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "consumer name").await?;
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let cu = match txn.get_db_consumer(ns, db, &name, None).await {
+			Ok(x) => x,
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::CuNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Delete the checkpoint
+		let key = crate::key::database::ck::new(ns, db, &cu.name);
+		txn.del(&key).await?;
+		// Delete the definition
+		let key = crate::key::database::cu::new(ns, db, &cu.name);
+		txn.del(&key).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
@@ -77,6 +77,8 @@ impl RemoveDatabaseStatement {
 		}
 		// Clear the cache
 		txn.clear_cache();
+		// Clear the cached quotas
+		ctx.clear_quota_cache();
 		// Ok all good
 		Ok(Value::None)
 	}
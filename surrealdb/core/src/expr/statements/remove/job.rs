@@ -0,0 +1,65 @@
+use anyhow::Result;
+use reblessive::tree::Stk;
+
+use crate::catalog::providers::DatabaseProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RemoveJobStatement {
+	pub name: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveJobStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl RemoveJobStatement {
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Job, &Base::Db)?;
+		// Compute the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "job name").await?;
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let jb = match txn.get_db_job(ns, db, &name, None).await {
+			Ok(x) => x,
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::JbNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Delete the run history
+		let key = crate::key::database::js::new(ns, db, &jb.name);
+		txn.del(&key).await?;
+		// Delete the definition
+		let key = crate::key::database::jb::new(ns, db, &jb.name);
+		txn.del(&key).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
@@ -3,16 +3,19 @@ mod analyzer;
 mod api;
 mod bucket;
 mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
 mod model;
 mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 mod user;
 
@@ -22,17 +25,20 @@ use anyhow::Result;
 pub(crate) use api::RemoveApiStatement;
 pub(crate) use bucket::RemoveBucketStatement;
 pub(crate) use config::RemoveConfigStatement;
+pub(crate) use consumer::RemoveConsumerStatement;
 pub(crate) use database::RemoveDatabaseStatement;
 pub(crate) use event::RemoveEventStatement;
 pub(crate) use field::RemoveFieldStatement;
 pub(crate) use function::RemoveFunctionStatement;
 pub(crate) use index::RemoveIndexStatement;
+pub(crate) use job::RemoveJobStatement;
 pub(crate) use model::RemoveModelStatement;
 pub(crate) use module::RemoveModuleStatement;
 pub(crate) use namespace::RemoveNamespaceStatement;
 pub(crate) use param::RemoveParamStatement;
 use reblessive::tree::Stk;
 pub(crate) use sequence::RemoveSequenceStatement;
+pub(crate) use sink::RemoveSinkStatement;
 pub(crate) use table::RemoveTableStatement;
 pub(crate) use user::RemoveUserStatement;
 
@@ -58,6 +64,9 @@ pub(crate) enum RemoveStatement {
 	Api(RemoveApiStatement),
 	Bucket(RemoveBucketStatement),
 	Sequence(RemoveSequenceStatement),
+	Job(RemoveJobStatement),
+	Consumer(RemoveConsumerStatement),
+	Sink(RemoveSinkStatement),
 	Module(RemoveModuleStatement),
 	Config(RemoveConfigStatement),
 }
@@ -87,6 +96,9 @@ impl RemoveStatement {
 			Self::Api(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Bucket(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Sequence(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Job(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Consumer(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Sink(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Module(v) => v.compute(ctx, opt).await,
 			Self::Config(v) => v.compute(ctx, opt).await,
 		}
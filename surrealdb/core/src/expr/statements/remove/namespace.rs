@@ -82,6 +82,8 @@ impl RemoveNamespaceStatement {
 		}
 		// Clear the cache
 		txn.clear_cache();
+		// Clear the cached quotas
+		ctx.clear_quota_cache();
 		// Ok all good
 		Ok(Value::None)
 	}
@@ -65,6 +65,8 @@ impl RemoveUserStatement {
 				txn.del(&key).await?;
 				// Clear the cache
 				txn.clear_cache();
+				// Clear the cached quotas
+				ctx.clear_quota_cache();
 				// Ok all good
 				Ok(Value::None)
 			}
@@ -92,6 +94,8 @@ impl RemoveUserStatement {
 				txn.del(&key).await?;
 				// Clear the cache
 				txn.clear_cache();
+				// Clear the cached quotas
+				ctx.clear_quota_cache();
 				// Ok all good
 				Ok(Value::None)
 			}
@@ -120,6 +124,8 @@ impl RemoveUserStatement {
 				txn.del(&key).await?;
 				// Clear the cache
 				txn.clear_cache();
+				// Clear the cached quotas
+				ctx.clear_quota_cache();
 				// Ok all good
 				Ok(Value::None)
 			}
@@ -99,6 +99,13 @@ impl SelectStatement {
 		// This is calculated on the parent doc
 		let ctx = stm.setup_timeout(stk, ctx, &opt, parent_doc).await?;
 
+		// Hybrid searches are only planned by the streaming executor
+		if matches!(self.with, Some(With::Fusion(_))) {
+			return Err(anyhow::Error::new(Error::Query {
+				message: "WITH FUSION is only supported by the streaming query planner".to_string(),
+			}));
+		}
+
 		// Get a query planner
 		let mut planner = QueryPlanner::new();
 
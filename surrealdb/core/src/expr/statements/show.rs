@@ -11,6 +11,8 @@ use crate::val::{Datetime, TableName};
 pub enum ShowSince {
 	Timestamp(Datetime),
 	Versionstamp(u64),
+	/// The changes after the checkpoint of a consumer group
+	Consumer(String),
 }
 
 /// A SHOW CHANGES statement for displaying changes made to a table or database.
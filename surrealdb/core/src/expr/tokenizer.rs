@@ -2,23 +2,42 @@ use std::fmt;
 use std::fmt::Display;
 
 use revision::revisioned;
+use surrealdb_types::ToSql;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Tokenizer {
 	Blank,
 	Camel,
 	Class,
 	Punct,
+	/// Unicode (UAX #29) word segmentation
+	#[revision(start = 2)]
+	Unicode,
+	/// Dictionary-based segmentation of CJK text, with an optional path to
+	/// a word list
+	#[revision(start = 2)]
+	Cjk(Option<String>),
+	/// Every match of a regular expression is a token
+	#[revision(start = 2)]
+	Regex(String),
+	/// The whole input is a single token
+	#[revision(start = 2)]
+	Keyword,
+}
+
+impl Tokenizer {
+	/// Character class tokenizers split the input at the boundaries between
+	/// classes of characters. The other tokenizers segment the input, before
+	/// any character class tokenizer is applied to each segment.
+	pub(crate) fn is_character_class(&self) -> bool {
+		matches!(self, Self::Blank | Self::Camel | Self::Class | Self::Punct)
+	}
 }
 
 impl Display for Tokenizer {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str(match self {
-			Self::Blank => "BLANK",
-			Self::Camel => "CAMEL",
-			Self::Class => "CLASS",
-			Self::Punct => "PUNCT",
-		})
+		let tokenizer: crate::sql::tokenizer::Tokenizer = self.clone().into();
+		f.write_str(&tokenizer.to_sql())
 	}
 }
@@ -0,0 +1,34 @@
+use std::time;
+
+use revision::revisioned;
+use surrealdb_types::ToSql;
+
+use crate::expr::Idiom;
+use crate::expr::statements::info::InfoStructure;
+use crate::val::{Duration, Value};
+
+/// The time-to-live of the records in a table
+///
+/// Expired records are removed by a background sweeper, which deletes them
+/// like a normal `DELETE` statement would.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum Ttl {
+	/// Records expire this long after they were last written
+	Duration(time::Duration),
+	/// Records expire at the datetime held in this field
+	Field(Idiom),
+}
+
+impl InfoStructure for Ttl {
+	fn structure(self) -> Value {
+		match self {
+			Self::Duration(v) => Value::from(map! {
+				"duration".to_string() => Duration(v).into(),
+			}),
+			Self::Field(v) => Value::from(map! {
+				"field".to_string() => v.to_sql().into(),
+			}),
+		}
+	}
+}
@@ -24,3 +24,18 @@ impl Default for UserDuration {
 		}
 	}
 }
+
+/// The TOTP second factor of a user
+#[derive(Debug, Default, Hash, Clone, Eq, PartialEq)]
+pub(crate) enum UserTotp {
+	/// The user is not enrolled
+	#[default]
+	Unset,
+	/// Enrol the user with a newly generated secret and recovery codes
+	Generate,
+	/// An existing secret, along with the hashes of the unused recovery codes
+	Secret {
+		secret: String,
+		recovery: Vec<String>,
+	},
+}
@@ -13,20 +13,24 @@ use crate::expr::statements::access::{
 };
 use crate::expr::statements::alter::{
 	AlterAccessStatement, AlterAnalyzerStatement, AlterApiClause, AlterApiStatement,
-	AlterBucketStatement, AlterConfigStatement, AlterDatabaseStatement, AlterDefault,
-	AlterEventStatement, AlterFieldStatement, AlterFunctionStatement, AlterIndexStatement,
-	AlterKind, AlterModuleStatement, AlterNamespaceStatement, AlterParamStatement,
-	AlterSequenceStatement, AlterSystemStatement, AlterTableStatement, AlterUserStatement,
+	AlterBucketStatement, AlterConfigStatement, AlterConsumerStatement, AlterDatabaseStatement,
+	AlterDefault, AlterEventStatement, AlterFieldStatement, AlterFunctionStatement,
+	AlterIndexStatement, AlterJobStatement, AlterKind, AlterModuleStatement,
+	AlterNamespaceStatement, AlterParamStatement, AlterSequenceStatement, AlterSystemStatement,
+	AlterTableStatement, AlterUserStatement,
 };
+use crate::expr::statements::analyze::AnalyzeStatement;
 use crate::expr::statements::define::config::ConfigInner;
 use crate::expr::statements::define::config::api::ApiConfig;
 use crate::expr::statements::define::config::defaults::DefaultConfig;
 use crate::expr::statements::define::{
-	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineDefault, DefineSequenceStatement,
+	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineConsumerStatement,
+	DefineDefault, DefineJobStatement, DefineSequenceStatement, DefineSinkStatement,
 };
 use crate::expr::statements::rebuild::RebuildStatement;
 use crate::expr::statements::remove::{
-	RemoveApiStatement, RemoveBucketStatement, RemoveSequenceStatement,
+	RemoveApiStatement, RemoveBucketStatement, RemoveConsumerStatement, RemoveJobStatement,
+	RemoveSequenceStatement, RemoveSinkStatement,
 };
 use crate::expr::statements::{
 	AccessStatement, AlterStatement, CreateStatement, DefineAccessStatement,
@@ -134,6 +138,9 @@ implement_visitor! {
 			TopLevelExpr::Begin => {},
 			TopLevelExpr::Cancel => {},
 			TopLevelExpr::Commit => {},
+			TopLevelExpr::Savepoint(_) => {},
+			TopLevelExpr::RollbackTo(_) => {},
+			TopLevelExpr::Release(_) => {},
 			TopLevelExpr::Access(s) => {this.visit_access(s)? },
 			TopLevelExpr::Kill(s) => {this.visit_kill(s)?; },
 			TopLevelExpr::Live(s) => {this.visit_live(s)?; },
@@ -284,6 +291,9 @@ implement_visitor! {
 			Expr::Rebuild(s) => {
 				this.visit_rebuild(s)?;
 			},
+			Expr::Analyze(s) => {
+				this.visit_analyze(s)?;
+			},
 			Expr::Alter(s) => {
 				this.visit_alter(s)?;
 			},
@@ -356,6 +366,8 @@ implement_visitor! {
 			AlterStatement::Event(a)=>{ this.visit_alter_event(a)?; },
 			AlterStatement::Index(a) => { this.visit_alter_index(a)?; },
 			AlterStatement::Sequence(a) => { this.visit_alter_sequence(a)?; },
+			AlterStatement::Job(a) => { this.visit_alter_job(a)?; },
+			AlterStatement::Consumer(a) => { this.visit_alter_consumer(a)?; },
 			AlterStatement::Field(a) => { this.visit_alter_field(a)?; },
 			AlterStatement::Param(a) => { this.visit_alter_param(a)?; },
 			AlterStatement::Bucket(a) => { this.visit_alter_bucket(a)?; },
@@ -452,6 +464,20 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_alter_job(this, a: &AlterJobStatement){
+		if let Some(ref x) = a.then {
+			this.visit_expr(x)?;
+		}
+		Ok(())
+	}
+
+	fn visit_alter_consumer(this, a: &AlterConsumerStatement){
+		if let Some(ref x) = a.ack {
+			this.visit_expr(x)?;
+		}
+		Ok(())
+	}
+
 	fn visit_alter_param(this, a: &AlterParamStatement){
 		if let Some(ref x) = a.value {
 			this.visit_expr(x)?;
@@ -541,6 +567,10 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_analyze(this, a: &AnalyzeStatement){
+		Ok(())
+	}
+
 	fn visit_use(this, t: &UseStatement){
 		Ok(())
 	}
@@ -658,6 +688,15 @@ implement_visitor! {
 			RemoveStatement::Sequence(r) => {
 				this.visit_remove_sequence(r)?;
 			},
+			RemoveStatement::Job(r) => {
+				this.visit_remove_job(r)?;
+			},
+			RemoveStatement::Consumer(r) => {
+				this.visit_remove_consumer(r)?;
+			},
+			RemoveStatement::Sink(r) => {
+				this.visit_remove_sink(r)?;
+			},
 			RemoveStatement::Module(r) => {
 				this.visit_remove_module(r)?;
 			},
@@ -750,6 +789,21 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_remove_job(this, r: &RemoveJobStatement){
+		this.visit_expr(&r.name)?;
+		Ok(())
+	}
+
+	fn visit_remove_consumer(this, r: &RemoveConsumerStatement){
+		this.visit_expr(&r.name)?;
+		Ok(())
+	}
+
+	fn visit_remove_sink(this, r: &RemoveSinkStatement){
+		this.visit_expr(&r.name)?;
+		Ok(())
+	}
+
 	fn visit_relate(this, o: &RelateStatement){
 		this.visit_expr(&o.through)?;
 		this.visit_expr(&o.from)?;
@@ -811,6 +865,7 @@ implement_visitor! {
 				this.visit_expr(expr)?;
 				this.visit_expr(expr1)?;
 			},
+			InfoStatement::Api => {},
 		}
 		Ok(())
 	}
@@ -897,6 +952,15 @@ implement_visitor! {
 			DefineStatement::Sequence(d) => {
 				this.visit_define_sequence(d)?;
 			},
+			DefineStatement::Job(d) => {
+				this.visit_define_job(d)?;
+			},
+			DefineStatement::Consumer(d) => {
+				this.visit_define_consumer(d)?;
+			},
+			DefineStatement::Sink(d) => {
+				this.visit_define_sink(d)?;
+			},
 			DefineStatement::Module(d) => {
 				this.visit_define_module(d)?;
 			},
@@ -912,6 +976,26 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_define_job(this, d: &DefineJobStatement) {
+		this.visit_expr(&d.name)?;
+		this.visit_expr(&d.then)?;
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
+	fn visit_define_consumer(this, d: &DefineConsumerStatement) {
+		this.visit_expr(&d.name)?;
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
+	fn visit_define_sink(this, d: &DefineSinkStatement) {
+		this.visit_expr(&d.name)?;
+		this.visit_expr(&d.target_table)?;
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
 	fn visit_define_bucket(this, d: &DefineBucketStatement) {
 		this.visit_expr(&d.name)?;
 		if let Some(expr) = d.backend.as_ref(){
@@ -1644,6 +1728,9 @@ implement_visitor_mut! {
 			TopLevelExpr::Begin => {},
 			TopLevelExpr::Cancel => {},
 			TopLevelExpr::Commit => {},
+			TopLevelExpr::Savepoint(_) => {},
+			TopLevelExpr::RollbackTo(_) => {},
+			TopLevelExpr::Release(_) => {},
 			TopLevelExpr::Access(s) => {this.visit_mut_access(s)? },
 			TopLevelExpr::Kill(s) => {this.visit_mut_kill(s)?; },
 			TopLevelExpr::Live(s) => {this.visit_mut_live(s)?; },
@@ -1794,6 +1881,9 @@ implement_visitor_mut! {
 			Expr::Rebuild(s) => {
 				this.visit_mut_rebuild(s)?;
 			},
+			Expr::Analyze(s) => {
+				this.visit_mut_analyze(s)?;
+			},
 			Expr::Alter(s) => {
 				this.visit_mut_alter(s)?;
 			},
@@ -1866,6 +1956,8 @@ implement_visitor_mut! {
 			AlterStatement::Event(a)=>{ this.visit_mut_alter_event(a)?;},
 			AlterStatement::Index(a)=>{ this.visit_mut_alter_index(a)?;},
 			AlterStatement::Sequence(a) => { this.visit_mut_alter_sequence(a)?; },
+			AlterStatement::Job(a) => { this.visit_mut_alter_job(a)?; },
+			AlterStatement::Consumer(a) => { this.visit_mut_alter_consumer(a)?; },
 			AlterStatement::Field(a) => { this.visit_mut_alter_field(a)?; },
 			AlterStatement::Param(a) => { this.visit_mut_alter_param(a)?; },
 			AlterStatement::Bucket(a) => { this.visit_mut_alter_bucket(a)?; },
@@ -1995,6 +2087,20 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_alter_job(this, a: &mut AlterJobStatement){
+		if let Some(ref mut x) = a.then {
+			this.visit_mut_expr(x)?;
+		}
+		Ok(())
+	}
+
+	fn visit_mut_alter_consumer(this, a: &mut AlterConsumerStatement){
+		if let Some(ref mut x) = a.ack {
+			this.visit_mut_expr(x)?;
+		}
+		Ok(())
+	}
+
 	fn visit_mut_alter_field(this, a: &mut AlterFieldStatement){
 		this.visit_mut_idiom(&mut a.name)?;
 
@@ -2051,6 +2157,10 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_analyze(this, a: &mut AnalyzeStatement){
+		Ok(())
+	}
+
 	fn visit_mut_use(this, t: &mut UseStatement){
 		Ok(())
 	}
@@ -2168,6 +2278,15 @@ implement_visitor_mut! {
 			RemoveStatement::Sequence(r) => {
 				this.visit_mut_remove_sequence(r)?;
 			},
+			RemoveStatement::Job(r) => {
+				this.visit_mut_remove_job(r)?;
+			},
+			RemoveStatement::Consumer(r) => {
+				this.visit_mut_remove_consumer(r)?;
+			},
+			RemoveStatement::Sink(r) => {
+				this.visit_mut_remove_sink(r)?;
+			},
 			RemoveStatement::Module(r) => {
 				this.visit_mut_remove_module(r)?;
 			},
@@ -2260,6 +2379,21 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_remove_job(this, r: &mut RemoveJobStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		Ok(())
+	}
+
+	fn visit_mut_remove_consumer(this, r: &mut RemoveConsumerStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		Ok(())
+	}
+
+	fn visit_mut_remove_sink(this, r: &mut RemoveSinkStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		Ok(())
+	}
+
 	fn visit_mut_relate(this, o: &mut RelateStatement){
 		this.visit_mut_expr(&mut o.through)?;
 		this.visit_mut_expr(&mut o.from)?;
@@ -2321,6 +2455,7 @@ implement_visitor_mut! {
 				this.visit_mut_expr(expr)?;
 				this.visit_mut_expr(expr1)?;
 			},
+			InfoStatement::Api => {},
 		}
 		Ok(())
 	}
@@ -2407,6 +2542,15 @@ implement_visitor_mut! {
 			DefineStatement::Sequence(d) => {
 				this.visit_mut_define_sequence(d)?;
 			},
+			DefineStatement::Job(d) => {
+				this.visit_mut_define_job(d)?;
+			},
+			DefineStatement::Consumer(d) => {
+				this.visit_mut_define_consumer(d)?;
+			},
+			DefineStatement::Sink(d) => {
+				this.visit_mut_define_sink(d)?;
+			},
 			DefineStatement::Module(d) => {
 				this.visit_mut_define_module(d)?;
 			},
@@ -2414,6 +2558,26 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_define_job(this, d: &mut DefineJobStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		this.visit_mut_expr(&mut d.then)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
+	fn visit_mut_define_consumer(this, d: &mut DefineConsumerStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
+	fn visit_mut_define_sink(this, d: &mut DefineSinkStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		this.visit_mut_expr(&mut d.target_table)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
 	fn visit_mut_define_sequence(this, d: &mut DefineSequenceStatement) {
 		this.visit_mut_expr(&mut d.name)?;
 		this.visit_mut_expr(&mut d.batch)?;
@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use surrealdb_types::{SqlFormat, ToSql};
 
 use crate::fmt::{EscapeKwFreeIdent, Fmt};
@@ -6,6 +8,7 @@ use crate::fmt::{EscapeKwFreeIdent, Fmt};
 pub enum With {
 	NoIndex,
 	Index(Vec<String>),
+	Fusion(Fusion),
 }
 
 impl ToSql for With {
@@ -18,6 +21,84 @@ impl ToSql for With {
 				Fmt::comma_separated(i.iter().map(|x| EscapeKwFreeIdent(x.as_str())))
 					.fmt_sql(f, fmt)
 			}
+			With::Fusion(fusion) => {
+				f.push_str(" FUSION ");
+				fusion.fmt_sql(f, fmt);
+			}
+		}
+	}
+}
+
+/// How the results of a KNN search and of a full-text search are fused
+/// into a single ranking by a hybrid query.
+#[derive(Clone, Debug)]
+pub enum Fusion {
+	/// Reciprocal rank fusion, with the given rank constant.
+	Rrf(u32),
+	/// Weighted sum of the normalized scores of each search.
+	Linear {
+		/// The weight of the KNN score.
+		vector: f32,
+		/// The weight of the full-text score.
+		text: f32,
+		norm: FusionNorm,
+	},
+}
+
+impl PartialEq for Fusion {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Fusion::Rrf(a), Fusion::Rrf(b)) => a == b,
+			(
+				Fusion::Linear {
+					vector,
+					text,
+					norm,
+				},
+				Fusion::Linear {
+					vector: other_vector,
+					text: other_text,
+					norm: other_norm,
+				},
+			) => {
+				vector.to_bits() == other_vector.to_bits()
+					&& text.to_bits() == other_text.to_bits()
+					&& norm == other_norm
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Eq for Fusion {}
+
+impl Hash for Fusion {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		match self {
+			Fusion::Rrf(k) => k.hash(state),
+			Fusion::Linear {
+				vector,
+				text,
+				norm,
+			} => {
+				vector.to_bits().hash(state);
+				text.to_bits().hash(state);
+				norm.hash(state);
+			}
 		}
 	}
 }
+
+impl ToSql for Fusion {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let fusion: crate::sql::with::Fusion = self.clone().into();
+		fusion.fmt_sql(f, fmt);
+	}
+}
+
+/// The normalization applied to the scores of a linear fusion.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum FusionNorm {
+	MinMax,
+	ZScore,
+}
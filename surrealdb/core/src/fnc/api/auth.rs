@@ -0,0 +1,89 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use reblessive::tree::Stk;
+
+use crate::api::err::ApiError;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::iam::Role;
+use crate::val::{Closure, Value};
+
+/// Middleware function that requires the request to be authenticated.
+///
+/// Anonymous requests receive a `401 Unauthorized` response. The check is made
+/// against the session after the `AUTH LIMIT` of the API has been applied.
+///
+/// # Arguments
+/// * `req` - The API request object
+/// * `next` - The next middleware or handler in the chain
+///
+/// # Returns
+/// * `Ok(response)` - The response from the next middleware/handler
+/// * `Err(e)` - Error if the request is not authenticated
+///
+/// # Example
+/// ```surql
+/// DEFINE API "/account"
+///     FOR get
+///         MIDDLEWARE
+///             api::auth::required()
+///         THEN {
+///             RETURN { status: 200, body: $auth };
+///         };
+/// ```
+pub async fn required(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+	(req, next): (Value, Box<Closure>),
+) -> Result<Value> {
+	if opt.auth.is_anon() {
+		return Err(ApiError::Unauthorized.into());
+	}
+
+	next.invoke(stk, ctx, opt, doc, vec![req]).await
+}
+
+/// Middleware function that requires the request to be made by a system user
+/// with at least the given role.
+///
+/// Anonymous requests receive a `401 Unauthorized` response, and requests from
+/// record users or system users with a lesser role receive a `403 Forbidden`
+/// response. The check is made against the session after the `AUTH LIMIT` of
+/// the API has been applied.
+///
+/// # Arguments
+/// * `req` - The API request object
+/// * `next` - The next middleware or handler in the chain
+/// * `role` - The minimum role required, one of `viewer`, `editor` or `owner`
+///
+/// # Returns
+/// * `Ok(response)` - The response from the next middleware/handler
+/// * `Err(e)` - Error if the role is invalid, or the request does not have the role
+///
+/// # Example
+/// ```surql
+/// DEFINE API "/admin"
+///     FOR post
+///         MIDDLEWARE
+///             api::auth::role("editor")
+///         THEN {
+///             RETURN { status: 200, body: {} };
+///         };
+/// ```
+pub async fn role(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+	(req, next, role): (Value, Box<Closure>, String),
+) -> Result<Value> {
+	let role = Role::from_str(&role)?;
+
+	if opt.auth.is_anon() {
+		return Err(ApiError::Unauthorized.into());
+	}
+
+	if !opt.auth.max_role().is_some_and(|x| x >= role) {
+		return Err(ApiError::PermissionDenied.into());
+	}
+
+	next.invoke(stk, ctx, opt, doc, vec![req]).await
+}
@@ -1,5 +1,6 @@
-use anyhow::Result;
-use http::header::{ACCEPT, CONTENT_TYPE};
+use anyhow::{Result, bail};
+use http::header::{ACCEPT, CONTENT_TYPE, ORIGIN, RETRY_AFTER};
+use http::{HeaderName, HeaderValue};
 use reblessive::tree::Stk;
 use tracing::trace;
 use uuid::Uuid;
@@ -8,6 +9,8 @@ use super::args::Optional;
 use crate::api::err::ApiError;
 use crate::api::format as api_format;
 use crate::api::invocation::process_api_request_with_stack;
+use crate::api::middleware::cors::CorsOptions;
+use crate::api::middleware::limit::{self, RateLimit};
 use crate::api::request::ApiRequest;
 use crate::api::response::ApiResponse;
 use crate::catalog::ApiDefinition;
@@ -16,12 +19,17 @@ use crate::ctx::{Context, FrozenContext};
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::expr::paths::IP;
 use crate::fnc::args::FromPublic;
 use crate::val::{Closure, Duration, Value};
 
+pub mod auth;
 pub mod req;
 pub mod res;
 
+const X_RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+const X_RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+
 /// Invokes an API endpoint programmatically from within SurrealQL.
 ///
 /// This function allows you to call a defined API endpoint from within SurrealQL code,
@@ -142,3 +150,162 @@ pub async fn timeout(
 
 	next.invoke(stk, ctx, opt, doc, vec![req]).await
 }
+
+/// Middleware function that applies CORS headers to the response.
+///
+/// Responses to requests with an allowed `Origin` header receive the matching
+/// `Access-Control-*` headers. Requests from other origins are still processed,
+/// but receive no CORS headers, so browsers will refuse to expose the response.
+/// Preflight `OPTIONS` requests are answered by the server itself.
+///
+/// # Arguments
+/// * `req` - The API request object
+/// * `next` - The next middleware or handler in the chain
+/// * `options` - Optional CORS options. If not provided, any origin is allowed:
+///   - `origins`: The allowed origins, where `*` allows any origin (default: `["*"]`)
+///   - `methods`: The allowed methods (default: `GET, POST, PUT, PATCH, DELETE`)
+///   - `headers`: The allowed request headers (default: `Accept, Authorization, Content-Type`)
+///   - `expose`: The response headers exposed to scripts (default: none)
+///   - `credentials`: Whether credentials are allowed (default: `false`)
+///   - `max_age`: How long the CORS response may be cached (default: none)
+///
+/// # Returns
+/// * `Ok(response)` - The response with CORS headers applied
+/// * `Err(e)` - Error if any of the configured values are not valid header values
+///
+/// # Example
+/// ```surql
+/// DEFINE API "/public"
+///     FOR get
+///         MIDDLEWARE
+///             api::cors({ origins: ["https://example.com"], credentials: true })
+///         THEN {
+///             RETURN { status: 200, body: {} };
+///         };
+/// ```
+pub async fn cors(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+	(FromPublic(req), next, Optional(options)): (
+		FromPublic<ApiRequest>,
+		Box<Closure>,
+		Optional<FromPublic<CorsOptions>>,
+	),
+) -> Result<Value> {
+	let options = match options {
+		Some(FromPublic(options)) => options,
+		// Without any options, any origin is allowed
+		None => CorsOptions::default(),
+	};
+	let origin = req.headers.get(ORIGIN).cloned();
+
+	let res = next.invoke(stk, ctx, opt, doc, vec![req.into()]).await?;
+
+	// Requests without an allowed origin receive no CORS headers
+	let Some(origin) = origin.filter(|x| x.to_str().is_ok_and(|x| options.allows(x))) else {
+		return Ok(res);
+	};
+
+	let mut res: ApiResponse = res.try_into()?;
+	options.apply(&origin, &mut res.headers)?;
+	Ok(res.into())
+}
+
+/// Middleware function that limits the number of requests a client can make.
+///
+/// Requests are counted in fixed windows per client, and requests over the
+/// limit receive a `429 Too Many Requests` response with a `Retry-After`
+/// header. Counters are kept in memory on each node, and are shared by all
+/// endpoints in the database which use the same limit and window.
+///
+/// # Arguments
+/// * `req` - The API request object
+/// * `next` - The next middleware or handler in the chain
+/// * `limit` - The maximum number of requests allowed in each window
+/// * `window` - The duration of each window
+/// * `key` - Optional client key. If not provided, defaults to `ip`:
+///   - `ip`: Requests are counted per client IP address
+///   - `auth`: Requests are counted per authenticated user, falling back to the client IP address
+///     for anonymous requests
+///
+/// Requests counted by IP address which have no client IP address, such as
+/// requests made with `api::invoke`, do not come from a remote client, and
+/// are not limited.
+///
+/// # Returns
+/// * `Ok(response)` - The response from the next middleware/handler, or a 429 response
+/// * `Err(e)` - Error if the arguments are invalid
+///
+/// # Example
+/// ```surql
+/// DEFINE API "/search"
+///     FOR get
+///         MIDDLEWARE
+///             api::rate_limit(100, 1m, "auth")
+///         THEN {
+///             RETURN { status: 200, body: {} };
+///         };
+/// ```
+pub async fn rate_limit(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+	(FromPublic(req), next, limit, window, Optional(key)): (
+		FromPublic<ApiRequest>,
+		Box<Closure>,
+		i64,
+		Duration,
+		Optional<String>,
+	),
+) -> Result<Value> {
+	let Ok(limit) = u64::try_from(limit) else {
+		bail!(Error::InvalidFunctionArguments {
+			name: "api::rate_limit".to_string(),
+			message: "The limit must be a positive integer".to_string(),
+		});
+	};
+	if window.is_zero() {
+		bail!(Error::InvalidFunctionArguments {
+			name: "api::rate_limit".to_string(),
+			message: "The window must be greater than zero".to_string(),
+		});
+	}
+	// Identify the client making the request
+	let ip = match ctx.value("session").map(|session| session.pick(IP.as_ref())) {
+		Some(Value::String(ip)) => Some(ip),
+		_ => None,
+	};
+	let client = match (key.as_deref().unwrap_or("ip"), ip) {
+		("auth", _) if !opt.auth.is_anon() => format!("auth:{}{}", opt.auth.level(), opt.auth.id()),
+		("ip" | "auth", Some(ip)) => format!("ip:{ip}"),
+		// Counting every request without an address against a single
+		// client would let one client exhaust the limit for all the others
+		("ip" | "auth", None) => return next.invoke(stk, ctx, opt, doc, vec![req.into()]).await,
+		_ => bail!(Error::InvalidFunctionArguments {
+			name: "api::rate_limit".to_string(),
+			message: "The key must be one of 'ip' or 'auth'".to_string(),
+		}),
+	};
+	let (ns, db) = opt.ns_db()?;
+	let key = format!("{ns}\0{db}\0{limit}\0{}\0{client}", window.as_millis());
+	// Count the request against the limit
+	match limit::check(key, limit, *window) {
+		RateLimit::Allowed {
+			remaining,
+		} => {
+			let res = next.invoke(stk, ctx, opt, doc, vec![req.into()]).await?;
+			let mut res: ApiResponse = res.try_into()?;
+			res.headers.insert(X_RATELIMIT_LIMIT, HeaderValue::from(limit));
+			res.headers.insert(X_RATELIMIT_REMAINING, HeaderValue::from(remaining));
+			Ok(res.into())
+		}
+		RateLimit::Exceeded {
+			retry_after,
+		} => {
+			let mut res = ApiResponse::from_error(ApiError::TooManyRequests.into(), req.request_id);
+			// Round up so that clients never retry before the window resets
+			let retry_after = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+			res.headers.insert(RETRY_AFTER, HeaderValue::from(retry_after));
+			res.headers.insert(X_RATELIMIT_LIMIT, HeaderValue::from(limit));
+			res.headers.insert(X_RATELIMIT_REMAINING, HeaderValue::from(0u64));
+			Ok(res.into())
+		}
+	}
+}
@@ -2,11 +2,12 @@ use anyhow::Result;
 use reblessive::tree::Stk;
 
 use crate::api::middleware::common::BodyStrategy;
-use crate::api::middleware::req::BodyParser;
+use crate::api::middleware::req::{BodyParser, ensure_max_size, validate_body};
 use crate::api::request::ApiRequest;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
+use crate::expr::{Bytesize, Kind};
 use crate::fnc::args::{FromPublic, Optional};
 use crate::val::{Closure, Value};
 
@@ -59,3 +60,74 @@ pub async fn body(
 
 	next.invoke(stk, ctx, opt, doc, vec![req.into()]).await
 }
+
+/// Middleware function that limits the size of the request body.
+///
+/// Requests which declare or send a body larger than the given size receive a
+/// `413 Payload Too Large` response. This is checked in addition to the maximum
+/// body size configured for the server.
+///
+/// # Arguments
+/// * `req` - The API request object
+/// * `next` - The next middleware or handler in the chain
+/// * `size` - The maximum body size, for example `"512kb"` or `"1mb"`
+///
+/// # Returns
+/// * `Ok(response)` - The response from the next middleware/handler
+/// * `Err(e)` - Error if the size is invalid or the body is too large
+///
+/// # Example
+/// ```surql
+/// DEFINE API "/upload"
+///     FOR post
+///         MIDDLEWARE
+///             api::req::max_size("1mb")
+///         THEN {
+///             RETURN { status: 201, body: {} };
+///         };
+/// ```
+pub async fn max_size(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+	(FromPublic(req), next, size): (FromPublic<ApiRequest>, Box<Closure>, String),
+) -> Result<Value> {
+	let max = Bytesize::parse(&size)?;
+	ensure_max_size(&req, max)?;
+
+	next.invoke(stk, ctx, opt, doc, vec![req.into()]).await
+}
+
+/// Middleware function that validates the request body against a type.
+///
+/// The request body is coerced to the given type, and requests with a body
+/// which does not match receive a `400 Bad Request` response. As the body must
+/// already be parsed, this should be placed after `api::req::body`.
+///
+/// # Arguments
+/// * `req` - The API request object (modified in place)
+/// * `next` - The next middleware or handler in the chain
+/// * `kind` - The type the body must match, written as in SurrealQL
+///
+/// # Returns
+/// * `Ok(response)` - The response from the next middleware/handler
+/// * `Err(e)` - Error if the type is invalid or the body does not match it
+///
+/// # Example
+/// ```surql
+/// DEFINE API "/users"
+///     FOR post
+///         MIDDLEWARE
+///             api::req::body("json"),
+///             api::req::validate("{ name: string, age: int }")
+///         THEN {
+///             RETURN { status: 201, body: $request.body };
+///         };
+/// ```
+pub async fn validate(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+	(FromPublic(mut req), next, kind): (FromPublic<ApiRequest>, Box<Closure>, String),
+) -> Result<Value> {
+	let kind: Kind = crate::syn::kind(&kind)?.into();
+	validate_body(&mut req, &kind)?;
+
+	next.invoke(stk, ctx, opt, doc, vec![req.into()]).await
+}
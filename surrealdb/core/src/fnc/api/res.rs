@@ -1,19 +1,24 @@
 use std::collections::BTreeMap;
 
 use anyhow::Result;
+use http::header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH};
 use http::{HeaderName, HeaderValue, StatusCode};
 use reblessive::tree::Stk;
 
 use crate::api::err::ApiError;
 use crate::api::middleware::common::BodyStrategy;
-use crate::api::middleware::res::{convert_response_value, output_body_strategy};
+use crate::api::middleware::res::{
+	convert_response_value, entity_tag, matches_entity_tag, output_body_strategy,
+};
 use crate::api::request::ApiRequest;
 use crate::api::response::ApiResponse;
+use crate::catalog::ApiMethod;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::fnc::args::{FromPublic, Optional};
-use crate::val::{Closure, Value};
+use crate::types::PublicValue;
+use crate::val::{Closure, Duration, Value};
 
 /// Middleware function that serializes the response body according to the specified strategy.
 ///
@@ -204,3 +209,66 @@ pub async fn headers(
 
 	Ok(res.into())
 }
+
+/// Middleware function that enables conditional caching of `GET` responses.
+///
+/// Successful responses receive an `ETag` header computed from the response
+/// body, along with a `Cache-Control` header. When the request carries an
+/// `If-None-Match` header matching the `ETag`, the body is dropped and a
+/// `304 Not Modified` response is returned instead. The handler still runs,
+/// so this saves bandwidth rather than computation.
+///
+/// # Arguments
+/// * `req` - The API request object
+/// * `next` - The next middleware or handler in the chain
+/// * `max_age` - Optional duration for which clients may reuse the response without
+///   revalidating. If not provided, clients must always revalidate (`no-cache`).
+///
+/// # Returns
+/// * `Ok(response)` - The response with caching headers, or a 304 response
+/// * `Err(e)` - Error if processing fails
+///
+/// # Example
+/// ```surql
+/// DEFINE API "/products"
+///     FOR get
+///         MIDDLEWARE
+///             api::res::cache(5m)
+///         THEN {
+///             RETURN { status: 200, body: SELECT * FROM product };
+///         };
+/// ```
+pub async fn cache(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+	(FromPublic(req), next, Optional(max_age)): (
+		FromPublic<ApiRequest>,
+		Box<Closure>,
+		Optional<Duration>,
+	),
+) -> Result<Value> {
+	let method = req.method;
+	let if_none_match = req.headers.get(IF_NONE_MATCH).cloned();
+
+	let res = next.invoke(stk, ctx, opt, doc, vec![req.into()]).await?;
+	let mut res: ApiResponse = res.try_into()?;
+
+	// Only successful reads can be cached
+	if method != ApiMethod::Get || !res.status.is_success() {
+		return Ok(res.into());
+	}
+
+	let etag = entity_tag(&res.body);
+	let cache_control = match max_age {
+		Some(max_age) => format!("max-age={}", max_age.as_secs()),
+		None => "no-cache".to_string(),
+	};
+	res.headers.insert(ETAG, HeaderValue::from_str(&etag)?);
+	res.headers.insert(CACHE_CONTROL, HeaderValue::from_str(&cache_control)?);
+
+	if if_none_match.is_some_and(|x| matches_entity_tag(&x, &etag)) {
+		res.status = StatusCode::NOT_MODIFIED;
+		res.body = PublicValue::None;
+	}
+
+	Ok(res.into())
+}
@@ -228,6 +228,47 @@ pub mod scrypt {
 	}
 }
 
+pub mod totp {
+
+	use anyhow::Result;
+
+	use crate::cnf::SERVER_NAME;
+	use crate::fnc::args::Optional;
+	use crate::iam::totp;
+	use crate::val::Value;
+
+	pub fn code((secret,): (String,)) -> Result<Value> {
+		Ok(totp::current_code(&secret)?.into())
+	}
+
+	pub fn secret(_: ()) -> Result<Value> {
+		Ok(totp::generate_secret().into())
+	}
+
+	pub fn uri(
+		(secret, account, Optional(issuer)): (String, String, Optional<String>),
+	) -> Result<Value> {
+		totp::validate_secret(&secret)?;
+		let issuer = issuer.unwrap_or_else(|| SERVER_NAME.to_owned());
+		Ok(totp::provisioning_uri(&secret, &account, &issuer).into())
+	}
+
+	pub fn verify((secret, code): (String, String)) -> Result<Value> {
+		Ok(totp::verify_code(&secret, &code)?.into())
+	}
+
+	/// Returns the time step of a valid code, or `NONE`. Codes for steps up
+	/// to and including `last` are rejected, so that a record access method
+	/// which stores the step of the last used code can reject replayed codes.
+	pub fn step((secret, code, Optional(last)): (String, String, Optional<i64>)) -> Result<Value> {
+		let last = last.and_then(|v| u64::try_from(v).ok());
+		Ok(match totp::verify_step(&secret, &code, last)? {
+			Some(step) => Value::from(step as i64),
+			None => Value::None,
+		})
+	}
+}
+
 /// Code borrowed from [joaat-rs](https://github.com/Pocakking/joaat-rs).
 /// All credits to its author.
 mod joaat {
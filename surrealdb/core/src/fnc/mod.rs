@@ -217,6 +217,11 @@ pub fn synchronous(
 		"crypto::sha1" => crypto::sha1,
 		"crypto::sha256" => crypto::sha256,
 		"crypto::sha512" => crypto::sha512,
+		"crypto::totp::code" => crypto::totp::code,
+		"crypto::totp::secret" => crypto::totp::secret,
+		"crypto::totp::step" => crypto::totp::step,
+		"crypto::totp::uri" => crypto::totp::uri,
+		"crypto::totp::verify" => crypto::totp::verify,
 		//
 		"duration::days" => duration::days,
 		"duration::hours" => duration::hours,
@@ -570,13 +575,20 @@ pub async fn asynchronous(
 		args,
 		"no such builtin function found",
 		//
+		"api::auth::required" => api::auth::required((stk, ctx, opt, doc)).await,
+		"api::auth::role" => api::auth::role((stk, ctx, opt, doc)).await,
+		"api::cors" => api::cors((stk, ctx, opt, doc)).await,
 		"api::invoke" => api::invoke((stk, ctx, opt)).await,
+		"api::rate_limit" => api::rate_limit((stk, ctx, opt, doc)).await,
 		"api::req::body" => api::req::body((stk, ctx, opt, doc)).await,
+		"api::req::max_size" => api::req::max_size((stk, ctx, opt, doc)).await,
+		"api::req::validate" => api::req::validate((stk, ctx, opt, doc)).await,
 		"api::res::body" => api::res::body((stk, ctx, opt, doc)).await,
 		"api::timeout" => api::timeout((stk, ctx, opt, doc)).await,
 		"api::res::status" => api::res::status((stk, ctx, opt, doc)).await,
 		"api::res::header" => api::res::header((stk, ctx, opt, doc)).await,
 		"api::res::headers" => api::res::headers((stk, ctx, opt, doc)).await,
+		"api::res::cache" => api::res::cache((stk, ctx, opt, doc)).await,
 		//
 		"array::all" => array::all((stk, ctx, Some(opt), doc)).await,
 		"array::any" => array::any((stk, ctx, Some(opt), doc)).await,
@@ -5,14 +5,18 @@ use crate::fnc::script::modules::impl_module_def;
 
 pub struct Package;
 
+mod auth;
 mod req;
 mod res;
 
 impl_module_def!(
 	Package,
 	"api",
+	"cors" => fut Async,
 	"invoke" => fut Async,
+	"rate_limit" => fut Async,
 	"timeout" => fut Async,
+	"auth" => (auth::Package),
 	"res" => (res::Package),
 	"req" => (req::Package),
 );
@@ -9,4 +9,6 @@ impl_module_def!(
 	Package,
 	"api::req",
 	"body" => fut Async,
+	"max_size" => fut Async,
+	"validate" => fut Async,
 );
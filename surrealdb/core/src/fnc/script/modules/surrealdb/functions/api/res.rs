@@ -12,4 +12,5 @@ impl_module_def!(
 	"status" => fut Async,
 	"header" => fut Async,
 	"headers" => fut Async,
+	"cache" => fut Async,
 );
@@ -0,0 +1,14 @@
+use super::run;
+use crate::fnc::script::modules::impl_module_def;
+
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"crypto::totp",
+	"code" => run,
+	"secret" => run,
+	"step" => run,
+	"uri" => run,
+	"verify" => run
+);
@@ -0,0 +1,297 @@
+//! Aggregate queries over tables.
+//!
+//! For each table (e.g. `order`), an `_aggregate_order(filter/where, groupBy,
+//! version)` Query field is generated, returning one `_aggregate_order` row
+//! per group:
+//!
+//! ```graphql
+//! type _aggregate_order {
+//!     group: object
+//!     count: Int!
+//!     sum: _aggregate_order_fields
+//!     avg: _aggregate_order_fields
+//!     min: _aggregate_order_fields
+//!     max: _aggregate_order_fields
+//! }
+//! ```
+//!
+//! `_aggregate_order_fields` has a field for each numeric column of the table.
+//! The statistics fields are only generated when the table has numeric columns.
+//!
+//! The aggregates are computed in a single `SELECT ... GROUP BY` statement,
+//! using `count()`, `math::sum`, `math::mean`, `math::min` and `math::max`.
+//! Without `groupBy` the whole table is aggregated as one group, and `group`
+//! is `null`.
+
+use std::sync::Arc;
+
+use async_graphql::dynamic::indexmap::IndexMap;
+use async_graphql::dynamic::{Field, FieldFuture, FieldValue, InputValue, Object, TypeRef};
+use async_graphql::{Name, Value as GqlValue};
+
+use super::error::{GqlError, internal_error};
+use super::schema::sql_value_to_gql_value;
+use super::tables::{
+	execute_select, filter_name_from_table, make_sub_field_resolver, parse_filter_arg,
+	parse_version_arg, select_all_from_table,
+};
+use crate::catalog::{FieldDefinition, TableDefinition};
+use crate::dbs::Session;
+use crate::expr::field::Selector;
+use crate::expr::{
+	Expr, Field as SelectField, Fields, Function, FunctionCall, Group, Groups, Idiom, Kind,
+};
+use crate::kvs::Datastore;
+use crate::val::{Object as SurObject, Value};
+
+/// The statistics computed for each numeric column, with the function used to
+/// compute them.
+const STATS: [(&str, &str); 4] =
+	[("sum", "math::sum"), ("avg", "math::mean"), ("min", "math::min"), ("max", "math::max")];
+
+/// A single aggregated group of records.
+struct AggregateRow {
+	/// The values of the grouped fields, or `None` without `groupBy`
+	group: Option<SurObject>,
+	/// The number of records in the group
+	count: i64,
+	/// The statistics for the numeric columns, in the order of [`STATS`]
+	stats: [SurObject; 4],
+}
+
+/// Derive the aggregate row type name for a table (e.g. `_aggregate_person`).
+fn aggregate_name(tb_name: &str) -> String {
+	format!("_aggregate_{tb_name}")
+}
+
+/// Derive the aggregate statistics type name for a table (e.g.
+/// `_aggregate_person_fields`).
+fn aggregate_fields_name(tb_name: &str) -> String {
+	format!("_aggregate_{tb_name}_fields")
+}
+
+/// Returns true if every value of this kind, other than `NONE`, is a number.
+fn is_numeric(kind: &Kind) -> bool {
+	match kind {
+		Kind::Int | Kind::Float | Kind::Decimal | Kind::Number => true,
+		Kind::Either(ks) => {
+			ks.iter().any(|k| !matches!(k, Kind::None | Kind::Null))
+				&& ks.iter().all(|k| matches!(k, Kind::None | Kind::Null) || is_numeric(k))
+		}
+		_ => false,
+	}
+}
+
+/// The names of the top-level numeric columns of a table.
+fn numeric_fields(fds: &[FieldDefinition]) -> Vec<String> {
+	fds.iter()
+		.filter(|fd| !fd.name.is_id() && fd.name.0.len() == 1)
+		.filter(|fd| fd.field_kind.as_ref().is_some_and(is_numeric))
+		.map(|fd| fd.name.to_raw_string())
+		.collect()
+}
+
+/// The alias a statistic of a column is selected as.
+fn stat_alias(stat: &str, field: &str) -> String {
+	format!("__{stat}_{field}")
+}
+
+/// Parse the optional `groupBy` argument into the names of the grouped fields.
+fn parse_group_by_arg(args: &IndexMap<Name, GqlValue>) -> Vec<String> {
+	match args.get("groupBy") {
+		Some(GqlValue::List(l)) => l
+			.iter()
+			.filter_map(|v| match v {
+				GqlValue::Enum(n) => Some(n.as_str().to_string()),
+				_ => None,
+			})
+			.collect(),
+		Some(GqlValue::Enum(n)) => vec![n.as_str().to_string()],
+		_ => vec![],
+	}
+}
+
+/// Build a selector calling an aggregate function, with an alias.
+fn aggregate_selector(function: &str, arguments: Vec<Expr>, alias: String) -> SelectField {
+	SelectField::Single(Selector {
+		expr: Expr::FunctionCall(Box::new(FunctionCall {
+			receiver: Function::Normal(function.to_string()),
+			arguments,
+		})),
+		alias: Some(Idiom::field(alias)),
+	})
+}
+
+/// Reshape a row of the aggregate query into an [`AggregateRow`].
+fn row_from_value(
+	v: Value,
+	group_by: &[String],
+	numeric: &[String],
+) -> Result<AggregateRow, GqlError> {
+	let Value::Object(mut obj) = v else {
+		error!("Expected object in aggregate result, found: {v:?}");
+		return Err(internal_error("Expected object in aggregate result"));
+	};
+	let count = match obj.remove("count") {
+		Some(Value::Number(n)) => n.to_int(),
+		_ => 0,
+	};
+	let stats = STATS.map(|(stat, _)| {
+		let mut out = SurObject::default();
+		for field in numeric {
+			if let Some(v) = obj.remove(&stat_alias(stat, field)) {
+				out.insert(field.clone(), v);
+			}
+		}
+		out
+	});
+	let group = if group_by.is_empty() {
+		None
+	} else {
+		let mut out = SurObject::default();
+		for field in group_by {
+			out.insert(field.clone(), obj.remove(field).unwrap_or(Value::None));
+		}
+		Some(out)
+	};
+	Ok(AggregateRow {
+		group,
+		count,
+		stats,
+	})
+}
+
+/// Build the `_aggregate_<table>` query field for computing counts and
+/// statistics over the records of a table.
+pub(super) fn make_table_aggregate_field(
+	tb: &TableDefinition,
+	fds: Arc<[FieldDefinition]>,
+	kvs: Arc<Datastore>,
+) -> Field {
+	let tb_name = tb.name.clone();
+	let tb_name_str = tb_name.clone().into_string();
+	let table_orderable_name = format!("_orderable_{tb_name}");
+	let table_filter_name = filter_name_from_table(&tb_name);
+	let numeric: Arc<[String]> = numeric_fields(&fds).into();
+
+	Field::new(
+		aggregate_name(&tb_name_str),
+		TypeRef::named_nn_list_nn(aggregate_name(&tb_name_str)),
+		move |ctx| {
+			let tb_name = tb_name.clone();
+			let fds = fds.clone();
+			let kvs = kvs.clone();
+			let numeric = numeric.clone();
+			FieldFuture::new(async move {
+				let sess = ctx.data::<Arc<Session>>()?;
+				let args = ctx.args.as_index_map();
+
+				let version = parse_version_arg(args)?;
+				let cond = parse_filter_arg(args, &fds, tb_name.as_str())?;
+				let group_by = parse_group_by_arg(args);
+
+				// SELECT count() AS count, math::sum(f) AS __sum_f, ..., <group>
+				// FROM <table> WHERE <filter> GROUP BY <group>
+				let mut fields = vec![aggregate_selector("count", vec![], "count".to_string())];
+				for field in numeric.iter() {
+					for (stat, function) in STATS {
+						fields.push(aggregate_selector(
+							function,
+							vec![Expr::Idiom(Idiom::field(field.clone()))],
+							stat_alias(stat, field),
+						));
+					}
+				}
+				for field in group_by.iter() {
+					fields.push(SelectField::Single(Selector {
+						expr: Expr::Idiom(Idiom::field(field.clone())),
+						alias: None,
+					}));
+				}
+				let mut stmt =
+					select_all_from_table(Expr::Table(tb_name), cond, None, None, None, &version);
+				stmt.fields = Fields::Select(fields);
+				stmt.group =
+					Some(Groups(group_by.iter().map(|f| Group(Idiom::field(f.clone()))).collect()));
+
+				let rows = match execute_select(&kvs, sess, stmt).await? {
+					Value::Array(a) => a,
+					v => {
+						error!("Found top level value, in result which should be array: {v:?}");
+						return Err(
+							internal_error("Unexpected result type from aggregate query").into()
+						);
+					}
+				};
+				let mut rows = rows
+					.0
+					.into_iter()
+					.map(|v| row_from_value(v, &group_by, &numeric))
+					.collect::<Result<Vec<_>, _>>()?;
+				// Aggregating an empty table still produces a single row
+				if rows.is_empty() && group_by.is_empty() {
+					rows.push(AggregateRow {
+						group: None,
+						count: 0,
+						stats: Default::default(),
+					});
+				}
+				Ok(Some(FieldValue::list(rows.into_iter().map(FieldValue::owned_any))))
+			})
+		},
+	)
+	.description(format!(
+		"Generated from table `{}`\nallows computing counts and statistics over a table",
+		tb.name
+	))
+	.argument(InputValue::new("filter", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("where", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("groupBy", TypeRef::named_nn_list(&table_orderable_name)))
+	.argument(InputValue::new("version", TypeRef::named(TypeRef::STRING)))
+}
+
+/// Build the aggregate Object types for a table: the row type, and the
+/// statistics type when the table has numeric columns.
+pub(super) fn make_aggregate_types(tb_name: &str, fds: &[FieldDefinition]) -> Vec<Object> {
+	let numeric = numeric_fields(fds);
+	let mut row = Object::new(aggregate_name(tb_name))
+		.description(format!("Generated from `{tb_name}` the aggregates of a group of records"))
+		.field(Field::new("group", TypeRef::named("object"), |ctx| {
+			FieldFuture::new(async move {
+				let row = ctx.parent_value.try_downcast_ref::<AggregateRow>()?;
+				match &row.group {
+					Some(group) => {
+						let group = sql_value_to_gql_value(Value::Object(group.clone()))?;
+						Ok(Some(FieldValue::value(group)))
+					}
+					None => Ok(None),
+				}
+			})
+		}))
+		.field(Field::new("count", TypeRef::named_nn(TypeRef::INT), |ctx| {
+			FieldFuture::new(async move {
+				let row = ctx.parent_value.try_downcast_ref::<AggregateRow>()?;
+				Ok(Some(FieldValue::value(row.count)))
+			})
+		}));
+	if numeric.is_empty() {
+		return vec![row];
+	}
+
+	let fields_name = aggregate_fields_name(tb_name);
+	for (i, (stat, _)) in STATS.into_iter().enumerate() {
+		row = row.field(Field::new(stat, TypeRef::named(&fields_name), move |ctx| {
+			FieldFuture::new(async move {
+				let row = ctx.parent_value.try_downcast_ref::<AggregateRow>()?;
+				Ok(Some(FieldValue::owned_any(row.stats[i].clone())))
+			})
+		}));
+	}
+	let mut fields = Object::new(&fields_name)
+		.description(format!("Generated from `{tb_name}` a statistic for each numeric field"));
+	for field in numeric {
+		let resolver = make_sub_field_resolver(field.clone(), Some(Kind::Number), None);
+		fields = fields.field(Field::new(field, TypeRef::named("number"), resolver));
+	}
+	vec![row, fields]
+}
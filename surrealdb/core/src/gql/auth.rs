@@ -103,6 +103,9 @@ pub fn add_auth_mutations(
 							access,
 							..
 						} => access,
+						Token::Challenge {
+							challenge,
+						} => challenge,
 					};
 
 					Ok(Some(FieldValue::value(GqlValue::String(access_token))))
@@ -163,6 +166,9 @@ pub fn add_auth_mutations(
 							access,
 							..
 						} => access,
+						Token::Challenge {
+							challenge,
+						} => challenge,
 					};
 
 					Ok(Some(FieldValue::value(GqlValue::String(access_token))))
@@ -0,0 +1,466 @@
+//! Relay-style cursor pagination for table queries.
+//!
+//! For each table (e.g. `person`), a `_connection_person(first, after, last,
+//! before, order, filter/where, version)` Query field is generated, returning a
+//! `personConnection`:
+//!
+//! ```graphql
+//! type personConnection {
+//!     edges: [personEdge!]!
+//!     pageInfo: PageInfo!
+//!     totalCount: Int!
+//! }
+//!
+//! type personEdge {
+//!     node: person!
+//!     cursor: String!
+//! }
+//! ```
+//!
+//! Cursors are opaque, base64-encoded record ids. Pages are fetched with
+//! keyset pagination: the records after (or before) a cursor are selected with
+//! a condition on the ordered fields of the cursor record, rather than by
+//! skipping records, so that deep pages are as cheap as the first one. The
+//! record id is always used as the final ordering, so that the ordering is
+//! total and no record is skipped or repeated between pages.
+
+use std::sync::Arc;
+
+use async_graphql::dynamic::indexmap::IndexMap;
+use async_graphql::dynamic::{Field, FieldFuture, FieldValue, InputValue, Object, TypeRef};
+use async_graphql::{Name, Value as GqlValue};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use surrealdb_types::ToSql;
+
+use super::error::{GqlError, internal_error, resolver_error};
+use super::tables::{
+	CachedRecord, execute_select, filter_name_from_table, parse_filter_arg, parse_order_arg,
+	parse_version_arg, select_all_from_record, select_all_from_table,
+};
+use crate::catalog::{FieldDefinition, TableDefinition};
+use crate::dbs::Session;
+use crate::expr::field::Selector;
+use crate::expr::order::{OrderList, Ordering};
+use crate::expr::{
+	self, BinaryOperator, Cond, Expr, Field as SelectField, Fields, Function, FunctionCall, Groups,
+	Idiom, Limit, Literal,
+};
+use crate::kvs::Datastore;
+use crate::val::{Datetime, RecordId, TableName, Value};
+
+/// The name of the shared page info type
+const PAGE_INFO: &str = "PageInfo";
+
+/// A page of records returned by a connection field.
+struct ConnectionPage {
+	/// The table the records were selected from
+	table: TableName,
+	/// The filter applied to the records, used to count the total records
+	cond: Option<Cond>,
+	/// The version the records were selected at
+	version: Option<Datetime>,
+	/// The records in this page, in order
+	edges: Vec<CachedRecord>,
+	/// Pagination details of this page
+	page_info: PageInfo,
+}
+
+/// The Relay `PageInfo` of a page of records.
+#[derive(Clone)]
+struct PageInfo {
+	has_next_page: bool,
+	has_previous_page: bool,
+	start_cursor: Option<String>,
+	end_cursor: Option<String>,
+}
+
+/// Derive the connection type name for a table (e.g. `personConnection`).
+fn connection_name(tb_name: &str) -> String {
+	format!("{tb_name}Connection")
+}
+
+/// Derive the edge type name for a table (e.g. `personEdge`).
+fn edge_name(tb_name: &str) -> String {
+	format!("{tb_name}Edge")
+}
+
+/// Encode a record id as an opaque cursor.
+fn encode_cursor(rid: &RecordId) -> String {
+	URL_SAFE_NO_PAD.encode(rid.to_sql())
+}
+
+/// Decode an opaque cursor into a record id of the given table.
+fn decode_cursor(cursor: &str, tb_name: &str) -> Result<RecordId, GqlError> {
+	let invalid = || resolver_error(format!("Invalid cursor: {cursor}"));
+	let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+	let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+	let rid: RecordId = crate::syn::record_id(&raw).map_err(|_| invalid())?.into();
+	if rid.table.as_str() != tb_name {
+		return Err(invalid());
+	}
+	Ok(rid)
+}
+
+/// Parse an optional non-negative page size argument.
+fn parse_count_arg(args: &IndexMap<Name, GqlValue>, name: &str) -> Result<Option<i64>, GqlError> {
+	match args.get(name).and_then(|v| v.as_i64()) {
+		Some(n) if n < 0 => Err(resolver_error(format!("`{name}` must not be negative"))),
+		n => Ok(n),
+	}
+}
+
+/// Parse an optional cursor argument.
+fn parse_cursor_arg(
+	args: &IndexMap<Name, GqlValue>,
+	name: &str,
+	tb_name: &str,
+) -> Result<Option<RecordId>, GqlError> {
+	match args.get(name) {
+		Some(GqlValue::String(s)) => decode_cursor(s, tb_name).map(Some),
+		_ => Ok(None),
+	}
+}
+
+/// Combine two optional conditions with `AND`.
+fn and_cond(left: Option<Expr>, right: Expr) -> Expr {
+	match left {
+		Some(left) => Expr::Binary {
+			left: Box::new(left),
+			op: BinaryOperator::And,
+			right: Box::new(right),
+		},
+		None => right,
+	}
+}
+
+/// Build the keyset condition selecting the records which come after a
+/// cursor record in the given ordering, or before it when `after` is false.
+///
+/// For an ordering on `a, b, id` this produces
+/// `a > $a OR (a = $a AND b > $b) OR (a = $a AND b = $b AND id > $id)`,
+/// with the comparisons flipped for descending orders.
+fn keyset_cond(orders: &[expr::Order], values: &[Value], after: bool) -> Expr {
+	let mut cond: Option<Expr> = None;
+	for (i, (order, value)) in orders.iter().zip(values).enumerate() {
+		let op = if order.direction == after {
+			BinaryOperator::MoreThan
+		} else {
+			BinaryOperator::LessThan
+		};
+		let mut branch = Expr::Binary {
+			left: Box::new(Expr::Idiom(order.value.clone())),
+			op,
+			right: Box::new(value.clone().into_literal()),
+		};
+		for (order, value) in orders[..i].iter().zip(values).rev() {
+			branch = Expr::Binary {
+				left: Box::new(Expr::Binary {
+					left: Box::new(Expr::Idiom(order.value.clone())),
+					op: BinaryOperator::Equal,
+					right: Box::new(value.clone().into_literal()),
+				}),
+				op: BinaryOperator::And,
+				right: Box::new(branch),
+			};
+		}
+		cond = Some(match cond {
+			Some(cond) => Expr::Binary {
+				left: Box::new(cond),
+				op: BinaryOperator::Or,
+				right: Box::new(branch),
+			},
+			None => branch,
+		});
+	}
+	cond.unwrap_or(Expr::Literal(Literal::Bool(true)))
+}
+
+/// Fetch the values of the ordered fields of a cursor record.
+async fn cursor_values(
+	ds: &Datastore,
+	sess: &Session,
+	orders: &[expr::Order],
+	rid: &RecordId,
+	version: &Option<Datetime>,
+) -> Result<Vec<Value>, GqlError> {
+	// An ordering on the record id alone needs no lookup
+	if let [order] = orders
+		&& order.value.is_id()
+	{
+		return Ok(vec![Value::RecordId(rid.clone())]);
+	}
+	let stmt = select_all_from_record(rid, version);
+	let Value::Object(obj) = execute_select(ds, sess, stmt).await? else {
+		return Err(resolver_error("The cursor refers to a record which does not exist"));
+	};
+	Ok(orders
+		.iter()
+		.map(|o| {
+			let field = o.value.to_raw_string();
+			obj.get(&field).cloned().unwrap_or(Value::None)
+		})
+		.collect())
+}
+
+/// Build the `_connection_<table>` query field for paginating through the
+/// records of a table with cursors.
+pub(super) fn make_table_connection_field(
+	tb: &TableDefinition,
+	fds: Arc<[FieldDefinition]>,
+	kvs: Arc<Datastore>,
+) -> Field {
+	let tb_name = tb.name.clone();
+	let tb_name_str = tb_name.clone().into_string();
+	let table_order_name = format!("_order_{tb_name}");
+	let table_filter_name = filter_name_from_table(&tb_name);
+
+	Field::new(
+		format!("_connection_{tb_name}"),
+		TypeRef::named_nn(connection_name(&tb_name_str)),
+		move |ctx| {
+			let tb_name = tb_name.clone();
+			let fds = fds.clone();
+			let kvs = kvs.clone();
+			FieldFuture::new(async move {
+				let sess = ctx.data::<Arc<Session>>()?;
+				let args = ctx.args.as_index_map();
+
+				let first = parse_count_arg(args, "first")?;
+				let last = parse_count_arg(args, "last")?;
+				if first.is_some() && last.is_some() {
+					return Err(
+						resolver_error("Cannot paginate with both `first` and `last`").into()
+					);
+				}
+				let after = parse_cursor_arg(args, "after", tb_name.as_str())?;
+				let before = parse_cursor_arg(args, "before", tb_name.as_str())?;
+				let version = parse_version_arg(args)?;
+				let filter = parse_filter_arg(args, &fds, tb_name.as_str())?;
+
+				// Always order by the record id last, so the ordering is total
+				let mut orders = match parse_order_arg(args)? {
+					Some(Ordering::Order(OrderList(orders))) => orders,
+					_ => vec![],
+				};
+				if !orders.iter().any(|o| o.value.is_id()) {
+					orders.push(expr::Order {
+						value: Idiom::field("id".to_string()),
+						direction: true,
+						..Default::default()
+					});
+				}
+
+				// Restrict the records to those between the cursors
+				let mut cond = filter.clone().map(|c| c.0);
+				if let Some(rid) = &after {
+					let values = cursor_values(&kvs, sess, &orders, rid, &version).await?;
+					cond = Some(and_cond(cond, keyset_cond(&orders, &values, true)));
+				}
+				if let Some(rid) = &before {
+					let values = cursor_values(&kvs, sess, &orders, rid, &version).await?;
+					cond = Some(and_cond(cond, keyset_cond(&orders, &values, false)));
+				}
+
+				// Paginating backwards selects the records in reverse order
+				let backward = last.is_some();
+				if backward {
+					for order in orders.iter_mut() {
+						order.direction = !order.direction;
+					}
+				}
+				// Fetch one more record than requested to know if there are more
+				let limit = first
+					.or(last)
+					.map(|n| Limit(Expr::Literal(Literal::Integer(n.saturating_add(1)))));
+				let stmt = select_all_from_table(
+					Expr::Table(tb_name.clone()),
+					cond.map(Cond),
+					Some(Ordering::Order(OrderList(orders))),
+					limit,
+					None,
+					&version,
+				);
+				let mut records = match execute_select(&kvs, sess, stmt).await? {
+					Value::Array(a) => a,
+					v => {
+						error!("Found top level value, in result which should be array: {v:?}");
+						return Err(
+							internal_error("Unexpected result type from connection query").into()
+						);
+					}
+				};
+				let more = match first.or(last) {
+					Some(n) if records.len() as i64 > n => {
+						records.0.truncate(n as usize);
+						true
+					}
+					_ => false,
+				};
+				if backward {
+					records.0.reverse();
+				}
+
+				let cursor_at = |v: Option<&Value>| match v {
+					Some(Value::Object(obj)) => match obj.get("id") {
+						Some(Value::RecordId(rid)) => Some(encode_cursor(rid)),
+						_ => None,
+					},
+					_ => None,
+				};
+				let page_info = PageInfo {
+					has_next_page: if backward {
+						before.is_some()
+					} else {
+						more || (first.is_none() && before.is_some())
+					},
+					has_previous_page: if backward {
+						more
+					} else {
+						after.is_some()
+					},
+					start_cursor: cursor_at(records.first()),
+					end_cursor: cursor_at(records.last()),
+				};
+				let edges = records
+					.0
+					.into_iter()
+					.filter_map(|v| {
+						let Value::Object(data) = v else {
+							return None;
+						};
+						let Some(Value::RecordId(rid)) = data.get("id").cloned() else {
+							return None;
+						};
+						Some(CachedRecord {
+							rid,
+							version: version.clone(),
+							data,
+						})
+					})
+					.collect();
+				Ok(Some(FieldValue::owned_any(ConnectionPage {
+					table: tb_name,
+					cond: filter,
+					version,
+					edges,
+					page_info,
+				})))
+			})
+		},
+	)
+	.description(format!(
+		"Generated from table `{}`\nallows paginating through a table with cursors",
+		tb.name
+	))
+	.argument(InputValue::new("first", TypeRef::named(TypeRef::INT)))
+	.argument(InputValue::new("after", TypeRef::named(TypeRef::STRING)))
+	.argument(InputValue::new("last", TypeRef::named(TypeRef::INT)))
+	.argument(InputValue::new("before", TypeRef::named(TypeRef::STRING)))
+	.argument(InputValue::new("order", TypeRef::named(&table_order_name)))
+	.argument(InputValue::new("filter", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("where", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("version", TypeRef::named(TypeRef::STRING)))
+}
+
+/// Build the connection and edge Object types for a table.
+pub(super) fn make_connection_types(tb_name: &str) -> [Object; 2] {
+	let connection = Object::new(connection_name(tb_name))
+		.description(format!("Generated from `{tb_name}` a page of records"))
+		.field(Field::new("edges", TypeRef::named_nn_list_nn(edge_name(tb_name)), |ctx| {
+			FieldFuture::new(async move {
+				let page = ctx.parent_value.try_downcast_ref::<ConnectionPage>()?;
+				Ok(Some(FieldValue::list(
+					page.edges.iter().map(|record| FieldValue::owned_any(record.clone())),
+				)))
+			})
+		}))
+		.field(Field::new("pageInfo", TypeRef::named_nn(PAGE_INFO), |ctx| {
+			FieldFuture::new(async move {
+				let page = ctx.parent_value.try_downcast_ref::<ConnectionPage>()?;
+				Ok(Some(FieldValue::owned_any(page.page_info.clone())))
+			})
+		}))
+		.field(Field::new("totalCount", TypeRef::named_nn(TypeRef::INT), |ctx| {
+			FieldFuture::new(async move {
+				let page = ctx.parent_value.try_downcast_ref::<ConnectionPage>()?;
+				let ds = ctx.data::<Arc<Datastore>>()?;
+				let sess = ctx.data::<Arc<Session>>()?;
+				// SELECT count() FROM <table> WHERE <filter> GROUP ALL
+				let count = Expr::FunctionCall(Box::new(FunctionCall {
+					receiver: Function::Normal("count".to_string()),
+					arguments: vec![],
+				}));
+				let mut stmt = select_all_from_table(
+					Expr::Table(page.table.clone()),
+					page.cond.clone(),
+					None,
+					None,
+					None,
+					&page.version,
+				);
+				stmt.fields = Fields::Select(vec![SelectField::Single(Selector {
+					expr: count,
+					alias: Some(Idiom::field("count".to_string())),
+				})]);
+				stmt.group = Some(Groups(vec![]));
+				let count = match execute_select(ds, sess, stmt).await? {
+					Value::Array(a) => match a.0.into_iter().next() {
+						Some(Value::Object(obj)) => obj.get("count").cloned(),
+						_ => None,
+					},
+					_ => None,
+				};
+				let count = match count {
+					Some(Value::Number(n)) => n.to_int(),
+					_ => 0,
+				};
+				Ok(Some(FieldValue::value(count)))
+			})
+		}));
+	let edge = Object::new(edge_name(tb_name))
+		.description(format!("Generated from `{tb_name}` a record within a page"))
+		.field(Field::new("node", TypeRef::named_nn(tb_name), |ctx| {
+			FieldFuture::new(async move {
+				let record = ctx.parent_value.try_downcast_ref::<CachedRecord>()?;
+				Ok(Some(FieldValue::owned_any(record.clone())))
+			})
+		}))
+		.field(Field::new("cursor", TypeRef::named_nn(TypeRef::STRING), |ctx| {
+			FieldFuture::new(async move {
+				let record = ctx.parent_value.try_downcast_ref::<CachedRecord>()?;
+				Ok(Some(FieldValue::value(encode_cursor(&record.rid))))
+			})
+		}));
+	[connection, edge]
+}
+
+/// Build the `PageInfo` Object type shared by all connections.
+pub(super) fn make_page_info_type() -> Object {
+	Object::new(PAGE_INFO)
+		.description("Information about a page of records, for cursor pagination")
+		.field(Field::new("hasNextPage", TypeRef::named_nn(TypeRef::BOOLEAN), |ctx| {
+			FieldFuture::new(async move {
+				let info = ctx.parent_value.try_downcast_ref::<PageInfo>()?;
+				Ok(Some(FieldValue::value(info.has_next_page)))
+			})
+		}))
+		.field(Field::new("hasPreviousPage", TypeRef::named_nn(TypeRef::BOOLEAN), |ctx| {
+			FieldFuture::new(async move {
+				let info = ctx.parent_value.try_downcast_ref::<PageInfo>()?;
+				Ok(Some(FieldValue::value(info.has_previous_page)))
+			})
+		}))
+		.field(Field::new("startCursor", TypeRef::named(TypeRef::STRING), |ctx| {
+			FieldFuture::new(async move {
+				let info = ctx.parent_value.try_downcast_ref::<PageInfo>()?;
+				Ok(info.start_cursor.clone().map(FieldValue::value))
+			})
+		}))
+		.field(Field::new("endCursor", TypeRef::named(TypeRef::STRING), |ctx| {
+			FieldFuture::new(async move {
+				let info = ctx.parent_value.try_downcast_ref::<PageInfo>()?;
+				Ok(info.end_cursor.clone().map(FieldValue::value))
+			})
+		}))
+}
@@ -0,0 +1,417 @@
+//! Per-request batching and caching of record fetches.
+//!
+//! Resolving a record link or a relation field for every object in a list
+//! would otherwise issue one query per parent object (the N+1 problem). The
+//! [`RecordLoader`] instead collects the records requested by all resolvers
+//! which are polled together, and fetches them in a single statement once
+//! those resolvers are all waiting.
+//!
+//! `async_graphql` resolves the items of a list, and the fields of an object,
+//! concurrently within the same task. The first resolver to request a record
+//! starts a batch and yields once, which lets every sibling resolver add its
+//! own key to the batch before it is fetched. A list of 500 people with their
+//! `company` link therefore resolves the links in a single round-trip.
+//!
+//! Batches are fetched through `SELECT` statements, so that table and field
+//! permissions and computed fields apply exactly as they do for unbatched
+//! queries. Fetched records are cached for the rest of the request.
+//!
+//! The loader is added to the request data by the HTTP layer. When it is not
+//! present, resolvers fall back to fetching each record individually.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+use async_graphql::dynamic::ResolverContext;
+use futures::FutureExt;
+use futures::future::{BoxFuture, Shared};
+use parking_lot::Mutex;
+
+use super::error::{GqlError, internal_error};
+use super::relations::RelationDirection;
+use super::tables::{execute_select, select_all_from_table};
+use crate::dbs::Session;
+use crate::expr::order::Ordering;
+use crate::expr::{BinaryOperator, Cond, Expr, Idiom, Literal};
+use crate::kvs::{Datastore, Metric, Metrics};
+use crate::val::{Datetime, Object as SurObject, RecordId, TableName, Value};
+
+/// The result of a batch, shared between all the resolvers waiting on it.
+type BatchResult<K, V> = Result<Arc<HashMap<K, V>>, Arc<str>>;
+
+/// A batch of keys which has been started, but not yet fetched.
+struct PendingBatch<K, V> {
+	/// The keys in the order they were requested
+	keys: Vec<K>,
+	/// The keys already in the batch, to skip duplicate requests
+	seen: HashSet<K>,
+	result: Shared<BoxFuture<'static, BatchResult<K, V>>>,
+}
+
+/// The state of a [`Batcher`], guarded by a mutex.
+struct BatchState<K, V> {
+	/// The values fetched so far in this request
+	cache: HashMap<K, V>,
+	/// The batch currently collecting keys, if any
+	pending: Option<PendingBatch<K, V>>,
+}
+
+/// Collects keys requested by concurrently polled resolvers into batches.
+struct Batcher<K, V> {
+	state: Mutex<BatchState<K, V>>,
+}
+
+impl<K, V> Default for Batcher<K, V> {
+	fn default() -> Self {
+		Self {
+			state: Mutex::new(BatchState {
+				cache: HashMap::new(),
+				pending: None,
+			}),
+		}
+	}
+}
+
+impl<K, V> Batcher<K, V>
+where
+	K: Clone + Eq + Hash + Send + Sync + 'static,
+	V: Clone + Send + Sync + 'static,
+{
+	/// Load the value of a key, adding it to the pending batch.
+	///
+	/// If no batch is pending, a new one is started, which is fetched with
+	/// `fetch` once the resolvers polled alongside this one have added their
+	/// keys. The fetched map must contain a value for every key.
+	async fn load<F>(self: &Arc<Self>, key: K, fetch: F) -> Result<V, async_graphql::Error>
+	where
+		F: FnOnce(Vec<K>) -> BoxFuture<'static, Result<HashMap<K, V>, GqlError>> + Send + 'static,
+	{
+		let result = {
+			let mut state = self.state.lock();
+			if let Some(v) = state.cache.get(&key) {
+				LoaderMetrics::cache_hit();
+				return Ok(v.clone());
+			}
+			match state.pending.as_mut() {
+				Some(pending) => {
+					if pending.seen.insert(key.clone()) {
+						pending.keys.push(key.clone());
+					}
+					pending.result.clone()
+				}
+				None => {
+					let result = self.clone().run(fetch).boxed().shared();
+					state.pending = Some(PendingBatch {
+						keys: vec![key.clone()],
+						seen: HashSet::from([key.clone()]),
+						result: result.clone(),
+					});
+					result
+				}
+			}
+		};
+		match result.await {
+			Ok(values) => values
+				.get(&key)
+				.cloned()
+				.ok_or_else(|| async_graphql::Error::new("Missing value in batched fetch")),
+			Err(e) => Err(async_graphql::Error::new(e.to_string())),
+		}
+	}
+
+	/// Fetch the pending batch once the sibling resolvers have added their keys.
+	async fn run<F>(self: Arc<Self>, fetch: F) -> BatchResult<K, V>
+	where
+		F: FnOnce(Vec<K>) -> BoxFuture<'static, Result<HashMap<K, V>, GqlError>>,
+	{
+		// Let the resolvers polled alongside this one add their keys
+		tokio::task::yield_now().await;
+		// The batch is only taken here, by the future which started it
+		let Some(pending) = self.state.lock().pending.take() else {
+			return Err(Arc::from(internal_error("Missing pending batch").to_string()));
+		};
+		let keys = pending.keys;
+		LoaderMetrics::batch(keys.len());
+		let values = fetch(keys).await.map_err(|e| Arc::<str>::from(e.to_string()))?;
+		self.state.lock().cache.extend(values.iter().map(|(k, v)| (k.clone(), v.clone())));
+		Ok(Arc::new(values))
+	}
+
+	/// Forget the values fetched so far.
+	fn clear(&self) {
+		self.state.lock().cache.clear();
+	}
+}
+
+/// A record requested through a record link.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct RecordKey {
+	rid: RecordId,
+	version: Option<Datetime>,
+}
+
+/// The records of a relation table linked to a parent record.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RelationKey {
+	/// The relation table
+	pub table: TableName,
+	/// The side of the relation the parent record is on
+	pub direction: RelationDirection,
+	/// The parent record
+	pub rid: RecordId,
+	/// The version the relation is selected at
+	pub version: Option<Datetime>,
+	/// The user-supplied filter on the relation records
+	pub cond: Option<Cond>,
+	/// The user-supplied ordering of the relation records
+	pub order: Option<Ordering>,
+}
+
+/// The parts of a [`RelationKey`] other than the parent record. Keys with the
+/// same group are fetched with a single statement.
+type RelationGroup =
+	(TableName, RelationDirection, Option<Datetime>, Option<Cond>, Option<Ordering>);
+
+impl RelationKey {
+	fn group(&self) -> RelationGroup {
+		(
+			self.table.clone(),
+			self.direction,
+			self.version.clone(),
+			self.cond.clone(),
+			self.order.clone(),
+		)
+	}
+}
+
+/// Batches and caches the record fetches of a single GraphQL request.
+pub struct RecordLoader {
+	ds: Arc<Datastore>,
+	sess: Arc<Session>,
+	records: Arc<Batcher<RecordKey, Option<SurObject>>>,
+	relations: Arc<Batcher<RelationKey, Vec<SurObject>>>,
+}
+
+impl RecordLoader {
+	/// Create a loader for a request made with the given session.
+	pub fn new(ds: Arc<Datastore>, sess: Arc<Session>) -> Self {
+		Self {
+			ds,
+			sess,
+			records: Default::default(),
+			relations: Default::default(),
+		}
+	}
+
+	/// Load a single record, at an optional version.
+	///
+	/// Returns `None` if the record does not exist, or cannot be selected.
+	pub(crate) async fn load_record(
+		&self,
+		rid: RecordId,
+		version: Option<Datetime>,
+	) -> Result<Option<SurObject>, async_graphql::Error> {
+		let (ds, sess) = (self.ds.clone(), self.sess.clone());
+		let key = RecordKey {
+			rid,
+			version,
+		};
+		self.records.load(key, move |keys| fetch_records(ds, sess, keys).boxed()).await
+	}
+
+	/// Load the relation records linked to a parent record.
+	pub(crate) async fn load_relation(
+		&self,
+		key: RelationKey,
+	) -> Result<Vec<SurObject>, async_graphql::Error> {
+		let (ds, sess) = (self.ds.clone(), self.sess.clone());
+		self.relations.load(key, move |keys| fetch_relations(ds, sess, keys).boxed()).await
+	}
+
+	/// Clear the records cached by the loader of a request, if it has one.
+	///
+	/// Called by mutations, as the records they change would otherwise be
+	/// served stale to the fields resolved after them.
+	pub(crate) fn clear_cache(ctx: &ResolverContext<'_>) {
+		if let Ok(loader) = ctx.data::<Arc<RecordLoader>>() {
+			loader.records.clear();
+			loader.relations.clear();
+		}
+	}
+}
+
+/// Fetch a batch of records, with one `SELECT * FROM <ids>` per version.
+async fn fetch_records(
+	ds: Arc<Datastore>,
+	sess: Arc<Session>,
+	keys: Vec<RecordKey>,
+) -> Result<HashMap<RecordKey, Option<SurObject>>, GqlError> {
+	let mut groups: HashMap<Option<Datetime>, Vec<RecordId>> = HashMap::new();
+	for key in keys.iter() {
+		groups.entry(key.version.clone()).or_default().push(key.rid.clone());
+	}
+	let mut out: HashMap<RecordKey, Option<SurObject>> =
+		keys.into_iter().map(|k| (k, None)).collect();
+	for (version, rids) in groups {
+		let what = rids.into_iter().map(|rid| Value::RecordId(rid).into_literal()).collect();
+		let what = Expr::Literal(Literal::Array(what));
+		let stmt = select_all_from_table(what, None, None, None, None, &version);
+		let Value::Array(rows) = execute_select(&ds, &sess, stmt).await? else {
+			continue;
+		};
+		for row in rows.0 {
+			let Value::Object(obj) = row else {
+				continue;
+			};
+			let Some(Value::RecordId(rid)) = obj.get("id").cloned() else {
+				continue;
+			};
+			out.insert(
+				RecordKey {
+					rid,
+					version: version.clone(),
+				},
+				Some(obj),
+			);
+		}
+	}
+	Ok(out)
+}
+
+/// Fetch a batch of relations, with one statement for each set of keys which
+/// only differ by their parent record:
+///
+/// `SELECT * FROM <relation> WHERE <in|out> INSIDE [<parents>] AND <filter>
+/// ORDER BY <order>`
+async fn fetch_relations(
+	ds: Arc<Datastore>,
+	sess: Arc<Session>,
+	keys: Vec<RelationKey>,
+) -> Result<HashMap<RelationKey, Vec<SurObject>>, GqlError> {
+	let mut groups: HashMap<RelationGroup, Vec<RecordId>> = HashMap::new();
+	for key in keys.iter() {
+		groups.entry(key.group()).or_default().push(key.rid.clone());
+	}
+	let mut out: HashMap<RelationKey, Vec<SurObject>> =
+		keys.into_iter().map(|k| (k, Vec::new())).collect();
+	for ((table, direction, version, user_cond, order), rids) in groups {
+		let side = match direction {
+			RelationDirection::Outgoing => "in",
+			RelationDirection::Incoming => "out",
+		};
+		let parents = rids.into_iter().map(|rid| Value::RecordId(rid).into_literal()).collect();
+		let mut cond = Expr::Binary {
+			left: Box::new(Expr::Idiom(Idiom::field(side.to_string()))),
+			op: BinaryOperator::Inside,
+			right: Box::new(Expr::Literal(Literal::Array(parents))),
+		};
+		if let Some(user_cond) = user_cond.clone() {
+			cond = Expr::Binary {
+				left: Box::new(cond),
+				op: BinaryOperator::And,
+				right: Box::new(user_cond.0),
+			};
+		}
+		let stmt = select_all_from_table(
+			Expr::Table(table.clone()),
+			Some(Cond(cond)),
+			order.clone(),
+			None,
+			None,
+			&version,
+		);
+		let Value::Array(rows) = execute_select(&ds, &sess, stmt).await? else {
+			continue;
+		};
+		// Rows are partitioned by parent, keeping the order of the statement
+		for row in rows.0 {
+			let Value::Object(obj) = row else {
+				continue;
+			};
+			let Some(Value::RecordId(rid)) = obj.get(side).cloned() else {
+				continue;
+			};
+			let key = RelationKey {
+				table: table.clone(),
+				direction,
+				rid,
+				version: version.clone(),
+				cond: user_cond.clone(),
+				order: order.clone(),
+			};
+			if let Some(records) = out.get_mut(&key) {
+				records.push(obj);
+			}
+		}
+	}
+	Ok(out)
+}
+
+// ---------------------------------------------------------------------------
+// Metrics
+// ---------------------------------------------------------------------------
+
+static BATCHES: AtomicU64 = AtomicU64::new(0);
+static BATCHED_KEYS: AtomicU64 = AtomicU64::new(0);
+static LARGEST_BATCH: AtomicU64 = AtomicU64::new(0);
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+
+/// Counters describing the batches fetched by [`RecordLoader`]s.
+pub struct LoaderMetrics;
+
+impl LoaderMetrics {
+	const BATCHES: &str = "graphql.loader.batches";
+	const BATCHED_KEYS: &str = "graphql.loader.batched_keys";
+	const LARGEST_BATCH: &str = "graphql.loader.largest_batch";
+	const CACHE_HITS: &str = "graphql.loader.cache_hits";
+
+	/// Record a fetched batch of the given size
+	fn batch(size: usize) {
+		BATCHES.fetch_add(1, AtomicOrdering::Relaxed);
+		BATCHED_KEYS.fetch_add(size as u64, AtomicOrdering::Relaxed);
+		LARGEST_BATCH.fetch_max(size as u64, AtomicOrdering::Relaxed);
+	}
+
+	/// Record a key which was served from the request cache
+	fn cache_hit() {
+		CACHE_HITS.fetch_add(1, AtomicOrdering::Relaxed);
+	}
+
+	/// The metrics describing the batches fetched by GraphQL requests
+	pub fn metrics() -> Metrics {
+		Metrics {
+			name: "surrealdb.graphql",
+			u64_metrics: vec![
+				Metric {
+					name: Self::BATCHES,
+					description: "Returns the number of record batches fetched by GraphQL requests.",
+				},
+				Metric {
+					name: Self::BATCHED_KEYS,
+					description: "Returns the number of records requested through batches.",
+				},
+				Metric {
+					name: Self::LARGEST_BATCH,
+					description: "Returns the number of records in the largest batch fetched.",
+				},
+				Metric {
+					name: Self::CACHE_HITS,
+					description: "Returns the number of records served from a request cache.",
+				},
+			],
+		}
+	}
+
+	/// Collects a specific u64 metric by name
+	pub fn collect_u64_metric(metric: &str) -> Option<u64> {
+		match metric {
+			Self::BATCHES => Some(BATCHES.load(AtomicOrdering::Relaxed)),
+			Self::BATCHED_KEYS => Some(BATCHED_KEYS.load(AtomicOrdering::Relaxed)),
+			Self::LARGEST_BATCH => Some(LARGEST_BATCH.load(AtomicOrdering::Relaxed)),
+			Self::CACHE_HITS => Some(CACHE_HITS.load(AtomicOrdering::Relaxed)),
+			_ => None,
+		}
+	}
+}
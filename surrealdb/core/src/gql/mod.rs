@@ -14,6 +14,10 @@
 //! - **Table queries** ([`tables`]) -- generates Query root fields and Object types for each
 //!   exposed table, including field resolvers, filter/order types, nested objects, and relation
 //!   fields.
+//! - **Connections** ([`connections`]) -- generates Relay-style cursor-paginated connection fields
+//!   for each exposed table.
+//! - **Aggregates** ([`aggregates`]) -- generates count and statistics fields for each exposed
+//!   table, optionally grouped by columns.
 //! - **Mutations** ([`mutations`]) -- generates Mutation root fields (create, update, upsert,
 //!   delete -- single and bulk) with corresponding input types.
 //! - **Functions** ([`functions`]) -- exposes user-defined database functions as Query fields.
@@ -21,6 +25,8 @@
 //!   definitions.
 //! - **Relations** ([`relations`]) -- discovers relation tables and provides data structures for
 //!   relation field generation.
+//! - **Batching** ([`loader`]) -- batches and caches the record links and relations fetched while
+//!   resolving a single request, avoiding one query per parent object.
 //! - **Caching** ([`cache`]) -- caches generated schemas keyed by namespace, database, and GraphQL
 //!   configuration.
 //! - **Error handling** ([`error`]) -- domain error type ([`GqlError`]) with helper constructors.
@@ -36,11 +42,14 @@
 //! because `async_graphql` and the HTTP serving stack are not compatible with WASM.
 #![cfg(not(target_family = "wasm"))]
 
+mod aggregates;
 mod auth;
 pub mod cache;
+mod connections;
 pub mod error;
 mod ext;
 mod functions;
+mod loader;
 mod mutations;
 mod relations;
 pub mod schema;
@@ -50,4 +59,5 @@ mod utils;
 
 pub use cache::*;
 pub use error::GqlError;
+pub use loader::{LoaderMetrics, RecordLoader};
 pub use subscriptions::NotificationRouter;
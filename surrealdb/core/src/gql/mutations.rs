@@ -19,6 +19,7 @@ use async_graphql::{Name, Value as GqlValue};
 use surrealdb_types::ToSql;
 
 use super::error::{GqlError, resolver_error};
+use super::loader::RecordLoader;
 use super::schema::{
 	SchemaContext, gql_to_sql_kind_with_scope, kind_to_type_with_enum_prefix, unwrap_type,
 };
@@ -293,6 +294,7 @@ fn add_create_field(mutation: Object, tc: &MutationTableContext, input_name: &st
 				let tb_name = tb_name.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
+					RecordLoader::clear_cache(&ctx);
 					let args = ctx.args.as_index_map();
 					let data_obj = get_data_object(args)?;
 					let id_opt = data_obj.get("id").and_then(GqlValueUtils::as_string);
@@ -324,6 +326,7 @@ fn add_update_field(mutation: Object, tc: &MutationTableContext, input_name: &st
 				let tb_name = tb_name.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
+					RecordLoader::clear_cache(&ctx);
 					let args = ctx.args.as_index_map();
 					let id_str = get_required_id(args)?;
 					let data_obj = get_data_object(args)?;
@@ -377,6 +380,7 @@ fn add_upsert_field(mutation: Object, tc: &MutationTableContext, input_name: &st
 				let tb_name = tb_name.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
+					RecordLoader::clear_cache(&ctx);
 					let args = ctx.args.as_index_map();
 					let id_str = get_required_id(args)?;
 					let data_obj = get_data_object(args)?;
@@ -428,6 +432,7 @@ fn add_delete_field(mutation: Object, tc: &MutationTableContext) -> Object {
 				let tb_name = tb_name.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
+					RecordLoader::clear_cache(&ctx);
 					let args = ctx.args.as_index_map();
 					let id_str = get_required_id(args)?;
 
@@ -475,6 +480,7 @@ fn add_create_many_field(mutation: Object, tc: &MutationTableContext, input_name
 				let tb_name = tb_name.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
+					RecordLoader::clear_cache(&ctx);
 					let args = ctx.args.as_index_map();
 					let data_list =
 						args.get("data").and_then(GqlValueUtils::as_list).ok_or_else(|| {
@@ -531,6 +537,7 @@ fn add_update_many_field(mutation: Object, tc: &MutationTableContext, input_name
 				let tb_name = tb_name.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
+					RecordLoader::clear_cache(&ctx);
 					let args = ctx.args.as_index_map();
 
 					let data_obj = get_data_object(args)?;
@@ -583,6 +590,7 @@ fn add_upsert_many_field(mutation: Object, tc: &MutationTableContext, input_name
 				let tb_name = tb_name.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
+					RecordLoader::clear_cache(&ctx);
 					let args = ctx.args.as_index_map();
 
 					let data_obj = get_data_object(args)?;
@@ -635,6 +643,7 @@ fn add_delete_many_field(mutation: Object, tc: &MutationTableContext) -> Object
 				let tb_name = tb_name.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
+					RecordLoader::clear_cache(&ctx);
 					let args = ctx.args.as_index_map();
 
 					let cond = parse_where_arg(args, &fds, tb_name.as_str())?;
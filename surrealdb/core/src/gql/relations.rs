@@ -24,7 +24,7 @@ pub(crate) struct RelationInfo {
 }
 
 /// The direction of a relation from the perspective of the table the field is added to.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub(crate) enum RelationDirection {
 	/// This table is the source (appears in the `FROM` / `IN` list).
 	/// Resolved by filtering: `WHERE in = $current_record`.
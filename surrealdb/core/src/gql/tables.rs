@@ -9,6 +9,10 @@
 //!
 //! - `person(limit, start, order, filter/where, version)` -- list query returning `[person!]!`
 //! - `_get_person(id, version)` -- single-record fetch returning `person`
+//! - `_connection_person(first, after, last, before, order, filter/where, version)` -- cursor
+//!   pagination returning `personConnection!` (see [`super::connections`])
+//! - `_aggregate_person(filter/where, groupBy, version)` -- counts and statistics returning
+//!   `[_aggregate_person!]!` (see [`super::aggregates`])
 //!
 //! A generic `_get(id, version)` field is also added to fetch any record by
 //! its full ID string (e.g. `"person:alice"`).
@@ -22,6 +26,8 @@
 //! - An **orderable enum** (`_orderable_<table>`) listing sortable fields.
 //! - An **order input** (`_order_<table>`) for specifying sort criteria.
 //! - A **filter input** (`_filter_<table>`) with per-field comparison operators.
+//! - **Connection and edge types** (`<table>Connection`, `<table>Edge`) for cursor pagination.
+//! - **Aggregate types** (`_aggregate_<table>`, `_aggregate_<table>_fields`) for aggregate queries.
 //!
 //! ## Performance: CachedRecord
 //!
@@ -50,7 +56,10 @@ use async_graphql::dynamic::{
 use async_graphql::{Name, Value as GqlValue};
 use surrealdb_types::ToSql;
 
+use super::aggregates::{make_aggregate_types, make_table_aggregate_field};
+use super::connections::{make_connection_types, make_page_info_type, make_table_connection_field};
 use super::error::{GqlError, resolver_error};
+use super::loader::{RecordLoader, RelationKey};
 use super::relations::{RelationDirection, RelationInfo};
 use super::schema::{
 	SchemaContext, gql_to_sql_kind, gql_to_sql_kind_with_scope, sql_value_to_gql_value,
@@ -135,7 +144,9 @@ fn version_to_expr(version: &Option<Datetime>) -> Expr {
 
 /// Parse the optional `version` argument from GraphQL query arguments.
 /// Expects an ISO 8601 / RFC 3339 datetime string (e.g. `"2024-06-01T00:00:00Z"`).
-fn parse_version_arg(args: &IndexMap<Name, GqlValue>) -> Result<Option<Datetime>, GqlError> {
+pub(super) fn parse_version_arg(
+	args: &IndexMap<Name, GqlValue>,
+) -> Result<Option<Datetime>, GqlError> {
 	match args.get("version") {
 		Some(GqlValue::String(s)) => {
 			let dt = crate::syn::datetime(s)
@@ -169,7 +180,9 @@ fn parse_limit_arg(args: &IndexMap<Name, GqlValue>) -> Option<Limit> {
 /// ```
 /// Each node has exactly one of `asc` or `desc` (an enum value naming the
 /// field) and an optional `then` link to the next ordering criterion.
-fn parse_order_arg(args: &IndexMap<Name, GqlValue>) -> Result<Option<Ordering>, GqlError> {
+pub(super) fn parse_order_arg(
+	args: &IndexMap<Name, GqlValue>,
+) -> Result<Option<Ordering>, GqlError> {
 	let order = args.get("order");
 	match order {
 		Some(GqlValue::Object(o)) => {
@@ -234,7 +247,10 @@ pub(crate) fn parse_filter_arg(
 ///
 /// Used by `_get_`, `_get`, and record-link dereferencing to fetch a single
 /// record's full data for caching.
-fn select_all_from_record(rid: &RecordId, version: &Option<Datetime>) -> SelectStatement {
+pub(super) fn select_all_from_record(
+	rid: &RecordId,
+	version: &Option<Datetime>,
+) -> SelectStatement {
 	SelectStatement {
 		what: vec![Value::RecordId(rid.clone()).into_literal()],
 		fields: Fields::all(),
@@ -292,7 +308,7 @@ fn select_field_from_record(
 /// ordering, pagination, and versioning.
 ///
 /// Used by the table list query and relation field resolvers.
-fn select_all_from_table(
+pub(super) fn select_all_from_table(
 	what: Expr,
 	cond: Option<Cond>,
 	order: Option<Ordering>,
@@ -321,7 +337,7 @@ fn select_all_from_table(
 }
 
 /// Execute a `SelectStatement` via `LogicalPlan` and return the result.
-async fn execute_select(
+pub(super) async fn execute_select(
 	ds: &Datastore,
 	sess: &Session,
 	stmt: SelectStatement,
@@ -504,7 +520,7 @@ fn make_nested_object_type(
 ///
 /// The resolver downcasts the parent value to `SurObject` and extracts the
 /// named field, converting it to the appropriate GraphQL value.
-fn make_sub_field_resolver(
+pub(super) fn make_sub_field_resolver(
 	field_name: String,
 	kind: Option<Kind>,
 	enum_scope: Option<String>,
@@ -648,7 +664,7 @@ pub(crate) fn filter_name_from_table(tb_name: impl Display) -> String {
 /// Each `Value::Object` in the array is wrapped in a `CachedRecord` so that
 /// field resolvers can extract values directly from memory. Used by table list
 /// queries, relation field resolvers, and bulk mutation results.
-fn objects_to_cached_records(
+pub(super) fn objects_to_cached_records(
 	arr: SurArray,
 	version: Option<Datetime>,
 ) -> Result<Option<FieldValue<'static>>, async_graphql::Error> {
@@ -1114,6 +1130,8 @@ pub async fn process_tbs(
 		// Add query root fields for this table
 		query = query.field(make_table_list_field(tb, fds.clone(), ctx.datastore.clone()));
 		query = query.field(make_table_get_field(tb, ctx.datastore.clone()));
+		query = query.field(make_table_connection_field(tb, fds.clone(), ctx.datastore.clone()));
+		query = query.field(make_table_aggregate_field(tb, fds.clone(), ctx.datastore.clone()));
 
 		// Build and register the table's type system
 		let tt = build_table_type(
@@ -1128,8 +1146,14 @@ pub async fn process_tbs(
 		types.push(tt.order.into());
 		types.push(Type::Enum(tt.orderable));
 		types.push(Type::InputObject(tt.filter));
+		let tb_name_str = tb.name.clone().into_string();
+		types.extend(make_connection_types(&tb_name_str).map(Type::Object));
+		types.extend(make_aggregate_types(&tb_name_str, &fds).into_iter().map(Type::Object));
 	}
 
+	// Add the page info type shared by all connection fields
+	types.push(Type::Object(make_page_info_type()));
+
 	// Add generic _get query field for fetching any record by full ID
 	query = query.field(make_generic_get_field(ctx.datastore.clone()));
 
@@ -1228,12 +1252,21 @@ async fn resolve_field_value(
 		Value::RecordId(target_rid) if fd_name != "id" => {
 			// Record-link dereferencing: fetch the full target record and
 			// wrap it as CachedRecord so the target's field resolvers can
-			// also benefit from caching.
-			let ds = ctx.data::<Arc<Datastore>>()?;
-			let sess = ctx.data::<Arc<Session>>()?;
-
-			let stmt = select_all_from_record(&target_rid, version);
-			let target_val = execute_select(ds, sess, stmt).await?;
+			// also benefit from caching. The links of sibling objects are
+			// fetched in a single batch when the request has a loader.
+			let target_val = match ctx.data_opt::<Arc<RecordLoader>>() {
+				Some(loader) => loader
+					.load_record(target_rid.clone(), version.clone())
+					.await?
+					.map(Value::Object)
+					.unwrap_or(Value::None),
+				None => {
+					let ds = ctx.data::<Arc<Datastore>>()?;
+					let sess = ctx.data::<Arc<Session>>()?;
+					let stmt = select_all_from_record(&target_rid, version);
+					execute_select(ds, sess, stmt).await?
+				}
+			};
 
 			match target_val {
 				Value::Object(obj) => {
@@ -1277,8 +1310,8 @@ async fn resolve_field_value(
 /// Fast-path field resolution from a [`CachedRecord`].
 ///
 /// Extracts the field value directly from the cached record data. For
-/// record-link fields, fetches the linked record's full data in a single
-/// `SELECT *` query (instead of N per-field queries).
+/// record-link fields, fetches the linked record's full data through the
+/// request's [`RecordLoader`], batched with the links of sibling records.
 async fn resolve_field_from_cached_record(
 	ctx: &ResolverContext<'_>,
 	cached: &CachedRecord,
@@ -1334,6 +1367,9 @@ fn make_relation_field(
 /// 2. Builds `SELECT * FROM <relation_table> WHERE <in|out> = $current_record`
 /// 3. Optionally combines with user-supplied filter, ordering, and pagination
 /// 4. Returns the matching relation records as a list
+///
+/// Without pagination, the relations of sibling records are fetched in a
+/// single batch through the request's [`RecordLoader`], if it has one.
 fn make_relation_field_resolver(
 	relation_table_name: TableName,
 	direction: RelationDirection,
@@ -1363,6 +1399,31 @@ fn make_relation_field_resolver(
 			let limit = parse_limit_arg(args);
 			let order = parse_order_arg(args)?;
 
+			// Batch the relations of sibling records, as long as the records
+			// are not paginated separately for each parent
+			if let Some(loader) = ctx.data_opt::<Arc<RecordLoader>>()
+				&& start.is_none()
+				&& limit.is_none()
+			{
+				let cond = match fds {
+					Some(ref fds) => parse_filter_arg(args, fds, relation_table.as_str())?,
+					None => None,
+				};
+				let key = RelationKey {
+					table: relation_table,
+					direction,
+					rid,
+					version: version.clone(),
+					cond,
+					order,
+				};
+				let records = loader.load_relation(key).await?;
+				return objects_to_cached_records(
+					SurArray(records.into_iter().map(Value::Object).collect()),
+					version,
+				);
+			}
+
 			// Build the base condition: WHERE in = $record or WHERE out = $record
 			let filter_field = match direction {
 				RelationDirection::Outgoing => "in",
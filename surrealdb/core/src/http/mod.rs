@@ -1,15 +1,25 @@
+use std::sync::{Arc, LazyLock};
+
 use anyhow::Result;
 use http::Method;
+use quick_cache::sync::Cache;
 #[cfg(not(target_family = "wasm"))]
 use reqwest::redirect::{Action, Attempt};
 use reqwest::{Client, RequestBuilder};
 use url::Url;
 
+use crate::cnf::HTTP_CLIENT_CACHE_SIZE;
 use crate::dbs::capabilities::{NetTarget, Targets};
 
 #[cfg(not(target_family = "wasm"))]
 mod resolve;
 
+/// The http clients for each distinct set of allowed and denied network
+/// targets, so that contexts with the same effective capabilities share a
+/// client and its connection pool.
+static HTTP_CLIENTS: LazyLock<Cache<(Targets<NetTarget>, Targets<NetTarget>), Arc<HttpClient>>> =
+	LazyLock::new(|| Cache::new(HTTP_CLIENT_CACHE_SIZE.max(1)));
+
 pub struct HttpClient {
 	client: Client,
 }
@@ -21,6 +31,14 @@ struct NetFilter {
 }
 
 impl HttpClient {
+	/// Returns the shared http client for the allowed and denied network
+	/// targets, creating it if it is not cached.
+	pub fn cached(allow: &Targets<NetTarget>, deny: &Targets<NetTarget>) -> Result<Arc<Self>> {
+		let key = (allow.clone(), deny.clone());
+		HTTP_CLIENTS
+			.get_or_insert_with(&key, || Self::new(allow.clone(), deny.clone()).map(Arc::new))
+	}
+
 	#[cfg(not(target_family = "wasm"))]
 	pub fn new(allow: Targets<NetTarget>, deny: Targets<NetTarget>) -> Result<Self> {
 		Self::new_with_redirect_policy(allow, deny, |policy| policy.follow())
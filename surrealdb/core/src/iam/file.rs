@@ -11,6 +11,17 @@ pub(crate) fn is_path_allowed(path: &Path) -> Result<PathBuf> {
 	check_is_path_allowed(path, &FILE_ALLOWLIST)
 }
 
+/// Checks if the requested file path is within the allowlist, denying
+/// every path when the allowlist is empty.
+///
+/// This is used for files which the database writes to, rather than reads.
+pub(crate) fn check_is_path_allowlisted(path: &Path, allowed_paths: &[PathBuf]) -> Result<PathBuf> {
+	if allowed_paths.is_empty() {
+		return Err(Error::FileAccessDenied(path.to_string_lossy().to_string()).into());
+	}
+	check_is_path_allowed(path, allowed_paths)
+}
+
 /// Checks if the requested file path is within any of the allowed directories.
 fn check_is_path_allowed(path: &Path, allowed_paths: &[PathBuf]) -> Result<PathBuf> {
 	// Convert the requested path to its canonical form.
@@ -111,6 +122,16 @@ mod tests {
 		assert!(result.is_ok(), "File access should be allowed when no restrictions are set");
 	}
 
+	#[test]
+	fn test_empty_allow_list_denies_allowlisted_access() {
+		let dir = tempdir().expect("failed to create temp dir");
+		let result = check_is_path_allowlisted(dir.path(), &[]);
+		assert!(result.is_err(), "Writes should be denied when no allowlist is set");
+		let allowlist = extract_allowed_paths(&dir.path().to_string_lossy(), true, "file");
+		let result = check_is_path_allowlisted(dir.path(), &allowlist);
+		assert!(result.is_ok(), "Writes should be allowed within the allowlist");
+	}
+
 	#[test]
 	fn test_allow_list_access() {
 		// Use the appropriate delimiter for the platform.
@@ -18,6 +18,7 @@ pub mod reset;
 pub mod signin;
 pub mod signup;
 pub mod token;
+pub(crate) mod totp;
 pub mod verify;
 
 pub use self::auth::*;
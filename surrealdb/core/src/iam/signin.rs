@@ -29,9 +29,9 @@ use crate::expr::statements::access;
 use crate::iam::issue::{config, expiration};
 use crate::iam::token::{Claims, HEADER, Token};
 use crate::iam::{self, Auth, algorithm_to_jwt_algorithm};
-use crate::kvs::Datastore;
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
+use crate::kvs::{Datastore, Transaction};
 use crate::types::{PublicValue, PublicVariables};
 use crate::val::{Datetime, Value};
 
@@ -53,6 +53,7 @@ use crate::val::{Datetime, Value};
 /// Returns a `Token` that can be either:
 /// - An access token only
 /// - An access token and a refresh token
+/// - A challenge, for system users with TOTP enabled, which must be completed with [`challenge`]
 ///
 /// # Authentication Methods
 ///
@@ -462,6 +463,233 @@ fn auth_from_level_user(level: Level, user: &catalog::UserDefinition) -> Result<
 	Ok(Auth::new(actor))
 }
 
+/// Issues a token for a system user whose credentials have been verified.
+///
+/// If the user has TOTP enabled, the session is left untouched and a challenge
+/// is returned instead, which must be completed with [`challenge`].
+fn user_token(session: &mut Session, level: Level, u: &catalog::UserDefinition) -> Result<Token> {
+	match u.totp {
+		Some(_) => challenge_token(level, u),
+		None => issue_user_token(session, level, u),
+	}
+}
+
+/// Issues an access token for a system user, and authenticates the session
+fn issue_user_token(
+	session: &mut Session,
+	level: Level,
+	u: &catalog::UserDefinition,
+) -> Result<Token> {
+	// Create the authentication key
+	let key = EncodingKey::from_secret(u.code.as_ref());
+	// Create the authentication claim
+	let (ns, db) = match &level {
+		Level::Database(ns, db) => (Some(ns.clone()), Some(db.clone())),
+		Level::Namespace(ns) => (Some(ns.clone()), None),
+		_ => (None, None),
+	};
+	let val = Claims {
+		iss: Some(SERVER_NAME.to_owned()),
+		iat: Some(Utc::now().timestamp()),
+		nbf: Some(Utc::now().timestamp()),
+		exp: expiration(u.token_duration)?,
+		jti: Some(Uuid::new_v4().to_string()),
+		ns: ns.clone(),
+		db: db.clone(),
+		id: Some(u.name.clone()),
+		..Claims::default()
+	};
+	// Create the authentication token
+	let enc = encode(&HEADER, &val, &key);
+
+	let au = auth_from_level_user(level, u)?;
+
+	// Set the authentication on the session
+	session.tk = Some(
+		crate::val::convert_value_to_public_value(val.into_claims_object().into())
+			.expect("claims conversion should succeed"),
+	);
+	if ns.is_some() {
+		session.ns = ns;
+	}
+	if db.is_some() {
+		session.db = db;
+	}
+	session.exp = expiration(u.session_duration)?;
+	session.au = Arc::new(au);
+	// Check the authentication token
+	match enc {
+		// The auth token was created successfully
+		Ok(tk) => Ok(Token::Access(tk)),
+		_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
+	}
+}
+
+/// The key used to sign the challenges of a user.
+///
+/// This differs from the key used to sign access tokens, so that a challenge
+/// can never be used to authenticate.
+fn challenge_key(u: &catalog::UserDefinition) -> String {
+	format!("{}:totp", u.code)
+}
+
+/// Issues a short-lived challenge for a user with TOTP enabled
+fn challenge_token(level: Level, u: &catalog::UserDefinition) -> Result<Token> {
+	let key = EncodingKey::from_secret(challenge_key(u).as_ref());
+	let (ns, db) = match level {
+		Level::Database(ns, db) => (Some(ns), Some(db)),
+		Level::Namespace(ns) => (Some(ns), None),
+		_ => (None, None),
+	};
+	let val = Claims {
+		iss: Some(SERVER_NAME.to_owned()),
+		iat: Some(Utc::now().timestamp()),
+		nbf: Some(Utc::now().timestamp()),
+		exp: expiration(Some(iam::totp::CHALLENGE_DURATION))?,
+		jti: Some(Uuid::new_v4().to_string()),
+		ns,
+		db,
+		id: Some(u.name.clone()),
+		..Claims::default()
+	};
+	match encode(&HEADER, &val, &key) {
+		Ok(challenge) => Ok(Token::Challenge {
+			challenge,
+		}),
+		_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
+	}
+}
+
+/// Completes a signin challenge with a second factor.
+///
+/// The code can either be the current TOTP code of the user, or one of the
+/// recovery codes generated on enrolment. Recovery codes can only be used
+/// once, and are removed from the user definition when used. TOTP codes are
+/// only accepted for a later time step than the last code which was used, so
+/// they can not be replayed. After too many consecutive failed attempts, across
+/// all of the challenges of the user, challenges are rejected until the lockout
+/// has passed. On success, the session is authenticated as the user and an
+/// access token is returned.
+///
+/// Challenges are only issued to system users. Record access methods check a
+/// second factor within their `SIGNIN` clause, using the `crypto::totp`
+/// functions.
+pub async fn challenge(
+	kvs: &Datastore,
+	session: &mut Session,
+	challenge: String,
+	code: String,
+) -> Result<Token> {
+	// Decode the claims to find the user, the signature is verified below
+	let claims = match jsonwebtoken::dangerous::insecure_decode::<Claims>(&challenge) {
+		Ok(v) => v.claims,
+		Err(_) => bail!(Error::InvalidAuth),
+	};
+	let Some(user) = claims.id else {
+		bail!(Error::InvalidAuth);
+	};
+	let level = match (claims.ns, claims.db) {
+		(Some(ns), Some(db)) => Level::Database(ns, db),
+		(Some(ns), None) => Level::Namespace(ns),
+		(None, None) => Level::Root,
+		_ => bail!(Error::InvalidAuth),
+	};
+	// Create a new write transaction, as recovery codes are consumed
+	let tx = kvs.transaction(Write, Optimistic).await?;
+	let u = match &level {
+		Level::Database(ns, db) => match catch!(tx, tx.get_db_by_name(ns, db, None).await) {
+			Some(db) => {
+				catch!(tx, tx.get_db_user(db.namespace_id, db.database_id, &user, None).await)
+			}
+			None => None,
+		},
+		Level::Namespace(ns) => match catch!(tx, tx.get_ns_by_name(ns, None).await) {
+			Some(ns) => catch!(tx, tx.get_ns_user(ns.namespace_id, &user, None).await),
+			None => None,
+		},
+		_ => catch!(tx, tx.get_root_user(&user, None).await),
+	};
+	let Some(mut u) = u.map(|u| u.as_ref().clone()) else {
+		let _ = tx.cancel().await;
+		bail!(Error::InvalidAuth);
+	};
+	// Verify the challenge was issued for this user, and has not expired
+	let key = jsonwebtoken::DecodingKey::from_secret(challenge_key(&u).as_ref());
+	let validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS512);
+	if let Err(e) = jsonwebtoken::decode::<Claims>(&challenge, &key, &validation) {
+		let _ = tx.cancel().await;
+		debug!("Failed to verify signin challenge for user `{user}`: {e}");
+		bail!(Error::InvalidAuth);
+	}
+	let Some(totp) = u.totp.as_mut() else {
+		let _ = tx.cancel().await;
+		bail!(Error::InvalidAuth);
+	};
+	// Forget the failed attempts once the lockout has passed
+	let now = Utc::now().timestamp();
+	let lockout = iam::totp::LOCKOUT_DURATION.as_secs() as i64;
+	if totp.last_failure.is_some_and(|t| now.saturating_sub(t) >= lockout) {
+		totp.failed_attempts = 0;
+		totp.last_failure = None;
+	}
+	// Reject every challenge of the user while it is locked out
+	if totp.failed_attempts >= iam::totp::MAX_FAILED_ATTEMPTS {
+		let _ = tx.cancel().await;
+		debug!("Too many failed attempts at signin challenges for user `{user}`");
+		bail!(Error::InvalidAuth);
+	}
+	// Check the code, falling back to the recovery codes
+	if let Some(step) = catch!(tx, iam::totp::verify_step(&totp.secret, &code, totp.last_step)) {
+		// Codes can not be used again, even within their validity period
+		totp.last_step = Some(step);
+	} else if let Some(pos) = iam::totp::find_recovery_code(&totp.recovery, &code) {
+		// Recovery codes can only be used once
+		totp.recovery.remove(pos);
+	} else {
+		// Record the failed attempt against the user
+		totp.failed_attempts += 1;
+		totp.last_failure = Some(now);
+		catch!(tx, put_challenge_user(&tx, &level, &u).await);
+		// Clear the cache
+		tx.clear_cache();
+		tx.commit().await?;
+		debug!("Failed to verify second factor for user `{user}`");
+		bail!(Error::InvalidAuth);
+	}
+	// The challenge has been completed
+	totp.failed_attempts = 0;
+	totp.last_failure = None;
+	catch!(tx, put_challenge_user(&tx, &level, &u).await);
+	// Clear the cache
+	tx.clear_cache();
+	tx.commit().await?;
+	// Issue the access token, and set the session
+	trace!("Completed signin challenge for user `{user}`");
+	issue_user_token(session, level, &u)
+}
+
+/// Stores the second factor state of a user after a signin challenge
+async fn put_challenge_user(
+	tx: &Transaction,
+	level: &Level,
+	u: &catalog::UserDefinition,
+) -> Result<()> {
+	match level {
+		Level::Database(ns, db) => {
+			let db = tx.expect_db_by_name(ns, db).await?;
+			tx.put_db_user(db.namespace_id, db.database_id, u).await?;
+		}
+		Level::Namespace(ns) => {
+			let ns = tx.expect_ns_by_name(ns).await?;
+			tx.put_ns_user(ns.namespace_id, u).await?;
+		}
+		_ => {
+			tx.put_root_user(u).await?;
+		}
+	}
+	Ok(())
+}
+
 pub async fn db_user(
 	kvs: &Datastore,
 	session: &mut Session,
@@ -472,42 +700,10 @@ pub async fn db_user(
 ) -> Result<Token> {
 	match verify_db_creds(kvs, &ns, &db, &user, &pass).await {
 		Ok(u) => {
-			// Create the authentication key
-			let key = EncodingKey::from_secret(u.code.as_ref());
-			// Create the authentication claim
-			let val = Claims {
-				iss: Some(SERVER_NAME.to_owned()),
-				iat: Some(Utc::now().timestamp()),
-				nbf: Some(Utc::now().timestamp()),
-				exp: expiration(u.token_duration)?,
-				jti: Some(Uuid::new_v4().to_string()),
-				ns: Some(ns.clone()),
-				db: Some(db.clone()),
-				id: Some(user),
-				..Claims::default()
-			};
 			// Log the authenticated database info
 			trace!("Signing in to database `{ns}/{db}`");
-			// Create the authentication token
-			let enc = encode(&HEADER, &val, &key);
-
-			let au = auth_from_level_user(Level::Database(ns.clone(), db.clone()), &u)?;
-
-			// Set the authentication on the session
-			session.tk = Some(
-				crate::val::convert_value_to_public_value(val.into_claims_object().into())
-					.expect("claims conversion should succeed"),
-			);
-			session.ns = Some(ns.clone());
-			session.db = Some(db.clone());
-			session.exp = expiration(u.session_duration)?;
-			session.au = Arc::new(au);
-			// Check the authentication token
-			match enc {
-				// The auth token was created successfully
-				Ok(tk) => Ok(Token::Access(tk)),
-				_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
-			}
+			// Issue a token, or a challenge if a second factor is required
+			user_token(session, Level::Database(ns, db), &u)
 		}
 		// The password did not verify
 		Err(e) => {
@@ -561,41 +757,10 @@ pub async fn ns_user(
 ) -> Result<Token> {
 	match verify_ns_creds(kvs, &ns, &user, &pass).await {
 		Ok(u) => {
-			// Create the authentication key
-			let key = EncodingKey::from_secret(u.code.as_ref());
-			// Create the authentication claim
-			let val = Claims {
-				iss: Some(SERVER_NAME.to_owned()),
-				iat: Some(Utc::now().timestamp()),
-				nbf: Some(Utc::now().timestamp()),
-				exp: expiration(u.token_duration)?,
-
-				jti: Some(Uuid::new_v4().to_string()),
-				ns: Some(ns.clone()),
-				id: Some(user),
-				..Claims::default()
-			};
 			// Log the authenticated namespace info
 			trace!("Signing in to namespace `{ns}`");
-			// Create the authentication token
-			let enc = encode(&HEADER, &val, &key);
-
-			let au = auth_from_level_user(Level::Namespace(ns.clone()), &u)?;
-
-			// Set the authentication on the session
-			session.tk = Some(
-				crate::val::convert_value_to_public_value(val.into_claims_object().into())
-					.expect("claims conversion should succeed"),
-			);
-			session.ns = Some(ns.clone());
-			session.exp = expiration(u.session_duration)?;
-			session.au = Arc::new(au);
-			// Check the authentication token
-			match enc {
-				// The auth token was created successfully
-				Ok(tk) => Ok(Token::Access(tk)),
-				_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
-			}
+			// Issue a token, or a challenge if a second factor is required
+			user_token(session, Level::Namespace(ns), &u)
 		}
 		// The password did not verify
 		Err(e) => {
@@ -615,38 +780,10 @@ pub async fn root_user(
 ) -> Result<Token> {
 	match verify_root_creds(kvs, &user, &pass).await {
 		Ok(u) => {
-			// Create the authentication key
-			let key = EncodingKey::from_secret(u.code.as_ref());
-			// Create the authentication claim
-			let val = Claims {
-				iss: Some(SERVER_NAME.to_owned()),
-				iat: Some(Utc::now().timestamp()),
-				nbf: Some(Utc::now().timestamp()),
-				exp: expiration(u.token_duration)?,
-				jti: Some(Uuid::new_v4().to_string()),
-				id: Some(user),
-				..Claims::default()
-			};
 			// Log the authenticated root info
 			trace!("Signing in as root");
-			// Create the authentication token
-			let enc = encode(&HEADER, &val, &key);
-
-			let au = auth_from_level_user(Level::Root, &u)?;
-
-			// Set the authentication on the session
-			session.tk = Some(
-				crate::val::convert_value_to_public_value(val.into_claims_object().into())
-					.expect("claims conversion should succeed"),
-			);
-			session.exp = expiration(u.session_duration)?;
-			session.au = Arc::new(au);
-			// Check the authentication token
-			match enc {
-				// The auth token was created successfully
-				Ok(tk) => Ok(Token::Access(tk)),
-				_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
-			}
+			// Issue a token, or a challenge if a second factor is required
+			user_token(session, Level::Root, &u)
 		}
 		// The password did not verify
 		Err(e) => {
@@ -4241,6 +4378,8 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				session_duration: Expr::Literal(Literal::None),
 				token_duration: Expr::Literal(Literal::None),
 				comment: Expr::Literal(Literal::None),
+				quota: None,
+				totp: Default::default(),
 			};
 
 			let ast = Ast {
@@ -4292,4 +4431,110 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 			}
 		}
 	}
+
+	#[tokio::test]
+	async fn test_signin_totp_challenge() {
+		use crate::iam::totp;
+
+		const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner();
+		let sql = format!(
+			"DEFINE USER user ON ROOT PASSWORD 'pass' TOTP SECRET '{SECRET}' RECOVERY ['{}', '{}', '{}'] ROLES OWNER",
+			totp::hash_recovery_code("abcde-12345"),
+			totp::hash_recovery_code("fghij-67890"),
+			totp::hash_recovery_code("klmno-24680"),
+		);
+		ds.execute(&sql, &sess, None).await.unwrap();
+
+		// Signing in returns a challenge, without authenticating the session
+		let signin = async || {
+			let mut sess = Session::default();
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string()).await;
+			assert!(sess.au.is_anon(), "Session should not be authenticated");
+			match res.unwrap() {
+				Token::Challenge {
+					challenge,
+				} => challenge,
+				_ => panic!("Challenge was not returned"),
+			}
+		};
+
+		// The challenge cannot be used as an access token
+		{
+			let tk = signin().await;
+			let mut sess = Session::default();
+			iam::verify::token(&ds, &mut sess, &tk).await.unwrap_err();
+		}
+		// An invalid code does not complete the challenge
+		{
+			let tk = signin().await;
+			let mut sess = Session::default();
+			let res = challenge(&ds, &mut sess, tk, "abcdef".to_string()).await;
+			assert!(matches!(res.unwrap_err().downcast().unwrap(), Error::InvalidAuth));
+		}
+		// A valid code completes the challenge
+		{
+			let tk = signin().await;
+			let mut sess = Session::default();
+			let code = totp::current_code(SECRET).unwrap();
+			let res = challenge(&ds, &mut sess, tk, code.clone()).await.unwrap();
+			assert!(matches!(res, Token::Access(_)));
+			assert_eq!(sess.au.id(), "user");
+			assert!(sess.au.is_root());
+			// The same code cannot be replayed
+			let tk = signin().await;
+			let mut sess = Session::default();
+			let res = challenge(&ds, &mut sess, tk, code).await;
+			assert!(matches!(res.unwrap_err().downcast().unwrap(), Error::InvalidAuth));
+			assert!(sess.au.is_anon());
+		}
+		// A recovery code completes the challenge only once
+		{
+			let tk = signin().await;
+			let mut sess = Session::default();
+			let res = challenge(&ds, &mut sess, tk, "ABCDE-12345".to_string()).await;
+			assert!(matches!(res.unwrap(), Token::Access(_)));
+			let tk = signin().await;
+			let mut sess = Session::default();
+			let res = challenge(&ds, &mut sess, tk, "abcde-12345".to_string()).await;
+			assert!(matches!(res.unwrap_err().downcast().unwrap(), Error::InvalidAuth));
+			assert!(sess.au.is_anon());
+		}
+		// Failed attempts are counted across challenges, and lock the user out
+		{
+			for _ in 0..totp::MAX_FAILED_ATTEMPTS {
+				let tk = signin().await;
+				let mut sess = Session::default();
+				let res = challenge(&ds, &mut sess, tk, "abcdef".to_string()).await;
+				assert!(matches!(res.unwrap_err().downcast().unwrap(), Error::InvalidAuth));
+			}
+			// A valid code is rejected while the user is locked out
+			let tk = signin().await;
+			let mut sess = Session::default();
+			let res = challenge(&ds, &mut sess, tk, "fghij-67890".to_string()).await;
+			assert!(matches!(res.unwrap_err().downcast().unwrap(), Error::InvalidAuth));
+			assert!(sess.au.is_anon());
+			// Move the last failed attempt back past the lockout
+			let tx = ds.transaction(Write, Optimistic).await.unwrap();
+			let mut u = tx.get_root_user("user", None).await.unwrap().unwrap().as_ref().clone();
+			let state = u.totp.as_mut().unwrap();
+			assert_eq!(state.failed_attempts, totp::MAX_FAILED_ATTEMPTS);
+			let lockout = totp::LOCKOUT_DURATION.as_secs() as i64;
+			state.last_failure = state.last_failure.map(|t| t - lockout);
+			tx.put_root_user(&u).await.unwrap();
+			tx.commit().await.unwrap();
+			// A new challenge can be completed once the lockout has passed
+			let tk = signin().await;
+			let mut sess = Session::default();
+			let res = challenge(&ds, &mut sess, tk, "fghij-67890".to_string()).await;
+			assert!(matches!(res.unwrap(), Token::Access(_)));
+			// Which resets the failed attempts
+			let tx = ds.transaction(Read, Optimistic).await.unwrap();
+			let u = tx.get_root_user("user", None).await.unwrap().unwrap();
+			tx.cancel().await.unwrap();
+			let state = u.totp.as_ref().unwrap();
+			assert_eq!((state.failed_attempts, state.last_failure), (0, None));
+		}
+	}
 }
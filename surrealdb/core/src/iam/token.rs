@@ -27,13 +27,15 @@ fn decode_access_token_claims(token: &str) -> Result<jsonwebtoken::TokenData<Cla
 
 /// A token that can be either an access token alone or an access token with a refresh token.
 ///
-/// This enum supports two authentication scenarios:
+/// This enum supports three authentication scenarios:
 /// - **Access-only**: A single access token for basic authentication
 /// - **With refresh**: An access token paired with a refresh token for enhanced security
+/// - **Challenge**: A second factor is required before an access token is issued
 ///
 /// The enum uses untagged serialization, meaning it will serialize as either:
 /// - A string (for access-only tokens)
 /// - An object with `access` and `refresh` fields (for tokens with refresh)
+/// - An object with a `challenge` field (for pending second factor challenges)
 ///
 /// # Refresh Token Flow
 ///
@@ -85,6 +87,15 @@ pub enum Token {
 		/// The refresh token used to obtain new access tokens
 		refresh: String,
 	},
+	/// A challenge which must be completed with a second factor.
+	///
+	/// This variant is returned when signing in as a system user with TOTP
+	/// enabled. No session is established until the challenge is completed,
+	/// along with a valid code, using [`iam::signin::challenge`].
+	Challenge {
+		/// The short-lived challenge token identifying the pending signin
+		challenge: String,
+	},
 }
 
 impl Token {
@@ -152,6 +163,12 @@ impl Token {
 				name: "refresh".into(),
 				message: "Token is an access token, cannot refresh".into(),
 			}),
+			Token::Challenge {
+				..
+			} => bail!(Error::InvalidFunctionArguments {
+				name: "refresh".into(),
+				message: "Token is a challenge, cannot refresh".into(),
+			}),
 			Token::WithRefresh {
 				access,
 				refresh,
@@ -186,6 +203,12 @@ impl Token {
 				name: "refresh".into(),
 				message: "Token is an access token, cannot revoke refresh token".into(),
 			}),
+			Token::Challenge {
+				..
+			} => bail!(Error::InvalidFunctionArguments {
+				name: "refresh".into(),
+				message: "Token is a challenge, cannot revoke refresh token".into(),
+			}),
 			Token::WithRefresh {
 				access,
 				refresh,
@@ -218,6 +241,9 @@ impl fmt::Debug for Token {
 			Token::WithRefresh {
 				..
 			} => write!(f, "Token::WithRefresh {{ access: REDACTED, refresh: REDACTED }}"),
+			Token::Challenge {
+				..
+			} => write!(f, "Token::Challenge {{ challenge: REDACTED }}"),
 		}
 	}
 }
@@ -106,6 +106,7 @@ pub async fn basic(
 		// DB signin
 		(Some(ns), Some(db)) => match verify_db_creds(kvs, ns, db, user, pass).await {
 			Ok(u) => {
+				ensure_single_factor(&u)?;
 				debug!("Authenticated as database user '{}'", user);
 				session.exp = expiration(u.session_duration)?;
 				let au = Auth::new(Actor::from_role_names(
@@ -122,6 +123,7 @@ pub async fn basic(
 		// NS signin
 		(Some(ns), None) => match verify_ns_creds(kvs, ns, user, pass).await {
 			Ok(u) => {
+				ensure_single_factor(&u)?;
 				debug!("Authenticated as namespace user '{}'", user);
 				session.exp = expiration(u.session_duration)?;
 				let au = Auth::new(Actor::from_role_names(
@@ -138,6 +140,7 @@ pub async fn basic(
 		// Root signin
 		(None, None) => match verify_root_creds(kvs, user, pass).await {
 			Ok(u) => {
+				ensure_single_factor(&u)?;
 				debug!("Authenticated as root user '{}'", user);
 				session.exp = expiration(u.session_duration)?;
 				let au = Auth::new(Actor::from_role_names(u.name.clone(), &u.roles, Level::Root)?);
@@ -835,6 +838,17 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 	}
 }
 
+/// Ensures that a user can authenticate with a password alone.
+///
+/// Users with a second factor must sign in, and complete the challenge.
+fn ensure_single_factor(user: &catalog::UserDefinition) -> Result<()> {
+	if user.totp.is_some() {
+		debug!("Rejected basic authentication for user `{}` with a second factor", user.name);
+		bail!(Error::InvalidAuth);
+	}
+	Ok(())
+}
+
 pub async fn verify_root_creds(
 	ds: &Datastore,
 	user: &str,
@@ -1154,6 +1168,8 @@ mod tests {
 				token_duration: Expr::Literal(Literal::None),
 				session_duration: Expr::Literal(Literal::None),
 				comment: Expr::Literal(Literal::None),
+				quota: None,
+				totp: Default::default(),
 			};
 
 			let ast = Ast {
@@ -1182,6 +1198,46 @@ mod tests {
 		}
 	}
 
+	#[rstest]
+	#[tokio::test]
+	async fn test_basic_rejects_totp(
+		#[values(
+			TestLevel {
+				level: "ROOT",
+				ns: None,
+				db: None,
+			},
+			TestLevel {
+				level: "NS",
+				ns: Some("test"),
+				db: None,
+			},
+			TestLevel {
+				level: "DB",
+				ns: Some("test"),
+				db: Some("test"),
+			},
+		)]
+		level: TestLevel,
+	) {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+
+		let sql = format!("DEFINE USER user ON {} PASSWORD 'pass' TOTP ROLES OWNER", level.level);
+		ds.execute(&sql, &sess, None).await.unwrap();
+
+		let mut sess = Session {
+			ns: level.ns.map(String::from),
+			db: level.db.map(String::from),
+			..Default::default()
+		};
+
+		// The password alone is not enough for a user with a second factor
+		let res = basic(&ds, &mut sess, "user", "pass", level.ns, level.db).await;
+		assert!(matches!(res.unwrap_err().downcast().unwrap(), Error::InvalidAuth));
+		assert!(sess.au.is_anon(), "Session should not be authenticated");
+	}
+
 	#[rstest]
 	#[case::with_no_roles(None, "secret", vec![Role::Viewer], false)]
 	#[case::with_roles(Some(vec!["editor", "owner"]), "secret", vec![Role::Editor, Role::Owner], false)]
@@ -0,0 +1,86 @@
+#[cfg(target_family = "wasm")]
+use std::fs::File;
+#[cfg(target_family = "wasm")]
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+use ahash::HashSet;
+use anyhow::Result;
+#[cfg(not(target_family = "wasm"))]
+use tokio::fs::File;
+#[cfg(not(target_family = "wasm"))]
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use crate::iam::file::is_path_allowed;
+
+/// A word list used to segment text written without spaces between words.
+///
+/// The file contains one word per line. Anything following the first
+/// whitespace on a line (such as the word frequencies found in common
+/// segmentation dictionaries) is ignored, as are empty lines and lines
+/// starting with `#`.
+#[derive(Clone, Default)]
+pub(in crate::idx) struct Dictionary {
+	words: Arc<HashSet<String>>,
+	/// The length, in characters, of the longest word
+	max_len: usize,
+}
+
+impl Dictionary {
+	pub(in crate::idx) async fn new(path: &Path) -> Result<Self> {
+		let path = is_path_allowed(path)?;
+		let mut dictionary = Self::default();
+		let mut words = HashSet::default();
+		Self::iterate_file(&mut words, &path).await?;
+		dictionary.max_len = words.iter().map(|w| w.chars().count()).max().unwrap_or(0);
+		dictionary.words = Arc::new(words);
+		Ok(dictionary)
+	}
+
+	fn add_line(words: &mut HashSet<String>, line: &str) {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			return;
+		}
+		if let Some(word) = line.split_whitespace().next() {
+			words.insert(word.to_string());
+		}
+	}
+
+	#[cfg(not(target_family = "wasm"))]
+	async fn iterate_file(words: &mut HashSet<String>, path: &Path) -> Result<()> {
+		let file = File::open(path).await?;
+		let reader = BufReader::new(file);
+		let mut lines = reader.lines();
+		while let Some(line) = lines.next_line().await? {
+			yield_now!();
+			Self::add_line(words, &line);
+		}
+		Ok(())
+	}
+
+	#[cfg(target_family = "wasm")]
+	async fn iterate_file(words: &mut HashSet<String>, path: &Path) -> Result<()> {
+		let file = File::open(path)?;
+		let reader = BufReader::new(file);
+		for line_result in reader.lines() {
+			Self::add_line(words, &line_result?);
+		}
+		Ok(())
+	}
+
+	/// Returns the length, in characters, of the longest word of the
+	/// dictionary found at the start of the given characters.
+	pub(in crate::idx::ft) fn longest_match(&self, chars: &[char]) -> Option<usize> {
+		let mut word = String::new();
+		let mut longest = None;
+		for (i, c) in chars.iter().take(self.max_len).enumerate() {
+			word.push(*c);
+			if i > 0 && self.words.contains(&word) {
+				longest = Some(i + 1);
+			}
+		}
+		longest
+	}
+}
@@ -1,11 +1,14 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use deunicode::deunicode;
 use rust_stemmers::{Algorithm, Stemmer};
 
-use crate::expr::filter::Filter as SqlFilter;
+use crate::err::Error;
+use crate::expr::filter::{Filter as SqlFilter, WordList};
 use crate::expr::language::Language;
 use crate::idx::ft::Position;
 use crate::idx::ft::analyzer::mapper::Mapper;
+use crate::idx::ft::analyzer::stopwords::Stopwords;
+use crate::idx::ft::analyzer::synonyms::Synonyms;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
 use crate::idx::trees::store::IndexStores;
 
@@ -22,6 +25,8 @@ pub(super) enum Filter {
 	Lowercase,
 	Uppercase,
 	Mapper(Mapper),
+	Stopwords(Stopwords),
+	Synonyms(Synonyms),
 }
 
 impl Filter {
@@ -56,6 +61,18 @@ impl Filter {
 			}
 			SqlFilter::Uppercase => Filter::Uppercase,
 			SqlFilter::Mapper(path) => Filter::Mapper(ixs.mappers().get(path)?),
+			SqlFilter::Stopwords(WordList::Language(l)) => {
+				Filter::Stopwords(Stopwords::from_language(*l))
+			}
+			SqlFilter::Stopwords(WordList::List(lines) | WordList::File(_, lines)) => {
+				Filter::Stopwords(Stopwords::from_lines(lines))
+			}
+			SqlFilter::Synonyms(WordList::List(lines) | WordList::File(_, lines)) => {
+				Filter::Synonyms(Synonyms::new(lines)?)
+			}
+			SqlFilter::Synonyms(WordList::Language(l)) => {
+				bail!(Error::AnalyzerError(format!("There are no built-in synonyms for {l}")))
+			}
 		};
 		Ok(f)
 	}
@@ -107,6 +124,8 @@ impl Filter {
 			Filter::Stemmer(s) => Self::stem(s, c),
 			Filter::Uppercase => Self::uppercase(c),
 			Filter::Mapper(m) => m.map(c),
+			Filter::Stopwords(s) => s.filter(c),
+			Filter::Synonyms(s) => s.expand(c),
 		}
 	}
 
@@ -901,4 +920,61 @@ mod tests {
 		)
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_stopwords_language() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class FILTERS lowercase,stopwords(english)",
+			"The quick brown fox jumps over the lazy dog",
+			&["quick", "brown", "fox", "jumps", "lazy", "dog"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_stopwords_list() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS stopwords(['quick', 'lazy | a comment', '# over'])",
+			"The quick brown fox jumps over the lazy dog",
+			&["The", "brown", "fox", "jumps", "over", "the", "dog"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_synonyms() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,synonyms(['car, automobile, auto', 'tv => television', '# car => bus'])",
+			"My Car and TV",
+			&["my", "car", "automobile", "auto", "and", "television"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_synonyms_tokens() {
+		test_analyzer_tokens(
+			"ANALYZER test TOKENIZERS blank FILTERS synonyms(['car, auto'])",
+			"a car",
+			&[
+				Token::Ref {
+					chars: (0, 0, 1),
+					bytes: (0, 1),
+					len: 1,
+				},
+				Token::Ref {
+					chars: (2, 2, 5),
+					bytes: (2, 5),
+					len: 3,
+				},
+				Token::String {
+					chars: (2, 2, 5),
+					bytes: (2, 5),
+					term: "auto".to_string(),
+					len: 4,
+				},
+			],
+		)
+		.await;
+	}
 }
@@ -13,25 +13,30 @@ use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::{FlowResultExt as _, Function};
 use crate::idx::ft::analyzer::filter::FilteringStage;
-use crate::idx::ft::analyzer::tokenizer::{Tokenizer, Tokens};
+use crate::idx::ft::analyzer::tokenizer::{Tokenizers, Tokens};
 use crate::idx::ft::offset::Offset;
 use crate::idx::ft::{DocLength, TermFrequency};
 use crate::idx::trees::store::IndexStores;
 use crate::val::Value;
 
+pub(in crate::idx) mod dictionary;
 pub(in crate::idx::ft) mod filter;
 pub(in crate::idx) mod mapper;
+mod stopwords;
+mod synonyms;
 pub(in crate::idx::ft) mod tokenizer;
 
 #[derive(Clone)]
 pub(crate) struct Analyzer {
 	az: Arc<catalog::AnalyzerDefinition>,
+	tokenizers: Arc<Tokenizers>,
 	filters: Arc<Option<Vec<Filter>>>,
 }
 
 impl Analyzer {
 	pub(crate) fn new(ixs: &IndexStores, az: Arc<catalog::AnalyzerDefinition>) -> Result<Self> {
 		Ok(Self {
+			tokenizers: Arc::new(Tokenizers::try_from(ixs, &az.tokenizers)?),
 			filters: Arc::new(Filter::try_from(ixs, &az.filters)?),
 			az,
 		})
@@ -111,11 +116,7 @@ impl Analyzer {
 			return Ok(Tokens::new(input));
 		}
 
-		let tokens = if let Some(t) = &self.az.tokenizers {
-			Tokenizer::tokenize(t, input)
-		} else {
-			Tokenizer::tokenize(&[], input)
-		};
+		let tokens = self.tokenizers.tokenize(input);
 		Filter::apply_filters(tokens, &self.filters, stage)
 	}
 
@@ -158,10 +159,19 @@ impl Analyzer {
 		let mut dl = 0;
 		let mut tfos: HashMap<&str, Vec<Offset>> = HashMap::new();
 		for (i, tks) in inputs.iter().enumerate() {
+			// The position of the word within the value. Tokens generated from
+			// the same original term share the same position.
+			let mut pos = 0;
+			let mut previous = None;
 			for tk in tks.list() {
 				dl += 1;
 				let s = tks.get_token_string(tk)?;
-				let o = tk.new_offset(i as u32);
+				let start = tk.get_start();
+				if previous.is_some_and(|p| p != start) {
+					pos += 1;
+				}
+				previous = Some(start);
+				let o = tk.new_offset(i as u32, pos);
 				tfos.entry(s).or_default().push(o);
 			}
 		}
@@ -0,0 +1,1032 @@
+use std::sync::Arc;
+
+use ahash::HashSet;
+
+use crate::expr::language::Language;
+use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+
+/// A set of words which are removed from the tokens
+#[derive(Clone)]
+pub(super) struct Stopwords(Arc<HashSet<String>>);
+
+impl Stopwords {
+	pub(super) fn from_language(l: Language) -> Self {
+		Self(Arc::new(words(l).iter().map(|w| w.to_string()).collect()))
+	}
+
+	/// Builds the stopwords from lines holding one or more words separated by
+	/// whitespace. Lines starting with `#` and anything following a `|` are
+	/// comments, as in the Snowball stopword lists.
+	pub(super) fn from_lines(lines: &[String]) -> Self {
+		let mut set = HashSet::default();
+		for line in lines {
+			let line = line.trim();
+			if line.starts_with('#') {
+				continue;
+			}
+			let (words, _) = line.split_once('|').unwrap_or((line, ""));
+			set.extend(words.split_whitespace().map(str::to_owned));
+		}
+		Self(Arc::new(set))
+	}
+
+	pub(super) fn filter(&self, c: &str) -> FilterResult {
+		if self.0.contains(c) {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+}
+
+/// The built-in stopwords of a language
+fn words(l: Language) -> &'static [&'static str] {
+	match l {
+		Language::Arabic => &[
+			"في",
+			"من",
+			"على",
+			"إلى",
+			"عن",
+			"مع",
+			"هذا",
+			"هذه",
+			"ذلك",
+			"تلك",
+			"التي",
+			"الذي",
+			"الذين",
+			"هو",
+			"هي",
+			"هم",
+			"أن",
+			"إن",
+			"كان",
+			"كانت",
+			"قد",
+			"لا",
+			"ما",
+			"لم",
+			"لن",
+			"و",
+			"أو",
+			"ثم",
+			"بين",
+			"كل",
+			"بعد",
+			"قبل",
+			"حتى",
+			"إذا",
+			"عند",
+			"غير",
+			"لكن",
+			"أي",
+			"منذ",
+			"هناك",
+			"أيضا",
+			"كما",
+			"فيه",
+			"فيها",
+			"به",
+			"بها",
+			"له",
+			"لها",
+		],
+		Language::Danish => &[
+			"og", "i", "jeg", "det", "at", "en", "den", "til", "er", "som", "på", "de", "med",
+			"han", "af", "for", "ikke", "der", "var", "mig", "sig", "men", "et", "har", "om", "vi",
+			"min", "havde", "ham", "hun", "nu", "over", "da", "fra", "du", "ud", "sin", "dem",
+			"os", "op", "man", "hans", "hvor", "eller", "hvad", "skal", "selv", "her", "alle",
+			"vil", "blev", "kunne", "ind", "når", "være", "dog", "noget", "ville", "jo", "deres",
+			"efter", "ned", "skulle", "denne", "end", "dette", "mit", "også", "under", "have",
+			"dig", "anden", "hende", "mine", "alt", "meget", "sit", "sine", "vor", "mod", "disse",
+			"hvis", "din", "nogle", "hos", "blive", "mange", "ad", "bliver", "hendes", "været",
+			"thi", "jer", "sådan",
+		],
+		Language::Dutch => &[
+			"de", "en", "van", "ik", "te", "dat", "die", "in", "een", "hij", "het", "niet", "zijn",
+			"is", "was", "op", "aan", "met", "als", "voor", "had", "er", "maar", "om", "hem",
+			"dan", "zou", "of", "wat", "mijn", "men", "dit", "zo", "door", "over", "ze", "zich",
+			"bij", "ook", "tot", "je", "mij", "uit", "der", "daar", "haar", "naar", "heb", "hoe",
+			"heeft", "hebben", "deze", "u", "want", "nog", "zal", "me", "zij", "nu", "ge", "geen",
+			"omdat", "iets", "worden", "toch", "al", "waren", "veel", "meer", "doen", "toen",
+			"moet", "ben", "zonder", "kan", "hun", "dus", "alles", "onder", "ja", "eens", "hier",
+			"wie", "werd", "altijd", "doch", "wordt", "wezen", "kunnen", "ons", "zelf", "tegen",
+			"na", "reeds", "wil", "kon", "niets", "uw", "iemand", "geweest", "andere",
+		],
+		Language::English => &[
+			"a",
+			"about",
+			"above",
+			"after",
+			"again",
+			"against",
+			"all",
+			"am",
+			"an",
+			"and",
+			"any",
+			"are",
+			"as",
+			"at",
+			"be",
+			"because",
+			"been",
+			"before",
+			"being",
+			"below",
+			"between",
+			"both",
+			"but",
+			"by",
+			"can",
+			"did",
+			"do",
+			"does",
+			"doing",
+			"down",
+			"during",
+			"each",
+			"few",
+			"for",
+			"from",
+			"further",
+			"had",
+			"has",
+			"have",
+			"having",
+			"he",
+			"her",
+			"here",
+			"hers",
+			"herself",
+			"him",
+			"himself",
+			"his",
+			"how",
+			"i",
+			"if",
+			"in",
+			"into",
+			"is",
+			"it",
+			"its",
+			"itself",
+			"just",
+			"me",
+			"more",
+			"most",
+			"my",
+			"myself",
+			"no",
+			"nor",
+			"not",
+			"now",
+			"of",
+			"off",
+			"on",
+			"once",
+			"only",
+			"or",
+			"other",
+			"our",
+			"ours",
+			"ourselves",
+			"out",
+			"over",
+			"own",
+			"same",
+			"she",
+			"should",
+			"so",
+			"some",
+			"such",
+			"than",
+			"that",
+			"the",
+			"their",
+			"theirs",
+			"them",
+			"themselves",
+			"then",
+			"there",
+			"these",
+			"they",
+			"this",
+			"those",
+			"through",
+			"to",
+			"too",
+			"under",
+			"until",
+			"up",
+			"very",
+			"was",
+			"we",
+			"were",
+			"what",
+			"when",
+			"where",
+			"which",
+			"while",
+			"who",
+			"whom",
+			"why",
+			"will",
+			"with",
+			"you",
+			"your",
+			"yours",
+			"yourself",
+			"yourselves",
+		],
+		Language::Finnish => &[
+			"olla", "olen", "olet", "on", "olemme", "olette", "ovat", "ole", "oli", "olisi",
+			"olisit", "olisin", "olisimme", "olisitte", "olisivat", "olit", "olin", "olimme",
+			"olitte", "olivat", "ollut", "olleet", "en", "et", "ei", "emme", "ette", "eivät",
+			"minä", "sinä", "hän", "me", "te", "he", "tämä", "tuo", "se", "nämä", "nuo", "ne",
+			"mikä", "mitkä", "kuka", "ketkä", "joka", "jotka", "että", "ja", "jos", "koska",
+			"kuin", "mutta", "niin", "sekä", "sillä", "tai", "vaan", "vai", "vaikka", "kanssa",
+			"mukaan", "noin", "poikki", "yli", "kun", "nyt", "itse",
+		],
+		Language::French => &[
+			"au", "aux", "avec", "ce", "ces", "dans", "de", "des", "du", "elle", "en", "et", "eux",
+			"il", "ils", "je", "la", "le", "les", "leur", "lui", "ma", "mais", "me", "même", "mes",
+			"moi", "mon", "ne", "nos", "notre", "nous", "on", "ou", "par", "pas", "pour", "qu",
+			"que", "qui", "sa", "se", "ses", "son", "sur", "ta", "te", "tes", "toi", "ton", "tu",
+			"un", "une", "vos", "votre", "vous", "c", "d", "j", "l", "à", "m", "n", "s", "t", "y",
+			"été", "étée", "étées", "étés", "étant", "suis", "es", "est", "sommes", "êtes", "sont",
+			"serai", "sera", "serons", "seront", "étais", "était", "étions", "étaient", "fus",
+			"fut", "ai", "as", "avons", "avez", "ont", "aurai", "aura", "avais", "avait",
+			"avaient", "eu", "eut", "ayant", "ceci", "cela", "celà", "cet", "cette", "ici",
+			"leurs", "quel", "quels", "quelle", "quelles", "sans", "soi",
+		],
+		Language::German => &[
+			"aber", "alle", "allem", "allen", "aller", "alles", "als", "also", "am", "an", "ander",
+			"andere", "anderem", "anderen", "anderer", "anderes", "auch", "auf", "aus", "bei",
+			"bin", "bis", "bist", "da", "damit", "dann", "der", "den", "des", "dem", "die", "das",
+			"dass", "daß", "derselbe", "dein", "deine", "denn", "dich", "dir", "doch", "dort",
+			"du", "durch", "ein", "eine", "einem", "einen", "einer", "eines", "er", "es", "euch",
+			"euer", "für", "gegen", "hab", "habe", "haben", "hat", "hatte", "hier", "hin",
+			"hinter", "ich", "ihm", "ihn", "ihnen", "ihr", "ihre", "im", "in", "indem", "ins",
+			"ist", "jede", "jedem", "jeden", "jeder", "jedes", "jetzt", "kann", "kein", "keine",
+			"können", "man", "mein", "meine", "mich", "mir", "mit", "muss", "nach", "nicht",
+			"nichts", "noch", "nun", "nur", "ob", "oder", "ohne", "sehr", "sein", "seine", "sich",
+			"sie", "sind", "so", "solche", "soll", "sondern", "um", "und", "uns", "unser", "unter",
+			"viel", "vom", "von", "vor", "war", "waren", "warst", "was", "weil", "weiter",
+			"welche", "wenn", "werde", "werden", "wie", "wieder", "will", "wir", "wird", "wo",
+			"wollen", "zu", "zum", "zur", "zwar", "zwischen",
+		],
+		Language::Greek => &[
+			"ο",
+			"η",
+			"το",
+			"οι",
+			"τα",
+			"του",
+			"της",
+			"των",
+			"τον",
+			"την",
+			"και",
+			"κι",
+			"κ",
+			"ειμαι",
+			"εισαι",
+			"ειναι",
+			"ειμαστε",
+			"ειστε",
+			"στο",
+			"στον",
+			"στη",
+			"στην",
+			"μα",
+			"αλλα",
+			"απο",
+			"για",
+			"προς",
+			"με",
+			"σε",
+			"ως",
+			"παρα",
+			"αντι",
+			"κατα",
+			"μετα",
+			"θα",
+			"να",
+			"δε",
+			"δεν",
+			"μη",
+			"μην",
+			"επι",
+			"ενω",
+			"εαν",
+			"αν",
+			"τοτε",
+			"που",
+			"πως",
+			"ποιος",
+			"ποια",
+			"ποιο",
+			"ποιοι",
+			"ποιες",
+			"ποιων",
+			"ποιους",
+			"αυτος",
+			"αυτη",
+			"αυτο",
+			"αυτοι",
+			"αυτων",
+			"αυτους",
+			"αυτες",
+			"αυτα",
+			"εκεινος",
+			"εκεινη",
+			"εκεινο",
+			"εκεινοι",
+			"εκεινες",
+			"εκεινα",
+			"εκεινων",
+			"εκεινους",
+			"οπως",
+			"ομως",
+			"ισως",
+			"οσο",
+			"οτι",
+		],
+		Language::Hungarian => &[
+			"a",
+			"ahogy",
+			"ahol",
+			"aki",
+			"akik",
+			"akkor",
+			"alatt",
+			"által",
+			"általában",
+			"amely",
+			"amelyek",
+			"amelyekben",
+			"amelyeket",
+			"amelyet",
+			"amelynek",
+			"ami",
+			"amit",
+			"amolyan",
+			"amíg",
+			"amikor",
+			"át",
+			"abban",
+			"ahhoz",
+			"annak",
+			"arra",
+			"arról",
+			"az",
+			"azok",
+			"azon",
+			"azt",
+			"azzal",
+			"azért",
+			"aztán",
+			"azután",
+			"azonban",
+			"bár",
+			"be",
+			"belül",
+			"benne",
+			"cikk",
+			"cikkek",
+			"cikkeket",
+			"csak",
+			"de",
+			"e",
+			"eddig",
+			"egész",
+			"egy",
+			"egyes",
+			"egyetlen",
+			"egyéb",
+			"egyik",
+			"egyre",
+			"ekkor",
+			"el",
+			"elég",
+			"ellen",
+			"elő",
+			"először",
+			"előtt",
+			"első",
+			"én",
+			"éppen",
+			"ebben",
+			"ehhez",
+			"emilyen",
+			"ennek",
+			"erre",
+			"ez",
+			"ezt",
+			"ezek",
+			"ezen",
+			"ezzel",
+			"ezért",
+			"és",
+			"fel",
+			"felé",
+			"hanem",
+			"hiszen",
+			"hogy",
+			"hogyan",
+			"igen",
+			"így",
+			"illetve",
+			"ill.",
+			"ill",
+			"ilyen",
+			"ilyenkor",
+			"ismét",
+			"itt",
+			"jó",
+			"jól",
+			"jobban",
+			"kell",
+			"kellett",
+			"keresztül",
+			"keressünk",
+			"ki",
+			"kívül",
+			"között",
+			"közül",
+			"legalább",
+			"lehet",
+			"lehetett",
+			"legyen",
+			"lenne",
+			"lenni",
+			"lesz",
+			"lett",
+			"maga",
+			"magát",
+			"majd",
+			"már",
+			"más",
+			"másik",
+			"meg",
+			"még",
+			"mellett",
+			"mert",
+			"mely",
+			"melyek",
+			"mi",
+			"mit",
+			"míg",
+			"miért",
+			"milyen",
+			"mikor",
+			"minden",
+			"mindent",
+			"mindenki",
+			"mindig",
+			"mint",
+			"mintha",
+			"mivel",
+			"most",
+			"nagy",
+			"nagyobb",
+			"nagyon",
+			"ne",
+			"néha",
+			"nekem",
+			"neki",
+			"nem",
+			"néhány",
+			"nélkül",
+			"nincs",
+			"olyan",
+			"ott",
+			"össze",
+			"ő",
+			"ők",
+			"őket",
+			"pedig",
+			"persze",
+			"rá",
+			"s",
+			"saját",
+			"sem",
+			"semmi",
+			"sok",
+			"sokat",
+			"sokkal",
+			"számára",
+			"szemben",
+			"szerint",
+			"szinte",
+			"talán",
+			"tehát",
+			"teljes",
+			"tovább",
+			"továbbá",
+			"több",
+			"úgy",
+			"ugyanis",
+			"új",
+			"újabb",
+			"újra",
+			"után",
+			"utána",
+			"utolsó",
+			"vagy",
+			"vagyis",
+			"valaki",
+			"valami",
+			"valamint",
+			"való",
+			"vagyok",
+			"van",
+			"vannak",
+			"volt",
+			"voltam",
+			"voltak",
+			"voltunk",
+			"vissza",
+			"vele",
+			"viszont",
+			"volna",
+		],
+		Language::Italian => &[
+			"ad", "al", "allo", "ai", "agli", "all", "agl", "alla", "alle", "con", "col", "coi",
+			"da", "dal", "dallo", "dai", "dagli", "dall", "dagl", "dalla", "dalle", "di", "del",
+			"dello", "dei", "degli", "dell", "degl", "della", "delle", "in", "nel", "nello", "nei",
+			"negli", "nell", "negl", "nella", "nelle", "su", "sul", "sullo", "sui", "sugli",
+			"sull", "sugl", "sulla", "sulle", "per", "tra", "contro", "io", "tu", "lui", "lei",
+			"noi", "voi", "loro", "mio", "mia", "miei", "mie", "tuo", "tua", "tuoi", "tue", "suo",
+			"sua", "suoi", "sue", "nostro", "nostra", "nostri", "nostre", "vostro", "vostra",
+			"vostri", "vostre", "mi", "ti", "ci", "vi", "lo", "la", "li", "le", "gli", "ne", "il",
+			"un", "uno", "una", "ma", "ed", "se", "perché", "anche", "come", "dov", "dove", "che",
+			"chi", "cui", "non", "più", "quale", "quanto", "quanti", "quanta", "quante", "quello",
+			"quelli", "quella", "quelle", "questo", "questi", "questa", "queste", "si", "tutto",
+			"tutti", "a", "c", "e", "i", "l", "o", "ho", "hai", "ha", "abbiamo", "avete", "hanno",
+			"sono", "sei", "è", "siamo", "siete", "era", "erano",
+		],
+		Language::Norwegian => &[
+			"og", "i", "jeg", "det", "at", "en", "et", "den", "til", "er", "som", "på", "de",
+			"med", "han", "av", "ikke", "der", "så", "var", "meg", "seg", "men", "ett", "har",
+			"om", "vi", "min", "mitt", "ha", "hadde", "hun", "nå", "over", "da", "ved", "fra",
+			"du", "ut", "sin", "dem", "oss", "opp", "man", "kan", "hans", "hvor", "eller", "hva",
+			"skal", "selv", "sjøl", "her", "alle", "vil", "bli", "ble", "blitt", "kunne", "inn",
+			"når", "være", "kom", "noen", "noe", "ville", "dere", "deres", "kun", "ja", "etter",
+			"ned", "skulle", "denne", "for", "deg", "si", "sine", "sitt", "mot", "å", "meget",
+			"hvorfor", "dette", "disse", "uten", "hvordan", "ingen", "din", "ditt", "blir",
+			"samme", "hvilken", "hvilke", "sånn", "inni", "mellom", "vår", "hver", "hvem", "vors",
+			"hvis", "både", "bare", "enn", "fordi", "før", "mange", "også", "slik", "vært", "båe",
+			"begge", "siden",
+		],
+		Language::Portuguese => &[
+			"de", "a", "o", "que", "e", "do", "da", "em", "um", "para", "com", "não", "uma", "os",
+			"no", "se", "na", "por", "mais", "as", "dos", "como", "mas", "ao", "ele", "das", "à",
+			"seu", "sua", "ou", "quando", "muito", "nos", "já", "eu", "também", "só", "pelo",
+			"pela", "até", "isso", "ela", "entre", "depois", "sem", "mesmo", "aos", "seus", "quem",
+			"nas", "me", "esse", "eles", "você", "essa", "num", "nem", "suas", "meu", "às",
+			"minha", "numa", "pelos", "elas", "qual", "nós", "lhe", "deles", "essas", "esses",
+			"pelas", "este", "dele", "tu", "te", "vocês", "vos", "lhes", "meus", "minhas", "teu",
+			"tua", "teus", "tuas", "nosso", "nossa", "nossos", "nossas", "dela", "delas", "esta",
+			"estes", "estas", "aquele", "aquela", "aqueles", "aquelas", "isto", "aquilo", "é",
+			"são", "foi", "era", "ser", "ter", "tem", "há",
+		],
+		Language::Romanian => &[
+			"a",
+			"acea",
+			"aceasta",
+			"această",
+			"aceea",
+			"acei",
+			"aceia",
+			"acel",
+			"acela",
+			"acele",
+			"acelea",
+			"acest",
+			"acesta",
+			"aceste",
+			"acestea",
+			"acestei",
+			"acestia",
+			"acestui",
+			"aceşti",
+			"aceştia",
+			"acolo",
+			"acum",
+			"ai",
+			"aia",
+			"al",
+			"ale",
+			"alt",
+			"alta",
+			"altceva",
+			"altcineva",
+			"am",
+			"ar",
+			"are",
+			"asta",
+			"astfel",
+			"au",
+			"avea",
+			"avem",
+			"aveţi",
+			"azi",
+			"aş",
+			"aşa",
+			"că",
+			"care",
+			"cât",
+			"câte",
+			"ce",
+			"cel",
+			"cele",
+			"cine",
+			"cu",
+			"cum",
+			"da",
+			"dar",
+			"de",
+			"deci",
+			"din",
+			"după",
+			"e",
+			"ea",
+			"ei",
+			"el",
+			"ele",
+			"este",
+			"eu",
+			"fi",
+			"fie",
+			"fost",
+			"i",
+			"ia",
+			"iar",
+			"ii",
+			"în",
+			"îl",
+			"îmi",
+			"încât",
+			"însă",
+			"la",
+			"le",
+			"li",
+			"lor",
+			"lui",
+			"mai",
+			"mea",
+			"mei",
+			"mele",
+			"mi",
+			"mult",
+			"multe",
+			"ne",
+			"nici",
+			"nimic",
+			"noi",
+			"nu",
+			"o",
+			"or",
+			"ori",
+			"pe",
+			"pentru",
+			"poate",
+			"prin",
+			"s",
+			"sa",
+			"se",
+			"si",
+			"sub",
+			"sunt",
+			"sau",
+			"să",
+			"şi",
+			"tu",
+			"un",
+			"una",
+			"unde",
+			"unei",
+			"unor",
+			"unui",
+			"va",
+			"vom",
+			"voi",
+		],
+		Language::Russian => &[
+			"и",
+			"в",
+			"во",
+			"не",
+			"что",
+			"он",
+			"на",
+			"я",
+			"с",
+			"со",
+			"как",
+			"а",
+			"то",
+			"все",
+			"она",
+			"так",
+			"его",
+			"но",
+			"да",
+			"ты",
+			"к",
+			"у",
+			"же",
+			"вы",
+			"за",
+			"бы",
+			"по",
+			"только",
+			"ее",
+			"мне",
+			"было",
+			"вот",
+			"от",
+			"меня",
+			"еще",
+			"нет",
+			"о",
+			"из",
+			"ему",
+			"теперь",
+			"когда",
+			"даже",
+			"ну",
+			"вдруг",
+			"ли",
+			"если",
+			"уже",
+			"или",
+			"ни",
+			"быть",
+			"был",
+			"него",
+			"до",
+			"вас",
+			"нибудь",
+			"опять",
+			"уж",
+			"вам",
+			"ведь",
+			"там",
+			"потом",
+			"себя",
+			"ничего",
+			"ей",
+			"может",
+			"они",
+			"тут",
+			"где",
+			"есть",
+			"надо",
+			"ней",
+			"для",
+			"мы",
+			"тебя",
+			"их",
+			"чем",
+			"была",
+			"сам",
+			"чтоб",
+			"без",
+			"будто",
+			"чего",
+			"раз",
+			"тоже",
+			"себе",
+			"под",
+			"будет",
+			"ж",
+			"тогда",
+			"кто",
+			"этот",
+			"того",
+			"потому",
+			"этого",
+			"какой",
+			"совсем",
+			"ним",
+			"здесь",
+			"этом",
+			"один",
+			"почти",
+			"мой",
+			"тем",
+			"чтобы",
+			"нее",
+			"были",
+			"куда",
+			"зачем",
+			"всех",
+			"никогда",
+			"можно",
+			"при",
+			"наконец",
+			"два",
+			"об",
+			"другой",
+			"хоть",
+			"после",
+			"над",
+			"больше",
+			"тот",
+			"через",
+			"эти",
+			"нас",
+			"про",
+			"всего",
+			"них",
+			"какая",
+			"много",
+			"разве",
+			"три",
+			"эту",
+			"моя",
+			"впрочем",
+			"хорошо",
+			"свою",
+			"этой",
+			"перед",
+			"иногда",
+			"лучше",
+			"чуть",
+			"том",
+			"нельзя",
+			"такой",
+			"им",
+			"более",
+			"всегда",
+			"конечно",
+			"всю",
+			"между",
+		],
+		Language::Spanish => &[
+			"de", "la", "que", "el", "en", "y", "a", "los", "del", "se", "las", "por", "un",
+			"para", "con", "no", "una", "su", "al", "lo", "como", "más", "pero", "sus", "le", "ya",
+			"o", "este", "sí", "porque", "esta", "entre", "cuando", "muy", "sin", "sobre",
+			"también", "me", "hasta", "hay", "donde", "quien", "desde", "todo", "nos", "durante",
+			"todos", "uno", "les", "ni", "contra", "otros", "ese", "eso", "ante", "ellos", "e",
+			"esto", "mí", "antes", "algunos", "qué", "unos", "yo", "otro", "otras", "otra", "él",
+			"tanto", "esa", "estos", "mucho", "quienes", "nada", "muchos", "cual", "poco", "ella",
+			"estar", "estas", "algunas", "algo", "nosotros", "mi", "mis", "tú", "te", "ti", "tu",
+			"tus", "ellas", "nosotras", "vosotros", "vosotras", "os", "mío", "mía", "míos", "mías",
+			"tuyo", "tuya", "tuyos", "tuyas", "suyo", "suya", "suyos", "suyas", "nuestro",
+			"nuestra", "nuestros", "nuestras", "vuestro", "vuestra", "vuestros", "vuestras",
+			"esos", "esas", "es", "son", "fue", "era", "ser", "ha", "han", "he",
+		],
+		Language::Swedish => &[
+			"och", "det", "att", "i", "en", "jag", "hon", "som", "han", "på", "den", "med", "var",
+			"sig", "för", "så", "till", "är", "men", "ett", "om", "hade", "de", "av", "icke",
+			"mig", "du", "henne", "då", "sin", "nu", "har", "inte", "hans", "honom", "skulle",
+			"hennes", "där", "min", "man", "ej", "vid", "kunde", "något", "från", "ut", "när",
+			"efter", "upp", "vi", "dem", "vara", "vad", "över", "än", "dig", "kan", "sina", "här",
+			"ha", "mot", "alla", "under", "någon", "eller", "allt", "mycket", "sedan", "ju",
+			"denna", "själv", "detta", "åt", "utan", "varit", "hur", "ingen", "mitt", "ni", "bli",
+			"blev", "oss", "din", "dessa", "några", "deras", "blir", "mina", "samma", "vilken",
+			"er", "sådan", "vår", "blivit", "dess", "inom", "mellan", "sådant", "varför", "varje",
+			"vilka", "ditt", "vem", "vilket", "sitta", "sådana", "vart", "dina", "vars", "vårt",
+			"våra", "ert", "era", "vilkas",
+		],
+		Language::Tamil => &[
+			"ஒரு",
+			"என்று",
+			"மற்றும்",
+			"இந்த",
+			"இது",
+			"என்ற",
+			"கொண்டு",
+			"என்பது",
+			"பல",
+			"ஆகும்",
+			"அல்லது",
+			"அவர்",
+			"நான்",
+			"உள்ள",
+			"அந்த",
+			"இவர்",
+			"என",
+			"முதல்",
+			"என்ன",
+			"இருந்து",
+			"சில",
+			"என்",
+			"போன்ற",
+			"வேண்டும்",
+			"வந்து",
+			"இதன்",
+			"அது",
+			"அவன்",
+			"தான்",
+			"பலரும்",
+			"என்னும்",
+			"மேலும்",
+			"பின்னர்",
+			"கொண்ட",
+			"இருக்கும்",
+			"தனது",
+			"உள்ளது",
+			"போது",
+			"என்றும்",
+			"அதன்",
+			"தன்",
+			"பிறகு",
+			"அவர்கள்",
+			"வரை",
+			"அவள்",
+			"நீ",
+			"ஆகிய",
+			"இருந்தது",
+			"உள்ளன",
+			"வந்த",
+			"இருந்த",
+			"மிகவும்",
+			"இங்கு",
+			"மீது",
+			"ஓர்",
+			"இவை",
+			"இந்தக்",
+			"பற்றி",
+			"வரும்",
+			"வேறு",
+			"இரு",
+			"இதில்",
+			"போல்",
+			"இப்போது",
+			"அவரது",
+			"மட்டும்",
+			"இந்தப்",
+			"எனும்",
+			"மேல்",
+			"பின்",
+			"சேர்ந்த",
+			"ஆகியோர்",
+			"எனக்கு",
+			"இன்னும்",
+			"அந்தப்",
+			"அன்று",
+			"ஒரே",
+			"மிக",
+			"அங்கு",
+			"பல்வேறு",
+			"விட்டு",
+			"பெரும்",
+			"அதை",
+			"பற்றிய",
+			"உன்",
+			"அதிக",
+			"அந்தக்",
+			"பேர்",
+			"இதனால்",
+			"அவை",
+			"அதே",
+			"ஏன்",
+			"முறை",
+			"யார்",
+			"என்பதை",
+			"எல்லாம்",
+			"மட்டுமே",
+			"இங்கே",
+			"அங்கே",
+			"இடம்",
+			"இடத்தில்",
+			"அதில்",
+			"நாம்",
+			"அதற்கு",
+			"எனவே",
+			"பிற",
+			"சிறு",
+			"மற்ற",
+			"விட",
+			"எந்த",
+			"எனவும்",
+			"எனப்படும்",
+			"எனினும்",
+			"அடுத்த",
+			"இதனை",
+			"இதை",
+			"கொள்ள",
+			"இந்தத்",
+			"இதற்கு",
+			"அதனால்",
+			"தவிர",
+			"போல",
+			"வரையில்",
+			"சற்று",
+			"எனக்",
+		],
+		Language::Turkish => &[
+			"acaba", "ama", "aslında", "az", "bazı", "belki", "biri", "birkaç", "birşey", "biz",
+			"bu", "çok", "çünkü", "da", "daha", "de", "defa", "diye", "eğer", "en", "gibi", "hem",
+			"hep", "hepsi", "her", "hiç", "için", "ile", "ise", "kez", "ki", "kim", "mı", "mu",
+			"mü", "nasıl", "ne", "neden", "nerde", "nerede", "nereye", "niçin", "niye", "o",
+			"sanki", "şey", "siz", "şu", "tüm", "ve", "veya", "ya", "yani",
+		],
+	}
+}
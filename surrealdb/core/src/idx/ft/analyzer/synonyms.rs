@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use ahash::HashMap;
+use anyhow::{Result, bail};
+
+use crate::err::Error;
+use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+
+/// Expands a token into its synonyms.
+///
+/// The rules use the Solr synonym format, with one rule per line:
+/// - `car, automobile, auto`: each term is expanded into all the terms of the
+///   list.
+/// - `ipod, i-pod => ipod, i pod`: each term on the left is replaced by the
+///   terms on the right.
+///
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Clone)]
+pub(super) struct Synonyms(Arc<HashMap<String, Vec<String>>>);
+
+impl Synonyms {
+	pub(super) fn new(lines: &[String]) -> Result<Self> {
+		let mut map: HashMap<String, Vec<String>> = HashMap::default();
+		for (n, line) in lines.iter().enumerate() {
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+			let (from, to) = match line.split_once("=>") {
+				Some((from, to)) => (Self::terms(from), Self::terms(to)),
+				None => (Self::terms(line), Self::terms(line)),
+			};
+			if from.is_empty() || to.is_empty() {
+				bail!(Error::AnalyzerError(format!(
+					"Invalid synonym rule on line {}: {line}",
+					n + 1
+				)));
+			}
+			for f in from {
+				let terms = map.entry(f).or_default();
+				for t in &to {
+					if !terms.contains(t) {
+						terms.push(t.clone());
+					}
+				}
+			}
+		}
+		Ok(Self(Arc::new(map)))
+	}
+
+	fn terms(s: &str) -> Vec<String> {
+		s.split(',').map(str::trim).filter(|t| !t.is_empty()).map(str::to_owned).collect()
+	}
+
+	pub(super) fn expand(&self, c: &str) -> FilterResult {
+		let Some(terms) = self.0.get(c) else {
+			return FilterResult::Term(Term::Unchanged);
+		};
+		FilterResult::Terms(
+			terms
+				.iter()
+				.map(|t| {
+					if t == c {
+						Term::Unchanged
+					} else {
+						Term::NewTerm(t.clone(), 0)
+					}
+				})
+				.collect(),
+		)
+	}
+}
@@ -1,10 +1,14 @@
 use anyhow::{Result, bail};
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::err::Error;
 use crate::expr::tokenizer::Tokenizer as SqlTokenizer;
 use crate::idx::ft::Position;
+use crate::idx::ft::analyzer::dictionary::Dictionary;
 use crate::idx::ft::analyzer::filter::{Filter, FilterResult, Term};
 use crate::idx::ft::offset::Offset;
+use crate::idx::trees::store::IndexStores;
 use crate::val::Value;
 
 pub(in crate::idx::ft) struct Tokens {
@@ -129,16 +133,31 @@ impl Token {
 		}
 	}
 
-	pub(in crate::idx::ft) fn new_offset(&self, i: u32) -> Offset {
+	pub(in crate::idx::ft) fn new_offset(&self, i: u32, pos: Position) -> Offset {
 		match self {
 			Token::Ref {
 				chars,
 				..
-			} => Offset::new(i, chars.0, chars.1, chars.2),
+			} => Offset::new(i, chars.0, chars.1, chars.2, pos),
 			Token::String {
 				chars,
 				..
-			} => Offset::new(i, chars.0, chars.1, chars.2),
+			} => Offset::new(i, chars.0, chars.1, chars.2, pos),
+		}
+	}
+
+	/// The start position of the original term. Tokens generated from the
+	/// same original term (by n-grams or synonyms, for instance) share it.
+	pub(in crate::idx::ft) fn get_start(&self) -> Position {
+		match self {
+			Token::Ref {
+				chars,
+				..
+			}
+			| Token::String {
+				chars,
+				..
+			} => chars.0,
 		}
 	}
 
@@ -192,12 +211,257 @@ impl Token {
 	}
 }
 
-pub(super) struct Tokenizer {
+/// The tokenizers of an analyzer.
+///
+/// The segmenting tokenizers (`UNICODE`, `CJK`, `REGEX` and `KEYWORD`) are
+/// applied first, in the order they are defined, each one splitting the
+/// segments produced by the previous one. The character class tokenizers
+/// (`BLANK`, `CAMEL`, `CLASS` and `PUNCT`) are then applied to every
+/// resulting segment.
+pub(super) struct Tokenizers {
+	segmenters: Vec<Segmenter>,
+	classes: Vec<SqlTokenizer>,
+}
+
+impl Tokenizers {
+	pub(super) fn try_from(ixs: &IndexStores, t: &Option<Vec<SqlTokenizer>>) -> Result<Self> {
+		let mut segmenters = Vec::new();
+		let mut classes = Vec::new();
+		for t in t.iter().flatten() {
+			if t.is_character_class() {
+				classes.push(t.clone());
+			} else {
+				segmenters.push(Segmenter::new(ixs, t)?);
+			}
+		}
+		Ok(Self {
+			segmenters,
+			classes,
+		})
+	}
+
+	pub(super) fn tokenize(&self, i: String) -> Tokens {
+		let mut segments = vec![Segment {
+			chars: (0, i.chars().count() as Position),
+			bytes: (0, i.len() as Position),
+		}];
+		for s in &self.segmenters {
+			let mut next = Vec::with_capacity(segments.len());
+			for sg in segments {
+				s.segment(&i, sg, &mut next);
+			}
+			segments = next;
+		}
+		let mut t = Vec::new();
+		if self.segmenters.is_empty() || !self.classes.is_empty() {
+			for sg in segments {
+				Tokenizer::new(&self.classes).split(&i, sg, &mut t);
+			}
+		} else {
+			for sg in segments {
+				if sg.chars.0 < sg.chars.1 {
+					t.push(sg.into());
+				}
+			}
+		}
+		Tokens {
+			i,
+			t,
+		}
+	}
+}
+
+/// A part of the input string, delimited by its character and byte positions
+#[derive(Clone, Copy)]
+struct Segment {
+	chars: (Position, Position),
+	bytes: (Position, Position),
+}
+
+impl Segment {
+	fn as_str<'a>(&self, i: &'a str) -> &'a str {
+		&i[self.bytes.0 as usize..self.bytes.1 as usize]
+	}
+
+	/// Builds the sub-segment for the string `s`, found at the byte offset
+	/// `b` of this segment, and starting at the character position `c`.
+	fn sub(&self, c: Position, b: usize, s: &str) -> Self {
+		let b = self.bytes.0 + b as Position;
+		Self {
+			chars: (c, c + s.chars().count() as Position),
+			bytes: (b, b + s.len() as Position),
+		}
+	}
+}
+
+impl From<Segment> for Token {
+	fn from(sg: Segment) -> Self {
+		Token::Ref {
+			chars: (sg.chars.0, sg.chars.0, sg.chars.1),
+			bytes: sg.bytes,
+			len: sg.chars.1 - sg.chars.0,
+		}
+	}
+}
+
+/// A tokenizer splitting the input into segments, rather than classifying
+/// each character.
+enum Segmenter {
+	/// Unicode word segmentation (UAX #29)
+	Unicode,
+	/// Segmentation of Chinese, Japanese, Korean and other scripts written
+	/// without spaces between words
+	Cjk(Option<Dictionary>),
+	/// Every match of a regular expression is a token
+	Regex(Regex),
+	/// The whole input is a single token
+	Keyword,
+}
+
+impl Segmenter {
+	fn new(ixs: &IndexStores, t: &SqlTokenizer) -> Result<Self> {
+		Ok(match t {
+			SqlTokenizer::Unicode => Self::Unicode,
+			SqlTokenizer::Cjk(None) => Self::Cjk(None),
+			SqlTokenizer::Cjk(Some(path)) => Self::Cjk(Some(ixs.dictionaries().get(path)?)),
+			SqlTokenizer::Regex(pattern) => match Regex::new(pattern) {
+				Ok(r) => Self::Regex(r),
+				Err(e) => bail!(Error::AnalyzerError(format!("Invalid regex: {e}"))),
+			},
+			SqlTokenizer::Keyword => Self::Keyword,
+			SqlTokenizer::Blank
+			| SqlTokenizer::Camel
+			| SqlTokenizer::Class
+			| SqlTokenizer::Punct => {
+				bail!(Error::AnalyzerError(format!("{t} is not a segmenting tokenizer")))
+			}
+		})
+	}
+
+	fn segment(&self, i: &str, sg: Segment, out: &mut Vec<Segment>) {
+		let s = sg.as_str(i);
+		match self {
+			Self::Keyword => {
+				if !s.is_empty() {
+					out.push(sg);
+				}
+			}
+			Self::Unicode => {
+				let mut c = sg.chars.0;
+				for (b, w) in s.split_word_bound_indices() {
+					let r = sg.sub(c, b, w);
+					if w.chars().any(char::is_alphanumeric) {
+						out.push(r);
+					}
+					c = r.chars.1;
+				}
+			}
+			Self::Regex(r) => {
+				let mut c = sg.chars.0;
+				let mut last = 0;
+				for m in r.find_iter(s) {
+					c += s[last..m.start()].chars().count() as Position;
+					let r = sg.sub(c, m.start(), m.as_str());
+					if !m.is_empty() {
+						out.push(r);
+					}
+					c = r.chars.1;
+					last = m.end();
+				}
+			}
+			Self::Cjk(d) => Self::segment_cjk(d.as_ref(), s, sg, out),
+		}
+	}
+
+	/// Splits the runs of characters from scripts written without spaces into
+	/// words using a dictionary. Without a dictionary, runs of Han, Kana and
+	/// Hangul characters are split into overlapping bigrams, while runs of
+	/// Thai, Lao, Myanmar and Khmer characters, whose syllables span several
+	/// characters, are kept whole. Any other run of alphanumeric characters is
+	/// kept as a single segment.
+	fn segment_cjk(d: Option<&Dictionary>, s: &str, sg: Segment, out: &mut Vec<Segment>) {
+		let chars: Vec<char> = s.chars().collect();
+		let mut bytes: Vec<Position> = s.char_indices().map(|(b, _)| b as Position).collect();
+		bytes.push(s.len() as Position);
+		let mut push = |from: usize, to: usize| {
+			out.push(Segment {
+				chars: (sg.chars.0 + from as Position, sg.chars.0 + to as Position),
+				bytes: (sg.bytes.0 + bytes[from], sg.bytes.0 + bytes[to]),
+			})
+		};
+		let mut i = 0;
+		while i < chars.len() {
+			let c = chars[i];
+			if is_separator(c) {
+				i += 1;
+				continue;
+			}
+			let script = Script::of(c);
+			let mut j = i + 1;
+			while j < chars.len() && !is_separator(chars[j]) && Script::of(chars[j]) == script {
+				j += 1;
+			}
+			if script == Script::Spaced {
+				push(i, j);
+			} else if let Some(d) = d {
+				let mut k = i;
+				while k < j {
+					let l = d.longest_match(&chars[k..j]).unwrap_or(1);
+					push(k, k + l);
+					k += l;
+				}
+			} else if script == Script::Syllabic || j - i == 1 {
+				push(i, j);
+			} else {
+				for k in i..j - 1 {
+					push(k, k + 2);
+				}
+			}
+			i = j;
+		}
+	}
+}
+
+/// Whitespace, punctuation and symbols delimit words in every script
+fn is_separator(c: char) -> bool {
+	!c.is_alphanumeric() && Script::of(c) == Script::Spaced
+}
+
+/// How the words of a script are delimited
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Script {
+	/// Words are delimited by spaces or punctuation
+	Spaced,
+	/// Han, Kana and Hangul, written without spaces, where each character
+	/// is a syllable
+	Ideographic,
+	/// Thai, Lao, Myanmar and Khmer, written without spaces, where vowels
+	/// and tone marks combine with consonants into syllables
+	Syllabic,
+}
+
+impl Script {
+	fn of(c: char) -> Self {
+		match c as u32 {
+			// Han
+			0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x2FA1F
+			// Hiragana and Katakana
+			| 0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F
+			// Hangul
+			| 0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Self::Ideographic,
+			// Thai, Lao, Myanmar and Khmer
+			0x0E00..=0x0EFF | 0x1000..=0x109F | 0x1780..=0x17FF => Self::Syllabic,
+			_ => Self::Spaced,
+		}
+	}
+}
+
+struct Tokenizer {
 	splitters: Vec<Splitter>,
 }
 
 impl Tokenizer {
-	pub(in crate::idx::ft) fn new(t: &[SqlTokenizer]) -> Self {
+	fn new(t: &[SqlTokenizer]) -> Self {
 		Self {
 			splitters: t.iter().map(|t| t.into()).collect(),
 		}
@@ -229,17 +493,16 @@ impl Tokenizer {
 		r
 	}
 
-	pub(super) fn tokenize(t: &[SqlTokenizer], i: String) -> Tokens {
-		let mut w = Tokenizer::new(t);
-		let mut last_char_pos = 0;
-		let mut last_byte_pos = 0;
-		let mut current_char_pos = 0;
-		let mut current_byte_pos = 0;
+	/// Splits a segment of the input into tokens
+	fn split(&mut self, i: &str, sg: Segment, t: &mut Vec<Token>) {
+		let mut last_char_pos = sg.chars.0;
+		let mut last_byte_pos = sg.bytes.0;
+		let mut current_char_pos = sg.chars.0;
+		let mut current_byte_pos = sg.bytes.0;
 		let mut previous_character_role = CharacterRole::PartOfCurrentToken;
-		let mut t = Vec::new();
-		for c in i.chars() {
+		for c in sg.as_str(i).chars() {
 			let char_len = c.len_utf8() as Position;
-			let cr = w.character_role(c);
+			let cr = self.character_role(c);
 			// if the new character is not part of the current token,
 			if !matches!(cr, CharacterRole::PartOfCurrentToken)
 				|| matches!(previous_character_role, CharacterRole::IsolatedToken)
@@ -273,10 +536,6 @@ impl Tokenizer {
 				len: current_char_pos - last_char_pos,
 			});
 		}
-		Tokens {
-			i,
-			t,
-		}
 	}
 }
 
@@ -349,6 +608,8 @@ impl Splitter {
 			SqlTokenizer::Camel => self.camel_role(cl),
 			SqlTokenizer::Class => self.class_role(cl),
 			SqlTokenizer::Punct => self.punct_role(cl),
+			// Segmenting tokenizers do not classify characters
+			_ => CharacterRole::PartOfCurrentToken,
 		}
 	}
 
@@ -413,7 +674,8 @@ impl Splitter {
 
 #[cfg(test)]
 mod tests {
-	use crate::idx::ft::analyzer::tests::test_analyzer;
+	use crate::idx::ft::analyzer::tests::{test_analyzer, test_analyzer_tokens};
+	use crate::idx::ft::analyzer::tokenizer::Token;
 
 	#[tokio::test]
 	async fn test_tokenize_blank_class() {
@@ -485,4 +747,107 @@ static LANGUAGE: &str = "Rust";"#,
 		)
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_tokenize_unicode() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS unicode FILTERS lowercase",
+			"The quick (\"brown\") fox can't jump 32.3 feet, right?",
+			&["the", "quick", "brown", "fox", "can't", "jump", "32.3", "feet", "right"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_unicode_offsets() {
+		test_analyzer_tokens(
+			"ANALYZER test TOKENIZERS unicode",
+			"Große Straße",
+			&[
+				Token::Ref {
+					chars: (0, 0, 5),
+					bytes: (0, 6),
+					len: 5,
+				},
+				Token::Ref {
+					chars: (6, 6, 12),
+					bytes: (7, 14),
+					len: 6,
+				},
+			],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_regex() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS regex('[A-Z]{2}-[0-9]+')",
+			"Tickets AB-123, CD-4 and ef-56 were closed",
+			&["AB-123", "CD-4"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_keyword() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS keyword FILTERS lowercase",
+			"  New York City ",
+			&["  new york city "],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_cjk_bigrams() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS cjk FILTERS lowercase",
+			"我爱北京, SurrealDB 数据库。日",
+			&["我爱", "爱北", "北京", "surrealdb", "数据", "据库", "日"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_cjk_unspaced_scripts() {
+		// Thai and Khmer runs are not split into bigrams without a dictionary
+		test_analyzer(
+			"ANALYZER test TOKENIZERS cjk",
+			"สวัสดีครับ ភាសាខ្មែរ 日本",
+			&["สวัสดีครับ", "ភាសាខ្មែរ", "日本"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_cjk_offsets() {
+		test_analyzer_tokens(
+			"ANALYZER test TOKENIZERS cjk",
+			"a 北京",
+			&[
+				Token::Ref {
+					chars: (0, 0, 1),
+					bytes: (0, 1),
+					len: 1,
+				},
+				Token::Ref {
+					chars: (2, 2, 4),
+					bytes: (2, 8),
+					len: 2,
+				},
+			],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_unicode_class() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS unicode,class FILTERS lowercase",
+			"Release v2beta is out",
+			&["release", "v", "2", "beta", "is", "out"],
+		)
+		.await;
+	}
 }
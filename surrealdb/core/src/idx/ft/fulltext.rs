@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use reblessive::tree::Stk;
 use revision::revisioned;
 use roaring::RoaringTreemap;
@@ -24,18 +24,21 @@ use crate::catalog::{FullTextParams, Scoring};
 /// - Compaction of index data
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
+use crate::err::Error;
 use crate::expr::Idiom;
-use crate::expr::operator::BooleanOperator;
+use crate::expr::operator::{BooleanOperator, Fuzziness, MatchesOperator};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::analyzer::Analyzer;
 use crate::idx::ft::analyzer::filter::FilteringStage;
-use crate::idx::ft::analyzer::tokenizer::Tokens;
+use crate::idx::ft::analyzer::tokenizer::{Token, Tokens};
 use crate::idx::ft::highlighter::{HighlightParams, Highlighter, Offseter};
 use crate::idx::ft::offset::Offset;
+use crate::idx::ft::query::{self, ClauseKind, ClausePosition, ClauseText, Occur, QueryClause};
 use crate::idx::ft::{DocLength, Score, TermFrequency};
 use crate::idx::planner::iterators::MatchesHitsIterator;
 use crate::idx::seqdocids::{DocId, SeqDocIds};
 use crate::idx::trees::store::IndexStores;
+use crate::key::index::td::Td;
 use crate::key::index::tt::Tt;
 use crate::kvs::{Transaction, impl_kv_value_revisioned};
 use crate::val::{RecordId, Value};
@@ -82,14 +85,86 @@ impl DocLengthAndCount {
 	}
 }
 
+/// The number of keys read per batch when scanning the term dictionary
+const TERMS_BATCH_SIZE: u32 = 1000;
+
+/// A unique term of a search query
+struct QueryTerm {
+	/// The term
+	term: String,
+	/// The length of the term, in characters
+	len: u32,
+	/// The terms of the index matching the term. This is the term itself,
+	/// unless the query uses the `FUZZY` or `PREFIX` options.
+	expansions: Vec<String>,
+	/// The documents containing any of the matching terms
+	docs: Option<RoaringTreemap>,
+}
+
+/// How the terms of a query are matched against the terms of the index,
+/// given the `FUZZY` and `PREFIX` options of the `MATCHES` operator
+#[derive(Clone, Copy, Default)]
+struct TermExpansion {
+	fuzzy: Option<Fuzziness>,
+	prefix: bool,
+}
+
+impl From<&MatchesOperator> for TermExpansion {
+	fn from(mo: &MatchesOperator) -> Self {
+		Self {
+			fuzzy: mo.fuzzy,
+			prefix: mo.prefix,
+		}
+	}
+}
+
+impl TermExpansion {
+	fn is_exact(&self) -> bool {
+		self.fuzzy.is_none() && !self.prefix
+	}
+
+	/// Checks if a term of the index matches a term of the query. The edit
+	/// distance is the Damerau-Levenshtein distance, as used by
+	/// `string::distance::damerau_levenshtein`. With `PREFIX`, the distance
+	/// is computed against the beginnings of the candidate.
+	fn accepts(&self, term: &str, candidate: &str) -> bool {
+		if candidate == term || (self.prefix && candidate.starts_with(term)) {
+			return true;
+		}
+		let Some(fuzzy) = self.fuzzy else {
+			return false;
+		};
+		let max = fuzzy.max_distance(term);
+		if max == 0 {
+			return false;
+		}
+		let len = term.chars().count();
+		let candidate_len = candidate.chars().count();
+		if candidate_len.abs_diff(len) <= max && strsim::damerau_levenshtein(term, candidate) <= max
+		{
+			return true;
+		}
+		if self.prefix {
+			for l in len.saturating_sub(max)..=(len + max).min(candidate_len.saturating_sub(1)) {
+				let end = candidate.char_indices().nth(l).map_or(candidate.len(), |(i, _)| i);
+				if strsim::damerau_levenshtein(term, &candidate[..end]) <= max {
+					return true;
+				}
+			}
+		}
+		false
+	}
+}
+
 /// Represents the terms in a search query and their associated document sets
 pub(crate) struct QueryTerms {
-	/// The tokenized query terms
-	#[allow(dead_code)]
-	tokens: Tokens,
-	/// Document sets for each term (RoaringTreemap of document IDs)
-	#[allow(dead_code)]
-	docs: Vec<Option<RoaringTreemap>>,
+	/// The unique terms of the query
+	terms: Vec<QueryTerm>,
+	/// The clauses of a structured query (phrases, NEAR, required or excluded
+	/// terms). Empty if the query is a plain list of terms.
+	clauses: Vec<QueryClause>,
+	/// How the terms are matched against the terms of a value
+	expansion: TermExpansion,
 	/// Indicates if any terms in the query are not found in the index
 	#[allow(dead_code)]
 	has_unknown_terms: bool,
@@ -97,23 +172,54 @@ pub(crate) struct QueryTerms {
 
 impl QueryTerms {
 	pub(crate) fn is_empty(&self) -> bool {
-		self.tokens.list().is_empty()
+		self.terms.is_empty()
 	}
 
 	pub(crate) fn contains_doc(&self, doc_id: DocId) -> bool {
-		for d in self.docs.iter().flatten() {
-			if d.contains(doc_id) {
-				return true;
+		if self.clauses.is_empty() {
+			return self.terms.iter().any(|t| t.docs.as_ref().is_some_and(|d| d.contains(doc_id)));
+		}
+		self.matches_clauses(BooleanOperator::Or, |c| {
+			c.docs.as_ref().is_some_and(|d| d.contains(doc_id))
+		})
+	}
+
+	/// Combines the clauses of a structured query, given whether each clause
+	/// matches
+	fn matches_clauses(&self, bo: BooleanOperator, matches: impl Fn(&QueryClause) -> bool) -> bool {
+		let mut required = false;
+		let mut optional = false;
+		for c in &self.clauses {
+			match (c.occur, bo) {
+				(Occur::Must, _) | (Occur::Should, BooleanOperator::And) => {
+					if !matches(c) {
+						return false;
+					}
+					required = true;
+				}
+				(Occur::Should, BooleanOperator::Or) => optional |= matches(c),
+				(Occur::MustNot, _) => {
+					if matches(c) {
+						return false;
+					}
+				}
 			}
 		}
-		false
+		required || optional
 	}
 
-	pub(in crate::idx::ft) fn matches_or(&self, tks: &[Tokens]) -> Result<bool> {
-		for t in self.tokens.list() {
-			let t = self.tokens.get_token_string(t)?;
-			for tokens in tks {
-				if tokens.try_contains(t)? {
+	/// Checks if the tokens of an analyzed value contain a term matching the
+	/// query term
+	fn value_contains(&self, tks: &[Tokens], term: &str) -> Result<bool> {
+		for tokens in tks {
+			if self.expansion.is_exact() {
+				if tokens.try_contains(term)? {
+					return Ok(true);
+				}
+				continue;
+			}
+			for t in tokens.list() {
+				if self.expansion.accepts(term, tokens.get_token_string(t)?) {
 					return Ok(true);
 				}
 			}
@@ -121,17 +227,18 @@ impl QueryTerms {
 		Ok(false)
 	}
 
-	pub(in crate::idx::ft) fn matches_and(&self, tks: &[Tokens]) -> Result<bool> {
-		for t in self.tokens.list() {
-			let t = self.tokens.get_token_string(t)?;
-			let mut found = false;
-			for tokens in tks {
-				if tokens.try_contains(t)? {
-					found = true;
-					break;
-				}
+	pub(in crate::idx::ft) fn matches_or(&self, tks: &[Tokens]) -> Result<bool> {
+		for t in &self.terms {
+			if self.value_contains(tks, &t.term)? {
+				return Ok(true);
 			}
-			if !found {
+		}
+		Ok(false)
+	}
+
+	pub(in crate::idx::ft) fn matches_and(&self, tks: &[Tokens]) -> Result<bool> {
+		for t in &self.terms {
+			if !self.value_contains(tks, &t.term)? {
 				return Ok(false);
 			}
 		}
@@ -139,6 +246,46 @@ impl QueryTerms {
 	}
 }
 
+/// Collects the unique terms of a query
+#[derive(Default)]
+struct QueryTermsCollector {
+	terms: Vec<(String, u32)>,
+	indexes: HashMap<String, usize>,
+}
+
+impl QueryTermsCollector {
+	fn add(&mut self, tokens: &Tokens, token: &Token) -> Result<usize> {
+		let term = tokens.get_token_string(token)?;
+		if let Some(i) = self.indexes.get(term) {
+			return Ok(*i);
+		}
+		let i = self.terms.len();
+		self.terms.push((term.to_owned(), token.get_char_len()));
+		self.indexes.insert(term.to_owned(), i);
+		Ok(i)
+	}
+
+	/// Collects the terms of an analyzed clause, grouped by word position
+	fn positions(&mut self, tokens: &Tokens) -> Result<Vec<ClausePosition>> {
+		let mut positions: Vec<ClausePosition> = Vec::new();
+		let mut previous = None;
+		for token in tokens.list() {
+			let t = self.add(tokens, token)?;
+			let start = token.get_start();
+			match positions.last_mut() {
+				Some(p) if previous == Some(start) => {
+					if !p.contains(&t) {
+						p.push(t);
+					}
+				}
+				_ => positions.push(vec![t]),
+			}
+			previous = Some(start);
+		}
+		Ok(positions)
+	}
+}
+
 #[derive(Clone)]
 pub(crate) struct Bm25Params {
 	pub(in crate::idx) k1: f32,
@@ -173,6 +320,7 @@ impl FullTextIndex {
 	) -> Result<Self> {
 		let az = tx.get_db_analyzer(ikb.0.ns, ikb.0.db, &p.analyzer, None).await?;
 		ixs.mappers().check(&az).await?;
+		ixs.dictionaries().check(&az).await?;
 		Self::with_analyzer(ixs, az, ikb, p)
 	}
 
@@ -374,30 +522,109 @@ impl FullTextIndex {
 	/// Extracts query terms from a search string
 	///
 	/// Tokenizes the query string, then retrieves the document bitmaps for each
-	/// unique term. The compacted bitmap fetches are batched via `tx.getm()` to
-	/// reduce KV round trips (one batch instead of N sequential gets).
+	/// unique term. If the query string uses the query syntax (phrases, `NEAR`,
+	/// `+` or `-`), each clause is analyzed separately, and the documents
+	/// matching each clause are computed.
+	///
+	/// With the `FUZZY` or `PREFIX` options, each term is expanded to the
+	/// terms of the index within the edit distance, or starting with the term.
 	pub(crate) async fn extract_querying_terms(
 		&self,
 		stk: &mut Stk,
 		ctx: &FrozenContext,
 		opt: &Options,
 		query_string: String,
+		mo: &MatchesOperator,
 	) -> Result<QueryTerms> {
-		let tokens = self
-			.analyzer
-			.generate_tokens(stk, ctx, opt, FilteringStage::Querying, query_string)
-			.await?;
-
-		let mut unique_terms: Vec<&str> = Vec::new();
-		let mut unique_tokens = HashSet::new();
-		for token in tokens.list() {
-			if unique_tokens.insert(token) {
-				unique_terms.push(tokens.get_token_string(token)?);
+		let expansion = TermExpansion::from(mo);
+		let mut collector = QueryTermsCollector::default();
+		let Some(clauses) = query::parse(&query_string)? else {
+			let tokens = self
+				.analyzer
+				.generate_tokens(stk, ctx, opt, FilteringStage::Querying, query_string)
+				.await?;
+			for token in tokens.list() {
+				collector.add(&tokens, token)?;
+			}
+			return self.resolve_query_terms(ctx, collector, Vec::new(), expansion).await;
+		};
+		let mut kinds = Vec::with_capacity(clauses.len());
+		for c in clauses {
+			let kind = match c.text {
+				ClauseText::Term(t) => ClauseKind::Terms(
+					self.clause_positions(stk, ctx, opt, &mut collector, t).await?,
+				),
+				ClauseText::Phrase(p) => {
+					let positions = self.clause_positions(stk, ctx, opt, &mut collector, p).await?;
+					if positions.len() == 1 {
+						ClauseKind::Terms(positions)
+					} else {
+						ClauseKind::Phrase(positions)
+					}
+				}
+				ClauseText::Near(l, r, d) => ClauseKind::Near(
+					self.clause_positions(stk, ctx, opt, &mut collector, l).await?,
+					self.clause_positions(stk, ctx, opt, &mut collector, r).await?,
+					d,
+				),
+			};
+			// The clause only contained ignored terms (stop words, for instance)
+			if kind.is_empty() {
+				continue;
+			}
+			if kind.requires_positions() && !self.highlighting {
+				bail!(Error::InvalidMatchesQuery(
+					"Phrase and NEAR queries require a full-text index with HIGHLIGHTS".to_string()
+				));
 			}
+			kinds.push((c.occur, kind));
 		}
+		self.resolve_query_terms(ctx, collector, kinds, expansion).await
+	}
 
+	async fn clause_positions(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		collector: &mut QueryTermsCollector,
+		text: String,
+	) -> Result<Vec<ClausePosition>> {
+		let tokens =
+			self.analyzer.generate_tokens(stk, ctx, opt, FilteringStage::Querying, text).await?;
+		collector.positions(&tokens)
+	}
+
+	/// Retrieves the documents of the collected terms, then the documents
+	/// matching each clause. The compacted bitmap fetches are batched via
+	/// `tx.getm()` to reduce KV round trips (one batch instead of N sequential
+	/// gets).
+	async fn resolve_query_terms(
+		&self,
+		ctx: &FrozenContext,
+		collector: QueryTermsCollector,
+		kinds: Vec<(Occur, ClauseKind)>,
+		expansion: TermExpansion,
+	) -> Result<QueryTerms> {
 		let tx = ctx.tx();
 
+		// Expand the query terms to the matching terms of the index
+		let expanded = self.expand_terms(&tx, &collector.terms, expansion).await?;
+		let mut unique_terms: Vec<&str> = Vec::new();
+		let mut unique_indexes: HashMap<&str, usize> = HashMap::new();
+		let mut expanded_indexes: Vec<Vec<usize>> = Vec::with_capacity(expanded.len());
+		for terms in &expanded {
+			let mut indexes = Vec::with_capacity(terms.len());
+			for term in terms {
+				let i = *unique_indexes.entry(term.as_str()).or_insert_with(|| {
+					unique_terms.push(term.as_str());
+					unique_terms.len() - 1
+				});
+				indexes.push(i);
+			}
+			expanded_indexes.push(indexes);
+		}
+
 		// Phase 1: Collect deltas for each term (sequential range scans)
 		let mut all_deltas: Vec<HashMap<DocId, i64>> = Vec::with_capacity(unique_terms.len());
 		for term in &unique_terms {
@@ -421,8 +648,7 @@ impl FullTextIndex {
 		let bitmaps: Vec<Option<RoaringTreemap>> = tx.getm(bitmap_keys, None).await?;
 
 		// Phase 3: Merge deltas into bitmaps
-		let mut docs = Vec::with_capacity(unique_terms.len());
-		let mut has_unknown_terms = false;
+		let mut index_docs = Vec::with_capacity(unique_terms.len());
 		for (bitmap, deltas) in bitmaps.into_iter().zip(all_deltas.iter()) {
 			let mut doc_set = bitmap.unwrap_or_default();
 			for (doc_id, delta) in deltas {
@@ -436,23 +662,189 @@ impl FullTextIndex {
 					Ordering::Equal => {}
 				}
 			}
-			if doc_set.is_empty() {
-				if !has_unknown_terms {
-					has_unknown_terms = true;
-				}
-				docs.push(None);
-			} else {
-				docs.push(Some(doc_set));
+			index_docs.push((!doc_set.is_empty()).then_some(doc_set));
+		}
+
+		// Phase 4: Unite the documents of the matching terms of each query term
+		let mut terms = Vec::with_capacity(expanded.len());
+		let mut has_unknown_terms = false;
+		for (((term, len), expansions), indexes) in
+			collector.terms.into_iter().zip(expanded).zip(expanded_indexes)
+		{
+			let docs = Self::union_operation(
+				&indexes.iter().map(|i| index_docs[*i].as_ref()).collect::<Vec<_>>(),
+			);
+			if docs.is_none() {
+				has_unknown_terms = true;
 			}
+			let expansions = expansions
+				.into_iter()
+				.zip(indexes)
+				.filter(|(_, i)| index_docs[*i].is_some())
+				.map(|(t, _)| t)
+				.collect();
+			terms.push(QueryTerm {
+				term,
+				len,
+				expansions,
+				docs,
+			});
+		}
+
+		// Phase 5: Compute the documents matching each clause
+		let mut clauses = Vec::with_capacity(kinds.len());
+		for (occur, kind) in kinds {
+			let docs = self.clause_docs(&tx, &terms, &kind).await?;
+			clauses.push(QueryClause {
+				occur,
+				kind,
+				docs,
+			});
 		}
 
 		Ok(QueryTerms {
-			tokens,
-			docs,
+			terms,
+			clauses,
+			expansion,
 			has_unknown_terms,
 		})
 	}
 
+	/// Expands each query term to the terms of the index it matches. Exact
+	/// terms are not expanded. A `PREFIX` expansion scans the terms starting
+	/// with each query term, while a `FUZZY` expansion scans the whole term
+	/// dictionary once and compares every term to the query terms.
+	async fn expand_terms(
+		&self,
+		tx: &Transaction,
+		terms: &[(String, u32)],
+		expansion: TermExpansion,
+	) -> Result<Vec<Vec<String>>> {
+		if expansion.is_exact() {
+			return Ok(terms.iter().map(|(t, _)| vec![t.clone()]).collect());
+		}
+		let mut expanded = vec![Vec::new(); terms.len()];
+		if expansion.fuzzy.is_none() {
+			for ((term, _), e) in terms.iter().zip(expanded.iter_mut()) {
+				self.scan_terms(tx, term, |t| e.push(t.to_owned())).await?;
+			}
+		} else {
+			self.scan_terms(tx, "", |t| {
+				for ((term, _), e) in terms.iter().zip(expanded.iter_mut()) {
+					if expansion.accepts(term, t) {
+						e.push(t.to_owned());
+					}
+				}
+			})
+			.await?;
+		}
+		Ok(expanded)
+	}
+
+	/// Scans the term dictionary, calling `f` once for each distinct term
+	/// starting with the given prefix. The keys are read in batches, and the
+	/// scan skips the remaining keys of the last term of each batch.
+	async fn scan_terms(
+		&self,
+		tx: &Transaction,
+		prefix: &str,
+		mut f: impl FnMut(&str),
+	) -> Result<()> {
+		let (mut beg, end) = self.ikb.new_td_prefix_range(prefix)?;
+		let mut last: Option<String> = None;
+		loop {
+			let keys = tx.keys(beg..end.clone(), TERMS_BATCH_SIZE, 0, None).await?;
+			for k in &keys {
+				let term = Td::decode_term(k)?;
+				if last.as_ref() != Some(&term) {
+					f(&term);
+					last = Some(term);
+				}
+			}
+			match &last {
+				Some(term) if keys.len() == TERMS_BATCH_SIZE as usize => {
+					beg = self.ikb.new_td_term_end(term)?;
+				}
+				_ => return Ok(()),
+			}
+		}
+	}
+
+	/// Computes the documents matching a clause. The candidates contain a
+	/// term of every position of the clause. For phrases and NEAR clauses,
+	/// the word positions of each candidate are then verified.
+	async fn clause_docs(
+		&self,
+		tx: &Transaction,
+		terms: &[QueryTerm],
+		kind: &ClauseKind,
+	) -> Result<Option<RoaringTreemap>> {
+		let positions: Vec<Option<RoaringTreemap>> = kind
+			.positions()
+			.map(|p| {
+				Self::union_operation(
+					&p.iter().map(|t| terms[*t].docs.as_ref()).collect::<Vec<_>>(),
+				)
+			})
+			.collect();
+		let Some(candidates) =
+			Self::intersection_operation(&positions.iter().map(Option::as_ref).collect::<Vec<_>>())
+		else {
+			return Ok(None);
+		};
+		if !kind.requires_positions() {
+			return Ok(Some(candidates));
+		}
+		let mut docs = RoaringTreemap::new();
+		for doc_id in candidates {
+			let offsets = self.get_clause_offsets(tx, terms, kind, doc_id).await?;
+			if !kind.occurrences(&offsets).is_empty() {
+				docs.insert(doc_id);
+			}
+		}
+		Ok((!docs.is_empty()).then_some(docs))
+	}
+
+	/// Reads the offsets of the terms of a clause in a document
+	async fn get_clause_offsets(
+		&self,
+		tx: &Transaction,
+		terms: &[QueryTerm],
+		kind: &ClauseKind,
+		doc_id: DocId,
+	) -> Result<HashMap<usize, Vec<Offset>>> {
+		let mut offsets = HashMap::new();
+		for t in kind.positions().flatten() {
+			if offsets.contains_key(t) {
+				continue;
+			}
+			let o = self.get_term_offsets(tx, doc_id, &terms[*t]).await?;
+			if !o.is_empty() {
+				offsets.insert(*t, o);
+			}
+		}
+		Ok(offsets)
+	}
+
+	/// Reads the offsets of the matching terms of a query term in a document
+	async fn get_term_offsets(
+		&self,
+		tx: &Transaction,
+		doc_id: DocId,
+		term: &QueryTerm,
+	) -> Result<Vec<Offset>> {
+		let mut offsets = Vec::new();
+		for e in &term.expansions {
+			if let Some(td) = self.get_term_document(tx, doc_id, e).await? {
+				offsets.extend(td.o);
+			}
+		}
+		if term.expansions.len() > 1 {
+			offsets.sort_by_key(|o| (o.index, o.start));
+		}
+		Ok(offsets)
+	}
+
 	pub(in crate::idx) async fn matches_value(
 		&self,
 		stk: &mut Stk,
@@ -464,10 +856,23 @@ impl FullTextIndex {
 	) -> Result<bool> {
 		let mut tks = vec![];
 		self.analyzer.analyze_value(stk, ctx, opt, val, FilteringStage::Indexing, &mut tks).await?;
-		match bo {
-			BooleanOperator::And => qt.matches_and(&tks),
-			BooleanOperator::Or => qt.matches_or(&tks),
+		if qt.clauses.is_empty() {
+			return match bo {
+				BooleanOperator::And => qt.matches_and(&tks),
+				BooleanOperator::Or => qt.matches_or(&tks),
+			};
+		}
+		// Evaluate the clauses against the offsets of the analyzed value
+		let (_, value_offsets) = Analyzer::extract_offsets(&tks)?;
+		let mut offsets: HashMap<usize, Vec<Offset>> = HashMap::new();
+		for (i, t) in qt.terms.iter().enumerate() {
+			for (term, o) in &value_offsets {
+				if qt.expansion.accepts(&t.term, term) {
+					offsets.entry(i).or_default().extend(o.iter().cloned());
+				}
+			}
 		}
+		Ok(qt.matches_clauses(bo, |c| !c.kind.occurrences(&offsets).is_empty()))
 	}
 
 	async fn append_term_docs_delta(
@@ -577,9 +982,14 @@ impl FullTextIndex {
 		bo: BooleanOperator,
 	) -> Option<FullTextHitsIterator> {
 		// Execute the operation depending on the operator
-		let hits = match bo {
-			BooleanOperator::And => Self::intersection_operation(&qt.docs),
-			BooleanOperator::Or => Self::union_operation(&qt.docs),
+		let hits = if qt.clauses.is_empty() {
+			let docs: Vec<_> = qt.terms.iter().map(|t| t.docs.as_ref()).collect();
+			match bo {
+				BooleanOperator::And => Self::intersection_operation(&docs),
+				BooleanOperator::Or => Self::union_operation(&docs),
+			}
+		} else {
+			Self::clauses_operation(&qt.clauses, bo)
 		};
 
 		// Create and return an iterator if we have matching documents
@@ -593,14 +1003,14 @@ impl FullTextIndex {
 		None
 	}
 
-	fn intersection_operation(docs: &[Option<RoaringTreemap>]) -> Option<RoaringTreemap> {
+	fn intersection_operation(docs: &[Option<&RoaringTreemap>]) -> Option<RoaringTreemap> {
 		// Early return for empty input
 		if docs.is_empty() {
 			return None;
 		}
 
 		// Collect only the "Some" variants
-		let mut valid_docs: Vec<&RoaringTreemap> = docs.iter().flatten().collect();
+		let mut valid_docs: Vec<&RoaringTreemap> = docs.iter().flatten().copied().collect();
 
 		// If any term has no documents, the intersection is empty
 		if docs.len() != valid_docs.len() {
@@ -634,9 +1044,9 @@ impl FullTextIndex {
 		}
 	}
 
-	fn union_operation(docs: &[Option<RoaringTreemap>]) -> Option<RoaringTreemap> {
+	fn union_operation(docs: &[Option<&RoaringTreemap>]) -> Option<RoaringTreemap> {
 		// Convert docs to an iterator
-		let mut docs = docs.iter().flatten();
+		let mut docs = docs.iter().flatten().copied();
 
 		// Start with the first set
 		if let Some(mut result) = docs.next().cloned() {
@@ -651,6 +1061,37 @@ impl FullTextIndex {
 		}
 	}
 
+	/// Combines the documents of the clauses of a structured query. The
+	/// required clauses (and the other clauses when the operator is AND) are
+	/// intersected, otherwise the other clauses are united. The documents of
+	/// the excluded clauses are then removed.
+	fn clauses_operation(clauses: &[QueryClause], bo: BooleanOperator) -> Option<RoaringTreemap> {
+		let mut required = Vec::new();
+		let mut optional = Vec::new();
+		for c in clauses {
+			match (c.occur, bo) {
+				(Occur::Must, _) | (Occur::Should, BooleanOperator::And) => {
+					required.push(c.docs.as_ref())
+				}
+				(Occur::Should, BooleanOperator::Or) => optional.push(c.docs.as_ref()),
+				(Occur::MustNot, _) => {}
+			}
+		}
+		let mut hits = if required.is_empty() {
+			Self::union_operation(&optional)?
+		} else {
+			Self::intersection_operation(&required)?
+		};
+		for c in clauses {
+			if c.occur == Occur::MustNot
+				&& let Some(docs) = &c.docs
+			{
+				hits -= docs;
+			}
+		}
+		Some(hits)
+	}
+
 	pub(crate) async fn get_doc_id(
 		&self,
 		tx: &Transaction,
@@ -757,18 +1198,49 @@ impl FullTextIndex {
 		let doc_id = self.get_doc_id(tx, thg).await?;
 		if let Some(doc_id) = doc_id {
 			let mut hl = Highlighter::new(hlp, idiom, doc);
-			for tk in qt.tokens.list() {
-				if let Some(td) =
-					self.get_term_document(tx, doc_id, qt.tokens.get_token_string(tk)?).await?
-				{
-					hl.highlight(tk.get_char_len(), td.o);
-				}
+			for (len, o) in self.matching_offsets(tx, qt, doc_id).await? {
+				hl.highlight(len, o);
 			}
 			return hl.try_into();
 		}
 		Ok(Value::None)
 	}
 
+	/// Collects the offsets of the query terms in a document, along with the
+	/// length of their term. The offsets of the excluded clauses are ignored,
+	/// and phrases and NEAR clauses only return the offsets of their
+	/// occurrences.
+	async fn matching_offsets(
+		&self,
+		tx: &Transaction,
+		qt: &QueryTerms,
+		doc_id: DocId,
+	) -> Result<Vec<(u32, Vec<Offset>)>> {
+		let mut res = Vec::new();
+		if qt.clauses.is_empty() {
+			for t in &qt.terms {
+				for e in &t.expansions {
+					if let Some(td) = self.get_term_document(tx, doc_id, e).await? {
+						res.push((t.len, td.o));
+					}
+				}
+			}
+			return Ok(res);
+		}
+		for c in &qt.clauses {
+			if c.occur == Occur::MustNot || !c.docs.as_ref().is_some_and(|d| d.contains(doc_id)) {
+				continue;
+			}
+			let offsets = self.get_clause_offsets(tx, &qt.terms, &c.kind, doc_id).await?;
+			for occurrence in c.kind.occurrences(&offsets) {
+				for (t, o) in occurrence {
+					res.push((qt.terms[t].len, vec![o]));
+				}
+			}
+		}
+		Ok(res)
+	}
+
 	async fn get_term_document(
 		&self,
 		tx: &Transaction,
@@ -789,12 +1261,8 @@ impl FullTextIndex {
 		let doc_id = self.get_doc_id(tx, thg).await?;
 		if let Some(doc_id) = doc_id {
 			let mut or = Offseter::new(partial);
-			for tk in qt.tokens.list() {
-				let term = qt.tokens.get_token_string(tk)?;
-				let o = self.get_term_document(tx, doc_id, term).await?;
-				if let Some(o) = o {
-					or.highlight(tk.get_char_len(), o.o);
-				}
+			for (len, o) in self.matching_offsets(tx, qt, doc_id).await? {
+				or.highlight(len, o);
 			}
 			return Ok(or.into());
 		}
@@ -895,23 +1363,61 @@ impl Scorer {
 		doc_id: DocId,
 	) -> Result<Score> {
 		let mut sc = 0.0;
-		let tl = qt.tokens.list();
 		let doc_length = fti.get_doc_length(tx, doc_id).await?.unwrap_or(0) as f64;
-		for (i, d) in qt.docs.iter().enumerate() {
-			if let Some(docs) = d
+		if qt.clauses.is_empty() {
+			for t in &qt.terms {
+				sc += self.term_score(fti, tx, t, doc_id, doc_length).await?;
+			}
+			return Ok(sc as f32);
+		}
+		let mut scored = HashSet::new();
+		for c in &qt.clauses {
+			if c.occur == Occur::MustNot {
+				continue;
+			}
+			if let ClauseKind::Terms(positions) = &c.kind {
+				for t in positions.iter().flatten() {
+					if scored.insert(*t) {
+						sc += self.term_score(fti, tx, &qt.terms[*t], doc_id, doc_length).await?;
+					}
+				}
+			} else if let Some(docs) = &c.docs
 				&& docs.contains(doc_id)
-				&& let Some(token) = tl.get(i)
 			{
-				let term = qt.tokens.get_token_string(token)?;
-				let td = fti.get_term_document(tx, doc_id, term).await?;
-				if let Some(td) = td {
-					sc += self.compute_bm25_score(td.f as f64, docs.len() as f64, doc_length)
-				}
+				// A phrase is scored as a single term, whose frequency is the
+				// number of occurrences of the phrase in the document
+				let offsets = fti.get_clause_offsets(tx, &qt.terms, &c.kind, doc_id).await?;
+				let f = c.kind.occurrences(&offsets).len();
+				sc += self.compute_bm25_score(f as f64, docs.len() as f64, doc_length);
 			}
 		}
 		Ok(sc as f32)
 	}
 
+	async fn term_score(
+		&self,
+		fti: &FullTextIndex,
+		tx: &Transaction,
+		t: &QueryTerm,
+		doc_id: DocId,
+		doc_length: f64,
+	) -> Result<f64> {
+		let Some(docs) = &t.docs else {
+			return Ok(0.0);
+		};
+		if !docs.contains(doc_id) {
+			return Ok(0.0);
+		}
+		// The frequencies of the matching terms are summed
+		let mut f = 0;
+		for e in &t.expansions {
+			if let Some(td) = fti.get_term_document(tx, doc_id, e).await? {
+				f += td.f;
+			}
+		}
+		Ok(self.compute_bm25_score(f as f64, docs.len() as f64, doc_length))
+	}
+
 	/// Computes the Okapi-BM25 score for a single term.
 	///
 	/// Variant:
@@ -967,11 +1473,12 @@ mod tests {
 	use tokio::time::sleep;
 	use uuid::Uuid;
 
-	use super::{FullTextIndex, TermDocument};
+	use super::{FullTextIndex, TermDocument, TermExpansion};
 	use crate::catalog::{DatabaseId, FullTextParams, IndexId, NamespaceId};
 	use crate::cnf::dynamic::DynamicConfiguration;
 	use crate::ctx::{Context, FrozenContext};
 	use crate::dbs::Options;
+	use crate::expr::operator::Fuzziness;
 	use crate::expr::statements::DefineAnalyzerStatement;
 	use crate::idx::IndexKeyBase;
 	use crate::idx::ft::offset::Offset;
@@ -1137,30 +1644,35 @@ mod tests {
 							start: 44,
 							gen_start: 44,
 							end: 47,
+							pos: 6,
 						},
 						Offset {
 							index: 3,
 							start: 42,
 							gen_start: 42,
 							end: 45,
+							pos: 7,
 						},
 						Offset {
 							index: 16,
 							start: 4,
 							gen_start: 4,
 							end: 7,
+							pos: 1,
 						},
 						Offset {
 							index: 18,
 							start: 8,
 							gen_start: 8,
 							end: 11,
+							pos: 2,
 						},
 						Offset {
 							index: 19,
 							start: 59,
 							gen_start: 59,
 							end: 62,
+							pos: 12,
 						},
 					],
 				}
@@ -1318,4 +1830,37 @@ mod tests {
 		let scorer_after = test.fti.new_scorer(&read_ctx).await.unwrap();
 		assert!(scorer_after.is_some(), "scorer should still exist after compaction");
 	}
+
+	#[test]
+	fn term_expansion() {
+		let fuzzy = TermExpansion {
+			fuzzy: Some(Fuzziness::Auto),
+			prefix: false,
+		};
+		assert!(fuzzy.accepts("hello", "hello"));
+		assert!(fuzzy.accepts("hello", "helo"));
+		assert!(fuzzy.accepts("hello", "hlelo"));
+		assert!(!fuzzy.accepts("hello", "help"));
+		assert!(!fuzzy.accepts("at", "it"));
+		assert!(fuzzy.accepts("brwon", "brown"));
+		assert!(!fuzzy.accepts("brow", "browser"));
+
+		let prefix = TermExpansion {
+			fuzzy: None,
+			prefix: true,
+		};
+		assert!(prefix.accepts("bro", "brown"));
+		assert!(prefix.accepts("brown", "brown"));
+		assert!(!prefix.accepts("brw", "brown"));
+		assert!(!prefix.accepts("rown", "brown"));
+
+		let both = TermExpansion {
+			fuzzy: Some(Fuzziness::Distance(1)),
+			prefix: true,
+		};
+		assert!(both.accepts("bro", "browser"));
+		assert!(both.accepts("brw", "browser"));
+		assert!(both.accepts("qiuck", "quickly"));
+		assert!(!both.accepts("xyz", "browser"));
+	}
 }
@@ -2,7 +2,7 @@ use revision::revisioned;
 
 use crate::idx::ft::Position;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct Offset {
 	pub(super) index: u32,
@@ -12,15 +12,31 @@ pub(crate) struct Offset {
 	pub(super) gen_start: Position,
 	// End position of the original term
 	pub(super) end: Position,
+	// Position of the word within the value, used by phrase and NEAR queries.
+	// Offsets indexed before this field existed have no position, and never
+	// match a phrase until the index is rebuilt.
+	#[revision(start = 2, default_fn = "default_pos")]
+	pub(super) pos: Position,
 }
 
 impl Offset {
-	pub(crate) fn new(index: u32, start: Position, gen_start: Position, end: Position) -> Self {
+	pub(crate) fn new(
+		index: u32,
+		start: Position,
+		gen_start: Position,
+		end: Position,
+		pos: Position,
+	) -> Self {
 		Self {
 			index,
 			start,
 			gen_start,
 			end,
+			pos,
 		}
 	}
+
+	fn default_pos(_revision: u16) -> Result<Position, revision::Error> {
+		Ok(Position::MAX)
+	}
 }
@@ -0,0 +1,480 @@
+//! Parses the query string of the `@@` MATCHES operator.
+//!
+//! A query string is a list of clauses separated by whitespace:
+//! - `word`: a term, combined with the other terms using the boolean operator
+//!   of the MATCHES operator (`AND` by default).
+//! - `"quick brown fox"`: a phrase, whose terms must be found next to each
+//!   other, in that order.
+//! - `quick NEAR/3 fox`: both sides must be found with at most 3 words between
+//!   them, in any order. `NEAR` alone allows up to 10 words. The sides can be
+//!   words or phrases, and `a NEAR b NEAR c` is the same as `a NEAR b` and `b
+//!   NEAR c`.
+//! - `+word`, `+"a phrase"`: the clause is required, whatever the boolean
+//!   operator is.
+//! - `-word`, `-"a phrase"`: documents matching the clause are excluded.
+//!
+//! A query string which does not use any of this syntax is a plain list of
+//! terms, and is analyzed as a whole.
+
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use roaring::RoaringTreemap;
+
+use crate::err::Error;
+use crate::idx::ft::Position;
+use crate::idx::ft::offset::Offset;
+
+/// The number of words allowed between the two sides of a `NEAR` without an
+/// explicit distance.
+const DEFAULT_NEAR_DISTANCE: u32 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Occur {
+	/// The clause is combined with the boolean operator of the query
+	Should,
+	/// The clause is required (`+`)
+	Must,
+	/// The clause is excluded (`-`)
+	MustNot,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum ClauseText {
+	/// A single word
+	Term(String),
+	/// A quoted phrase
+	Phrase(String),
+	/// Two words or phrases, with the maximum number of words between them
+	Near(String, String, u32),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Clause {
+	pub(super) occur: Occur,
+	pub(super) text: ClauseText,
+}
+
+/// The terms found at the same position of a clause, as indexes in the list of
+/// the query terms. Several terms share a position when a filter (synonyms,
+/// n-grams...) generates them from the same word, and any of them satisfies
+/// the position.
+pub(super) type ClausePosition = Vec<usize>;
+
+/// An occurrence of a clause in a document: the offsets of its words, along
+/// with the index of their term.
+pub(super) type Occurrence = Vec<(usize, Offset)>;
+
+/// A clause of a structured query, once analyzed
+pub(super) struct QueryClause {
+	pub(super) occur: Occur,
+	pub(super) kind: ClauseKind,
+	/// The documents matching the clause
+	pub(super) docs: Option<RoaringTreemap>,
+}
+
+pub(super) enum ClauseKind {
+	/// Every position must be found, anywhere in the document
+	Terms(Vec<ClausePosition>),
+	/// The positions must be found next to each other, in order
+	Phrase(Vec<ClausePosition>),
+	/// Two phrases separated by at most the given number of words
+	Near(Vec<ClausePosition>, Vec<ClausePosition>, u32),
+}
+
+impl ClauseKind {
+	pub(super) fn is_empty(&self) -> bool {
+		match self {
+			Self::Terms(p) | Self::Phrase(p) => p.is_empty(),
+			Self::Near(l, r, _) => l.is_empty() || r.is_empty(),
+		}
+	}
+
+	/// Phrases and NEAR clauses are verified against the word positions
+	/// stored in the offsets.
+	pub(super) fn requires_positions(&self) -> bool {
+		matches!(self, Self::Phrase(_) | Self::Near(..))
+	}
+
+	pub(super) fn positions(&self) -> impl Iterator<Item = &ClausePosition> {
+		let (p, r): (&[ClausePosition], &[ClausePosition]) = match self {
+			Self::Terms(p) | Self::Phrase(p) => (p.as_slice(), [].as_slice()),
+			Self::Near(l, r, _) => (l.as_slice(), r.as_slice()),
+		};
+		p.iter().chain(r.iter())
+	}
+
+	/// Returns the occurrences of the clause, given the offsets of its terms.
+	/// Each occurrence of a `Terms` clause is a single offset.
+	pub(super) fn occurrences(&self, offsets: &HashMap<usize, Vec<Offset>>) -> Vec<Occurrence> {
+		match self {
+			Self::Terms(positions) => {
+				let found = |p: &ClausePosition| {
+					p.iter().any(|t| offsets.get(t).is_some_and(|o| !o.is_empty()))
+				};
+				if !positions.iter().all(found) {
+					return vec![];
+				}
+				let mut res = Vec::new();
+				for t in positions.iter().flatten() {
+					if let Some(os) = offsets.get(t) {
+						res.extend(os.iter().map(|o| vec![(*t, o.clone())]));
+					}
+				}
+				res
+			}
+			Self::Phrase(positions) => Self::phrase_occurrences(positions, offsets),
+			Self::Near(left, right, distance) => {
+				let left = Self::phrase_occurrences(left, offsets);
+				let right = Self::phrase_occurrences(right, offsets);
+				let mut res = Vec::new();
+				for l in &left {
+					for r in &right {
+						if Self::is_near(l, r, *distance) {
+							res.push(l.iter().chain(r).cloned().collect());
+						}
+					}
+				}
+				res
+			}
+		}
+	}
+
+	fn phrase_occurrences(
+		positions: &[ClausePosition],
+		offsets: &HashMap<usize, Vec<Offset>>,
+	) -> Vec<Occurrence> {
+		// Index the offsets of each position by value index and word position
+		let lookup: Vec<HashMap<(u32, Position), (usize, &Offset)>> = positions
+			.iter()
+			.map(|p| {
+				let mut m = HashMap::new();
+				for t in p {
+					for o in offsets.get(t).into_iter().flatten() {
+						m.entry((o.index, o.pos)).or_insert((*t, o));
+					}
+				}
+				m
+			})
+			.collect();
+		let Some((first, rest)) = lookup.split_first() else {
+			return vec![];
+		};
+		let mut res = Vec::new();
+		for (&(index, pos), &(t, o)) in first {
+			let mut occurrence = vec![(t, o.clone())];
+			for (i, l) in rest.iter().enumerate() {
+				// Offsets without a position never match
+				let Some(next) = pos.checked_add(i as Position + 1) else {
+					break;
+				};
+				let Some(&(t, o)) = l.get(&(index, next)) else {
+					break;
+				};
+				occurrence.push((t, o.clone()));
+			}
+			if occurrence.len() == positions.len() {
+				res.push(occurrence);
+			}
+		}
+		res
+	}
+
+	fn is_near(l: &Occurrence, r: &Occurrence, distance: u32) -> bool {
+		let (Some((_, l_first)), Some((_, l_last)), Some((_, r_first)), Some((_, r_last))) =
+			(l.first(), l.last(), r.first(), r.last())
+		else {
+			return false;
+		};
+		if l_first.index != r_first.index {
+			return false;
+		}
+		if l_last.pos < r_first.pos {
+			r_first.pos - l_last.pos - 1 <= distance
+		} else if r_last.pos < l_first.pos {
+			l_first.pos - r_last.pos - 1 <= distance
+		} else {
+			false
+		}
+	}
+}
+
+/// A word or a phrase
+enum Atom {
+	Word(String),
+	Phrase(String),
+}
+
+impl Atom {
+	fn into_text(self) -> String {
+		match self {
+			Self::Word(s) | Self::Phrase(s) => s,
+		}
+	}
+}
+
+struct QueryParser<'a> {
+	query: &'a str,
+	pos: usize,
+}
+
+/// Parses a query string. Returns `None` if the query string does not use
+/// any query syntax, in which case it is a plain list of terms.
+pub(super) fn parse(query: &str) -> Result<Option<Vec<Clause>>> {
+	let mut parser = QueryParser {
+		query,
+		pos: 0,
+	};
+	let mut clauses = Vec::new();
+	let mut structured = false;
+	while let Some(occur) = parser.next_occur() {
+		if occur != Occur::Should {
+			structured = true;
+		}
+		let Some(mut left) = parser.next_atom()? else {
+			break;
+		};
+		let mut near = false;
+		while let Some(distance) = parser.next_near()? {
+			let Some(right) = parser.next_atom()? else {
+				bail!(Error::InvalidMatchesQuery(
+					"NEAR must be followed by a word or a phrase".into()
+				));
+			};
+			let (l, r) = (left.into_text(), right.into_text());
+			left = Atom::Word(r.clone());
+			clauses.push(Clause {
+				occur,
+				text: ClauseText::Near(l, r, distance),
+			});
+			near = true;
+		}
+		if near {
+			structured = true;
+			continue;
+		}
+		let text = match left {
+			Atom::Word(w) => ClauseText::Term(w),
+			Atom::Phrase(p) => {
+				structured = true;
+				ClauseText::Phrase(p)
+			}
+		};
+		clauses.push(Clause {
+			occur,
+			text,
+		});
+	}
+	Ok(structured.then_some(clauses))
+}
+
+impl<'a> QueryParser<'a> {
+	fn rest(&self) -> &'a str {
+		&self.query[self.pos..]
+	}
+
+	fn skip_whitespaces(&mut self) {
+		let rest = self.rest();
+		self.pos += rest.len() - rest.trim_start().len();
+	}
+
+	/// Reads the optional `+` or `-` prefix of a clause. Returns `None` at the
+	/// end of the query string.
+	fn next_occur(&mut self) -> Option<Occur> {
+		self.skip_whitespaces();
+		let mut chars = self.rest().chars();
+		let occur = match chars.next()? {
+			'+' => Occur::Must,
+			'-' => Occur::MustNot,
+			_ => return Some(Occur::Should),
+		};
+		// A lone `+` or `-` is a word on its own
+		match chars.next() {
+			Some(c) if !c.is_whitespace() => {
+				self.pos += 1;
+				Some(occur)
+			}
+			_ => Some(Occur::Should),
+		}
+	}
+
+	fn next_atom(&mut self) -> Result<Option<Atom>> {
+		self.skip_whitespaces();
+		let rest = self.rest();
+		if rest.is_empty() {
+			return Ok(None);
+		}
+		if let Some(phrase) = rest.strip_prefix('"') {
+			let Some(end) = phrase.find('"') else {
+				bail!(Error::InvalidMatchesQuery(format!("Unterminated phrase: {rest}")));
+			};
+			let phrase = phrase[..end].to_owned();
+			self.pos += end + 2;
+			return Ok(Some(Atom::Phrase(phrase)));
+		}
+		let word = self.next_word();
+		if Self::near_distance(word)?.is_some() {
+			bail!(Error::InvalidMatchesQuery("NEAR must be preceded by a word or a phrase".into()));
+		}
+		let word = word.to_owned();
+		self.pos += word.len();
+		Ok(Some(Atom::Word(word)))
+	}
+
+	fn next_word(&self) -> &'a str {
+		let rest = self.rest();
+		let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+		&rest[..end]
+	}
+
+	/// Consumes a `NEAR` or `NEAR/n` operator, and returns its distance
+	fn next_near(&mut self) -> Result<Option<u32>> {
+		self.skip_whitespaces();
+		let word = self.next_word();
+		let distance = Self::near_distance(word)?;
+		if distance.is_some() {
+			self.pos += word.len();
+		}
+		Ok(distance)
+	}
+
+	fn near_distance(word: &str) -> Result<Option<u32>> {
+		let Some(distance) = word.strip_prefix("NEAR") else {
+			return Ok(None);
+		};
+		if distance.is_empty() {
+			return Ok(Some(DEFAULT_NEAR_DISTANCE));
+		}
+		let Some(distance) = distance.strip_prefix('/') else {
+			// A word starting with NEAR, like NEARBY
+			return Ok(None);
+		};
+		match distance.parse() {
+			Ok(d) => Ok(Some(d)),
+			Err(_) => bail!(Error::InvalidMatchesQuery(format!("Invalid NEAR distance: {word}"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use super::{Clause, ClauseKind, ClauseText, Occur, parse};
+	use crate::idx::ft::offset::Offset;
+
+	fn clause(occur: Occur, text: ClauseText) -> Clause {
+		Clause {
+			occur,
+			text,
+		}
+	}
+
+	#[test]
+	fn test_plain_query() {
+		assert_eq!(parse("hello world").unwrap(), None);
+		assert_eq!(parse("  co-locate a+b + - NEARBY  ").unwrap(), None);
+		assert_eq!(parse("").unwrap(), None);
+	}
+
+	#[test]
+	fn test_phrase() {
+		assert_eq!(
+			parse(r#"blog "quick brown fox""#).unwrap(),
+			Some(vec![
+				clause(Occur::Should, ClauseText::Term("blog".into())),
+				clause(Occur::Should, ClauseText::Phrase("quick brown fox".into())),
+			])
+		);
+		assert!(parse(r#"blog "quick brown"#).is_err());
+	}
+
+	#[test]
+	fn test_required_and_excluded() {
+		assert_eq!(
+			parse(r#"+blog -"blog post" -draft"#).unwrap(),
+			Some(vec![
+				clause(Occur::Must, ClauseText::Term("blog".into())),
+				clause(Occur::MustNot, ClauseText::Phrase("blog post".into())),
+				clause(Occur::MustNot, ClauseText::Term("draft".into())),
+			])
+		);
+	}
+
+	#[test]
+	fn test_near() {
+		assert_eq!(
+			parse(r#"+lorem NEAR/3 "sit amet" NEAR dolor"#).unwrap(),
+			Some(vec![
+				clause(Occur::Must, ClauseText::Near("lorem".into(), "sit amet".into(), 3)),
+				clause(Occur::Must, ClauseText::Near("sit amet".into(), "dolor".into(), 10)),
+			])
+		);
+		assert!(parse("NEAR/2 lorem").is_err());
+		assert!(parse("lorem NEAR/2").is_err());
+		assert!(parse("lorem NEAR/x ipsum").is_err());
+	}
+
+	/// Offsets of the terms of "the quick brown fox jumps over the lazy dog",
+	/// with the terms: 0: the, 1: quick, 2: brown, 3: fox, 4: lazy, 5: dog
+	fn offsets() -> HashMap<usize, Vec<Offset>> {
+		let o = |pos: u32| Offset::new(0, pos, pos, pos + 1, pos);
+		HashMap::from([
+			(0, vec![o(0), o(6)]),
+			(1, vec![o(1)]),
+			(2, vec![o(2)]),
+			(3, vec![o(3)]),
+			(4, vec![o(7)]),
+			(5, vec![o(8)]),
+		])
+	}
+
+	#[test]
+	fn test_phrase_occurrences() {
+		let offsets = offsets();
+		let phrase = ClauseKind::Phrase(vec![vec![1], vec![2], vec![3]]);
+		let occurrences = phrase.occurrences(&offsets);
+		assert_eq!(occurrences.len(), 1);
+		assert_eq!(occurrences[0].iter().map(|(t, _)| *t).collect::<Vec<_>>(), vec![1, 2, 3]);
+		// Wrong order
+		assert!(ClauseKind::Phrase(vec![vec![2], vec![1]]).occurrences(&offsets).is_empty());
+		// Any term of a position matches
+		assert_eq!(ClauseKind::Phrase(vec![vec![0], vec![4, 1]]).occurrences(&offsets).len(), 2);
+		// Offsets without a position never match
+		let legacy = HashMap::from([
+			(1, vec![Offset::new(0, 4, 4, 9, u32::MAX)]),
+			(2, vec![Offset::new(0, 10, 10, 15, u32::MAX)]),
+		]);
+		assert!(ClauseKind::Phrase(vec![vec![1], vec![2]]).occurrences(&legacy).is_empty());
+	}
+
+	#[test]
+	fn test_near_occurrences() {
+		let offsets = offsets();
+		// 5 words between quick and lazy
+		assert_eq!(
+			ClauseKind::Near(vec![vec![1]], vec![vec![4]], 5).occurrences(&offsets).len(),
+			1
+		);
+		assert!(ClauseKind::Near(vec![vec![1]], vec![vec![4]], 4).occurrences(&offsets).is_empty());
+		// In any order
+		assert_eq!(
+			ClauseKind::Near(vec![vec![5]], vec![vec![3]], 4).occurrences(&offsets).len(),
+			1
+		);
+		// A phrase on one side
+		assert_eq!(
+			ClauseKind::Near(vec![vec![4], vec![5]], vec![vec![2], vec![3]], 3)
+				.occurrences(&offsets)
+				.len(),
+			1
+		);
+	}
+
+	#[test]
+	fn test_terms_occurrences() {
+		let offsets = offsets();
+		assert_eq!(ClauseKind::Terms(vec![vec![0], vec![5]]).occurrences(&offsets).len(), 3);
+		assert!(ClauseKind::Terms(vec![vec![0], vec![9]]).occurrences(&offsets).is_empty());
+	}
+}
@@ -1,5 +1,5 @@
 //! This module applies index mutations for a single document across different
-//! index types (UNIQUE, regular, search, fulltext, Hnsw). Index keys are
+//! index types (UNIQUE, regular, search, fulltext, Hnsw, IVF-PQ, spatial). Index keys are
 //! constructed via key::index and field values are encoded using
 //! key::value::Array.
 //!
@@ -13,6 +13,8 @@
 //! - Numeric predicates need a single probe/range in the index; per-variant fan-out is no longer
 //!   required.
 
+use std::collections::BTreeSet;
+
 use anyhow::{Result, bail};
 use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
@@ -20,7 +22,8 @@ use uuid::Uuid;
 
 use crate::catalog::providers::TableProvider;
 use crate::catalog::{
-	DatabaseId, FullTextParams, HnswParams, Index, IndexDefinition, NamespaceId, TableId,
+	DatabaseId, FullTextParams, HnswParams, Index, IndexDefinition, IvfPqParams, NamespaceId,
+	TableId,
 };
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
@@ -29,6 +32,7 @@ use crate::expr::{Cond, Part};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::fulltext::FullTextIndex;
 use crate::idx::planner::iterators::IndexCountThingIterator;
+use crate::idx::spatial;
 use crate::idx::trees::store::IndexStores;
 use crate::key;
 use crate::key::index::iu::IndexCountKey;
@@ -112,6 +116,8 @@ impl<'a> IndexOperation<'a> {
 			Index::FullText(p) => self.index_fulltext(stk, p, require_compaction).await,
 			Index::Hnsw(p) => self.index_hnsw(p, require_compaction).await,
 			Index::Count(c) => self.index_count(stk, c.as_ref(), require_compaction).await,
+			Index::Spatial => self.index_spatial().await,
+			Index::IvfPq(p) => self.index_ivfpq(p, require_compaction).await,
 		}
 	}
 
@@ -294,6 +300,27 @@ impl<'a> IndexOperation<'a> {
 		Ok(())
 	}
 
+	/// Advances the training of an IVF-PQ index.
+	///
+	/// Every compaction runs in its own transaction, so while documents
+	/// remain to be encoded, another compaction is queued to continue.
+	pub(crate) async fn index_ivfpq_compaction(
+		ctx: &FrozenContext,
+		ixs: &IndexStores,
+		ikb: &IndexKeyBase,
+		ix: &IndexDefinition,
+		p: &IvfPqParams,
+		nid: Uuid,
+	) -> Result<()> {
+		let tx = ctx.tx();
+		if let Some(tb) = tx.get_tb(ikb.ns(), ikb.db(), ikb.table(), None).await?
+			&& ixs.get_index_ivfpq(ikb.ns(), ikb.db(), tb.table_id, ix, p).await.train(ctx).await?
+		{
+			IndexOperation::compaction_trigger(ikb, &tx, nid).await?;
+		}
+		Ok(())
+	}
+
 	pub(crate) async fn index_count_compaction(ikb: &IndexKeyBase, tx: &Transaction) -> Result<()> {
 		IndexCountThingIterator::new(ikb.ns(), ikb.db(), ikb.table(), ikb.index())?
 			.compaction(ikb, tx)
@@ -370,6 +397,8 @@ impl<'a> IndexOperation<'a> {
 	/// Compaction helps optimize index performance after many mutations.
 	/// For full-text indexes it consolidates term frequency and document
 	/// length data; for HNSW indexes it processes pending vector operations;
+	/// for IVF-PQ indexes it trains the model once enough vectors are indexed,
+	/// then encodes the documents in batches;
 	/// for count indexes it reconciles count tracking entries.
 	pub(crate) async fn compaction_trigger(
 		ikb: &IndexKeyBase,
@@ -381,6 +410,52 @@ impl<'a> IndexOperation<'a> {
 		Ok(())
 	}
 
+	/// Maintains the geohash cells of a spatial index.
+	///
+	/// Every cell lists all the cells of the record, so the cells are only
+	/// rewritten when they differ between the old and the new geometries, and
+	/// updating a document without moving it is free.
+	async fn index_spatial(&mut self) -> Result<()> {
+		let old_cells = match self.o.take() {
+			Some(o) => spatial::record_cells(&o),
+			None => BTreeSet::new(),
+		};
+		let new_cells = match self.n.take() {
+			Some(n) => spatial::record_cells(&n),
+			None => BTreeSet::new(),
+		};
+		if old_cells == new_cells {
+			return Ok(());
+		}
+		let txn = self.ctx.tx();
+		// Delete the cells which are no longer covered
+		for cell in old_cells.difference(&new_cells) {
+			let key = self.get_spatial_index_key(cell);
+			txn.del(&key).await?;
+		}
+		// Store every cell, listing the new cells of the record
+		let entry = spatial::SpatialEntry {
+			rid: self.rid.clone(),
+			cells: new_cells.iter().cloned().collect(),
+		};
+		for cell in &new_cells {
+			let key = self.get_spatial_index_key(cell);
+			txn.set(&key, &entry).await?;
+		}
+		Ok(())
+	}
+
+	fn get_spatial_index_key<'b>(&'b self, cell: &'b str) -> key::index::gc::Gc<'b> {
+		key::index::gc::Gc::new(
+			self.ns,
+			self.db,
+			&self.ix.table_name,
+			self.ix.index_id,
+			cell,
+			&self.rid.key,
+		)
+	}
+
 	async fn index_hnsw(&mut self, p: &HnswParams, require_compaction: &mut bool) -> Result<()> {
 		let hnsw = self
 			.ctx
@@ -395,6 +470,19 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_ivfpq(&mut self, p: &IvfPqParams, require_compaction: &mut bool) -> Result<()> {
+		let ivfpq = self
+			.ctx
+			.get_index_stores()
+			.get_index_ivfpq(self.ns, self.db, self.tb, self.ix, p)
+			.await;
+		// The compaction trains the model while the index is untrained
+		if ivfpq.index(self.ctx, &self.rid.key, self.o.take(), self.n.take()).await? {
+			*require_compaction = true;
+		}
+		Ok(())
+	}
 }
 
 /// Extract from the given document, the values required by the index and put
@@ -2,6 +2,7 @@ pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
 pub(super) mod seqdocids;
+pub(crate) mod spatial;
 pub mod trees;
 
 use std::borrow::Cow;
@@ -34,6 +35,11 @@ use crate::key::index::ig::IndexAppending;
 use crate::key::index::ii::Ii;
 use crate::key::index::ip::Ip;
 use crate::key::index::is::Is;
+use crate::key::index::qb::Qb;
+use crate::key::index::qc::Qc;
+use crate::key::index::ql::Ql;
+use crate::key::index::qs::Qs;
+use crate::key::index::qv::Qv;
 use crate::key::index::td::{Td, TdRoot};
 use crate::key::index::tt::Tt;
 use crate::key::root::ic::IndexCompactionKey;
@@ -124,6 +130,46 @@ impl IndexKeyBase {
 		Hs::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
 	}
 
+	fn new_qs_key(&self) -> Qs<'_> {
+		Qs::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
+	}
+
+	fn new_qc_key(&self, list: u16) -> Qc<'_> {
+		Qc::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, list)
+	}
+
+	fn new_qc_range(&self) -> Result<Range<Key>> {
+		Qc::new_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
+	}
+
+	fn new_qb_key(&self, sub: u16, code: u8) -> Qb<'_> {
+		Qb::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, sub, code)
+	}
+
+	fn new_qb_range(&self) -> Result<Range<Key>> {
+		Qb::new_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
+	}
+
+	fn new_qv_key(&self, doc_id: DocId) -> Qv<'_> {
+		Qv::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, doc_id)
+	}
+
+	fn new_qv_range(&self) -> Result<Range<Key>> {
+		Qv::new_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
+	}
+
+	fn new_ql_key(&self, list: u16, doc_id: DocId) -> Ql<'_> {
+		Ql::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, list, doc_id)
+	}
+
+	fn new_ql_list_range(&self, list: u16) -> Result<Range<Key>> {
+		Ql::new_list_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix, list)
+	}
+
+	fn new_ql_range(&self) -> Result<Range<Key>> {
+		Ql::new_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
+	}
+
 	fn new_ii_key(&self, doc_id: DocId) -> Ii<'_> {
 		Ii::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, doc_id)
 	}
@@ -179,6 +225,14 @@ impl IndexKeyBase {
 		Td::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, term, doc_id)
 	}
 
+	fn new_td_prefix_range(&self, prefix: &str) -> Result<(Key, Key)> {
+		Td::prefix_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix, prefix)
+	}
+
+	fn new_td_term_end(&self, term: &str) -> Result<Key> {
+		Td::term_end(self.0.ns, self.0.db, &self.0.tb, self.0.ix, term)
+	}
+
 	fn new_tt<'a>(
 		&'a self,
 		term: &'a str,
@@ -0,0 +1,401 @@
+//! A cost model for choosing between access paths, based on the statistics
+//! collected by `ANALYZE TABLE`.
+//!
+//! Costs are expressed in units of one record read during a table scan. Reading
+//! a record through an index costs more, as every index entry requires a
+//! separate lookup of the record. The number of records returned by an index
+//! scan is estimated from the selectivity of its conditions, assuming that the
+//! columns of a compound index are independent.
+//!
+//! Tables which have not been analyzed have no cost model, and the planners
+//! fall back to their heuristics.
+
+use std::ops::{Add, Bound};
+
+use crate::catalog::{FieldStatistics, Index, IndexDefinition, TableStatistics};
+use crate::expr::Idiom;
+use crate::val::Value;
+
+/// The cost of reading a record during a table scan
+const TABLE_ROW_COST: f64 = 1.0;
+/// The cost of reading a record through an index
+const INDEX_ROW_COST: f64 = 4.0;
+/// The fixed cost of starting an index scan
+const INDEX_SCAN_COST: f64 = 10.0;
+/// The lowest cost of a table scan, which is the cost of a unique index lookup
+const MIN_TABLE_SCAN_COST: f64 = INDEX_SCAN_COST + INDEX_ROW_COST;
+/// The selectivity of an equality condition on a field without statistics
+const DEFAULT_EQUALITY_SELECTIVITY: f64 = 0.005;
+/// The selectivity of each bound of a range on a field without statistics
+const DEFAULT_RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+
+/// The estimated number of records read by an access path, and its cost
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Estimate {
+	pub(crate) rows: f64,
+	pub(crate) cost: f64,
+}
+
+impl Add for Estimate {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		Self {
+			rows: self.rows + other.rows,
+			cost: self.cost + other.cost,
+		}
+	}
+}
+
+/// Estimates the cost of access paths on a single table
+pub(crate) struct CostModel<'a> {
+	stats: &'a TableStatistics,
+}
+
+impl<'a> CostModel<'a> {
+	pub(crate) fn new(stats: &'a TableStatistics) -> Self {
+		Self {
+			stats,
+		}
+	}
+
+	/// The estimate of a full table scan
+	///
+	/// The statistics of a small table quickly go stale as the table grows,
+	/// so a table scan is never estimated to be cheaper than an equality
+	/// lookup on a unique index.
+	pub(crate) fn table_scan(&self) -> Estimate {
+		let rows = self.stats.records as f64;
+		Estimate {
+			rows,
+			cost: (rows * TABLE_ROW_COST).max(MIN_TABLE_SCAN_COST),
+		}
+	}
+
+	/// The estimate of an index scan with equality conditions on the leading
+	/// columns of the index, and an optional range on the following column.
+	pub(crate) fn index_scan(
+		&self,
+		ix: &IndexDefinition,
+		prefix: &[Value],
+		range: Option<(Bound<&Value>, Bound<&Value>)>,
+	) -> Estimate {
+		let records = self.stats.records as f64;
+		let rows = if matches!(ix.index, Index::Uniq)
+			&& range.is_none()
+			&& prefix.len() == ix.cols.len()
+			&& !prefix.iter().any(Value::is_nullish)
+		{
+			// A unique index returns at most one record
+			records.min(1.0)
+		} else {
+			let mut selectivity = 1.0;
+			for (col, v) in ix.cols.iter().zip(prefix) {
+				selectivity *= self.equality_selectivity(self.field(col), v);
+			}
+			if let Some((from, to)) = range
+				&& let Some(col) = ix.cols.get(prefix.len())
+			{
+				selectivity *= self.range_selectivity(self.field(col), from, to);
+			}
+			(records * selectivity).clamp(0.0, records)
+		};
+		Estimate {
+			rows,
+			cost: INDEX_SCAN_COST + rows * INDEX_ROW_COST,
+		}
+	}
+
+	/// The estimate of an equality lookup on the leading column of an index,
+	/// for a value which is not known at plan time, such as a correlated key.
+	pub(crate) fn index_lookup(&self, ix: &IndexDefinition) -> Estimate {
+		match ix.cols.first() {
+			Some(_) if matches!(ix.index, Index::Uniq) && ix.cols.len() == 1 => {
+				let rows = (self.stats.records as f64).min(1.0);
+				Estimate {
+					rows,
+					cost: INDEX_SCAN_COST + rows * INDEX_ROW_COST,
+				}
+			}
+			Some(col) => self.key_lookup(&col.to_raw_string()),
+			None => self.table_scan(),
+		}
+	}
+
+	/// The estimate of reading the records which hold a value in a field,
+	/// through a key range such as an index or the edges of a record, for a
+	/// value which is not known at plan time.
+	pub(crate) fn key_lookup(&self, field: &str) -> Estimate {
+		let records = self.stats.records as f64;
+		let rows = match self.stats.field(field) {
+			// Assume the records are evenly spread over the distinct values
+			Some(fs) => records / fs.distinct.max(1) as f64,
+			None => records * DEFAULT_EQUALITY_SELECTIVITY,
+		};
+		Estimate {
+			rows,
+			cost: INDEX_SCAN_COST + rows * INDEX_ROW_COST,
+		}
+	}
+
+	/// The statistics of an indexed column, if it was analyzed
+	fn field(&self, col: &Idiom) -> Option<&FieldStatistics> {
+		self.stats.field(&col.to_raw_string())
+	}
+
+	/// The fraction of records where a field is equal to a value
+	fn equality_selectivity(&self, fs: Option<&FieldStatistics>, v: &Value) -> f64 {
+		let Some(fs) = fs else {
+			return DEFAULT_EQUALITY_SELECTIVITY;
+		};
+		let sampled = self.stats.sampled as f64;
+		if sampled == 0.0 {
+			return 0.0;
+		}
+		if v.is_nullish() {
+			return fs.nulls as f64 / sampled;
+		}
+		if let Some((_, count)) = fs.common.iter().find(|(c, _)| c == v) {
+			return *count as f64 / sampled;
+		}
+		// Spread the remaining records evenly over the remaining values
+		let common: u64 = fs.common.iter().map(|(_, c)| c).sum();
+		let rest = (self.stats.sampled.saturating_sub(fs.nulls + common)) as f64 / sampled;
+		let distinct = fs.distinct.saturating_sub(fs.common.len() as u64).max(1);
+		rest / distinct as f64
+	}
+
+	/// The fraction of records where a field is within a range
+	fn range_selectivity(
+		&self,
+		fs: Option<&FieldStatistics>,
+		from: Bound<&Value>,
+		to: Bound<&Value>,
+	) -> f64 {
+		let Some(fs) = fs else {
+			let bounds = [&from, &to].iter().filter(|b| !matches!(b, Bound::Unbounded)).count();
+			return DEFAULT_RANGE_SELECTIVITY.powi(bounds as i32);
+		};
+		let sampled = self.stats.sampled as f64;
+		if sampled == 0.0 {
+			return 0.0;
+		}
+		let contains = |v: &Value| {
+			(match from {
+				Bound::Included(f) => v >= f,
+				Bound::Excluded(f) => v > f,
+				Bound::Unbounded => true,
+			}) && (match to {
+				Bound::Included(t) => v <= t,
+				Bound::Excluded(t) => v < t,
+				Bound::Unbounded => true,
+			})
+		};
+		// The most common values are counted exactly
+		let common: u64 = fs.common.iter().map(|(_, c)| c).sum();
+		let matched: u64 = fs.common.iter().filter(|(v, _)| contains(v)).map(|(_, c)| c).sum();
+		// The remaining values are located within the histogram
+		let rest = (self.stats.sampled.saturating_sub(fs.nulls + common)) as f64 / sampled;
+		let fraction = match fs.histogram.as_slice() {
+			[] => 0.0,
+			[v] => {
+				if contains(v) {
+					1.0
+				} else {
+					0.0
+				}
+			}
+			h => {
+				let lower = match from {
+					Bound::Included(v) | Bound::Excluded(v) => histogram_position(h, v),
+					Bound::Unbounded => 0.0,
+				};
+				let upper = match to {
+					Bound::Included(v) | Bound::Excluded(v) => histogram_position(h, v),
+					Bound::Unbounded => 1.0,
+				};
+				(upper - lower).max(0.0)
+			}
+		};
+		matched as f64 / sampled + rest * fraction
+	}
+}
+
+/// The fraction of the values of an equi-depth histogram which are below a
+/// value, interpolating within a bucket when its bounds are numbers.
+fn histogram_position(histogram: &[Value], v: &Value) -> f64 {
+	let last = histogram.len() - 1;
+	if v <= &histogram[0] {
+		return 0.0;
+	}
+	if v >= &histogram[last] {
+		return 1.0;
+	}
+	// Find the bucket containing the value
+	let i = histogram.partition_point(|x| x <= v) - 1;
+	let fraction = match (&histogram[i], v, &histogram[i + 1]) {
+		(Value::Number(lo), Value::Number(x), Value::Number(hi)) => {
+			let (lo, x, hi) = (lo.to_float(), x.to_float(), hi.to_float());
+			if hi > lo {
+				((x - lo) / (hi - lo)).clamp(0.0, 1.0)
+			} else {
+				0.5
+			}
+		}
+		_ => 0.5,
+	};
+	(i as f64 + fraction) / last as f64
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::catalog::IndexId;
+	use crate::val::TableName;
+
+	fn stats(records: u64, fields: Vec<(&str, Vec<Value>)>) -> TableStatistics {
+		let sampled = match fields.first() {
+			Some((_, v)) => v.len() as u64,
+			None => 0,
+		};
+		TableStatistics {
+			records,
+			sampled,
+			fields: fields
+				.into_iter()
+				.map(|(f, v)| FieldStatistics::from_sample(f.to_string(), v, records))
+				.collect(),
+			..Default::default()
+		}
+	}
+
+	fn index(cols: &[&str], index: Index) -> IndexDefinition {
+		IndexDefinition {
+			index_id: IndexId(1),
+			name: "ix".to_string(),
+			table_name: TableName::from("test"),
+			cols: cols.iter().map(|c| crate::syn::idiom(c).unwrap().into()).collect(),
+			index,
+			comment: None,
+			prepare_remove: false,
+		}
+	}
+
+	#[test]
+	fn field_statistics() {
+		// 100 records: 10 without a value, 40 with status 'active', and 50 unique values
+		let mut values = vec![Value::None; 10];
+		values.extend(vec![Value::from("active"); 40]);
+		values.extend((0..50).map(|i| Value::from(format!("s{i:02}"))));
+		let fs = FieldStatistics::from_sample("status".to_string(), values, 100);
+		assert_eq!(fs.nulls, 10);
+		assert_eq!(fs.common, vec![(Value::from("active"), 40)]);
+		assert_eq!(fs.distinct, 51);
+		assert_eq!(fs.histogram.len(), 33);
+		assert_eq!(fs.histogram.first(), Some(&Value::from("s00")));
+		assert_eq!(fs.histogram.last(), Some(&Value::from("s49")));
+	}
+
+	#[test]
+	fn distinct_from_sample() {
+		// Every sampled value is unique, so the field is likely to be unique
+		let values: Vec<Value> = (0..100).map(Value::from).collect();
+		let fs = FieldStatistics::from_sample("id".to_string(), values, 10_000);
+		assert_eq!(fs.distinct, 10_000);
+		// Every value is repeated, so all of them were likely seen
+		let values: Vec<Value> = (0..100).map(|i| Value::from(i % 5)).collect();
+		let fs = FieldStatistics::from_sample("kind".to_string(), values, 10_000);
+		assert_eq!(fs.distinct, 5);
+	}
+
+	#[test]
+	fn equality_estimates() {
+		let mut status = vec![Value::from("active"); 90];
+		status.extend((0..10).map(|i| Value::from(format!("s{i}"))));
+		let st = stats(10_000, vec![("status", status)]);
+		let cost = CostModel::new(&st);
+		let ix = index(&["status"], Index::Idx);
+		// A common value is estimated from its frequency
+		let e = cost.index_scan(&ix, &[Value::from("active")], None);
+		assert!((e.rows - 9_000.0).abs() < 1e-6, "{}", e.rows);
+		assert!(e.cost > cost.table_scan().cost);
+		// A rare value shares the remaining records with the other rare values
+		let e = cost.index_scan(&ix, &[Value::from("s3")], None);
+		assert!(e.rows < 100.0);
+		assert!(e.cost < cost.table_scan().cost);
+		// A unique index returns a single record
+		let ix = index(&["status"], Index::Uniq);
+		assert_eq!(cost.index_scan(&ix, &[Value::from("s3")], None).rows, 1.0);
+	}
+
+	#[test]
+	fn range_estimates() {
+		let age = (0..1000).map(|i| Value::from(i % 100)).collect();
+		let st = stats(1000, vec![("age", age)]);
+		let cost = CostModel::new(&st);
+		let ix = index(&["age"], Index::Idx);
+		let v = Value::from(90);
+		let e = cost.index_scan(&ix, &[], Some((Bound::Excluded(&v), Bound::Unbounded)));
+		assert!((80.0..=120.0).contains(&e.rows), "{}", e.rows);
+		let e = cost.index_scan(&ix, &[], Some((Bound::Unbounded, Bound::Excluded(&v))));
+		assert!((850.0..=950.0).contains(&e.rows), "{}", e.rows);
+		assert!(e.cost > cost.table_scan().cost);
+	}
+
+	#[test]
+	fn lookup_estimates() {
+		let post = (0..1000).map(|i| Value::from(i % 50)).collect();
+		let st = stats(1000, vec![("post", post)]);
+		let cost = CostModel::new(&st);
+		// An unknown value matches the average number of records per value
+		let e = cost.index_lookup(&index(&["post"], Index::Idx));
+		assert!((e.rows - 20.0).abs() < 1e-6, "{}", e.rows);
+		assert!(e.cost < cost.table_scan().cost);
+		assert_eq!(cost.index_lookup(&index(&["post"], Index::Uniq)).rows, 1.0);
+	}
+
+	#[test]
+	fn compound_estimates() {
+		let a = (0..1000).map(|i| Value::from(i % 10)).collect();
+		let b = (0..1000).map(|i| Value::from(i % 1000)).collect();
+		let st = stats(1000, vec![("a", a), ("b", b)]);
+		let cost = CostModel::new(&st);
+		let ix = index(&["a", "b"], Index::Idx);
+		let e = cost.index_scan(&ix, &[Value::from(1)], None);
+		assert!((e.rows - 100.0).abs() < 1e-6, "{}", e.rows);
+		let e = cost.index_scan(&ix, &[Value::from(1), Value::from(11)], None);
+		assert!(e.rows <= 1.0, "{}", e.rows);
+	}
+
+	#[test]
+	fn small_table_estimates() {
+		// A table analyzed while it held only a few records
+		let email = (0..3).map(Value::from).collect();
+		let st = stats(3, vec![("email", email)]);
+		let cost = CostModel::new(&st);
+		// A unique equality lookup is never more expensive than a table scan
+		let ix = index(&["email"], Index::Uniq);
+		let e = cost.index_scan(&ix, &[Value::from(1)], None);
+		assert!(e.cost <= cost.table_scan().cost, "{} > {}", e.cost, cost.table_scan().cost);
+		let e = cost.index_lookup(&ix);
+		assert!(e.cost <= cost.table_scan().cost, "{} > {}", e.cost, cost.table_scan().cost);
+		// Nor is it for an empty table
+		let st = stats(0, vec![]);
+		let cost = CostModel::new(&st);
+		let e = cost.index_scan(&ix, &[Value::from(1)], None);
+		assert!(e.cost <= cost.table_scan().cost, "{} > {}", e.cost, cost.table_scan().cost);
+	}
+
+	#[test]
+	fn without_field_statistics() {
+		let st = TableStatistics {
+			records: 1000,
+			sampled: 1000,
+			..Default::default()
+		};
+		let cost = CostModel::new(&st);
+		let ix = index(&["missing"], Index::Idx);
+		let e = cost.index_scan(&ix, &[Value::from(1)], None);
+		assert!((e.rows - 5.0).abs() < 1e-6, "{}", e.rows);
+	}
+}
@@ -56,6 +56,7 @@ impl MutVisitor for CountLimitRewriter {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Analyze(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_)
 			| Expr::Info(_)
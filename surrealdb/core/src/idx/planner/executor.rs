@@ -22,7 +22,7 @@ use crate::idx::planner::iterators::{
 	IndexCountThingIterator, IndexEqualThingIterator, IndexJoinThingIterator,
 	IndexRangeReverseThingIterator, IndexRangeThingIterator, IndexUnionThingIterator,
 	IteratorRecord, IteratorRef, KnnIterator, KnnIteratorResult, MatchesThingIterator,
-	RecordIterator, UniqueEqualThingIterator, UniqueJoinThingIterator,
+	RecordIterator, SpatialThingIterator, UniqueEqualThingIterator, UniqueJoinThingIterator,
 	UniqueRangeReverseThingIterator, UniqueRangeThingIterator, UniqueUnionThingIterator,
 };
 use crate::idx::planner::knn::{KnnBruteForceResult, KnnPriorityList};
@@ -31,6 +31,7 @@ use crate::idx::planner::plan::{IndexOperator, IndexOption, RangeValue};
 use crate::idx::planner::tree::{IdiomPosition, IndexReference};
 use crate::idx::planner::{IterationStage, ScanDirection};
 use crate::idx::trees::store::hnsw::SharedHnswIndex;
+use crate::idx::trees::store::ivfpq::SharedIvfPqIndex;
 use crate::val::{Array, Number, Object, RecordId, TableName, Value};
 
 pub(super) type KnnBruteForceEntry = (KnnPriorityList, Idiom, Arc<Vec<Number>>, Distance);
@@ -66,6 +67,7 @@ pub(crate) struct QueryExecutor(Arc<InnerQueryExecutor>);
 enum PerIndexReferenceIndex {
 	FullText(FullTextIndex),
 	Hnsw(SharedHnswIndex),
+	IvfPq(SharedIvfPqIndex),
 }
 
 /// Execution-time entry per expression. Associates a parsed expression with
@@ -73,6 +75,7 @@ enum PerIndexReferenceIndex {
 enum PerExpressionEntry {
 	FullText(FullTextEntry),
 	Hnsw(HnswEntry),
+	IvfPq(IvfPqEntry),
 	KnnBruteForce(KnnBruteForceEntry),
 }
 
@@ -252,6 +255,55 @@ impl InnerQueryExecutor {
 						}
 					}
 				}
+				Index::IvfPq(p) => {
+					if let IndexOperator::Ann(a, k, probes) = io.op() {
+						let qi = match ir_map.entry(index_reference.clone()) {
+							Entry::Occupied(e) => {
+								if let PerIndexReferenceIndex::IvfPq(qi) = e.get() {
+									Some(qi.clone())
+								} else {
+									None
+								}
+							}
+							Entry::Vacant(e) => {
+								let tb = ctx
+									.tx()
+									.expect_tb(
+										doc_ctx.ns.namespace_id,
+										doc_ctx.db.database_id,
+										&index_reference.table_name,
+									)
+									.await?;
+								let qi = ctx
+									.get_index_stores()
+									.get_index_ivfpq(
+										doc_ctx.ns.namespace_id,
+										doc_ctx.db.database_id,
+										tb.table_id,
+										index_reference,
+										p,
+									)
+									.await;
+								e.insert(PerIndexReferenceIndex::IvfPq(qi.clone()));
+								Some(qi)
+							}
+						};
+						if let Some(qi) = qi {
+							let qe = IvfPqEntry::new(
+								stk,
+								ctx,
+								opt,
+								qi,
+								a,
+								*k,
+								*probes,
+								knn_condition.clone(),
+							)
+							.await?;
+							exp_entries.insert(exp, PerExpressionEntry::IvfPq(qe));
+						}
+					}
+				}
 				_ => {}
 			}
 		}
@@ -394,6 +446,8 @@ impl QueryExecutor {
 				..
 			} => self.new_fulltext_index_iterator(irf, io.clone()).await,
 			Index::Hnsw(_) => Ok(self.new_hnsw_index_ann_iterator(irf)),
+			Index::IvfPq(_) => Ok(self.new_ivfpq_index_ann_iterator(irf)),
+			Index::Spatial => Self::new_spatial_index_iterator(ns, db, irf, io),
 		}
 	}
 
@@ -658,6 +712,29 @@ impl QueryExecutor {
 		None
 	}
 
+	fn new_ivfpq_index_ann_iterator(&self, ir: IteratorRef) -> Option<RecordIterator> {
+		if let Some(IteratorEntry::Single(Some(exp), ..)) = self.0.it_entries.get(ir)
+			&& let Some(PerExpressionEntry::IvfPq(qe)) = self.0.exp_entries.get(exp)
+		{
+			let it = KnnIterator::new(ir, qe.res.clone());
+			return Some(RecordIterator::Knn(it));
+		}
+		None
+	}
+
+	fn new_spatial_index_iterator(
+		ns: NamespaceId,
+		db: DatabaseId,
+		irf: IteratorRef,
+		io: &IndexOption,
+	) -> Result<Option<RecordIterator>> {
+		if let IndexOperator::Spatial(region) = io.op() {
+			let it = SpatialThingIterator::new(irf, ns, db, io.index_reference(), region)?;
+			return Ok(Some(RecordIterator::Spatial(it)));
+		}
+		Ok(None)
+	}
+
 	async fn build_iterators(
 		&self,
 		ns: NamespaceId,
@@ -855,7 +932,7 @@ impl FullTextEntry {
 		io: IndexOption,
 	) -> Result<Option<Self>> {
 		if let Matches(qs, mo) = io.op() {
-			let qt = fti.extract_querying_terms(stk, ctx, opt, qs.to_owned()).await?;
+			let qt = fti.extract_querying_terms(stk, ctx, opt, qs.to_owned(), mo).await?;
 			let scorer = fti.new_scorer(ctx).await?;
 			Ok(Some(Self(Arc::new(InnerFullTextEntry {
 				bo: mo.operator,
@@ -893,3 +970,28 @@ impl HnswEntry {
 		})
 	}
 }
+
+#[derive(Clone)]
+pub(super) struct IvfPqEntry {
+	res: VecDeque<KnnIteratorResult>,
+}
+
+impl IvfPqEntry {
+	#[expect(clippy::too_many_arguments)]
+	async fn new(
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		q: SharedIvfPqIndex,
+		v: &[Number],
+		n: u32,
+		probes: u32,
+		cond: Option<Arc<Cond>>,
+	) -> Result<Self> {
+		let cond_filter = cond.map(|cond| (opt, cond));
+		let res = q.knn_search(ctx, stk, v, n as usize, probes as usize, cond_filter).await?;
+		Ok(Self {
+			res,
+		})
+	}
+}
@@ -16,6 +16,7 @@ use crate::idx::ft::fulltext::FullTextHitsIterator;
 use crate::idx::planner::plan::RangeValue;
 use crate::idx::planner::tree::IndexReference;
 use crate::idx::seqdocids::DocId;
+use crate::idx::spatial::{SpatialIterator, SpatialRegion};
 use crate::key::index::Index;
 use crate::key::index::iu::IndexCountKey;
 use crate::kvs::{KVKey, Key, Transaction, Val};
@@ -131,6 +132,7 @@ pub(crate) enum RecordIterator {
 	UniqueJoin(Box<UniqueJoinThingIterator>),
 	FullTextMatches(MatchesThingIterator<FullTextHitsIterator>),
 	Knn(KnnIterator),
+	Spatial(SpatialThingIterator),
 }
 
 impl RecordIterator {
@@ -156,6 +158,7 @@ impl RecordIterator {
 			Self::UniqueUnion(i) => i.next_batch(ctx, txn, size).await,
 			Self::FullTextMatches(i) => i.next_batch(ctx, txn, size).await,
 			Self::Knn(i) => i.next_batch(ctx, size).await,
+			Self::Spatial(i) => i.next_batch(txn, size).await,
 			Self::IndexJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::IndexCount(_) => {
@@ -185,6 +188,7 @@ impl RecordIterator {
 			Self::UniqueUnion(i) => i.next_count(ctx, txn, size).await,
 			Self::FullTextMatches(i) => i.next_count(ctx, txn, size).await,
 			Self::Knn(i) => i.next_count(ctx, size).await,
+			Self::Spatial(i) => i.next_count(txn, size).await,
 			Self::IndexJoin(i) => Box::pin(i.next_count(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_count(ctx, txn, size)).await,
 			Self::IndexCount(i) => i.next_count(ctx, txn, size).await,
@@ -1848,6 +1852,41 @@ impl KnnIterator {
 	}
 }
 
+/// Iterates over the candidates of a spatial index. The records are
+/// de-duplicated, as a geometry may be stored in several cells.
+pub(crate) struct SpatialThingIterator {
+	irf: IteratorRef,
+	inner: SpatialIterator,
+}
+
+impl SpatialThingIterator {
+	pub(super) fn new(
+		irf: IteratorRef,
+		ns: NamespaceId,
+		db: DatabaseId,
+		ix: &IndexDefinition,
+		region: &SpatialRegion,
+	) -> Result<Self> {
+		Ok(Self {
+			irf,
+			inner: SpatialIterator::new(ns, db, ix, region)?,
+		})
+	}
+
+	async fn next_batch<B: IteratorBatch>(&mut self, tx: &Transaction, limit: u32) -> Result<B> {
+		let res = self.inner.next_batch(tx, limit).await?;
+		let mut records = B::with_capacity(res.len());
+		for rid in res {
+			records.add(IndexItemRecord::new_key(rid, self.irf.into()));
+		}
+		Ok(records)
+	}
+
+	async fn next_count(&mut self, tx: &Transaction, limit: u32) -> Result<usize> {
+		Ok(self.inner.next_batch(tx, limit).await?.len())
+	}
+}
+
 pub(crate) struct IndexCountThingIterator(Option<Range<Key>>);
 
 impl IndexCountThingIterator {
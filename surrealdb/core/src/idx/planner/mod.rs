@@ -1,3 +1,4 @@
+pub(crate) mod cost;
 pub(crate) mod count_exists_rewriter;
 pub(crate) mod executor;
 pub(crate) mod iterators;
@@ -20,6 +21,7 @@ use crate::doc::NsDbTbCtx;
 use crate::expr::order::Ordering;
 use crate::expr::with::With;
 use crate::expr::{Cond, Fields, Groups};
+use crate::idx::planner::cost::{CostModel, Estimate};
 use crate::idx::planner::executor::{InnerQueryExecutor, IteratorEntry, QueryExecutor};
 use crate::idx::planner::iterators::IteratorRef;
 use crate::idx::planner::knn::KnnBruteForceResults;
@@ -235,6 +237,9 @@ pub(crate) struct QueryPlanner {
 	ordering_indexes: Vec<IteratorRef>,
 	granted_permissions: HashMap<TableName, GrantedPermission>,
 	any_specific_permission: bool,
+	/// The estimated number of records read by each iterator, for tables
+	/// which have statistics
+	estimated_rows: HashMap<(TableName, Option<IteratorRef>), u64>,
 }
 
 impl QueryPlanner {
@@ -248,6 +253,7 @@ impl QueryPlanner {
 			ordering_indexes: vec![],
 			granted_permissions: HashMap::default(),
 			any_specific_permission: false,
+			estimated_rows: HashMap::default(),
 		}
 	}
 
@@ -286,6 +292,9 @@ impl QueryPlanner {
 		let tree = Tree::build(stk, stm_ctx, t).await?;
 
 		let is_knn = !tree.knn_expressions.is_empty();
+		// Statistics are only needed to choose between index options
+		let has_index_options =
+			!tree.index_map.options.is_empty() || !tree.index_map.compound_indexes.is_empty();
 		let mut exe = InnerQueryExecutor::new(
 			&doc_ctx,
 			stk,
@@ -297,6 +306,15 @@ impl QueryPlanner {
 			tree.knn_condition,
 		)
 		.await?;
+		let stats = if has_index_options {
+			stm_ctx
+				.ctx
+				.tx()
+				.get_tb_statistics(doc_ctx.ns.namespace_id, doc_ctx.db.database_id, t)
+				.await?
+		} else {
+			None
+		};
 		let p = PlanBuilderParameters {
 			root: tree.root,
 			gp,
@@ -307,14 +325,19 @@ impl QueryPlanner {
 			all_and: tree.all_and,
 			all_expressions_with_index: tree.all_expressions_with_index,
 			all_and_groups: tree.all_and_groups,
+			stats: stats.clone(),
 		};
+		let cost = stats.as_deref().map(CostModel::new);
+		let estimate = |e: Option<Estimate>| e.map(|e| e.rows.round() as u64);
 		match PlanBuilder::build(stm_ctx, p).await? {
 			Plan::SingleIndex(exp, io, rs) => {
 				if io.require_distinct() {
 					self.requires_distinct = true;
 				}
 				let is_order = io.is_order();
+				let rows = estimate(cost.as_ref().and_then(|c| io.estimate(c)));
 				let ir = exe.add_iterator(IteratorEntry::Single(exp, io));
+				self.add_estimate(t, Some(ir), rows);
 				self.add(doc_ctx.clone(), t.clone(), Some(ir), exe, it, rs);
 				if is_order {
 					self.ordering_indexes.push(ir);
@@ -322,21 +345,27 @@ impl QueryPlanner {
 			}
 			Plan::MultiIndex(non_range_indexes, ranges_indexes, rs) => {
 				for (exp, io) in non_range_indexes {
+					let rows = estimate(cost.as_ref().and_then(|c| io.estimate(c)));
 					let ie = IteratorEntry::Single(Some(exp), io);
 					let ir = exe.add_iterator(ie);
+					self.add_estimate(t, Some(ir), rows);
 					it.ingest(Iterable::Index(doc_ctx.clone(), t.clone(), ir, rs));
 				}
 				for (ixr, rq) in ranges_indexes {
+					let rows = estimate(cost.as_ref().map(|c| rq.estimate(c, &ixr)));
 					let ie =
 						IteratorEntry::Range(rq.exps, ixr, rq.from, rq.to, ScanDirection::Forward);
 					let ir = exe.add_iterator(ie);
+					self.add_estimate(t, Some(ir), rows);
 					it.ingest(Iterable::Index(doc_ctx.clone(), t.clone(), ir, rs));
 				}
 				self.requires_distinct = true;
 				self.add(doc_ctx.clone(), t.clone(), None, exe, it, rs);
 			}
 			Plan::SingleIndexRange(ixn, rq, keys_only, sc, is_order) => {
+				let rows = estimate(cost.as_ref().map(|c| rq.estimate(c, &ixn)));
 				let ir = exe.add_iterator(IteratorEntry::Range(rq.exps, ixn, rq.from, rq.to, sc));
+				self.add_estimate(t, Some(ir), rows);
 				if is_order {
 					self.ordering_indexes.push(ir);
 				}
@@ -346,6 +375,7 @@ impl QueryPlanner {
 				if let Some(reason) = reason {
 					self.fallbacks.push(reason);
 				}
+				self.add_estimate(t, None, estimate(cost.as_ref().map(CostModel::table_scan)));
 				self.add(doc_ctx.clone(), t.clone(), None, exe, it, rs);
 				it.ingest(Iterable::Table(doc_ctx.clone(), t.clone(), rs, sc));
 				is_table_iterator = true;
@@ -373,6 +403,19 @@ impl QueryPlanner {
 			it.ingest(Iterable::Index(doc_ctx, tb, irf, rs));
 		}
 	}
+
+	fn add_estimate(&mut self, tb: &TableName, irf: Option<IteratorRef>, rows: Option<u64>) {
+		if let Some(rows) = rows {
+			self.estimated_rows.insert((tb.clone(), irf), rows);
+		}
+	}
+
+	/// The estimated number of records read by an iterator, or by a table
+	/// scan when no iterator is given
+	pub(crate) fn estimated_rows(&self, tb: &TableName, irf: Option<IteratorRef>) -> Option<u64> {
+		self.estimated_rows.get(&(tb.clone(), irf)).copied()
+	}
+
 	pub(crate) fn has_executors(&self) -> bool {
 		!self.executors.is_empty()
 	}
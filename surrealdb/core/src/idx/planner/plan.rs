@@ -1,19 +1,22 @@
 use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::Hash;
+use std::ops::Bound;
 use std::sync::Arc;
 
 use anyhow::Result;
 use surrealdb_types::ToSql;
 
-use crate::catalog::Index;
+use crate::catalog::{Index, IndexDefinition, TableStatistics};
 use crate::expr::operator::{MatchesOperator, NearestNeighbor};
 use crate::expr::with::With;
 use crate::expr::{BinaryOperator, Expr, Idiom};
+use crate::idx::planner::cost::{CostModel, Estimate};
 use crate::idx::planner::tree::{
 	CompoundIndexes, GroupRef, IdiomCol, IdiomPosition, IndexReference, Node, WithIndexes,
 };
 use crate::idx::planner::{GrantedPermission, RecordStrategy, ScanDirection, StatementContext};
+use crate::idx::spatial::SpatialRegion;
 use crate::val::{Array, Number, Object, Value};
 
 /// The `PlanBuilder` struct represents a builder for constructing query plans.
@@ -39,6 +42,7 @@ pub(super) struct PlanBuilderParameters {
 	pub(super) all_and: bool,
 	pub(super) all_expressions_with_index: bool,
 	pub(super) all_and_groups: HashMap<GroupRef, bool>,
+	pub(super) stats: Option<Arc<TableStatistics>>,
 }
 
 impl PlanBuilder {
@@ -110,6 +114,53 @@ impl PlanBuilder {
 				}
 			}
 
+			// The first available range query (deterministic group order)
+			let mut range =
+				b.groups.into_iter().next().and_then(|(_, group)| group.take_first_range());
+
+			// The last non-range single-index option (heuristic)
+			let mut non_range = b.non_range_indexes.pop();
+
+			// When the table has statistics, only keep the cheapest option, or
+			// fall back to a table scan if it is cheaper than every option.
+			if let Some(stats) = p.stats.as_deref()
+				&& p.order_limit.is_none()
+			{
+				let cost = CostModel::new(stats);
+				let estimates = [
+					compound_index.as_ref().map(|(_, io)| io.estimate(&cost)),
+					range.as_ref().map(|(ixr, rq)| Some(rq.estimate(&cost, ixr))),
+					non_range.as_ref().map(|(_, io)| io.estimate(&cost)),
+				];
+				// Every option must be estimable
+				if !estimates.iter().any(|e| matches!(e, Some(None)))
+					&& let Some((best, e)) = estimates
+						.iter()
+						.enumerate()
+						.filter_map(|(i, e)| Some((i, (*e)??)))
+						.min_by(|a, b| a.1.cost.total_cmp(&b.1.cost))
+				{
+					let table = cost.table_scan();
+					if e.cost > table.cost && !matches!(ctx.with, Some(With::Index(_))) {
+						let reason = format!(
+							"Estimated {} records read through an index, out of {} records",
+							e.rows.round(),
+							table.rows.round()
+						);
+						return Self::table_iterator(ctx, Some(&reason), p.gp).await;
+					}
+					if best != 0 {
+						compound_index = None;
+					}
+					if best != 1 {
+						range = None;
+					}
+					if best != 2 {
+						non_range = None;
+					}
+				}
+			}
+
 			if let Some((_, io)) = compound_index {
 				// Evaluate whether we can use index-only access (no table lookups needed)
 				let record_strategy =
@@ -118,10 +169,8 @@ impl PlanBuilder {
 				return Ok(Plan::SingleIndex(None, io, record_strategy));
 			}
 
-			// Select the first available range query (deterministic group order)
-			if let Some((_, group)) = b.groups.into_iter().next()
-				&& let Some((index_reference, rq)) = group.take_first_range()
-			{
+			// Select the first available range query
+			if let Some((index_reference, rq)) = range {
 				// Evaluate the record strategy
 				let record_strategy =
 					ctx.check_record_strategy(p.all_expressions_with_index, p.gp)?;
@@ -143,9 +192,8 @@ impl PlanBuilder {
 				));
 			}
 
-			// Otherwise, pick a non-range single-index
-			// option (heuristic)
-			if let Some((e, i)) = b.non_range_indexes.pop() {
+			// Otherwise, pick the non-range single-index option
+			if let Some((e, i)) = non_range {
 				// Evaluate the record strategy
 				let record_strategy =
 					ctx.check_record_strategy(p.all_expressions_with_index, p.gp)?;
@@ -392,6 +440,28 @@ pub(super) enum Plan {
 	SingleIndexRange(IndexReference, UnionRangeQueryBuilder, RecordStrategy, ScanDirection, bool),
 }
 
+impl Plan {
+	/// Estimates the records read by this plan, and its cost.
+	/// Returns `None` if any index operator cannot be estimated.
+	pub(super) fn estimate(&self, cost: &CostModel) -> Option<Estimate> {
+		match self {
+			Plan::TableIterator(..) => Some(cost.table_scan()),
+			Plan::SingleIndex(_, io, _) => io.estimate(cost),
+			Plan::SingleIndexRange(ixr, rq, ..) => Some(rq.estimate(cost, ixr)),
+			Plan::MultiIndex(ios, ranges, _) => {
+				let mut estimate = Estimate::default();
+				for (_, io) in ios {
+					estimate = estimate + io.estimate(cost)?;
+				}
+				for (ixr, rq) in ranges {
+					estimate = estimate + rq.estimate(cost, ixr);
+				}
+				Some(estimate)
+			}
+		}
+	}
+}
+
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 pub(super) struct IndexOption {
 	/// A reference to the index definition
@@ -416,6 +486,8 @@ pub(super) enum IndexOperator {
 	/// false = ascending, true = descending
 	Order(bool),
 	Count,
+	/// Records whose geometry may intersect the region
+	Spatial(SpatialRegion),
 }
 
 impl IndexOption {
@@ -457,6 +529,44 @@ impl IndexOption {
 		self.idiom_position
 	}
 
+	/// Estimates the records read through this index option, and its cost.
+	/// Returns `None` for operators which cannot be estimated.
+	pub(super) fn estimate(&self, cost: &CostModel) -> Option<Estimate> {
+		let ix: &IndexDefinition = &self.index_reference;
+		// An array describes the values of the leading columns of the index
+		let prefix = |v: &Value| match v {
+			Value::Array(a) => a.0.clone(),
+			v => vec![v.clone()],
+		};
+		match self.op() {
+			IndexOperator::Equality(v) => Some(cost.index_scan(ix, &prefix(v), None)),
+			IndexOperator::Union(v) => match v.as_ref() {
+				Value::Array(a) => Some(
+					a.iter()
+						.map(|v| cost.index_scan(ix, &prefix(v), None))
+						.fold(Estimate::default(), |acc, e| acc + e),
+				),
+				v => Some(cost.index_scan(ix, &prefix(v), None)),
+			},
+			IndexOperator::RangePart(op, v) => {
+				let (from, to) = RangeValue::bounds([(op, v)]);
+				Some(cost.index_scan(ix, &[], Some((from.bound(), to.bound()))))
+			}
+			IndexOperator::Range(prefix, ranges) => {
+				let (from, to) = RangeValue::bounds(ranges.iter().map(|(o, v)| (o, v)));
+				Some(cost.index_scan(ix, prefix, Some((from.bound(), to.bound()))))
+			}
+			IndexOperator::Order(_) => {
+				Some(cost.index_scan(ix, &[], Some((Bound::Unbounded, Bound::Unbounded))))
+			}
+			IndexOperator::Join(_)
+			| IndexOperator::Matches(..)
+			| IndexOperator::Ann(..)
+			| IndexOperator::Count
+			| IndexOperator::Spatial(_) => None,
+		}
+	}
+
 	fn reduce_array(value: &Value) -> Value {
 		if let Value::Array(a) = value
 			&& a.len() == 1
@@ -532,6 +642,10 @@ impl IndexOption {
 					e.insert("where", Value::from(c.to_sql()));
 				}
 			}
+			IndexOperator::Spatial(r) => {
+				e.insert("operator", Value::from("Spatial"));
+				e.insert("value", Value::from(r.to_string()));
+			}
 		};
 		Value::from(e)
 	}
@@ -546,6 +660,33 @@ pub(super) struct RangeValue {
 }
 
 impl RangeValue {
+	/// Merges range conditions on a column into a lower and an upper bound.
+	fn bounds<'a>(
+		parts: impl IntoIterator<Item = (&'a BinaryOperator, &'a Arc<Value>)>,
+	) -> (Self, Self) {
+		let mut from = Self::default();
+		let mut to = Self::default();
+		for (op, v) in parts {
+			match op {
+				BinaryOperator::LessThan => to.set_to(v),
+				BinaryOperator::LessThanEqual => to.set_to_inclusive(v),
+				BinaryOperator::MoreThan => from.set_from(v),
+				BinaryOperator::MoreThanEqual => from.set_from_inclusive(v),
+				_ => {}
+			}
+		}
+		(from, to)
+	}
+
+	/// The bound described by this value.
+	fn bound(&self) -> Bound<&Value> {
+		match &self.value {
+			None => Bound::Unbounded,
+			Some(v) if self.inclusive => Bound::Included(v.as_ref()),
+			Some(v) => Bound::Excluded(v.as_ref()),
+		}
+	}
+
 	fn set_to(&mut self, v: &Arc<Value>) {
 		// Merge an exclusive upper bound (e.g., < v). We choose the maximum 'to' value.
 		let Some(current) = &self.value else {
@@ -683,6 +824,11 @@ impl UnionRangeQueryBuilder {
 		}
 	}
 
+	/// Estimates the records read by this range on an index, and its cost.
+	pub(super) fn estimate(&self, cost: &CostModel, ix: &IndexDefinition) -> Estimate {
+		cost.index_scan(ix, &[], Some((self.from.bound(), self.to.bound())))
+	}
+
 	fn add(&mut self, exp: Arc<Expr>, io: IndexOption) -> bool {
 		if let IndexOperator::RangePart(op, val) = io.op() {
 			match op {
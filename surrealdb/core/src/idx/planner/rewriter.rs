@@ -38,6 +38,7 @@ impl<'a> MutVisitor for KnnConditionRewriter<'a> {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Analyze(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_)
 			| Expr::Info(_)
@@ -13,7 +13,8 @@ use crate::expr::operator::NearestNeighbor;
 use crate::expr::order::{OrderList, Ordering};
 use crate::expr::visit::MutVisitor;
 use crate::expr::{
-	BinaryOperator, Cond, Expr, FlowResultExt as _, Idiom, Kind, Literal, Order, Part, With,
+	BinaryOperator, Cond, Expr, FlowResultExt as _, Function, Idiom, Kind, Literal, Order, Part,
+	With,
 };
 use crate::idx::planner::StatementContext;
 use crate::idx::planner::executor::{
@@ -21,8 +22,9 @@ use crate::idx::planner::executor::{
 };
 use crate::idx::planner::plan::{IndexOperator, IndexOption};
 use crate::idx::planner::rewriter::KnnConditionRewriter;
+use crate::idx::spatial::SpatialRegion;
 use crate::kvs::Transaction;
-use crate::val::{Array, Number, TableName, Value};
+use crate::val::{Array, Geometry, Number, TableName, Value};
 
 pub(super) struct Tree {
 	pub(super) root: Option<Node>,
@@ -218,6 +220,9 @@ impl<'a> TreeBuilder<'a> {
 					return Ok(re.into());
 				}
 				self.check_boolean_operator(group, op);
+				if let Some(n) = self.eval_spatial_distance(stk, group, v, left, op, right).await? {
+					return Ok(n);
+				}
 				let left_node = stk.run(|stk| self.eval_value(stk, group, left)).await?;
 				let right_node = stk.run(|stk| self.eval_value(stk, group, right)).await?;
 				// If both values are computable, then we can delegate the computation to the parent
@@ -278,7 +283,8 @@ impl<'a> TreeBuilder<'a> {
 				| Literal::None
 				| Literal::Null
 				| Literal::Decimal(_)
-				| Literal::Float(_),
+				| Literal::Float(_)
+				| Literal::Geometry(_),
 			)
 			| Expr::Param(_)
 			| Expr::FunctionCall(_) => {
@@ -465,7 +471,9 @@ impl<'a> TreeBuilder<'a> {
 	}
 
 	fn check_leaf_node_with_index(&mut self, io: Option<&IndexOption>) {
+		// Spatial indexes return candidates, the condition still has to be checked
 		if let Some(io) = io
+			&& !matches!(io.op(), IndexOperator::Spatial(_))
 			&& self.with_indexes.allowed_index(io.index_reference().index_id)
 		{
 			self.leaf_nodes_with_index_count += 2;
@@ -525,6 +533,8 @@ impl<'a> TreeBuilder<'a> {
 					..
 				} if *col == 0 => Self::eval_matches_operator(op, n),
 				Index::Hnsw(h) if *col == 0 => self.eval_hnsw_knn(e, op, n, h)?,
+				Index::IvfPq(q) if *col == 0 => self.eval_ivfpq_knn(e, op, n, q)?,
+				Index::Spatial if *col == 0 => Self::eval_spatial_operator(op, n),
 				_ => None,
 			};
 			if res.is_none()
@@ -557,6 +567,96 @@ impl<'a> TreeBuilder<'a> {
 		None
 	}
 
+	fn eval_spatial_operator(op: &BinaryOperator, n: &Node) -> Option<IndexOperator> {
+		// Whichever side the field is on, each of these operators implies that
+		// the field intersects the geometry
+		if let Some(v) = n.is_computed()
+			&& let Value::Geometry(g) = v.as_ref()
+			&& matches!(
+				op,
+				BinaryOperator::Inside | BinaryOperator::Contain | BinaryOperator::Intersects
+			) {
+			return SpatialRegion::from_geometry(g).map(IndexOperator::Spatial);
+		}
+		None
+	}
+
+	/// Recognises `geo::distance(field, point) < radius`, and its mirrored
+	/// forms, when the field is backed by a spatial index.
+	async fn eval_spatial_distance(
+		&mut self,
+		stk: &mut Stk,
+		group: GroupRef,
+		exp: &Expr,
+		left: &Expr,
+		op: &BinaryOperator,
+		right: &Expr,
+	) -> Result<Option<Node>> {
+		let (call, radius) = match op {
+			BinaryOperator::LessThan | BinaryOperator::LessThanEqual => (left, right),
+			BinaryOperator::MoreThan | BinaryOperator::MoreThanEqual => (right, left),
+			_ => return Ok(None),
+		};
+		let Expr::FunctionCall(f) = call else {
+			return Ok(None);
+		};
+		if !matches!(&f.receiver, Function::Normal(name) if name == "geo::distance")
+			|| f.arguments.len() != 2
+		{
+			return Ok(None);
+		}
+		let (field, point) = match (&f.arguments[0], &f.arguments[1]) {
+			(Expr::Idiom(i), p) | (p, Expr::Idiom(i))
+				if !matches!(i.0.first(), Some(Part::Start(_))) =>
+			{
+				(i, p)
+			}
+			_ => return Ok(None),
+		};
+		let Node::IndexedField(id, irs) = self.resolve_idiom(field).await? else {
+			return Ok(None);
+		};
+		let Some(ixr) = irs
+			.iter()
+			.find(|(ixr, col)| *col == 0 && matches!(ixr.index, Index::Spatial))
+			.map(|(ixr, _)| ixr.clone())
+		else {
+			return Ok(None);
+		};
+		let point = self.compute(stk, point, Node::Computable).await?;
+		let radius = self.compute(stk, radius, Node::Computable).await?;
+		let (Node::Computed(point), Node::Computed(radius)) = (point, radius) else {
+			return Ok(None);
+		};
+		let (Value::Geometry(Geometry::Point(point)), Value::Number(distance)) =
+			(point.as_ref(), radius.as_ref())
+		else {
+			return Ok(None);
+		};
+		let Some(region) = SpatialRegion::from_radius(*point, distance.to_float()) else {
+			return Ok(None);
+		};
+		// The expression is a leaf which is not entirely resolved by the index
+		self.leaf_nodes_count += 1;
+		let exp = Arc::new(exp.clone());
+		let io = IndexOption::new(
+			ixr,
+			Some(id.clone()),
+			IdiomPosition::Left,
+			IndexOperator::Spatial(region),
+		);
+		self.index_map.options.push((exp.clone(), io.clone()));
+		let re = ResolvedExpression {
+			group,
+			exp: exp.clone(),
+			io: Some(io),
+			left: Arc::new(Node::IndexedField(id, irs)),
+			right: Arc::new(Node::Computed(radius)),
+		};
+		self.resolved_expressions.insert(exp, re.clone());
+		Ok(Some(re.into()))
+	}
+
 	fn eval_hnsw_knn(
 		&mut self,
 		exp: &Arc<Expr>,
@@ -585,6 +685,34 @@ impl<'a> TreeBuilder<'a> {
 		Ok(None)
 	}
 
+	fn eval_ivfpq_knn(
+		&mut self,
+		exp: &Arc<Expr>,
+		op: &BinaryOperator,
+		n: &Node,
+		ivfpq: &catalog::IvfPqParams,
+	) -> Result<Option<IndexOperator>> {
+		let BinaryOperator::NearestNeighbor(nn) = op else {
+			return Ok(None);
+		};
+
+		// For IVF-PQ, the second parameter of the operator is the number of probed lists
+		let (k, probes) = match &**nn {
+			NearestNeighbor::Approximate(k, probes) => (*k, *probes),
+			NearestNeighbor::K(k, d) if *d == ivfpq.distance => (*k, ivfpq.probes as u32),
+			NearestNeighbor::KTree(k) => (*k, ivfpq.probes as u32),
+			_ => return Ok(None),
+		};
+
+		if let Node::Computed(v) = n {
+			let vec: Arc<Vec<Number>> = Arc::new(v.as_ref().clone().coerce_to()?);
+			self.knn_expressions.insert(exp.clone());
+			return Ok(Some(IndexOperator::Ann(vec, k, probes)));
+		}
+
+		Ok(None)
+	}
+
 	fn eval_bruteforce_knn(&mut self, id: &Idiom, val: &Node, exp: &Arc<Expr>) -> Result<()> {
 		if self.knn_expressions.contains(exp) {
 			return Ok(());
@@ -0,0 +1,542 @@
+//! Geospatial index support.
+//!
+//! A `SPATIAL` index maps every indexed geometry to the geohash cells covering
+//! its bounding box. The precision of the cells is chosen per geometry, so
+//! that a bounding box is always covered by at most four cells: small
+//! geometries (e.g. points) are stored in deep cells, while large geometries
+//! are stored in shallow cells.
+//!
+//! A query region is translated into covering cells in the same way. Because a
+//! geohash is a prefix of the geohashes of all the cells it contains, a record
+//! can only intersect the query region if one of its cells is either a
+//! descendant of a query cell, or an ancestor of a query cell. The iterator
+//! therefore scans the descendant range of every query cell, and probes every
+//! ancestor of every query cell.
+//!
+//! `geo::distance` returns `NONE` for any geometry other than a point, and
+//! `NONE` is lower than any radius. Non-point geometries are therefore also
+//! stored under a marker cell, which radius queries always scan.
+//!
+//! A record holding many geometries would be stored in many cells, so beyond
+//! [`MAX_RECORD_CELLS`] cells a record is instead stored under the cells
+//! covering the union of the bounding boxes of its geometries.
+//!
+//! Every stored cell lists all the cells of the record. As the ranges scanned
+//! for a region never overlap, a record is only returned from the first of its
+//! cells in scan order, so no record ids need to be kept while iterating.
+//!
+//! The index is approximate: it returns a superset of the matching records,
+//! and the query condition is always re-evaluated against the documents.
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+use anyhow::Result;
+use geo::{BoundingRect, Point};
+use revision::revisioned;
+
+use crate::catalog::{DatabaseId, IndexDefinition, NamespaceId};
+use crate::fnc::util::geo::encode;
+use crate::key::debug::Sprintable;
+use crate::key::index::gc::Gc;
+use crate::kvs::{Key, Transaction, impl_kv_value_revisioned};
+use crate::val::{Geometry, RecordId, Value};
+
+/// The maximum number of cells a record is stored under, before falling back
+/// to the cells covering all of its geometries.
+const MAX_RECORD_CELLS: usize = 16;
+
+/// The deepest geohash precision used to store a geometry.
+const MAX_PRECISION: usize = 10;
+
+/// The mean radius of the Earth in metres, as used by `geo::distance`.
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// A small margin, in degrees, added around radius regions to absorb
+/// floating point rounding errors.
+const RADIUS_MARGIN: f64 = 1e-9;
+
+/// The cell under which non-point geometries are additionally stored. This
+/// is not a valid geohash, so it never matches a region cell.
+const NON_POINT_CELL: &str = "~";
+
+/// A bounding box, in longitude (x) and latitude (y) degrees.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SpatialRegion {
+	min_x: f64,
+	min_y: f64,
+	max_x: f64,
+	max_y: f64,
+	/// Whether every non-point geometry matches, regardless of its position
+	non_points: bool,
+}
+
+impl Eq for SpatialRegion {}
+
+impl Hash for SpatialRegion {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.min_x.to_bits().hash(state);
+		self.min_y.to_bits().hash(state);
+		self.max_x.to_bits().hash(state);
+		self.max_y.to_bits().hash(state);
+		self.non_points.hash(state);
+	}
+}
+
+impl Display for SpatialRegion {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "[({}, {}), ({}, {})]", self.min_x, self.min_y, self.max_x, self.max_y)?;
+		if self.non_points {
+			f.write_str(" + non-points")?;
+		}
+		Ok(())
+	}
+}
+
+impl SpatialRegion {
+	/// Returns the bounding box of a geometry, if it has one.
+	pub(crate) fn from_geometry(g: &Geometry) -> Option<Self> {
+		let g: geo::Geometry<f64> = g.clone().into();
+		let r = g.bounding_rect()?;
+		let region = Self {
+			min_x: r.min().x,
+			min_y: r.min().y,
+			max_x: r.max().x,
+			max_y: r.max().y,
+			non_points: false,
+		};
+		region.is_finite().then_some(region)
+	}
+
+	/// Returns the bounding box of the points within `distance` metres of
+	/// `center`, using the same spherical model as `geo::distance`. As the
+	/// distance to a non-point geometry is `NONE`, those always match.
+	pub(crate) fn from_radius(center: Point<f64>, distance: f64) -> Option<Self> {
+		if !distance.is_finite() || !center.x().is_finite() || !center.y().is_finite() {
+			return None;
+		}
+		let angular = distance.max(0.0) / EARTH_RADIUS;
+		let dlat = angular.to_degrees() + RADIUS_MARGIN;
+		let min_y = (center.y() - dlat).max(-90.0);
+		let max_y = (center.y() + dlat).min(90.0);
+		// When the circle reaches a pole, every longitude is reachable
+		if min_y <= -90.0 || max_y >= 90.0 {
+			return Some(Self::full_longitude(min_y, max_y));
+		}
+		let dlon = (angular.sin() / center.y().to_radians().cos()).asin().to_degrees();
+		let dlon = dlon + RADIUS_MARGIN;
+		let min_x = center.x() - dlon;
+		let max_x = center.x() + dlon;
+		// When the circle crosses the antimeridian, we fall back to the full longitude range
+		if !dlon.is_finite() || min_x < -180.0 || max_x > 180.0 {
+			return Some(Self::full_longitude(min_y, max_y));
+		}
+		Some(Self {
+			min_x,
+			min_y,
+			max_x,
+			max_y,
+			non_points: true,
+		})
+	}
+
+	/// Extends this region to also cover another region.
+	fn union(&mut self, other: &Self) {
+		self.min_x = self.min_x.min(other.min_x);
+		self.min_y = self.min_y.min(other.min_y);
+		self.max_x = self.max_x.max(other.max_x);
+		self.max_y = self.max_y.max(other.max_y);
+	}
+
+	fn full_longitude(min_y: f64, max_y: f64) -> Self {
+		Self {
+			min_x: -180.0,
+			min_y,
+			max_x: 180.0,
+			max_y,
+			non_points: true,
+		}
+	}
+
+	fn is_finite(&self) -> bool {
+		self.min_x.is_finite()
+			&& self.min_y.is_finite()
+			&& self.max_x.is_finite()
+			&& self.max_y.is_finite()
+	}
+
+	/// Returns the width and height, in degrees, of a geohash cell.
+	fn cell_size(precision: usize) -> (f64, f64) {
+		let bits = 5 * precision as u32;
+		let lon_bits = bits.div_ceil(2);
+		let lat_bits = bits / 2;
+		(360.0 / 2f64.powi(lon_bits as i32), 180.0 / 2f64.powi(lat_bits as i32))
+	}
+
+	/// Returns the geohash cells covering this region.
+	///
+	/// The precision is the deepest one for which a cell is at least as large
+	/// as the region, so that the region is covered by the cells of its four
+	/// corners. An empty geohash denotes the whole world.
+	pub(crate) fn covering_cells(&self) -> Vec<String> {
+		let width = self.max_x - self.min_x;
+		let height = self.max_y - self.min_y;
+		let mut precision = 0;
+		while precision < MAX_PRECISION {
+			let (w, h) = Self::cell_size(precision + 1);
+			if w < width || h < height {
+				break;
+			}
+			precision += 1;
+		}
+		if precision == 0 {
+			return vec![String::new()];
+		}
+		let corners = [
+			(self.min_x, self.min_y),
+			(self.min_x, self.max_y),
+			(self.max_x, self.min_y),
+			(self.max_x, self.max_y),
+		];
+		let cells: BTreeSet<String> =
+			corners.into_iter().map(|(x, y)| encode(Point::new(x, y), precision)).collect();
+		cells.into_iter().collect()
+	}
+}
+
+/// The value stored under every cell of a record.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct SpatialEntry {
+	/// The indexed record
+	pub(crate) rid: RecordId,
+	/// All the cells the record is stored under
+	pub(crate) cells: Vec<String>,
+}
+
+impl_kv_value_revisioned!(SpatialEntry);
+
+/// Collects the geohash cells under which the indexed values of a record are
+/// stored.
+///
+/// Geometries are indexed by their covering cells, arrays are indexed by the
+/// cells of each of their geometries, and any other value is not indexed.
+/// Non-point geometries are also indexed under the non-point marker cell.
+/// When this exceeds [`MAX_RECORD_CELLS`] cells, the record is indexed by the
+/// cells covering the union of the bounding boxes of its geometries instead.
+pub(crate) fn record_cells(values: &[Value]) -> BTreeSet<String> {
+	let mut cells = BTreeSet::new();
+	for v in values {
+		value_cells(v, &mut cells);
+	}
+	if cells.len() <= MAX_RECORD_CELLS {
+		return cells;
+	}
+	let mut bounds = None;
+	let mut non_points = false;
+	for v in values {
+		value_bounds(v, &mut bounds, &mut non_points);
+	}
+	let mut cells = BTreeSet::new();
+	if let Some(r) = bounds {
+		cells.extend(r.covering_cells());
+	}
+	if non_points {
+		cells.insert(NON_POINT_CELL.to_string());
+	}
+	cells
+}
+
+/// Collects the cells of a single indexed value.
+fn value_cells(v: &Value, cells: &mut BTreeSet<String>) {
+	match v {
+		Value::Geometry(g) => {
+			if let Some(r) = SpatialRegion::from_geometry(g) {
+				cells.extend(r.covering_cells());
+			}
+			if !matches!(g, Geometry::Point(_)) {
+				cells.insert(NON_POINT_CELL.to_string());
+			}
+		}
+		Value::Array(a) => {
+			for v in a.iter() {
+				value_cells(v, cells);
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Extends a region to cover the geometries of a single indexed value.
+fn value_bounds(v: &Value, bounds: &mut Option<SpatialRegion>, non_points: &mut bool) {
+	match v {
+		Value::Geometry(g) => {
+			if let Some(r) = SpatialRegion::from_geometry(g) {
+				match bounds {
+					Some(b) => b.union(&r),
+					None => *bounds = Some(r),
+				}
+			}
+			if !matches!(g, Geometry::Point(_)) {
+				*non_points = true;
+			}
+		}
+		Value::Array(a) => {
+			for v in a.iter() {
+				value_bounds(v, bounds, non_points);
+			}
+		}
+		_ => {}
+	}
+}
+
+/// A key range scanned by a [`SpatialIterator`].
+struct CellRange {
+	/// The cell the range was built from
+	cell: String,
+	/// Whether the range also covers the descendants of the cell
+	descendants: bool,
+	/// The keys which remain to be scanned
+	keys: Range<Key>,
+}
+
+impl CellRange {
+	/// Returns whether a cell is stored within this range.
+	fn contains(&self, cell: &str) -> bool {
+		if self.descendants {
+			cell.starts_with(self.cell.as_str())
+		} else {
+			cell == self.cell
+		}
+	}
+}
+
+/// Iterates over the record ids stored in a spatial index which may
+/// intersect a given region.
+pub(crate) struct SpatialIterator {
+	/// The length of the key prefix preceding the cell
+	prefix: usize,
+	/// The key ranges to scan, which never overlap
+	ranges: Vec<CellRange>,
+	/// The index of the range being scanned
+	current: usize,
+}
+
+impl SpatialIterator {
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		ix: &IndexDefinition,
+		region: &SpatialRegion,
+	) -> Result<Self> {
+		let tb = &ix.table_name;
+		let cells = region.covering_cells();
+		// The ancestors of the query cells, probed for an exact match
+		let mut ancestors = BTreeSet::new();
+		for cell in &cells {
+			for len in 0..cell.len() {
+				ancestors.insert(&cell[..len]);
+			}
+		}
+		let mut ranges = Vec::with_capacity(cells.len() + ancestors.len() + 1);
+		for cell in ancestors {
+			ranges.push(CellRange {
+				cell: cell.to_string(),
+				descendants: false,
+				keys: Gc::cell_range(ns, db, tb, ix.index_id, cell)?,
+			});
+		}
+		// The descendants of the root cell include the non-point cell
+		if region.non_points && !cells.iter().any(String::is_empty) {
+			ranges.push(CellRange {
+				cell: NON_POINT_CELL.to_string(),
+				descendants: false,
+				keys: Gc::cell_range(ns, db, tb, ix.index_id, NON_POINT_CELL)?,
+			});
+		}
+		// The query cells, scanned with all their descendants
+		for cell in &cells {
+			ranges.push(CellRange {
+				cell: cell.clone(),
+				descendants: true,
+				keys: Gc::descendants_range(ns, db, tb, ix.index_id, cell)?,
+			});
+		}
+		Ok(Self {
+			prefix: Gc::prefix(ns, db, tb, ix.index_id)?.len(),
+			ranges,
+			current: 0,
+		})
+	}
+
+	/// Returns whether a record stored in a cell of the current range is
+	/// returned from this cell, rather than from another of its cells which
+	/// is scanned earlier.
+	fn is_first(&self, cell: &str, entry: &SpatialEntry) -> bool {
+		entry.cells.iter().filter(|c| c.as_str() != cell).all(|c| {
+			match self.ranges.iter().position(|r| r.contains(c)) {
+				Some(pos) => pos > self.current || (pos == self.current && c.as_str() > cell),
+				None => true,
+			}
+		})
+	}
+
+	/// Fetch the next batch of distinct record ids.
+	///
+	/// Returns an empty `Vec` when iteration is complete.
+	pub(crate) async fn next_batch(
+		&mut self,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<Vec<RecordId>> {
+		while let Some(rng) = self.ranges.get_mut(self.current) {
+			let res = tx.scan(rng.keys.start.clone()..rng.keys.end.clone(), limit, 0, None).await?;
+			if res.is_empty() {
+				self.current += 1;
+				continue;
+			}
+			// Advance the range past the last returned key
+			if let Some((key, _)) = res.last() {
+				rng.keys.start.clone_from(key);
+				rng.keys.start.push(0x00);
+			}
+			let mut records = Vec::with_capacity(res.len());
+			for (key, val) in res {
+				let entry: SpatialEntry = revision::from_slice(&val)?;
+				// The cell is stored as a zero-terminated string after the prefix
+				let cell = key
+					.get(self.prefix..)
+					.and_then(|k| k.split(|b| *b == 0).next())
+					.and_then(|c| std::str::from_utf8(c).ok());
+				let Some(cell) = cell else {
+					anyhow::bail!(crate::err::Error::Internal(format!(
+						"Invalid spatial index key: {}",
+						key.sprint()
+					)));
+				};
+				if self.is_first(cell, &entry) {
+					records.push(entry.rid);
+				}
+			}
+			if !records.is_empty() {
+				return Ok(records);
+			}
+		}
+		Ok(Vec::new())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::catalog::{Index, IndexId};
+	use crate::val::TableName;
+
+	fn region(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> SpatialRegion {
+		SpatialRegion {
+			min_x,
+			min_y,
+			max_x,
+			max_y,
+			non_points: false,
+		}
+	}
+
+	#[test]
+	fn point_uses_max_precision() {
+		let r = SpatialRegion::from_geometry(&Geometry::Point(Point::new(-0.13, 51.5))).unwrap();
+		assert_eq!(r.covering_cells(), vec![encode(Point::new(-0.13, 51.5), MAX_PRECISION)]);
+	}
+
+	#[test]
+	fn world_uses_root_cell() {
+		let r = region(-180.0, -90.0, 180.0, 90.0);
+		assert_eq!(r.covering_cells(), vec![String::new()]);
+	}
+
+	#[test]
+	fn cells_are_large_enough() {
+		let r = region(-0.5, 51.2, 0.3, 51.7);
+		let cells = r.covering_cells();
+		assert!(!cells.is_empty() && cells.len() <= 4);
+		let (w, h) = SpatialRegion::cell_size(cells[0].len());
+		assert!(w >= 0.8 && h >= 0.5);
+		let (w, h) = SpatialRegion::cell_size(cells[0].len() + 1);
+		assert!(w < 0.8 || h < 0.5);
+	}
+
+	#[test]
+	fn point_cells_descend_from_region_cells() {
+		let r = region(-0.5, 51.2, 0.3, 51.7);
+		let cells = r.covering_cells();
+		for p in [(-0.5, 51.2), (-0.1, 51.5), (0.3, 51.7), (0.0, 51.3)] {
+			let hash = encode(Point::new(p.0, p.1), MAX_PRECISION);
+			assert!(cells.iter().any(|c| hash.starts_with(c.as_str())), "{hash} {cells:?}");
+		}
+	}
+
+	#[test]
+	fn radius_region() {
+		// One degree of latitude is about 111.2km
+		let r = SpatialRegion::from_radius(Point::new(0.0, 0.0), 111_195.0).unwrap();
+		assert!((r.max_y - 1.0).abs() < 1e-3);
+		assert!((r.max_x - 1.0).abs() < 1e-3);
+		// Longitude degrees shrink with the latitude
+		let r = SpatialRegion::from_radius(Point::new(0.0, 60.0), 111_195.0).unwrap();
+		assert!((r.max_x - 2.0).abs() < 1e-2);
+		// Crossing a pole covers every longitude
+		let r = SpatialRegion::from_radius(Point::new(10.0, 89.5), 111_195.0).unwrap();
+		assert_eq!((r.min_x, r.max_x, r.max_y), (-180.0, 180.0, 90.0));
+		// Crossing the antimeridian covers every longitude
+		let r = SpatialRegion::from_radius(Point::new(179.9, 0.0), 111_195.0).unwrap();
+		assert_eq!((r.min_x, r.max_x), (-180.0, 180.0));
+		assert!(r.non_points);
+	}
+
+	#[test]
+	fn non_point_marker() {
+		let mut cells = BTreeSet::new();
+		value_cells(&Value::Geometry(Geometry::Point(Point::new(-0.13, 51.5))), &mut cells);
+		assert!(!cells.contains(NON_POINT_CELL));
+		let line = geo::LineString::from(vec![(0.0, 0.0), (1.0, 1.0)]);
+		value_cells(&Value::Geometry(Geometry::Line(line)), &mut cells);
+		assert!(cells.contains(NON_POINT_CELL));
+	}
+
+	#[test]
+	fn non_point_cell_is_scanned_once() {
+		let ix = IndexDefinition {
+			index_id: IndexId(1),
+			name: "loc".to_string(),
+			table_name: TableName::from("place"),
+			cols: vec![crate::syn::idiom("location").unwrap().into()],
+			index: Index::Spatial,
+			comment: None,
+			prepare_remove: false,
+		};
+		let scanned = |region: &SpatialRegion| {
+			let it = SpatialIterator::new(NamespaceId(1), DatabaseId(2), &ix, region).unwrap();
+			it.ranges.iter().filter(|r| r.contains(NON_POINT_CELL)).count()
+		};
+		// A radius region scans the non-point cell on its own
+		let r = SpatialRegion::from_radius(Point::new(0.0, 0.0), 111_195.0).unwrap();
+		assert_eq!(scanned(&r), 1);
+		// A radius region covering the world scans it within the root cell
+		let r = SpatialRegion::from_radius(Point::new(0.0, 0.0), 30_000_000.0).unwrap();
+		assert_eq!(r.covering_cells(), vec![String::new()]);
+		assert_eq!(scanned(&r), 1);
+	}
+
+	#[test]
+	fn record_cells_are_bounded() {
+		let points: Vec<Value> = (0..100)
+			.map(|i| Value::Geometry(Geometry::Point(Point::new(i as f64, i as f64 / 2.0))))
+			.collect();
+		let cells = record_cells(&[Value::Array(points.into())]);
+		assert!(!cells.is_empty() && cells.len() <= 4, "{cells:?}");
+		// The union of the points is covered by the cells
+		let hash = encode(Point::new(42.0, 21.0), MAX_PRECISION);
+		assert!(cells.iter().any(|c| hash.starts_with(c.as_str())), "{cells:?}");
+	}
+}
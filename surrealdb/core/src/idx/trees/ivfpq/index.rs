@@ -0,0 +1,711 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::Arc;
+
+use ahash::HashMap;
+use anyhow::{Result, bail};
+use futures::StreamExt;
+use rand::prelude::SmallRng;
+use rand::{Rng, SeedableRng, thread_rng};
+use reblessive::tree::Stk;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{Distance, IvfPqParams, Record, VectorType};
+use crate::cnf::INDEXING_BATCH_SIZE;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::{Cond, FlowResultExt as _};
+use crate::idx::IndexKeyBase;
+use crate::idx::planner::ScanDirection;
+use crate::idx::planner::iterators::KnnIteratorResult;
+use crate::idx::seqdocids::{DocId, SeqDocIds};
+use crate::idx::trees::ivfpq::model::IvfPqModel;
+use crate::idx::trees::ivfpq::{IvfPqCentroid, IvfPqCodes, IvfPqState, IvfPqVectors};
+use crate::idx::trees::knn::DoublePriorityQueue;
+use crate::idx::trees::vector::{SerializedVector, Vector};
+use crate::key::index::qb::Qb;
+use crate::key::index::ql::Ql;
+use crate::key::index::qv::Qv;
+use crate::kvs::{KVKey, KVValue, Transaction};
+use crate::val::{Number, RecordId, RecordIdKey, Value};
+
+/// Number of candidates, per requested neighbour, reranked with the exact distance.
+const RERANK_FACTOR: usize = 4;
+/// Number of indexed documents, per centroid, required before the model is trained.
+const TRAINING_THRESHOLD_FACTOR: usize = 8;
+/// Maximum number of vectors, per centroid, sampled to train the model.
+const TRAINING_SAMPLE_FACTOR: usize = 64;
+/// Number of consecutive documents read from each random position while sampling.
+const TRAINING_SAMPLE_RUN: usize = 8;
+
+/// IVF-PQ index whose data lives in the key-value store.
+///
+/// Documents are written synchronously: their full-precision vectors are
+/// stored, and, once a model exists, their codes are appended to the
+/// inverted lists of their vectors. The model is trained by the index
+/// compaction (see [`train`](Self::train)) and cached in memory, keyed by the
+/// version persisted in the index state. Searches only use a model once every
+/// document has been encoded with it.
+pub(crate) struct IvfPqIndex {
+	/// Expected vector dimensionality.
+	dim: usize,
+	/// Distance metric used to rank the results.
+	distance: Distance,
+	/// The type of vector stored in this index.
+	vector_type: VectorType,
+	/// Number of inverted lists to train.
+	lists: usize,
+	/// Number of sub-vectors each vector is split into.
+	subvectors: usize,
+	/// Number of bits of each sub-vector code.
+	bits: u8,
+	/// Key base for generating index-related storage keys.
+	ikb: IndexKeyBase,
+	/// Mapping between record ids and document ids.
+	doc_ids: SeqDocIds,
+	/// The cached model, with the version it was loaded for.
+	model: RwLock<Option<(Uuid, Arc<IvfPqModel>)>>,
+}
+
+impl IvfPqIndex {
+	pub(crate) fn new(ikb: IndexKeyBase, p: &IvfPqParams) -> Self {
+		Self {
+			dim: p.dimension as usize,
+			distance: p.distance.clone(),
+			vector_type: p.vector_type,
+			lists: p.lists as usize,
+			subvectors: p.subvectors as usize,
+			bits: p.bits,
+			doc_ids: SeqDocIds::new(ikb.clone()),
+			ikb,
+			model: RwLock::new(None),
+		}
+	}
+
+	/// Converts content values into serialized vectors, validating dimensionality.
+	fn content_to_vectors(&self, content: Vec<Value>) -> Result<Vec<SerializedVector>> {
+		let mut vectors = Vec::with_capacity(content.len());
+		for value in content.into_iter().filter(|v| !v.is_nullish()) {
+			let vector = SerializedVector::try_from_value(self.vector_type, self.dim, value)?;
+			Vector::check_expected_dimension(vector.dimension(), self.dim)?;
+			vectors.push(vector);
+		}
+		Ok(vectors)
+	}
+
+	/// Projects a vector into the space the model is trained on.
+	///
+	/// The model always quantizes euclidean distances, which rank the
+	/// normalized vectors the same way the cosine distance ranks the original
+	/// ones. Other metrics rely on the exact reranking.
+	fn to_model_space(&self, vector: &Vector) -> Vec<f32> {
+		let mut v = vector.to_f32_vec();
+		if matches!(self.distance, Distance::Cosine) {
+			let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+			if norm > 0.0 {
+				v.iter_mut().for_each(|x| *x /= norm);
+			}
+		}
+		v
+	}
+
+	/// Returns the current model with the state of the index, or `None` if
+	/// the index is not trained yet.
+	async fn current_model(
+		&self,
+		tx: &Transaction,
+	) -> Result<Option<(IvfPqState, Arc<IvfPqModel>)>> {
+		let Some(state) = tx.get(&self.ikb.new_qs_key(), None).await? else {
+			return Ok(None);
+		};
+		if let Some((version, model)) = &*self.model.read().await
+			&& *version == state.version
+		{
+			return Ok(Some((state, model.clone())));
+		}
+		let mut cache = self.model.write().await;
+		if let Some((version, model)) = &*cache
+			&& *version == state.version
+		{
+			return Ok(Some((state, model.clone())));
+		}
+		let model = Arc::new(self.load_model(tx).await?);
+		*cache = Some((state.version, model.clone()));
+		Ok(Some((state, model)))
+	}
+
+	/// Loads the centroids and the codebooks from the key-value store.
+	async fn load_model(&self, tx: &Transaction) -> Result<IvfPqModel> {
+		let mut centroids = Vec::with_capacity(self.lists);
+		for (_, v) in tx.getr(self.ikb.new_qc_range()?, None).await? {
+			centroids.push(IvfPqCentroid::kv_decode_value(v)?.vector);
+		}
+		let mut codebooks: Vec<Vec<Vec<f32>>> = Vec::with_capacity(self.subvectors);
+		for (k, v) in tx.getr(self.ikb.new_qb_range()?, None).await? {
+			let sub = Qb::decode_key(&k)?.sub as usize;
+			if codebooks.len() <= sub {
+				codebooks.resize_with(sub + 1, Vec::new);
+			}
+			codebooks[sub].push(IvfPqCentroid::kv_decode_value(v)?.vector);
+		}
+		IvfPqModel::new(self.dim, centroids, codebooks)
+	}
+
+	/// Groups the codes of the given vectors by inverted list.
+	fn encode(
+		&self,
+		model: &IvfPqModel,
+		vectors: &[SerializedVector],
+	) -> BTreeMap<u16, IvfPqCodes> {
+		let mut lists: BTreeMap<u16, IvfPqCodes> = BTreeMap::new();
+		for v in vectors {
+			let v = self.to_model_space(&Vector::from(v.clone()));
+			let list = model.assign(&v);
+			lists
+				.entry(list)
+				.or_insert_with(|| IvfPqCodes {
+					codes: vec![],
+				})
+				.codes
+				.push(model.encode(&v, list));
+		}
+		lists
+	}
+
+	/// Indexes the vectors of a document.
+	///
+	/// Returns `true` if the index is not trained yet, in which case a
+	/// compaction should be triggered so the model can eventually be trained.
+	pub(crate) async fn index(
+		&self,
+		ctx: &FrozenContext,
+		id: &RecordIdKey,
+		old_values: Option<Vec<Value>>,
+		new_values: Option<Vec<Value>>,
+	) -> Result<bool> {
+		let old_vectors = match old_values {
+			Some(v) => self.content_to_vectors(v)?,
+			None => vec![],
+		};
+		let new_vectors = match new_values {
+			Some(v) => self.content_to_vectors(v)?,
+			None => vec![],
+		};
+		if old_vectors.is_empty() && new_vectors.is_empty() {
+			return Ok(false);
+		}
+		let tx = ctx.tx();
+		// Documents are encoded as soon as a model exists, even while the
+		// existing documents are still being encoded by the compaction
+		let model = self.current_model(&tx).await?.map(|(_, model)| model);
+		// Remove the old vectors from the inverted lists
+		if !old_vectors.is_empty()
+			&& let Some(doc_id) = self.doc_ids.get_doc_id(&tx, id).await?
+		{
+			if let Some(model) = &model {
+				let lists: BTreeSet<u16> = old_vectors
+					.iter()
+					.map(|v| model.assign(&self.to_model_space(&Vector::from(v.clone()))))
+					.collect();
+				for list in lists {
+					tx.del(&self.ikb.new_ql_key(list, doc_id)).await?;
+				}
+			}
+			if new_vectors.is_empty() {
+				tx.del(&self.ikb.new_qv_key(doc_id)).await?;
+				self.doc_ids.remove_doc_id(&tx, doc_id).await?;
+				return Ok(false);
+			}
+		}
+		if new_vectors.is_empty() {
+			return Ok(false);
+		}
+		// Index the new vectors
+		let doc_id = self.doc_ids.resolve_doc_id(ctx, id.clone()).await?.doc_id();
+		if let Some(model) = &model {
+			for (list, codes) in self.encode(model, &new_vectors) {
+				tx.set(&self.ikb.new_ql_key(list, doc_id), &codes).await?;
+			}
+		}
+		let vectors = IvfPqVectors {
+			vectors: new_vectors,
+		};
+		tx.set(&self.ikb.new_qv_key(doc_id), &vectors).await?;
+		Ok(model.is_none())
+	}
+
+	/// Advances the training of the index.
+	///
+	/// Once the index holds enough documents, the model is trained over a
+	/// sample of the indexed vectors. The following calls then encode the
+	/// documents into the inverted lists, one batch per call, so that no
+	/// transaction has to write the whole index. Returns `true` if more work
+	/// remains, in which case another compaction should be triggered.
+	pub(crate) async fn train(&self, ctx: &FrozenContext) -> Result<bool> {
+		let tx = ctx.tx();
+		match tx.get(&self.ikb.new_qs_key(), None).await? {
+			None => self.train_model(ctx, &tx).await,
+			Some(IvfPqState {
+				version,
+				pending: Some(next),
+			}) => self.encode_batch(ctx, &tx, version, next).await,
+			Some(_) => Ok(false),
+		}
+	}
+
+	/// Trains a new model if the index holds enough documents.
+	///
+	/// The model is persisted under a new version, with every document still
+	/// pending, so the searches keep using the exact scan until the documents
+	/// are encoded. Returns `true` if a model has been trained.
+	async fn train_model(&self, ctx: &FrozenContext, tx: &Transaction) -> Result<bool> {
+		let centroids = self.lists.max(1 << self.bits);
+		let threshold = centroids * TRAINING_THRESHOLD_FACTOR;
+		let docs = tx.keys(self.ikb.new_qv_range()?, threshold as u32, 0, None).await?;
+		if docs.len() < threshold {
+			return Ok(false);
+		}
+		let mut rng = SmallRng::from_rng(thread_rng())?;
+		let sample = self.sample(ctx, tx, centroids * TRAINING_SAMPLE_FACTOR, &mut rng).await?;
+		let model =
+			IvfPqModel::train(self.dim, &sample, self.lists, self.subvectors, self.bits, &mut rng)?;
+		drop(sample);
+		// Persist the model
+		tx.delr(self.ikb.new_qc_range()?).await?;
+		tx.delr(self.ikb.new_qb_range()?).await?;
+		for (list, c) in model.centroids().iter().enumerate() {
+			let centroid = IvfPqCentroid {
+				vector: c.clone(),
+			};
+			tx.set(&self.ikb.new_qc_key(list as u16), &centroid).await?;
+		}
+		for (sub, codebook) in model.codebooks().iter().enumerate() {
+			for (code, c) in codebook.iter().enumerate() {
+				let codeword = IvfPqCentroid {
+					vector: c.clone(),
+				};
+				tx.set(&self.ikb.new_qb_key(sub as u16, code as u8), &codeword).await?;
+			}
+		}
+		// The documents are encoded by the following compactions
+		tx.delr(self.ikb.new_ql_range()?).await?;
+		// A new version invalidates the models cached by every node
+		let version = Uuid::new_v4();
+		let state = IvfPqState {
+			version,
+			pending: Some(0),
+		};
+		tx.set(&self.ikb.new_qs_key(), &state).await?;
+		*self.model.write().await = Some((version, Arc::new(model)));
+		Ok(true)
+	}
+
+	/// Collects a sample of the indexed vectors, projected into the model space.
+	///
+	/// Rather than reading every document, short runs of consecutive documents
+	/// are read from random positions, so the cost of the sampling depends on
+	/// the size of the sample, not on the size of the index.
+	async fn sample(
+		&self,
+		ctx: &FrozenContext,
+		tx: &Transaction,
+		capacity: usize,
+		rng: &mut SmallRng,
+	) -> Result<Vec<Vec<f32>>> {
+		let range = self.ikb.new_qv_range()?;
+		let Some(last) = tx.keysr(range.clone(), 1, 0, None).await?.pop() else {
+			return Ok(vec![]);
+		};
+		let last = Qv::decode_key(&last)?.doc_id;
+		let mut sample = Vec::with_capacity(capacity);
+		// Small indexes are sampled whole
+		if last < capacity as DocId {
+			for (_, v) in tx.scan(range, capacity as u32, 0, None).await? {
+				for v in IvfPqVectors::kv_decode_value(v)?.vectors {
+					sample.push(self.to_model_space(&Vector::from(v)));
+				}
+			}
+			sample.truncate(capacity);
+			return Ok(sample);
+		}
+		// Document ids may have gaps, so a few more runs than needed are allowed
+		let runs = capacity.div_ceil(TRAINING_SAMPLE_RUN) * 2;
+		for run in 0..runs {
+			if sample.len() >= capacity {
+				break;
+			}
+			if ctx.is_done(Some(run)).await? {
+				bail!(Error::QueryCancelled)
+			}
+			let beg = self.ikb.new_qv_key(rng.gen_range(0..=last)).encode_key()?;
+			let docs = tx.scan(beg..range.end.clone(), TRAINING_SAMPLE_RUN as u32, 0, None).await?;
+			for (_, v) in docs {
+				for v in IvfPqVectors::kv_decode_value(v)?.vectors {
+					sample.push(self.to_model_space(&Vector::from(v)));
+				}
+			}
+		}
+		sample.truncate(capacity);
+		Ok(sample)
+	}
+
+	/// Encodes a batch of documents, starting at the given document id, into
+	/// the inverted lists of the model being trained.
+	///
+	/// Once the last batch is encoded, the model is swapped in for the
+	/// searches. Returns `true` if documents remain to be encoded.
+	async fn encode_batch(
+		&self,
+		ctx: &FrozenContext,
+		tx: &Transaction,
+		version: Uuid,
+		next: DocId,
+	) -> Result<bool> {
+		let Some((_, model)) = self.current_model(tx).await? else {
+			return Ok(false);
+		};
+		let beg = self.ikb.new_qv_key(next).encode_key()?;
+		let end = self.ikb.new_qv_range()?.end;
+		let batch = tx.batch_keys_vals(beg..end, *INDEXING_BATCH_SIZE, None).await?;
+		let mut pending = None;
+		for (i, (k, v)) in batch.result.into_iter().enumerate() {
+			if ctx.is_done(Some(i)).await? {
+				bail!(Error::QueryCancelled)
+			}
+			let doc_id = Qv::decode_key(&k)?.doc_id;
+			let vectors = IvfPqVectors::kv_decode_value(v)?.vectors;
+			for (list, codes) in self.encode(&model, &vectors) {
+				tx.set(&self.ikb.new_ql_key(list, doc_id), &codes).await?;
+			}
+			pending = Some(doc_id + 1);
+		}
+		// The model is swapped in once the last document is encoded
+		if batch.next.is_none() {
+			pending = None;
+		}
+		let state = IvfPqState {
+			version,
+			pending,
+		};
+		tx.set(&self.ikb.new_qs_key(), &state).await?;
+		Ok(pending.is_some())
+	}
+
+	/// Performs a k-nearest neighbor search.
+	///
+	/// Once the model is trained, the `probes` inverted lists closest to the
+	/// query are scanned, the candidates are ranked by their approximate
+	/// distance, and the best of them are reranked with the exact distance.
+	/// Until the model is trained and every document encoded with it, the
+	/// search is an exact scan over every document.
+	pub(crate) async fn knn_search(
+		&self,
+		ctx: &FrozenContext,
+		stk: &mut Stk,
+		pt: &[Number],
+		k: usize,
+		probes: usize,
+		cond_filter: Option<(&Options, Arc<Cond>)>,
+	) -> Result<VecDeque<KnnIteratorResult>> {
+		let vector = Vector::try_from_vector(self.vector_type, pt)?;
+		vector.check_dimension(self.dim)?;
+		let tx = ctx.tx();
+		let mut search = IvfPqSearch {
+			ctx,
+			tx: &tx,
+			ikb: &self.ikb,
+			k,
+			results: DoublePriorityQueue::default(),
+			filter: cond_filter.map(|(opt, cond)| IvfPqFilter {
+				opt,
+				cond,
+				records: HashMap::default(),
+			}),
+		};
+		match self.current_model(&tx).await? {
+			Some((state, model)) if state.pending.is_none() => {
+				self.search_lists(stk, &mut search, &model, &vector, probes).await?
+			}
+			_ => self.search_all(stk, &mut search, &vector).await?,
+		}
+		// Build the final result, replacing the doc ids with record ids
+		let IvfPqSearch {
+			results,
+			mut filter,
+			..
+		} = search;
+		let mut res = VecDeque::with_capacity(results.len());
+		for (dist, doc_id) in results.to_vec() {
+			if let Some(filter) = &mut filter
+				&& let Some((rid, record)) = filter.records.remove(&doc_id)
+			{
+				res.push_back((rid, dist, Some(record)));
+				continue;
+			}
+			if let Some(id) = SeqDocIds::get_id(&self.ikb, &tx, doc_id).await? {
+				let rid = RecordId::new(self.ikb.table().clone(), id);
+				res.push_back((Arc::new(rid), dist, None));
+			}
+		}
+		Ok(res)
+	}
+
+	/// Returns the exact distance between the query and the closest vector of a document.
+	fn exact_distance(&self, query: &Vector, vectors: Vec<SerializedVector>) -> Option<f64> {
+		vectors
+			.into_iter()
+			.map(|v| self.distance.calculate(query, &Vector::from(v)))
+			.min_by(|a, b| a.total_cmp(b))
+	}
+
+	/// Exact search over every indexed document, used until the model is ready.
+	async fn search_all(
+		&self,
+		stk: &mut Stk,
+		search: &mut IvfPqSearch<'_>,
+		query: &Vector,
+	) -> Result<()> {
+		let tx = search.tx;
+		let mut count = 0;
+		let mut stream = tx.stream_keys_vals(
+			self.ikb.new_qv_range()?,
+			None,
+			None,
+			0,
+			ScanDirection::Forward,
+			false,
+		);
+		while let Some(res) = stream.next().await {
+			for (k, v) in res? {
+				if search.ctx.is_done(Some(count)).await? {
+					bail!(Error::QueryCancelled)
+				}
+				count += 1;
+				let doc_id = Qv::decode_key(&k)?.doc_id;
+				let vectors = IvfPqVectors::kv_decode_value(v)?.vectors;
+				if let Some(dist) = self.exact_distance(query, vectors) {
+					search.add(stk, dist, doc_id).await?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Approximate search over the inverted lists closest to the query.
+	async fn search_lists(
+		&self,
+		stk: &mut Stk,
+		search: &mut IvfPqSearch<'_>,
+		model: &IvfPqModel,
+		query: &Vector,
+		probes: usize,
+	) -> Result<()> {
+		let tx = search.tx;
+		let q = self.to_model_space(query);
+		// Collect the candidates with their approximate distance
+		let mut candidates: HashMap<DocId, f32> = HashMap::default();
+		let mut count = 0;
+		for list in model.nearest_lists(&q, probes) {
+			let table = model.lookup_table(&q, list);
+			let mut stream = tx.stream_keys_vals(
+				self.ikb.new_ql_list_range(list)?,
+				None,
+				None,
+				0,
+				ScanDirection::Forward,
+				false,
+			);
+			while let Some(res) = stream.next().await {
+				for (k, v) in res? {
+					if search.ctx.is_done(Some(count)).await? {
+						bail!(Error::QueryCancelled)
+					}
+					count += 1;
+					let doc_id = Ql::decode_key(&k)?.doc_id;
+					for codes in IvfPqCodes::kv_decode_value(v)?.codes {
+						let d = model.approximate_distance(&table, &codes);
+						let best = candidates.entry(doc_id).or_insert(f32::INFINITY);
+						if d < *best {
+							*best = d;
+						}
+					}
+				}
+			}
+		}
+		let mut candidates: Vec<(f32, DocId)> =
+			candidates.into_iter().map(|(doc_id, d)| (d, doc_id)).collect();
+		candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+		// Rerank the best candidates with the exact distance. With a filter,
+		// the reranking continues until enough documents match it.
+		let rerank = search.k * RERANK_FACTOR;
+		for (i, (_, doc_id)) in candidates.into_iter().enumerate() {
+			if i >= rerank && search.results.len() >= search.k {
+				break;
+			}
+			if let Some(vectors) = tx.get(&self.ikb.new_qv_key(doc_id), None).await?
+				&& let Some(dist) = self.exact_distance(query, vectors.vectors)
+			{
+				search.add(stk, dist, doc_id).await?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// State of an ongoing k-nearest neighbor search.
+struct IvfPqSearch<'a> {
+	ctx: &'a FrozenContext,
+	tx: &'a Transaction,
+	ikb: &'a IndexKeyBase,
+	/// The number of neighbors to return.
+	k: usize,
+	/// The best results found so far, by exact distance.
+	results: DoublePriorityQueue,
+	/// The optional `WHERE` condition the results must match.
+	filter: Option<IvfPqFilter<'a>>,
+}
+
+impl IvfPqSearch<'_> {
+	/// Adds a document to the results if it is closer than the current
+	/// results and matches the filter.
+	async fn add(&mut self, stk: &mut Stk, dist: f64, doc_id: DocId) -> Result<()> {
+		if self.results.len() >= self.k && self.results.peek_last_dist().is_some_and(|d| dist >= d)
+		{
+			return Ok(());
+		}
+		if let Some(filter) = &mut self.filter
+			&& !filter.check(self.ctx, self.tx, self.ikb, stk, doc_id).await?
+		{
+			return Ok(());
+		}
+		self.results.push(dist, doc_id);
+		if self.results.len() > self.k
+			&& let Some((_, evicted)) = self.results.pop_last()
+			&& let Some(filter) = &mut self.filter
+		{
+			filter.records.remove(&evicted);
+		}
+		Ok(())
+	}
+}
+
+/// Evaluates a `WHERE` condition against the candidates of a search.
+///
+/// The records of the documents matching the condition are kept, so the
+/// results do not need to be fetched twice.
+struct IvfPqFilter<'a> {
+	opt: &'a Options,
+	cond: Arc<Cond>,
+	records: HashMap<DocId, (Arc<RecordId>, Arc<Record>)>,
+}
+
+impl IvfPqFilter<'_> {
+	async fn check(
+		&mut self,
+		ctx: &FrozenContext,
+		tx: &Transaction,
+		ikb: &IndexKeyBase,
+		stk: &mut Stk,
+		doc_id: DocId,
+	) -> Result<bool> {
+		let Some(id) = SeqDocIds::get_id(ikb, tx, doc_id).await? else {
+			return Ok(false);
+		};
+		let rid = Arc::new(RecordId::new(ikb.table().clone(), id));
+		let val = tx.get_record(ikb.ns(), ikb.db(), &rid.table, &rid.key, None).await?;
+		if val.data.is_nullish() {
+			return Ok(false);
+		}
+		let cursor_doc = CursorDoc {
+			rid: Some(rid.clone()),
+			ir: None,
+			doc: val.into(),
+			fields_computed: false,
+		};
+		let truthy = stk
+			.run(|stk| self.cond.0.compute(stk, ctx, self.opt, Some(&cursor_doc)))
+			.await
+			.catch_return()?
+			.is_truthy();
+		if truthy {
+			self.records.insert(doc_id, (rid, cursor_doc.doc.into_read_only()));
+		}
+		Ok(truthy)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+	use crate::kvs::Datastore;
+	use crate::kvs::LockType::Optimistic;
+	use crate::kvs::TransactionType::Write;
+
+	async fn new_ctx(ds: &Datastore) -> FrozenContext {
+		let mut ctx = ds.setup_ctx().unwrap();
+		ctx.set_transaction(ds.transaction(Write, Optimistic).await.unwrap().into());
+		ctx.freeze()
+	}
+
+	async fn state(ds: &Datastore, ivfpq: &IvfPqIndex) -> Option<IvfPqState> {
+		let ctx = new_ctx(ds).await;
+		let state = ctx.tx().get(&ivfpq.ikb.new_qs_key(), None).await.unwrap();
+		ctx.tx().cancel().await.unwrap();
+		state
+	}
+
+	#[tokio::test]
+	async fn train_encodes_documents_in_batches() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ikb = IndexKeyBase::new(NamespaceId(1), DatabaseId(2), "tb".into(), IndexId(3));
+		let p = IvfPqParams {
+			dimension: 4,
+			distance: Distance::Euclidean,
+			vector_type: VectorType::F32,
+			lists: 2,
+			subvectors: 2,
+			bits: 2,
+			probes: 1,
+		};
+		let ivfpq = IvfPqIndex::new(ikb, &p);
+		// Index more documents than a single encoding batch
+		let docs = *INDEXING_BATCH_SIZE as usize + 50;
+		let ctx = new_ctx(&ds).await;
+		for i in 0..docs {
+			let v = Value::from(vec![Value::from(i as f64); 4]);
+			let id = RecordIdKey::Number(i as i64);
+			assert!(ivfpq.index(&ctx, &id, None, Some(vec![v])).await.unwrap());
+		}
+		ctx.tx().commit().await.unwrap();
+		// Every call runs in its own transaction, as the compaction does
+		let mut calls = 0;
+		loop {
+			let ctx = new_ctx(&ds).await;
+			let more = ivfpq.train(&ctx).await.unwrap();
+			ctx.tx().commit().await.unwrap();
+			calls += 1;
+			if !more {
+				break;
+			}
+			// The model is not used by the searches until every document is encoded
+			let state = state(&ds, &ivfpq).await.unwrap();
+			assert!(state.pending.is_some());
+		}
+		// One call trains the model, then two batches encode the documents
+		assert_eq!(calls, 3);
+		let state = state(&ds, &ivfpq).await.unwrap();
+		assert_eq!(state.pending, None);
+		// Every document is in an inverted list
+		let ctx = new_ctx(&ds).await;
+		let tx = ctx.tx();
+		let lists = tx.keys(ivfpq.ikb.new_ql_range().unwrap(), u32::MAX, 0, None).await.unwrap();
+		assert_eq!(lists.len(), docs);
+		// A trained index has nothing left to do
+		assert!(!ivfpq.train(&ctx).await.unwrap());
+		tx.cancel().await.unwrap();
+	}
+}
@@ -0,0 +1,78 @@
+//! IVF-PQ (inverted file with product quantization) vector index.
+//!
+//! Unlike HNSW, which keeps the full-precision vectors and the graph in
+//! memory, an IVF-PQ index keeps only a small model in memory: the coarse
+//! centroids and the product quantization codebooks. Everything else lives
+//! in the key-value store:
+//! - `qs` holds the state of the index, which identifies the current model;
+//! - `qc` and `qb` hold the coarse centroids and the codebooks;
+//! - `qv` holds the full-precision vectors of each document;
+//! - `ql` holds, for every inverted list, the codes of the documents assigned to it.
+//!
+//! The model is trained by the index compaction once enough documents have
+//! been indexed, over a sample of their vectors. The following compactions
+//! then encode the documents into the inverted lists in batches, each in its
+//! own transaction. Until the last batch is encoded, searches are exact
+//! brute-force scans over `qv`, and the model is only swapped in once every
+//! document is encoded. `REBUILD INDEX` wipes the model, so the codebooks are
+//! retrained over the current content of the table.
+pub(crate) mod index;
+mod model;
+
+use revision::revisioned;
+use uuid::Uuid;
+
+use crate::idx::seqdocids::DocId;
+use crate::idx::trees::vector::SerializedVector;
+use crate::kvs::impl_kv_value_revisioned;
+
+/// Persisted state of a trained IVF-PQ index.
+///
+/// The version changes every time the model is trained, which lets every
+/// node detect that its in-memory copy of the model is stale.
+#[revisioned(revision = 2)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IvfPqState {
+	pub(super) version: Uuid,
+	/// While the documents are being encoded into the inverted lists, the
+	/// first document which is not encoded yet. The model is only used by
+	/// searches once this is `None`.
+	#[revision(start = 2, default_fn = "default_pending")]
+	pub(super) pending: Option<DocId>,
+}
+
+impl IvfPqState {
+	fn default_pending(_revision: u16) -> Result<Option<DocId>, revision::Error> {
+		// Models persisted before batched encoding were encoded when trained
+		Ok(None)
+	}
+}
+
+impl_kv_value_revisioned!(IvfPqState);
+
+/// A coarse centroid, or a codeword of a product quantization codebook.
+#[revisioned(revision = 1)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IvfPqCentroid {
+	pub(super) vector: Vec<f32>,
+}
+
+impl_kv_value_revisioned!(IvfPqCentroid);
+
+/// The full-precision vectors of a document.
+#[revisioned(revision = 1)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IvfPqVectors {
+	pub(super) vectors: Vec<SerializedVector>,
+}
+
+impl_kv_value_revisioned!(IvfPqVectors);
+
+/// The product quantization codes of the vectors of a document assigned to an inverted list.
+#[revisioned(revision = 1)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IvfPqCodes {
+	pub(super) codes: Vec<Vec<u8>>,
+}
+
+impl_kv_value_revisioned!(IvfPqCodes);
@@ -0,0 +1,323 @@
+use anyhow::{Result, ensure};
+use rand::Rng;
+use rand::prelude::SmallRng;
+
+use crate::err::Error;
+
+/// Maximum number of Lloyd iterations run by the k-means clustering.
+const KMEANS_ITERATIONS: usize = 16;
+
+/// In-memory IVF-PQ model.
+///
+/// The model is made of the coarse centroids, which partition the vector
+/// space into inverted lists, and of one product quantization codebook per
+/// sub-vector. The codebooks quantize the residual between a vector and the
+/// centroid of its inverted list, so that the distance between a query and a
+/// document can be approximated with a lookup table (asymmetric distance
+/// computation). Only the model lives in memory: the codes and the
+/// full-precision vectors stay in the key-value store.
+#[derive(Debug)]
+pub(super) struct IvfPqModel {
+	/// The coarse centroids, one per inverted list.
+	centroids: Vec<Vec<f32>>,
+	/// The codebooks, one per sub-vector, each containing `ksub` codewords.
+	codebooks: Vec<Vec<Vec<f32>>>,
+	/// The start and end offsets of each sub-vector.
+	bounds: Vec<(usize, usize)>,
+	/// The number of codewords of the codebooks.
+	ksub: usize,
+}
+
+impl IvfPqModel {
+	/// Builds a model from its centroids and codebooks, checking they are consistent.
+	pub(super) fn new(
+		dim: usize,
+		centroids: Vec<Vec<f32>>,
+		codebooks: Vec<Vec<Vec<f32>>>,
+	) -> Result<Self> {
+		let bounds = Self::bounds(dim, codebooks.len());
+		let ksub = codebooks.first().map(Vec::len).unwrap_or(0);
+		ensure!(
+			!centroids.is_empty()
+				&& ksub > 0 && ksub <= 256
+				&& centroids.iter().all(|c| c.len() == dim)
+				&& codebooks.iter().zip(&bounds).all(|(cb, (s, e))| {
+					cb.len() == ksub && cb.iter().all(|c| c.len() == e - s)
+				}),
+			Error::Unreachable("Inconsistent IVF-PQ model".to_owned())
+		);
+		Ok(Self {
+			centroids,
+			codebooks,
+			bounds,
+			ksub,
+		})
+	}
+
+	/// Trains a model over the given sample of vectors.
+	///
+	/// The number of lists and of codewords is capped by the size of the
+	/// sample, so a small sample produces a smaller, but still valid, model.
+	pub(super) fn train(
+		dim: usize,
+		vectors: &[Vec<f32>],
+		lists: usize,
+		subvectors: usize,
+		bits: u8,
+		rng: &mut SmallRng,
+	) -> Result<Self> {
+		let points: Vec<&[f32]> = vectors.iter().map(Vec::as_slice).collect();
+		let centroids = kmeans(&points, lists, rng);
+		// The codebooks are trained over the residuals
+		let residuals: Vec<Vec<f32>> = vectors
+			.iter()
+			.map(|v| {
+				let c = &centroids[nearest(&centroids, v)];
+				v.iter().zip(c).map(|(a, b)| a - b).collect()
+			})
+			.collect();
+		let bounds = Self::bounds(dim, subvectors);
+		let mut codebooks = Vec::with_capacity(subvectors);
+		for (s, e) in &bounds {
+			let points: Vec<&[f32]> = residuals.iter().map(|r| &r[*s..*e]).collect();
+			codebooks.push(kmeans(&points, 1 << bits, rng));
+		}
+		Self::new(dim, centroids, codebooks)
+	}
+
+	/// Splits `dim` dimensions into `subvectors` contiguous ranges of (almost) equal size.
+	fn bounds(dim: usize, subvectors: usize) -> Vec<(usize, usize)> {
+		let mut bounds = Vec::with_capacity(subvectors);
+		if subvectors == 0 {
+			return bounds;
+		}
+		let (size, rem) = (dim / subvectors, dim % subvectors);
+		let mut start = 0;
+		for i in 0..subvectors {
+			let end = start + size + usize::from(i < rem);
+			bounds.push((start, end));
+			start = end;
+		}
+		bounds
+	}
+
+	pub(super) fn centroids(&self) -> &[Vec<f32>] {
+		&self.centroids
+	}
+
+	pub(super) fn codebooks(&self) -> &[Vec<Vec<f32>>] {
+		&self.codebooks
+	}
+
+	/// Returns the inverted list a vector belongs to.
+	pub(super) fn assign(&self, v: &[f32]) -> u16 {
+		nearest(&self.centroids, v) as u16
+	}
+
+	/// Returns the `probes` inverted lists closest to the vector.
+	pub(super) fn nearest_lists(&self, v: &[f32], probes: usize) -> Vec<u16> {
+		let mut lists: Vec<(f32, u16)> =
+			self.centroids.iter().enumerate().map(|(i, c)| (squared_l2(v, c), i as u16)).collect();
+		lists.sort_by(|a, b| a.0.total_cmp(&b.0));
+		lists.into_iter().take(probes.max(1)).map(|(_, l)| l).collect()
+	}
+
+	/// Encodes the residual of a vector relative to the centroid of the given list.
+	pub(super) fn encode(&self, v: &[f32], list: u16) -> Vec<u8> {
+		let c = &self.centroids[list as usize];
+		let residual: Vec<f32> = v.iter().zip(c).map(|(a, b)| a - b).collect();
+		self.codebooks
+			.iter()
+			.zip(&self.bounds)
+			.map(|(cb, (s, e))| nearest(cb, &residual[*s..*e]) as u8)
+			.collect()
+	}
+
+	/// Computes the table of the squared distances between the residual of
+	/// the query (relative to the given list) and every codeword.
+	pub(super) fn lookup_table(&self, q: &[f32], list: u16) -> Vec<f32> {
+		let c = &self.centroids[list as usize];
+		let residual: Vec<f32> = q.iter().zip(c).map(|(a, b)| a - b).collect();
+		let mut table = Vec::with_capacity(self.codebooks.len() * self.ksub);
+		for (cb, (s, e)) in self.codebooks.iter().zip(&self.bounds) {
+			let r = &residual[*s..*e];
+			table.extend(cb.iter().map(|w| squared_l2(r, w)));
+		}
+		table
+	}
+
+	/// Approximates the squared distance between the query of a lookup table and some codes.
+	pub(super) fn approximate_distance(&self, table: &[f32], codes: &[u8]) -> f32 {
+		codes.iter().enumerate().map(|(s, c)| table[s * self.ksub + *c as usize]).sum()
+	}
+}
+
+fn squared_l2(a: &[f32], b: &[f32]) -> f32 {
+	a.iter()
+		.zip(b)
+		.map(|(x, y)| {
+			let d = x - y;
+			d * d
+		})
+		.sum()
+}
+
+/// Returns the position of the centroid closest to the vector.
+fn nearest(centroids: &[Vec<f32>], v: &[f32]) -> usize {
+	let mut best = (f32::INFINITY, 0);
+	for (i, c) in centroids.iter().enumerate() {
+		let d = squared_l2(v, c);
+		if d < best.0 {
+			best = (d, i);
+		}
+	}
+	best.1
+}
+
+/// Clusters the points into (at most) `k` centroids using Lloyd's algorithm.
+///
+/// The centroids are seeded with k-means++. A centroid which ends up
+/// without any point is reseeded with a random point.
+fn kmeans(points: &[&[f32]], k: usize, rng: &mut SmallRng) -> Vec<Vec<f32>> {
+	let k = k.min(points.len());
+	if k == 0 {
+		return vec![];
+	}
+	let dim = points[0].len();
+	let mut centroids = kmeans_plus_plus(points, k, rng);
+	let mut assignments = vec![usize::MAX; points.len()];
+	for _ in 0..KMEANS_ITERATIONS {
+		let mut changed = false;
+		for (a, p) in assignments.iter_mut().zip(points) {
+			let c = nearest(&centroids, p);
+			if *a != c {
+				*a = c;
+				changed = true;
+			}
+		}
+		if !changed {
+			break;
+		}
+		let mut sums = vec![vec![0f64; dim]; k];
+		let mut counts = vec![0usize; k];
+		for (a, p) in assignments.iter().zip(points) {
+			counts[*a] += 1;
+			for (s, x) in sums[*a].iter_mut().zip(p.iter()) {
+				*s += *x as f64;
+			}
+		}
+		for ((c, s), n) in centroids.iter_mut().zip(sums).zip(counts) {
+			if n == 0 {
+				*c = points[rng.gen_range(0..points.len())].to_vec();
+			} else {
+				*c = s.into_iter().map(|x| (x / n as f64) as f32).collect();
+			}
+		}
+	}
+	centroids
+}
+
+/// Picks `k` initial centroids, each point being chosen with a probability
+/// proportional to its squared distance to the closest centroid already picked.
+fn kmeans_plus_plus(points: &[&[f32]], k: usize, rng: &mut SmallRng) -> Vec<Vec<f32>> {
+	let mut centroids = Vec::with_capacity(k);
+	centroids.push(points[rng.gen_range(0..points.len())].to_vec());
+	let mut dists: Vec<f32> = points.iter().map(|p| squared_l2(p, &centroids[0])).collect();
+	while centroids.len() < k {
+		let total: f64 = dists.iter().map(|d| *d as f64).sum();
+		let next = if total > 0.0 {
+			let mut target = rng.gen_range(0.0..total);
+			dists
+				.iter()
+				.position(|d| {
+					target -= *d as f64;
+					target < 0.0
+				})
+				.unwrap_or(points.len() - 1)
+		} else {
+			// Every point is already a centroid
+			rng.gen_range(0..points.len())
+		};
+		let c = points[next].to_vec();
+		for (d, p) in dists.iter_mut().zip(points) {
+			*d = d.min(squared_l2(p, &c));
+		}
+		centroids.push(c);
+	}
+	centroids
+}
+
+#[cfg(test)]
+mod tests {
+	use rand::SeedableRng;
+	use rand::prelude::SmallRng;
+
+	use super::*;
+
+	fn clustered_vectors(rng: &mut SmallRng) -> Vec<Vec<f32>> {
+		let centers = [[0.0, 0.0, 0.0, 0.0], [10.0, 10.0, 10.0, 10.0], [-10.0, 10.0, -10.0, 10.0]];
+		let mut vectors = Vec::new();
+		for c in centers {
+			for _ in 0..50 {
+				vectors.push(c.iter().map(|x| x + rng.gen_range(-0.5..0.5)).collect());
+			}
+		}
+		vectors
+	}
+
+	#[test]
+	fn kmeans_finds_clusters() {
+		let mut rng = SmallRng::seed_from_u64(7);
+		let vectors = clustered_vectors(&mut rng);
+		let points: Vec<&[f32]> = vectors.iter().map(Vec::as_slice).collect();
+		let centroids = kmeans(&points, 3, &mut rng);
+		assert_eq!(centroids.len(), 3);
+		// Each cluster is captured by a single centroid
+		for chunk in vectors.chunks(50) {
+			let c = nearest(&centroids, &chunk[0]);
+			assert!(chunk.iter().all(|v| nearest(&centroids, v) == c));
+		}
+		// Fewer points than centroids
+		let centroids = kmeans(&points[0..2], 3, &mut rng);
+		assert_eq!(centroids.len(), 2);
+	}
+
+	#[test]
+	fn bounds() {
+		assert_eq!(IvfPqModel::bounds(8, 4), vec![(0, 2), (2, 4), (4, 6), (6, 8)]);
+		assert_eq!(IvfPqModel::bounds(7, 3), vec![(0, 3), (3, 5), (5, 7)]);
+		assert_eq!(IvfPqModel::bounds(3, 3), vec![(0, 1), (1, 2), (2, 3)]);
+	}
+
+	#[test]
+	fn train_encode_and_approximate() {
+		let mut rng = SmallRng::seed_from_u64(11);
+		let vectors = clustered_vectors(&mut rng);
+		let model = IvfPqModel::train(4, &vectors, 3, 2, 4, &mut rng).unwrap();
+		assert_eq!(model.centroids().len(), 3);
+		assert_eq!(model.codebooks().len(), 2);
+		assert!(model.codebooks().iter().all(|cb| cb.len() == 16));
+		for v in &vectors {
+			let list = model.assign(v);
+			assert_eq!(model.nearest_lists(v, 1), vec![list]);
+			let codes = model.encode(v, list);
+			assert_eq!(codes.len(), 2);
+			// The approximated distance of a vector to itself is small
+			let table = model.lookup_table(v, list);
+			assert!(model.approximate_distance(&table, &codes) < 1.0);
+		}
+		// Probing more lists returns them by increasing distance
+		let q = &vectors[0];
+		let list = model.assign(q);
+		let lists = model.nearest_lists(q, 5);
+		assert_eq!(lists.len(), 3);
+		assert_eq!(lists[0], list);
+	}
+
+	#[test]
+	fn inconsistent_model() {
+		assert!(IvfPqModel::new(2, vec![vec![0.0, 0.0]], vec![vec![vec![0.0]]]).is_err());
+		assert!(IvfPqModel::new(2, vec![], vec![vec![vec![0.0, 0.0]]]).is_err());
+		assert!(IvfPqModel::new(2, vec![vec![0.0, 0.0]], vec![vec![vec![0.0, 0.0]]]).is_ok());
+	}
+}
@@ -1,6 +1,7 @@
 pub mod dynamicset;
 mod graph;
 pub mod hnsw;
+pub mod ivfpq;
 pub(in crate::idx) mod knn;
 pub mod store;
 pub mod vector;
@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use ahash::HashSet;
+use anyhow::{Result, bail};
+use dashmap::DashMap;
+
+use crate::catalog;
+use crate::err::Error;
+use crate::expr::Tokenizer;
+use crate::iam::file::is_path_allowed;
+use crate::idx::ft::analyzer::dictionary::Dictionary;
+
+#[derive(Default)]
+pub(crate) struct Dictionaries(DashMap<String, Dictionary>);
+
+/// Returns the dictionary paths used by the tokenizers of an analyzer.
+fn paths(az: &catalog::AnalyzerDefinition) -> impl Iterator<Item = &String> {
+	az.tokenizers.iter().flatten().filter_map(|t| match t {
+		Tokenizer::Cjk(Some(path)) => Some(path),
+		_ => None,
+	})
+}
+
+impl Dictionaries {
+	/// If any dictionary is defined, it will be loaded in memory.
+	pub(crate) async fn load(&self, az: &catalog::AnalyzerDefinition) -> Result<()> {
+		for path in paths(az) {
+			self.insert(path).await?;
+		}
+		Ok(())
+	}
+
+	/// Ensure that if a dictionary is defined, that it is also loaded in
+	/// memory. This method does not reload a dictionary if it is already in
+	/// memory.
+	pub(crate) async fn check(&self, az: &catalog::AnalyzerDefinition) -> Result<()> {
+		for path in paths(az) {
+			if !self.0.contains_key(path) {
+				self.insert(path).await?;
+			}
+		}
+		Ok(())
+	}
+
+	async fn insert(&self, path: &str) -> Result<()> {
+		let p = Path::new(path);
+		// Check the path is allowed
+		is_path_allowed(p)?;
+		if !p.exists() || !p.is_file() {
+			bail!(Error::Internal(format!("Invalid dictionary path: {p:?}")));
+		}
+		let dictionary = Dictionary::new(p).await?;
+		self.0.insert(path.to_string(), dictionary);
+		Ok(())
+	}
+
+	pub(in crate::idx) fn get(&self, path: &str) -> Result<Dictionary> {
+		match self.0.get(path) {
+			None => {
+				Err(anyhow::Error::new(Error::Internal(format!("Dictionary not found for {path}"))))
+			}
+			Some(e) => Ok(e.value().clone()),
+		}
+	}
+
+	pub(crate) fn cleanup(&self, azs: &[catalog::AnalyzerDefinition]) {
+		// Collect every existing dictionary
+		let mut keys: HashSet<String> = self.0.iter().map(|e| e.key().clone()).collect();
+		// Remove keys that still exist in the definitions
+		for az in azs {
+			for path in paths(az) {
+				keys.remove(path);
+			}
+		}
+		// Any left key can be removed
+		for key in keys {
+			self.0.remove(&key);
+		}
+	}
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+
+use crate::catalog::{DatabaseId, IndexId, IvfPqParams, NamespaceId, TableId};
+use crate::idx::IndexKeyBase;
+use crate::idx::trees::ivfpq::index::IvfPqIndex;
+
+/// A shared reference to an [`IvfPqIndex`].
+pub(crate) type SharedIvfPqIndex = Arc<IvfPqIndex>;
+
+type SharedIvfPqKey = (NamespaceId, DatabaseId, TableId, IndexId);
+
+/// Registry of all active IVF-PQ indexes, keyed by `(NamespaceId, DatabaseId, TableId, IndexId)`.
+///
+/// Only the trained model of an IVF-PQ index is held in memory, so keeping
+/// the instances around is what allows the model to be loaded once per node.
+pub(crate) struct IvfPqIndexes(Arc<RwLock<HashMap<SharedIvfPqKey, SharedIvfPqIndex>>>);
+
+impl Default for IvfPqIndexes {
+	fn default() -> Self {
+		Self(Arc::new(RwLock::new(HashMap::new())))
+	}
+}
+
+impl IvfPqIndexes {
+	/// Retrieves or lazily creates an IVF-PQ index for the given table and index key.
+	pub(super) async fn get(
+		&self,
+		tb: TableId,
+		ikb: &IndexKeyBase,
+		p: &IvfPqParams,
+	) -> SharedIvfPqIndex {
+		let key = (ikb.ns(), ikb.db(), tb, ikb.index());
+		let i = self.0.read().await.get(&key).cloned();
+		if let Some(i) = i {
+			return i;
+		}
+		match self.0.write().await.entry(key) {
+			Entry::Occupied(e) => e.get().clone(),
+			Entry::Vacant(e) => e.insert(Arc::new(IvfPqIndex::new(ikb.clone(), p))).clone(),
+		}
+	}
+
+	/// Removes an IVF-PQ index from the registry.
+	pub(super) async fn remove(&self, tb: TableId, ikb: &IndexKeyBase) {
+		let key = (ikb.ns(), ikb.db(), tb, ikb.index());
+		self.0.write().await.remove(&key);
+	}
+}
@@ -1,4 +1,6 @@
+mod dictionary;
 pub(crate) mod hnsw;
+pub(crate) mod ivfpq;
 mod mapper;
 
 use std::sync::Arc;
@@ -7,12 +9,15 @@ use anyhow::Result;
 
 use crate::catalog::providers::{DatabaseProvider, TableProvider};
 use crate::catalog::{
-	DatabaseId, HnswParams, Index, IndexDefinition, NamespaceId, TableDefinition, TableId,
+	DatabaseId, HnswParams, Index, IndexDefinition, IvfPqParams, NamespaceId, TableDefinition,
+	TableId,
 };
 use crate::ctx::FrozenContext;
 use crate::idx::IndexKeyBase;
 use crate::idx::trees::hnsw::cache::VectorCache;
+use crate::idx::trees::store::dictionary::Dictionaries;
 use crate::idx::trees::store::hnsw::{HnswIndexes, SharedHnswIndex};
+use crate::idx::trees::store::ivfpq::{IvfPqIndexes, SharedIvfPqIndex};
 use crate::idx::trees::store::mapper::Mappers;
 use crate::kvs::Transaction;
 use crate::kvs::index::IndexBuilder;
@@ -22,7 +27,9 @@ pub struct IndexStores(Arc<Inner>);
 
 struct Inner {
 	hnsw_indexes: HnswIndexes,
+	ivfpq_indexes: IvfPqIndexes,
 	mappers: Mappers,
+	dictionaries: Dictionaries,
 	vector_cache: VectorCache,
 }
 
@@ -30,7 +37,9 @@ impl Default for IndexStores {
 	fn default() -> Self {
 		Self(Arc::new(Inner {
 			hnsw_indexes: HnswIndexes::default(),
+			ivfpq_indexes: IvfPqIndexes::default(),
 			mappers: Mappers::default(),
+			dictionaries: Dictionaries::default(),
 			vector_cache: VectorCache::default(),
 		}))
 	}
@@ -50,6 +59,18 @@ impl IndexStores {
 		self.0.hnsw_indexes.get(ctx, tb, &ikb, p).await
 	}
 
+	pub(crate) async fn get_index_ivfpq(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: TableId,
+		ix: &IndexDefinition,
+		p: &IvfPqParams,
+	) -> SharedIvfPqIndex {
+		let ikb = IndexKeyBase::new(ns, db, ix.table_name.clone(), ix.index_id);
+		self.0.ivfpq_indexes.get(tb, &ikb, p).await
+	}
+
 	pub(crate) async fn index_removed(
 		&self,
 		ib: Option<&IndexBuilder>,
@@ -113,9 +134,16 @@ impl IndexStores {
 		tb: TableId,
 		ix: &IndexDefinition,
 	) -> Result<()> {
-		if matches!(ix.index, Index::Hnsw(_)) {
-			let ikb = IndexKeyBase::new(ns, db, ix.table_name.clone(), ix.index_id);
-			self.remove_hnsw_index(tb, ikb).await?;
+		match ix.index {
+			Index::Hnsw(_) => {
+				let ikb = IndexKeyBase::new(ns, db, ix.table_name.clone(), ix.index_id);
+				self.remove_hnsw_index(tb, ikb).await?;
+			}
+			Index::IvfPq(_) => {
+				let ikb = IndexKeyBase::new(ns, db, ix.table_name.clone(), ix.index_id);
+				self.0.ivfpq_indexes.remove(tb, &ikb).await;
+			}
+			_ => {}
 		}
 		Ok(())
 	}
@@ -130,6 +158,10 @@ impl IndexStores {
 		&self.0.mappers
 	}
 
+	pub(crate) fn dictionaries(&self) -> &Dictionaries {
+		&self.0.dictionaries
+	}
+
 	pub(crate) fn vector_cache(&self) -> &VectorCache {
 		&self.0.vector_cache
 	}
@@ -654,6 +654,17 @@ impl Vector {
 	pub(super) fn check_dimension(&self, expected_dim: usize) -> Result<()> {
 		Self::check_expected_dimension(self.len(), expected_dim)
 	}
+
+	/// Converts the vector into single precision components.
+	pub(super) fn to_f32_vec(&self) -> Vec<f32> {
+		match self {
+			Self::F64(v) => v.iter().map(|x| *x as f32).collect(),
+			Self::F32(v) => v.to_vec(),
+			Self::I64(v) => v.iter().map(|x| *x as f32).collect(),
+			Self::I32(v) => v.iter().map(|x| *x as f32).collect(),
+			Self::I16(v) => v.iter().map(|x| *x as f32).collect(),
+		}
+	}
 }
 
 impl Distance {
@@ -0,0 +1,148 @@
+//! Scheduled jobs, defined with `DEFINE JOB`.
+//!
+//! Jobs are run by [`Datastore::run_jobs`](crate::kvs::Datastore::run_jobs),
+//! which is called periodically on every node, and coordinated through a task
+//! lease so that a single node runs the jobs at a time. The time each job is
+//! next due, and the history of its recent runs, are stored alongside the job
+//! definition as a [`JobState`].
+//!
+//! A job which is overdue, for example because no node was running when it
+//! was due, is run once, and then scheduled from the current time.
+
+mod schedule;
+
+use anyhow::{Result, bail};
+use tracing::warn;
+
+pub(crate) use self::schedule::Schedule;
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider};
+use crate::catalog::{DatabaseDefinition, JobDefinition, JobRun, JobState, NamespaceDefinition};
+use crate::cnf::JOB_HISTORY_SIZE;
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::iam::{Level, Role};
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{Datastore, LockType, TransactionType};
+use crate::val::Datetime;
+
+/// Parses the schedule of a job
+pub(crate) fn parse_schedule(schedule: &str) -> Result<Schedule> {
+	match schedule.parse() {
+		Ok(v) => Ok(v),
+		Err(e) => bail!(Error::Query {
+			message: format!("`{schedule}` is not a valid job schedule: {e}"),
+		}),
+	}
+}
+
+/// Calculates when a job with the given schedule is next due after a time
+pub(crate) fn next_run(schedule: &str, after: &Datetime) -> Result<Option<Datetime>> {
+	Ok(parse_schedule(schedule)?.next_after(after.0).map(Datetime))
+}
+
+/// Runs the jobs which are due in every database.
+///
+/// Returns the number of jobs which were run. A job which fails to run is
+/// recorded in its history, and does not hold back the other jobs.
+pub(crate) async fn run(ds: &Datastore, lh: &LeaseHandler) -> Result<usize> {
+	let mut count = 0;
+	let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	let nss = catch!(txn, txn.all_ns(None).await);
+	txn.cancel().await?;
+	for ns in nss.iter() {
+		let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let dbs = catch!(txn, txn.all_db(ns.namespace_id, None).await);
+		txn.cancel().await?;
+		for db in dbs.iter() {
+			let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+			let jbs = catch!(txn, txn.all_db_jobs(ns.namespace_id, db.database_id, None).await);
+			txn.cancel().await?;
+			for jb in jbs.iter().filter(|jb| !jb.paused) {
+				lh.try_maintain_lease().await?;
+				match run_job(ds, ns, db, jb).await {
+					Ok(true) => count += 1,
+					Ok(false) => {}
+					Err(e) => warn!("Failed to run job {}/{}/{}: {e}", ns.name, db.name, jb.name),
+				}
+			}
+		}
+	}
+	Ok(count)
+}
+
+/// Runs a single job if it is due, returning whether it was run
+async fn run_job(
+	ds: &Datastore,
+	ns: &NamespaceDefinition,
+	db: &DatabaseDefinition,
+	jb: &JobDefinition,
+) -> Result<bool> {
+	let key = crate::key::database::js::new(ns.namespace_id, db.database_id, &jb.name);
+	// Check whether the job is due
+	let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	let state = catch!(txn, txn.get(&key, None).await);
+	txn.cancel().await?;
+	let Some(state) = state else {
+		// The job has not been scheduled yet
+		return Ok(false);
+	};
+	let now = Datetime::now();
+	let Some(scheduled) = state.next.filter(|next| next <= &now) else {
+		return Ok(false);
+	};
+	// Run the job as an owner of its database
+	let sess = Session::for_level(Level::Database(ns.name.clone(), db.name.clone()), Role::Owner);
+	let started = Datetime::now();
+	let res = ds.evaluate(&jb.then, &sess, None).await;
+	let finished = Datetime::now();
+	if let Err(e) = &res {
+		warn!("Job {}/{}/{} failed: {e}", ns.name, db.name, jb.name);
+	}
+	// Record the run, and when the job is next due
+	let txn = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+	let jbk = crate::key::database::jb::new(ns.namespace_id, db.database_id, &jb.name);
+	let Some(jb) = catch!(txn, txn.get(&jbk, None).await) else {
+		// The job was removed while it was running
+		txn.cancel().await?;
+		return Ok(true);
+	};
+	let mut state = match catch!(txn, txn.get(&key, None).await) {
+		// The job may have been redefined, or resumed, while it was running
+		Some(mut state) => {
+			if state.next.as_ref() == Some(&scheduled) {
+				state.next = catch!(txn, next_run(&jb.schedule, &finished));
+			}
+			state
+		}
+		// The state was removed while the job was running, so schedule the
+		// job again from this run
+		None => JobState {
+			next: catch!(txn, next_run(&jb.schedule, &finished)),
+			runs: Vec::new(),
+		},
+	};
+	state.runs.insert(
+		0,
+		JobRun {
+			scheduled,
+			started,
+			finished,
+			node: ds.id(),
+			error: res.err().map(|e| e.to_string()),
+		},
+	);
+	state.runs.truncate(*JOB_HISTORY_SIZE);
+	catch!(txn, txn.set(&key, &state).await);
+	txn.commit().await?;
+	Ok(true)
+}
+
+impl JobState {
+	/// The state of a job which was defined, or resumed, at the given time
+	pub(crate) fn scheduled(self, schedule: &str, now: &Datetime) -> Result<Self> {
+		Ok(JobState {
+			next: next_run(schedule, now)?,
+			..self
+		})
+	}
+}
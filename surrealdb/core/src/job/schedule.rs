@@ -0,0 +1,266 @@
+//! Cron schedules for `DEFINE JOB`.
+//!
+//! A schedule is a standard five field cron expression, evaluated in UTC:
+//!
+//! ```text
+//! ┌───────────── minute (0-59)
+//! │ ┌─────────── hour (0-23)
+//! │ │ ┌───────── day of the month (1-31)
+//! │ │ │ ┌─────── month (1-12 or JAN-DEC)
+//! │ │ │ │ ┌───── day of the week (0-7 or SUN-SAT, where 0 and 7 are Sunday)
+//! │ │ │ │ │
+//! * * * * *
+//! ```
+//!
+//! Each field accepts `*`, single values, ranges (`1-5`), steps (`*/15`,
+//! `0-30/10`) and comma separated lists of these. The `@yearly`,
+//! `@annually`, `@monthly`, `@weekly`, `@daily`, `@midnight` and `@hourly`
+//! shorthands are also accepted. As with cron, when both the day of the month
+//! and the day of the week are restricted, a day matching either of them
+//! matches the schedule.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+
+const MONTHS: [&str; 12] =
+	["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// How many years ahead to search for the next run of a schedule
+const SEARCH_YEARS: i32 = 5;
+
+/// A parsed cron schedule
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Schedule {
+	minutes: u64,
+	hours: u64,
+	days: u64,
+	months: u64,
+	weekdays: u64,
+	/// Whether the day of the month field was unrestricted
+	any_day: bool,
+	/// Whether the day of the week field was unrestricted
+	any_weekday: bool,
+}
+
+impl FromStr for Schedule {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let expr = match s.to_ascii_lowercase().as_str() {
+			"@yearly" | "@annually" => "0 0 1 1 *",
+			"@monthly" => "0 0 1 * *",
+			"@weekly" => "0 0 * * 0",
+			"@daily" | "@midnight" => "0 0 * * *",
+			"@hourly" => "0 * * * *",
+			x if x.starts_with('@') => return Err(format!("unknown schedule shorthand `{s}`")),
+			_ => s,
+		};
+		let fields: Vec<&str> = expr.split_whitespace().collect();
+		let [minute, hour, day, month, weekday] = fields.as_slice() else {
+			return Err(format!("expected 5 fields in the schedule, found {}", fields.len()));
+		};
+		// Sunday may be written as either 0 or 7
+		let mut weekdays = parse_field(weekday, "day of the week", 0, 7, &WEEKDAYS)?;
+		if weekdays & (1 << 7) != 0 {
+			weekdays = (weekdays | 1) & !(1 << 7);
+		}
+		Ok(Schedule {
+			minutes: parse_field(minute, "minute", 0, 59, &[])?,
+			hours: parse_field(hour, "hour", 0, 23, &[])?,
+			days: parse_field(day, "day of the month", 1, 31, &[])?,
+			months: parse_field(month, "month", 1, 12, &MONTHS)?,
+			weekdays,
+			any_day: day.starts_with('*'),
+			any_weekday: weekday.starts_with('*'),
+		})
+	}
+}
+
+impl Schedule {
+	/// Returns the first time the schedule fires strictly after the given time.
+	///
+	/// Returns `None` if the schedule can never fire, such as on the 30th of
+	/// February.
+	pub(crate) fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+		// Start at the beginning of the following minute
+		let mut t = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+		let limit = t.year() + SEARCH_YEARS;
+		while t.year() <= limit {
+			if !self.has(self.months, t.month()) {
+				// Skip to the start of the next month
+				let (y, m) = match t.month() {
+					12 => (t.year() + 1, 1),
+					m => (t.year(), m + 1),
+				};
+				t = Utc.with_ymd_and_hms(y, m, 1, 0, 0, 0).single()?;
+				continue;
+			}
+			if !self.matches_day(t.date_naive()) {
+				// Skip to the start of the next day
+				t = t.date_naive().succ_opt()?.and_hms_opt(0, 0, 0)?.and_utc();
+				continue;
+			}
+			if !self.has(self.hours, t.hour()) {
+				// Skip to the start of the next hour
+				t = t.with_minute(0)? + Duration::hours(1);
+				continue;
+			}
+			if !self.has(self.minutes, t.minute()) {
+				t += Duration::minutes(1);
+				continue;
+			}
+			return Some(t);
+		}
+		None
+	}
+
+	fn has(&self, set: u64, v: u32) -> bool {
+		set & (1 << v) != 0
+	}
+
+	fn matches_day(&self, date: NaiveDate) -> bool {
+		let day = self.has(self.days, date.day());
+		let weekday = self.has(self.weekdays, date.weekday().num_days_from_sunday());
+		if self.any_day || self.any_weekday {
+			day && weekday
+		} else {
+			day || weekday
+		}
+	}
+}
+
+/// Parses a single field of a cron expression into a bitset of its values
+fn parse_field(field: &str, name: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+	let mut set = 0u64;
+	for part in field.split(',') {
+		let (range, step) = match part.split_once('/') {
+			Some((range, step)) => match step.parse::<u32>() {
+				Ok(step) if step > 0 => (range, Some(step)),
+				_ => return Err(format!("invalid step `{step}` in the {name} field")),
+			},
+			None => (part, None),
+		};
+		let (beg, end) = if range == "*" {
+			(min, max)
+		} else if let Some((beg, end)) = range.split_once('-') {
+			(parse_value(beg, name, names)?, parse_value(end, name, names)?)
+		} else {
+			let v = parse_value(range, name, names)?;
+			// A single value with a step runs until the end of the range
+			(
+				v,
+				if step.is_some() {
+					max
+				} else {
+					v
+				},
+			)
+		};
+		if beg < min || end > max || beg > end {
+			return Err(format!(
+				"`{range}` is out of range for the {name} field, which must be within {min}-{max}"
+			));
+		}
+		for v in (beg..=end).step_by(step.unwrap_or(1) as usize) {
+			set |= 1 << v;
+		}
+	}
+	Ok(set)
+}
+
+/// Parses a single value of a cron field, either as a number or a name
+fn parse_value(v: &str, name: &str, names: &[&str]) -> Result<u32, String> {
+	if let Ok(v) = v.parse::<u32>() {
+		return Ok(v);
+	}
+	match names.iter().position(|n| n.eq_ignore_ascii_case(v)) {
+		// Months are numbered from 1, and days of the week from 0
+		Some(i) if names.len() == 12 => Ok(i as u32 + 1),
+		Some(i) => Ok(i as u32),
+		None => Err(format!("invalid value `{v}` in the {name} field")),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn next(schedule: &str, after: &str) -> Option<String> {
+		let schedule: Schedule = schedule.parse().unwrap();
+		let after = DateTime::parse_from_rfc3339(after).unwrap().with_timezone(&Utc);
+		schedule.next_after(after).map(|v| v.to_rfc3339())
+	}
+
+	#[test]
+	fn parse_invalid() {
+		for s in [
+			"",
+			"* * * *",
+			"* * * * * *",
+			"60 * * * *",
+			"* 24 * * *",
+			"* * 0 * *",
+			"*/0 * * * *",
+			"5-1 * * * *",
+			"* * * FOO *",
+			"@often",
+		] {
+			assert!(s.parse::<Schedule>().is_err(), "{s}");
+		}
+	}
+
+	#[test]
+	fn parse_valid() {
+		for s in [
+			"* * * * *",
+			"0 */5 * * *",
+			"0,30 9-17 * * MON-FRI",
+			"15 3 1 jan,jul *",
+			"0 0 * * 7",
+			"@daily",
+			"@HOURLY",
+		] {
+			assert!(s.parse::<Schedule>().is_ok(), "{s}");
+		}
+	}
+
+	#[test]
+	fn next_runs() {
+		assert_eq!(next("* * * * *", "2024-01-01T10:15:30Z").unwrap(), "2024-01-01T10:16:00+00:00");
+		assert_eq!(
+			next("0 */5 * * *", "2024-01-01T10:15:00Z").unwrap(),
+			"2024-01-01T15:00:00+00:00"
+		);
+		assert_eq!(
+			next("*/15 * * * *", "2024-01-01T23:50:00Z").unwrap(),
+			"2024-01-02T00:00:00+00:00"
+		);
+		assert_eq!(next("@monthly", "2024-12-31T12:00:00Z").unwrap(), "2025-01-01T00:00:00+00:00");
+		assert_eq!(
+			next("0 12 29 2 *", "2024-03-01T00:00:00Z").unwrap(),
+			"2028-02-29T12:00:00+00:00"
+		);
+		// Sundays, as either 0 or 7
+		assert_eq!(next("0 0 * * 0", "2024-01-01T00:00:00Z").unwrap(), "2024-01-07T00:00:00+00:00");
+		assert_eq!(
+			next("0 0 * * SUN", "2024-01-01T00:00:00Z").unwrap(),
+			"2024-01-07T00:00:00+00:00"
+		);
+		assert_eq!(next("0 0 * * 7", "2024-01-01T00:00:00Z").unwrap(), "2024-01-07T00:00:00+00:00");
+		// Either the day of the month or the day of the week
+		assert_eq!(
+			next("0 0 15 * FRI", "2024-01-01T00:00:00Z").unwrap(),
+			"2024-01-05T00:00:00+00:00"
+		);
+		assert_eq!(
+			next("0 0 15 * FRI", "2024-01-12T00:00:00Z").unwrap(),
+			"2024-01-15T00:00:00+00:00"
+		);
+		// A schedule which never runs
+		assert_eq!(next("0 0 30 2 *", "2024-01-01T00:00:00Z"), None);
+	}
+}
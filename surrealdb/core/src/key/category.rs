@@ -91,12 +91,24 @@ pub enum Category {
 	DatabaseAnalyzer,
 	/// crate::key::database::bu             /*{ns}*{db}!bu{bu}
 	DatabaseBucket,
+	/// crate::key::database::ck             /*{ns}*{db}!ck{cu}
+	DatabaseConsumerCheckpoint,
+	/// crate::key::database::cu             /*{ns}*{db}!cu{cu}
+	DatabaseConsumer,
 	/// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 	DatabaseFunction,
+	/// crate::key::database::jb             /*{ns}*{db}!jb{jb}
+	DatabaseJob,
+	/// crate::key::database::js             /*{ns}*{db}!js{jb}
+	DatabaseJobState,
 	/// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::sd             /*{ns}*{db}!sd{sk}
+	DatabaseSinkState,
+	/// crate::key::database::sk             /*{ns}*{db}!sk{sk}
+	DatabaseSink,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 	DatabaseTable,
 	/// crate::key::database::ts             /*{ns}*{db}!ts{ts}
@@ -116,6 +128,8 @@ pub enum Category {
 	TableRoot,
 	/// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 	TableEvent,
+	/// crate::key::table::ex                /*{ns}*{db}*{tb}!ex{secs}{nanos}{id}
+	TableExpiry,
 	/// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 	TableField,
 	/// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
@@ -124,6 +138,8 @@ pub enum Category {
 	IndexDefinition,
 	/// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
 	TableLiveQuery,
+	/// crate::key::table::st                /*{ns}*{db}*{tb}!st
+	TableStatistics,
 	///
 	/// ------------------------------
 	///
@@ -175,6 +191,18 @@ pub enum Category {
 	IndexFullTextDocIdsSequenceState,
 	/// crate::key::index::iu                /*{ns}*{db}*{tb}+{ix}*iu{uuid}{uuid}{count}
 	IndexCountState,
+	/// crate::key::index::gc                /*{ns}*{db}*{tb}+{ix}!gc{cell}{id}
+	IndexSpatialCell,
+	/// crate::key::index::qs                /*{ns}*{db}*{tb}+{ix}!qs
+	IndexIvfPqState,
+	/// crate::key::index::qc                /*{ns}*{db}*{tb}+{ix}!qc{list}
+	IndexIvfPqCentroid,
+	/// crate::key::index::qb                /*{ns}*{db}*{tb}+{ix}!qb{sub}{code}
+	IndexIvfPqCodebook,
+	/// crate::key::index::qv                /*{ns}*{db}*{tb}+{ix}!qv{id}
+	IndexIvfPqVectors,
+	/// crate::key::index::ql                /*{ns}*{db}*{tb}+{ix}!ql{list}{id}
+	IndexIvfPqList,
 	/// crate::key::index                    /*{ns}*{db}*{tb}+{ix}*{fd}{id}
 	Index,
 	///
@@ -236,9 +264,15 @@ impl Display for Category {
 			Self::DatabaseApi => "DatabaseApi",
 			Self::DatabaseAnalyzer => "DatabaseAnalyzer",
 			Self::DatabaseBucket => "DatabaseBucket",
+			Self::DatabaseConsumerCheckpoint => "DatabaseConsumerCheckpoint",
+			Self::DatabaseConsumer => "DatabaseConsumer",
 			Self::DatabaseFunction => "DatabaseFunction",
+			Self::DatabaseJob => "DatabaseJob",
+			Self::DatabaseJobState => "DatabaseJobState",
 			Self::DatabaseModel => "DatabaseModel",
 			Self::DatabaseParameter => "DatabaseParameter",
+			Self::DatabaseSinkState => "DatabaseSinkState",
+			Self::DatabaseSink => "DatabaseSink",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifierBatch => "DatabaseTableIdentifierBatch",
 			Self::DatabaseTableIdentifierState => "DatabaseTableIdentifierState",
@@ -249,10 +283,12 @@ impl Display for Category {
 			Self::DatabaseConfig => "DatabaseConfig",
 			Self::TableRoot => "TableRoot",
 			Self::TableEvent => "TableEvent",
+			Self::TableExpiry => "TableExpiry",
 			Self::TableField => "TableField",
 			Self::TableView => "TableView",
 			Self::IndexDefinition => "IndexDefinition",
 			Self::TableLiveQuery => "TableLiveQuery",
+			Self::TableStatistics => "TableStatistics",
 			Self::IndexRoot => "IndexRoot",
 			Self::IndexTermDocList => "IndexTermDocList",
 			Self::IndexBTreeNode => "IndexBTreeNode",
@@ -286,6 +322,12 @@ impl Display for Category {
 			Self::IndexTermDocuments => "IndexTermDocuments",
 			Self::IndexCompaction => "IndexCompaction",
 			Self::IndexCountState => "IndexCountState",
+			Self::IndexSpatialCell => "IndexSpatialCell",
+			Self::IndexIvfPqState => "IndexIvfPqState",
+			Self::IndexIvfPqCentroid => "IndexIvfPqCentroid",
+			Self::IndexIvfPqCodebook => "IndexIvfPqCodebook",
+			Self::IndexIvfPqVectors => "IndexIvfPqVectors",
+			Self::IndexIvfPqList => "IndexIvfPqList",
 			Self::EventQueue => "EventQueue",
 			Self::TableIndexIdentifierBatch => "TableIndexIdentifierBatch",
 			Self::TableIndexIdentifierState => "TableIndexIdentifierState",
@@ -0,0 +1,62 @@
+//! Stores the acknowledged checkpoint of a DEFINE CONSUMER definition
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{ConsumerCheckpoint, DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct ConsumerCheckpointKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub cu: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(ConsumerCheckpointKey<'_> => ConsumerCheckpoint);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, cu: &str) -> ConsumerCheckpointKey<'_> {
+	ConsumerCheckpointKey::new(ns, db, cu)
+}
+
+impl Categorise for ConsumerCheckpointKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseConsumerCheckpoint
+	}
+}
+
+impl<'a> ConsumerCheckpointKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, cu: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b'c', // c
+			_e: b'k', // k
+			cu: Cow::Borrowed(cu),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = ConsumerCheckpointKey::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = ConsumerCheckpointKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cktest\0");
+	}
+}
@@ -0,0 +1,86 @@
+//! Stores a DEFINE CONSUMER definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{ConsumerDefinition, DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct ConsumerKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub cu: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(ConsumerKey<'_> => ConsumerDefinition);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, cu: &str) -> ConsumerKey<'_> {
+	ConsumerKey::new(ns, db, cu)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!cu\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!cu\xff");
+	Ok(k)
+}
+
+impl Categorise for ConsumerKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseConsumer
+	}
+}
+
+impl<'a> ConsumerKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, cu: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b'c', // c
+			_e: b'u', // u
+			cu: Cow::Borrowed(cu),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let val = ConsumerKey::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = ConsumerKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cutest\0");
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cu\0");
+	}
+
+	#[test]
+	fn suffix() {
+		let val = super::suffix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cu\xff");
+	}
+}
@@ -0,0 +1,62 @@
+//! Stores the schedule and run history of a DEFINE JOB definition
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, JobState, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct JobStateKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub jb: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(JobStateKey<'_> => JobState);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, jb: &str) -> JobStateKey<'_> {
+	JobStateKey::new(ns, db, jb)
+}
+
+impl Categorise for JobStateKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseJobState
+	}
+}
+
+impl<'a> JobStateKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, jb: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b'j', // j
+			_e: b's', // s
+			jb: Cow::Borrowed(jb),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = JobStateKey::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = JobStateKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!jstest\0");
+	}
+}
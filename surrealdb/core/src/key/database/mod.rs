@@ -5,10 +5,16 @@ pub mod ap;
 pub mod az;
 pub mod bu;
 pub mod cg;
+pub mod ck;
+pub mod cu;
 pub mod fc;
+pub mod jb;
+pub mod js;
 pub mod md;
 pub mod ml;
 pub mod pa;
+pub mod sd;
+pub mod sk;
 pub mod sq;
 pub mod tb;
 pub mod th;
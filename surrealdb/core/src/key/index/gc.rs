@@ -0,0 +1,200 @@
+//! Stores the geohash cells of a spatial index
+//!
+//! A spatial index stores one key per geohash cell covering the bounding box
+//! of an indexed geometry. The cell is stored as a zero-terminated string
+//! followed by the record id, so that all the records within a cell, and all
+//! the records within any of its descendant cells, are contiguous in the
+//! keyspace.
+//!
+//! The value of every key lists all the cells of the record, so that a record
+//! stored in several cells can be returned only once, without keeping track
+//! of the records already returned.
+use std::borrow::Cow;
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::idx::spatial::SpatialEntry;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, Key, impl_kv_key_storekey};
+use crate::val::{IndexFormat, RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct GcPrefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+}
+
+impl_kv_key_storekey!(GcPrefix<'_> => Vec<u8>);
+
+impl<'a> GcPrefix<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, ix: IndexId) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'g',
+			_g: b'c',
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "IndexFormat")]
+pub(crate) struct Gc<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub cell: Cow<'a, str>,
+	pub id: Cow<'a, RecordIdKey>,
+}
+
+impl KVKey for Gc<'_> {
+	type ValueType = SpatialEntry;
+	fn encode_key(&self) -> Result<Vec<u8>> {
+		Ok(storekey::encode_vec_format::<IndexFormat, _>(self)
+			.map_err(|_| crate::err::Error::Unencodable)?)
+	}
+}
+
+impl Categorise for Gc<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexSpatialCell
+	}
+}
+
+impl<'a> Gc<'a> {
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		cell: &'a str,
+		id: &'a RecordIdKey,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'g',
+			_g: b'c',
+			cell: Cow::Borrowed(cell),
+			id: Cow::Borrowed(id),
+		}
+	}
+
+	/// Returns the prefix shared by all the cells of an index.
+	pub(crate) fn prefix(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		ix: IndexId,
+	) -> Result<Key> {
+		GcPrefix::new(ns, db, tb, ix).encode_key()
+	}
+
+	/// Returns the range covering the records stored in exactly the given
+	/// cell, excluding the records stored in its descendant cells.
+	pub(crate) fn cell_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		ix: IndexId,
+		cell: &str,
+	) -> Result<Range<Key>> {
+		let mut beg = GcPrefix::new(ns, db, tb, ix).encode_key()?;
+		beg.extend_from_slice(cell.as_bytes());
+		let mut end = beg.clone();
+		beg.push(0x00);
+		end.push(0x01);
+		Ok(beg..end)
+	}
+
+	/// Returns the range covering the records stored in the given cell and
+	/// in every cell whose geohash starts with it.
+	pub(crate) fn descendants_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		ix: IndexId,
+		cell: &str,
+	) -> Result<Range<Key>> {
+		let mut beg = GcPrefix::new(ns, db, tb, ix).encode_key()?;
+		beg.extend_from_slice(cell.as_bytes());
+		let mut end = beg.clone();
+		end.push(0xff);
+		Ok(beg..end)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String("testid".into());
+		let val = Gc::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "u09t", &id);
+		let enc = Gc::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!gcu09t\0\x03testid\0",
+			"{}",
+			String::from_utf8_lossy(&enc)
+		);
+	}
+
+	#[test]
+	fn cell_range() {
+		let tb = TableName::from("testtb");
+		let rng = Gc::cell_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "u09").unwrap();
+		assert_eq!(rng.start, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!gcu09\0");
+		assert_eq!(rng.end, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!gcu09\x01");
+	}
+
+	#[test]
+	fn descendants_range() {
+		let tb = TableName::from("testtb");
+		let rng =
+			Gc::descendants_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "u09").unwrap();
+		assert_eq!(rng.start, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!gcu09");
+		assert_eq!(rng.end, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!gcu09\xff");
+	}
+}
@@ -15,6 +15,7 @@
 pub mod all;
 pub mod dc;
 pub mod dl;
+pub mod gc;
 pub mod hd;
 pub mod he;
 pub mod hh;
@@ -31,6 +32,11 @@ pub mod ii;
 pub mod ip;
 pub mod is;
 pub mod iu;
+pub mod qb;
+pub mod qc;
+pub mod ql;
+pub mod qs;
+pub mod qv;
 pub mod td;
 pub mod tt;
 
@@ -0,0 +1,100 @@
+//! Stores the product quantization codebooks of an IVF-PQ index
+//!
+//! Each codeword is stored as a separate KV entry keyed by its sub-vector
+//! and its code, so that the codebooks never need to fit within a single
+//! value.
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::ops::Range;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::idx::trees::ivfpq::IvfPqCentroid;
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Debug, Clone, PartialEq, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Qb<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub sub: u16,
+	pub code: u8,
+}
+
+impl_kv_key_storekey!(Qb<'_> => IvfPqCentroid);
+
+impl<'a> Qb<'a> {
+	pub fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		sub: u16,
+		code: u8,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'q',
+			_g: b'b',
+			sub,
+			code,
+		}
+	}
+
+	/// Returns a key range covering the codewords of every sub-vector.
+	pub(crate) fn new_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+	) -> anyhow::Result<Range<Vec<u8>>> {
+		let beg = Self::new(ns, db, tb, ix, 0, 0).encode_key()?;
+		let mut end = Self::new(ns, db, tb, ix, u16::MAX, u8::MAX).encode_key()?;
+		end.push(0xff);
+		Ok(beg..end)
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> anyhow::Result<Qb<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Qb::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), 7, 8);
+		let enc = Qb::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!qb\0\x07\x08",
+			"{}",
+			String::from_utf8_lossy(&enc)
+		);
+	}
+}
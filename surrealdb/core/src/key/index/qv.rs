@@ -0,0 +1,97 @@
+//! Stores the full-precision vectors of the documents of an IVF-PQ index
+//!
+//! The vectors are used to train the model, to rerank the candidates found
+//! through the compressed codes, and to search the index before it is trained.
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::ops::Range;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::idx::seqdocids::DocId;
+use crate::idx::trees::ivfpq::IvfPqVectors;
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Debug, Clone, PartialEq, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Qv<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub doc_id: DocId,
+}
+
+impl_kv_key_storekey!(Qv<'_> => IvfPqVectors);
+
+impl<'a> Qv<'a> {
+	pub fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		doc_id: DocId,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'q',
+			_g: b'v',
+			doc_id,
+		}
+	}
+
+	/// Returns a key range covering the vectors of every document.
+	pub(crate) fn new_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+	) -> anyhow::Result<Range<Vec<u8>>> {
+		let beg = Self::new(ns, db, tb, ix, 0).encode_key()?;
+		let mut end = Self::new(ns, db, tb, ix, DocId::MAX).encode_key()?;
+		end.push(0xff);
+		Ok(beg..end)
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> anyhow::Result<Qv<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Qv::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), 7);
+		let enc = Qv::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!qv\0\0\0\0\0\0\0\x07",
+			"{}",
+			String::from_utf8_lossy(&enc)
+		);
+	}
+}
@@ -16,6 +16,7 @@
 
 use std::borrow::Cow;
 
+use anyhow::Result;
 use roaring::RoaringTreemap;
 use storekey::{BorrowDecode, Encode};
 
@@ -23,7 +24,7 @@ use crate::catalog::{DatabaseId, IndexId, NamespaceId};
 use crate::idx::ft::fulltext::TermDocument;
 use crate::idx::seqdocids::DocId;
 use crate::key::category::{Categorise, Category};
-use crate::kvs::impl_kv_key_storekey;
+use crate::kvs::{KVKey, impl_kv_key_storekey};
 use crate::val::TableName;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
@@ -144,12 +145,95 @@ impl<'a> Td<'a> {
 			id,
 		}
 	}
+
+	/// Creates a key range covering the terms starting with the given prefix.
+	///
+	/// The range contains both the compacted root keys and the per-document
+	/// keys of the terms, ordered by term. An empty prefix covers every term
+	/// of the index, which makes the range usable as a term dictionary.
+	pub(crate) fn prefix_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		prefix: &str,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let mut beg = TdTermsPrefix::new(ns, db, tb, ix).encode_key()?;
+		beg.extend_from_slice(prefix.as_bytes());
+		let mut end = beg.clone();
+		end.push(255);
+		Ok((beg, end))
+	}
+
+	/// Returns a key ordered after every key of the given term, and before
+	/// the keys of the following terms.
+	pub(crate) fn term_end(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		term: &'a str,
+	) -> Result<Vec<u8>> {
+		let mut key = TdRoot::new(ns, db, tb, ix, term).encode_key()?;
+		key.extend([255; 9]);
+		Ok(key)
+	}
+
+	pub fn decode_key(k: &[u8]) -> Result<Td<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Decodes the term of either a term-document key or a root key
+	pub(crate) fn decode_term(k: &[u8]) -> Result<String> {
+		if let Ok(td) = Self::decode_key(k) {
+			return Ok(td.term.into_owned());
+		}
+		let root: TdRoot<'_> = storekey::decode_borrow(k)?;
+		Ok(root.term.into_owned())
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct TdTermsPrefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+}
+
+impl_kv_key_storekey!(TdTermsPrefix<'_> => RoaringTreemap);
+
+impl<'a> TdTermsPrefix<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, ix: IndexId) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b't',
+			_g: b'd',
+		}
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::kvs::KVKey;
 
 	#[test]
 	fn root() {
@@ -169,4 +253,28 @@ mod tests {
 			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdterm\0\0\0\0\0\0\0\0\x81"
 		);
 	}
+
+	#[test]
+	fn prefix_range() {
+		let tb = TableName::from("testtb");
+		let (beg, end) =
+			Td::prefix_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "te").unwrap();
+		assert_eq!(beg, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdte");
+		assert_eq!(end, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdte\xff");
+	}
+
+	#[test]
+	fn decode_term() {
+		let tb = TableName::from("testtb");
+		let root = TdRoot::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term");
+		let enc = TdRoot::encode_key(&root).unwrap();
+		assert_eq!(Td::decode_term(&enc).unwrap(), "term");
+		let td = Td::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term", 129);
+		let enc = Td::encode_key(&td).unwrap();
+		assert_eq!(Td::decode_term(&enc).unwrap(), "term");
+		let end = Td::term_end(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term").unwrap();
+		assert!(enc < end);
+		let next = TdRoot::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "terms");
+		assert!(end < TdRoot::encode_key(&next).unwrap());
+	}
 }
@@ -49,10 +49,16 @@
 //! crate::key::database::ac             /*{ns}*{db}!ac{ac_name}
 //! crate::key::database::az             /*{ns}*{db}!az{az_name}
 //! crate::key::database::bu             /*{ns}*{db}!bu{bu_name}
+//! crate::key::database::ck             /*{ns}*{db}!ck{cu_name} -> ConsumerCheckpoint
+//! crate::key::database::cu             /*{ns}*{db}!cu{cu_name} -> ConsumerDefinition
 //! crate::key::database::fc             /*{ns}*{db}!fn{fc_name}
+//! crate::key::database::jb             /*{ns}*{db}!jb{jb_name} -> JobDefinition
+//! crate::key::database::js             /*{ns}*{db}!js{jb_name} -> JobState
 //! crate::key::database::md             /*{ns}*{db}!md{md_name} -> ModuleDefinition
 //! crate::key::database::ml             /*{ns}*{db}!ml{ml_name}{vn}
 //! crate::key::database::pa             /*{ns}*{db}!pa{pa_name}
+//! crate::key::database::sd             /*{ns}*{db}!sd{sk_name} -> SinkState
+//! crate::key::database::sk             /*{ns}*{db}!sk{sk_name} -> SinkDefinition
 //! crate::key::database::sq             /*{ns}*{db}!sq{sq_name}
 //! crate::key::database::tb             /*{ns}*{db}!tb{tb_name} -> TableDefinition
 //! crate::key::database::ti             /+{ns}*{db}!ti
@@ -66,11 +72,13 @@
 //!
 //! crate::key::table::all               /*{ns}*{db}*{tb_name}
 //! crate::key::table::ev                /*{ns}*{db}*{tb_name}!ev{ev}
+//! crate::key::table::ex                /*{ns}*{db}*{tb_name}!ex{secs}{nanos}{id}
 //! crate::key::table::fd                /*{ns}*{db}*{tb_name}!fd{fd}
 //! crate::key::table::ft                /*{ns}*{db}*{tb_name}!ft{ft}
 //! crate::key::table::ix                /*{ns}*{db}*{tb_name}!il{ix} -> ix_name
 //! crate::key::table::ix                /*{ns}*{db}*{tb_name}!ix{ix_name} -> IndexDefinition
 //! crate::key::table::lq                /*{ns}*{db}*{tb_name}!lq{lq}
+//! crate::key::table::st                /*{ns}*{db}*{tb_name}!st -> TableStatistics
 //!
 //! crate::key::index::all               /*{ns}*{db}*{tb_name}+{ix}
 //! crate::key::index::bc                /*{ns}*{db}*{tb_name}+{ix}!bc{id}
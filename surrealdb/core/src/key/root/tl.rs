@@ -29,6 +29,9 @@ impl Tl {
 			TaskLeaseType::ChangeFeedCleanup => 1,
 			TaskLeaseType::IndexCompaction => 2,
 			TaskLeaseType::EventProcessing => 3,
+			TaskLeaseType::TtlSweep => 4,
+			TaskLeaseType::JobScheduler => 5,
+			TaskLeaseType::SinkDelivery => 6,
 		};
 		Self {
 			__: b'/',
@@ -0,0 +1,130 @@
+//! Stores the expiry time of a record in a table with a time-to-live
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::{Datetime, RecordIdKey, TableName};
+
+/// Ex indexes the records of a table by the time at which they expire, so
+/// that the expired records can be found with a range scan.
+///
+/// The expiry time is encoded as seconds and nanoseconds since the epoch, so
+/// that the keys are ordered by time.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Ex<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub secs: i64,
+	pub nanos: u32,
+	pub id: RecordIdKey,
+}
+
+impl_kv_key_storekey!(Ex<'_> => ());
+
+pub fn new<'a>(
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &'a TableName,
+	expires: &Datetime,
+	id: &RecordIdKey,
+) -> Ex<'a> {
+	Ex::new(ns, db, tb, expires, id.to_owned())
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(b"!ex");
+	Ok(k)
+}
+
+/// Returns the key which sorts before the entries expiring at or after the
+/// given time, and after all the entries expiring before it.
+pub fn suffix(ns: NamespaceId, db: DatabaseId, tb: &TableName, at: &Datetime) -> Result<Vec<u8>> {
+	let mut k = prefix(ns, db, tb)?;
+	// Encoded as storekey encodes the `secs` and `nanos` fields of the key
+	k.extend_from_slice(&((at.timestamp() as u64) ^ (1 << 63)).to_be_bytes());
+	k.extend_from_slice(&at.timestamp_subsec_nanos().to_be_bytes());
+	Ok(k)
+}
+
+impl Categorise for Ex<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableExpiry
+	}
+}
+
+impl<'a> Ex<'a> {
+	pub fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		expires: &Datetime,
+		id: RecordIdKey,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'e',
+			_f: b'x',
+			secs: expires.timestamp(),
+			nanos: expires.timestamp_subsec_nanos(),
+			id,
+		}
+	}
+
+	pub fn decode_key(k: &[u8]) -> Result<Ex<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::{DateTime, Utc};
+
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let at = Datetime(DateTime::<Utc>::from_timestamp(1, 2).unwrap());
+		let val = Ex::new(NamespaceId(1), DatabaseId(2), &tb, &at, RecordIdKey::Number(3));
+		let enc = Ex::encode_key(&val).unwrap();
+		assert!(enc.starts_with(
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!ex\x80\0\0\0\0\0\0\x01\0\0\0\x02"
+		));
+		assert_eq!(Ex::decode_key(&enc).unwrap(), val);
+	}
+
+	#[test]
+	fn ordered_by_expiry() {
+		let tb = TableName::from("testtb");
+		let (ns, db) = (NamespaceId(1), DatabaseId(2));
+		let early = Datetime(DateTime::<Utc>::from_timestamp(-10, 5).unwrap());
+		let late = Datetime(DateTime::<Utc>::from_timestamp(10, 0).unwrap());
+		let beg = super::prefix(ns, db, &tb).unwrap();
+		let end = super::suffix(ns, db, &tb, &late).unwrap();
+		let early = Ex::new(ns, db, &tb, &early, RecordIdKey::Number(9)).encode_key().unwrap();
+		let late = Ex::new(ns, db, &tb, &late, RecordIdKey::Number(1)).encode_key().unwrap();
+		assert!(beg <= early && early < end);
+		assert!(end <= late);
+	}
+}
@@ -1,8 +1,10 @@
 pub mod all;
 pub mod ev;
+pub mod ex;
 pub mod fd;
 pub mod ft;
 pub mod ih;
 pub mod is;
 pub mod ix;
 pub mod lq;
+pub mod st;
@@ -0,0 +1,55 @@
+//! Stores the statistics collected by ANALYZE TABLE
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId, TableStatistics};
+use crate::key::category::{Categorise, Category};
+use crate::key::table::all::TableRoot;
+use crate::kvs::impl_kv_key_storekey;
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct TableStatisticsKey<'a> {
+	table_root: TableRoot<'a>,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+}
+
+impl_kv_key_storekey!(TableStatisticsKey<'_> => TableStatistics);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> TableStatisticsKey<'_> {
+	TableStatisticsKey::new(ns, db, tb)
+}
+
+impl Categorise for TableStatisticsKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableStatistics
+	}
+}
+
+impl<'a> TableStatisticsKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName) -> Self {
+		TableStatisticsKey {
+			table_root: TableRoot::new(ns, db, tb),
+			_c: b'!',
+			_d: b's',
+			_e: b't',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = TableStatisticsKey::new(NamespaceId(1), DatabaseId(2), &tb);
+		let enc = TableStatisticsKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!st");
+	}
+}
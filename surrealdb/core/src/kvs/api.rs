@@ -94,6 +94,14 @@ pub trait Transactable: requirements::TransactionRequirements {
 	/// will return a [`crate::kvs::Error::TransactionReadonly`] error.
 	fn writeable(&self) -> bool;
 
+	/// Check if the storage engine keeps historical versions of each key.
+	///
+	/// Versioned storage engines can read keys as they were at an earlier
+	/// version, but historical versions can not be listed or written back.
+	fn keeps_versions(&self) -> bool {
+		false
+	}
+
 	/// Cancel a transaction.
 	///
 	/// This reverses all changes made within the transaction.
@@ -467,6 +475,14 @@ pub trait Transactable: requirements::TransactionRequirements {
 	/// Rollback to the last save point.
 	async fn rollback_to_save_point(&self) -> Result<()>;
 
+	/// Whether releasing a save point removes it from the transaction.
+	///
+	/// Storage engines which keep released save points revert them along with
+	/// the enclosing save point, so each of them has to be rolled back.
+	fn releases_save_points(&self) -> bool {
+		false
+	}
+
 	// --------------------------------------------------
 	// Timestamp functions
 	// --------------------------------------------------
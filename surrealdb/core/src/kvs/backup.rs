@@ -0,0 +1,498 @@
+//! Binary datastore snapshots.
+//!
+//! A backup is a raw copy of the key-value pairs stored in a datastore, taken
+//! from a single read transaction, so it is consistent and includes every
+//! namespace, database, record and index. As the keys and values are copied
+//! without being decoded, a backup is much faster to produce and restore than
+//! the SurrealQL text produced by [`export`](super::export), and it can be
+//! restored into any storage engine running the same storage version.
+//!
+//! Only the latest version of each key is included, as historical versions
+//! kept by versioned storage engines can not be listed or written back. A
+//! backup of a versioned datastore is refused unless
+//! [`Config::latest_only`] is set, and a restored datastore only holds the
+//! version of each key written by the restore.
+//!
+//! A restore stages the backup under a separate key range, and only replaces
+//! the existing data once the whole backup has been validated. A marker key
+//! is then kept until the staged entries have been moved into place, so that
+//! a restore which is interrupted is finished when the datastore is next
+//! opened, or before the next restore starts.
+//!
+//! A backup is laid out as follows, with all integers encoded big-endian:
+//!
+//! ```text
+//! header  := MAGIC format:u16 storage:u16
+//! entry   := klen:u32 key val_len:u32 val      (klen > 0)
+//! trailer := 0:u32 count:u64
+//! ```
+
+use std::ops::Range;
+
+use anyhow::{Result, bail, ensure};
+use async_channel::Sender;
+
+use super::version::MajorVersion;
+use super::{Key, Transaction, Val};
+use crate::cnf::{BACKUP_MAX_ENTRY_SIZE, EXPORT_BATCH_SIZE};
+use crate::err::Error;
+
+/// The bytes which start every backup
+pub const MAGIC: &[u8; 8] = b"SURBAKUP";
+
+/// The current version of the backup format
+pub const FORMAT_VERSION: u16 = 1;
+
+/// The length of the backup header
+const HEADER_LEN: usize = MAGIC.len() + 2 + 2;
+
+/// The length of the backup trailer, excluding the terminator
+const TRAILER_LEN: usize = 8;
+
+/// The prefix under which a backup is staged while it is being restored
+const STAGING_PREFIX: &[u8] = b"/!rs";
+
+/// The key which is set while a staged backup replaces the existing data
+const RESTORE_MARKER: &[u8] = b"/!rm";
+
+/// Options for taking a backup.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Config {
+	/// Whether to back up a versioned datastore, with only the latest version
+	/// of each key
+	pub latest_only: bool,
+}
+
+/// The step reached by a restore which is replacing the existing data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RestoreStep {
+	/// The existing data is being removed
+	Clear,
+	/// The staged entries are being moved into place
+	Apply,
+}
+
+impl RestoreStep {
+	/// Encodes the step as the value of the restore marker.
+	pub(super) fn encode(self) -> Val {
+		match self {
+			Self::Clear => vec![0],
+			Self::Apply => vec![1],
+		}
+	}
+
+	/// Decodes the step from the value of the restore marker.
+	pub(super) fn decode(val: &[u8]) -> Result<Self> {
+		match val {
+			[0] => Ok(Self::Clear),
+			[1] => Ok(Self::Apply),
+			_ => bail!(Error::InvalidBackup("Found an invalid restore marker".to_string())),
+		}
+	}
+}
+
+/// Returns whether a key is included in a backup.
+///
+/// The storage version is written to the backup header, and the cluster
+/// membership, node-local and task lease keys only describe the nodes
+/// connected to the source datastore. Staged restore keys and the restore
+/// marker are never part of the data being backed up or replaced.
+pub(super) fn is_backed_up(key: &[u8]) -> bool {
+	!(key == b"!v"
+		|| key == RESTORE_MARKER
+		|| key.starts_with(b"/$")
+		|| key.starts_with(b"/!nd")
+		|| key.starts_with(b"/!tl")
+		|| key.starts_with(STAGING_PREFIX))
+}
+
+/// The key which marks a restore in progress.
+pub(super) fn restore_marker() -> Key {
+	RESTORE_MARKER.to_vec()
+}
+
+/// The key under which a restored key is staged.
+pub(super) fn staged_key(key: &[u8]) -> Key {
+	let mut out = Vec::with_capacity(STAGING_PREFIX.len() + key.len());
+	out.extend_from_slice(STAGING_PREFIX);
+	out.extend_from_slice(key);
+	out
+}
+
+/// The restored key which a staged key holds.
+pub(super) fn unstaged_key(key: &[u8]) -> Result<&[u8]> {
+	key.strip_prefix(STAGING_PREFIX).ok_or_else(|| {
+		Error::InvalidBackup("Found an invalid staged backup key".to_string()).into()
+	})
+}
+
+/// The range of every staged restore key.
+pub(super) fn staging_range() -> Range<Key> {
+	let mut end = STAGING_PREFIX.to_vec();
+	end.push(0xff);
+	STAGING_PREFIX.to_vec()..end
+}
+
+/// Encodes the backup header.
+fn encode_header(version: MajorVersion) -> Vec<u8> {
+	let mut out = Vec::with_capacity(HEADER_LEN);
+	out.extend_from_slice(MAGIC);
+	out.extend_from_slice(&FORMAT_VERSION.to_be_bytes());
+	out.extend_from_slice(&u16::from(version).to_be_bytes());
+	out
+}
+
+/// Encodes a key-value pair onto a backup chunk.
+fn encode_entry(out: &mut Vec<u8>, key: &[u8], val: &[u8]) -> Result<()> {
+	let klen = u32::try_from(key.len())
+		.map_err(|_| Error::InvalidBackup("Key is too large to be backed up".to_string()))?;
+	let vlen = u32::try_from(val.len())
+		.map_err(|_| Error::InvalidBackup("Value is too large to be backed up".to_string()))?;
+	out.extend_from_slice(&klen.to_be_bytes());
+	out.extend_from_slice(key);
+	out.extend_from_slice(&vlen.to_be_bytes());
+	out.extend_from_slice(val);
+	Ok(())
+}
+
+/// Encodes the backup trailer.
+fn encode_trailer(count: u64) -> Vec<u8> {
+	let mut out = Vec::with_capacity(4 + TRAILER_LEN);
+	out.extend_from_slice(&0u32.to_be_bytes());
+	out.extend_from_slice(&count.to_be_bytes());
+	out
+}
+
+impl Transaction {
+	/// Writes a binary snapshot of the entire datastore.
+	pub async fn backup(&self, cfg: Config, chn: Sender<Vec<u8>>) -> Result<()> {
+		// Historical versions would otherwise be silently dropped
+		ensure!(cfg.latest_only || !self.inner.keeps_versions(), Error::VersionedBackup);
+		// Output the header
+		let version = match self.get(&crate::key::version::new(), None).await? {
+			Some(v) => v,
+			None => MajorVersion::latest(),
+		};
+		chn.send(encode_header(version)).await?;
+		// Output every key-value pair in batches
+		let mut count: u64 = 0;
+		let mut next: Option<Range<Key>> = Some(vec![0x00]..vec![0xff]);
+		while let Some(rng) = next {
+			let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
+			next = batch.next;
+			let mut chunk = Vec::new();
+			for (k, v) in batch.result.iter().filter(|(k, _)| is_backed_up(k)) {
+				encode_entry(&mut chunk, k, v)?;
+				count += 1;
+			}
+			if !chunk.is_empty() {
+				chn.send(chunk).await?;
+			}
+		}
+		// Output the trailer
+		chn.send(encode_trailer(count)).await?;
+		Ok(())
+	}
+}
+
+/// The section of a backup which is being decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+	Header,
+	Entries,
+	Trailer,
+	Done,
+}
+
+/// An incremental decoder for a backup.
+///
+/// Chunks of the backup are pushed as they are received, and the decoded
+/// key-value pairs are then taken with [`BackupDecoder::next_entry`].
+#[derive(Debug)]
+pub struct BackupDecoder {
+	/// The bytes received but not yet decoded
+	buf: Vec<u8>,
+	/// The position of the next undecoded byte in the buffer
+	pos: usize,
+	/// The section being decoded
+	state: State,
+	/// The storage version the backup was taken from
+	version: Option<MajorVersion>,
+	/// The number of entries decoded so far
+	count: u64,
+	/// The maximum size of a single key or value, which bounds the bytes
+	/// buffered before an entry can be decoded
+	max_entry_size: usize,
+}
+
+impl Default for BackupDecoder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl BackupDecoder {
+	pub fn new() -> Self {
+		Self {
+			buf: Vec::new(),
+			pos: 0,
+			state: State::Header,
+			version: None,
+			count: 0,
+			max_entry_size: *BACKUP_MAX_ENTRY_SIZE,
+		}
+	}
+
+	/// Sets the maximum size of a single key or value.
+	pub fn with_max_entry_size(mut self, max_entry_size: usize) -> Self {
+		self.max_entry_size = max_entry_size;
+		self
+	}
+
+	/// Adds a received chunk of the backup.
+	pub fn push(&mut self, chunk: &[u8]) {
+		// Drop the bytes which have already been decoded
+		if self.pos > 0 && self.pos >= self.buf.len() / 2 {
+			self.buf.drain(..self.pos);
+			self.pos = 0;
+		}
+		self.buf.extend_from_slice(chunk);
+	}
+
+	/// The storage version the backup was taken from, once the header has
+	/// been decoded.
+	pub fn version(&self) -> Option<MajorVersion> {
+		self.version
+	}
+
+	/// Returns whether the whole backup has been decoded.
+	pub fn is_done(&self) -> bool {
+		self.state == State::Done
+	}
+
+	/// Checks that the whole backup was received.
+	pub fn finish(&self) -> Result<()> {
+		ensure!(
+			self.is_done(),
+			Error::InvalidBackup("The backup ended before it was complete".to_string())
+		);
+		ensure!(
+			self.pos == self.buf.len(),
+			Error::InvalidBackup("Unexpected data found after the end of the backup".to_string())
+		);
+		Ok(())
+	}
+
+	/// Takes `len` bytes from the buffer, if they have been received.
+	fn take(&mut self, len: usize) -> Option<&[u8]> {
+		let end = self.pos.checked_add(len)?;
+		if end > self.buf.len() {
+			return None;
+		}
+		let out = &self.buf[self.pos..end];
+		self.pos = end;
+		Some(out)
+	}
+
+	/// Reads a big-endian `u32` at an offset from the current position.
+	fn peek_u32(&self, offset: usize) -> Option<u32> {
+		let beg = self.pos + offset;
+		let bytes = self.buf.get(beg..beg + 4)?;
+		Some(u32::from_be_bytes(bytes.try_into().ok()?))
+	}
+
+	/// Checks the length of a key or value against the maximum entry size.
+	fn check_entry_size(&self, len: u32, what: &str) -> Result<usize> {
+		let len = len as usize;
+		ensure!(
+			len <= self.max_entry_size,
+			Error::InvalidBackup(format!(
+				"A backup {what} of {len} bytes exceeds the maximum entry size of {} bytes",
+				self.max_entry_size
+			))
+		);
+		Ok(len)
+	}
+
+	/// Decodes the backup header, returning `false` when more bytes are
+	/// needed.
+	pub fn read_header(&mut self) -> Result<bool> {
+		if self.state != State::Header {
+			return Ok(true);
+		}
+		let Some(head) = self.take(HEADER_LEN) else {
+			return Ok(false);
+		};
+		if &head[..MAGIC.len()] != MAGIC {
+			bail!(Error::InvalidBackup("The data is not a SurrealDB backup".to_string()));
+		}
+		let format = u16::from_be_bytes([head[8], head[9]]);
+		let storage = u16::from_be_bytes([head[10], head[11]]);
+		if format != FORMAT_VERSION {
+			bail!(Error::InvalidBackup(format!("Unsupported backup format version {format}")));
+		}
+		self.version = Some(storage.into());
+		self.state = State::Entries;
+		Ok(true)
+	}
+
+	/// Decodes the next key-value pair of the backup.
+	///
+	/// Returns `None` when more bytes are needed, or once the trailer has
+	/// been decoded.
+	pub fn next_entry(&mut self) -> Result<Option<(Key, Val)>> {
+		loop {
+			match self.state {
+				State::Header => {
+					if !self.read_header()? {
+						return Ok(None);
+					}
+				}
+				State::Entries => {
+					let Some(klen) = self.peek_u32(0) else {
+						return Ok(None);
+					};
+					if klen == 0 {
+						self.pos += 4;
+						self.state = State::Trailer;
+						continue;
+					}
+					let klen = self.check_entry_size(klen, "key")?;
+					let Some(vlen) = self.peek_u32(4 + klen) else {
+						return Ok(None);
+					};
+					let vlen = self.check_entry_size(vlen, "value")?;
+					if self.buf.len() - self.pos < 4 + klen + 4 + vlen {
+						return Ok(None);
+					}
+					self.pos += 4;
+					let Some(key) = self.take(klen).map(<[u8]>::to_vec) else {
+						bail!(Error::InvalidBackup(
+							"The backup entry key is incomplete".to_string()
+						));
+					};
+					self.pos += 4;
+					let Some(val) = self.take(vlen).map(<[u8]>::to_vec) else {
+						bail!(Error::InvalidBackup(
+							"The backup entry value is incomplete".to_string()
+						));
+					};
+					self.count += 1;
+					return Ok(Some((key, val)));
+				}
+				State::Trailer => {
+					let Some(count) = self.take(TRAILER_LEN) else {
+						return Ok(None);
+					};
+					let count = u64::from_be_bytes(count.try_into()?);
+					if count != self.count {
+						bail!(Error::InvalidBackup(format!(
+							"The backup contains {} entries, but {count} were expected",
+							self.count
+						)));
+					}
+					self.state = State::Done;
+				}
+				State::Done => return Ok(None),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn backup(entries: &[(&[u8], &[u8])]) -> Vec<u8> {
+		let mut out = encode_header(MajorVersion::latest());
+		for (k, v) in entries {
+			encode_entry(&mut out, k, v).unwrap();
+		}
+		out.extend(encode_trailer(entries.len() as u64));
+		out
+	}
+
+	fn decode(data: &[u8], chunk: usize) -> Result<Vec<(Key, Val)>> {
+		let mut dec = BackupDecoder::new();
+		let mut out = Vec::new();
+		for c in data.chunks(chunk) {
+			dec.push(c);
+			while let Some(kv) = dec.next_entry()? {
+				out.push(kv);
+			}
+		}
+		dec.finish()?;
+		Ok(out)
+	}
+
+	#[test]
+	fn round_trip_in_chunks() {
+		let entries: &[(&[u8], &[u8])] =
+			&[(b"/!ns\x00test", b"definition"), (b"/*\x00\x00\x00\x01", b""), (b"/+a", b"xyz")];
+		let data = backup(entries);
+		for chunk in [1, 3, 7, data.len()] {
+			let out = decode(&data, chunk).unwrap();
+			assert_eq!(out.len(), entries.len());
+			for ((k, v), (ek, ev)) in out.iter().zip(entries) {
+				assert_eq!(k.as_slice(), *ek);
+				assert_eq!(v.as_slice(), *ev);
+			}
+		}
+	}
+
+	#[test]
+	fn rejects_invalid_backups() {
+		let data = backup(&[(b"/a", b"1"), (b"/b", b"2")]);
+		// Not a backup
+		decode(b"DEFINE TABLE person;", 64).unwrap_err();
+		// Truncated
+		decode(&data[..data.len() - 3], 64).unwrap_err();
+		// Wrong entry count
+		let mut bad = data.clone();
+		let last = bad.len() - 1;
+		bad[last] = 5;
+		decode(&bad, 64).unwrap_err();
+		// Trailing data
+		let mut bad = data;
+		bad.push(0);
+		decode(&bad, 64).unwrap_err();
+	}
+
+	#[test]
+	fn rejects_oversized_entries() {
+		let data = backup(&[(b"/a", b"1234")]);
+		let mut dec = BackupDecoder::new().with_max_entry_size(3);
+		// Only the length prefix of the value is needed to reject it
+		dec.push(&data[..HEADER_LEN + 4 + 2 + 4]);
+		dec.next_entry().unwrap_err();
+	}
+
+	#[test]
+	fn stages_restored_keys() {
+		let staged = staged_key(b"/!ns\x00test");
+		assert!(!is_backed_up(&staged));
+		assert!(staging_range().contains(&staged));
+		assert_eq!(unstaged_key(&staged).unwrap(), b"/!ns\x00test");
+		unstaged_key(b"/!ns\x00test").unwrap_err();
+	}
+
+	#[test]
+	fn restore_steps() {
+		for step in [RestoreStep::Clear, RestoreStep::Apply] {
+			assert_eq!(RestoreStep::decode(&step.encode()).unwrap(), step);
+		}
+		RestoreStep::decode(b"").unwrap_err();
+		RestoreStep::decode(&[2]).unwrap_err();
+	}
+
+	#[test]
+	fn skips_node_local_keys() {
+		assert!(!is_backed_up(b"!v"));
+		assert!(!is_backed_up(b"/!nd\x00"));
+		assert!(!is_backed_up(b"/!tl\x00\x01"));
+		assert!(!is_backed_up(b"/$\x00"));
+		assert!(!is_backed_up(&restore_marker()));
+		assert!(is_backed_up(b"/!ns\x00test"));
+		assert!(is_backed_up(b"/*\x00\x00\x00\x01"));
+	}
+}
@@ -56,6 +56,12 @@ pub(crate) enum Entry {
 	Pas(Arc<[catalog::ParamDefinition]>),
 	/// A slice of DefineSequenceStatement specified on a namespace.
 	Sqs(Arc<[catalog::SequenceDefinition]>),
+	/// A slice of DefineJobStatement specified on a database.
+	Jbs(Arc<[catalog::JobDefinition]>),
+	/// A slice of DefineConsumerStatement specified on a database.
+	Cus(Arc<[catalog::ConsumerDefinition]>),
+	/// A slice of DefineSinkStatement specified on a database.
+	Sks(Arc<[catalog::SinkDefinition]>),
 	/// A slice of DefineEventStatement specified on a table.
 	Evs(Arc<[catalog::EventDefinition]>),
 	/// A slice of DefineFieldStatement specified on a table.
@@ -210,6 +216,30 @@ impl Entry {
 			_ => fail!("Unable to convert type into Entry::Sqs"),
 		}
 	}
+	/// Converts this cache entry into a slice of [`catalog::JobDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Jbs`].
+	pub(crate) fn try_into_jbs(self) -> Result<Arc<[catalog::JobDefinition]>> {
+		match self {
+			Entry::Jbs(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Jbs"),
+		}
+	}
+	/// Converts this cache entry into a slice of [`catalog::ConsumerDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Cus`].
+	pub(crate) fn try_into_cus(self) -> Result<Arc<[catalog::ConsumerDefinition]>> {
+		match self {
+			Entry::Cus(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Cus"),
+		}
+	}
+	/// Converts this cache entry into a slice of [`catalog::SinkDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Sks`].
+	pub(crate) fn try_into_sks(self) -> Result<Arc<[catalog::SinkDefinition]>> {
+		match self {
+			Entry::Sks(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Sks"),
+		}
+	}
 
 	/// Converts this cache entry into a slice of [`catalog::FunctionDefinition`].
 	/// This panics if called on a cache entry that is not an [`Entry::Fcs`].
@@ -50,6 +50,12 @@ pub(crate) enum Key {
 	Tbs(NamespaceId, DatabaseId),
 	/// A cache key for sequences (on a database)
 	Sqs(NamespaceId, DatabaseId),
+	/// A cache key for jobs (on a database)
+	Jbs(NamespaceId, DatabaseId),
+	/// A cache key for consumer groups (on a database)
+	Cus(NamespaceId, DatabaseId),
+	/// A cache key for sinks (on a database)
+	Sks(NamespaceId, DatabaseId),
 	/// A cache key for events (on a table)
 	Evs(NamespaceId, DatabaseId, String),
 	/// A cache key for fieds (on a table)
@@ -108,12 +114,20 @@ pub(crate) enum Key {
 	TbByName(String, String, String),
 	/// A cache key for a table by id.
 	Tb(NamespaceId, DatabaseId, String),
+	/// A cache key for a job (on a database)
+	Jb(NamespaceId, DatabaseId, String),
+	/// A cache key for a consumer group (on a database)
+	Cu(NamespaceId, DatabaseId, String),
+	/// A cache key for a sink (on a database)
+	Sk(NamespaceId, DatabaseId, String),
 	/// A cache key for an event (on a table)
 	Ev(NamespaceId, DatabaseId, String, String),
 	/// A cache key for a fied (on a table)
 	Fd(NamespaceId, DatabaseId, String, String),
 	/// A cache key for an index (on a table)
 	Ix(NamespaceId, DatabaseId, String, String),
+	/// A cache key for the statistics of a table
+	St(NamespaceId, DatabaseId, String),
 	/// A cache key for a record
 	Record(NamespaceId, DatabaseId, String, RecordIdKey),
 }
@@ -144,6 +158,9 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Cgs(a, b) => Key::Cgs(a, b),
 			Lookup::Pas(a, b) => Key::Pas(a, b),
 			Lookup::Sqs(a, b) => Key::Sqs(a, b),
+			Lookup::Jbs(a, b) => Key::Jbs(a, b),
+			Lookup::Cus(a, b) => Key::Cus(a, b),
+			Lookup::Sks(a, b) => Key::Sks(a, b),
 			Lookup::Tbs(a, b) => Key::Tbs(a, b),
 			Lookup::Evs(a, b, c) => Key::Evs(a, b, c.to_string()),
 			Lookup::Fds(a, b, c) => Key::Fds(a, b, c.to_string()),
@@ -173,11 +190,15 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Cg(a, b, c) => Key::Cg(a, b, c.to_string()),
 			Lookup::Pa(a, b, c) => Key::Pa(a, b, c.to_string()),
 			Lookup::Sq(a, b,c) => Key::Sq(a, b, c.to_string()),
+			Lookup::Jb(a, b, c) => Key::Jb(a, b, c.to_string()),
+			Lookup::Cu(a, b, c) => Key::Cu(a, b, c.to_string()),
+			Lookup::Sk(a, b, c) => Key::Sk(a, b, c.to_string()),
 			Lookup::Tb(a, b, c) => Key::Tb(a, b, c.to_string()),
 			Lookup::TbByName(a, b, c) => Key::TbByName(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Ev(a, b, c, d) => Key::Ev(a, b, c.to_string(), d.to_string()),
 			Lookup::Fd(a, b, c, d) => Key::Fd(a, b, c.to_string(), d.to_string()),
 			Lookup::Ix(a, b, c, d) => Key::Ix(a, b, c.to_string(), d.to_string()),
+			Lookup::St(a, b, c) => Key::St(a, b, c.to_string()),
 			Lookup::Record(a, b, c, d) => Key::Record(a, b, c.to_string(), d.to_owned()),
 		}
 	}
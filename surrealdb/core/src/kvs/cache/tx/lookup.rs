@@ -51,6 +51,12 @@ pub(crate) enum Lookup<'a> {
 	Sqs(NamespaceId, DatabaseId),
 	/// A cache key for tables
 	Tbs(NamespaceId, DatabaseId),
+	/// A cache key for jobs (on a database)
+	Jbs(NamespaceId, DatabaseId),
+	/// A cache key for consumer groups (on a database)
+	Cus(NamespaceId, DatabaseId),
+	/// A cache key for sinks (on a database)
+	Sks(NamespaceId, DatabaseId),
 	/// A cache key for events (on a table)
 	Evs(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for fields (on a table)
@@ -109,12 +115,20 @@ pub(crate) enum Lookup<'a> {
 	Tb(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a table by name.
 	TbByName(&'a str, &'a str, &'a str),
+	/// A cache key for a job (on a database)
+	Jb(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for a consumer group (on a database)
+	Cu(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for a sink (on a database)
+	Sk(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for an event (on a table)
 	Ev(NamespaceId, DatabaseId, &'a str, &'a str),
 	/// A cache key for a field (on a table)
 	Fd(NamespaceId, DatabaseId, &'a str, &'a str),
 	/// A cache key for an index (on a table)
 	Ix(NamespaceId, DatabaseId, &'a str, &'a str),
+	/// A cache key for the statistics of a table
+	St(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a record
 	Record(NamespaceId, DatabaseId, &'a str, &'a RecordIdKey),
 }
@@ -145,6 +159,9 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Cgs(la, lb), Key::Cgs(ka, kb)) => la == ka && lb == kb,
 			(Self::Pas(la, lb), Key::Pas(ka, kb)) => la == ka && lb == kb,
 			(Self::Sqs(la, lb), Key::Sqs(ka, kb)) => la == ka && lb == kb,
+			(Self::Jbs(la, lb), Key::Jbs(ka, kb)) => la == ka && lb == kb,
+			(Self::Cus(la, lb), Key::Cus(ka, kb)) => la == ka && lb == kb,
+			(Self::Sks(la, lb), Key::Sks(ka, kb)) => la == ka && lb == kb,
 			(Self::Tbs(la, lb), Key::Tbs(ka, kb)) => la == ka && lb == kb,
 			(Self::Evs(la, lb, lc), Key::Evs(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Fds(la, lb, lc), Key::Fds(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
@@ -174,11 +191,15 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Cg(la, lb, lc), Key::Cg(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Pa(la, lb, lc), Key::Pa(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Sq(la, lb, lc), Key::Sq(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Jb(la, lb, lc), Key::Jb(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Cu(la, lb, lc), Key::Cu(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Sk(la, lb, lc), Key::Sk(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Tb(la, lb, lc), Key::Tb(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::TbByName(la, lb, lc), Key::TbByName(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Ev(la, lb, lc, ld), Key::Ev(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Fd(la, lb, lc, ld), Key::Fd(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Ix(la, lb, lc, ld), Key::Ix(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
+			(Self::St(la, lb, lc), Key::St(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Record(la, lb, lc, ld), Key::Record(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && *ld == kd,
 			//
 			_ => false,
@@ -278,11 +299,15 @@ mod tests {
 	#[case(Lookup::Cg(NamespaceId(1), DatabaseId(1), "test"), Key::Cg(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Pa(NamespaceId(1), DatabaseId(1), "test"), Key::Pa(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Sq(NamespaceId(1), DatabaseId(1), "test"), Key::Sq(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(Lookup::Jb(NamespaceId(1), DatabaseId(1), "test"), Key::Jb(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(Lookup::Cu(NamespaceId(1), DatabaseId(1), "test"), Key::Cu(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(Lookup::Sk(NamespaceId(1), DatabaseId(1), "test"), Key::Sk(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Tb(NamespaceId(1), DatabaseId(1), "test"), Key::Tb(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::TbByName("test", "test", "test"), Key::TbByName("test".to_string(), "test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::Ev(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Ev(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::Fd(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Fd(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::Ix(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Ix(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
+	#[case(Lookup::St(NamespaceId(1), DatabaseId(1), "test"), Key::St(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Record(NamespaceId(1), DatabaseId(1), "test", &RecordIdKey::Number(1)), Key::Record(NamespaceId(1), DatabaseId(1), "test".to_string(), RecordIdKey::Number(1)), true)]
 	#[case(Lookup::Record(NamespaceId(1), DatabaseId(1), "test", &RecordIdKey::Number(1)), Key::Record(NamespaceId(1), DatabaseId(1), "test".to_string(), RecordIdKey::Number(2)), false)]
 	#[case(Lookup::Record(NamespaceId(1), DatabaseId(1), "test", &RecordIdKey::Number(1)), Key::Record(NamespaceId(1), DatabaseId(2), "test".to_string(), RecordIdKey::Number(1)), false)]
@@ -17,7 +17,7 @@ use anyhow::bail;
 use anyhow::{Context as _, Result, ensure};
 use async_channel::Sender;
 use bytes::{Bytes, BytesMut};
-use futures::{Future, Stream};
+use futures::{Future, Stream, StreamExt};
 use rand::{Rng, thread_rng};
 use reblessive::TreeStack;
 use surrealdb_types::{AuthError, Error as TypesError, SurrealValue, object};
@@ -32,12 +32,16 @@ use tracing::{debug, instrument, trace, warn};
 use uuid::Uuid;
 
 use super::api::Transactable;
+use super::encryption::{self, Encrypted, Keyring};
 use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::MajorVersion;
-use super::{Key, Val, export};
+use super::{Key, Val, backup, export, fix, ttl};
 use crate::api::err::ApiError;
 use crate::api::invocation::process_api_request;
+use crate::api::middleware::common::BodyStrategy;
+use crate::api::middleware::res::convert_response_value;
+use crate::api::openapi::{self, OPENAPI_PATH};
 use crate::api::request::ApiRequest;
 use crate::api::response::ApiResponse;
 use crate::buc::manager::BucketsManager;
@@ -45,9 +49,9 @@ use crate::catalog::providers::{
 	ApiProvider, CatalogProvider, DatabaseProvider, NamespaceProvider, NodeProvider, TableProvider,
 	UserProvider,
 };
-use crate::catalog::{ApiDefinition, Index, NodeLiveQuery, SubscriptionDefinition};
-use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::catalog::{ApiDefinition, ApiMethod, Index, NodeLiveQuery, SubscriptionDefinition};
 use crate::cnf::dynamic::DynamicConfiguration;
+use crate::cnf::{EXPORT_BATCH_SIZE, NORMAL_FETCH_SIZE};
 use crate::ctx::Context;
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
@@ -78,6 +82,7 @@ use crate::kvs::ds::requirements::{
 	TransactionBuilderFactoryRequirements, TransactionBuilderRequirements,
 };
 use crate::kvs::index::IndexBuilder;
+use crate::kvs::quota::Quotas;
 use crate::kvs::sequences::Sequences;
 use crate::kvs::slowlog::SlowLog;
 use crate::kvs::tasklease::{LeaseHandler, TaskLeaseType};
@@ -121,6 +126,8 @@ pub struct Datastore {
 	index_stores: IndexStores,
 	// The cross transaction cache
 	cache: Arc<DatastoreCache>,
+	// The resource quota usage shared between statements
+	quotas: Arc<Quotas>,
 	// The index asynchronous builder
 	index_builder: IndexBuilder,
 	#[cfg(feature = "jwks")]
@@ -171,6 +178,8 @@ pub(crate) struct TransactionFactory {
 	builder: Arc<Box<dyn TransactionBuilder>>,
 	// Async event processing trigger
 	async_event_trigger: Arc<Notify>,
+	// The keys used to encrypt stored values
+	encryption: Option<Arc<Keyring>>,
 }
 
 impl TransactionFactory {
@@ -181,9 +190,28 @@ impl TransactionFactory {
 		Self {
 			builder: Arc::new(builder),
 			async_event_trigger,
+			encryption: None,
 		}
 	}
 
+	/// Encrypt the values stored by every transaction with this keyring.
+	pub(super) fn with_encryption(mut self, keyring: Option<Arc<Keyring>>) -> Self {
+		self.encryption = keyring;
+		self
+	}
+
+	/// The keys used to encrypt stored values, if encryption is enabled.
+	pub(super) fn encryption(&self) -> Option<&Keyring> {
+		self.encryption.as_deref()
+	}
+
+	/// Create a new storage engine transaction, which reads and writes values
+	/// without encrypting them.
+	pub(super) async fn unencrypted(&self, write: bool) -> Result<Box<dyn Transactable>> {
+		let (inner, _) = self.builder.new_transaction(write, false).await?;
+		Ok(inner)
+	}
+
 	#[allow(
 		unreachable_code,
 		unreachable_patterns,
@@ -208,6 +236,11 @@ impl TransactionFactory {
 		};
 		// Create a new transaction on the datastore
 		let (inner, local) = self.builder.new_transaction(write, lock).await?;
+		// Encrypt the stored values if enabled
+		let inner: Box<dyn Transactable> = match &self.encryption {
+			Some(keyring) => Box::new(Encrypted::new(inner, keyring.clone())),
+			None => inner,
+		};
 		Ok(Transaction::new(
 			local,
 			sequences,
@@ -646,7 +679,17 @@ impl Datastore {
 	///
 	/// - `metric`: The name of the metric to collect.
 	pub fn collect_u64_metric(&self, metric: &str) -> Option<u64> {
-		self.transaction_factory.collect_u64_metric(metric)
+		self.quotas
+			.collect_u64_metric(metric)
+			.or_else(|| self.transaction_factory.collect_u64_metric(metric))
+	}
+
+	/// Registers the metrics describing the usage of resource quotas.
+	///
+	/// These are available for every datastore flavor, and are collected
+	/// through [`Datastore::collect_u64_metric`].
+	pub fn register_quota_metrics(&self) -> Metrics {
+		Quotas::metrics()
 	}
 
 	/// Create a new datastore with the same persistent data (inner), with
@@ -668,6 +711,7 @@ impl Datastore {
 			#[cfg(storage)]
 			temporary_directory: self.temporary_directory,
 			cache: Arc::new(DatastoreCache::new()),
+			quotas: Arc::new(Quotas::default()),
 			buckets: self.buckets,
 			sequences: Sequences::new(self.transaction_factory.clone(), self.id),
 			transaction_factory: self.transaction_factory,
@@ -787,6 +831,8 @@ impl Datastore {
 				actual: version.into(),
 			});
 		}
+		// Finish a restore which was interrupted while replacing the data
+		self.restore_resume().await?;
 		// Everything ok
 		Ok((version, is_new))
 	}
@@ -1750,7 +1796,7 @@ impl Datastore {
 	///
 	/// Looks up the index definition identified by `ikb` and dispatches to
 	/// the appropriate compaction implementation based on the index type:
-	/// full-text, count, or HNSW. Indexes that are being removed
+	/// full-text, count, HNSW, or IVF-PQ. Indexes that are being removed
 	/// (`prepare_remove`), not found, or of an unsupported type are silently
 	/// skipped with a trace log.
 	async fn process_index_compaction(
@@ -1774,6 +1820,20 @@ impl Datastore {
 					IndexOperation::index_hnsw_compaction(&ctx, &self.index_stores, ikb, &ix, p)
 						.await?;
 				}
+				Index::IvfPq(p) => {
+					let mut ctx = self.setup_ctx()?;
+					ctx.set_transaction(txn.clone());
+					let ctx = ctx.freeze();
+					IndexOperation::index_ivfpq_compaction(
+						&ctx,
+						&self.index_stores,
+						ikb,
+						&ix,
+						p,
+						self.id,
+					)
+					.await?;
+				}
 				_ => {
 					trace!(target: TARGET, "Index compaction: Index {:?} does not support compaction, skipping", ikb);
 				}
@@ -1816,6 +1876,155 @@ impl Datastore {
 		}
 	}
 
+	/// Removes expired records from tables defined with a time-to-live.
+	///
+	/// A distributed lease ensures that a single node sweeps the tables at a
+	/// time. Expired records are deleted like a normal `DELETE` statement, so
+	/// events, changefeeds and live query notifications are processed for
+	/// them. Once a batch starts it runs to completion even if the lease
+	/// expires, so brief overlap is possible.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between sweeps, to calculate the lease duration
+	///
+	/// # Returns
+	/// The number of records which were removed.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn ttl_sweep(&self, interval: Duration) -> Result<usize> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting expired record removal");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::TtlSweep,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(0);
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running expired record removal");
+		// Remove the expired records
+		ttl::sweep(self, &lh).await
+	}
+
+	/// Runs the scheduled jobs which are due.
+	///
+	/// A distributed lease ensures that a single node runs the jobs at a
+	/// time. Each job is run as an owner of its database, and the outcome is
+	/// recorded in the run history of the job.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between runs, to calculate the lease duration
+	///
+	/// # Returns
+	/// The number of jobs which were run.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn run_jobs(&self, interval: Duration) -> Result<usize> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting to run scheduled jobs");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::JobScheduler,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(0);
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running scheduled jobs");
+		// Run the jobs which are due
+		crate::job::run(self, &lh).await
+	}
+
+	/// Delivers pending changefeed changes to webhook and file sinks
+	///
+	/// A distributed lease ensures that a single node delivers the changes at
+	/// a time. A sink which fails to deliver its changes is retried with an
+	/// exponential backoff.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between runs, to calculate the lease duration
+	///
+	/// # Returns
+	/// The number of change events which were delivered.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn run_sinks(&self, interval: Duration) -> Result<usize> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting to deliver changefeed sinks");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::SinkDelivery,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(0);
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Delivering changefeed sinks");
+		// Deliver the pending changes of each sink
+		crate::sink::run(self, &lh).await
+	}
+
+	/// Measures the storage size of the namespaces and databases which have a
+	/// storage quota.
+	///
+	/// Every node measures the storage sizes itself, as the measurements are
+	/// held in memory and checked by the statements which run on the node.
+	/// Writes are checked against the last measurement, so this should be
+	/// called periodically.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn refresh_quota_storage(&self) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Measuring the storage size of quotas");
+		// Measure the storage size of each namespace and database
+		self.quotas.refresh_storage(self).await
+	}
+
+	/// Reads the next batch of changes from a stream sink as NDJSON
+	///
+	/// The changes following the `after` versionstamp are returned, or the
+	/// changes following the last acknowledged versionstamp if none is given.
+	/// Returns the versionstamp of the batch, to be acknowledged with
+	/// [`Datastore::ack_sink_batch`] once the changes have been delivered.
+	pub async fn next_sink_batch(
+		&self,
+		sess: &Session,
+		sink: &str,
+		after: Option<u64>,
+	) -> Result<Option<(u64, String)>> {
+		let batch = crate::sink::next_stream_batch(self, sess, sink, after).await?;
+		Ok(batch.map(|b| {
+			let mut out = String::new();
+			for event in b.events {
+				out.push_str(&event);
+				out.push('\n');
+			}
+			(b.versionstamp, out)
+		}))
+	}
+
+	/// Acknowledges the changes of a stream sink up to a versionstamp
+	pub async fn ack_sink_batch(
+		&self,
+		sess: &Session,
+		sink: &str,
+		versionstamp: u64,
+	) -> Result<()> {
+		crate::sink::ack_stream_batch(self, sess, sink, versionstamp).await
+	}
+
 	// --------------------------------------------------
 	// Other functions
 	// --------------------------------------------------
@@ -2009,13 +2218,25 @@ impl Datastore {
 		if let Some(vars) = vars {
 			ctx.attach_variables(vars.into())?;
 		}
+		// Parse with the capability overrides of the namespace and database.
+		// Each statement is then executed with the overrides of the namespace
+		// and database which are selected when it runs.
+		let txn = self.transaction(Read, Optimistic).await?;
+		let capabilities = catch!(
+			txn,
+			Context::restricted_capabilities(
+				&txn,
+				&self.capabilities,
+				sess.ns.as_deref(),
+				sess.db.as_deref()
+			)
+			.await
+		);
+		txn.cancel().await?;
 		// Process all statements
-
 		let parser_settings = ParserSettings {
-			files_enabled: ctx.get_capabilities().allows_experimental(&ExperimentalTarget::Files),
-			surrealism_enabled: ctx
-				.get_capabilities()
-				.allows_experimental(&ExperimentalTarget::Surrealism),
+			files_enabled: capabilities.allows_experimental(&ExperimentalTarget::Files),
+			surrealism_enabled: capabilities.allows_experimental(&ExperimentalTarget::Surrealism),
 			..Default::default()
 		};
 		let mut statements_stream = StatementStream::new_with_settings(parser_settings);
@@ -2179,6 +2400,29 @@ impl Datastore {
 		};
 		// Start a new transaction
 		let txn = self.transaction(txn_type, Optimistic).await?.enclose();
+		// Apply the capability overrides of the namespace and database
+		catch!(
+			txn,
+			ctx.restrict_capabilities(
+				&txn,
+				&self.capabilities,
+				sess.ns.as_deref(),
+				sess.db.as_deref()
+			)
+			.await
+		);
+		// Apply the resource quotas of the namespace, database, and user
+		catch!(
+			txn,
+			ctx.apply_quotas(
+				&txn,
+				sess.ns.as_deref(),
+				sess.db.as_deref(),
+				&sess.au,
+				!val.read_only()
+			)
+			.await
+		);
 		// Store the transaction
 		ctx.set_transaction(txn.clone());
 
@@ -2251,17 +2495,275 @@ impl Datastore {
 		ensure!(!sess.expired(), Error::ExpiredSession);
 		// Retrieve the provided NS and DB
 		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Only owners can export the TOTP enrolments of users
+		let secrets = self.has_snapshot_access(sess);
 		// Create a new readonly transaction
 		let txn = self.transaction(Read, Optimistic).await?;
 		// Return an async export job
 		Ok(async move {
 			// Process the export
-			let res = txn.export(&ns, &db, cfg, chn).await;
+			let res = txn.export(&ns, &db, cfg, secrets, chn).await;
+			txn.cancel().await?;
+			res
+		})
+	}
+
+	/// Performs a binary snapshot of the entire datastore
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn backup(
+		&self,
+		sess: &Session,
+		cfg: backup::Config,
+		chn: Sender<Vec<u8>>,
+	) -> Result<impl Future<Output = Result<()>> + use<>> {
+		// A snapshot includes the raw user and access catalog
+		self.check_snapshot_access(sess)?;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Return an async backup job
+		Ok(async move {
+			// Process the backup
+			let res = txn.backup(cfg, chn).await;
 			txn.cancel().await?;
 			res
 		})
 	}
 
+	/// Restores a binary snapshot into this datastore
+	///
+	/// The snapshot is first staged in batches, under a separate key range,
+	/// and only once the whole snapshot has been received and validated is
+	/// the existing data removed and replaced with the staged contents. A
+	/// snapshot which fails to validate leaves the datastore untouched, and
+	/// a restore which is interrupted while replacing the existing data is
+	/// finished by [`Datastore::check_version`] or by the next restore.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn restore<S>(&self, sess: &Session, stream: S) -> Result<()>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		// A snapshot overwrites the raw user and access catalog
+		self.check_snapshot_access(sess)?;
+		// Finish an earlier restore which was interrupted
+		self.restore_resume().await?;
+		// Fetch the current storage version
+		let txn = self.transaction(Read, Optimistic).await?;
+		let current = catch!(txn, txn.get(&crate::key::version::new(), None).await);
+		txn.cancel().await?;
+		// Remove anything left behind by an earlier failed staging
+		self.restore_discard().await?;
+		// Decode and stage the snapshot in batches
+		let mut decoder = backup::BackupDecoder::new();
+		if let Err(e) = self.restore_stage(&mut decoder, current, stream).await {
+			// Leave the existing data untouched
+			if let Err(err) = self.restore_discard().await {
+				warn!("Failed to remove a partially staged backup: {err}");
+			}
+			return Err(e);
+		}
+		// Mark the restore as started, so that it is finished if interrupted
+		let txn = self.transaction(Write, Optimistic).await?;
+		let step = backup::RestoreStep::Clear.encode();
+		catch!(txn, txn.set(&backup::restore_marker(), &step).await);
+		// Write the storage version if this datastore has none yet
+		if current.is_none()
+			&& let Some(version) = decoder.version()
+		{
+			catch!(txn, txn.replace(&crate::key::version::new(), &version).await);
+		}
+		txn.commit().await?;
+		// Replace the existing data with the staged snapshot
+		self.restore_replace(backup::RestoreStep::Clear).await
+	}
+
+	/// Finishes a restore which was interrupted while the existing data was
+	/// being replaced with the staged snapshot
+	async fn restore_resume(&self) -> Result<()> {
+		let txn = self.transaction(Read, Optimistic).await?;
+		let marker = catch!(txn, txn.get(&backup::restore_marker(), None).await);
+		txn.cancel().await?;
+		if let Some(step) = marker {
+			let step = backup::RestoreStep::decode(&step)?;
+			warn!("Finishing a restore which was interrupted");
+			self.restore_replace(step).await?;
+		}
+		Ok(())
+	}
+
+	/// Replaces the existing data with the staged snapshot, starting from the
+	/// step which the restore has reached
+	async fn restore_replace(&self, step: backup::RestoreStep) -> Result<()> {
+		if step == backup::RestoreStep::Clear {
+			self.restore_clear().await?;
+			let txn = self.transaction(Write, Optimistic).await?;
+			let chk = backup::RestoreStep::Clear.encode();
+			let step = backup::RestoreStep::Apply.encode();
+			catch!(txn, txn.putc(&backup::restore_marker(), &step, Some(&chk)).await);
+			txn.commit().await?;
+		}
+		self.restore_apply().await?;
+		// The restore is complete
+		let txn = self.transaction(Write, Optimistic).await?;
+		catch!(txn, txn.clr(&backup::restore_marker()).await);
+		txn.commit().await?;
+		// Clear any cached definitions
+		self.cache.clear();
+		Ok(())
+	}
+
+	/// Decodes a snapshot, validating it and writing its entries to the
+	/// staging key range
+	async fn restore_stage<S>(
+		&self,
+		decoder: &mut backup::BackupDecoder,
+		current: Option<MajorVersion>,
+		stream: S,
+	) -> Result<()>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		let mut entries = Vec::new();
+		let mut checked = false;
+		let mut stream = pin!(stream);
+		while let Some(chunk) = stream.next().await {
+			decoder.push(&chunk?);
+			// Check the header before staging any entry
+			if !checked {
+				if !decoder.read_header()? {
+					continue;
+				}
+				if let (Some(version), Some(current)) = (decoder.version(), current) {
+					ensure!(
+						version == current,
+						Error::OutdatedStorageVersion {
+							expected: current.into(),
+							actual: version.into(),
+						}
+					);
+				}
+				checked = true;
+			}
+			while let Some((k, v)) = decoder.next_entry()? {
+				entries.push((backup::staged_key(&k), v));
+				if entries.len() >= *EXPORT_BATCH_SIZE as usize {
+					self.restore_batch(std::mem::take(&mut entries)).await?;
+				}
+			}
+		}
+		decoder.finish()?;
+		self.restore_batch(entries).await
+	}
+
+	/// Moves the staged snapshot entries into place
+	async fn restore_apply(&self) -> Result<()> {
+		let mut next = Some(backup::staging_range());
+		while let Some(rng) = next {
+			let txn = self.transaction(Write, Optimistic).await?;
+			let batch = catch!(txn, txn.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await);
+			next = batch.next;
+			for (k, v) in batch.result.iter() {
+				let key = catch!(txn, backup::unstaged_key(k));
+				catch!(txn, txn.set(&key.to_vec(), v).await);
+				catch!(txn, txn.clr(k).await);
+			}
+			txn.commit().await?;
+		}
+		Ok(())
+	}
+
+	/// Removes any staged snapshot entries
+	async fn restore_discard(&self) -> Result<()> {
+		let mut next = Some(backup::staging_range());
+		while let Some(rng) = next {
+			let txn = self.transaction(Write, Optimistic).await?;
+			let batch = catch!(txn, txn.batch_keys(rng, *EXPORT_BATCH_SIZE, None).await);
+			next = batch.next;
+			for k in batch.result.iter() {
+				catch!(txn, txn.clr(k).await);
+			}
+			txn.commit().await?;
+		}
+		Ok(())
+	}
+
+	/// Checks that a session may take or restore a snapshot of the entire
+	/// datastore, which is limited to root owners
+	fn check_snapshot_access(&self, sess: &Session) -> Result<()> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		ensure!(
+			self.has_snapshot_access(sess),
+			Error::from(IamError::NotAllowed {
+				actor: sess.au.id().to_string(),
+				action: Action::Edit.to_string(),
+				resource: ResourceKind::Any.on_root().to_string(),
+			})
+		);
+		Ok(())
+	}
+
+	/// Checks whether a session can read the raw user and access catalog
+	fn has_snapshot_access(&self, sess: &Session) -> bool {
+		// Skip auth for Anonymous users if auth is disabled
+		if !self.is_auth_enabled() && sess.au.is_anon() {
+			return true;
+		}
+		sess.au.is_root() && sess.au.has_role(Role::Owner)
+	}
+
+	/// Removes the existing data which a restored snapshot replaces
+	async fn restore_clear(&self) -> Result<()> {
+		let step = backup::RestoreStep::Clear.encode();
+		let mut next = Some(vec![0x00]..vec![0xff]);
+		while let Some(rng) = next {
+			let txn = self.transaction(Write, Optimistic).await?;
+			// Check the marker, so that this batch fails if a concurrent
+			// restore has already started applying the staged entries
+			catch!(txn, txn.putc(&backup::restore_marker(), &step, Some(&step)).await);
+			let batch = catch!(txn, txn.batch_keys(rng, *EXPORT_BATCH_SIZE, None).await);
+			next = batch.next;
+			for k in batch.result.iter().filter(|k| backup::is_backed_up(k)) {
+				catch!(txn, txn.clr(k).await);
+			}
+			txn.commit().await?;
+		}
+		Ok(())
+	}
+
+	/// Writes a batch of restored key-value pairs
+	async fn restore_batch(&self, entries: Vec<(Key, Val)>) -> Result<()> {
+		if entries.is_empty() {
+			return Ok(());
+		}
+		let txn = self.transaction(Write, Optimistic).await?;
+		for (k, v) in entries {
+			catch!(txn, txn.set(&k, &v).await);
+		}
+		txn.commit().await
+	}
+
+	/// Checks the consistency of the entire datastore
+	///
+	/// Index entries, graph edges, record references and live query
+	/// registrations are checked against the records and nodes they refer to.
+	/// When `repair` is set, every inconsistent key is removed. This is meant
+	/// to be run offline, as concurrent writes can be reported as problems.
+	#[instrument(err, level = "debug", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn fix(&self, repair: bool) -> Result<fix::Report> {
+		fix::Checker::new(self, repair).run().await
+	}
+
+	/// Re-encrypts the entire datastore with the current encryption key
+	///
+	/// Values encrypted with a previous key, or stored before encryption was
+	/// enabled when the keyring allows plaintext values, are rewritten with
+	/// the current key. This is meant to be run offline, once the keys have
+	/// been rotated.
+	#[instrument(err, level = "debug", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn reencrypt(&self) -> Result<encryption::Report> {
+		encryption::reencrypt(&self.transaction_factory).await
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<()> {
@@ -2304,6 +2806,8 @@ impl Datastore {
 		)?;
 		// Setup the notification channel
 		ctx.add_notifications(self.notification_channel.as_ref());
+		// Setup the resource quotas
+		ctx.set_quotas(self.quotas.clone());
 		Ok(ctx)
 	}
 
@@ -2432,12 +2936,55 @@ impl Datastore {
 				let opt = self.setup_options(session);
 
 				let mut ctx = self.setup_ctx()?;
+				catch!(
+					tx,
+					ctx.restrict_capabilities(&tx, &self.capabilities, Some(ns), Some(&db.name))
+						.await
+				);
 				ctx.set_transaction(Arc::clone(&tx));
 				ctx.attach_session(session)?;
 				let ctx = &ctx.freeze();
 
 				process_api_request(ctx, &opt, api, req).await
 			}
+			None if req.method == ApiMethod::Get && path.trim_matches('/') == OPENAPI_PATH => {
+				trace!(
+					request_id = %req.request_id,
+					path = %path,
+					"No API definition found for path, serving the OpenAPI document"
+				);
+
+				let opt = self.setup_options(session);
+
+				let mut ctx = self.setup_ctx()?;
+				catch!(
+					tx,
+					ctx.restrict_capabilities(&tx, &self.capabilities, Some(ns), Some(&db.name))
+						.await
+				);
+				ctx.set_transaction(Arc::clone(&tx));
+				ctx.attach_session(session)?;
+				let ctx = &ctx.freeze();
+
+				let res = match openapi::generate(ctx, &opt).await {
+					Ok(doc) => {
+						let mut res = ApiResponse {
+							status: http::StatusCode::OK,
+							body: convert_value_to_public_value(doc)?,
+							request_id: req.request_id.clone(),
+							..Default::default()
+						};
+						convert_response_value(&mut res, BodyStrategy::Json)?;
+						res
+					}
+					Err(e) if matches!(e.downcast_ref::<Error>(), Some(Error::IamError(_))) => {
+						ApiResponse::from_error(ApiError::PermissionDenied.into(), req.request_id)
+					}
+					Err(e) => ApiResponse::from_error_secure(e, req.request_id),
+				};
+				tx.cancel().await?;
+				return Ok(res);
+			}
 			None => {
 				trace!(
 					request_id = %req.request_id,
@@ -2550,6 +3097,33 @@ mod test {
 		}
 	}
 
+	#[tokio::test]
+	async fn export_redacts_totp_for_non_owners() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE USER user ON DB PASSWORD 'pass' TOTP SECRET 'GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ' RECOVERY []";
+		ds.execute(sql, &sess, None).await.unwrap();
+
+		let export = async |sess: &Session| {
+			let (snd, rcv) = async_channel::unbounded();
+			ds.export(sess, snd).await.unwrap().await.unwrap();
+			let mut data = Vec::new();
+			while let Ok(chunk) = rcv.try_recv() {
+				data.extend(chunk);
+			}
+			String::from_utf8(data).unwrap()
+		};
+
+		// Owners export the second factor of users
+		let out = export(&sess).await;
+		assert!(out.contains("TOTP SECRET 'GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ'"), "{out}");
+		// Other users only export the rest of the definition
+		let sess = Session::viewer().with_ns("test").with_db("test");
+		let out = export(&sess).await;
+		assert!(out.contains("DEFINE USER user ON DATABASE"), "{out}");
+		assert!(!out.contains("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"), "{out}");
+	}
+
 	#[tokio::test]
 	pub async fn very_deep_query() -> Result<()> {
 		use reblessive::{Stack, Stk};
@@ -23,7 +23,9 @@ use crate::http::HttpClient;
 use crate::iam::jwks::JwksCache;
 use crate::idx::trees::store::IndexStores;
 use crate::kvs::cache::ds::DatastoreCache;
+use crate::kvs::encryption::Keyring;
 use crate::kvs::index::IndexBuilder;
+use crate::kvs::quota::Quotas;
 use crate::kvs::sequences::Sequences;
 use crate::kvs::slowlog::SlowLog;
 use crate::kvs::{Datastore, TransactionBuilder, TransactionBuilderFactory, TransactionFactory};
@@ -42,6 +44,7 @@ pub struct Builder {
 	query_timeout: Option<Duration>,
 	temporary_directory: Option<Arc<PathBuf>>,
 	authenticate: bool,
+	encryption: Option<Keyring>,
 	#[cfg(feature = "surrealism")]
 	lazy_surrealism: bool,
 }
@@ -64,6 +67,7 @@ impl Builder {
 			query_timeout: None,
 			temporary_directory: None,
 			authenticate: false,
+			encryption: None,
 			#[cfg(feature = "surrealism")]
 			lazy_surrealism: false,
 		}
@@ -134,6 +138,15 @@ impl Builder {
 		self
 	}
 
+	/// Encrypts the values stored in the datastore with this keyring
+	///
+	/// When not set, the keyring is loaded from the `SURREAL_ENCRYPTION_*`
+	/// environment variables, and values are only encrypted if a key is set.
+	pub fn with_encryption(mut self, keyring: Keyring) -> Self {
+		self.encryption = Some(keyring);
+		self
+	}
+
 	#[cfg(feature = "surrealism")]
 	pub fn with_lazy_surrealism(mut self, lazy_surrealism: bool) -> Self {
 		self.lazy_surrealism = lazy_surrealism;
@@ -160,16 +173,19 @@ impl Builder {
 		buckets: BucketsManager,
 	) -> Result<Datastore> {
 		let async_event_trigger = Arc::new(Notify::new());
-		let tf = TransactionFactory::new(async_event_trigger.clone(), builder);
+		let encryption = match self.encryption {
+			Some(keyring) => Some(keyring),
+			None => Keyring::from_env()?,
+		};
+		let tf = TransactionFactory::new(async_event_trigger.clone(), builder)
+			.with_encryption(encryption.map(Arc::new));
 		let id = self.id.unwrap_or_else(Uuid::new_v4);
 		let capabilities = Arc::new(self.capabilities);
 		let dynamic_configuration = DynamicConfiguration::default();
 		dynamic_configuration.set_query_timeout(self.query_timeout);
 		#[cfg(feature = "http")]
-		let http_client = Arc::new(
-			HttpClient::new(capabilities.allow_net.clone(), capabilities.deny_net.clone())
-				.context("Could not create http client")?,
-		);
+		let http_client = HttpClient::cached(&capabilities.allow_net, &capabilities.deny_net)
+			.context("Could not create http client")?;
 
 		Ok(Datastore {
 			id,
@@ -187,6 +203,7 @@ impl Builder {
 			#[cfg(storage)]
 			temporary_directory: self.temporary_directory,
 			cache: Arc::new(DatastoreCache::new()),
+			quotas: Arc::new(Quotas::default()),
 			buckets,
 			sequences: Sequences::new(tf, id),
 			#[cfg(feature = "surrealism")]
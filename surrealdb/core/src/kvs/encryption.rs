@@ -0,0 +1,711 @@
+//! Encryption at rest for stored values.
+//!
+//! When a [`Keyring`] is configured on a datastore, every transaction created
+//! by the datastore is wrapped in an [`Encrypted`] transaction, which encrypts
+//! values before they are handed to the storage engine, and decrypts them when
+//! they are read back. This applies to every storage engine, so the files
+//! written by RocksDB, SurrealKV, and the persistence files of the `mem://`
+//! engine only ever contain encrypted values.
+//!
+//! Values are encrypted with AES-256-GCM, using a random nonce for each value.
+//! The key under which a value is stored is used as additional authenticated
+//! data, so an encrypted value can not be moved to another key without the
+//! decryption failing. Keys are not encrypted, as the storage engines depend
+//! on their ordering for range scans.
+//!
+//! An encrypted value is laid out as follows:
+//!
+//! ```text
+//! value := MAGIC FORMAT_VERSION key_id:[u8; 4] nonce:[u8; 12] ciphertext tag:[u8; 16]
+//! ```
+//!
+//! The key id is derived from the SHA-256 digest of the encryption key. Values
+//! are always encrypted with the current key, and are decrypted with the key
+//! matching the key id, so keys can be rotated by configuring the new key as
+//! the current key, and the old key as a previous key. Values encrypted with a
+//! previous key are re-encrypted when they are next written, or all at once
+//! with [`Datastore::reencrypt`](super::Datastore::reencrypt) while the
+//! datastore is offline. Once this is complete, the previous key can be
+//! removed.
+//!
+//! The keyring is read from the following environment variables, each key
+//! being 32 bytes encoded as base64:
+//!
+//! - `SURREAL_ENCRYPTION_KEY`: the current key,
+//! - `SURREAL_ENCRYPTION_KEY_FILE`: a file containing the current key,
+//! - `SURREAL_ENCRYPTION_PREVIOUS_KEYS`: a comma-separated list of previous keys,
+//! - `SURREAL_ENCRYPTION_PREVIOUS_KEY_FILES`: a comma-separated list of files containing previous
+//!   keys.
+//!
+//! Historical versions kept by versioned storage engines are not re-encrypted,
+//! so previous keys are needed for as long as those versions are retained.
+//! Backups and exports are taken through transactions, and so contain
+//! decrypted values.
+
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::api::{ScanLimit, Transactable};
+use super::batch::Batch;
+use super::ds::TransactionFactory;
+use super::err::{Error, Result};
+use super::{BoxTimeStamp, BoxTimeStampImpl, Key, Val};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::key::debug::Sprintable;
+
+/// The current encryption key, encoded as base64.
+static SURREAL_ENCRYPTION_KEY: LazyLock<Option<String>> =
+	lazy_env_parse!("SURREAL_ENCRYPTION_KEY", Option<String>);
+
+/// A file containing the current encryption key, encoded as base64.
+static SURREAL_ENCRYPTION_KEY_FILE: LazyLock<Option<String>> =
+	lazy_env_parse!("SURREAL_ENCRYPTION_KEY_FILE", Option<String>);
+
+/// A comma-separated list of previous encryption keys, encoded as base64.
+static SURREAL_ENCRYPTION_PREVIOUS_KEYS: LazyLock<Option<String>> =
+	lazy_env_parse!("SURREAL_ENCRYPTION_PREVIOUS_KEYS", Option<String>);
+
+/// A comma-separated list of files containing previous encryption keys.
+static SURREAL_ENCRYPTION_PREVIOUS_KEY_FILES: LazyLock<Option<String>> =
+	lazy_env_parse!("SURREAL_ENCRYPTION_PREVIOUS_KEY_FILES", Option<String>);
+
+/// The byte which starts every encrypted value
+const MAGIC: u8 = 0xE5;
+
+/// The current version of the encrypted value format
+const FORMAT_VERSION: u8 = 1;
+
+/// The length of an encryption key
+pub const KEY_LEN: usize = 32;
+
+/// The length of a key id
+const KEY_ID_LEN: usize = 4;
+
+/// The length of the authentication tag appended to the ciphertext
+const TAG_LEN: usize = 16;
+
+/// The length of the header of an encrypted value
+const HEADER_LEN: usize = 2 + KEY_ID_LEN + NONCE_LEN;
+
+/// A 256-bit key used to encrypt stored values.
+pub struct EncryptionKey {
+	/// The id stored alongside values encrypted with this key
+	id: [u8; KEY_ID_LEN],
+	/// The AES-256-GCM key
+	key: LessSafeKey,
+}
+
+impl fmt::Debug for EncryptionKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("EncryptionKey").field("id", &hex::encode(self.id)).finish_non_exhaustive()
+	}
+}
+
+impl EncryptionKey {
+	/// Create an encryption key from 32 raw bytes.
+	pub fn new(bytes: &[u8]) -> Result<Self> {
+		if bytes.len() != KEY_LEN {
+			return Err(Error::Encryption(format!(
+				"The encryption key must be {KEY_LEN} bytes, found {} bytes",
+				bytes.len()
+			)));
+		}
+		let key = UnboundKey::new(&AES_256_GCM, bytes)
+			.map_err(|_| Error::Encryption("Invalid encryption key".to_string()))?;
+		let digest = Sha256::digest(bytes);
+		let mut id = [0u8; KEY_ID_LEN];
+		id.copy_from_slice(&digest[..KEY_ID_LEN]);
+		Ok(Self {
+			id,
+			key: LessSafeKey::new(key),
+		})
+	}
+
+	/// Create an encryption key from its base64 encoding.
+	pub fn from_base64(encoded: &str) -> Result<Self> {
+		let bytes = STANDARD.decode(encoded.trim()).map_err(|e| {
+			Error::Encryption(format!("The encryption key is not valid base64: {e}"))
+		})?;
+		Self::new(&bytes)
+	}
+
+	/// Read an encryption key, encoded as base64, from a file.
+	pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+		let path = path.as_ref();
+		let encoded = std::fs::read_to_string(path).map_err(|e| {
+			Error::Encryption(format!(
+				"Unable to read the encryption key file '{}': {e}",
+				path.display()
+			))
+		})?;
+		Self::from_base64(&encoded)
+	}
+
+	/// Generate a new random encryption key, encoded as base64.
+	pub fn generate() -> Result<String> {
+		let mut bytes = [0u8; KEY_LEN];
+		SystemRandom::new()
+			.fill(&mut bytes)
+			.map_err(|_| Error::Encryption("Unable to generate an encryption key".to_string()))?;
+		Ok(STANDARD.encode(bytes))
+	}
+
+	/// The id stored alongside values encrypted with this key, as hex.
+	pub fn id(&self) -> String {
+		hex::encode(self.id)
+	}
+}
+
+/// The keys used to encrypt and decrypt stored values.
+#[derive(Debug)]
+pub struct Keyring {
+	/// The key used to encrypt values
+	current: EncryptionKey,
+	/// Older keys, only used to decrypt values
+	previous: Vec<EncryptionKey>,
+	/// Whether values which were stored without encryption can be read
+	plaintext: bool,
+	/// The source of the nonces
+	rng: SystemRandom,
+}
+
+impl Keyring {
+	/// Create a keyring which encrypts values with the given key.
+	pub fn new(current: EncryptionKey) -> Self {
+		Self {
+			current,
+			previous: Vec::new(),
+			plaintext: false,
+			rng: SystemRandom::new(),
+		}
+	}
+
+	/// Add a previous key, used to decrypt values encrypted before the key
+	/// was rotated.
+	pub fn with_previous(mut self, key: EncryptionKey) -> Self {
+		if key.id != self.current.id && self.previous.iter().all(|k| k.id != key.id) {
+			self.previous.push(key);
+		}
+		self
+	}
+
+	/// Set whether values which were stored before encryption was enabled
+	/// can be read.
+	///
+	/// This is only intended for encrypting an existing datastore with
+	/// [`Datastore::reencrypt`](super::Datastore::reencrypt).
+	pub fn with_plaintext(mut self, plaintext: bool) -> Self {
+		self.plaintext = plaintext;
+		self
+	}
+
+	/// Load the keyring from the environment, if an encryption key is set.
+	pub fn from_env() -> Result<Option<Self>> {
+		let current =
+			match (SURREAL_ENCRYPTION_KEY.as_deref(), SURREAL_ENCRYPTION_KEY_FILE.as_deref()) {
+				(Some(_), Some(_)) => {
+					return Err(Error::Encryption(
+					"Only one of SURREAL_ENCRYPTION_KEY and SURREAL_ENCRYPTION_KEY_FILE can be set"
+						.to_string(),
+				));
+				}
+				(Some(key), None) => EncryptionKey::from_base64(key)?,
+				(None, Some(path)) => EncryptionKey::from_file(path)?,
+				(None, None) => {
+					if SURREAL_ENCRYPTION_PREVIOUS_KEYS.is_some()
+						|| SURREAL_ENCRYPTION_PREVIOUS_KEY_FILES.is_some()
+					{
+						return Err(Error::Encryption(
+							"Previous encryption keys are set without a current encryption key"
+								.to_string(),
+						));
+					}
+					return Ok(None);
+				}
+			};
+		let mut keyring = Self::new(current);
+		for key in list(SURREAL_ENCRYPTION_PREVIOUS_KEYS.as_deref()) {
+			keyring = keyring.with_previous(EncryptionKey::from_base64(key)?);
+		}
+		for path in list(SURREAL_ENCRYPTION_PREVIOUS_KEY_FILES.as_deref()) {
+			keyring = keyring.with_previous(EncryptionKey::from_file(path)?);
+		}
+		Ok(Some(keyring))
+	}
+
+	/// Returns whether a value starts with the header of an encrypted value.
+	fn is_framed(val: &[u8]) -> bool {
+		val.len() >= 2 && val[0] == MAGIC && val[1] == FORMAT_VERSION
+	}
+
+	/// Returns the key a value was encrypted with, if it is encrypted with a
+	/// key in this keyring.
+	fn key_for(&self, val: &[u8]) -> Option<&EncryptionKey> {
+		if !Self::is_framed(val) || val.len() < HEADER_LEN + TAG_LEN {
+			return None;
+		}
+		let id = &val[2..2 + KEY_ID_LEN];
+		std::iter::once(&self.current).chain(self.previous.iter()).find(|k| k.id == id)
+	}
+
+	/// Returns whether a value is encrypted with the current key.
+	fn is_current(&self, val: &[u8]) -> bool {
+		self.key_for(val).is_some_and(|k| k.id == self.current.id)
+	}
+
+	/// Encrypt a value stored under a key with the current key.
+	pub(crate) fn encrypt(&self, key: &[u8], val: Val) -> Result<Val> {
+		let mut nonce = [0u8; NONCE_LEN];
+		self.rng
+			.fill(&mut nonce)
+			.map_err(|_| Error::Encryption("Unable to generate a nonce".to_string()))?;
+		let mut out = Vec::with_capacity(HEADER_LEN + val.len() + TAG_LEN);
+		out.push(MAGIC);
+		out.push(FORMAT_VERSION);
+		out.extend_from_slice(&self.current.id);
+		out.extend_from_slice(&nonce);
+		out.extend_from_slice(&val);
+		let tag = self
+			.current
+			.key
+			.seal_in_place_separate_tag(
+				Nonce::assume_unique_for_key(nonce),
+				Aad::from(key),
+				&mut out[HEADER_LEN..],
+			)
+			.map_err(|_| {
+				Error::Encryption(format!("Unable to encrypt the value of {}", key.sprint()))
+			})?;
+		out.extend_from_slice(tag.as_ref());
+		Ok(out)
+	}
+
+	/// Decrypt a value stored under a key.
+	///
+	/// Values without an encryption header are only returned as they are when
+	/// plaintext values can be read. A value with an encryption header which
+	/// can not be decrypted is always an error, so that tampered or corrupted
+	/// values are never mistaken for plaintext.
+	pub(crate) fn decrypt(&self, key: &[u8], mut val: Val) -> Result<Val> {
+		if self.plaintext && !Self::is_framed(&val) {
+			return Ok(val);
+		}
+		let Some(k) = self.key_for(&val) else {
+			return Err(Error::Encryption(format!(
+				"The value of {} is not encrypted with a known key",
+				key.sprint()
+			)));
+		};
+		let nonce = Nonce::try_assume_unique_for_key(&val[2 + KEY_ID_LEN..HEADER_LEN])
+			.map_err(|_| Error::Encryption("Invalid nonce".to_string()))?;
+		let Ok(len) =
+			k.key.open_in_place(nonce, Aad::from(key), &mut val[HEADER_LEN..]).map(|v| v.len())
+		else {
+			return Err(Error::Encryption(format!(
+				"Unable to decrypt the value of {}",
+				key.sprint()
+			)));
+		};
+		val.truncate(HEADER_LEN + len);
+		val.drain(..HEADER_LEN);
+		Ok(val)
+	}
+
+	/// Decrypt a list of key-value pairs.
+	fn decrypt_pairs(&self, pairs: Vec<(Key, Val)>) -> Result<Vec<(Key, Val)>> {
+		pairs
+			.into_iter()
+			.map(|(k, v)| {
+				let v = self.decrypt(&k, v)?;
+				Ok((k, v))
+			})
+			.collect()
+	}
+}
+
+/// Split a comma-separated environment variable into its trimmed items.
+fn list(value: Option<&str>) -> impl Iterator<Item = &str> {
+	value.into_iter().flat_map(|v| v.split(',')).map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// A transaction which encrypts the values written to, and decrypts the values
+/// read from, an underlying storage engine transaction.
+pub(super) struct Encrypted {
+	/// The storage engine transaction
+	inner: Box<dyn Transactable>,
+	/// The keys used to encrypt and decrypt values
+	keyring: Arc<Keyring>,
+}
+
+impl Encrypted {
+	/// Wrap a storage engine transaction.
+	pub(super) fn new(inner: Box<dyn Transactable>, keyring: Arc<Keyring>) -> Self {
+		Self {
+			inner,
+			keyring,
+		}
+	}
+
+	/// Check a conditional value against the decrypted stored value.
+	///
+	/// The storage engine compares the condition against the stored bytes,
+	/// so when the decrypted value matches, the stored encrypted value is
+	/// returned, to be passed to the storage engine as the condition.
+	async fn check(&self, key: &Key, chk: Option<Val>) -> Result<Option<Val>> {
+		let Some(chk) = chk else {
+			return Ok(None);
+		};
+		if self.inner.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		if !self.inner.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		match self.inner.get(key.clone(), None).await? {
+			Some(stored) if self.keyring.decrypt(key, stored.clone())? == chk => Ok(Some(stored)),
+			_ => Err(Error::TransactionConditionNotMet),
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Transactable for Encrypted {
+	fn kind(&self) -> &'static str {
+		self.inner.kind()
+	}
+
+	fn closed(&self) -> bool {
+		self.inner.closed()
+	}
+
+	fn writeable(&self) -> bool {
+		self.inner.writeable()
+	}
+
+	fn keeps_versions(&self) -> bool {
+		self.inner.keeps_versions()
+	}
+
+	async fn cancel(&self) -> Result<()> {
+		self.inner.cancel().await
+	}
+
+	async fn commit(&self) -> Result<()> {
+		self.inner.commit().await
+	}
+
+	async fn exists(&self, key: Key, version: Option<u64>) -> Result<bool> {
+		self.inner.exists(key, version).await
+	}
+
+	async fn get(&self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+		match self.inner.get(key.clone(), version).await? {
+			Some(val) => Ok(Some(self.keyring.decrypt(&key, val)?)),
+			None => Ok(None),
+		}
+	}
+
+	async fn set(&self, key: Key, val: Val) -> Result<()> {
+		let val = self.keyring.encrypt(&key, val)?;
+		self.inner.set(key, val).await
+	}
+
+	async fn put(&self, key: Key, val: Val) -> Result<()> {
+		let val = self.keyring.encrypt(&key, val)?;
+		self.inner.put(key, val).await
+	}
+
+	async fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+		let chk = self.check(&key, chk).await?;
+		let val = self.keyring.encrypt(&key, val)?;
+		self.inner.putc(key, val, chk).await
+	}
+
+	async fn del(&self, key: Key) -> Result<()> {
+		self.inner.del(key).await
+	}
+
+	async fn delc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		let chk = self.check(&key, chk).await?;
+		self.inner.delc(key, chk).await
+	}
+
+	async fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.inner.keys(rng, limit, skip, version).await
+	}
+
+	async fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.inner.keysr(rng, limit, skip, version).await
+	}
+
+	async fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.scan(rng, limit, skip, version).await?;
+		self.keyring.decrypt_pairs(res)
+	}
+
+	async fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.scanr(rng, limit, skip, version).await?;
+		self.keyring.decrypt_pairs(res)
+	}
+
+	async fn replace(&self, key: Key, val: Val) -> Result<()> {
+		let val = self.keyring.encrypt(&key, val)?;
+		self.inner.replace(key, val).await
+	}
+
+	async fn clr(&self, key: Key) -> Result<()> {
+		self.inner.clr(key).await
+	}
+
+	async fn clrc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		let chk = self.check(&key, chk).await?;
+		self.inner.clrc(key, chk).await
+	}
+
+	async fn getm(&self, keys: Vec<Key>, version: Option<u64>) -> Result<Vec<Option<Val>>> {
+		let res = self.inner.getm(keys.clone(), version).await?;
+		keys.iter()
+			.zip(res)
+			.map(|(k, v)| v.map(|v| self.keyring.decrypt(k, v)).transpose())
+			.collect()
+	}
+
+	async fn getp(&self, key: Key, version: Option<u64>) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.getp(key, version).await?;
+		self.keyring.decrypt_pairs(res)
+	}
+
+	async fn getr(&self, rng: Range<Key>, version: Option<u64>) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.getr(rng, version).await?;
+		self.keyring.decrypt_pairs(res)
+	}
+
+	async fn delp(&self, key: Key) -> Result<()> {
+		self.inner.delp(key).await
+	}
+
+	async fn delr(&self, rng: Range<Key>) -> Result<()> {
+		self.inner.delr(rng).await
+	}
+
+	async fn clrp(&self, key: Key) -> Result<()> {
+		self.inner.clrp(key).await
+	}
+
+	async fn clrr(&self, rng: Range<Key>) -> Result<()> {
+		self.inner.clrr(rng).await
+	}
+
+	async fn count(&self, rng: Range<Key>, version: Option<u64>) -> Result<usize> {
+		self.inner.count(rng, version).await
+	}
+
+	async fn batch_keys(
+		&self,
+		rng: Range<Key>,
+		batch: u32,
+		version: Option<u64>,
+	) -> Result<Batch<Key>> {
+		self.inner.batch_keys(rng, batch, version).await
+	}
+
+	async fn batch_keys_vals(
+		&self,
+		rng: Range<Key>,
+		batch: u32,
+		version: Option<u64>,
+	) -> Result<Batch<(Key, Val)>> {
+		let res = self.inner.batch_keys_vals(rng, batch, version).await?;
+		Ok(Batch::new(res.next, self.keyring.decrypt_pairs(res.result)?))
+	}
+
+	async fn new_save_point(&self) -> Result<()> {
+		self.inner.new_save_point().await
+	}
+
+	async fn release_last_save_point(&self) -> Result<()> {
+		self.inner.release_last_save_point().await
+	}
+
+	async fn rollback_to_save_point(&self) -> Result<()> {
+		self.inner.rollback_to_save_point().await
+	}
+
+	fn releases_save_points(&self) -> bool {
+		self.inner.releases_save_points()
+	}
+
+	async fn timestamp(&self) -> Result<BoxTimeStamp> {
+		self.inner.timestamp().await
+	}
+
+	fn timestamp_impl(&self) -> BoxTimeStampImpl {
+		self.inner.timestamp_impl()
+	}
+
+	async fn compact(&self, range: Option<Range<Key>>) -> anyhow::Result<()> {
+		self.inner.compact(range).await
+	}
+}
+
+/// The outcome of re-encrypting a datastore
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Report {
+	/// The number of values which were read
+	pub scanned: u64,
+	/// The number of values which were encrypted with the current key
+	pub reencrypted: u64,
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"Re-encrypted {} of {} values with the current encryption key",
+			self.reencrypted, self.scanned
+		)
+	}
+}
+
+/// Re-encrypt every value which is not encrypted with the current key.
+///
+/// The keyspace is processed in batches, each in its own transaction, which
+/// is committed before the next batch is read. Values already encrypted with
+/// the current key are left untouched, so an interrupted run can be resumed.
+pub(super) async fn reencrypt(tf: &TransactionFactory) -> anyhow::Result<Report> {
+	let Some(keyring) = tf.encryption() else {
+		anyhow::bail!(Error::Encryption("No encryption key is configured".to_string()));
+	};
+	let mut report = Report::default();
+	let mut next: Option<Range<Key>> = Some(vec![0x00]..vec![0xff]);
+	while let Some(rng) = next {
+		let txn = tf.unencrypted(true).await?;
+		let res = async {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			for (k, v) in batch.result {
+				report.scanned += 1;
+				if keyring.is_current(&v) {
+					continue;
+				}
+				let v = keyring.decrypt(&k, v)?;
+				let v = keyring.encrypt(&k, v)?;
+				txn.set(k, v).await?;
+				report.reencrypted += 1;
+			}
+			Ok::<_, Error>(batch.next)
+		}
+		.await;
+		match res {
+			Ok(rng) => {
+				txn.commit().await?;
+				next = rng;
+			}
+			Err(e) => {
+				txn.cancel().await?;
+				return Err(e.into());
+			}
+		}
+	}
+	Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn keyring() -> Keyring {
+		Keyring::new(EncryptionKey::new(&[1u8; KEY_LEN]).unwrap())
+	}
+
+	#[test]
+	fn roundtrip() {
+		let keyring = keyring();
+		let val = keyring.encrypt(b"key", b"value".to_vec()).unwrap();
+		assert_eq!(val.len(), HEADER_LEN + 5 + TAG_LEN);
+		assert_ne!(&val[HEADER_LEN..HEADER_LEN + 5], b"value");
+		assert!(keyring.is_current(&val));
+		assert_eq!(keyring.decrypt(b"key", val).unwrap(), b"value");
+	}
+
+	#[test]
+	fn bound_to_key() {
+		let keyring = keyring();
+		let val = keyring.encrypt(b"key", b"value".to_vec()).unwrap();
+		keyring.decrypt(b"other", val).unwrap_err();
+	}
+
+	#[test]
+	fn rotation() {
+		let old = keyring();
+		let val = old.encrypt(b"key", b"value".to_vec()).unwrap();
+		// A new key can not decrypt values encrypted with the old key
+		let new = Keyring::new(EncryptionKey::new(&[2u8; KEY_LEN]).unwrap());
+		new.decrypt(b"key", val.clone()).unwrap_err();
+		// Unless the old key is a previous key
+		let new = new.with_previous(EncryptionKey::new(&[1u8; KEY_LEN]).unwrap());
+		assert!(!new.is_current(&val));
+		assert_eq!(new.decrypt(b"key", val).unwrap(), b"value");
+		let val = new.encrypt(b"key", b"value".to_vec()).unwrap();
+		assert!(new.is_current(&val));
+		old.decrypt(b"key", val).unwrap_err();
+	}
+
+	#[test]
+	fn plaintext() {
+		let keyring = keyring();
+		keyring.decrypt(b"key", b"value".to_vec()).unwrap_err();
+		let keyring = keyring.with_plaintext(true);
+		assert_eq!(keyring.decrypt(b"key", b"value".to_vec()).unwrap(), b"value");
+		// Encrypted values which fail to decrypt are never read as plaintext
+		let mut val = keyring.encrypt(b"key", b"value".to_vec()).unwrap();
+		keyring.decrypt(b"other", val.clone()).unwrap_err();
+		let last = val.len() - 1;
+		val[last] ^= 0xff;
+		keyring.decrypt(b"key", val).unwrap_err();
+		let other = Keyring::new(EncryptionKey::new(&[2u8; KEY_LEN]).unwrap()).with_plaintext(true);
+		let val = other.encrypt(b"key", b"value".to_vec()).unwrap();
+		keyring.decrypt(b"key", val).unwrap_err();
+	}
+
+	#[test]
+	fn invalid_keys() {
+		EncryptionKey::new(&[0u8; 16]).unwrap_err();
+		EncryptionKey::from_base64("not base64!").unwrap_err();
+		let key = EncryptionKey::generate().unwrap();
+		EncryptionKey::from_base64(&key).unwrap();
+	}
+}
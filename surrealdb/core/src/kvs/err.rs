@@ -64,6 +64,10 @@ pub enum Error {
 	#[error("The specified timestamp is not valid for the underlying datastore: {0}")]
 	TimestampInvalid(String),
 
+	/// A stored value could not be encrypted or decrypted
+	#[error("There was a problem with datastore encryption: {0}")]
+	Encryption(String),
+
 	/// There was an unknown internal error
 	#[error("There was an internal error: {0}")]
 	Internal(String),
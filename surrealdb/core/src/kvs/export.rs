@@ -36,6 +36,8 @@ pub struct Config {
 	pub versions: bool,
 	pub records: bool,
 	pub sequences: bool,
+	pub jobs: bool,
+	pub sinks: bool,
 }
 
 impl Default for Config {
@@ -54,6 +56,8 @@ impl Default for Config {
 			versions: false,
 			records: true,
 			sequences: true,
+			jobs: true,
+			sinks: true,
 		}
 	}
 }
@@ -160,11 +164,14 @@ impl<F: fmt::Display> fmt::Display for InlineCommentDisplay<F> {
 
 impl Transaction {
 	/// Writes the full database contents as binary SQL.
+	///
+	/// The TOTP enrolments of users are only written when `secrets` is set.
 	pub async fn export(
 		&self,
 		ns: &str,
 		db: &str,
 		cfg: Config,
+		secrets: bool,
 		chn: Sender<Vec<u8>>,
 	) -> Result<()> {
 		let db = self.get_db_by_name(ns, db, None).await?.ok_or_else(|| {
@@ -174,7 +181,7 @@ impl Transaction {
 		})?;
 
 		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
-		self.export_metadata(&cfg, &chn, db.namespace_id, db.database_id).await?;
+		self.export_metadata(&cfg, secrets, &chn, db.namespace_id, db.database_id).await?;
 		// Output TABLES
 		self.export_tables(&cfg, &chn, db.namespace_id, db.database_id).await?;
 		Ok(())
@@ -183,6 +190,7 @@ impl Transaction {
 	async fn export_metadata(
 		&self,
 		cfg: &Config,
+		secrets: bool,
 		chn: &Sender<Vec<u8>>,
 		ns: NamespaceId,
 		db: DatabaseId,
@@ -195,7 +203,14 @@ impl Transaction {
 			let users = self.all_db_users(ns, db, None).await?;
 			self.export_section(
 				"USERS",
-				users.iter().map(|x| DefineUserStatement::from_definition(Base::Db, x)),
+				users.iter().map(|x| {
+					let stmt = DefineUserStatement::from_definition(Base::Db, x);
+					if secrets {
+						stmt
+					} else {
+						stmt.redact()
+					}
+				}),
 				chn,
 			)
 			.await?;
@@ -267,6 +282,18 @@ impl Transaction {
 			self.export_section("SEQUENCES", sequences.iter(), chn).await?;
 		}
 
+		// Output JOBS
+		if cfg.jobs {
+			let jobs = self.all_db_jobs(ns, db, None).await?;
+			self.export_section("JOBS", jobs.iter(), chn).await?;
+		}
+
+		// Output SINKS
+		if cfg.sinks {
+			let sinks = self.all_db_sinks(ns, db, None).await?;
+			self.export_section("SINKS", sinks.iter(), chn).await?;
+		}
+
 		Ok(())
 	}
 
@@ -0,0 +1,488 @@
+//! Offline datastore consistency checks.
+//!
+//! Walks the keyspace of every namespace, database and table defined in the
+//! catalog, and checks the secondary data kept alongside records against the
+//! records themselves:
+//!
+//! - entries of standard and unique indexes (`key::index`) must point at an existing record,
+//! - index data must belong to an index which is still defined on the table,
+//! - both ends of graph edge pointers (`key::graph`) must exist,
+//! - both ends of record references (`key::ref`) must exist,
+//! - live query registrations (`key::node::lq` and `key::table::lq`) must belong to an active node
+//!   and be registered on both the node and the table.
+//!
+//! Each batch of keys is checked in its own transaction. When repairing, the
+//! transaction is writeable, the inconsistent keys are removed, and the
+//! transaction is committed before the next batch is read.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+
+use anyhow::Result;
+use serde::Serialize;
+use surrealdb_types::ToSql;
+use uuid::Uuid;
+
+use super::{Datastore, KVValue, LockType, Transaction, TransactionType};
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, NodeProvider, TableProvider};
+use crate::catalog::{
+	DatabaseDefinition, DatabaseId, Index, IndexDefinition, NamespaceDefinition, NamespaceId,
+	NodeLiveQuery, SubscriptionDefinition, TableDefinition,
+};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::key::debug::Sprintable;
+use crate::kvs::KVKey;
+use crate::val::{RecordId, RecordIdKey, TableName};
+
+/// The kind of inconsistency found in the datastore
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemKind {
+	/// An index entry pointing at a record which does not exist
+	OrphanedIndexEntry,
+	/// Index data left behind by an index which is no longer defined
+	UndefinedIndex,
+	/// A graph edge pointer whose origin or target record does not exist
+	DanglingEdge,
+	/// A record reference whose origin or target record does not exist
+	DanglingReference,
+	/// A live query registration which no node will ever serve
+	StaleLiveQuery,
+}
+
+impl fmt::Display for ProblemKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::OrphanedIndexEntry => f.write_str("orphaned index entry"),
+			Self::UndefinedIndex => f.write_str("undefined index"),
+			Self::DanglingEdge => f.write_str("dangling edge"),
+			Self::DanglingReference => f.write_str("dangling reference"),
+			Self::StaleLiveQuery => f.write_str("stale live query"),
+		}
+	}
+}
+
+/// An inconsistency found in the datastore
+#[derive(Clone, Debug, Serialize)]
+pub struct Problem {
+	/// The kind of inconsistency
+	pub kind: ProblemKind,
+	/// The namespace, database and table the inconsistency was found in
+	pub location: String,
+	/// A description of the inconsistency
+	pub detail: String,
+	/// The affected key, with non-printable bytes escaped
+	pub key: String,
+}
+
+impl fmt::Display for Problem {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} in {}: {}", self.kind, self.location, self.detail)
+	}
+}
+
+/// The result of a datastore consistency check
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Report {
+	/// The number of keys which were checked
+	pub checked: u64,
+	/// Whether the inconsistent keys were removed
+	pub repaired: bool,
+	/// The inconsistencies which were found
+	pub problems: Vec<Problem>,
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for problem in self.problems.iter() {
+			writeln!(f, "{problem}")?;
+		}
+		let action = if self.repaired {
+			"repaired"
+		} else {
+			"found"
+		};
+		write!(f, "Checked {} keys, {} {} problems", self.checked, action, self.problems.len())
+	}
+}
+
+/// Checks the consistency of a datastore, optionally repairing it.
+pub(super) struct Checker<'a> {
+	ds: &'a Datastore,
+	repair: bool,
+	report: Report,
+	/// The nodes which are still active in the cluster
+	nodes: HashSet<Uuid>,
+}
+
+impl<'a> Checker<'a> {
+	pub(super) fn new(ds: &'a Datastore, repair: bool) -> Self {
+		Self {
+			ds,
+			repair,
+			report: Report {
+				repaired: repair,
+				..Default::default()
+			},
+			nodes: HashSet::new(),
+		}
+	}
+
+	/// Runs all of the checks over the datastore
+	pub(super) async fn run(mut self) -> Result<Report> {
+		let txn = self.read().await?;
+		let nodes = catch!(txn, txn.all_nodes().await);
+		let nss = catch!(txn, txn.all_ns(None).await);
+		txn.cancel().await?;
+		self.nodes = nodes.iter().filter(|n| n.is_active()).map(|n| n.id).collect();
+		for ns in nss.iter() {
+			let txn = self.read().await?;
+			let dbs = catch!(txn, txn.all_db(ns.namespace_id, None).await);
+			txn.cancel().await?;
+			for db in dbs.iter() {
+				let txn = self.read().await?;
+				let tbs = catch!(txn, txn.all_tb(ns.namespace_id, db.database_id, None).await);
+				txn.cancel().await?;
+				for tb in tbs.iter() {
+					self.check_table(ns, db, tb).await?;
+				}
+			}
+		}
+		self.check_node_live_queries().await?;
+		Ok(self.report)
+	}
+
+	/// Opens a read transaction for fetching catalog definitions
+	async fn read(&self) -> Result<Transaction> {
+		self.ds.transaction(TransactionType::Read, LockType::Optimistic).await
+	}
+
+	/// Opens a transaction for checking a batch of keys
+	async fn batch(&self) -> Result<Transaction> {
+		let tt = if self.repair {
+			TransactionType::Write
+		} else {
+			TransactionType::Read
+		};
+		self.ds.transaction(tt, LockType::Optimistic).await
+	}
+
+	/// Completes the transaction for a checked batch of keys
+	async fn finish(&self, txn: Transaction) -> Result<()> {
+		if self.repair {
+			txn.commit().await
+		} else {
+			txn.cancel().await
+		}
+	}
+
+	/// Records a problem, removing the key when repairing
+	async fn found(
+		&mut self,
+		txn: &Transaction,
+		kind: ProblemKind,
+		location: &str,
+		detail: String,
+		key: &[u8],
+	) -> Result<()> {
+		if self.repair {
+			txn.clr(&key.to_vec()).await?;
+		}
+		self.report.problems.push(Problem {
+			kind,
+			location: location.to_string(),
+			detail,
+			key: key.sprint(),
+		});
+		Ok(())
+	}
+
+	async fn check_table(
+		&mut self,
+		ns: &NamespaceDefinition,
+		db: &DatabaseDefinition,
+		tb: &TableDefinition,
+	) -> Result<()> {
+		let location = format!("{}/{}/{}", ns.name, db.name, tb.name);
+		let (ns, db) = (db.namespace_id, db.database_id);
+		let txn = self.read().await?;
+		let ixs = catch!(txn, txn.all_tb_indexes(ns, db, &tb.name, None).await);
+		txn.cancel().await?;
+		// Check the entries of standard and unique indexes
+		for ix in ixs.iter().filter(|ix| matches!(ix.index, Index::Idx | Index::Uniq)) {
+			self.check_index(ns, db, &tb.name, ix, &location).await?;
+		}
+		// Check for data of indexes which are no longer defined
+		self.check_undefined_indexes(ns, db, &tb.name, &ixs, &location).await?;
+		// Check the graph edges and references of the records
+		self.check_graph(ns, db, &tb.name, &location).await?;
+		self.check_references(ns, db, &tb.name, &location).await?;
+		// Check the live queries registered on the table
+		self.check_table_live_queries(ns, db, &tb.name, &location).await
+	}
+
+	async fn check_index(
+		&mut self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		ix: &IndexDefinition,
+		location: &str,
+	) -> Result<()> {
+		let beg = crate::key::index::Index::prefix_beg(ns, db, tb, ix.index_id)?;
+		let end = crate::key::index::Index::prefix_end(ns, db, tb, ix.index_id)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let txn = self.batch().await?;
+			let res = catch!(txn, txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await);
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				self.report.checked += 1;
+				let rid: RecordId = catch!(txn, KVValue::kv_decode_value(v.clone()));
+				if !catch!(txn, record_exists(&txn, ns, db, &rid.table, &rid.key).await) {
+					let detail =
+						format!("index `{}` points at missing record {}", ix.name, rid.to_sql());
+					let kind = ProblemKind::OrphanedIndexEntry;
+					catch!(txn, self.found(&txn, kind, location, detail, k).await);
+				}
+			}
+			self.finish(txn).await?;
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	async fn check_undefined_indexes(
+		&mut self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		ixs: &[IndexDefinition],
+		location: &str,
+	) -> Result<()> {
+		let defined: HashSet<u32> = ixs.iter().map(|ix| ix.index_id.0).collect();
+		let root = crate::key::table::all::new(ns, db, tb).encode_key()?;
+		let mut next = Some(table_range(&root, b'+'));
+		let mut reported = HashSet::new();
+		while let Some(rng) = next {
+			let txn = self.batch().await?;
+			let res = catch!(txn, txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await);
+			next = res.next;
+			for k in res.result.iter() {
+				self.report.checked += 1;
+				// The index id follows the table prefix and the `+` separator
+				let Some(id) = k.get(root.len() + 1..root.len() + 5) else {
+					continue;
+				};
+				let id = u32::from_be_bytes([id[0], id[1], id[2], id[3]]);
+				if defined.contains(&id) {
+					continue;
+				}
+				if self.repair {
+					catch!(txn, txn.clr(k).await);
+				}
+				// Report each undefined index once, at its first key
+				if reported.insert(id) {
+					self.report.problems.push(Problem {
+						kind: ProblemKind::UndefinedIndex,
+						location: location.to_string(),
+						detail: format!("data found for undefined index with id {id}"),
+						key: k.sprint(),
+					});
+				}
+			}
+			self.finish(txn).await?;
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	async fn check_graph(
+		&mut self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		location: &str,
+	) -> Result<()> {
+		let root = crate::key::table::all::new(ns, db, tb).encode_key()?;
+		let mut next = Some(table_range(&root, b'~'));
+		while let Some(rng) = next {
+			let txn = self.batch().await?;
+			let res = catch!(txn, txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await);
+			next = res.next;
+			for k in res.result.iter() {
+				self.report.checked += 1;
+				let key = catch!(txn, crate::key::graph::Graph::decode_key(k));
+				let from = RecordId {
+					table: key.tb.into_owned(),
+					key: key.id,
+				};
+				let to = RecordId {
+					table: key.ft.into_owned(),
+					key: key.fk.into_owned(),
+				};
+				let detail = catch!(txn, missing_ends(&txn, ns, db, &from, &to).await);
+				if let Some(missing) = detail {
+					let detail = format!(
+						"edge {} {} {} {missing}",
+						from.to_sql(),
+						key.eg.to_sql(),
+						to.to_sql()
+					);
+					let kind = ProblemKind::DanglingEdge;
+					catch!(txn, self.found(&txn, kind, location, detail, k).await);
+				}
+			}
+			self.finish(txn).await?;
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	async fn check_references(
+		&mut self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		location: &str,
+	) -> Result<()> {
+		let root = crate::key::table::all::new(ns, db, tb).encode_key()?;
+		let mut next = Some(table_range(&root, b'&'));
+		while let Some(rng) = next {
+			let txn = self.batch().await?;
+			let res = catch!(txn, txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await);
+			next = res.next;
+			for k in res.result.iter() {
+				self.report.checked += 1;
+				let key = catch!(txn, crate::key::r#ref::Ref::decode_key(k));
+				let target = RecordId {
+					table: key.tb.into_owned(),
+					key: key.id.into_owned(),
+				};
+				let origin = RecordId {
+					table: key.ft.into_owned(),
+					key: key.fk.into_owned(),
+				};
+				let detail = catch!(txn, missing_ends(&txn, ns, db, &origin, &target).await);
+				if let Some(missing) = detail {
+					let detail = format!(
+						"reference from {} field `{}` to {} {missing}",
+						origin.to_sql(),
+						key.ff,
+						target.to_sql()
+					);
+					let kind = ProblemKind::DanglingReference;
+					catch!(txn, self.found(&txn, kind, location, detail, k).await);
+				}
+			}
+			self.finish(txn).await?;
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	async fn check_table_live_queries(
+		&mut self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		location: &str,
+	) -> Result<()> {
+		let beg = crate::key::table::lq::prefix(ns, db, tb)?;
+		let end = crate::key::table::lq::suffix(ns, db, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let txn = self.batch().await?;
+			let res = catch!(txn, txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await);
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				self.report.checked += 1;
+				let stm: SubscriptionDefinition = catch!(txn, KVValue::kv_decode_value(v.clone()));
+				let detail = if !self.nodes.contains(&stm.node) {
+					format!("live query {} belongs to inactive node {}", stm.id, stm.node)
+				} else {
+					let nlq = crate::key::node::lq::new(stm.node, stm.id);
+					if catch!(txn, txn.exists(&nlq, None).await) {
+						continue;
+					}
+					format!("live query {} is not registered on node {}", stm.id, stm.node)
+				};
+				let kind = ProblemKind::StaleLiveQuery;
+				catch!(txn, self.found(&txn, kind, location, detail, k).await);
+			}
+			self.finish(txn).await?;
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	async fn check_node_live_queries(&mut self) -> Result<()> {
+		let mut next = Some(b"/$\x00".to_vec()..b"/$\xff".to_vec());
+		while let Some(rng) = next {
+			let txn = self.batch().await?;
+			let res = catch!(txn, txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await);
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				// Only live queries are stored under the node keyspace
+				let Ok(nlq) = crate::key::node::lq::Lq::decode_key(k.clone()) else {
+					continue;
+				};
+				self.report.checked += 1;
+				let val: NodeLiveQuery = catch!(txn, KVValue::kv_decode_value(v.clone()));
+				let detail = if !self.nodes.contains(&nlq.nd) {
+					format!("live query {} belongs to inactive node {}", nlq.lq, nlq.nd)
+				} else {
+					let tlq = crate::key::table::lq::new(val.ns, val.db, &val.tb, nlq.lq);
+					if catch!(txn, txn.exists(&tlq, None).await) {
+						continue;
+					}
+					format!("live query {} is not registered on table `{}`", nlq.lq, val.tb)
+				};
+				let location = format!("node {}", nlq.nd);
+				let kind = ProblemKind::StaleLiveQuery;
+				catch!(txn, self.found(&txn, kind, &location, detail, k).await);
+			}
+			self.finish(txn).await?;
+			yield_now!();
+		}
+		Ok(())
+	}
+}
+
+/// Returns the range of keys under a table which start with the given separator
+fn table_range(root: &[u8], separator: u8) -> Range<Vec<u8>> {
+	let mut beg = root.to_vec();
+	beg.extend_from_slice(&[separator, 0x00]);
+	let mut end = root.to_vec();
+	end.extend_from_slice(&[separator, 0xff]);
+	beg..end
+}
+
+async fn record_exists(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	id: &RecordIdKey,
+) -> Result<bool> {
+	txn.exists(&crate::key::record::new(ns, db, tb, id), None).await
+}
+
+/// Describes which of two linked records are missing, if any
+async fn missing_ends(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	from: &RecordId,
+	to: &RecordId,
+) -> Result<Option<&'static str>> {
+	let from = record_exists(txn, ns, db, &from.table, &from.key).await?;
+	let to = record_exists(txn, ns, db, &to.table, &to.key).await?;
+	Ok(match (from, to) {
+		(true, true) => None,
+		(false, true) => Some("has a missing origin"),
+		(true, false) => Some("has a missing target"),
+		(false, false) => Some("has missing origin and target"),
+	})
+}
@@ -22,6 +22,8 @@ use crate::kvs::{Key, Val};
 
 pub struct Datastore {
 	db: Database,
+	/// Whether the datastore supports transaction versioning
+	versioned: bool,
 }
 
 pub struct Transaction {
@@ -29,6 +31,8 @@ pub struct Transaction {
 	done: AtomicBool,
 	/// Is the transaction writeable?
 	write: bool,
+	/// Whether the datastore supports transaction versioning
+	versioned: bool,
 	/// The underlying datastore transaction
 	inner: RwLock<Tx>,
 }
@@ -82,6 +86,7 @@ impl Datastore {
 		// Return the new datastore
 		Ok(Datastore {
 			db,
+			versioned: config.versioned,
 		})
 	}
 
@@ -99,6 +104,7 @@ impl Datastore {
 		Ok(Box::new(Transaction {
 			done: AtomicBool::new(false),
 			write,
+			versioned: self.versioned,
 			inner: RwLock::new(txn),
 		}))
 	}
@@ -121,6 +127,11 @@ impl Transactable for Transaction {
 		self.write
 	}
 
+	/// Check if versioning is enabled
+	fn keeps_versions(&self) -> bool {
+		self.versioned
+	}
+
 	/// Cancels the transaction.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
 	async fn cancel(&self) -> Result<()> {
@@ -14,8 +14,11 @@
 //!   database
 //! - `mem`: in-memory database
 
+pub mod backup;
 pub mod config;
+pub mod encryption;
 pub mod export;
+pub mod fix;
 
 mod api;
 mod batch;
@@ -28,6 +31,7 @@ mod scanner;
 mod threadpool;
 mod timestamp;
 mod tr;
+pub(crate) mod ttl;
 mod tx;
 mod util;
 
@@ -42,6 +46,7 @@ mod tests;
 
 pub(crate) mod cache;
 pub(crate) mod index;
+pub(crate) mod quota;
 pub(crate) mod sequences;
 pub(crate) mod slowlog;
 pub(crate) mod tasklease;
@@ -0,0 +1,360 @@
+//! Resource quotas of namespaces, databases, and users.
+//!
+//! Quotas are defined with the `QUOTA` clause of `DEFINE NAMESPACE`,
+//! `DEFINE DATABASE`, and `DEFINE USER`. When a statement starts, every quota
+//! which applies to it is enforced as follows:
+//!
+//! - `max_concurrent` limits the number of statements running at the same time, and is tracked
+//!   separately for each namespace, database, and user which defines it.
+//! - `max_storage` limits the number of bytes stored within a namespace or database, and is checked
+//!   before any statement which can write. The storage size is measured in the background by
+//!   [`Datastore::refresh_quota_storage`], so a namespace or database can exceed its quota by the
+//!   data written between two measurements.
+//! - `max_scanned` and `max_memory` are enforced while the statement runs, through the
+//!   [`QueryUsage`] of the statement, using the lowest of the applicable quotas.
+//!
+//! The quotas which apply to a namespace, database, and user are cached for
+//! [`QUOTA_DEFINITION_CACHE_SECS`] seconds, so that statements don't fetch the
+//! definitions each time they start. The cache is cleared whenever a quota is
+//! defined or removed on this node.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use futures::StreamExt;
+use parking_lot::Mutex;
+use quick_cache::sync::Cache;
+use web_time::Instant;
+
+use super::util::to_prefix_range;
+use super::{Datastore, LockType, Metric, Metrics, Transaction, TransactionType};
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, UserProvider};
+use crate::catalog::{DatabaseId, NamespaceId, QuotaDefinition};
+use crate::cnf::{QUOTA_DEFINITION_CACHE_SECS, QUOTA_DEFINITION_CACHE_SIZE};
+use crate::err::Error;
+use crate::exec::QueryUsage;
+use crate::iam::{Auth, Level};
+use crate::idx::planner::ScanDirection;
+
+/// The level at which a quota is defined
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum QuotaScope {
+	Namespace(NamespaceId),
+	Database(NamespaceId, DatabaseId),
+	User(Level, String),
+}
+
+/// The namespace, database, and user which a statement runs as
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct ScopesKey {
+	ns: Option<String>,
+	db: Option<String>,
+	level: Level,
+	id: String,
+}
+
+/// The quotas which apply to a statement, along with the time they were fetched
+type Scopes = (Instant, Arc<[(QuotaScope, QuotaDefinition)]>);
+
+/// Tracks the usage of the quotas shared between statements
+pub(crate) struct Quotas {
+	/// The quotas which apply to each namespace, database, and user
+	scopes: Cache<ScopesKey, Scopes>,
+	/// The number of statements running, for each concurrency quota
+	running: Mutex<HashMap<QuotaScope, u64>>,
+	/// The last measured storage size of each namespace and database with a
+	/// storage quota
+	storage: Mutex<HashMap<QuotaScope, u64>>,
+	/// The number of statements running with a quota
+	active: AtomicU64,
+	/// The number of statements which have exceeded a quota
+	rejected: AtomicU64,
+	/// The number of records scanned by statements with a quota
+	scanned: AtomicU64,
+}
+
+impl Default for Quotas {
+	fn default() -> Self {
+		Self {
+			scopes: Cache::new(QUOTA_DEFINITION_CACHE_SIZE.max(1)),
+			running: Mutex::default(),
+			storage: Mutex::default(),
+			active: AtomicU64::default(),
+			rejected: AtomicU64::default(),
+			scanned: AtomicU64::default(),
+		}
+	}
+}
+
+impl std::fmt::Debug for Quotas {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Quotas")
+			.field("running", &self.running)
+			.field("storage", &self.storage)
+			.field("active", &self.active)
+			.field("rejected", &self.rejected)
+			.field("scanned", &self.scanned)
+			.finish_non_exhaustive()
+	}
+}
+
+impl Quotas {
+	const ACTIVE: &str = "quota.active_statements";
+	const REJECTED: &str = "quota.rejected_statements";
+	const SCANNED: &str = "quota.scanned_records";
+
+	/// Starts a statement, returning the usage against which the statement is
+	/// checked while it runs, or `None` if no quota applies to the statement.
+	pub(crate) async fn start(
+		self: &Arc<Self>,
+		txn: &Transaction,
+		ns: Option<&str>,
+		db: Option<&str>,
+		auth: &Auth,
+		write: bool,
+	) -> Result<Option<QueryUsage>> {
+		let scopes = self.scopes(txn, ns, db, auth).await?;
+		if scopes.is_empty() {
+			return Ok(None);
+		}
+		// Check there is space left before anything is written
+		if write {
+			for (scope, quota) in scopes.iter() {
+				if let Some(limit) = quota.max_storage {
+					self.check_storage(scope, limit)?;
+				}
+			}
+		}
+		// Take a slot for every concurrency quota, releasing them on failure
+		let mut slots = Vec::new();
+		for (scope, quota) in scopes.iter() {
+			if let Some(limit) = quota.max_concurrent {
+				slots.push(self.acquire(scope, limit)?);
+			}
+		}
+		let quota = scopes.iter().fold(QuotaDefinition::default(), |acc, (_, q)| acc.merge(q));
+		self.active.fetch_add(1, Ordering::Relaxed);
+		Ok(Some(QueryUsage::new(quota, slots, self.clone())))
+	}
+
+	/// Fetches the quotas which apply to a statement, from the cache if they
+	/// were fetched recently
+	async fn scopes(
+		&self,
+		txn: &Transaction,
+		ns: Option<&str>,
+		db: Option<&str>,
+		auth: &Auth,
+	) -> Result<Arc<[(QuotaScope, QuotaDefinition)]>> {
+		let key = ScopesKey {
+			ns: ns.map(str::to_owned),
+			db: db.map(str::to_owned),
+			level: auth.level().clone(),
+			id: auth.id().to_owned(),
+		};
+		let interval = Duration::from_secs(*QUOTA_DEFINITION_CACHE_SECS);
+		if let Some((time, scopes)) = self.scopes.get(&key)
+			&& time.elapsed() < interval
+		{
+			return Ok(scopes);
+		}
+		let mut scopes = Vec::new();
+		if let Some(ns) = ns
+			&& let Some(def) = txn.get_ns_by_name(ns, None).await?
+		{
+			if let Some(quota) = &def.quota {
+				scopes.push((QuotaScope::Namespace(def.namespace_id), quota.clone()));
+			}
+			if let Some(db) = db
+				&& let Some(def) = txn.get_db_by_name(ns, db, None).await?
+				&& let Some(quota) = &def.quota
+			{
+				scopes
+					.push((QuotaScope::Database(def.namespace_id, def.database_id), quota.clone()));
+			}
+		}
+		if let Some(quota) = user_quota(txn, auth).await? {
+			scopes.push((QuotaScope::User(auth.level().clone(), auth.id().to_owned()), quota));
+		}
+		let scopes: Arc<[_]> = scopes.into();
+		self.scopes.insert(key, (Instant::now(), scopes.clone()));
+		Ok(scopes)
+	}
+
+	/// Clears the cached quotas, after a quota has been defined or removed
+	pub(crate) fn clear(&self) {
+		self.scopes.clear();
+	}
+
+	/// Records that a statement with a quota has finished
+	pub(crate) fn finished(&self) {
+		self.active.fetch_sub(1, Ordering::Relaxed);
+	}
+
+	/// Records that records have been scanned by a statement with a quota
+	pub(crate) fn scanned(&self, count: u64) {
+		self.scanned.fetch_add(count, Ordering::Relaxed);
+	}
+
+	/// Records that a statement has exceeded a quota, returning the error
+	pub(crate) fn exceeded(&self, name: &str, limit: u64) -> Error {
+		self.rejected.fetch_add(1, Ordering::Relaxed);
+		Error::QuotaExceeded {
+			name: name.to_owned(),
+			limit,
+		}
+	}
+
+	/// Takes a slot of a concurrency quota
+	fn acquire(self: &Arc<Self>, scope: &QuotaScope, limit: u64) -> Result<QuotaSlot> {
+		let mut running = self.running.lock();
+		let count = running.entry(scope.clone()).or_default();
+		if *count >= limit {
+			drop(running);
+			bail!(self.exceeded("max_concurrent", limit));
+		}
+		*count += 1;
+		Ok(QuotaSlot {
+			quotas: self.clone(),
+			scope: scope.clone(),
+		})
+	}
+
+	/// Releases a slot of a concurrency quota
+	fn release(&self, scope: &QuotaScope) {
+		let mut running = self.running.lock();
+		if let Some(count) = running.get_mut(scope) {
+			*count = count.saturating_sub(1);
+			if *count == 0 {
+				running.remove(scope);
+			}
+		}
+	}
+
+	/// Checks that a namespace or database is within its storage quota, as of
+	/// the last time its storage size was measured
+	fn check_storage(&self, scope: &QuotaScope, limit: u64) -> Result<()> {
+		// A scope which has not been measured yet is allowed to write
+		let size = match self.storage.lock().get(scope) {
+			Some(size) => *size,
+			None => return Ok(()),
+		};
+		if size >= limit {
+			bail!(self.exceeded("max_storage", limit));
+		}
+		Ok(())
+	}
+
+	/// Measures the storage size of every namespace and database which has a
+	/// storage quota, replacing the previous measurements.
+	///
+	/// Each namespace and database is measured in its own read transaction.
+	pub(crate) async fn refresh_storage(&self, ds: &Datastore) -> Result<()> {
+		let mut scopes = Vec::new();
+		let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let nss = catch!(txn, txn.all_ns(None).await);
+		for ns in nss.iter() {
+			if ns.quota.as_ref().is_some_and(|q| q.max_storage.is_some()) {
+				scopes.push(QuotaScope::Namespace(ns.namespace_id));
+			}
+			let dbs = catch!(txn, txn.all_db(ns.namespace_id, None).await);
+			for db in dbs.iter() {
+				if db.quota.as_ref().is_some_and(|q| q.max_storage.is_some()) {
+					scopes.push(QuotaScope::Database(db.namespace_id, db.database_id));
+				}
+			}
+		}
+		txn.cancel().await?;
+		let mut storage = HashMap::with_capacity(scopes.len());
+		for scope in scopes {
+			let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+			let size = catch!(txn, storage_size(&txn, &scope).await);
+			txn.cancel().await?;
+			storage.insert(scope, size);
+		}
+		*self.storage.lock() = storage;
+		Ok(())
+	}
+
+	/// The metrics describing the usage of quotas
+	pub(crate) fn metrics() -> Metrics {
+		Metrics {
+			name: "surrealdb.quota",
+			u64_metrics: vec![
+				Metric {
+					name: Self::ACTIVE,
+					description: "Returns the number of statements running with a quota.",
+				},
+				Metric {
+					name: Self::REJECTED,
+					description: "Returns the number of statements which have exceeded a quota.",
+				},
+				Metric {
+					name: Self::SCANNED,
+					description: "Returns the number of records scanned by statements with a quota.",
+				},
+			],
+		}
+	}
+
+	/// Collects a specific u64 metric by name
+	pub(crate) fn collect_u64_metric(&self, metric: &str) -> Option<u64> {
+		match metric {
+			Self::ACTIVE => Some(self.active.load(Ordering::Relaxed)),
+			Self::REJECTED => Some(self.rejected.load(Ordering::Relaxed)),
+			Self::SCANNED => Some(self.scanned.load(Ordering::Relaxed)),
+			_ => None,
+		}
+	}
+}
+
+/// A slot of a concurrency quota, released when dropped
+#[derive(Debug)]
+pub(crate) struct QuotaSlot {
+	quotas: Arc<Quotas>,
+	scope: QuotaScope,
+}
+
+impl Drop for QuotaSlot {
+	fn drop(&mut self) {
+		self.quotas.release(&self.scope);
+	}
+}
+
+/// Fetches the quota of the system user running a statement
+async fn user_quota(txn: &Transaction, auth: &Auth) -> Result<Option<QuotaDefinition>> {
+	let user = match auth.level() {
+		Level::Root => txn.get_root_user(auth.id(), None).await?,
+		Level::Namespace(ns) => match txn.get_ns_by_name(ns, None).await? {
+			Some(ns) => txn.get_ns_user(ns.namespace_id, auth.id(), None).await?,
+			None => None,
+		},
+		Level::Database(ns, db) => match txn.get_db_by_name(ns, db, None).await? {
+			Some(db) => txn.get_db_user(db.namespace_id, db.database_id, auth.id(), None).await?,
+			None => None,
+		},
+		Level::No | Level::Record(..) => None,
+	};
+	Ok(user.and_then(|user| user.quota.clone()))
+}
+
+/// Computes the number of bytes stored within a namespace or database
+async fn storage_size(txn: &Transaction, scope: &QuotaScope) -> Result<u64> {
+	let rng = match scope {
+		QuotaScope::Namespace(ns) => to_prefix_range(crate::key::namespace::all::new(*ns))?,
+		QuotaScope::Database(ns, db) => to_prefix_range(crate::key::database::all::new(*ns, *db))?,
+		QuotaScope::User(..) => return Ok(0),
+	};
+	let stream = txn.stream_keys_vals(rng, None, None, 0, ScanDirection::Forward, false);
+	futures::pin_mut!(stream);
+	let mut size = 0;
+	while let Some(batch) = stream.next().await {
+		for (key, val) in batch? {
+			size += (key.len() + val.len()) as u64;
+		}
+	}
+	Ok(size)
+}
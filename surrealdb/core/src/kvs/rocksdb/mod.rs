@@ -513,6 +513,11 @@ impl Transactable for Transaction {
 		self.write
 	}
 
+	/// Check if versioning is enabled
+	fn keeps_versions(&self) -> bool {
+		self.versioned
+	}
+
 	/// Cancel a transaction.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
 	async fn cancel(&self) -> Result<()> {
@@ -185,6 +185,11 @@ impl Transactable for Transaction {
 		self.write
 	}
 
+	/// Check if versioning is enabled
+	fn keeps_versions(&self) -> bool {
+		self.versioned
+	}
+
 	/// Cancels the transaction.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
 	async fn cancel(&self) -> Result<()> {
@@ -27,6 +27,12 @@ pub(crate) enum TaskLeaseType {
 	IndexCompaction,
 	/// Event processing
 	EventProcessing,
+	/// Removal of expired records from tables with a time-to-live
+	TtlSweep,
+	/// Running of scheduled jobs
+	JobScheduler,
+	/// Delivery of changefeeds to sinks
+	SinkDelivery,
 }
 
 /// Represents a distributed task lease stored in the datastore.
@@ -0,0 +1,242 @@
+use bytes::Bytes;
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::dbs::Session;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::kvs::backup;
+#[cfg(feature = "kv-mem")]
+use crate::kvs::{Datastore, backup::Config};
+#[cfg(feature = "kv-mem")]
+use crate::syn;
+
+pub async fn backup_and_restore(new_ds: impl CreateDs) {
+	// Create the source datastore
+	let node_id = Uuid::parse_str("6f2c1e4b-0a8d-4c3e-9b57-2d41f0a9e6c1").unwrap();
+	let (src, _) = new_ds.create_ds(node_id).await;
+	let tx = src.transaction(Write, Optimistic).await.unwrap();
+	for i in 0..2500u32 {
+		tx.set(&format!("/*test{i:05}").into_bytes(), &i.to_be_bytes().to_vec()).await.unwrap();
+	}
+	tx.set(&b"/$node".to_vec(), &b"local".to_vec()).await.unwrap();
+	tx.commit().await.unwrap();
+	// Take a backup of the source datastore
+	let (snd, rcv) = async_channel::unbounded();
+	let task = src.backup(&Session::owner(), Default::default(), snd).await.unwrap();
+	task.await.unwrap();
+	let mut chunks = Vec::new();
+	while let Ok(chunk) = rcv.try_recv() {
+		chunks.push(Ok(Bytes::from(chunk)));
+	}
+	// Create the target datastore, with some existing data
+	let node_id = Uuid::parse_str("b3e7a9d2-5f14-4e8b-a6c0-91d3f2e47b58").unwrap();
+	let (dst, _) = new_ds.create_ds(node_id).await;
+	let tx = dst.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&b"/*old".to_vec(), &b"old".to_vec()).await.unwrap();
+	tx.commit().await.unwrap();
+	// Restore the backup into the target datastore
+	dst.restore(&Session::owner(), futures::stream::iter(chunks)).await.unwrap();
+	// Check the target datastore contents
+	let tx = dst.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&b"/*test01234".to_vec(), None).await.unwrap();
+	assert_eq!(val.as_deref(), Some(1234u32.to_be_bytes().as_slice()));
+	let val = tx.get(&b"/*test02499".to_vec(), None).await.unwrap();
+	assert_eq!(val.as_deref(), Some(2499u32.to_be_bytes().as_slice()));
+	let val = tx.get(&b"/*old".to_vec(), None).await.unwrap();
+	assert!(val.is_none());
+	let val = tx.get(&b"/$node".to_vec(), None).await.unwrap();
+	assert!(val.is_none());
+	tx.cancel().await.unwrap();
+}
+
+pub async fn restore_invalid(new_ds: impl CreateDs) {
+	// Create a datastore with some existing data
+	let node_id = Uuid::parse_str("0d8e4f6a-7c21-4b93-8e5d-a4f9c3b21e07").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&b"/*test".to_vec(), &b"ok".to_vec()).await.unwrap();
+	tx.commit().await.unwrap();
+	// Attempt to restore data which is not a backup
+	let chunks = vec![Ok(Bytes::from_static(b"DEFINE TABLE person;"))];
+	ds.restore(&Session::owner(), futures::stream::iter(chunks)).await.unwrap_err();
+	// Check the existing data was left untouched
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&b"/*test".to_vec(), None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"ok")));
+	tx.cancel().await.unwrap();
+}
+
+pub async fn restore_truncated(new_ds: impl CreateDs) {
+	// Take a backup of a datastore
+	let node_id = Uuid::parse_str("5a1c9e37-2b84-4f60-8d1e-c7f3a2904b6d").unwrap();
+	let (src, _) = new_ds.create_ds(node_id).await;
+	let tx = src.transaction(Write, Optimistic).await.unwrap();
+	for i in 0..2500u32 {
+		tx.set(&format!("/*test{i:05}").into_bytes(), &i.to_be_bytes().to_vec()).await.unwrap();
+	}
+	tx.commit().await.unwrap();
+	let (snd, rcv) = async_channel::unbounded();
+	let task = src.backup(&Session::owner(), Default::default(), snd).await.unwrap();
+	task.await.unwrap();
+	let mut data = Vec::new();
+	while let Ok(chunk) = rcv.try_recv() {
+		data.extend(chunk);
+	}
+	// Create a datastore with some existing data
+	let node_id = Uuid::parse_str("e2b4d6f8-0a1c-4e3f-9b5d-7c8e9f0a1b2c").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&b"/*old".to_vec(), &b"old".to_vec()).await.unwrap();
+	tx.commit().await.unwrap();
+	// Attempt to restore a truncated backup, with a valid header
+	data.truncate(data.len() - 100);
+	let chunks = vec![Ok(Bytes::from(data))];
+	ds.restore(&Session::owner(), futures::stream::iter(chunks)).await.unwrap_err();
+	// Check the existing data was left untouched, and nothing was restored
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&b"/*old".to_vec(), None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"old")));
+	let val = tx.get(&b"/*test00000".to_vec(), None).await.unwrap();
+	assert!(val.is_none());
+	let keys = tx.keys(b"/!rs".to_vec()..b"/!rt".to_vec(), 10, 0, None).await.unwrap();
+	assert!(keys.is_empty());
+	tx.cancel().await.unwrap();
+}
+
+pub async fn restore_resume(new_ds: impl CreateDs) {
+	// Create a datastore with some existing data
+	let node_id = Uuid::parse_str("3e8b5c1d-9f27-4a60-b4d3-6c0e2a7f1b98").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	ds.check_version().await.unwrap();
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&b"/*old".to_vec(), &b"old".to_vec()).await.unwrap();
+	// Leave a restore which was interrupted before removing the existing data
+	tx.set(&backup::staged_key(b"/*new"), &b"new".to_vec()).await.unwrap();
+	tx.set(&backup::restore_marker(), &backup::RestoreStep::Clear.encode()).await.unwrap();
+	tx.commit().await.unwrap();
+	// The restore is finished when the datastore is next opened
+	ds.check_version().await.unwrap();
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&b"/*old".to_vec(), None).await.unwrap();
+	assert!(val.is_none());
+	let val = tx.get(&b"/*new".to_vec(), None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"new")));
+	tx.cancel().await.unwrap();
+	// Leave a restore which was interrupted while applying the staged entries
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&backup::staged_key(b"/*more"), &b"more".to_vec()).await.unwrap();
+	tx.set(&backup::restore_marker(), &backup::RestoreStep::Apply.encode()).await.unwrap();
+	tx.commit().await.unwrap();
+	// The entries which were already applied are kept
+	ds.check_version().await.unwrap();
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&b"/*new".to_vec(), None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"new")));
+	let val = tx.get(&b"/*more".to_vec(), None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"more")));
+	let val = tx.get(&backup::restore_marker(), None).await.unwrap();
+	assert!(val.is_none());
+	let keys = tx.keys(b"/!rs".to_vec()..b"/!rt".to_vec(), 10, 0, None).await.unwrap();
+	assert!(keys.is_empty());
+	tx.cancel().await.unwrap();
+}
+
+pub async fn restore_requires_owner(new_ds: impl CreateDs) {
+	let node_id = Uuid::parse_str("9c3f1a7e-4d2b-4b8a-a6e5-1f0d2c3b4a59").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	for sess in [Session::editor(), Session::viewer()] {
+		// Taking a snapshot is not allowed
+		let (snd, _rcv) = async_channel::unbounded();
+		assert!(ds.backup(&sess, Default::default(), snd).await.is_err());
+		// Restoring a snapshot is not allowed
+		let chunks: Vec<anyhow::Result<Bytes>> = Vec::new();
+		ds.restore(&sess, futures::stream::iter(chunks)).await.unwrap_err();
+	}
+}
+
+#[cfg(feature = "kv-mem")]
+#[tokio::test]
+#[serial_test::serial]
+async fn backup_versioned() {
+	// Create a versioned datastore, with a record which was updated
+	let src = Datastore::builder().build_with_path("mem://?versioned=true").await.unwrap();
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		CREATE person:tobie SET name = 'Tobie';
+		UPDATE person:tobie SET name = 'Tobie Morgan';
+	";
+	for res in src.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	// Historical versions can not be backed up
+	let (snd, _rcv) = async_channel::unbounded();
+	let task = src.backup(&Session::owner(), Config::default(), snd).await.unwrap();
+	task.await.unwrap_err();
+	// Back up only the latest version of each key
+	let (snd, rcv) = async_channel::unbounded();
+	let cfg = Config {
+		latest_only: true,
+	};
+	let task = src.backup(&Session::owner(), cfg, snd).await.unwrap();
+	task.await.unwrap();
+	let mut chunks = Vec::new();
+	while let Ok(chunk) = rcv.try_recv() {
+		chunks.push(Ok(Bytes::from(chunk)));
+	}
+	// Restore the backup into another versioned datastore
+	let dst = Datastore::builder().build_with_path("mem://?versioned=true").await.unwrap();
+	dst.restore(&Session::owner(), futures::stream::iter(chunks)).await.unwrap();
+	// The restored version can be read, and later versions are kept
+	let sql = "
+		SLEEP 10ms;
+		LET $restored = time::now();
+		SLEEP 10ms;
+		UPDATE person:tobie SET name = 'Jaime';
+		SELECT VALUE name FROM person VERSION $restored;
+		SELECT VALUE name FROM person;
+	";
+	let mut res = dst.execute(sql, &ses, None).await.unwrap();
+	for _ in 0..4 {
+		res.remove(0).result.unwrap();
+	}
+	let val = res.remove(0).result.unwrap();
+	assert_eq!(val, syn::value("['Tobie Morgan']").unwrap());
+	let val = res.remove(0).result.unwrap();
+	assert_eq!(val, syn::value("['Jaime']").unwrap());
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn backup_and_restore() {
+			super::backup::backup_and_restore($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn restore_invalid() {
+			super::backup::restore_invalid($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn restore_truncated() {
+			super::backup::restore_truncated($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn restore_resume() {
+			super::backup::restore_resume($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn restore_requires_owner() {
+			super::backup::restore_requires_owner($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
@@ -0,0 +1,97 @@
+//! Tests for the encryption of stored values
+
+use std::sync::Arc;
+
+use crate::kvs::Datastore;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+use crate::kvs::encryption::{EncryptionKey, Keyring, reencrypt};
+
+fn key(byte: u8) -> EncryptionKey {
+	EncryptionKey::new(&[byte; 32]).unwrap()
+}
+
+#[tokio::test]
+async fn values_are_encrypted() {
+	let ds = Datastore::builder()
+		.with_encryption(Keyring::new(key(1)))
+		.build_with_path("memory")
+		.await
+		.unwrap();
+	// Write a value through a transaction
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&b"/*test".to_vec(), &b"secret".to_vec()).await.unwrap();
+	tx.commit().await.unwrap();
+	// Check the value is encrypted in the storage engine
+	let raw = ds.transaction_factory().unencrypted(false).await.unwrap();
+	let val = raw.get(b"/*test".to_vec(), None).await.unwrap().unwrap();
+	assert!(!val.windows(6).any(|w| w == b"secret"));
+	raw.cancel().await.unwrap();
+	// Check the value is decrypted when read
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&b"/*test".to_vec(), None).await.unwrap();
+	assert_eq!(val.as_deref(), Some(b"secret".as_slice()));
+	let vals = tx.getr(b"/*".to_vec()..b"/*~".to_vec(), None).await.unwrap();
+	assert_eq!(vals, vec![(b"/*test".to_vec(), b"secret".to_vec())]);
+	tx.cancel().await.unwrap();
+	// Check conditional writes compare against the decrypted value
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.putc(&b"/*test".to_vec(), &b"other".to_vec(), Some(&b"wrong".to_vec())).await.unwrap_err();
+	tx.putc(&b"/*test".to_vec(), &b"other".to_vec(), Some(&b"secret".to_vec())).await.unwrap();
+	tx.commit().await.unwrap();
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&b"/*test".to_vec(), None).await.unwrap();
+	assert_eq!(val.as_deref(), Some(b"other".as_slice()));
+	tx.cancel().await.unwrap();
+}
+
+#[tokio::test]
+async fn reencrypt_rotated_key() {
+	let ds = Datastore::builder()
+		.with_encryption(Keyring::new(key(1)))
+		.build_with_path("memory")
+		.await
+		.unwrap();
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	for i in 0..1200u32 {
+		tx.set(&format!("/*test{i:05}").into_bytes(), &i.to_be_bytes().to_vec()).await.unwrap();
+	}
+	tx.commit().await.unwrap();
+	// Rotate the key, and re-encrypt the datastore
+	let keyring = Keyring::new(key(2)).with_previous(key(1));
+	let tf = ds.transaction_factory().clone().with_encryption(Some(Arc::new(keyring)));
+	let report = reencrypt(&tf).await.unwrap();
+	assert!(report.scanned >= 1200);
+	assert_eq!(report.reencrypted, report.scanned);
+	// Running again leaves the values untouched
+	let report = reencrypt(&tf).await.unwrap();
+	assert_eq!(report.reencrypted, 0);
+	// Check the values can be read with only the new key
+	let keyring = Keyring::new(key(2));
+	let raw = tf.unencrypted(false).await.unwrap();
+	let val = raw.get(b"/*test00042".to_vec(), None).await.unwrap().unwrap();
+	assert_eq!(keyring.decrypt(b"/*test00042", val).unwrap(), 42u32.to_be_bytes());
+	raw.cancel().await.unwrap();
+}
+
+#[tokio::test]
+async fn encrypt_plaintext_datastore() {
+	let ds = Datastore::builder().build_with_path("memory").await.unwrap();
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&b"/*test".to_vec(), &b"secret".to_vec()).await.unwrap();
+	tx.commit().await.unwrap();
+	// Plaintext values can not be read without allowing them
+	let tf = ds.transaction_factory().clone();
+	let strict = tf.clone().with_encryption(Some(Arc::new(Keyring::new(key(1)))));
+	reencrypt(&strict).await.unwrap_err();
+	// Encrypt the existing values
+	let keyring = Keyring::new(key(1)).with_plaintext(true);
+	let tf = tf.with_encryption(Some(Arc::new(keyring)));
+	let report = reencrypt(&tf).await.unwrap();
+	assert_eq!(report.reencrypted, 1);
+	let keyring = Keyring::new(key(1));
+	let raw = tf.unencrypted(false).await.unwrap();
+	let val = raw.get(b"/*test".to_vec(), None).await.unwrap().unwrap();
+	assert_eq!(keyring.decrypt(b"/*test", val).unwrap(), b"secret");
+	raw.cancel().await.unwrap();
+}
@@ -0,0 +1,62 @@
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::catalog::providers::DatabaseProvider;
+use crate::dbs::Session;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::kvs::fix::ProblemKind;
+use crate::val::{RecordIdKey, TableName};
+
+pub async fn fix_and_repair(new_ds: impl CreateDs) {
+	// Create a datastore with an index and a graph edge
+	let node_id = Uuid::parse_str("3a9f5c21-8e47-4b6d-a0c3-5d12e8f47b90").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE TABLE person;
+		DEFINE INDEX name ON person FIELDS name;
+		CREATE person:1 SET name = 'Tobie';
+		CREATE person:2 SET name = 'Jaime';
+		RELATE person:1->knows->person:2;
+	";
+	for res in ds.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	// A consistent datastore has no problems
+	let report = ds.fix(false).await.unwrap();
+	assert!(report.problems.is_empty(), "{report}");
+	assert!(report.checked > 0);
+	// Remove a record without its index entries and edges
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	let db = tx.expect_db_by_name("test", "test").await.unwrap();
+	let tb = TableName::from("person".to_string());
+	let key =
+		crate::key::record::new(db.namespace_id, db.database_id, &tb, &RecordIdKey::Number(2));
+	tx.del(&key).await.unwrap();
+	tx.commit().await.unwrap();
+	// The inconsistencies are reported, but left in place
+	let report = ds.fix(false).await.unwrap();
+	assert!(!report.repaired);
+	assert!(report.problems.iter().any(|p| p.kind == ProblemKind::OrphanedIndexEntry), "{report}");
+	assert!(report.problems.iter().any(|p| p.kind == ProblemKind::DanglingEdge), "{report}");
+	let found = report.problems.len();
+	assert_eq!(ds.fix(false).await.unwrap().problems.len(), found);
+	// The inconsistencies are removed when repairing
+	let report = ds.fix(true).await.unwrap();
+	assert!(report.repaired);
+	assert_eq!(report.problems.len(), found);
+	let report = ds.fix(false).await.unwrap();
+	assert!(report.problems.is_empty(), "{report}");
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn fix_and_repair() {
+			super::fix::fix_and_repair($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::catalog::JobState;
+use crate::catalog::providers::DatabaseProvider;
+use crate::dbs::Session;
+use crate::kvs::{Datastore, LockType, TransactionType};
+use crate::syn;
+use crate::val::{Datetime, Value};
+
+/// Makes a job due now, rather than waiting for its schedule
+async fn make_due(ds: &Datastore, name: &str) {
+	let txn = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+	let db = txn.get_db_by_name("test", "test", None).await.unwrap().unwrap();
+	let key = crate::key::database::js::new(db.namespace_id, db.database_id, name);
+	let state: JobState = txn.get(&key, None).await.unwrap().unwrap();
+	let state = JobState {
+		next: Some(Datetime(Datetime::now().0 - chrono::Duration::seconds(1))),
+		..state
+	};
+	txn.set(&key, &state).await.unwrap();
+	txn.commit().await.unwrap();
+}
+
+pub async fn run_jobs(new_ds: impl CreateDs) {
+	// Create a datastore with a scheduled job
+	let node_id = Uuid::parse_str("2f6a8d3e-91c4-4b7a-8e05-c3d1a94f6b27").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE JOB tick SCHEDULE '* * * * *' THEN { CREATE tick };
+		DEFINE JOB fail SCHEDULE '@daily' THEN { THROW 'failed' };
+	";
+	for res in ds.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	// Neither job is due yet
+	let run = ds.run_jobs(Duration::from_secs(1)).await.unwrap();
+	assert_eq!(run, 0);
+	// Both jobs run once they are due, even when one fails
+	make_due(&ds, "tick").await;
+	make_due(&ds, "fail").await;
+	let run = ds.run_jobs(Duration::from_secs(1)).await.unwrap();
+	assert_eq!(run, 2);
+	let run = ds.run_jobs(Duration::from_secs(1)).await.unwrap();
+	assert_eq!(run, 0);
+	let sql = "
+		count(SELECT * FROM tick);
+		(INFO FOR DB STRUCTURE).jobs.map(|$j| [$j.name, $j.runs.len(), $j.runs[0].error != NONE, $j.next > time::now()]);
+	";
+	let mut res = ds.execute(sql, &ses, None).await.unwrap();
+	let count = res.remove(0).result.unwrap();
+	assert_eq!(count, Value::from(1));
+	let jobs = res.remove(0).result.unwrap();
+	assert_eq!(jobs, syn::value("[['fail', 1, true, true], ['tick', 1, false, true]]").unwrap());
+	// A paused job is not run, even when it is due
+	let sql = "ALTER JOB tick PAUSE";
+	for res in ds.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	make_due(&ds, "tick").await;
+	let run = ds.run_jobs(Duration::from_secs(1)).await.unwrap();
+	assert_eq!(run, 0);
+	// Removing a job removes its history
+	let sql = "
+		REMOVE JOB tick;
+		(INFO FOR DB STRUCTURE).jobs.map(|$j| $j.name);
+	";
+	let mut res = ds.execute(sql, &ses, None).await.unwrap();
+	res.remove(0).result.unwrap();
+	let jobs = res.remove(0).result.unwrap();
+	assert_eq!(jobs, syn::value("['fail']").unwrap());
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn run_jobs() {
+			super::job::run_jobs($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
@@ -20,8 +20,22 @@ macro_rules! include_tests {
 	};
 }
 
+#[cfg(any(feature = "kv-mem", feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod backup;
+#[cfg(feature = "kv-mem")]
+mod encryption;
+#[cfg(any(feature = "kv-mem", feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod fix;
+#[cfg(any(feature = "kv-mem", feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod job;
 #[cfg(feature = "kv-rocksdb")]
 mod metrics;
+#[cfg(any(feature = "kv-mem", feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod quota;
+#[cfg(any(feature = "kv-mem", feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod sink;
+#[cfg(any(feature = "kv-mem", feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod ttl;
 
 mod multireader;
 mod multiwriter_different_keys;
@@ -30,6 +44,7 @@ mod multiwriter_same_keys_conflict;
 mod raw;
 #[cfg(feature = "kv-rocksdb")]
 mod read_and_deletion_only;
+mod savepoint;
 mod snapshot;
 #[cfg(feature = "kv-mem")]
 mod tx_cache_test;
@@ -83,7 +98,14 @@ mod mem {
 
 	include_tests!(new_ds =>
 		raw,
+		savepoint,
 		snapshot,
+		backup,
+		fix,
+		job,
+		quota,
+		sink,
+		ttl,
 		multireader,
 		multiwriter_different_keys,
 		multiwriter_same_keys_conflict,
@@ -115,7 +137,14 @@ mod rocksdb {
 
 	include_tests!(new_ds =>
 		raw,
+		savepoint,
 		snapshot,
+		backup,
+		fix,
+		job,
+		quota,
+		sink,
+		ttl,
 		multireader,
 		multiwriter_different_keys,
 		multiwriter_same_keys_conflict,
@@ -149,7 +178,14 @@ mod surrealkv {
 
 	include_tests!(new_ds =>
 		raw,
+		savepoint,
 		snapshot,
+		backup,
+		fix,
+		job,
+		quota,
+		sink,
+		ttl,
 		multireader,
 		multiwriter_different_keys,
 		multiwriter_same_keys_conflict,
@@ -183,6 +219,7 @@ mod tikv {
 
 	include_tests!(new_ds =>
 		raw,
+		savepoint,
 		snapshot,
 		multireader,
 		multiwriter_different_keys,
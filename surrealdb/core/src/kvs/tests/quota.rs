@@ -0,0 +1,47 @@
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::dbs::Session;
+use crate::syn;
+
+pub async fn storage_quota(new_ds: impl CreateDs) {
+	// Create a datastore with a database which has a storage quota
+	let node_id = Uuid::parse_str("b83f1c52-6e0d-4a97-9d24-5f7a0c3e8b16").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let ns = Session::owner().with_ns("test");
+	let ses = Session::owner().with_ns("test").with_db("limited");
+	let sql = "DEFINE DATABASE limited QUOTA { max_storage: 1 }";
+	for res in ds.execute(sql, &ns, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	// Writes are allowed until the storage size has been measured
+	let mut res = ds.execute("CREATE person:1", &ses, None).await.unwrap();
+	res.remove(0).result.unwrap();
+	ds.refresh_quota_storage().await.unwrap();
+	// Writes are denied once the database is over its quota
+	let mut res = ds.execute("CREATE person:2", &ses, None).await.unwrap();
+	let err = res.remove(0).result.unwrap_err().to_string();
+	assert!(err.contains("max_storage quota of 1"), "{err}");
+	// Reads are still allowed
+	let mut res = ds.execute("SELECT VALUE id FROM person", &ses, None).await.unwrap();
+	let ids = res.remove(0).result.unwrap();
+	assert_eq!(ids, syn::value("[person:1]").unwrap());
+	// A redefined quota applies immediately
+	let sql = "DEFINE DATABASE OVERWRITE limited QUOTA { max_storage: 1000000 }";
+	for res in ds.execute(sql, &ns, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	let mut res = ds.execute("CREATE person:2", &ses, None).await.unwrap();
+	res.remove(0).result.unwrap();
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn storage_quota() {
+			super::quota::storage_quota($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
@@ -0,0 +1,98 @@
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+
+pub async fn named_save_points(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("7e2a4c91-3b5d-4f08-a6c2-d19e8b7f0a34").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"test1", &"1".as_bytes().to_vec()).await.unwrap();
+	// Set a named save point
+	tx.set_named_save_point("outer").await.unwrap();
+	tx.set(&"test2", &"2".as_bytes().to_vec()).await.unwrap();
+	// Release an unnamed save point within the named save point
+	tx.new_save_point().await.unwrap();
+	tx.set(&"test3", &"3".as_bytes().to_vec()).await.unwrap();
+	tx.release_last_save_point().await.unwrap();
+	// Release a nested named save point
+	tx.set_named_save_point("inner").await.unwrap();
+	tx.set(&"test4", &"4".as_bytes().to_vec()).await.unwrap();
+	tx.release_named_save_point("inner").await.unwrap();
+	assert!(tx.has_named_save_point().await);
+	// Rolling back also reverts the changes within the released save points
+	tx.rollback_to_named_save_point("outer").await.unwrap();
+	assert!(tx.exists(&"test1", None).await.unwrap());
+	assert!(!tx.exists(&"test2", None).await.unwrap());
+	assert!(!tx.exists(&"test3", None).await.unwrap());
+	assert!(!tx.exists(&"test4", None).await.unwrap());
+	// Releasing the outermost named save point releases every save point
+	tx.release_named_save_point("outer").await.unwrap();
+	assert!(!tx.has_named_save_point().await);
+	tx.rollback_to_named_save_point("outer").await.unwrap_err();
+	// Unnamed save points are rolled back on their own again
+	tx.set(&"test5", &"5".as_bytes().to_vec()).await.unwrap();
+	tx.new_save_point().await.unwrap();
+	tx.set(&"test6", &"6".as_bytes().to_vec()).await.unwrap();
+	tx.rollback_to_save_point().await.unwrap();
+	assert!(tx.exists(&"test5", None).await.unwrap());
+	assert!(!tx.exists(&"test6", None).await.unwrap());
+	tx.cancel().await.unwrap();
+}
+
+pub async fn many_save_points(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("3c8f1e52-9a7d-4b26-8e04-5f6a1d2c9b73").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"before", &"0".as_bytes().to_vec()).await.unwrap();
+	// Set a named save point before importing many records
+	tx.set_named_save_point("import").await.unwrap();
+	for i in 0..1000 {
+		// Each record is written within its own save point
+		tx.new_save_point().await.unwrap();
+		tx.set(&format!("record{i}").into_bytes(), &i.to_string().into_bytes()).await.unwrap();
+		tx.release_last_save_point().await.unwrap();
+	}
+	// A save point still reverts the save points released within it
+	tx.new_save_point().await.unwrap();
+	tx.set(&"outer", &"1".as_bytes().to_vec()).await.unwrap();
+	tx.new_save_point().await.unwrap();
+	tx.set(&"inner", &"2".as_bytes().to_vec()).await.unwrap();
+	tx.release_last_save_point().await.unwrap();
+	tx.rollback_to_save_point().await.unwrap();
+	assert!(!tx.exists(&"outer", None).await.unwrap());
+	assert!(!tx.exists(&"inner", None).await.unwrap());
+	assert!(tx.exists(&"record999", None).await.unwrap());
+	// Rolling back reverts every record written since the named save point
+	tx.rollback_to_named_save_point("import").await.unwrap();
+	assert!(tx.exists(&"before", None).await.unwrap());
+	for i in 0..1000 {
+		assert!(!tx.exists(&format!("record{i}").into_bytes(), None).await.unwrap());
+	}
+	// The named save point can be used again after rolling back to it
+	tx.set(&"after", &"3".as_bytes().to_vec()).await.unwrap();
+	tx.release_named_save_point("import").await.unwrap();
+	assert!(!tx.has_named_save_point().await);
+	assert!(tx.exists(&"after", None).await.unwrap());
+	tx.cancel().await.unwrap();
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn named_save_points() {
+			super::savepoint::named_save_points($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn many_save_points() {
+			super::savepoint::many_save_points($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
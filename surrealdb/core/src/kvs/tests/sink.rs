@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::dbs::Session;
+use crate::syn;
+
+pub async fn file_sink_requires_allowlist(new_ds: impl CreateDs) {
+	// Create a datastore without a file allowlist
+	let node_id = Uuid::parse_str("5b1e0c7a-3d42-4f86-9a1c-7e2b64d0f813").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		DEFINE SINK audit ON person TO FILE 'person.ndjson';
+	";
+	let mut res = ds.execute(sql, &ses, None).await.unwrap();
+	res.remove(0).result.unwrap();
+	// File sinks are denied unless an allowlist is configured
+	let err = res.remove(0).result.unwrap_err();
+	assert!(err.to_string().contains("File access denied"), "{err}");
+	// Nothing is delivered
+	let count = ds.run_sinks(Duration::from_secs(1)).await.unwrap();
+	assert_eq!(count, 0);
+}
+
+pub async fn deliver_to_stream(new_ds: impl CreateDs) {
+	// Create a datastore with a stream sink
+	let node_id = Uuid::parse_str("0d6c2e58-8f1b-4a37-b9e4-51a7c3f2d806").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h INCLUDE ORIGINAL;
+		DEFINE TABLE other CHANGEFEED 1h;
+		DEFINE SINK audit ON person TO STREAM;
+	";
+	for res in ds.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	// Nothing is delivered before any changes are made
+	let batch = ds.next_sink_batch(&ses, "audit", None).await.unwrap();
+	assert!(batch.is_none_or(|(_, out)| out.is_empty()));
+	// Only the changes to the sink table are delivered
+	let sql = "
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE other:one;
+		UPDATE person:tobie SET name = 'Tobias';
+		DELETE person:tobie;
+	";
+	for res in ds.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	let (vs, out) = ds.next_sink_batch(&ses, "audit", None).await.unwrap().unwrap();
+	// The changes are written as Debezium change events
+	let events: Vec<serde_json::Value> =
+		out.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+	assert_eq!(events.len(), 3);
+	assert_eq!(events[0]["op"], "c");
+	assert_eq!(events[0]["after"]["name"], "Tobie");
+	assert_eq!(events[1]["op"], "u");
+	assert_eq!(events[1]["before"]["name"], "Tobie");
+	assert_eq!(events[1]["after"]["name"], "Tobias");
+	assert_eq!(events[2]["op"], "d");
+	assert_eq!(events[2]["before"]["name"], "Tobias");
+	assert_eq!(events[2]["source"]["table"], "person");
+	// Acknowledged changes are not delivered again
+	ds.ack_sink_batch(&ses, "audit", vs).await.unwrap();
+	assert!(ds.next_sink_batch(&ses, "audit", None).await.unwrap().is_none());
+	// The delivery state is recorded against the sink
+	let sql = "(INFO FOR DB STRUCTURE).sinks.map(|$s| [$s.name, $s.versionstamp > 0, $s.attempts, $s.delivered != NONE])";
+	let mut res = ds.execute(sql, &ses, None).await.unwrap();
+	let sinks = res.remove(0).result.unwrap();
+	assert_eq!(sinks, syn::value("[['audit', true, 0, true]]").unwrap());
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn file_sink_requires_allowlist() {
+			super::sink::file_sink_requires_allowlist($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn deliver_to_stream() {
+			super::sink::deliver_to_stream($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
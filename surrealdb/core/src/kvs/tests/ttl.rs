@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::dbs::Session;
+use crate::syn;
+
+pub async fn ttl_sweep(new_ds: impl CreateDs) {
+	// Create a datastore with tables which have a time-to-live
+	let node_id = Uuid::parse_str("7c2e94a1-3b5d-4f08-9e6a-1d8b4c7f2a53").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE TABLE otp TTL expires;
+		DEFINE TABLE session TTL 1h;
+		DEFINE TABLE log;
+		DEFINE EVENT expired ON otp WHEN $event = 'DELETE' THEN (CREATE log SET otp = $before.id);
+		CREATE otp:1 SET expires = time::now() - 1m;
+		CREATE otp:2 SET expires = time::now() + 1h;
+		CREATE otp:3 SET code = 1234;
+		CREATE session:1;
+	";
+	for res in ds.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	// Only the expired record is removed
+	let removed = ds.ttl_sweep(Duration::from_secs(1)).await.unwrap();
+	assert_eq!(removed, 1);
+	let sql = "
+		SELECT VALUE id FROM otp ORDER BY id;
+		SELECT VALUE id FROM session;
+		SELECT VALUE otp FROM log;
+	";
+	let mut res = ds.execute(sql, &ses, None).await.unwrap();
+	let otp = res.remove(0).result.unwrap();
+	assert_eq!(otp, syn::value("[otp:2, otp:3]").unwrap());
+	let session = res.remove(0).result.unwrap();
+	assert_eq!(session, syn::value("[session:1]").unwrap());
+	// The delete fired the table event
+	let log = res.remove(0).result.unwrap();
+	assert_eq!(log, syn::value("[otp:1]").unwrap());
+	// Nothing else has expired
+	let removed = ds.ttl_sweep(Duration::from_secs(1)).await.unwrap();
+	assert_eq!(removed, 0);
+	// Updating the expiry time moves the record in the expiry index, and a
+	// time-to-live added to a table indexes its existing records
+	let sql = "
+		UPDATE otp:2 SET expires = time::now() - 1m;
+		CREATE note:1 SET until = time::now() - 1m;
+		CREATE note:2 SET until = time::now() + 1h;
+		DEFINE TABLE OVERWRITE note TTL until;
+	";
+	for res in ds.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	let removed = ds.ttl_sweep(Duration::from_secs(1)).await.unwrap();
+	assert_eq!(removed, 2);
+	let sql = "
+		SELECT VALUE id FROM otp ORDER BY id;
+		SELECT VALUE id FROM note;
+	";
+	let mut res = ds.execute(sql, &ses, None).await.unwrap();
+	let otp = res.remove(0).result.unwrap();
+	assert_eq!(otp, syn::value("[otp:3]").unwrap());
+	let note = res.remove(0).result.unwrap();
+	assert_eq!(note, syn::value("[note:2]").unwrap());
+	// Expired records are deleted with the capabilities of their database
+	let sql = "DEFINE DATABASE restricted CAPABILITIES { deny_funcs: ['string::uppercase'] }";
+	for res in ds.execute(sql, &Session::owner().with_ns("test"), None).await.unwrap() {
+		res.result.unwrap();
+	}
+	let ses = Session::owner().with_ns("test").with_db("restricted");
+	let sql = "
+		DEFINE TABLE token TTL expires;
+		DEFINE EVENT expired ON token WHEN $event = 'DELETE' THEN (
+			CREATE log SET code = string::uppercase($before.code)
+		);
+		CREATE token:1 SET code = 'a', expires = time::now() - 1m;
+	";
+	for res in ds.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	// The denied function fails the delete, so the record is kept
+	let removed = ds.ttl_sweep(Duration::from_secs(1)).await.unwrap();
+	assert_eq!(removed, 0);
+	let mut res = ds.execute("SELECT VALUE id FROM token", &ses, None).await.unwrap();
+	let token = res.remove(0).result.unwrap();
+	assert_eq!(token, syn::value("[token:1]").unwrap());
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn ttl_sweep() {
+			super::ttl::ttl_sweep($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
@@ -26,6 +26,8 @@ async fn setup_tx_with_ns_db() -> (Datastore, crate::kvs::Transaction, Namespace
 		namespace_id: NamespaceId(1),
 		name: "test".to_string(),
 		comment: None,
+		capabilities: None,
+		quota: None,
 	};
 	tx.put_ns(ns_def).await.unwrap();
 
@@ -36,6 +38,8 @@ async fn setup_tx_with_ns_db() -> (Datastore, crate::kvs::Transaction, Namespace
 		strict: false,
 		comment: None,
 		changefeed: None,
+		capabilities: None,
+		quota: None,
 	};
 	tx.put_db("test", db_def).await.unwrap();
 
@@ -252,6 +256,8 @@ async fn test_single_tx_cache_invalidation_on_ns_put() {
 		namespace_id: NamespaceId(1),
 		name: "test".to_string(),
 		comment: None,
+		capabilities: None,
+		quota: None,
 	};
 	tx.put_ns(ns_def).await.unwrap();
 
@@ -280,6 +286,8 @@ async fn test_single_tx_cache_invalidation_on_db_put_and_del() {
 		namespace_id: NamespaceId(1),
 		name: "test".to_string(),
 		comment: None,
+		capabilities: None,
+		quota: None,
 	};
 	tx.put_ns(ns_def).await.unwrap();
 
@@ -295,6 +303,8 @@ async fn test_single_tx_cache_invalidation_on_db_put_and_del() {
 		strict: false,
 		comment: None,
 		changefeed: None,
+		capabilities: None,
+		quota: None,
 	};
 	tx.put_db("test", db_def).await.unwrap();
 
@@ -709,11 +709,26 @@ impl Transactable for Transaction {
 		// Load the inner transaction
 		let mut inner = self.inner.write().await;
 		// Release the last savepoint
-		inner.savepoints.pop();
+		if let Some(savepoint) = inner.savepoints.pop() {
+			if inner.savepoints.is_empty() {
+				// No savepoint remains to roll back to
+				inner.operations.clear();
+			} else {
+				// Keep the operations since the savepoint, so that rolling
+				// back to the enclosing savepoint also reverts them
+				let operations = std::mem::take(&mut inner.operations);
+				inner.operations = savepoint.operations;
+				inner.operations.extend(operations);
+			}
+		}
 		// Continue
 		Ok(())
 	}
 
+	fn releases_save_points(&self) -> bool {
+		true
+	}
+
 	/// Rollback to the last save point.
 	async fn rollback_to_save_point(&self) -> Result<()> {
 		// Check to see if transaction is closed
@@ -0,0 +1,223 @@
+//! Removal of expired records from tables with a time-to-live.
+//!
+//! The records of every table defined with a `TTL` are indexed by the time at
+//! which they expire (see [`crate::key::table::ex`]), so that the sweep only
+//! range-scans the entries which have already expired. Expired records are
+//! removed in batches with a `DELETE` statement, in the same transaction as
+//! the batch was read in, so that events, changefeeds and live query
+//! notifications are processed exactly as for a normal delete, and a record
+//! written concurrently with the sweep causes a conflict rather than being
+//! removed.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_channel::Sender;
+use reblessive::tree::TreeStack;
+use tracing::warn;
+
+use super::tasklease::LeaseHandler;
+use super::{Datastore, KVValue, LockType, Transaction, TransactionType};
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{
+	DatabaseDefinition, DatabaseId, NamespaceDefinition, NamespaceId, Record, TableDefinition,
+};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::dbs::Session;
+use crate::doc::DefaultBroker;
+use crate::expr::statements::DeleteStatement;
+use crate::expr::{Expr, Output, Param, Ttl};
+use crate::key::table::ex;
+use crate::types::PublicNotification;
+use crate::val::{Array, Datetime, RecordId, TableName, Value};
+
+/// The parameter holding the expired records of a batch
+const EXPIRED: &str = "expired";
+
+/// Removes the expired records of every table with a time-to-live.
+///
+/// Returns the number of records which were removed. A table which fails to
+/// be swept is logged and skipped, so that it does not hold back the others.
+pub(super) async fn sweep(ds: &Datastore, lh: &LeaseHandler) -> Result<usize> {
+	let mut count = 0;
+	let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	let nss = catch!(txn, txn.all_ns(None).await);
+	txn.cancel().await?;
+	for ns in nss.iter() {
+		let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let dbs = catch!(txn, txn.all_db(ns.namespace_id, None).await);
+		txn.cancel().await?;
+		for db in dbs.iter() {
+			let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+			let tbs = catch!(txn, txn.all_tb(ns.namespace_id, db.database_id, None).await);
+			txn.cancel().await?;
+			for tb in tbs.iter().filter(|tb| tb.ttl.is_some() && !tb.drop) {
+				match sweep_table(ds, lh, ns, db, tb).await {
+					Ok(n) => count += n,
+					Err(e) => {
+						warn!(
+							"Failed to remove expired records from {}/{}/{}: {e}",
+							ns.name, db.name, tb.name
+						)
+					}
+				}
+			}
+		}
+	}
+	Ok(count)
+}
+
+/// Removes the expired records of a single table, one batch per transaction.
+async fn sweep_table(
+	ds: &Datastore,
+	lh: &LeaseHandler,
+	ns: &NamespaceDefinition,
+	db: &DatabaseDefinition,
+	tb: &TableDefinition,
+) -> Result<usize> {
+	let (nsid, dbid) = (ns.namespace_id, db.database_id);
+	let mut count = 0;
+	loop {
+		lh.try_maintain_lease().await?;
+		let txn = Arc::new(ds.transaction(TransactionType::Write, LockType::Optimistic).await?);
+		// Fetch the next batch of entries which expired before now. The
+		// entries of a batch are removed with it, so each batch starts
+		// from the beginning of the index.
+		let beg = catch!(txn, ex::prefix(nsid, dbid, &tb.name));
+		let end = catch!(txn, ex::suffix(nsid, dbid, &tb.name, &Datetime::now()));
+		let keys = catch!(txn, txn.keys(beg..end, *NORMAL_FETCH_SIZE, 0, None).await);
+		if keys.is_empty() {
+			txn.cancel().await?;
+			break;
+		}
+		// Collect the expired records of this batch
+		let mut expired = Vec::with_capacity(keys.len());
+		for k in keys {
+			let key = catch!(txn, ex::Ex::decode_key(&k));
+			expired.push(Value::RecordId(RecordId {
+				table: key.tb.into_owned(),
+				key: key.id,
+			}));
+			// Remove the entry even if its record no longer exists
+			catch!(txn, txn.del(&k).await);
+		}
+		count += expired.len();
+		// Delete the expired records
+		let (send, recv) = async_channel::unbounded();
+		let ctx = catch!(txn, delete(ds, txn.clone(), ns, db, expired, send).await);
+		txn.commit().await?;
+		// Send the live query notifications once committed
+		if let Some(sink) = ctx {
+			while let Ok(notification) = recv.try_recv() {
+				if sink.send(notification).await.is_err() {
+					break;
+				}
+			}
+		}
+		yield_now!();
+	}
+	Ok(count)
+}
+
+/// Returns the time at which a record of a table with the given time-to-live
+/// expires, if it expires at all
+fn expiry(ttl: &Ttl, record: &Record) -> Option<Datetime> {
+	match ttl {
+		Ttl::Duration(_) => record.expires().cloned(),
+		Ttl::Field(field) => match record.data.pick(field) {
+			Value::Datetime(v) => Some(v),
+			_ => None,
+		},
+	}
+}
+
+/// Updates the expiry index entry of a record which was written or deleted.
+///
+/// The `after` record is `None` when the record was deleted.
+pub(crate) async fn index_record(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	rid: &RecordId,
+	ttl: &Ttl,
+	before: &Record,
+	after: Option<&Record>,
+) -> Result<()> {
+	let before = expiry(ttl, before);
+	let after = after.and_then(|r| expiry(ttl, r));
+	if before == after {
+		return Ok(());
+	}
+	if let Some(at) = before {
+		txn.del(&ex::new(ns, db, &rid.table, &at, &rid.key)).await?;
+	}
+	if let Some(at) = after {
+		txn.set(&ex::new(ns, db, &rid.table, &at, &rid.key), &()).await?;
+	}
+	Ok(())
+}
+
+/// Rebuilds the expiry index of a table whose time-to-live was changed.
+///
+/// The existing entries are removed, and every record which expires under
+/// the new time-to-live is indexed again.
+pub(crate) async fn reindex_table(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	ttl: Option<&Ttl>,
+) -> Result<()> {
+	txn.delp(&ex::prefix(ns, db, tb)?).await?;
+	let Some(ttl) = ttl else {
+		return Ok(());
+	};
+	let beg = crate::key::record::prefix(ns, db, tb)?;
+	let end = crate::key::record::suffix(ns, db, tb)?;
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		let res = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+		next = res.next;
+		for (k, v) in res.result {
+			let record: Record = KVValue::kv_decode_value(v)?;
+			if let Some(at) = expiry(ttl, &record) {
+				let key = crate::key::record::RecordKey::decode_key(&k)?;
+				txn.set(&ex::new(ns, db, tb, &at, &key.id), &()).await?;
+			}
+		}
+		yield_now!();
+	}
+	Ok(())
+}
+
+/// Runs a `DELETE` statement for the expired records within the transaction.
+///
+/// Returns the channel the buffered live query notifications are sent to.
+async fn delete(
+	ds: &Datastore,
+	txn: Arc<Transaction>,
+	ns: &NamespaceDefinition,
+	db: &DatabaseDefinition,
+	expired: Vec<Value>,
+	broker: Sender<PublicNotification>,
+) -> Result<Option<Sender<PublicNotification>>> {
+	let sess = Session::owner().with_ns(&ns.name).with_db(&db.name);
+	let mut opt = ds.setup_options(&sess);
+	opt.broker = Some(DefaultBroker::new(broker));
+	// Delete with the capabilities of the database
+	let mut ctx = ds.setup_ctx()?;
+	let base = ctx.get_capabilities();
+	ctx.restrict_capabilities(&txn, &base, Some(&ns.name), Some(&db.name)).await?;
+	ctx.set_transaction(txn);
+	ctx.attach_session(&sess)?;
+	ctx.add_value(EXPIRED, Arc::new(Value::Array(Array(expired))));
+	let ctx = ctx.freeze();
+	let stm = DeleteStatement {
+		what: vec![Expr::Param(Param::new(EXPIRED.to_string()))],
+		output: Some(Output::None),
+		..Default::default()
+	};
+	let mut stack = TreeStack::new();
+	stack.enter(|stk| stm.compute(stk, &ctx, &opt, None)).finish().await?;
+	Ok(ctx.notifications())
+}
@@ -69,6 +69,33 @@ pub struct Transaction {
 	/// Per index, track the pending append batch for cleanup after rollback (cancel or failed
 	/// commit).
 	pending_index_batches: Mutex<HashMap<SharedIndexKey, (BatchId, BatchIdsCleanQueue)>>,
+	/// The save points set since the first named save point, mirroring the
+	/// storage engine.
+	save_points: Mutex<Vec<SavePoint>>,
+}
+
+/// A save point set while a named save point is active.
+struct SavePoint {
+	/// The name of a save point set by a `SAVEPOINT` statement
+	name: Option<String>,
+	/// The number of save points set after this one which were released, but
+	/// which are still kept by the storage engine
+	released: usize,
+}
+
+impl SavePoint {
+	fn new(name: Option<String>) -> Self {
+		Self {
+			name,
+			released: 0,
+		}
+	}
+
+	/// The number of storage engine save points reverted when rolling back
+	/// to this save point.
+	fn depth(&self) -> usize {
+		1 + self.released
+	}
 }
 
 impl Deref for Transaction {
@@ -96,6 +123,7 @@ impl Transaction {
 			async_event_trigger,
 			trigger_async_event: AtomicBool::new(false),
 			pending_index_batches: Mutex::new(HashMap::new()),
+			save_points: Mutex::new(Vec::new()),
 		}
 	}
 
@@ -728,17 +756,117 @@ impl Transaction {
 
 	/// Set a new save point on the transaction.
 	pub async fn new_save_point(&self) -> Result<()> {
-		Ok(self.inner.new_save_point().await.map_err(Error::from)?)
+		let mut save_points = self.save_points.lock().await;
+		self.inner.new_save_point().await.map_err(Error::from)?;
+		if !save_points.is_empty() {
+			save_points.push(SavePoint::new(None));
+		}
+		Ok(())
 	}
 
 	/// Release the last save point.
+	///
+	/// While a named save point is set, the changes made since this save point
+	/// was set are still reverted when rolling back to the named save point.
 	pub async fn release_last_save_point(&self) -> Result<()> {
-		Ok(self.inner.release_last_save_point().await.map_err(Error::from)?)
+		let mut save_points = self.save_points.lock().await;
+		match save_points.len() {
+			0 => self.inner.release_last_save_point().await.map_err(Error::from)?,
+			len => self.release_save_points(&mut save_points, len - 1).await?,
+		}
+		Ok(())
 	}
 
 	/// Rollback to the last save point.
 	pub async fn rollback_to_save_point(&self) -> Result<()> {
-		Ok(self.inner.rollback_to_save_point().await.map_err(Error::from)?)
+		let mut save_points = self.save_points.lock().await;
+		let depth = save_points.last().map_or(1, SavePoint::depth);
+		for _ in 0..depth {
+			self.inner.rollback_to_save_point().await.map_err(Error::from)?;
+		}
+		save_points.pop();
+		Ok(())
+	}
+
+	/// Set a named save point on the transaction.
+	///
+	/// If a save point with the same name already exists, the new save point
+	/// hides it until it is released or rolled back.
+	pub async fn set_named_save_point(&self, name: &str) -> Result<()> {
+		let mut save_points = self.save_points.lock().await;
+		self.inner.new_save_point().await.map_err(Error::from)?;
+		save_points.push(SavePoint::new(Some(name.to_owned())));
+		Ok(())
+	}
+
+	/// Rollback to a named save point.
+	///
+	/// Every save point set after the named save point is discarded, while the
+	/// named save point itself is kept so that it can be rolled back to again.
+	pub async fn rollback_to_named_save_point(&self, name: &str) -> Result<()> {
+		let mut save_points = self.save_points.lock().await;
+		let idx = Self::find_save_point(&save_points, name)?;
+		// Revert each save point down to, and including, the named one
+		let depth: usize = save_points[idx..].iter().map(SavePoint::depth).sum();
+		for _ in 0..depth {
+			self.inner.rollback_to_save_point().await.map_err(Error::from)?;
+		}
+		save_points.truncate(idx);
+		// Cached entries may have been written after the save point
+		self.cache.clear();
+		// Set the named save point again
+		self.inner.new_save_point().await.map_err(Error::from)?;
+		save_points.push(SavePoint::new(Some(name.to_owned())));
+		Ok(())
+	}
+
+	/// Release a named save point, along with every save point set after it.
+	///
+	/// The changes made since the save point was set are kept.
+	pub async fn release_named_save_point(&self, name: &str) -> Result<()> {
+		let mut save_points = self.save_points.lock().await;
+		let idx = Self::find_save_point(&save_points, name)?;
+		self.release_save_points(&mut save_points, idx).await
+	}
+
+	/// Release the save points from a position of the stack onwards.
+	///
+	/// When the storage engine keeps released save points, and an enclosing
+	/// save point can still be rolled back to, the released save points are
+	/// counted on the enclosing save point instead, so that rolling back to
+	/// it reverts them all.
+	async fn release_save_points(
+		&self,
+		save_points: &mut Vec<SavePoint>,
+		idx: usize,
+	) -> Result<()> {
+		let depth: usize = save_points[idx..].iter().map(SavePoint::depth).sum();
+		save_points.truncate(idx);
+		match save_points.last_mut() {
+			Some(enclosing) if !self.inner.releases_save_points() => {
+				enclosing.released += depth;
+			}
+			_ => {
+				for _ in 0..depth {
+					self.inner.release_last_save_point().await.map_err(Error::from)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Check whether a named save point is set on the transaction.
+	pub async fn has_named_save_point(&self) -> bool {
+		self.save_points.lock().await.iter().any(|s| s.name.is_some())
+	}
+
+	/// Find the position of the most recent save point with this name.
+	fn find_save_point(save_points: &[SavePoint], name: &str) -> Result<usize> {
+		save_points.iter().rposition(|s| s.name.as_deref() == Some(name)).ok_or_else(|| {
+			anyhow::Error::new(Error::SavepointNotFound {
+				name: name.to_owned(),
+			})
+		})
 	}
 
 	// --------------------------------------------------
@@ -1166,6 +1294,8 @@ impl DatabaseProvider for Transaction {
 					comment: None,
 					changefeed: None,
 					strict: false,
+					capabilities: None,
+					quota: None,
 				};
 
 				return self.put_db(&ns_def.name, db_def).await;
@@ -1278,6 +1408,93 @@ impl DatabaseProvider for Transaction {
 		}
 	}
 
+	/// Retrieve all job definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_jobs(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		version: Option<u64>,
+	) -> Result<Arc<[catalog::JobDefinition]>> {
+		if version.is_some() {
+			let beg = crate::key::database::jb::prefix(ns, db)?;
+			let end = crate::key::database::jb::suffix(ns, db)?;
+			let val = self.getr(beg..end, version).await?;
+			return util::deserialize_cache(val.iter().map(|x| x.1.as_slice()));
+		}
+		let qey = cache::tx::Lookup::Jbs(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_jbs(),
+			None => {
+				let beg = crate::key::database::jb::prefix(ns, db)?;
+				let end = crate::key::database::jb::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Jbs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve all consumer group definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_consumers(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		version: Option<u64>,
+	) -> Result<Arc<[catalog::ConsumerDefinition]>> {
+		if version.is_some() {
+			let beg = crate::key::database::cu::prefix(ns, db)?;
+			let end = crate::key::database::cu::suffix(ns, db)?;
+			let val = self.getr(beg..end, version).await?;
+			return util::deserialize_cache(val.iter().map(|x| x.1.as_slice()));
+		}
+		let qey = cache::tx::Lookup::Cus(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_cus(),
+			None => {
+				let beg = crate::key::database::cu::prefix(ns, db)?;
+				let end = crate::key::database::cu::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Cus(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve all sink definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_sinks(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		version: Option<u64>,
+	) -> Result<Arc<[catalog::SinkDefinition]>> {
+		if version.is_some() {
+			let beg = crate::key::database::sk::prefix(ns, db)?;
+			let end = crate::key::database::sk::suffix(ns, db)?;
+			let val = self.getr(beg..end, version).await?;
+			return util::deserialize_cache(val.iter().map(|x| x.1.as_slice()));
+		}
+		let qey = cache::tx::Lookup::Sks(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_sks(),
+			None => {
+				let beg = crate::key::database::sk::prefix(ns, db)?;
+				let end = crate::key::database::sk::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Sks(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve all function definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	async fn all_db_functions(
@@ -1520,6 +1737,102 @@ impl DatabaseProvider for Transaction {
 		}
 	}
 
+	/// Retrieve a specific job definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_job(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		jb: &str,
+		version: Option<u64>,
+	) -> Result<Arc<catalog::JobDefinition>> {
+		if version.is_some() {
+			let key = crate::key::database::jb::new(ns, db, jb);
+			let val = self.get(&key, version).await?.ok_or_else(|| Error::JbNotFound {
+				name: jb.to_owned(),
+			})?;
+			return Ok(Arc::new(val));
+		}
+		let qey = cache::tx::Lookup::Jb(ns, db, jb);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::jb::new(ns, db, jb);
+				let val = self.get(&key, None).await?.ok_or_else(|| Error::JbNotFound {
+					name: jb.to_owned(),
+				})?;
+				let val = Arc::new(val);
+				let entry = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve a specific consumer group definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_consumer(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		cu: &str,
+		version: Option<u64>,
+	) -> Result<Arc<catalog::ConsumerDefinition>> {
+		if version.is_some() {
+			let key = crate::key::database::cu::new(ns, db, cu);
+			let val = self.get(&key, version).await?.ok_or_else(|| Error::CuNotFound {
+				name: cu.to_owned(),
+			})?;
+			return Ok(Arc::new(val));
+		}
+		let qey = cache::tx::Lookup::Cu(ns, db, cu);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::cu::new(ns, db, cu);
+				let val = self.get(&key, None).await?.ok_or_else(|| Error::CuNotFound {
+					name: cu.to_owned(),
+				})?;
+				let val = Arc::new(val);
+				let entry = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve a specific sink definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_sink(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		sk: &str,
+		version: Option<u64>,
+	) -> Result<Arc<catalog::SinkDefinition>> {
+		if version.is_some() {
+			let key = crate::key::database::sk::new(ns, db, sk);
+			let val = self.get(&key, version).await?.ok_or_else(|| Error::SkNotFound {
+				name: sk.to_owned(),
+			})?;
+			return Ok(Arc::new(val));
+		}
+		let qey = cache::tx::Lookup::Sk(ns, db, sk);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::sk::new(ns, db, sk);
+				let val = self.get(&key, None).await?.ok_or_else(|| Error::SkNotFound {
+					name: sk.to_owned(),
+				})?;
+				let val = Arc::new(val);
+				let entry = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve a specific function definition from a database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	async fn get_db_function(
@@ -2316,6 +2629,46 @@ impl TableProvider for Transaction {
 		Ok(())
 	}
 
+	/// Retrieve the statistics collected for a table.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_tb_statistics(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+	) -> Result<Option<Arc<catalog::TableStatistics>>> {
+		let qey = cache::tx::Lookup::St(ns, db, tb);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type().map(Some),
+			None => {
+				let key = crate::key::table::st::new(ns, db, tb);
+				let Some(val) = self.get(&key, None).await? else {
+					return Ok(None);
+				};
+				let val = Arc::new(val);
+				let entry = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(Some(val))
+			}
+		}
+	}
+
+	async fn put_tb_statistics(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		st: &catalog::TableStatistics,
+	) -> Result<()> {
+		let key = crate::key::table::st::new(ns, db, tb);
+		self.set(&key, st).await?;
+		// Set the entry in the cache
+		let qey = cache::tx::Lookup::St(ns, db, tb);
+		let entry = cache::tx::Entry::Any(Arc::new(st.clone()));
+		self.cache.insert(qey, entry);
+		Ok(())
+	}
+
 	/// Fetch a specific record value.
 	///
 	/// This function will return a new default initialized record if non exists.
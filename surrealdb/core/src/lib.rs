@@ -38,7 +38,9 @@ pub mod doc;
 mod exe;
 mod fmt;
 mod fnc;
+mod job;
 mod key;
+mod sink;
 #[doc(hidden)]
 pub mod str;
 #[cfg(feature = "surrealism")]
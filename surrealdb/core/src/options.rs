@@ -27,6 +27,23 @@ pub struct EngineOptions {
 	///
 	/// Default: 5 seconds
 	pub event_processing_interval: Duration,
+	/// Interval for removing expired records from tables with a time-to-live.
+	///
+	/// Default: 60 seconds
+	pub ttl_sweep_interval: Duration,
+	/// Interval for checking whether any scheduled jobs are due.
+	///
+	/// Default: 10 seconds
+	pub job_interval: Duration,
+	/// Interval for delivering changefeeds to webhook and file sinks.
+	///
+	/// Default: 5 seconds
+	pub sink_interval: Duration,
+	/// Interval for measuring the storage size of namespaces and databases
+	/// with a storage quota.
+	///
+	/// Default: 60 seconds
+	pub quota_storage_interval: Duration,
 }
 
 impl Default for EngineOptions {
@@ -38,6 +55,10 @@ impl Default for EngineOptions {
 			changefeed_gc_interval: Duration::from_secs(30),
 			index_compaction_interval: Duration::from_secs(5),
 			event_processing_interval: Duration::from_secs(5),
+			ttl_sweep_interval: Duration::from_secs(60),
+			job_interval: Duration::from_secs(10),
+			sink_interval: Duration::from_secs(5),
+			quota_storage_interval: Duration::from_secs(60),
 		}
 	}
 }
@@ -69,4 +90,24 @@ impl EngineOptions {
 		self.event_processing_interval = interval;
 		self
 	}
+
+	pub fn with_ttl_sweep_interval(mut self, interval: Duration) -> Self {
+		self.ttl_sweep_interval = interval;
+		self
+	}
+
+	pub fn with_job_interval(mut self, interval: Duration) -> Self {
+		self.job_interval = interval;
+		self
+	}
+
+	pub fn with_sink_interval(mut self, interval: Duration) -> Self {
+		self.sink_interval = interval;
+		self
+	}
+
+	pub fn with_quota_storage_interval(mut self, interval: Duration) -> Self {
+		self.quota_storage_interval = interval;
+		self
+	}
 }
@@ -7,6 +7,7 @@ pub enum Method {
 	Use,
 	Signup,
 	Signin,
+	Challenge,
 	Authenticate,
 	Refresh,
 	Invalidate,
@@ -35,6 +36,9 @@ pub enum Method {
 	Begin,
 	Commit,
 	Cancel,
+	Savepoint,
+	RollbackTo,
+	Release,
 }
 
 impl Method {
@@ -65,6 +69,7 @@ impl Method {
 			"use" => Self::Use,
 			"signup" => Self::Signup,
 			"signin" => Self::Signin,
+			"challenge" => Self::Challenge,
 			"authenticate" => Self::Authenticate,
 			"refresh" => Self::Refresh,
 			"invalidate" => Self::Invalidate,
@@ -93,6 +98,9 @@ impl Method {
 			"begin" => Self::Begin,
 			"commit" => Self::Commit,
 			"cancel" => Self::Cancel,
+			"savepoint" => Self::Savepoint,
+			"rollback_to" => Self::RollbackTo,
+			"release" => Self::Release,
 			_ => Self::Unknown,
 		}
 	}
@@ -107,6 +115,7 @@ impl Method {
 			Self::Use => "use",
 			Self::Signup => "signup",
 			Self::Signin => "signin",
+			Self::Challenge => "challenge",
 			Self::Authenticate => "authenticate",
 			Self::Refresh => "refresh",
 			Self::Invalidate => "invalidate",
@@ -135,6 +144,9 @@ impl Method {
 			Self::Begin => "begin",
 			Self::Commit => "commit",
 			Self::Cancel => "cancel",
+			Self::Savepoint => "savepoint",
+			Self::RollbackTo => "rollback_to",
+			Self::Release => "release",
 		}
 	}
 }
@@ -197,6 +197,7 @@ pub trait RpcProtocol {
 			Method::Use => self.yuse(session, params).await,
 			Method::Signup => self.signup(session, params).await,
 			Method::Signin => self.signin(session, params).await,
+			Method::Challenge => self.challenge(session, params).await,
 			Method::Authenticate => self.authenticate(session, params).await,
 			Method::Refresh => self.refresh(session, params).await,
 			Method::Invalidate => self.invalidate(session).await,
@@ -211,6 +212,9 @@ pub trait RpcProtocol {
 			Method::Begin => self.begin(txn, session).await,
 			Method::Commit => self.commit(txn, session, params).await,
 			Method::Cancel => self.cancel(txn, session, params).await,
+			Method::Savepoint => self.savepoint(txn, session, params).await,
+			Method::RollbackTo => self.rollback_to(txn, session, params).await,
+			Method::Release => self.release(txn, session, params).await,
 			Method::Sessions => self.sessions().await,
 			Method::Attach => self.attach(session).await,
 			Method::Detach => self.detach(session).await,
@@ -424,6 +428,29 @@ pub trait RpcProtocol {
 		out.map(DbResult::Other).map_err(types_error_from_anyhow)
 	}
 
+	async fn challenge(
+		&self,
+		session_id: Option<Uuid>,
+		params: PublicArray,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		// Process the method arguments
+		let Some((PublicValue::String(challenge), PublicValue::String(code))) =
+			extract_args::<(PublicValue, PublicValue)>(params.into_vec())
+		else {
+			return Err(invalid_params("Expected (challenge:string, code:string)".to_string()));
+		};
+		// Get a write lock on the session
+		let session_lock = self.get_session(&session_id)?;
+		let mut session = session_lock.write().await;
+		// Attempt to complete the challenge, mutating the session
+		let out: Result<PublicValue> =
+			crate::iam::signin::challenge(self.kvs(), &mut session, challenge, code)
+				.await
+				.map(SurrealValue::into_value);
+		// Return the signin result
+		out.map(DbResult::Other).map_err(types_error_from_anyhow)
+	}
+
 	async fn authenticate(
 		&self,
 		session_id: Option<Uuid>,
@@ -1553,6 +1580,36 @@ pub trait RpcProtocol {
 	) -> Result<DbResult, surrealdb_types::Error> {
 		Err(method_not_allowed(Method::Cancel.to_string()))
 	}
+
+	/// Set a named savepoint in a transaction
+	async fn savepoint(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		_params: PublicArray,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		Err(method_not_allowed(Method::Savepoint.to_string()))
+	}
+
+	/// Roll a transaction back to a named savepoint
+	async fn rollback_to(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		_params: PublicArray,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		Err(method_not_allowed(Method::RollbackTo.to_string()))
+	}
+
+	/// Release a named savepoint in a transaction
+	async fn release(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		_params: PublicArray,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		Err(method_not_allowed(Method::Release.to_string()))
+	}
 }
 
 enum QueryForm<'a> {
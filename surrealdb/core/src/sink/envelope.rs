@@ -0,0 +1,99 @@
+//! Conversion of changefeed mutations into Debezium change events.
+//!
+//! Each record change is described by an envelope with the shape of a
+//! Debezium event with schemas disabled:
+//!
+//! ```json
+//! {
+//!   "before": { "id": "person:tobie", "name": "Tobie" },
+//!   "after": { "id": "person:tobie", "name": "Tobias" },
+//!   "source": {
+//!     "version": "3.0.0",
+//!     "connector": "surrealdb",
+//!     "name": "sink",
+//!     "ts_ms": 1700000000000,
+//!     "ns": "test",
+//!     "db": "test",
+//!     "table": "person",
+//!     "versionstamp": 65536
+//!   },
+//!   "op": "u",
+//!   "ts_ms": 1700000000000
+//! }
+//! ```
+//!
+//! The `before` image is only known when the changefeed of the table includes
+//! the original values, and table definition changes are not delivered.
+
+use anyhow::Result;
+
+use crate::cf::TableMutation;
+use crate::val::{Array, Datetime, Object, Value};
+
+/// The details of where a batch of changes originated from
+pub(super) struct Source<'a> {
+	/// The name of the sink delivering the changes
+	pub name: &'a str,
+	pub ns: &'a str,
+	pub db: &'a str,
+	pub table: &'a str,
+	/// Whether the changefeed of the table includes the original values, so
+	/// that created records can be told apart from updated records
+	pub store_diff: bool,
+}
+
+impl Source<'_> {
+	/// Converts a mutation into a change event, if it describes a record change
+	pub(super) fn envelope(
+		&self,
+		versionstamp: u64,
+		time: &Datetime,
+		mutation: TableMutation,
+	) -> Result<Option<Value>> {
+		let (op, before, after) = match mutation {
+			TableMutation::Set(_, after) if self.store_diff => ("c", Value::Null, after),
+			TableMutation::Set(_, after) => ("u", Value::Null, after),
+			TableMutation::SetWithDiff(_, after, operations) => {
+				// The operations revert the current value to the previous value
+				let mut before = after.clone();
+				before.patch(Value::Array(Array(
+					operations.into_iter().map(|x| Value::Object(x.into_object())).collect(),
+				)))?;
+				("u", before, after)
+			}
+			TableMutation::Del(id) => (
+				"d",
+				Value::from(map! {
+					"id".to_string() => Value::RecordId(id),
+				}),
+				Value::Null,
+			),
+			TableMutation::DelWithOriginal(_, before) => ("d", before, Value::Null),
+			TableMutation::Def(_) => return Ok(None),
+		};
+		let ts_ms = Value::from(time.0.timestamp_millis());
+		let source = map! {
+			"version".to_string() => env!("CARGO_PKG_VERSION").into(),
+			"connector".to_string() => "surrealdb".into(),
+			"name".to_string() => self.name.into(),
+			"ts_ms".to_string() => ts_ms.clone(),
+			"ns".to_string() => self.ns.into(),
+			"db".to_string() => self.db.into(),
+			"table".to_string() => self.table.into(),
+			"versionstamp".to_string() => versionstamp.into(),
+		};
+		Ok(Some(Value::from(map! {
+			"before".to_string() => before,
+			"after".to_string() => after,
+			"source".to_string() => Value::Object(Object(source)),
+			"op".to_string() => op.into(),
+			"ts_ms".to_string() => ts_ms,
+		})))
+	}
+}
+
+/// Encodes a change event as a single line of JSON
+pub(super) fn to_json(event: Value) -> Result<String> {
+	let json = crate::val::convert_value_to_public_value(event)?.into_json_value();
+	Ok(serde_json::to_string(&json)?)
+}
@@ -0,0 +1,294 @@
+//! Changefeed sinks, defined with `DEFINE SINK`.
+//!
+//! A sink delivers the changefeed of a table, as Debezium change events, to an
+//! HTTP webhook, to a rolling NDJSON file, or to the clients of the HTTP
+//! streaming endpoint. Webhook and file sinks are pushed by
+//! [`Datastore::run_sinks`](crate::kvs::Datastore::run_sinks), which is called
+//! periodically on every node, and coordinated through a task lease so that a
+//! single node delivers the changes at a time. Stream sinks are pulled by the
+//! clients of the streaming endpoint.
+//!
+//! The versionstamp up to which changes have been delivered is stored
+//! alongside the sink definition as a [`SinkState`], and is only moved once a
+//! delivery has succeeded, so changes are delivered at least once. A failed
+//! delivery is retried with an exponential backoff. Changefeed garbage
+//! collection does not wait for sinks, so a sink which falls further behind
+//! than the changefeed expiry skips the changes which have expired.
+
+mod envelope;
+mod target;
+
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use tracing::warn;
+
+use self::envelope::Source;
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{DatabaseDefinition, NamespaceDefinition, SinkDefinition, SinkState};
+use crate::cnf::{SINK_BATCH_SIZE, SINK_RETRY_MAX_DELAY};
+use crate::ctx::FrozenContext;
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::expr::statements::show::ShowSince;
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{Datastore, LockType, Transaction, TransactionType};
+use crate::val::Datetime;
+
+/// A batch of change events read from the changefeed for a sink
+pub(crate) struct SinkBatch {
+	/// The versionstamp of the last changeset in the batch
+	pub versionstamp: u64,
+	/// The change events, each encoded as a line of JSON
+	pub events: Vec<String>,
+}
+
+/// Delivers the pending changes of every webhook and file sink.
+///
+/// Returns the number of change events which were delivered. A sink which
+/// fails to deliver its changes is retried later, and does not hold back the
+/// other sinks.
+pub(crate) async fn run(ds: &Datastore, lh: &LeaseHandler) -> Result<usize> {
+	let mut count = 0;
+	let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	let nss = catch!(txn, txn.all_ns(None).await);
+	txn.cancel().await?;
+	for ns in nss.iter() {
+		let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let dbs = catch!(txn, txn.all_db(ns.namespace_id, None).await);
+		txn.cancel().await?;
+		for db in dbs.iter() {
+			let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+			let sks = catch!(txn, txn.all_db_sinks(ns.namespace_id, db.database_id, None).await);
+			// Deliver with the capabilities of the database
+			let mut ctx = catch!(txn, ds.setup_ctx());
+			let base = ctx.get_capabilities();
+			catch!(
+				txn,
+				ctx.restrict_capabilities(&txn, &base, Some(&ns.name), Some(&db.name)).await
+			);
+			let ctx = ctx.freeze();
+			txn.cancel().await?;
+			for sk in sks.iter().filter(|sk| sk.target.is_pushed()) {
+				lh.try_maintain_lease().await?;
+				match deliver(ds, &ctx, ns, db, sk).await {
+					Ok(v) => count += v,
+					Err(e) => {
+						warn!("Failed to deliver sink {}/{}/{}: {e}", ns.name, db.name, sk.name)
+					}
+				}
+			}
+		}
+	}
+	Ok(count)
+}
+
+/// Delivers the next batch of changes of a single sink, returning the number
+/// of change events which were delivered
+async fn deliver(
+	ds: &Datastore,
+	ctx: &FrozenContext,
+	ns: &NamespaceDefinition,
+	db: &DatabaseDefinition,
+	sk: &SinkDefinition,
+) -> Result<usize> {
+	let key = crate::key::database::sd::new(ns.namespace_id, db.database_id, &sk.name);
+	// Read the next batch, unless a failed delivery is waiting to be retried
+	let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	let state = catch!(txn, get_state(&txn, ns, db, &sk.name).await);
+	let now = Datetime::now();
+	if state.retry.as_ref().is_some_and(|retry| retry > &now) {
+		txn.cancel().await?;
+		return Ok(0);
+	}
+	let batch = catch!(txn, next_batch(&txn, ns, db, sk, state.versionstamp).await);
+	txn.cancel().await?;
+	let Some(batch) = batch else {
+		return Ok(0);
+	};
+	// Deliver the change events, if the batch contained any for the table
+	let res = match batch.events.is_empty() {
+		true => Ok(()),
+		false => target::send(ctx, &sk.target, &batch.events).await,
+	};
+	// Record the outcome of the delivery
+	let txn = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+	let skk = crate::key::database::sk::new(ns.namespace_id, db.database_id, &sk.name);
+	if catch!(txn, txn.get(&skk, None).await).is_none() {
+		// The sink was removed while the changes were delivered
+		txn.cancel().await?;
+		return Ok(0);
+	}
+	let state = catch!(txn, get_state(&txn, ns, db, &sk.name).await);
+	let (state, res) = match res {
+		Ok(()) => {
+			(state.delivered(batch.versionstamp, !batch.events.is_empty()), Ok(batch.events.len()))
+		}
+		Err(e) => (state.failed(&e), Err(e)),
+	};
+	catch!(txn, txn.set(&key, &state).await);
+	txn.commit().await?;
+	res
+}
+
+/// Reads the change events for a sink which follow the given versionstamp
+pub(crate) async fn next_batch(
+	txn: &Transaction,
+	ns: &NamespaceDefinition,
+	db: &DatabaseDefinition,
+	sk: &SinkDefinition,
+	after: u64,
+) -> Result<Option<SinkBatch>> {
+	let limit = *SINK_BATCH_SIZE;
+	// Changes to other tables are read too, so that the sink moves past them
+	let since = ShowSince::Versionstamp(after.saturating_add(1));
+	let mut changesets =
+		crate::cf::read(txn, ns.namespace_id, db.database_id, None, since, Some(limit)).await?;
+	// The scan may have stopped part way through the last changeset
+	let mutations: usize = changesets.iter().map(|cs| cs.1.0.len()).sum();
+	if mutations >= limit as usize && changesets.len() > 1 {
+		changesets.pop();
+	}
+	let Some(last) = changesets.last() else {
+		return Ok(None);
+	};
+	let versionstamp = u64::try_from(last.0).unwrap_or(u64::MAX);
+	let store_diff = txn
+		.get_tb(ns.namespace_id, db.database_id, &sk.target_table, None)
+		.await?
+		.and_then(|tb| tb.changefeed)
+		.or(db.changefeed)
+		.is_some_and(|cf| cf.store_diff);
+	let source = Source {
+		name: &sk.name,
+		ns: &ns.name,
+		db: &db.name,
+		table: sk.target_table.as_str(),
+		store_diff,
+	};
+	let time = Datetime::now();
+	let mut events = Vec::new();
+	for changeset in changesets {
+		let vs = u64::try_from(changeset.0).unwrap_or(u64::MAX);
+		for tb in changeset.1.0.into_iter().filter(|tb| tb.0 == sk.target_table) {
+			for mutation in tb.1 {
+				if let Some(event) = source.envelope(vs, &time, mutation)? {
+					events.push(envelope::to_json(event)?);
+				}
+			}
+		}
+	}
+	Ok(Some(SinkBatch {
+		versionstamp,
+		events,
+	}))
+}
+
+/// Reads the change events of a stream sink which follow the given
+/// versionstamp, or the last acknowledged versionstamp of the sink
+pub(crate) async fn next_stream_batch(
+	ds: &Datastore,
+	sess: &Session,
+	sink: &str,
+	after: Option<u64>,
+) -> Result<Option<SinkBatch>> {
+	let (ns, db, sk) = get_stream_sink(ds, sess, sink).await?;
+	let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	let after = match after {
+		Some(v) => v,
+		None => catch!(txn, get_state(&txn, &ns, &db, sink).await).versionstamp,
+	};
+	let batch = catch!(txn, next_batch(&txn, &ns, &db, &sk, after).await);
+	txn.cancel().await?;
+	Ok(batch)
+}
+
+/// Acknowledges the change events of a stream sink up to a versionstamp
+pub(crate) async fn ack_stream_batch(
+	ds: &Datastore,
+	sess: &Session,
+	sink: &str,
+	versionstamp: u64,
+) -> Result<()> {
+	let (ns, db, _) = get_stream_sink(ds, sess, sink).await?;
+	let txn = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+	let key = crate::key::database::sd::new(ns.namespace_id, db.database_id, sink);
+	let state = catch!(txn, get_state(&txn, &ns, &db, sink).await);
+	if versionstamp > state.versionstamp {
+		catch!(txn, txn.set(&key, &state.delivered(versionstamp, true)).await);
+	}
+	txn.commit().await
+}
+
+/// Fetches the delivery state of a sink, which is stored when it is defined
+async fn get_state(
+	txn: &Transaction,
+	ns: &NamespaceDefinition,
+	db: &DatabaseDefinition,
+	sink: &str,
+) -> Result<SinkState> {
+	let key = crate::key::database::sd::new(ns.namespace_id, db.database_id, sink);
+	match txn.get(&key, None).await? {
+		Some(state) => Ok(state),
+		// The sink was removed, or its definition was never completed
+		None => bail!(Error::SkNotFound {
+			name: sink.to_owned(),
+		}),
+	}
+}
+
+/// Fetches a stream sink from the database selected by a session
+async fn get_stream_sink(
+	ds: &Datastore,
+	sess: &Session,
+	sink: &str,
+) -> Result<(Arc<NamespaceDefinition>, Arc<DatabaseDefinition>, Arc<SinkDefinition>)> {
+	let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+	ds.check(sess, Action::View, ResourceKind::Table.on_db(&ns, &db))?;
+	let txn = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	let Some(db) = catch!(txn, txn.get_db_by_name(&ns, &db, None).await) else {
+		txn.cancel().await?;
+		bail!(Error::SkNotFound {
+			name: sink.to_owned(),
+		});
+	};
+	let ns = catch!(txn, txn.expect_ns_by_name(&ns).await);
+	let sk = catch!(txn, txn.get_db_sink(db.namespace_id, db.database_id, sink, None).await);
+	txn.cancel().await?;
+	if sk.target.is_pushed() {
+		bail!(Error::SkNotStream {
+			name: sink.to_owned(),
+		});
+	}
+	Ok((ns, db, sk))
+}
+
+impl SinkState {
+	/// The state of a sink which has delivered its changes up to a versionstamp
+	pub(crate) fn delivered(self, versionstamp: u64, events: bool) -> Self {
+		SinkState {
+			versionstamp: self.versionstamp.max(versionstamp),
+			delivered: match events {
+				true => Some(Datetime::now()),
+				false => self.delivered,
+			},
+			attempts: 0,
+			error: None,
+			retry: None,
+		}
+	}
+
+	/// The state of a sink which has failed to deliver its changes
+	fn failed(self, error: &anyhow::Error) -> Self {
+		let attempts = self.attempts.saturating_add(1);
+		// Back off exponentially, from one second up to the maximum delay
+		let delay = 2u64.saturating_pow(attempts - 1).min(*SINK_RETRY_MAX_DELAY);
+		SinkState {
+			attempts,
+			error: Some(error.to_string()),
+			retry: Some(Datetime(Datetime::now().0 + chrono::Duration::seconds(delay as i64))),
+			..self
+		}
+	}
+}
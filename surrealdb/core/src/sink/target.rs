@@ -0,0 +1,143 @@
+//! Delivery of change events to the targets of webhook and file sinks.
+
+use anyhow::{Result, bail};
+
+use crate::catalog::SinkTarget;
+use crate::ctx::FrozenContext;
+use crate::err::Error;
+
+/// Sends a batch of change events to the target of a sink
+pub(super) async fn send(
+	ctx: &FrozenContext,
+	target: &SinkTarget,
+	events: &[String],
+) -> Result<()> {
+	match target {
+		SinkTarget::Webhook(url) => webhook(ctx, url, events).await,
+		SinkTarget::File(path) => file(path, events).await,
+		SinkTarget::Stream => Ok(()),
+	}
+}
+
+/// Posts the change events to the url as a JSON array
+#[cfg(feature = "http")]
+async fn webhook(ctx: &FrozenContext, url: &str, events: &[String]) -> Result<()> {
+	use reqwest::Method;
+	use reqwest::header::CONTENT_TYPE;
+
+	// Check if the url is valid and allowed
+	let url = url::Url::parse(url).map_err(|_| Error::InvalidUrl(url.to_owned()))?;
+	ctx.check_allowed_net(&url).await?;
+	// Send the events using the shared client
+	let body = format!("[{}]", events.join(","));
+	let res = ctx
+		.http_client()
+		.request(Method::POST, url)
+		.header(CONTENT_TYPE, "application/json")
+		.body(body)
+		.send()
+		.await
+		.map_err(Error::from)?;
+	// Check the response status
+	if let Err(err) = res.error_for_status() {
+		match err.status() {
+			Some(s) => match s.canonical_reason() {
+				Some(reason) => bail!(Error::Http(format!("{} {reason}", s.as_u16()))),
+				None => bail!(Error::Http(s.as_u16().to_string())),
+			},
+			None => bail!(Error::Http(err.to_string())),
+		}
+	}
+	Ok(())
+}
+
+#[cfg(not(feature = "http"))]
+async fn webhook(_: &FrozenContext, _: &str, _: &[String]) -> Result<()> {
+	bail!(Error::HttpDisabled)
+}
+
+/// Appends the change events to the file as NDJSON, rolling the file over
+/// once it has grown past the maximum size
+#[cfg(not(target_family = "wasm"))]
+async fn file(path: &str, events: &[String]) -> Result<()> {
+	append(path, &crate::cnf::FILE_ALLOWLIST, events).await
+}
+
+/// Appends the change events to a file within the given allowlist
+#[cfg(not(target_family = "wasm"))]
+async fn append(path: &str, allowlist: &[std::path::PathBuf], events: &[String]) -> Result<()> {
+	use std::path::Path;
+
+	use tokio::fs::{self, OpenOptions};
+	use tokio::io::AsyncWriteExt;
+
+	use crate::cnf::SINK_FILE_MAX_SIZE;
+	use crate::iam::file::check_is_path_allowlisted;
+
+	// Check the directory of the file is allowed. Files are only written
+	// within the configured allowlist, and never when it is not set.
+	let path = Path::new(path);
+	let Some(name) = path.file_name() else {
+		bail!(Error::Query {
+			message: format!("`{}` is not a valid sink file path", path.display()),
+		});
+	};
+	let dir = match path.parent() {
+		Some(dir) if !dir.as_os_str().is_empty() => dir,
+		_ => Path::new("."),
+	};
+	let path = check_is_path_allowlisted(dir, allowlist)?.join(name);
+	// Roll the file over if it is full
+	if let Ok(meta) = fs::metadata(&path).await
+		&& meta.len() >= *SINK_FILE_MAX_SIZE
+	{
+		let mut rolled = path.clone().into_os_string();
+		rolled.push(format!(".{}", chrono::Utc::now().timestamp_millis()));
+		fs::rename(&path, rolled).await?;
+	}
+	// Append the events to the file
+	let mut buf = String::new();
+	for event in events {
+		buf.push_str(event);
+		buf.push('\n');
+	}
+	let mut file = OpenOptions::new().create(true).append(true).open(&path).await?;
+	file.write_all(buf.as_bytes()).await?;
+	// Ensure the events are persisted before they are acknowledged
+	file.sync_data().await?;
+	Ok(())
+}
+
+#[cfg(target_family = "wasm")]
+async fn file(_: &str, _: &[String]) -> Result<()> {
+	bail!(Error::Query {
+		message: "File sinks are not supported on this platform".to_string(),
+	})
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+	use super::*;
+	use crate::iam::file::extract_allowed_paths;
+
+	#[tokio::test]
+	async fn append_requires_allowlist() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("changes.ndjson");
+		let path = path.to_str().unwrap();
+		let events = ["{\"op\":\"c\"}".to_string(), "{\"op\":\"d\"}".to_string()];
+		// Nothing is written without an allowlist
+		assert!(append(path, &[], &events).await.is_err());
+		assert!(!dir.path().join("changes.ndjson").exists());
+		// Batches are appended as lines within the allowlist
+		let allowlist = extract_allowed_paths(&dir.path().to_string_lossy(), true, "file");
+		append(path, &allowlist, &events).await.unwrap();
+		append(path, &allowlist, &events[..1]).await.unwrap();
+		let written = std::fs::read_to_string(path).unwrap();
+		assert_eq!(written.lines().collect::<Vec<_>>(), [&events[0], &events[1], &events[0]]);
+		// Files outside of the allowlist are denied
+		let other = tempfile::tempdir().unwrap();
+		let path = other.path().join("changes.ndjson");
+		assert!(append(path.to_str().unwrap(), &allowlist, &events).await.is_err());
+	}
+}
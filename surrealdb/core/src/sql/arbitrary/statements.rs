@@ -104,6 +104,8 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineUserStatement {
 			session_duration: u.arbitrary()?,
 			roles,
 			comment,
+			quota: u.arbitrary()?,
+			totp: u.arbitrary()?,
 		})
 	}
 }
@@ -126,7 +128,9 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineIndexStatement {
 				}
 				cols
 			}
-			Index::Hnsw(_) | Index::FullText(_) => vec![u.arbitrary()?],
+			Index::Hnsw(_) | Index::FullText(_) | Index::Spatial | Index::IvfPq(_) => {
+				vec![u.arbitrary()?]
+			}
 			Index::Count(_) => Vec::new(),
 		};
 
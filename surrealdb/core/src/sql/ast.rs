@@ -3,7 +3,7 @@ use std::fmt::{self};
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use crate::expr;
-use crate::fmt::Fmt;
+use crate::fmt::{EscapeKwFreeIdent, Fmt};
 use crate::sql::statements::{
 	AccessStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement, UseStatement,
 };
@@ -103,6 +103,9 @@ pub(crate) enum TopLevelExpr {
 	Begin,
 	Cancel,
 	Commit,
+	Savepoint(String),
+	RollbackTo(String),
+	Release(String),
 	Access(Box<AccessStatement>),
 	Kill(KillStatement),
 	Live(Box<LiveStatement>),
@@ -118,6 +121,9 @@ impl From<TopLevelExpr> for crate::expr::TopLevelExpr {
 			TopLevelExpr::Begin => crate::expr::TopLevelExpr::Begin,
 			TopLevelExpr::Cancel => crate::expr::TopLevelExpr::Cancel,
 			TopLevelExpr::Commit => crate::expr::TopLevelExpr::Commit,
+			TopLevelExpr::Savepoint(name) => crate::expr::TopLevelExpr::Savepoint(name),
+			TopLevelExpr::RollbackTo(name) => crate::expr::TopLevelExpr::RollbackTo(name),
+			TopLevelExpr::Release(name) => crate::expr::TopLevelExpr::Release(name),
 			TopLevelExpr::Access(access_statement) => {
 				crate::expr::TopLevelExpr::Access(Box::new((*access_statement).into()))
 			}
@@ -147,6 +153,9 @@ impl From<crate::expr::TopLevelExpr> for TopLevelExpr {
 			crate::expr::TopLevelExpr::Begin => TopLevelExpr::Begin,
 			crate::expr::TopLevelExpr::Cancel => TopLevelExpr::Cancel,
 			crate::expr::TopLevelExpr::Commit => TopLevelExpr::Commit,
+			crate::expr::TopLevelExpr::Savepoint(name) => TopLevelExpr::Savepoint(name),
+			crate::expr::TopLevelExpr::RollbackTo(name) => TopLevelExpr::RollbackTo(name),
+			crate::expr::TopLevelExpr::Release(name) => TopLevelExpr::Release(name),
 			crate::expr::TopLevelExpr::Access(access_statement) => {
 				TopLevelExpr::Access(Box::new((*access_statement).into()))
 			}
@@ -186,6 +195,15 @@ impl ToSql for TopLevelExpr {
 			TopLevelExpr::Begin => f.push_str("BEGIN"),
 			TopLevelExpr::Cancel => f.push_str("CANCEL"),
 			TopLevelExpr::Commit => f.push_str("COMMIT"),
+			TopLevelExpr::Savepoint(name) => {
+				write_sql!(f, fmt, "SAVEPOINT {}", EscapeKwFreeIdent(name))
+			}
+			TopLevelExpr::RollbackTo(name) => {
+				write_sql!(f, fmt, "ROLLBACK TO {}", EscapeKwFreeIdent(name))
+			}
+			TopLevelExpr::Release(name) => {
+				write_sql!(f, fmt, "RELEASE {}", EscapeKwFreeIdent(name))
+			}
 			TopLevelExpr::Access(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Kill(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Live(s) => s.fmt_sql(f, fmt),
@@ -8,10 +8,10 @@ use crate::sql::literal::ObjectEntry;
 use crate::sql::lookup::LookupKind;
 use crate::sql::operator::BindingPower;
 use crate::sql::statements::{
-	AlterStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
-	IfelseStatement, InfoStatement, InsertStatement, OutputStatement, RebuildStatement,
-	RelateStatement, RemoveStatement, SelectStatement, SetStatement, SleepStatement,
-	UpdateStatement, UpsertStatement,
+	AlterStatement, AnalyzeStatement, CreateStatement, DefineStatement, DeleteStatement,
+	ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, OutputStatement,
+	RebuildStatement, RelateStatement, RemoveStatement, SelectStatement, SetStatement,
+	SleepStatement, UpdateStatement, UpsertStatement,
 };
 use crate::sql::{
 	BinaryOperator, Block, Closure, Constant, Dir, FunctionCall, Idiom, Literal, Mock, Param, Part,
@@ -63,6 +63,7 @@ pub(crate) enum Expr {
 	Define(Box<DefineStatement>),
 	Remove(Box<RemoveStatement>),
 	Rebuild(Box<RebuildStatement>),
+	Analyze(Box<AnalyzeStatement>),
 	Upsert(Box<UpsertStatement>),
 	Alter(Box<AlterStatement>),
 	Info(Box<InfoStatement>),
@@ -154,6 +155,7 @@ impl Expr {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Analyze(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_)
 			| Expr::Info(_)
@@ -539,6 +541,7 @@ impl ToSql for Expr {
 			Expr::Define(s) => s.fmt_sql(f, fmt),
 			Expr::Remove(s) => s.fmt_sql(f, fmt),
 			Expr::Rebuild(s) => s.fmt_sql(f, fmt),
+			Expr::Analyze(s) => s.fmt_sql(f, fmt),
 			Expr::Upsert(s) => s.fmt_sql(f, fmt),
 			Expr::Alter(s) => s.fmt_sql(f, fmt),
 			Expr::Info(s) => s.fmt_sql(f, fmt),
@@ -615,6 +618,7 @@ impl From<Expr> for crate::expr::Expr {
 			Expr::Define(s) => crate::expr::Expr::Define(Box::new((*s).into())),
 			Expr::Remove(s) => crate::expr::Expr::Remove(Box::new((*s).into())),
 			Expr::Rebuild(s) => crate::expr::Expr::Rebuild(Box::new((*s).into())),
+			Expr::Analyze(s) => crate::expr::Expr::Analyze(Box::new((*s).into())),
 			Expr::Upsert(s) => crate::expr::Expr::Upsert(Box::new((*s).into())),
 			Expr::Alter(s) => crate::expr::Expr::Alter(Box::new((*s).into())),
 			Expr::Info(s) => crate::expr::Expr::Info(Box::new((*s).into())),
@@ -684,6 +688,7 @@ impl From<crate::expr::Expr> for Expr {
 			crate::expr::Expr::Define(s) => Expr::Define(Box::new((*s).into())),
 			crate::expr::Expr::Remove(s) => Expr::Remove(Box::new((*s).into())),
 			crate::expr::Expr::Rebuild(s) => Expr::Rebuild(Box::new((*s).into())),
+			crate::expr::Expr::Analyze(s) => Expr::Analyze(Box::new((*s).into())),
 			crate::expr::Expr::Upsert(s) => Expr::Upsert(Box::new((*s).into())),
 			crate::expr::Expr::Alter(s) => Expr::Alter(Box::new((*s).into())),
 			crate::expr::Expr::Info(s) => Expr::Info(Box::new((*s).into())),
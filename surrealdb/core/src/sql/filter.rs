@@ -1,7 +1,8 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
-use crate::fmt::QuoteStr;
+use crate::fmt::{Fmt, QuoteStr};
 use crate::sql::language::Language;
+use crate::types::PublicFile;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -13,6 +14,8 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	Stopwords(WordList),
+	Synonyms(WordList),
 }
 
 impl ToSql for Filter {
@@ -25,6 +28,8 @@ impl ToSql for Filter {
 			Self::Snowball(lang) => write_sql!(f, fmt, "SNOWBALL({lang})"),
 			Self::Uppercase => f.push_str("UPPERCASE"),
 			Self::Mapper(path) => write_sql!(f, fmt, "MAPPER({})", QuoteStr(path)),
+			Self::Stopwords(list) => write_sql!(f, fmt, "STOPWORDS({list})"),
+			Self::Synonyms(list) => write_sql!(f, fmt, "SYNONYMS({list})"),
 		}
 	}
 }
@@ -39,6 +44,8 @@ impl From<Filter> for crate::expr::Filter {
 			Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			Filter::Uppercase => Self::Uppercase,
 			Filter::Mapper(path) => Self::Mapper(path),
+			Filter::Stopwords(list) => Self::Stopwords(list.into()),
+			Filter::Synonyms(list) => Self::Synonyms(list.into()),
 		}
 	}
 }
@@ -53,6 +60,53 @@ impl From<crate::expr::Filter> for Filter {
 			crate::expr::Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			crate::expr::Filter::Uppercase => Self::Uppercase,
 			crate::expr::Filter::Mapper(path) => Self::Mapper(path),
+			crate::expr::Filter::Stopwords(list) => Self::Stopwords(list.into()),
+			crate::expr::Filter::Synonyms(list) => Self::Synonyms(list.into()),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum WordList {
+	Language(Language),
+	List(Vec<String>),
+	File(PublicFile),
+}
+
+impl ToSql for WordList {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			Self::Language(lang) => write_sql!(f, fmt, "{lang}"),
+			Self::List(words) => {
+				write_sql!(
+					f,
+					fmt,
+					"[{}]",
+					Fmt::comma_separated(words.iter().map(|w| QuoteStr(w.as_str())))
+				)
+			}
+			Self::File(file) => file.fmt_sql(f, fmt),
+		}
+	}
+}
+
+impl From<WordList> for crate::expr::filter::WordList {
+	fn from(v: WordList) -> Self {
+		match v {
+			WordList::Language(lang) => Self::Language(lang.into()),
+			WordList::List(words) => Self::List(words),
+			WordList::File(file) => Self::File(file.into(), Vec::new()),
+		}
+	}
+}
+
+impl From<crate::expr::filter::WordList> for WordList {
+	fn from(v: crate::expr::filter::WordList) -> Self {
+		match v {
+			crate::expr::filter::WordList::Language(lang) => Self::Language(lang.into()),
+			crate::expr::filter::WordList::List(words) => Self::List(words),
+			crate::expr::filter::WordList::File(file, _) => Self::File(file.into()),
 		}
 	}
 }
@@ -18,6 +18,10 @@ pub(crate) enum Index {
 	FullText(FullTextParams),
 	/// Count index
 	Count(Option<Cond>),
+	/// Geospatial index over geometry values
+	Spatial,
+	/// IVF-PQ index for approximate distance based metrics
+	IvfPq(IvfPqParams),
 }
 
 impl From<Index> for crate::catalog::Index {
@@ -28,6 +32,8 @@ impl From<Index> for crate::catalog::Index {
 			Index::Hnsw(p) => Self::Hnsw(p.into()),
 			Index::FullText(p) => Self::FullText(p.into()),
 			Index::Count(c) => Self::Count(c.map(Into::into)),
+			Index::Spatial => Self::Spatial,
+			Index::IvfPq(p) => Self::IvfPq(p.into()),
 		}
 	}
 }
@@ -40,6 +46,8 @@ impl From<crate::catalog::Index> for Index {
 			crate::catalog::Index::Hnsw(p) => Self::Hnsw(p.into()),
 			crate::catalog::Index::FullText(p) => Self::FullText(p.into()),
 			crate::catalog::Index::Count(c) => Self::Count(c.map(Into::into)),
+			crate::catalog::Index::Spatial => Self::Spatial,
+			crate::catalog::Index::IvfPq(p) => Self::IvfPq(p.into()),
 		}
 	}
 }
@@ -120,6 +128,46 @@ impl From<crate::catalog::HnswParams> for HnswParams {
 	}
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct IvfPqParams {
+	pub dimension: u16,
+	pub distance: Distance,
+	pub vector_type: VectorType,
+	pub lists: u16,
+	pub subvectors: u16,
+	pub bits: u8,
+	pub probes: u16,
+}
+
+impl From<IvfPqParams> for crate::catalog::IvfPqParams {
+	fn from(v: IvfPqParams) -> Self {
+		crate::catalog::IvfPqParams {
+			dimension: v.dimension,
+			distance: v.distance.into(),
+			vector_type: v.vector_type.into(),
+			lists: v.lists,
+			subvectors: v.subvectors,
+			bits: v.bits,
+			probes: v.probes,
+		}
+	}
+}
+
+impl From<crate::catalog::IvfPqParams> for IvfPqParams {
+	fn from(v: crate::catalog::IvfPqParams) -> Self {
+		Self {
+			dimension: v.dimension,
+			distance: v.distance.into(),
+			vector_type: v.vector_type.into(),
+			lists: v.lists,
+			subvectors: v.subvectors,
+			bits: v.bits,
+			probes: v.probes,
+		}
+	}
+}
+
 #[derive(Clone, Default, Debug, Eq, PartialEq, PartialOrd, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub(crate) enum Distance {
@@ -207,6 +255,7 @@ impl ToSql for Index {
 		match self {
 			Self::Idx => {}
 			Self::Uniq => f.push_str("UNIQUE"),
+			Self::Spatial => f.push_str("SPATIAL"),
 			Self::Count(c) => {
 				f.push_str("COUNT");
 				if let Some(v) = c {
@@ -242,6 +291,20 @@ impl ToSql for Index {
 					f.push_str(" HASHED_VECTOR")
 				}
 			}
+			Self::IvfPq(p) => {
+				write_sql!(
+					f,
+					fmt,
+					"IVFPQ DIMENSION {} DIST {} TYPE {} LISTS {} SUBVECTORS {} BITS {} PROBES {}",
+					p.dimension,
+					p.distance,
+					p.vector_type,
+					p.lists,
+					p.subvectors,
+					p.bits,
+					p.probes
+				);
+			}
 		}
 	}
 }
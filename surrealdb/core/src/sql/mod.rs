@@ -45,6 +45,7 @@ pub(crate) mod table_type;
 #[cfg(test)]
 mod test_to_sql;
 pub(crate) mod tokenizer;
+pub(crate) mod ttl;
 pub(crate) mod user;
 pub(crate) mod view;
 pub(crate) mod with;
@@ -106,5 +107,6 @@ pub(crate) use self::statements::{
 	UpdateStatement, UpsertStatement,
 };
 pub(crate) use self::table_type::TableType;
+pub(crate) use self::ttl::Ttl;
 pub(crate) use self::view::View;
 pub(crate) use self::with::With;
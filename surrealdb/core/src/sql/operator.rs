@@ -211,31 +211,40 @@ pub(crate) enum BinaryOperator {
 pub struct MatchesOperator {
 	pub rf: Option<u8>,
 	pub operator: Option<BooleanOperator>,
+	pub fuzzy: Option<Fuzziness>,
+	pub prefix: bool,
 }
 
 impl ToSql for MatchesOperator {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
-		if let Some(r) = self.rf {
-			if let Some(ref o) = self.operator {
-				// Don't show AND operator since it's the default
-				if !matches!(o, BooleanOperator::And) {
-					write_sql!(f, fmt, "@{r},{o}@");
-				} else {
-					write_sql!(f, fmt, "@{r}@");
-				}
-			} else {
-				write_sql!(f, fmt, "@{r}@");
-			}
-		} else if let Some(ref o) = self.operator {
-			// Don't show AND operator since it's the default
-			if !matches!(o, BooleanOperator::And) {
-				write_sql!(f, fmt, "@{o}@");
-			} else {
-				f.push_str("@@");
+		f.push('@');
+		let mut first = true;
+		let mut sep = |f: &mut String| {
+			if !first {
+				f.push(',');
 			}
-		} else {
-			f.push_str("@@");
+			first = false;
+		};
+		if let Some(r) = self.rf {
+			sep(f);
+			write_sql!(f, fmt, "{r}");
+		}
+		// Don't show AND operator since it's the default
+		if let Some(o) = &self.operator
+			&& !matches!(o, BooleanOperator::And)
+		{
+			sep(f);
+			o.fmt_sql(f, fmt);
+		}
+		if let Some(fz) = &self.fuzzy {
+			sep(f);
+			fz.fmt_sql(f, fmt);
 		}
+		if self.prefix {
+			sep(f);
+			f.push_str("PREFIX");
+		}
+		f.push('@');
 	}
 }
 
@@ -247,6 +256,8 @@ impl From<MatchesOperator> for crate::expr::operator::MatchesOperator {
 				.operator
 				.map(From::from)
 				.unwrap_or(crate::expr::operator::BooleanOperator::And),
+			fuzzy: value.fuzzy.map(From::from),
+			prefix: value.prefix,
 		}
 	}
 }
@@ -256,6 +267,43 @@ impl From<crate::expr::operator::MatchesOperator> for MatchesOperator {
 		MatchesOperator {
 			rf: value.rf,
 			operator: Some(value.operator.into()),
+			fuzzy: value.fuzzy.map(From::from),
+			prefix: value.prefix,
+		}
+	}
+}
+
+/// The maximum edit distance of a fuzzy full-text search
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Fuzziness {
+	Auto,
+	Distance(u8),
+}
+
+impl From<Fuzziness> for crate::expr::operator::Fuzziness {
+	fn from(value: Fuzziness) -> Self {
+		match value {
+			Fuzziness::Auto => crate::expr::operator::Fuzziness::Auto,
+			Fuzziness::Distance(d) => crate::expr::operator::Fuzziness::Distance(d),
+		}
+	}
+}
+
+impl From<crate::expr::operator::Fuzziness> for Fuzziness {
+	fn from(value: crate::expr::operator::Fuzziness) -> Self {
+		match value {
+			crate::expr::operator::Fuzziness::Auto => Fuzziness::Auto,
+			crate::expr::operator::Fuzziness::Distance(d) => Fuzziness::Distance(d),
+		}
+	}
+}
+
+impl ToSql for Fuzziness {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			Self::Auto => f.push_str("FUZZY"),
+			Self::Distance(d) => write_sql!(f, fmt, "FUZZY({d})"),
 		}
 	}
 }
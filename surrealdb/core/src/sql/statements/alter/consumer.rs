@@ -0,0 +1,56 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::AlterKind;
+use crate::fmt::{CoverStmts, EscapeKwIdent, QuoteStr};
+use crate::sql::Expr;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+/// AST node for `ALTER CONSUMER`.
+pub struct AlterConsumerStatement {
+	pub name: String,
+	pub if_exists: bool,
+	/// The versionstamp up to which changes are acknowledged
+	pub ack: Option<Expr>,
+	pub comment: AlterKind<String>,
+}
+
+impl ToSql for AlterConsumerStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "ALTER CONSUMER");
+		if self.if_exists {
+			write_sql!(f, fmt, " IF EXISTS");
+		}
+		write_sql!(f, fmt, " {}", EscapeKwIdent(&self.name, &["IF"]));
+		if let Some(ref v) = self.ack {
+			write_sql!(f, fmt, " ACK {}", CoverStmts(v));
+		}
+		match self.comment {
+			AlterKind::Set(ref v) => write_sql!(f, fmt, " COMMENT {}", QuoteStr(v)),
+			AlterKind::Drop => f.push_str(" DROP COMMENT"),
+			AlterKind::None => {}
+		}
+	}
+}
+
+impl From<AlterConsumerStatement> for crate::expr::statements::alter::AlterConsumerStatement {
+	fn from(v: AlterConsumerStatement) -> Self {
+		crate::expr::statements::alter::AlterConsumerStatement {
+			name: v.name,
+			if_exists: v.if_exists,
+			ack: v.ack.map(Into::into),
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::alter::AlterConsumerStatement> for AlterConsumerStatement {
+	fn from(v: crate::expr::statements::alter::AlterConsumerStatement) -> Self {
+		AlterConsumerStatement {
+			name: v.name,
+			if_exists: v.if_exists,
+			ack: v.ack.map(Into::into),
+			comment: v.comment.into(),
+		}
+	}
+}
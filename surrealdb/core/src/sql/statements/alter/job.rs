@@ -0,0 +1,70 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::AlterKind;
+use crate::fmt::{CoverStmts, EscapeKwIdent, QuoteStr};
+use crate::sql::Expr;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+/// AST node for `ALTER JOB`.
+pub struct AlterJobStatement {
+	pub name: String,
+	pub if_exists: bool,
+	/// `Some(true)` for `PAUSE`, `Some(false)` for `RESUME`
+	pub paused: Option<bool>,
+	pub schedule: Option<String>,
+	pub then: Option<Expr>,
+	pub comment: AlterKind<String>,
+}
+
+impl ToSql for AlterJobStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "ALTER JOB");
+		if self.if_exists {
+			write_sql!(f, fmt, " IF EXISTS");
+		}
+		write_sql!(f, fmt, " {}", EscapeKwIdent(&self.name, &["IF"]));
+		match self.paused {
+			Some(true) => f.push_str(" PAUSE"),
+			Some(false) => f.push_str(" RESUME"),
+			None => {}
+		}
+		if let Some(ref v) = self.schedule {
+			write_sql!(f, fmt, " SCHEDULE {}", QuoteStr(v));
+		}
+		if let Some(ref v) = self.then {
+			write_sql!(f, fmt, " THEN {}", CoverStmts(v));
+		}
+		match self.comment {
+			AlterKind::Set(ref v) => write_sql!(f, fmt, " COMMENT {}", QuoteStr(v)),
+			AlterKind::Drop => f.push_str(" DROP COMMENT"),
+			AlterKind::None => {}
+		}
+	}
+}
+
+impl From<AlterJobStatement> for crate::expr::statements::alter::AlterJobStatement {
+	fn from(v: AlterJobStatement) -> Self {
+		crate::expr::statements::alter::AlterJobStatement {
+			name: v.name,
+			if_exists: v.if_exists,
+			paused: v.paused,
+			schedule: v.schedule,
+			then: v.then.map(Into::into),
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::alter::AlterJobStatement> for AlterJobStatement {
+	fn from(v: crate::expr::statements::alter::AlterJobStatement) -> Self {
+		AlterJobStatement {
+			name: v.name,
+			if_exists: v.if_exists,
+			paused: v.paused,
+			schedule: v.schedule,
+			then: v.then.map(Into::into),
+			comment: v.comment.into(),
+		}
+	}
+}
@@ -8,8 +8,10 @@ pub mod field;
 mod function;
 mod module;
 use surrealdb_types::{SqlFormat, ToSql};
+mod consumer;
 mod database;
 mod index;
+mod job;
 mod param;
 mod sequence;
 mod user;
@@ -23,11 +25,13 @@ pub use analyzer::AlterAnalyzerStatement;
 pub use api::{AlterApiClause, AlterApiStatement};
 pub use bucket::AlterBucketStatement;
 pub use config::AlterConfigStatement;
+pub use consumer::AlterConsumerStatement;
 pub use database::AlterDatabaseStatement;
 pub use event::AlterEventStatement;
 pub use field::AlterFieldStatement;
 pub use function::AlterFunctionStatement;
 pub use index::AlterIndexStatement;
+pub use job::AlterJobStatement;
 pub use module::AlterModuleStatement;
 pub use namespace::AlterNamespaceStatement;
 pub use param::AlterParamStatement;
@@ -88,6 +92,8 @@ pub enum AlterStatement {
 	Event(AlterEventStatement),
 	Index(AlterIndexStatement),
 	Sequence(AlterSequenceStatement),
+	Job(AlterJobStatement),
+	Consumer(AlterConsumerStatement),
 	Field(AlterFieldStatement),
 	Param(AlterParamStatement),
 	Bucket(AlterBucketStatement),
@@ -110,6 +116,8 @@ impl ToSql for AlterStatement {
 			Self::Event(v) => v.fmt_sql(f, fmt),
 			Self::Index(v) => v.fmt_sql(f, fmt),
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
+			Self::Job(v) => v.fmt_sql(f, fmt),
+			Self::Consumer(v) => v.fmt_sql(f, fmt),
 			Self::Field(v) => v.fmt_sql(f, fmt),
 			Self::Param(v) => v.fmt_sql(f, fmt),
 			Self::Bucket(v) => v.fmt_sql(f, fmt),
@@ -134,6 +142,8 @@ impl From<AlterStatement> for crate::expr::statements::AlterStatement {
 			AlterStatement::Event(v) => Self::Event(v.into()),
 			AlterStatement::Index(v) => Self::Index(v.into()),
 			AlterStatement::Sequence(v) => Self::Sequence(v.into()),
+			AlterStatement::Job(v) => Self::Job(v.into()),
+			AlterStatement::Consumer(v) => Self::Consumer(v.into()),
 			AlterStatement::Field(v) => Self::Field(v.into()),
 			AlterStatement::Param(v) => Self::Param(v.into()),
 			AlterStatement::Bucket(v) => Self::Bucket(v.into()),
@@ -158,6 +168,8 @@ impl From<crate::expr::statements::AlterStatement> for AlterStatement {
 			crate::expr::statements::AlterStatement::Event(v) => Self::Event(v.into()),
 			crate::expr::statements::AlterStatement::Index(v) => Self::Index(v.into()),
 			crate::expr::statements::AlterStatement::Sequence(v) => Self::Sequence(v.into()),
+			crate::expr::statements::AlterStatement::Job(v) => Self::Job(v.into()),
+			crate::expr::statements::AlterStatement::Consumer(v) => Self::Consumer(v.into()),
 			crate::expr::statements::AlterStatement::Field(v) => Self::Field(v.into()),
 			crate::expr::statements::AlterStatement::Param(v) => Self::Param(v.into()),
 			crate::expr::statements::AlterStatement::Bucket(v) => Self::Bucket(v.into()),
@@ -0,0 +1,37 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::EscapeKwFreeIdent;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AnalyzeStatement {
+	pub what: String,
+	pub sample: Option<u64>,
+}
+
+impl ToSql for AnalyzeStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "ANALYZE TABLE {}", EscapeKwFreeIdent(&self.what));
+		if let Some(sample) = self.sample {
+			write_sql!(f, fmt, " SAMPLE {sample}");
+		}
+	}
+}
+
+impl From<AnalyzeStatement> for crate::expr::statements::AnalyzeStatement {
+	fn from(v: AnalyzeStatement) -> Self {
+		Self {
+			table: v.what.into(),
+			sample: v.sample,
+		}
+	}
+}
+
+impl From<crate::expr::statements::AnalyzeStatement> for AnalyzeStatement {
+	fn from(v: crate::expr::statements::AnalyzeStatement) -> Self {
+		Self {
+			what: v.table.into_string(),
+			sample: v.sample,
+		}
+	}
+}
@@ -0,0 +1,58 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineConsumerStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub comment: Expr,
+}
+
+impl Default for DefineConsumerStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl ToSql for DefineConsumerStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push_str("DEFINE CONSUMER");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => f.push_str(" OVERWRITE"),
+			DefineKind::IfNotExists => f.push_str(" IF NOT EXISTS"),
+		}
+		write_sql!(f, fmt, " {}", CoverStmts(&self.name));
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineConsumerStatement> for crate::expr::statements::define::DefineConsumerStatement {
+	fn from(v: DefineConsumerStatement) -> Self {
+		crate::expr::statements::define::DefineConsumerStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineConsumerStatement> for DefineConsumerStatement {
+	fn from(v: crate::expr::statements::define::DefineConsumerStatement) -> Self {
+		DefineConsumerStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
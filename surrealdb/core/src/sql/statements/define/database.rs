@@ -1,6 +1,7 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
+use crate::catalog::{CapabilitiesDefinition, QuotaDefinition};
 use crate::fmt::CoverStmts;
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::{Expr, Literal};
@@ -14,6 +15,8 @@ pub(crate) struct DefineDatabaseStatement {
 	pub strict: bool,
 	pub comment: Expr,
 	pub changefeed: Option<ChangeFeed>,
+	pub capabilities: Option<CapabilitiesDefinition>,
+	pub quota: Option<QuotaDefinition>,
 }
 
 impl Default for DefineDatabaseStatement {
@@ -25,6 +28,8 @@ impl Default for DefineDatabaseStatement {
 			comment: Expr::Literal(Literal::None),
 			changefeed: None,
 			strict: false,
+			capabilities: None,
+			quota: None,
 		}
 	}
 }
@@ -47,6 +52,12 @@ impl ToSql for DefineDatabaseStatement {
 		if let Some(ref v) = self.changefeed {
 			write_sql!(f, sql_fmt, " {v}");
 		}
+		if let Some(ref v) = self.capabilities {
+			write_sql!(f, sql_fmt, " CAPABILITIES {v}");
+		}
+		if let Some(ref v) = self.quota {
+			write_sql!(f, sql_fmt, " QUOTA {v}");
+		}
 	}
 }
 
@@ -59,6 +70,8 @@ impl From<DefineDatabaseStatement> for crate::expr::statements::DefineDatabaseSt
 			comment: v.comment.into(),
 			changefeed: v.changefeed.map(Into::into),
 			strict: v.strict,
+			capabilities: v.capabilities,
+			quota: v.quota,
 		}
 	}
 }
@@ -73,6 +86,8 @@ impl From<crate::expr::statements::DefineDatabaseStatement> for DefineDatabaseSt
 			strict: v.strict,
 			comment: v.comment.into(),
 			changefeed: v.changefeed.map(Into::into),
+			capabilities: v.capabilities,
+			quota: v.quota,
 		}
 	}
 }
@@ -0,0 +1,61 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::{CoverStmts, QuoteStr};
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineJobStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub schedule: String,
+	pub paused: bool,
+	pub then: Expr,
+	pub comment: Expr,
+}
+
+impl ToSql for DefineJobStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push_str("DEFINE JOB");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => f.push_str(" OVERWRITE"),
+			DefineKind::IfNotExists => f.push_str(" IF NOT EXISTS"),
+		}
+		write_sql!(f, fmt, " {} SCHEDULE {}", CoverStmts(&self.name), QuoteStr(&self.schedule));
+		if self.paused {
+			f.push_str(" PAUSED");
+		}
+		write_sql!(f, fmt, " THEN {}", CoverStmts(&self.then));
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineJobStatement> for crate::expr::statements::define::DefineJobStatement {
+	fn from(v: DefineJobStatement) -> Self {
+		crate::expr::statements::define::DefineJobStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			schedule: v.schedule,
+			paused: v.paused,
+			then: v.then.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineJobStatement> for DefineJobStatement {
+	fn from(v: crate::expr::statements::define::DefineJobStatement) -> Self {
+		DefineJobStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			schedule: v.schedule,
+			paused: v.paused,
+			then: v.then.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
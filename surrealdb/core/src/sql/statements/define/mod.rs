@@ -3,16 +3,19 @@ mod analyzer;
 mod api;
 mod bucket;
 pub mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
 mod model;
 mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 pub mod user;
 
@@ -21,16 +24,19 @@ pub(crate) use analyzer::DefineAnalyzerStatement;
 pub(crate) use api::{ApiAction, DefineApiStatement};
 pub(crate) use bucket::DefineBucketStatement;
 pub(crate) use config::DefineConfigStatement;
+pub(crate) use consumer::DefineConsumerStatement;
 pub(crate) use database::DefineDatabaseStatement;
 pub(crate) use event::DefineEventStatement;
 pub(crate) use field::{DefineDefault, DefineFieldStatement};
 pub(crate) use function::DefineFunctionStatement;
 pub(crate) use index::DefineIndexStatement;
+pub(crate) use job::DefineJobStatement;
 pub(crate) use model::DefineModelStatement;
 pub(crate) use module::DefineModuleStatement;
 pub(crate) use namespace::DefineNamespaceStatement;
 pub(crate) use param::DefineParamStatement;
 pub(crate) use sequence::DefineSequenceStatement;
+pub(crate) use sink::DefineSinkStatement;
 use surrealdb_types::{SqlFormat, ToSql};
 pub(crate) use table::DefineTableStatement;
 pub(crate) use user::DefineUserStatement;
@@ -84,6 +90,9 @@ pub(crate) enum DefineStatement {
 	Api(DefineApiStatement),
 	Bucket(DefineBucketStatement),
 	Sequence(DefineSequenceStatement),
+	Job(DefineJobStatement),
+	Consumer(DefineConsumerStatement),
+	Sink(DefineSinkStatement),
 	#[cfg_attr(feature = "arbitrary", arbitrary(skip))]
 	Module(DefineModuleStatement),
 }
@@ -107,6 +116,9 @@ impl ToSql for DefineStatement {
 			Self::Api(v) => v.fmt_sql(f, fmt),
 			Self::Bucket(v) => v.fmt_sql(f, fmt),
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
+			Self::Job(v) => v.fmt_sql(f, fmt),
+			Self::Consumer(v) => v.fmt_sql(f, fmt),
+			Self::Sink(v) => v.fmt_sql(f, fmt),
 			Self::Module(v) => v.fmt_sql(f, fmt),
 		}
 	}
@@ -131,6 +143,9 @@ impl From<DefineStatement> for crate::expr::statements::DefineStatement {
 			DefineStatement::Api(v) => Self::Api(v.into()),
 			DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			DefineStatement::Sequence(v) => Self::Sequence(v.into()),
+			DefineStatement::Job(v) => Self::Job(v.into()),
+			DefineStatement::Consumer(v) => Self::Consumer(v.into()),
+			DefineStatement::Sink(v) => Self::Sink(v.into()),
 			DefineStatement::Module(v) => Self::Module(v.into()),
 		}
 	}
@@ -155,6 +170,9 @@ impl From<crate::expr::statements::DefineStatement> for DefineStatement {
 			crate::expr::statements::DefineStatement::Api(v) => Self::Api(v.into()),
 			crate::expr::statements::DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			crate::expr::statements::DefineStatement::Sequence(v) => Self::Sequence(v.into()),
+			crate::expr::statements::DefineStatement::Job(v) => Self::Job(v.into()),
+			crate::expr::statements::DefineStatement::Consumer(v) => Self::Consumer(v.into()),
+			crate::expr::statements::DefineStatement::Sink(v) => Self::Sink(v.into()),
 			crate::expr::statements::DefineStatement::Module(v) => Self::Module(v.into()),
 		}
 	}
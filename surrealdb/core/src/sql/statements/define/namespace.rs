@@ -1,6 +1,7 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
+use crate::catalog::{CapabilitiesDefinition, QuotaDefinition};
 use crate::fmt::CoverStmts;
 use crate::sql::{Expr, Literal};
 
@@ -11,6 +12,8 @@ pub struct DefineNamespaceStatement {
 	pub id: Option<u32>,
 	pub name: Expr,
 	pub comment: Expr,
+	pub capabilities: Option<CapabilitiesDefinition>,
+	pub quota: Option<QuotaDefinition>,
 }
 
 impl Default for DefineNamespaceStatement {
@@ -20,6 +23,8 @@ impl Default for DefineNamespaceStatement {
 			id: None,
 			name: Expr::Literal(Literal::None),
 			comment: Expr::Literal(Literal::None),
+			capabilities: None,
+			quota: None,
 		}
 	}
 }
@@ -36,6 +41,12 @@ impl ToSql for DefineNamespaceStatement {
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
 			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
 		}
+		if let Some(ref v) = self.capabilities {
+			write_sql!(f, sql_fmt, " CAPABILITIES {v}");
+		}
+		if let Some(ref v) = self.quota {
+			write_sql!(f, sql_fmt, " QUOTA {v}");
+		}
 	}
 }
 
@@ -46,6 +57,8 @@ impl From<DefineNamespaceStatement> for crate::expr::statements::DefineNamespace
 			id: v.id,
 			name: v.name.into(),
 			comment: v.comment.into(),
+			capabilities: v.capabilities,
+			quota: v.quota,
 		}
 	}
 }
@@ -58,6 +71,8 @@ impl From<crate::expr::statements::DefineNamespaceStatement> for DefineNamespace
 			id: v.id,
 			name: v.name.into(),
 			comment: v.comment.into(),
+			capabilities: v.capabilities,
+			quota: v.quota,
 		}
 	}
 }
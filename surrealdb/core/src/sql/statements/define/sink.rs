@@ -0,0 +1,74 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::catalog::SinkTarget;
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineSinkStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub target: SinkTarget,
+	pub comment: Expr,
+}
+
+impl Default for DefineSinkStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			target_table: Expr::Literal(Literal::None),
+			target: SinkTarget::Stream,
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl ToSql for DefineSinkStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push_str("DEFINE SINK");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => f.push_str(" OVERWRITE"),
+			DefineKind::IfNotExists => f.push_str(" IF NOT EXISTS"),
+		}
+		write_sql!(
+			f,
+			fmt,
+			" {} ON {} TO {}",
+			CoverStmts(&self.name),
+			CoverStmts(&self.target_table),
+			self.target
+		);
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineSinkStatement> for crate::expr::statements::define::DefineSinkStatement {
+	fn from(v: DefineSinkStatement) -> Self {
+		crate::expr::statements::define::DefineSinkStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			target: v.target,
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineSinkStatement> for DefineSinkStatement {
+	fn from(v: crate::expr::statements::define::DefineSinkStatement) -> Self {
+		DefineSinkStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			target: v.target,
+			comment: v.comment.into(),
+		}
+	}
+}
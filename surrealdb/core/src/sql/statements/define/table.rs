@@ -3,7 +3,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use super::DefineKind;
 use crate::fmt::{CoverStmts, EscapeKwFreeIdent};
 use crate::sql::changefeed::ChangeFeed;
-use crate::sql::{Expr, Literal, Permissions, TableType, View};
+use crate::sql::{Expr, Literal, Permissions, TableType, Ttl, View};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -16,6 +16,7 @@ pub(crate) struct DefineTableStatement {
 	pub view: Option<View>,
 	pub permissions: Permissions,
 	pub changefeed: Option<ChangeFeed>,
+	pub ttl: Option<Ttl>,
 	pub comment: Expr,
 	pub table_type: TableType,
 }
@@ -31,6 +32,7 @@ impl Default for DefineTableStatement {
 			view: None,
 			permissions: Permissions::none(),
 			changefeed: None,
+			ttl: None,
 			comment: Expr::Literal(Literal::None),
 			table_type: TableType::default(),
 		}
@@ -92,6 +94,9 @@ impl ToSql for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write_sql!(f, sql_fmt, " {}", v);
 		}
+		if let Some(ref v) = self.ttl {
+			write_sql!(f, sql_fmt, " {}", v);
+		}
 		if sql_fmt.is_pretty() {
 			f.push('\n');
 			let inner_fmt = sql_fmt.increment();
@@ -114,6 +119,7 @@ impl From<DefineTableStatement> for crate::expr::statements::DefineTableStatemen
 			view: v.view.map(Into::into),
 			permissions: v.permissions.into(),
 			changefeed: v.changefeed.map(Into::into),
+			ttl: v.ttl.map(Into::into),
 			comment: v.comment.into(),
 			table_type: v.table_type.into(),
 		}
@@ -132,6 +138,7 @@ impl From<crate::expr::statements::DefineTableStatement> for DefineTableStatemen
 			view: v.view.map(Into::into),
 			permissions: v.permissions.into(),
 			changefeed: v.changefeed.map(Into::into),
+			ttl: v.ttl.map(Into::into),
 			comment: v.comment.into(),
 			table_type: v.table_type.into(),
 		}
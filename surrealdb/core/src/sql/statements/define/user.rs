@@ -6,6 +6,8 @@ use rand::rngs::OsRng;
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
+use crate::catalog::QuotaDefinition;
+use crate::expr::user::UserTotp;
 use crate::fmt::{CoverStmts, EscapeKwFreeIdent, QuoteStr};
 use crate::sql::{Base, Expr, Literal};
 
@@ -18,6 +20,20 @@ pub enum PassType {
 	Password(String),
 }
 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum TotpType {
+	#[default]
+	Unset,
+	/// Enrol the user with a newly generated secret and recovery codes
+	Generate,
+	/// An existing secret, along with the hashes of the unused recovery codes
+	Secret {
+		secret: String,
+		recovery: Vec<String>,
+	},
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct DefineUserStatement {
 	pub kind: DefineKind,
@@ -29,6 +45,8 @@ pub(crate) struct DefineUserStatement {
 	pub session_duration: Expr,
 
 	pub comment: Expr,
+	pub quota: Option<QuotaDefinition>,
+	pub totp: TotpType,
 }
 
 impl Default for DefineUserStatement {
@@ -42,6 +60,8 @@ impl Default for DefineUserStatement {
 			token_duration: Expr::Literal(Literal::None),
 			session_duration: Expr::Literal(Literal::None),
 			comment: Expr::Literal(Literal::None),
+			quota: None,
+			totp: TotpType::Unset,
 		}
 	}
 }
@@ -63,6 +83,24 @@ impl ToSql for DefineUserStatement {
 			PassType::Password(ref x) => write_sql!(f, fmt, " PASSWORD {}", QuoteStr(x)),
 		}
 
+		match self.totp {
+			TotpType::Unset => {}
+			TotpType::Generate => write_sql!(f, fmt, " TOTP"),
+			TotpType::Secret {
+				ref secret,
+				ref recovery,
+			} => {
+				write_sql!(f, fmt, " TOTP SECRET {} RECOVERY [", QuoteStr(secret));
+				for (idx, r) in recovery.iter().enumerate() {
+					if idx != 0 {
+						f.push_str(", ");
+					}
+					QuoteStr(r).fmt_sql(f, fmt);
+				}
+				f.push(']');
+			}
+		}
+
 		write_sql!(f, fmt, " ROLES ");
 		for (idx, r) in self.roles.iter().enumerate() {
 			if idx != 0 {
@@ -83,6 +121,9 @@ impl ToSql for DefineUserStatement {
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
 			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
 		}
+		if let Some(ref v) = self.quota {
+			write_sql!(f, fmt, " QUOTA {v}");
+		}
 	}
 }
 
@@ -117,6 +158,18 @@ impl From<DefineUserStatement> for crate::expr::statements::DefineUserStatement
 				session: v.session_duration.into(),
 			},
 			comment: v.comment.into(),
+			quota: v.quota,
+			totp: match v.totp {
+				TotpType::Unset => UserTotp::Unset,
+				TotpType::Generate => UserTotp::Generate,
+				TotpType::Secret {
+					secret,
+					recovery,
+				} => UserTotp::Secret {
+					secret,
+					recovery,
+				},
+			},
 		}
 	}
 }
@@ -132,6 +185,18 @@ impl From<crate::expr::statements::DefineUserStatement> for DefineUserStatement
 			token_duration: v.duration.token.into(),
 			session_duration: v.duration.session.into(),
 			comment: v.comment.into(),
+			quota: v.quota,
+			totp: match v.totp {
+				UserTotp::Unset => TotpType::Unset,
+				UserTotp::Generate => TotpType::Generate,
+				UserTotp::Secret {
+					secret,
+					recovery,
+				} => TotpType::Secret {
+					secret,
+					recovery,
+				},
+			},
 		}
 	}
 }
@@ -14,6 +14,7 @@ pub enum InfoStatement {
 	Tb(Expr, bool, Option<Expr>),
 	User(Expr, Option<Base>, bool),
 	Index(Expr, Expr, bool),
+	Api,
 }
 
 impl ToSql for InfoStatement {
@@ -95,6 +96,7 @@ impl ToSql for InfoStatement {
 					CoverStmts(t)
 				)
 			}
+			Self::Api => f.push_str("INFO FOR API"),
 		}
 	}
 }
@@ -108,6 +110,7 @@ impl From<InfoStatement> for crate::expr::statements::InfoStatement {
 			InfoStatement::Tb(t, v, ver) => Self::Tb(t.into(), v, ver.map(From::from)),
 			InfoStatement::User(u, b, v) => Self::User(u.into(), b.map(Into::into), v),
 			InfoStatement::Index(i, t, v) => Self::Index(i.into(), t.into(), v),
+			InfoStatement::Api => Self::Api,
 		}
 	}
 }
@@ -129,6 +132,7 @@ impl From<crate::expr::statements::InfoStatement> for InfoStatement {
 			crate::expr::statements::InfoStatement::Index(i, t, v) => {
 				Self::Index(i.into(), t.into(), v)
 			}
+			crate::expr::statements::InfoStatement::Api => Self::Api,
 		}
 	}
 }
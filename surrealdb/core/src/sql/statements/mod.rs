@@ -1,5 +1,6 @@
 pub(crate) mod access;
 pub(crate) mod alter;
+pub(crate) mod analyze;
 pub(crate) mod create;
 pub(crate) mod define;
 pub(crate) mod delete;
@@ -24,6 +25,7 @@ pub(crate) mod r#use;
 
 pub(crate) use self::access::AccessStatement;
 pub(crate) use self::alter::{AlterStatement, AlterTableStatement};
+pub(crate) use self::analyze::AnalyzeStatement;
 pub(crate) use self::create::CreateStatement;
 pub(crate) use self::define::{
 	DefineApiStatement, DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
@@ -4,16 +4,19 @@ mod analyzer;
 mod api;
 mod bucket;
 mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
 mod model;
 mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 mod user;
 
@@ -22,16 +25,19 @@ pub(crate) use analyzer::RemoveAnalyzerStatement;
 pub(crate) use api::RemoveApiStatement;
 pub(crate) use bucket::RemoveBucketStatement;
 pub(crate) use config::{RemoveConfigKind, RemoveConfigStatement};
+pub(crate) use consumer::RemoveConsumerStatement;
 pub(crate) use database::RemoveDatabaseStatement;
 pub(crate) use event::RemoveEventStatement;
 pub(crate) use field::RemoveFieldStatement;
 pub(crate) use function::RemoveFunctionStatement;
 pub(crate) use index::RemoveIndexStatement;
+pub(crate) use job::RemoveJobStatement;
 pub(crate) use model::RemoveModelStatement;
 pub(crate) use module::RemoveModuleStatement;
 pub(crate) use namespace::RemoveNamespaceStatement;
 pub(crate) use param::RemoveParamStatement;
 pub(crate) use sequence::RemoveSequenceStatement;
+pub(crate) use sink::RemoveSinkStatement;
 pub(crate) use table::RemoveTableStatement;
 pub(crate) use user::RemoveUserStatement;
 
@@ -54,6 +60,9 @@ pub(crate) enum RemoveStatement {
 	Api(RemoveApiStatement),
 	Bucket(RemoveBucketStatement),
 	Sequence(RemoveSequenceStatement),
+	Job(RemoveJobStatement),
+	Consumer(RemoveConsumerStatement),
+	Sink(RemoveSinkStatement),
 	Module(RemoveModuleStatement),
 	Config(RemoveConfigStatement),
 }
@@ -76,6 +85,9 @@ impl ToSql for RemoveStatement {
 			Self::Api(v) => v.fmt_sql(f, fmt),
 			Self::Bucket(v) => v.fmt_sql(f, fmt),
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
+			Self::Job(v) => v.fmt_sql(f, fmt),
+			Self::Consumer(v) => v.fmt_sql(f, fmt),
+			Self::Sink(v) => v.fmt_sql(f, fmt),
 			Self::Module(v) => v.fmt_sql(f, fmt),
 			Self::Config(v) => v.fmt_sql(f, fmt),
 		}
@@ -100,6 +112,9 @@ impl From<RemoveStatement> for crate::expr::statements::RemoveStatement {
 			RemoveStatement::Api(v) => Self::Api(v.into()),
 			RemoveStatement::Bucket(v) => Self::Bucket(v.into()),
 			RemoveStatement::Sequence(v) => Self::Sequence(v.into()),
+			RemoveStatement::Job(v) => Self::Job(v.into()),
+			RemoveStatement::Consumer(v) => Self::Consumer(v.into()),
+			RemoveStatement::Sink(v) => Self::Sink(v.into()),
 			RemoveStatement::Module(v) => Self::Module(v.into()),
 			RemoveStatement::Config(v) => Self::Config(v.into()),
 		}
@@ -124,6 +139,9 @@ impl From<crate::expr::statements::RemoveStatement> for RemoveStatement {
 			crate::expr::statements::RemoveStatement::Api(v) => Self::Api(v.into()),
 			crate::expr::statements::RemoveStatement::Bucket(v) => Self::Bucket(v.into()),
 			crate::expr::statements::RemoveStatement::Sequence(v) => Self::Sequence(v.into()),
+			crate::expr::statements::RemoveStatement::Job(v) => Self::Job(v.into()),
+			crate::expr::statements::RemoveStatement::Consumer(v) => Self::Consumer(v.into()),
+			crate::expr::statements::RemoveStatement::Sink(v) => Self::Sink(v.into()),
 			crate::expr::statements::RemoveStatement::Module(v) => Self::Module(v.into()),
 			crate::expr::statements::RemoveStatement::Config(v) => Self::Config(v.into()),
 		}
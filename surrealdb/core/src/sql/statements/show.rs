@@ -9,6 +9,7 @@ use crate::val::TableName;
 pub enum ShowSince {
 	Timestamp(PublicDatetime),
 	Versionstamp(u64),
+	Consumer(String),
 }
 
 impl From<ShowSince> for crate::expr::statements::show::ShowSince {
@@ -16,6 +17,7 @@ impl From<ShowSince> for crate::expr::statements::show::ShowSince {
 		match v {
 			ShowSince::Timestamp(v) => Self::Timestamp(v.into()),
 			ShowSince::Versionstamp(v) => Self::Versionstamp(v),
+			ShowSince::Consumer(v) => Self::Consumer(v),
 		}
 	}
 }
@@ -27,6 +29,7 @@ impl From<crate::expr::statements::show::ShowSince> for ShowSince {
 				ShowSince::Timestamp(v.into())
 			}
 			crate::expr::statements::show::ShowSince::Versionstamp(v) => ShowSince::Versionstamp(v),
+			crate::expr::statements::show::ShowSince::Consumer(v) => ShowSince::Consumer(v),
 		}
 	}
 }
@@ -50,6 +53,9 @@ impl ToSql for ShowStatement {
 		match self.since {
 			ShowSince::Timestamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
 			ShowSince::Versionstamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
+			ShowSince::Consumer(ref v) => {
+				write_sql!(f, fmt, " SINCE CONSUMER {}", EscapeKwFreeIdent(v))
+			}
 		}
 		if let Some(ref v) = self.limit {
 			write_sql!(f, fmt, " LIMIT {}", v)
@@ -9,11 +9,11 @@ use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::rebuild::RebuildIndexStatement;
 use crate::sql::statements::show::ShowSince;
 use crate::sql::statements::{
-	AccessStatement, AlterStatement, AlterTableStatement, CreateStatement, DefineStatement,
-	DefineTableStatement, DeleteStatement, ForeachStatement, IfelseStatement, InfoStatement,
-	InsertStatement, OptionStatement, OutputStatement, RebuildStatement, RelateStatement,
-	RemoveStatement, RemoveTableStatement, SelectStatement, SetStatement, ShowStatement,
-	SleepStatement, UpdateStatement, UpsertStatement, UseStatement,
+	AccessStatement, AlterStatement, AlterTableStatement, AnalyzeStatement, CreateStatement,
+	DefineStatement, DefineTableStatement, DeleteStatement, ForeachStatement, IfelseStatement,
+	InfoStatement, InsertStatement, OptionStatement, OutputStatement, RebuildStatement,
+	RelateStatement, RemoveStatement, RemoveTableStatement, SelectStatement, SetStatement,
+	ShowStatement, SleepStatement, UpdateStatement, UpsertStatement, UseStatement,
 };
 use crate::sql::{
 	BinaryOperator, Block, Closure, Constant, Data, Expr, Fields, Function, FunctionCall, Idiom,
@@ -174,6 +174,8 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
 #[case::expr_let(Expr::Let(Box::new(SetStatement { name: "x".to_string(), what: Expr::Literal(Literal::Integer(5)), kind: None })), "LET $x = 5", "LET $x = 5")]
 // Expression: Sleep
 #[case::expr_sleep(Expr::Sleep(Box::new(SleepStatement { duration: PublicDuration::from(Duration::from_secs(1)) })), "SLEEP 1s", "SLEEP 1s")]
+// Expression: Analyze
+#[case::expr_analyze(Expr::Analyze(Box::new(AnalyzeStatement { what: "person".to_string(), sample: Some(100) })), "ANALYZE TABLE person SAMPLE 100", "ANALYZE TABLE person SAMPLE 100")]
 // Complex nested expressions
 #[case::nested_if_else(
     Expr::IfElse(Box::new(IfelseStatement {
@@ -316,6 +318,9 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
 #[case::top_level_begin(TopLevelExpr::Begin, "BEGIN", "BEGIN")]
 #[case::top_level_cancel(TopLevelExpr::Cancel, "CANCEL", "CANCEL")]
 #[case::top_level_commit(TopLevelExpr::Commit, "COMMIT", "COMMIT")]
+#[case::top_level_savepoint(TopLevelExpr::Savepoint("before".to_string()), "SAVEPOINT before", "SAVEPOINT before")]
+#[case::top_level_rollback_to(TopLevelExpr::RollbackTo("before".to_string()), "ROLLBACK TO before", "ROLLBACK TO before")]
+#[case::top_level_release(TopLevelExpr::Release("before".to_string()), "RELEASE before", "RELEASE before")]
 #[case::top_level_access(TopLevelExpr::Access(Box::new(AccessStatement::Grant(
     AccessStatementGrant {
         ac: "user".to_string(),
@@ -3,6 +3,8 @@ use std::fmt::Display;
 
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
+use crate::fmt::QuoteStr;
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Tokenizer {
@@ -10,32 +12,31 @@ pub enum Tokenizer {
 	Camel,
 	Class,
 	Punct,
+	Unicode,
+	Cjk(Option<String>),
+	Regex(String),
+	Keyword,
 }
 
 impl Display for Tokenizer {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str(match self {
-			Self::Blank => "BLANK",
-			Self::Camel => "CAMEL",
-			Self::Class => "CLASS",
-			Self::Punct => "PUNCT",
-		})
+		f.write_str(&self.to_sql())
 	}
 }
 
 impl ToSql for Tokenizer {
 	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
-		write_sql!(
-			f,
-			sql_fmt,
-			"{}",
-			match self {
-				Self::Blank => "BLANK",
-				Self::Camel => "CAMEL",
-				Self::Class => "CLASS",
-				Self::Punct => "PUNCT",
-			}
-		)
+		match self {
+			Self::Blank => f.push_str("BLANK"),
+			Self::Camel => f.push_str("CAMEL"),
+			Self::Class => f.push_str("CLASS"),
+			Self::Punct => f.push_str("PUNCT"),
+			Self::Unicode => f.push_str("UNICODE"),
+			Self::Cjk(None) => f.push_str("CJK"),
+			Self::Cjk(Some(path)) => write_sql!(f, sql_fmt, "CJK({})", QuoteStr(path)),
+			Self::Regex(pattern) => write_sql!(f, sql_fmt, "REGEX({})", QuoteStr(pattern)),
+			Self::Keyword => f.push_str("KEYWORD"),
+		}
 	}
 }
 
@@ -46,6 +47,10 @@ impl From<Tokenizer> for crate::expr::Tokenizer {
 			Tokenizer::Camel => Self::Camel,
 			Tokenizer::Class => Self::Class,
 			Tokenizer::Punct => Self::Punct,
+			Tokenizer::Unicode => Self::Unicode,
+			Tokenizer::Cjk(path) => Self::Cjk(path),
+			Tokenizer::Regex(pattern) => Self::Regex(pattern),
+			Tokenizer::Keyword => Self::Keyword,
 		}
 	}
 }
@@ -57,6 +62,10 @@ impl From<crate::expr::Tokenizer> for Tokenizer {
 			crate::expr::Tokenizer::Camel => Self::Camel,
 			crate::expr::Tokenizer::Class => Self::Class,
 			crate::expr::Tokenizer::Punct => Self::Punct,
+			crate::expr::Tokenizer::Unicode => Self::Unicode,
+			crate::expr::Tokenizer::Cjk(path) => Self::Cjk(path),
+			crate::expr::Tokenizer::Regex(pattern) => Self::Regex(pattern),
+			crate::expr::Tokenizer::Keyword => Self::Keyword,
 		}
 	}
 }
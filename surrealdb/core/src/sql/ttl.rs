@@ -0,0 +1,44 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::sql::Idiom;
+use crate::types::PublicDuration;
+
+/// The time-to-live of the records in a table
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) enum Ttl {
+	/// Records expire this long after they were last written
+	Duration(PublicDuration),
+	/// Records expire at the datetime held in this field
+	Field(
+		#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::sql::arbitrary::basic_idiom))]
+		Idiom,
+	),
+}
+
+impl ToSql for Ttl {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		match self {
+			Self::Duration(v) => write_sql!(f, sql_fmt, "TTL {}", v),
+			Self::Field(v) => write_sql!(f, sql_fmt, "TTL {}", v),
+		}
+	}
+}
+
+impl From<Ttl> for crate::expr::Ttl {
+	fn from(v: Ttl) -> Self {
+		match v {
+			Ttl::Duration(v) => crate::expr::Ttl::Duration(v.into()),
+			Ttl::Field(v) => crate::expr::Ttl::Field(v.into()),
+		}
+	}
+}
+
+impl From<crate::expr::Ttl> for Ttl {
+	fn from(v: crate::expr::Ttl) -> Self {
+		match v {
+			crate::expr::Ttl::Duration(v) => Ttl::Duration(v.into()),
+			crate::expr::Ttl::Field(v) => Ttl::Field(v.into()),
+		}
+	}
+}
@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use crate::fmt::{EscapeKwFreeIdent, Fmt};
@@ -10,6 +12,7 @@ pub enum With {
 		#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::sql::arbitrary::atleast_one))]
 		Vec<String>,
 	),
+	Fusion(Fusion),
 }
 
 impl ToSql for With {
@@ -26,6 +29,7 @@ impl ToSql for With {
 					Fmt::comma_separated(i.iter().map(|x| EscapeKwFreeIdent(x)))
 				);
 			}
+			With::Fusion(fusion) => write_sql!(f, fmt, " FUSION {}", fusion),
 		}
 	}
 }
@@ -35,6 +39,7 @@ impl From<With> for crate::expr::With {
 		match v {
 			With::NoIndex => Self::NoIndex,
 			With::Index(i) => Self::Index(i),
+			With::Fusion(fusion) => Self::Fusion(fusion.into()),
 		}
 	}
 }
@@ -43,6 +48,143 @@ impl From<crate::expr::With> for With {
 		match v {
 			crate::expr::With::NoIndex => Self::NoIndex,
 			crate::expr::With::Index(i) => Self::Index(i),
+			crate::expr::With::Fusion(fusion) => Self::Fusion(fusion.into()),
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Fusion {
+	Rrf(u32),
+	Linear {
+		vector: f32,
+		text: f32,
+		norm: FusionNorm,
+	},
+}
+
+impl PartialEq for Fusion {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Fusion::Rrf(a), Fusion::Rrf(b)) => a == b,
+			(
+				Fusion::Linear {
+					vector,
+					text,
+					norm,
+				},
+				Fusion::Linear {
+					vector: other_vector,
+					text: other_text,
+					norm: other_norm,
+				},
+			) => {
+				vector.to_bits() == other_vector.to_bits()
+					&& text.to_bits() == other_text.to_bits()
+					&& norm == other_norm
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Eq for Fusion {}
+
+impl Hash for Fusion {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		match self {
+			Fusion::Rrf(k) => k.hash(state),
+			Fusion::Linear {
+				vector,
+				text,
+				norm,
+			} => {
+				vector.to_bits().hash(state);
+				text.to_bits().hash(state);
+				norm.hash(state);
+			}
+		}
+	}
+}
+
+impl ToSql for Fusion {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			Self::Rrf(k) => write_sql!(f, fmt, "RRF({k})"),
+			Self::Linear {
+				vector,
+				text,
+				norm,
+			} => write_sql!(f, fmt, "LINEAR({vector},{text},{norm})"),
+		}
+	}
+}
+
+impl From<Fusion> for crate::expr::with::Fusion {
+	fn from(v: Fusion) -> Self {
+		match v {
+			Fusion::Rrf(k) => Self::Rrf(k),
+			Fusion::Linear {
+				vector,
+				text,
+				norm,
+			} => Self::Linear {
+				vector,
+				text,
+				norm: norm.into(),
+			},
+		}
+	}
+}
+
+impl From<crate::expr::with::Fusion> for Fusion {
+	fn from(v: crate::expr::with::Fusion) -> Self {
+		match v {
+			crate::expr::with::Fusion::Rrf(k) => Self::Rrf(k),
+			crate::expr::with::Fusion::Linear {
+				vector,
+				text,
+				norm,
+			} => Self::Linear {
+				vector,
+				text,
+				norm: norm.into(),
+			},
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum FusionNorm {
+	MinMax,
+	ZScore,
+}
+
+impl ToSql for FusionNorm {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			Self::MinMax => write_sql!(f, fmt, "MINMAX"),
+			Self::ZScore => write_sql!(f, fmt, "ZSCORE"),
+		}
+	}
+}
+
+impl From<FusionNorm> for crate::expr::with::FusionNorm {
+	fn from(v: FusionNorm) -> Self {
+		match v {
+			FusionNorm::MinMax => Self::MinMax,
+			FusionNorm::ZScore => Self::ZScore,
+		}
+	}
+}
+
+impl From<crate::expr::with::FusionNorm> for FusionNorm {
+	fn from(v: crate::expr::with::FusionNorm) -> Self {
+		match v {
+			crate::expr::with::FusionNorm::MinMax => Self::MinMax,
+			crate::expr::with::FusionNorm::ZScore => Self::ZScore,
 		}
 	}
 }
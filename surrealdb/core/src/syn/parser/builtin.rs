@@ -23,13 +23,20 @@ pub(crate) static PATHS: phf::Map<
 	UniCase<&'static str>,
 	(PathKind, Option<UniCase<&'static str>>),
 > = phf_map! {
+		UniCase::ascii("api::cors") => (PathKind::Function, None),
 		UniCase::ascii("api::invoke") => (PathKind::Function, None),
+		UniCase::ascii("api::rate_limit") => (PathKind::Function, None),
 		UniCase::ascii("api::timeout") => (PathKind::Function, None),
+		UniCase::ascii("api::auth::required") => (PathKind::Function, None),
+		UniCase::ascii("api::auth::role") => (PathKind::Function, None),
 		UniCase::ascii("api::req::body") => (PathKind::Function, None),
+		UniCase::ascii("api::req::max_size") => (PathKind::Function, None),
+		UniCase::ascii("api::req::validate") => (PathKind::Function, None),
 		UniCase::ascii("api::res::body") => (PathKind::Function, None),
 		UniCase::ascii("api::res::status") => (PathKind::Function, None),
 		UniCase::ascii("api::res::header") => (PathKind::Function, None),
 		UniCase::ascii("api::res::headers") => (PathKind::Function, None),
+		UniCase::ascii("api::res::cache") => (PathKind::Function, None),
 		//
 		UniCase::ascii("array::add") => (PathKind::Function, None),
 		UniCase::ascii("array::all") => (PathKind::Function, None),
@@ -112,6 +119,11 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("crypto::pbkdf2::generate") => (PathKind::Function, None),
 		UniCase::ascii("crypto::scrypt::compare") => (PathKind::Function, None),
 		UniCase::ascii("crypto::scrypt::generate") => (PathKind::Function, None),
+		UniCase::ascii("crypto::totp::code") => (PathKind::Function, None),
+		UniCase::ascii("crypto::totp::secret") => (PathKind::Function, None),
+		UniCase::ascii("crypto::totp::step") => (PathKind::Function, None),
+		UniCase::ascii("crypto::totp::uri") => (PathKind::Function, None),
+		UniCase::ascii("crypto::totp::verify") => (PathKind::Function, None),
 		//
 		UniCase::ascii("duration::days") => (PathKind::Function, None),
 		UniCase::ascii("duration::hours") => (PathKind::Function, None),
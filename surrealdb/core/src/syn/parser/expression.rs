@@ -5,7 +5,9 @@ use surrealdb_types::ToSql;
 
 use super::enter_query_recursion;
 use super::mac::unexpected;
-use crate::sql::operator::{BindingPower, BooleanOperator, MatchesOperator, NearestNeighbor};
+use crate::sql::operator::{
+	BindingPower, BooleanOperator, Fuzziness, MatchesOperator, NearestNeighbor,
+};
 use crate::sql::{BinaryOperator, Expr, Literal, Part, PostfixOperator, PrefixOperator};
 use crate::syn::error::bail;
 use crate::syn::lexer::compound::Numeric;
@@ -536,52 +538,56 @@ impl Parser<'_> {
 	}
 
 	fn parse_matches(&mut self) -> ParseResult<MatchesOperator> {
-		let peek = self.peek();
-		match peek.kind {
-			TokenKind::Digits => {
-				let number = self.next_token_value()?;
-				let op = if self.eat(t!(",")) {
-					let peek = self.next();
-					let op = match peek.kind {
-						t!("AND") => BooleanOperator::And,
-						t!("OR") => BooleanOperator::Or,
-						_ => unexpected!(self, peek, "either `AND` or `OR`"),
-					};
-					Some(op)
+		let mut op = MatchesOperator {
+			rf: None,
+			operator: None,
+			fuzzy: None,
+			prefix: false,
+		};
+		if self.eat(t!("@")) {
+			return Ok(op);
+		}
+		if let TokenKind::Digits = self.peek().kind {
+			op.rf = Some(self.next_token_value()?);
+		} else {
+			self.parse_matches_option(&mut op)?;
+		}
+		while self.eat(t!(",")) {
+			self.parse_matches_option(&mut op)?;
+		}
+		expected!(self, t!("@"));
+		Ok(op)
+	}
+
+	/// Parses an option of the MATCHES operator: the boolean operator, or the
+	/// `FUZZY` and `PREFIX` term expansions, which are not reserved keywords.
+	fn parse_matches_option(&mut self, op: &mut MatchesOperator) -> ParseResult<()> {
+		let next = self.next();
+		match next.kind {
+			t!("AND") if op.operator.is_none() => op.operator = Some(BooleanOperator::And),
+			t!("OR") if op.operator.is_none() => op.operator = Some(BooleanOperator::Or),
+			TokenKind::Identifier
+				if op.fuzzy.is_none() && self.span_str(next.span).eq_ignore_ascii_case("FUZZY") =>
+			{
+				op.fuzzy = Some(if self.eat(t!("(")) {
+					let distance: u8 = self.next_token_value()?;
+					if distance > 2 {
+						bail!("The FUZZY distance must be at most 2", @self.last_span());
+					}
+					self.expect_closing_delimiter(t!(")"), next.span)?;
+					Fuzziness::Distance(distance)
 				} else {
-					None
-				};
-				expected!(self, t!("@"));
-				Ok(MatchesOperator {
-					operator: op,
-					rf: Some(number),
-				})
-			}
-			t!("AND") => {
-				self.pop_peek();
-				expected!(self, t!("@"));
-				Ok(MatchesOperator {
-					operator: Some(BooleanOperator::And),
-					rf: None,
-				})
+					Fuzziness::Auto
+				});
 			}
-			t!("OR") => {
-				self.pop_peek();
-				expected!(self, t!("@"));
-				Ok(MatchesOperator {
-					operator: Some(BooleanOperator::Or),
-					rf: None,
-				})
-			}
-			t!("@") => {
-				self.pop_peek();
-				Ok(MatchesOperator {
-					operator: None,
-					rf: None,
-				})
+			TokenKind::Identifier
+				if !op.prefix && self.span_str(next.span).eq_ignore_ascii_case("PREFIX") =>
+			{
+				op.prefix = true;
 			}
-			_ => unexpected!(self, peek, "a match reference, operator or `@`"),
+			_ => unexpected!(self, next, "`AND`, `OR`, `FUZZY` or `PREFIX`"),
 		}
+		Ok(())
 	}
 
 	async fn parse_postfix(
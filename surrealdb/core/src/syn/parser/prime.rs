@@ -260,6 +260,14 @@ impl Parser<'_> {
 				let stmt = self.parse_rebuild_stmt()?;
 				Expr::Rebuild(Box::new(stmt))
 			}
+			TokenKind::Identifier
+				if self.peek1().kind == t!("TABLE")
+					&& self.span_str(token.span).eq_ignore_ascii_case("ANALYZE") =>
+			{
+				self.pop_peek();
+				let stmt = self.parse_analyze_stmt()?;
+				Expr::Analyze(Box::new(stmt))
+			}
 			t!("ALTER") => {
 				self.pop_peek();
 				let stmt = self.parse_alter_stmt(stk).await?;
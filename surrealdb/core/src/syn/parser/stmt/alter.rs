@@ -2,18 +2,16 @@ use reblessive::Stk;
 
 use crate::catalog::{ApiMethod, EventDefinition, EventKind};
 use crate::sql::TableType;
-use crate::sql::filter::Filter;
 use crate::sql::statements::alter::field::AlterDefault;
 use crate::sql::statements::alter::{
 	AlterAccessStatement, AlterAnalyzerStatement, AlterApiClause, AlterApiStatement,
-	AlterBucketStatement, AlterConfigStatement, AlterDatabaseStatement, AlterEventStatement,
-	AlterFieldStatement, AlterFunctionStatement, AlterIndexStatement, AlterKind,
-	AlterModuleStatement, AlterNamespaceStatement, AlterParamStatement, AlterSequenceStatement,
-	AlterSystemStatement, AlterUserStatement,
+	AlterBucketStatement, AlterConfigStatement, AlterConsumerStatement, AlterDatabaseStatement,
+	AlterEventStatement, AlterFieldStatement, AlterFunctionStatement, AlterIndexStatement,
+	AlterJobStatement, AlterKind, AlterModuleStatement, AlterNamespaceStatement,
+	AlterParamStatement, AlterSequenceStatement, AlterSystemStatement, AlterUserStatement,
 };
 use crate::sql::statements::define::ApiAction;
 use crate::sql::statements::{AlterStatement, AlterTableStatement};
-use crate::sql::tokenizer::Tokenizer;
 use crate::syn::error::bail;
 use crate::syn::parser::mac::{expected, expected_whitespace, unexpected};
 use crate::syn::parser::{ParseResult, Parser};
@@ -40,6 +38,12 @@ impl Parser<'_> {
 			t!("CONFIG") => self.parse_alter_config(stk).await.map(AlterStatement::Config),
 			t!("API") => self.parse_alter_api(stk).await.map(AlterStatement::Api),
 			t!("MODULE") => self.parse_alter_module(stk).await.map(AlterStatement::Module),
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("JOB") => {
+				self.parse_alter_job(stk).await.map(AlterStatement::Job)
+			}
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("CONSUMER") => {
+				self.parse_alter_consumer(stk).await.map(AlterStatement::Consumer)
+			}
 			_ => unexpected!(self, next, "a alter statement keyword"),
 		}
 	}
@@ -652,15 +656,7 @@ impl Parser<'_> {
 					self.pop_peek();
 					let mut tokenizers = Vec::new();
 					loop {
-						let next = self.next();
-						let tokenizer = match next.kind {
-							t!("BLANK") => Tokenizer::Blank,
-							t!("CAMEL") => Tokenizer::Camel,
-							t!("CLASS") => Tokenizer::Class,
-							t!("PUNCT") => Tokenizer::Punct,
-							_ => unexpected!(self, next, "a tokenizer"),
-						};
-						tokenizers.push(tokenizer);
+						tokenizers.push(self.parse_tokenizer()?);
 						if !self.eat(t!(",")) {
 							break;
 						}
@@ -671,41 +667,7 @@ impl Parser<'_> {
 					self.pop_peek();
 					let mut filters = Vec::new();
 					loop {
-						let next = self.next();
-						match next.kind {
-							t!("ASCII") => filters.push(Filter::Ascii),
-							t!("LOWERCASE") => filters.push(Filter::Lowercase),
-							t!("UPPERCASE") => filters.push(Filter::Uppercase),
-							t!("EDGENGRAM") => {
-								let open_span = expected!(self, t!("(")).span;
-								let a = self.next_token_value()?;
-								expected!(self, t!(","));
-								let b = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::EdgeNgram(a, b));
-							}
-							t!("NGRAM") => {
-								let open_span = expected!(self, t!("(")).span;
-								let a = self.next_token_value()?;
-								expected!(self, t!(","));
-								let b = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Ngram(a, b));
-							}
-							t!("SNOWBALL") => {
-								let open_span = expected!(self, t!("(")).span;
-								let language = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Snowball(language));
-							}
-							t!("MAPPER") => {
-								let open_span = expected!(self, t!("(")).span;
-								let path: String = self.parse_string_lit()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Mapper(path));
-							}
-							_ => unexpected!(self, next, "a filter"),
-						}
+						filters.push(self.parse_filter()?);
 						if !self.eat(t!(",")) {
 							break;
 						}
@@ -1240,4 +1202,112 @@ impl Parser<'_> {
 
 		Ok(res)
 	}
+
+	pub(crate) async fn parse_alter_job(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<AlterJobStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.parse_ident()?;
+		let mut res = AlterJobStatement {
+			name,
+			if_exists,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("DROP") => {
+					self.pop_peek();
+					let peek = self.peek();
+					match peek.kind {
+						t!("COMMENT") => {
+							self.pop_peek();
+							res.comment = AlterKind::Drop;
+						}
+						_ => unexpected!(self, peek, "`COMMENT`"),
+					}
+				}
+				TokenKind::Identifier => {
+					let peek = self.peek();
+					let word = self.span_str(peek.span);
+					if word.eq_ignore_ascii_case("PAUSE") {
+						self.pop_peek();
+						res.paused = Some(true);
+					} else if word.eq_ignore_ascii_case("RESUME") {
+						self.pop_peek();
+						res.paused = Some(false);
+					} else if word.eq_ignore_ascii_case("SCHEDULE") {
+						self.pop_peek();
+						res.schedule = Some(self.parse_string_lit()?);
+					} else {
+						break;
+					}
+				}
+				t!("THEN") => {
+					self.pop_peek();
+					res.then = Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = AlterKind::Set(self.parse_string_lit()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	pub(crate) async fn parse_alter_consumer(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<AlterConsumerStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.parse_ident()?;
+		let mut res = AlterConsumerStatement {
+			name,
+			if_exists,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("DROP") => {
+					self.pop_peek();
+					let peek = self.peek();
+					match peek.kind {
+						t!("COMMENT") => {
+							self.pop_peek();
+							res.comment = AlterKind::Drop;
+						}
+						_ => unexpected!(self, peek, "`COMMENT`"),
+					}
+				}
+				TokenKind::Identifier
+					if self.span_str(self.peek().span).eq_ignore_ascii_case("ACK") =>
+				{
+					self.pop_peek();
+					res.ack = Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = AlterKind::Set(self.parse_string_lit()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
 }
@@ -1,23 +1,27 @@
 use reblessive::Stk;
 
-use crate::catalog::{ApiMethod, EventDefinition, EventKind};
+use crate::catalog::{
+	ApiMethod, CapabilitiesDefinition, CapabilityTargets, EventDefinition, EventKind,
+	QuotaDefinition, SinkTarget,
+};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::base::Base;
-use crate::sql::filter::Filter;
-use crate::sql::index::{Distance, HnswParams, VectorType};
+use crate::sql::filter::{Filter, WordList};
+use crate::sql::index::{Distance, HnswParams, IvfPqParams, VectorType};
 use crate::sql::kind::KindLiteral;
 use crate::sql::statements::define::config::api::{ApiConfig, Middleware};
 use crate::sql::statements::define::config::defaults::DefaultConfig;
 use crate::sql::statements::define::config::graphql::{GraphQLConfig, TableConfig};
 use crate::sql::statements::define::config::{ConfigInner, graphql};
-use crate::sql::statements::define::user::PassType;
+use crate::sql::statements::define::user::{PassType, TotpType};
 use crate::sql::statements::define::{
 	ApiAction, DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement,
-	DefineBucketStatement, DefineConfigStatement, DefineDatabaseStatement, DefineDefault,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineSequenceStatement,
-	DefineStatement, DefineTableStatement, DefineUserStatement,
+	DefineBucketStatement, DefineConfigStatement, DefineConsumerStatement, DefineDatabaseStatement,
+	DefineDefault, DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
+	DefineIndexStatement, DefineJobStatement, DefineKind, DefineNamespaceStatement,
+	DefineParamStatement, DefineSequenceStatement, DefineSinkStatement, DefineStatement,
+	DefineTableStatement, DefineUserStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
@@ -65,6 +69,15 @@ impl Parser<'_> {
 			t!("BUCKET") => self.parse_define_bucket(stk, next).await.map(DefineStatement::Bucket),
 			t!("SEQUENCE") => self.parse_define_sequence(stk).await.map(DefineStatement::Sequence),
 			t!("MODULE") => self.parse_define_module(stk).await.map(DefineStatement::Module),
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("JOB") => {
+				self.parse_define_job(stk, next).await.map(DefineStatement::Job)
+			}
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("CONSUMER") => {
+				self.parse_define_consumer(stk).await.map(DefineStatement::Consumer)
+			}
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("SINK") => {
+				self.parse_define_sink(stk).await.map(DefineStatement::Sink)
+			}
 			_ => unexpected!(self, next, "a define statement keyword"),
 		}
 	}
@@ -90,9 +103,25 @@ impl Parser<'_> {
 			..Default::default()
 		};
 
-		while let t!("COMMENT") = self.peek_kind() {
-			self.pop_peek();
-			res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		loop {
+			let peek = self.peek();
+			match peek.kind {
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				TokenKind::Identifier
+					if self.span_str(peek.span).eq_ignore_ascii_case("CAPABILITIES") =>
+				{
+					self.pop_peek();
+					res.capabilities = Some(self.parse_capabilities()?);
+				}
+				TokenKind::Identifier if self.span_str(peek.span).eq_ignore_ascii_case("QUOTA") => {
+					self.pop_peek();
+					res.quota = Some(self.parse_quota()?);
+				}
+				_ => break,
+			}
 		}
 
 		Ok(res)
@@ -118,7 +147,8 @@ impl Parser<'_> {
 			..Default::default()
 		};
 		loop {
-			match self.peek_kind() {
+			let peek = self.peek();
+			match peek.kind {
 				t!("COMMENT") => {
 					self.pop_peek();
 					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
@@ -131,6 +161,16 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.strict = true;
 				}
+				TokenKind::Identifier
+					if self.span_str(peek.span).eq_ignore_ascii_case("CAPABILITIES") =>
+				{
+					self.pop_peek();
+					res.capabilities = Some(self.parse_capabilities()?);
+				}
+				TokenKind::Identifier if self.span_str(peek.span).eq_ignore_ascii_case("QUOTA") => {
+					self.pop_peek();
+					res.quota = Some(self.parse_quota()?);
+				}
 				_ => break,
 			}
 		}
@@ -138,6 +178,100 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses the capabilities of a namespace or database, in the form
+	/// `{ scripting: false, allow_funcs: ['string::*'], deny_net: true }`.
+	fn parse_capabilities(&mut self) -> ParseResult<CapabilitiesDefinition> {
+		let open = expected!(self, t!("{")).span;
+		let mut res = CapabilitiesDefinition::default();
+		loop {
+			if self.eat(t!("}")) {
+				break;
+			}
+			let token = self.peek();
+			let name = self.parse_ident()?;
+			expected!(self, t!(":"));
+			match name.as_str() {
+				"scripting" => {
+					let next = self.next();
+					res.scripting = match next.kind {
+						t!("true") => Some(true),
+						t!("false") => Some(false),
+						_ => unexpected!(self, next, "`true` or `false`"),
+					};
+				}
+				"allow_funcs" => res.allow_funcs = Some(self.parse_capability_targets()?),
+				"deny_funcs" => res.deny_funcs = Some(self.parse_capability_targets()?),
+				"allow_net" => res.allow_net = Some(self.parse_capability_targets()?),
+				"deny_net" => res.deny_net = Some(self.parse_capability_targets()?),
+				"allow_experimental" => {
+					res.allow_experimental = Some(self.parse_capability_targets()?)
+				}
+				"deny_experimental" => {
+					res.deny_experimental = Some(self.parse_capability_targets()?)
+				}
+				_ => unexpected!(self, token, "a capability"),
+			}
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("}"), open)?;
+				break;
+			}
+		}
+		Ok(res)
+	}
+
+	/// Parses the resource quotas of a namespace, database, or user, in the
+	/// form `{ max_scanned: 100000, max_concurrent: 10 }`.
+	fn parse_quota(&mut self) -> ParseResult<QuotaDefinition> {
+		let open = expected!(self, t!("{")).span;
+		let mut res = QuotaDefinition::default();
+		loop {
+			if self.eat(t!("}")) {
+				break;
+			}
+			let token = self.peek();
+			let name = self.parse_ident()?;
+			expected!(self, t!(":"));
+			let value = Some(self.next_token_value::<u64>()?);
+			match name.as_str() {
+				"max_scanned" => res.max_scanned = value,
+				"max_memory" => res.max_memory = value,
+				"max_concurrent" => res.max_concurrent = value,
+				"max_storage" => res.max_storage = value,
+				_ => unexpected!(self, token, "a quota"),
+			}
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("}"), open)?;
+				break;
+			}
+		}
+		Ok(res)
+	}
+
+	/// Parses the targets of a capability, which is either `true` for every
+	/// target, `false` for no targets, or an array of targets.
+	fn parse_capability_targets(&mut self) -> ParseResult<CapabilityTargets> {
+		let next = self.next();
+		match next.kind {
+			t!("true") => Ok(CapabilityTargets::All),
+			t!("false") => Ok(CapabilityTargets::None),
+			t!("[") => {
+				let mut targets = Vec::new();
+				loop {
+					if self.eat(t!("]")) {
+						break;
+					}
+					targets.push(self.parse_string_lit()?);
+					if !self.eat(t!(",")) {
+						self.expect_closing_delimiter(t!("]"), next.span)?;
+						break;
+					}
+				}
+				Ok(CapabilityTargets::Some(targets))
+			}
+			_ => unexpected!(self, next, "`true`, `false` or an array of targets"),
+		}
+	}
+
 	pub(crate) async fn parse_define_function(
 		&mut self,
 		stk: &mut Stk,
@@ -338,7 +472,8 @@ impl Parser<'_> {
 		};
 
 		loop {
-			match self.peek_kind() {
+			let peek = self.peek();
+			match peek.kind {
 				t!("COMMENT") => {
 					self.pop_peek();
 					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
@@ -405,6 +540,14 @@ impl Parser<'_> {
 						}
 					}
 				}
+				TokenKind::Identifier if self.span_str(peek.span).eq_ignore_ascii_case("QUOTA") => {
+					self.pop_peek();
+					res.quota = Some(self.parse_quota()?);
+				}
+				TokenKind::Identifier if self.span_str(peek.span).eq_ignore_ascii_case("TOTP") => {
+					self.pop_peek();
+					res.totp = self.parse_totp()?;
+				}
 				_ => break,
 			}
 		}
@@ -412,6 +555,44 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses the clause following `TOTP` in `DEFINE USER`.
+	///
+	/// Without a `SECRET`, a new secret and recovery codes are generated when
+	/// the statement runs. Otherwise both are given explicitly, as exported.
+	fn parse_totp(&mut self) -> ParseResult<TotpType> {
+		let peek = self.peek();
+		if !(peek.kind == TokenKind::Identifier
+			&& self.span_str(peek.span).eq_ignore_ascii_case("SECRET"))
+		{
+			return Ok(TotpType::Generate);
+		}
+		self.pop_peek();
+		let secret = self.parse_string_lit()?;
+		let peek = self.peek();
+		if !(peek.kind == TokenKind::Identifier
+			&& self.span_str(peek.span).eq_ignore_ascii_case("RECOVERY"))
+		{
+			unexpected!(self, peek, "`RECOVERY`");
+		}
+		self.pop_peek();
+		let start = expected!(self, t!("[")).span;
+		let mut recovery = Vec::new();
+		loop {
+			if self.eat(t!("]")) {
+				break;
+			}
+			recovery.push(self.parse_string_lit()?);
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("]"), start)?;
+				break;
+			}
+		}
+		Ok(TotpType::Secret {
+			secret,
+			recovery,
+		})
+	}
+
 	pub(crate) async fn parse_define_access(
 		&mut self,
 		stk: &mut Stk,
@@ -693,6 +874,12 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				TokenKind::Identifier
+					if self.span_str(self.peek().span).eq_ignore_ascii_case("TTL") =>
+				{
+					self.pop_peek();
+					res.ttl = Some(self.parse_ttl()?);
+				}
 				t!("AS") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -1059,6 +1246,20 @@ impl Parser<'_> {
 					let cond = self.try_parse_condition(stk).await?;
 					res.index = Index::Count(cond);
 				}
+				// SPATIAL and IVFPQ are not reserved keywords, so they are matched as identifiers
+				TokenKind::Identifier => {
+					let token = self.peek();
+					let ident = self.span_str(token.span);
+					if ident.eq_ignore_ascii_case("SPATIAL") {
+						self.pop_peek();
+						res.index = Index::Spatial;
+					} else if ident.eq_ignore_ascii_case("IVFPQ") {
+						self.pop_peek();
+						res.index = Index::IvfPq(self.parse_ivfpq_params()?);
+					} else {
+						break;
+					}
+				}
 				t!("FULLTEXT") => {
 					self.pop_peek();
 					let mut analyzer: Option<String> = None;
@@ -1199,7 +1400,10 @@ impl Parser<'_> {
 					bail!("Cannot create a count index with fields", @field_span);
 				}
 			}
-			(field_span, Index::FullText(_) | Index::Hnsw(_)) => {
+			(
+				field_span,
+				Index::FullText(_) | Index::Hnsw(_) | Index::Spatial | Index::IvfPq(_),
+			) => {
 				if res.cols.len() != 1 {
 					if let Some(field_span) = field_span {
 						bail!("Expected one column, found {}", res.cols.len(), @field_span);
@@ -1218,6 +1422,75 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	fn parse_ivfpq_params(&mut self) -> ParseResult<IvfPqParams> {
+		expected!(self, t!("DIMENSION"));
+		let dimension: u16 = self.next_token_value()?;
+		let dimension_span = self.last_span();
+		if dimension == 0 {
+			bail!("Invalid value for IVFPQ parameter `DIMENSION`", @dimension_span => "`DIMENSION` must be greater than 0")
+		}
+		let mut distance = Distance::Euclidean;
+		let mut vector_type = VectorType::F32;
+		let mut lists = 100;
+		let mut subvectors = None;
+		let mut bits = 8;
+		let mut probes = 8;
+		loop {
+			match self.peek_kind() {
+				t!("DISTANCE") => {
+					self.pop_peek();
+					distance = self.parse_distance()?;
+				}
+				t!("TYPE") => {
+					self.pop_peek();
+					vector_type = self.parse_vector_type()?;
+				}
+				TokenKind::Identifier => {
+					let token = self.peek();
+					let ident = self.span_str(token.span);
+					if ident.eq_ignore_ascii_case("LISTS") {
+						self.pop_peek();
+						lists = self.next_token_value()?;
+						if lists == 0 {
+							bail!("Invalid value for IVFPQ parameter `LISTS`", @self.last_span() => "`LISTS` must be greater than 0")
+						}
+					} else if ident.eq_ignore_ascii_case("SUBVECTORS") {
+						self.pop_peek();
+						let v: u16 = self.next_token_value()?;
+						if v == 0 || v > dimension {
+							bail!("Invalid value for IVFPQ parameter `SUBVECTORS`", @self.last_span() => "`SUBVECTORS` must be between 1 and the dimension")
+						}
+						subvectors = Some(v);
+					} else if ident.eq_ignore_ascii_case("BITS") {
+						self.pop_peek();
+						bits = self.next_token_value()?;
+						if !(1..=8).contains(&bits) {
+							bail!("Invalid value for IVFPQ parameter `BITS`", @self.last_span() => "`BITS` must be between 1 and 8")
+						}
+					} else if ident.eq_ignore_ascii_case("PROBES") {
+						self.pop_peek();
+						probes = self.next_token_value()?;
+						if probes == 0 {
+							bail!("Invalid value for IVFPQ parameter `PROBES`", @self.last_span() => "`PROBES` must be greater than 0")
+						}
+					} else {
+						break;
+					}
+				}
+				_ => break,
+			}
+		}
+		Ok(IvfPqParams {
+			dimension,
+			distance,
+			vector_type,
+			lists,
+			subvectors: subvectors.unwrap_or(dimension.min(8)),
+			bits,
+			probes,
+		})
+	}
+
 	pub(crate) async fn parse_define_analyzer(
 		&mut self,
 		stk: &mut Stk,
@@ -1248,47 +1521,7 @@ impl Parser<'_> {
 					self.pop_peek();
 					let mut filters = Vec::new();
 					loop {
-						let next = self.next();
-						match next.kind {
-							t!("ASCII") => {
-								filters.push(Filter::Ascii);
-							}
-							t!("LOWERCASE") => {
-								filters.push(Filter::Lowercase);
-							}
-							t!("UPPERCASE") => {
-								filters.push(Filter::Uppercase);
-							}
-							t!("EDGENGRAM") => {
-								let open_span = expected!(self, t!("(")).span;
-								let a = self.next_token_value()?;
-								expected!(self, t!(","));
-								let b = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::EdgeNgram(a, b));
-							}
-							t!("NGRAM") => {
-								let open_span = expected!(self, t!("(")).span;
-								let a = self.next_token_value()?;
-								expected!(self, t!(","));
-								let b = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Ngram(a, b));
-							}
-							t!("SNOWBALL") => {
-								let open_span = expected!(self, t!("(")).span;
-								let language = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Snowball(language))
-							}
-							t!("MAPPER") => {
-								let open_span = expected!(self, t!("(")).span;
-								let path: String = self.parse_string_lit()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Mapper(path))
-							}
-							_ => unexpected!(self, next, "a filter"),
-						}
+						filters.push(self.parse_filter()?);
 						if !self.eat(t!(",")) {
 							break;
 						}
@@ -1300,15 +1533,7 @@ impl Parser<'_> {
 					let mut tokenizers = Vec::new();
 
 					loop {
-						let next = self.next();
-						let tokenizer = match next.kind {
-							t!("BLANK") => Tokenizer::Blank,
-							t!("CAMEL") => Tokenizer::Camel,
-							t!("CLASS") => Tokenizer::Class,
-							t!("PUNCT") => Tokenizer::Punct,
-							_ => unexpected!(self, next, "a tokenizer"),
-						};
-						tokenizers.push(tokenizer);
+						tokenizers.push(self.parse_tokenizer()?);
 						if !self.eat(t!(",")) {
 							break;
 						}
@@ -1338,6 +1563,135 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses a single analyzer filter.
+	///
+	/// STOPWORDS and SYNONYMS are not reserved keywords, so they are matched
+	/// as identifiers.
+	pub(crate) fn parse_filter(&mut self) -> ParseResult<Filter> {
+		let next = self.next();
+		let filter = match next.kind {
+			t!("ASCII") => Filter::Ascii,
+			t!("LOWERCASE") => Filter::Lowercase,
+			t!("UPPERCASE") => Filter::Uppercase,
+			t!("EDGENGRAM") => {
+				let open_span = expected!(self, t!("(")).span;
+				let a = self.next_token_value()?;
+				expected!(self, t!(","));
+				let b = self.next_token_value()?;
+				self.expect_closing_delimiter(t!(")"), open_span)?;
+				Filter::EdgeNgram(a, b)
+			}
+			t!("NGRAM") => {
+				let open_span = expected!(self, t!("(")).span;
+				let a = self.next_token_value()?;
+				expected!(self, t!(","));
+				let b = self.next_token_value()?;
+				self.expect_closing_delimiter(t!(")"), open_span)?;
+				Filter::Ngram(a, b)
+			}
+			t!("SNOWBALL") => {
+				let open_span = expected!(self, t!("(")).span;
+				let language = self.next_token_value()?;
+				self.expect_closing_delimiter(t!(")"), open_span)?;
+				Filter::Snowball(language)
+			}
+			t!("MAPPER") => {
+				let open_span = expected!(self, t!("(")).span;
+				let path: String = self.parse_string_lit()?;
+				self.expect_closing_delimiter(t!(")"), open_span)?;
+				Filter::Mapper(path)
+			}
+			TokenKind::Identifier => {
+				let ident = self.span_str(next.span);
+				if ident.eq_ignore_ascii_case("STOPWORDS") {
+					let open_span = expected!(self, t!("(")).span;
+					let list = self.parse_word_list(true)?;
+					self.expect_closing_delimiter(t!(")"), open_span)?;
+					Filter::Stopwords(list)
+				} else if ident.eq_ignore_ascii_case("SYNONYMS") {
+					let open_span = expected!(self, t!("(")).span;
+					let list = self.parse_word_list(false)?;
+					self.expect_closing_delimiter(t!(")"), open_span)?;
+					Filter::Synonyms(list)
+				} else {
+					unexpected!(self, next, "a filter")
+				}
+			}
+			_ => unexpected!(self, next, "a filter"),
+		};
+		Ok(filter)
+	}
+
+	/// Parses the words of a STOPWORDS or SYNONYMS filter: a list of strings,
+	/// a file, or, when `language` is true, a language.
+	fn parse_word_list(&mut self, language: bool) -> ParseResult<WordList> {
+		let peek = self.peek();
+		match peek.kind {
+			t!("[") => {
+				self.pop_peek();
+				let mut words = Vec::new();
+				loop {
+					if self.eat(t!("]")) {
+						break;
+					}
+					words.push(self.parse_string_lit()?);
+					if !self.eat(t!(",")) {
+						self.expect_closing_delimiter(t!("]"), peek.span)?;
+						break;
+					}
+				}
+				Ok(WordList::List(words))
+			}
+			t!("f\"") | t!("f'") => Ok(WordList::File(self.next_token_value::<PublicFile>()?)),
+			_ if language => Ok(WordList::Language(self.next_token_value()?)),
+			_ => unexpected!(self, peek, "a list of synonyms or a file"),
+		}
+	}
+
+	/// Parses a single analyzer tokenizer.
+	///
+	/// UNICODE, CJK and KEYWORD are not reserved keywords, so they are
+	/// matched as identifiers.
+	pub(crate) fn parse_tokenizer(&mut self) -> ParseResult<Tokenizer> {
+		let next = self.next();
+		let tokenizer = match next.kind {
+			t!("BLANK") => Tokenizer::Blank,
+			t!("CAMEL") => Tokenizer::Camel,
+			t!("CLASS") => Tokenizer::Class,
+			t!("PUNCT") => Tokenizer::Punct,
+			t!("REGEX") => {
+				let open_span = expected!(self, t!("(")).span;
+				let pattern: String = self.parse_string_lit()?;
+				if let Err(e) = regex::Regex::new(&pattern) {
+					bail!("Invalid regex syntax {e}", @self.last_span());
+				}
+				self.expect_closing_delimiter(t!(")"), open_span)?;
+				Tokenizer::Regex(pattern)
+			}
+			TokenKind::Identifier => {
+				let ident = self.span_str(next.span);
+				if ident.eq_ignore_ascii_case("UNICODE") {
+					Tokenizer::Unicode
+				} else if ident.eq_ignore_ascii_case("KEYWORD") {
+					Tokenizer::Keyword
+				} else if ident.eq_ignore_ascii_case("CJK") {
+					if self.eat(t!("(")) {
+						let open_span = self.last_span();
+						let path: String = self.parse_string_lit()?;
+						self.expect_closing_delimiter(t!(")"), open_span)?;
+						Tokenizer::Cjk(Some(path))
+					} else {
+						Tokenizer::Cjk(None)
+					}
+				} else {
+					unexpected!(self, next, "a tokenizer")
+				}
+			}
+			_ => unexpected!(self, next, "a tokenizer"),
+		};
+		Ok(tokenizer)
+	}
+
 	pub(crate) async fn parse_define_bucket(
 		&mut self,
 		stk: &mut Stk,
@@ -1426,6 +1780,147 @@ impl Parser<'_> {
 		})
 	}
 
+	pub(crate) async fn parse_define_job(
+		&mut self,
+		stk: &mut Stk,
+		token: Token,
+	) -> ParseResult<DefineJobStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+
+		let mut res = DefineJobStatement {
+			name,
+			kind,
+			..Default::default()
+		};
+
+		let mut schedule = false;
+		let mut then = false;
+		loop {
+			match self.peek_kind() {
+				TokenKind::Identifier
+					if self.span_str(self.peek().span).eq_ignore_ascii_case("SCHEDULE") =>
+				{
+					self.pop_peek();
+					res.schedule = self.parse_string_lit()?;
+					schedule = true;
+				}
+				TokenKind::Identifier
+					if self.span_str(self.peek().span).eq_ignore_ascii_case("PAUSED") =>
+				{
+					self.pop_peek();
+					res.paused = true;
+				}
+				t!("THEN") => {
+					self.pop_peek();
+					res.then = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+					then = true;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ => {
+					break;
+				}
+			}
+		}
+
+		if !schedule {
+			bail!("Expected a `SCHEDULE` clause", @token.span => "a job requires a schedule");
+		}
+		if !then {
+			bail!("Expected a `THEN` clause", @token.span => "a job requires a `THEN` clause");
+		}
+
+		Ok(res)
+	}
+
+	pub(crate) async fn parse_define_consumer(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineConsumerStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+
+		let mut res = DefineConsumerStatement {
+			name,
+			kind,
+			..Default::default()
+		};
+
+		while self.eat(t!("COMMENT")) {
+			res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		}
+
+		Ok(res)
+	}
+
+	pub(crate) async fn parse_define_sink(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineSinkStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let target_table = stk.run(|ctx| self.parse_expr_table(ctx)).await?;
+		expected!(self, t!("TO"));
+
+		let next = self.next();
+		let target = match next.kind {
+			t!("FILE") => SinkTarget::File(self.parse_string_lit()?),
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("WEBHOOK") => {
+				SinkTarget::Webhook(self.parse_string_lit()?)
+			}
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("STREAM") => {
+				SinkTarget::Stream
+			}
+			_ => unexpected!(self, next, "`WEBHOOK`, `FILE` or `STREAM`"),
+		};
+
+		let mut res = DefineSinkStatement {
+			kind,
+			name,
+			target_table,
+			target,
+			..Default::default()
+		};
+
+		while self.eat(t!("COMMENT")) {
+			res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		}
+
+		Ok(res)
+	}
+
 	pub(crate) async fn parse_define_config(
 		&mut self,
 		stk: &mut Stk,
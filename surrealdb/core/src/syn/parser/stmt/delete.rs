@@ -12,7 +12,7 @@ impl Parser<'_> {
 		self.eat(t!("FROM"));
 		let only = self.eat(t!("ONLY"));
 		let what = self.parse_what_list(stk).await?;
-		let with = self.try_parse_with()?;
+		let with = self.try_parse_with(false)?;
 		let cond = self.try_parse_condition(stk).await?;
 		let output = self.try_parse_output(stk).await?;
 		let timeout = self.try_parse_timeout(stk).await?;
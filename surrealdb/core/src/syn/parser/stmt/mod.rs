@@ -11,8 +11,9 @@ use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::rebuild::RebuildIndexStatement;
 use crate::sql::statements::show::ShowSince;
 use crate::sql::statements::{
-	ForeachStatement, InfoStatement, KillStatement, LiveStatement, OptionStatement,
-	OutputStatement, RebuildStatement, SetStatement, ShowStatement, SleepStatement, UseStatement,
+	AnalyzeStatement, ForeachStatement, InfoStatement, KillStatement, LiveStatement,
+	OptionStatement, OutputStatement, RebuildStatement, SetStatement, ShowStatement,
+	SleepStatement, UseStatement,
 };
 use crate::sql::{AssignOperator, ExplainFormat, Expr, Literal, Param, TopLevelExpr};
 use crate::syn::lexer::compound;
@@ -113,6 +114,27 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_show_stmt().map(TopLevelExpr::Show)
 			}
+			TokenKind::Identifier
+				if Self::kind_is_identifier(self.peek1().kind)
+					&& self.span_str(token.span).eq_ignore_ascii_case("SAVEPOINT") =>
+			{
+				self.pop_peek();
+				self.parse_savepoint()
+			}
+			TokenKind::Identifier
+				if self.peek1().kind == t!("TO")
+					&& self.span_str(token.span).eq_ignore_ascii_case("ROLLBACK") =>
+			{
+				self.pop_peek();
+				self.parse_rollback_to()
+			}
+			TokenKind::Identifier
+				if Self::kind_is_identifier(self.peek1().kind)
+					&& self.span_str(token.span).eq_ignore_ascii_case("RELEASE") =>
+			{
+				self.pop_peek();
+				self.parse_release()
+			}
 			_ => {
 				let covered = self.peek_kind() == t!("(");
 				let expr = self.parse_expr_start(stk).await?;
@@ -297,6 +319,31 @@ impl Parser<'_> {
 		Ok(TopLevelExpr::Commit)
 	}
 
+	/// Parsers a savepoint statement.
+	///
+	/// # Parser State
+	/// Expects `SAVEPOINT` to already be consumed.
+	fn parse_savepoint(&mut self) -> ParseResult<TopLevelExpr> {
+		Ok(TopLevelExpr::Savepoint(self.parse_ident()?))
+	}
+
+	/// Parsers a rollback to savepoint statement.
+	///
+	/// # Parser State
+	/// Expects `ROLLBACK` to already be consumed.
+	fn parse_rollback_to(&mut self) -> ParseResult<TopLevelExpr> {
+		expected!(self, t!("TO"));
+		Ok(TopLevelExpr::RollbackTo(self.parse_ident()?))
+	}
+
+	/// Parsers a release savepoint statement.
+	///
+	/// # Parser State
+	/// Expects `RELEASE` to already be consumed.
+	fn parse_release(&mut self) -> ParseResult<TopLevelExpr> {
+		Ok(TopLevelExpr::Release(self.parse_ident()?))
+	}
+
 	/// Parses an EXPLAIN expression.
 	///
 	/// # Parser State
@@ -469,6 +516,7 @@ impl Parser<'_> {
 				let structure = self.eat(t!("STRUCTURE"));
 				InfoStatement::Index(index, table, structure)
 			}
+			t!("API") => InfoStatement::Api,
 			_ => unexpected!(self, next, "an info target"),
 		};
 
@@ -569,6 +617,28 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses an ANALYZE statement.
+	///
+	/// # Parser State
+	/// Expects `ANALYZE` to already be consumed.
+	pub(super) fn parse_analyze_stmt(&mut self) -> ParseResult<AnalyzeStatement> {
+		expected!(self, t!("TABLE"));
+		let what = self.parse_ident()?;
+		let peek = self.peek();
+		let sample = if peek.kind == TokenKind::Identifier
+			&& self.span_str(peek.span).eq_ignore_ascii_case("SAMPLE")
+		{
+			self.pop_peek();
+			Some(self.next_token_value::<u64>()?)
+		} else {
+			None
+		};
+		Ok(AnalyzeStatement {
+			what,
+			sample,
+		})
+	}
+
 	/// Parsers a RETURN statement.
 	///
 	/// # Parser State
@@ -638,7 +708,11 @@ impl Parser<'_> {
 				ShowSince::Versionstamp(int)
 			}
 			t!("d\"") | t!("d'") => ShowSince::Timestamp(self.next_token_value()?),
-			_ => unexpected!(self, next, "a version stamp or a date-time"),
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("CONSUMER") => {
+				self.pop_peek();
+				ShowSince::Consumer(self.parse_ident()?)
+			}
+			_ => unexpected!(self, next, "a version stamp, a date-time or a consumer"),
 		};
 
 		let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
@@ -6,9 +6,10 @@ use surrealdb_types::ToSql;
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::index::{Distance, VectorType};
 use crate::sql::reference::{Reference, ReferenceDeleteStrategy};
+use crate::sql::with::{Fusion, FusionNorm};
 use crate::sql::{
 	Base, Cond, Data, Explain, Expr, Fetch, Fetchs, Field, Fields, Group, Groups, Idiom, Literal,
-	Output, Permission, Permissions, View, With,
+	Output, Permission, Permissions, Ttl, View, With,
 };
 use crate::syn::error::bail;
 use crate::syn::parser::mac::{expected, unexpected};
@@ -473,6 +474,19 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses a table time-to-live, either a duration or the field holding
+	/// the expiry datetime of a record
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `TTL` identifier
+	pub fn parse_ttl(&mut self) -> ParseResult<Ttl> {
+		if let TokenKind::Digits = self.peek_kind() {
+			Ok(Ttl::Duration(self.next_token_value::<PublicDuration>()?))
+		} else {
+			Ok(Ttl::Field(self.parse_basic_idiom()?))
+		}
+	}
+
 	/// Parses a reference
 	///
 	/// # Parser State
@@ -583,7 +597,11 @@ impl Parser<'_> {
 		Ok(self.eat(t!("EXPLAIN")).then(|| Explain(self.eat(t!("FULL")))))
 	}
 
-	pub(super) fn try_parse_with(&mut self) -> ParseResult<Option<With>> {
+	/// Parses a `WITH` clause if the next token is `WITH`.
+	///
+	/// `WITH FUSION` is only accepted when `fusion` is true, as only `SELECT`
+	/// statements can run hybrid searches.
+	pub(super) fn try_parse_with(&mut self, fusion: bool) -> ParseResult<Option<With>> {
 		if !self.eat(t!("WITH")) {
 			return Ok(None);
 		}
@@ -601,10 +619,78 @@ impl Parser<'_> {
 				}
 				With::Index(index)
 			}
+			TokenKind::Identifier
+				if fusion && self.span_str(next.span).eq_ignore_ascii_case("FUSION") =>
+			{
+				With::Fusion(self.parse_fusion()?)
+			}
+			_ if fusion => unexpected!(self, next, "`NO`, `NOINDEX`, `INDEX` or `FUSION`"),
 			_ => unexpected!(self, next, "`NO`, `NOINDEX` or `INDEX`"),
 		};
 		Ok(Some(with))
 	}
+
+	/// Parses the fusion method of a `WITH FUSION` clause.
+	///
+	/// Either `RRF` with an optional rank constant, or `LINEAR` with optional
+	/// weights for the KNN and the full-text scores and an optional
+	/// normalization.
+	fn parse_fusion(&mut self) -> ParseResult<Fusion> {
+		let next = self.next();
+		let method = match next.kind {
+			TokenKind::Identifier => self.span_str(next.span),
+			_ => unexpected!(self, next, "`RRF` or `LINEAR`"),
+		};
+		if method.eq_ignore_ascii_case("RRF") {
+			let mut k = 60;
+			if self.eat(t!("(")) {
+				let open = self.last_span();
+				k = self.next_token_value()?;
+				self.expect_closing_delimiter(t!(")"), open)?;
+			}
+			Ok(Fusion::Rrf(k))
+		} else if method.eq_ignore_ascii_case("LINEAR") {
+			let mut vector = 1.0;
+			let mut text = 1.0;
+			let mut norm = FusionNorm::MinMax;
+			if self.eat(t!("(")) {
+				let open = self.last_span();
+				vector = self.parse_fusion_weight()?;
+				expected!(self, t!(","));
+				text = self.parse_fusion_weight()?;
+				if self.eat(t!(",")) {
+					let next = self.next();
+					let ident = match next.kind {
+						TokenKind::Identifier => self.span_str(next.span),
+						_ => unexpected!(self, next, "`MINMAX` or `ZSCORE`"),
+					};
+					norm = if ident.eq_ignore_ascii_case("MINMAX") {
+						FusionNorm::MinMax
+					} else if ident.eq_ignore_ascii_case("ZSCORE") {
+						FusionNorm::ZScore
+					} else {
+						unexpected!(self, next, "`MINMAX` or `ZSCORE`")
+					};
+				}
+				self.expect_closing_delimiter(t!(")"), open)?;
+			}
+			Ok(Fusion::Linear {
+				vector,
+				text,
+				norm,
+			})
+		} else {
+			unexpected!(self, next, "`RRF` or `LINEAR`")
+		}
+	}
+
+	fn parse_fusion_weight(&mut self) -> ParseResult<f32> {
+		let weight: f32 = self.next_token_value()?;
+		if !weight.is_finite() || weight < 0.0 {
+			bail!("Invalid fusion weight", @self.last_span() => "weights must be positive numbers")
+		}
+		Ok(weight)
+	}
 }
 
 /// Check whether `prefix` is a strict prefix of `full`. For GROUP BY
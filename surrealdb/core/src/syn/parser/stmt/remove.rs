@@ -2,7 +2,8 @@ use reblessive::Stk;
 
 use crate::sql::statements::remove::{
 	RemoveAnalyzerStatement, RemoveApiStatement, RemoveBucketStatement, RemoveConfigKind,
-	RemoveConfigStatement, RemoveModuleStatement, RemoveSequenceStatement,
+	RemoveConfigStatement, RemoveConsumerStatement, RemoveJobStatement, RemoveModuleStatement,
+	RemoveSequenceStatement, RemoveSinkStatement,
 };
 use crate::sql::statements::{
 	RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
@@ -13,7 +14,7 @@ use crate::sql::{ModuleName, Param};
 use crate::syn::error::bail;
 use crate::syn::parser::mac::{expected, expected_whitespace, unexpected};
 use crate::syn::parser::{ParseResult, Parser};
-use crate::syn::token::t;
+use crate::syn::token::{TokenKind, t};
 
 impl Parser<'_> {
 	pub(crate) async fn parse_remove_stmt(
@@ -272,6 +273,45 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("JOB") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				RemoveStatement::Job(RemoveJobStatement {
+					name,
+					if_exists,
+				})
+			}
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("CONSUMER") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				RemoveStatement::Consumer(RemoveConsumerStatement {
+					name,
+					if_exists,
+				})
+			}
+			TokenKind::Identifier if self.span_str(next.span).eq_ignore_ascii_case("SINK") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				RemoveStatement::Sink(RemoveSinkStatement {
+					name,
+					if_exists,
+				})
+			}
 			t!("USER") => {
 				let if_exists = if self.eat(t!("IF")) {
 					expected!(self, t!("EXISTS"));
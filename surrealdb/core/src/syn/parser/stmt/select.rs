@@ -41,7 +41,7 @@ impl Parser<'_> {
 			what.push(stk.run(|ctx| self.parse_expr_table(ctx)).await?);
 		}
 
-		let with = self.try_parse_with()?;
+		let with = self.try_parse_with(true)?;
 		let cond = self.try_parse_condition(stk).await?;
 
 		let split_before = self.peek().span;
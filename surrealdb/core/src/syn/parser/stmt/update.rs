@@ -11,7 +11,7 @@ impl Parser<'_> {
 	) -> ParseResult<UpdateStatement> {
 		let only = self.eat(t!("ONLY"));
 		let what = self.parse_what_list(stk).await?;
-		let with = self.try_parse_with()?;
+		let with = self.try_parse_with(false)?;
 		let data = self.try_parse_data(stk).await?;
 		let cond = self.try_parse_condition(stk).await?;
 		let output = self.try_parse_output(stk).await?;
@@ -1,7 +1,9 @@
 use chrono::offset::TimeZone;
 use chrono::{NaiveDate, Offset, Utc};
 
-use crate::catalog::EventKind;
+use crate::catalog::{
+	CapabilitiesDefinition, CapabilityTargets, EventKind, QuotaDefinition, SinkTarget,
+};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::{
 	AccessType, BearerAccess, BearerAccessSubject, BearerAccessType, JwtAccess, JwtAccessIssue,
@@ -10,8 +12,8 @@ use crate::sql::access_type::{
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::data::Assignment;
 use crate::sql::field::Selector;
-use crate::sql::filter::Filter;
-use crate::sql::index::{Distance, FullTextParams, HnswParams, VectorType};
+use crate::sql::filter::{Filter, WordList};
+use crate::sql::index::{Distance, FullTextParams, HnswParams, IvfPqParams, VectorType};
 use crate::sql::language::Language;
 use crate::sql::literal::ObjectEntry;
 use crate::sql::lookup::{LookupKind, LookupSubject};
@@ -19,33 +21,39 @@ use crate::sql::statements::access::{
 	self, AccessStatementGrant, AccessStatementPurge, AccessStatementRevoke, AccessStatementShow,
 	PurgeKind,
 };
-use crate::sql::statements::define::user::PassType;
+use crate::sql::statements::alter::{
+	AlterConsumerStatement, AlterJobStatement, AlterKind, AlterStatement,
+};
+use crate::sql::statements::define::user::{PassType, TotpType};
 use crate::sql::statements::define::{
-	DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement, DefineDefault,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
+	DefineAccessStatement, DefineAnalyzerStatement, DefineConsumerStatement,
+	DefineDatabaseStatement, DefineDefault, DefineEventStatement, DefineFieldStatement,
+	DefineFunctionStatement, DefineIndexStatement, DefineJobStatement, DefineKind,
+	DefineNamespaceStatement, DefineParamStatement, DefineSinkStatement, DefineStatement,
 	DefineTableStatement,
 };
 use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::remove::{
-	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement,
+	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement, RemoveConsumerStatement,
+	RemoveJobStatement, RemoveSinkStatement,
 };
 use crate::sql::statements::show::{ShowSince, ShowStatement};
 use crate::sql::statements::sleep::SleepStatement;
 use crate::sql::statements::{
-	AccessStatement, CreateStatement, DeleteStatement, ForeachStatement, IfelseStatement,
-	InfoStatement, InsertStatement, KillStatement, OptionStatement, OutputStatement,
-	RelateStatement, RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement,
-	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
-	RemoveParamStatement, RemoveStatement, RemoveTableStatement, RemoveUserStatement,
-	SelectStatement, UpdateStatement, UpsertStatement, UseStatement,
+	AccessStatement, AnalyzeStatement, CreateStatement, DeleteStatement, ForeachStatement,
+	IfelseStatement, InfoStatement, InsertStatement, KillStatement, OptionStatement,
+	OutputStatement, RelateStatement, RemoveAccessStatement, RemoveDatabaseStatement,
+	RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
+	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
+	RemoveUserStatement, SelectStatement, UpdateStatement, UpsertStatement, UseStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
+use crate::sql::with::{Fusion, FusionNorm};
 use crate::sql::{
 	Algorithm, AssignOperator, Base, BinaryOperator, Block, Cond, Data, Dir, Explain, Expr, Fetch,
 	Fetchs, Field, Fields, Group, Groups, Idiom, Index, Kind, Literal, Lookup, Mock, Output, Param,
 	Part, Permission, Permissions, RecordIdKeyLit, RecordIdLit, Scoring, TableType, TopLevelExpr,
-	With,
+	Ttl, With,
 };
 use crate::syn;
 use crate::syn::parser::ParserSettings;
@@ -112,6 +120,31 @@ pub fn parse_commit() {
 	assert_eq!(res, TopLevelExpr::Commit);
 }
 
+#[test]
+pub fn parse_savepoint() {
+	let res = syn::parse_with(r#"SAVEPOINT before"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, TopLevelExpr::Savepoint("before".to_owned()));
+	let res = syn::parse_with(r#"rollback to before"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, TopLevelExpr::RollbackTo("before".to_owned()));
+	let res = syn::parse_with(r#"RELEASE before"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, TopLevelExpr::Release("before".to_owned()));
+	// Without a name, the words are still parsed as tables
+	let res = syn::parse_with(r#"savepoint"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, TopLevelExpr::Expr(Expr::Table("savepoint".to_owned())));
+}
+
 #[test]
 pub fn parse_continue() {
 	let res = syn::parse_with(r#"CONTINUE"#.as_bytes(), async |parser, stk| {
@@ -169,6 +202,8 @@ fn parse_define_namespace() {
 			id: None,
 			name: Expr::Idiom(Idiom::field("a".to_string())),
 			comment: Expr::Literal(Literal::String("test".to_string())),
+			capabilities: None,
+			quota: None,
 		})))
 	);
 
@@ -183,6 +218,8 @@ fn parse_define_namespace() {
 			id: None,
 			name: Expr::Idiom(Idiom::field("a".to_string())),
 			comment: Expr::Literal(Literal::None),
+			capabilities: None,
+			quota: None,
 		})))
 	)
 }
@@ -206,6 +243,8 @@ fn parse_define_database() {
 				expiry: PublicDuration::from_secs(60 * 10),
 				store_diff: true,
 			}),
+			capabilities: None,
+			quota: None,
 		})))
 	);
 
@@ -222,8 +261,75 @@ fn parse_define_database() {
 			strict: false,
 			comment: Expr::Literal(Literal::None),
 			changefeed: None,
+			capabilities: None,
+			quota: None,
+		})))
+	)
+}
+
+#[test]
+fn parse_define_database_capabilities() {
+	let res = syn::parse_with(
+		"DEFINE DATABASE a CAPABILITIES { scripting: false, allow_net: ['example.com'], deny_funcs: true }"
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Database(DefineDatabaseStatement {
+			kind: DefineKind::Default,
+			id: None,
+			name: Expr::Idiom(Idiom::field("a".to_string())),
+			strict: false,
+			comment: Expr::Literal(Literal::None),
+			changefeed: None,
+			capabilities: Some(CapabilitiesDefinition {
+				scripting: Some(false),
+				allow_net: Some(CapabilityTargets::Some(vec!["example.com".to_string()])),
+				deny_funcs: Some(CapabilityTargets::All),
+				..Default::default()
+			}),
+			quota: None,
 		})))
+	);
+
+	syn::parse_with(
+		"DEFINE DATABASE a CAPABILITIES { unknown: true }".as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_define_database_quota() {
+	let res = syn::parse_with(
+		"DEFINE DATABASE a QUOTA { max_scanned: 1000, max_concurrent: 4 }".as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
 	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Database(DefineDatabaseStatement {
+			kind: DefineKind::Default,
+			id: None,
+			name: Expr::Idiom(Idiom::field("a".to_string())),
+			strict: false,
+			comment: Expr::Literal(Literal::None),
+			changefeed: None,
+			capabilities: None,
+			quota: Some(QuotaDefinition {
+				max_scanned: Some(1000),
+				max_concurrent: Some(4),
+				..Default::default()
+			}),
+		})))
+	);
+
+	syn::parse_with("DEFINE DATABASE a QUOTA { max_rows: 10 }".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
 }
 
 #[test]
@@ -425,6 +531,57 @@ fn parse_define_user() {
 		)
 		.unwrap_err();
 	}
+	// With generated TOTP.
+	{
+		let res = syn::parse_with(
+			r#"DEFINE USER user ON ROOT PASSWORD 'hunter2' TOTP ROLES OWNER"#.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap();
+
+		let Expr::Define(res) = res else {
+			panic!()
+		};
+		let DefineStatement::User(stmt) = *res else {
+			panic!()
+		};
+
+		assert_eq!(stmt.totp, TotpType::Generate);
+		assert_eq!(stmt.roles, vec!["OWNER".to_string()]);
+	}
+	// With TOTP secret and recovery codes.
+	{
+		let res = syn::parse_with(
+			r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' TOTP SECRET 'JBSWY3DPEHPK3PXP' RECOVERY ['a', 'b']"#
+				.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap();
+
+		let Expr::Define(res) = res else {
+			panic!()
+		};
+		let DefineStatement::User(stmt) = *res else {
+			panic!()
+		};
+
+		assert_eq!(
+			stmt.totp,
+			TotpType::Secret {
+				secret: "JBSWY3DPEHPK3PXP".to_string(),
+				recovery: vec!["a".to_string(), "b".to_string()],
+			}
+		);
+	}
+	// With TOTP secret and no recovery codes.
+	{
+		syn::parse_with(
+			r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' TOTP SECRET 'JBSWY3DPEHPK3PXP'"#
+				.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap_err();
+	}
 }
 
 #[test]
@@ -1599,6 +1756,7 @@ fn parse_define_table() {
 				expiry: PublicDuration::from_secs(1),
 				store_diff: true,
 			}),
+			ttl: None,
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
@@ -1606,6 +1764,232 @@ fn parse_define_table() {
 	);
 }
 
+#[test]
+fn parse_define_table_ttl() {
+	let res = syn::parse_with(r#"DEFINE TABLE session TTL 1h"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a define statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a define table statement");
+	};
+	assert_eq!(stmt.ttl, Some(Ttl::Duration(PublicDuration::from_secs(3600))));
+
+	let res =
+		syn::parse_with(r#"DEFINE TABLE otp TTL expires.at"#.as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a define statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a define table statement");
+	};
+	assert_eq!(
+		stmt.ttl,
+		Some(Ttl::Field(Idiom(vec![
+			Part::Field("expires".to_string()),
+			Part::Field("at".to_string())
+		])))
+	);
+}
+
+#[test]
+fn parse_define_job() {
+	let res = syn::parse_with(
+		r#"DEFINE JOB cleanup SCHEDULE '0 */5 * * *' PAUSED THEN null COMMENT "test""#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Job(DefineJobStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("cleanup".to_string())),
+			schedule: "0 */5 * * *".to_string(),
+			paused: true,
+			then: Expr::Literal(Literal::Null),
+			comment: Expr::Literal(Literal::String("test".to_string())),
+		})))
+	);
+
+	// A job requires both a schedule and something to run
+	syn::parse_with(r#"DEFINE JOB cleanup THEN null"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+	syn::parse_with(r#"DEFINE JOB cleanup SCHEDULE '@daily'"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+}
+
+#[test]
+fn parse_alter_job() {
+	let res = syn::parse_with(
+		r#"ALTER JOB IF EXISTS cleanup RESUME SCHEDULE '@hourly' DROP COMMENT"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Alter(Box::new(AlterStatement::Job(AlterJobStatement {
+			name: "cleanup".to_string(),
+			if_exists: true,
+			paused: Some(false),
+			schedule: Some("@hourly".to_string()),
+			then: None,
+			comment: AlterKind::Drop,
+		})))
+	);
+}
+
+#[test]
+fn parse_remove_job() {
+	let res = syn::parse_with(r#"REMOVE JOB IF EXISTS cleanup"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Job(RemoveJobStatement {
+			name: Expr::Idiom(Idiom::field("cleanup".to_string())),
+			if_exists: true,
+		})))
+	);
+}
+
+#[test]
+fn parse_define_consumer() {
+	let res = syn::parse_with(
+		r#"DEFINE CONSUMER IF NOT EXISTS indexer COMMENT "test""#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Consumer(DefineConsumerStatement {
+			kind: DefineKind::IfNotExists,
+			name: Expr::Idiom(Idiom::field("indexer".to_string())),
+			comment: Expr::Literal(Literal::String("test".to_string())),
+		})))
+	);
+}
+
+#[test]
+fn parse_alter_consumer() {
+	let res = syn::parse_with(
+		r#"ALTER CONSUMER IF EXISTS indexer ACK 42 DROP COMMENT"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Alter(Box::new(AlterStatement::Consumer(AlterConsumerStatement {
+			name: "indexer".to_string(),
+			if_exists: true,
+			ack: Some(Expr::Literal(Literal::Integer(42))),
+			comment: AlterKind::Drop,
+		})))
+	);
+}
+
+#[test]
+fn parse_remove_consumer() {
+	let res = syn::parse_with(r#"REMOVE CONSUMER indexer"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Consumer(RemoveConsumerStatement {
+			name: Expr::Idiom(Idiom::field("indexer".to_string())),
+			if_exists: false,
+		})))
+	);
+}
+
+#[test]
+fn parse_define_sink() {
+	let res = syn::parse_with(
+		r#"DEFINE SINK hook ON TABLE person TO WEBHOOK "https://example.com/cdc" COMMENT "test""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Sink(DefineSinkStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("hook".to_string())),
+			target_table: Expr::Table("person".to_string()),
+			target: SinkTarget::Webhook("https://example.com/cdc".to_string()),
+			comment: Expr::Literal(Literal::String("test".to_string())),
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE SINK OVERWRITE log ON person TO FILE "/tmp/person.ndjson""#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Sink(DefineSinkStatement {
+			kind: DefineKind::Overwrite,
+			name: Expr::Idiom(Idiom::field("log".to_string())),
+			target_table: Expr::Table("person".to_string()),
+			target: SinkTarget::File("/tmp/person.ndjson".to_string()),
+			comment: Expr::Literal(Literal::None),
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE SINK live ON person TO STREAM"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Sink(DefineSinkStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("live".to_string())),
+			target_table: Expr::Table("person".to_string()),
+			target: SinkTarget::Stream,
+			comment: Expr::Literal(Literal::None),
+		})))
+	);
+}
+
+#[test]
+fn parse_remove_sink() {
+	let res = syn::parse_with(r#"REMOVE SINK IF EXISTS hook"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Sink(RemoveSinkStatement {
+			name: Expr::Idiom(Idiom::field("hook".to_string())),
+			if_exists: true,
+		})))
+	);
+}
+
 #[test]
 fn parse_define_event() {
 	let res = syn::parse_with(
@@ -1754,6 +2138,92 @@ fn parse_define_index() {
 			concurrently: false
 		})))
 	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS location SPATIAL"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".to_string()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("location".to_string())]))],
+			index: Index::Spatial,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a, b SPATIAL"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a IVFPQ DIMENSION 128 DIST COSINE TYPE F64 LISTS 256 SUBVECTORS 16 BITS 4 PROBES 12"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".to_string()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("a".to_string())]))],
+			index: Index::IvfPq(IvfPqParams {
+				dimension: 128,
+				distance: Distance::Cosine,
+				vector_type: VectorType::F64,
+				lists: 256,
+				subvectors: 16,
+				bits: 4,
+				probes: 12,
+			}),
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a IVFPQ DIMENSION 4"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!()
+	};
+	let DefineStatement::Index(stmt) = *stmt else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.index,
+		Index::IvfPq(IvfPqParams {
+			dimension: 4,
+			distance: Distance::Euclidean,
+			vector_type: VectorType::F32,
+			lists: 100,
+			subvectors: 4,
+			bits: 8,
+			probes: 8,
+		})
+	);
+
+	syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a IVFPQ DIMENSION 4 BITS 9"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+
+	syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a IVFPQ DIMENSION 4 SUBVECTORS 5"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
 }
 
 #[test]
@@ -1784,6 +2254,63 @@ fn parse_define_analyzer() {
 	)
 }
 
+#[test]
+fn parse_define_analyzer_segmenting_tokenizers() {
+	let res = syn::parse_with(r#"DEFINE ANALYZER ana TOKENIZERS UNICODE, CJK, CJK('/dict/zh.txt'), REGEX('[a-z]+'), KEYWORD, BLANK"#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Analyzer(DefineAnalyzerStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("ana".to_string())),
+			tokenizers: Some(vec![
+				Tokenizer::Unicode,
+				Tokenizer::Cjk(None),
+				Tokenizer::Cjk(Some("/dict/zh.txt".to_owned())),
+				Tokenizer::Regex("[a-z]+".to_owned()),
+				Tokenizer::Keyword,
+				Tokenizer::Blank,
+			]),
+			filters: None,
+			comment: Expr::Literal(Literal::None),
+			function: None,
+		}))),
+	);
+	syn::parse_with(
+		r#"DEFINE ANALYZER ana TOKENIZERS REGEX('[a-z')"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_define_analyzer_word_list_filters() {
+	let res = syn::parse_with(r#"DEFINE ANALYZER ana TOKENIZERS BLANK FILTERS LOWERCASE, STOPWORDS(english), STOPWORDS(['a', 'the']), SYNONYMS(['car, automobile', 'tv => television'])"#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Analyzer(DefineAnalyzerStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("ana".to_string())),
+			tokenizers: Some(vec![Tokenizer::Blank]),
+			filters: Some(vec![
+				Filter::Lowercase,
+				Filter::Stopwords(WordList::Language(Language::English)),
+				Filter::Stopwords(WordList::List(vec!["a".to_owned(), "the".to_owned()])),
+				Filter::Synonyms(WordList::List(vec![
+					"car, automobile".to_owned(),
+					"tv => television".to_owned()
+				])),
+			]),
+			comment: Expr::Literal(Literal::None),
+			function: None,
+		}))),
+	);
+	syn::parse_with(
+		r#"DEFINE ANALYZER ana FILTERS SYNONYMS(english)"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
 #[test]
 fn parse_delete() {
 	let res = syn::parse_with("DELETE FROM ONLY |foo:32..64| WITH INDEX index,index_2 Where 2 RETURN AFTER TIMEOUT 1s EXPLAIN FULL".as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
@@ -1801,6 +2328,58 @@ fn parse_delete() {
 	);
 }
 
+#[test]
+fn parse_select_with_fusion() {
+	let parse = |sql: &str| {
+		let res = syn::parse_with(sql.as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+		let Expr::Select(res) = res else {
+			panic!()
+		};
+		res.with
+	};
+	assert_eq!(
+		parse("SELECT * FROM a WITH FUSION RRF WHERE e <|2|> [1,2] AND t @1@ 'foo'"),
+		Some(With::Fusion(Fusion::Rrf(60)))
+	);
+	assert_eq!(
+		parse("SELECT * FROM a WITH FUSION rrf(10) WHERE e <|2|> [1,2] AND t @1@ 'foo'"),
+		Some(With::Fusion(Fusion::Rrf(10)))
+	);
+	assert_eq!(
+		parse("SELECT * FROM a WITH FUSION LINEAR WHERE e <|2|> [1,2] AND t @1@ 'foo'"),
+		Some(With::Fusion(Fusion::Linear {
+			vector: 1.0,
+			text: 1.0,
+			norm: FusionNorm::MinMax,
+		}))
+	);
+	assert_eq!(
+		parse(
+			"SELECT * FROM a WITH FUSION LINEAR(2, 0.5, ZSCORE) WHERE e <|2|> [1,2] AND t @1@ 'foo'"
+		),
+		Some(With::Fusion(Fusion::Linear {
+			vector: 2.0,
+			text: 0.5,
+			norm: FusionNorm::ZScore,
+		}))
+	);
+	syn::parse_with("SELECT * FROM a WITH FUSION LINEAR(-1, 1)".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+	syn::parse_with("SELECT * FROM a WITH FUSION MAX".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+	syn::parse_with("DELETE a WITH FUSION RRF".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+}
+
 #[test]
 fn parse_delete_2() {
 	let res = syn::parse_with(r#"DELETE FROM ONLY a:b->?[$][?true] WITH INDEX index,index_2 WHERE null RETURN NULL TIMEOUT 1h EXPLAIN"#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
@@ -1987,6 +2566,12 @@ fn parse_info() {
 			false
 		)))
 	);
+
+	let res = syn::parse_with("INFO FOR API".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, Expr::Info(Box::new(InfoStatement::Api)));
 }
 
 #[test]
@@ -2033,6 +2618,23 @@ fn parse_show() {
 			since: ShowSince::Timestamp(PublicDatetime::from(expected_datetime)),
 			limit: None
 		})
+	);
+
+	let res = syn::parse_with(
+		r#"SHOW CHANGES FOR TABLE foo SINCE CONSUMER indexer LIMIT 10"#.as_bytes(),
+		async |parser, stk| parser.parse_query(stk).await,
+	)
+	.unwrap()
+	.expressions
+	.pop()
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Show(ShowStatement {
+			table: Some("foo".to_string()),
+			since: ShowSince::Consumer("indexer".to_string()),
+			limit: Some(10)
+		})
 	)
 }
 
@@ -2049,6 +2651,32 @@ fn parse_sleep() {
 	assert_eq!(res, expect)
 }
 
+#[test]
+fn parse_analyze() {
+	let res =
+		syn::parse_with(r"ANALYZE TABLE person SAMPLE 100".as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+
+	let expect = Expr::Analyze(Box::new(AnalyzeStatement {
+		what: "person".to_string(),
+		sample: Some(100),
+	}));
+	assert_eq!(res, expect);
+
+	let res = syn::parse_with(r"analyze table person".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+
+	let expect = Expr::Analyze(Box::new(AnalyzeStatement {
+		what: "person".to_string(),
+		sample: None,
+	}));
+	assert_eq!(res, expect)
+}
+
 #[test]
 fn parse_use() {
 	let res = syn::parse_with(r"USE NS foo".as_bytes(), async |parser, stk| {
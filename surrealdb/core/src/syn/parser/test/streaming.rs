@@ -161,6 +161,8 @@ fn statements() -> Vec<TopLevelExpr> {
 				id: None,
 				name: Expr::Idiom(Idiom::field("a".to_owned())),
 				comment: Expr::Literal(Literal::String("test".to_owned())),
+				capabilities: None,
+				quota: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Namespace(
@@ -169,6 +171,8 @@ fn statements() -> Vec<TopLevelExpr> {
 				id: None,
 				name: Expr::Idiom(Idiom::field("a".to_owned())),
 				comment: Expr::Literal(Literal::None),
+				capabilities: None,
+				quota: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Database(
@@ -182,6 +186,8 @@ fn statements() -> Vec<TopLevelExpr> {
 					expiry: PublicDuration::from_secs(60 * 10),
 					store_diff: false,
 				}),
+				capabilities: None,
+				quota: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Database(
@@ -192,6 +198,8 @@ fn statements() -> Vec<TopLevelExpr> {
 				strict: false,
 				comment: Expr::Literal(Literal::None),
 				changefeed: None,
+				capabilities: None,
+				quota: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Function(
@@ -283,6 +291,7 @@ fn statements() -> Vec<TopLevelExpr> {
 				expiry: PublicDuration::from_secs(1),
 				store_diff: false,
 			}),
+			ttl: None,
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
@@ -3,6 +3,7 @@ use rust_decimal::Decimal;
 use surrealdb_types::ToSql;
 
 use crate::sql::literal::ObjectEntry;
+use crate::sql::operator::{BooleanOperator, Fuzziness, MatchesOperator};
 use crate::sql::{
 	BinaryOperator, Constant, Expr, Idiom, Literal, Part, RecordIdKeyLit, RecordIdLit,
 };
@@ -340,3 +341,44 @@ fn empty_string() {
 	syn::parse_with("".as_bytes(), async |parser, stk| parser.parse_expr_field(stk).await)
 		.unwrap_err();
 }
+
+#[test]
+fn parse_matches_options() {
+	let res =
+		syn::parse_with("title @1,OR,FUZZY(2),PREFIX@ 'helo'".as_bytes(), async |parser, stk| {
+			parser.parse_expr_field(stk).await
+		})
+		.unwrap();
+	let Expr::Binary {
+		op: BinaryOperator::Matches(op),
+		..
+	} = &res
+	else {
+		panic!("not a matches operator");
+	};
+	assert_eq!(
+		*op,
+		MatchesOperator {
+			rf: Some(1),
+			operator: Some(BooleanOperator::Or),
+			fuzzy: Some(Fuzziness::Distance(2)),
+			prefix: true,
+		}
+	);
+	assert_eq!(res.to_sql(), "title @1,OR,FUZZY(2),PREFIX@ 'helo'");
+
+	let res = syn::parse_with("title @fuzzy@ 'helo'".as_bytes(), async |parser, stk| {
+		parser.parse_expr_field(stk).await
+	})
+	.unwrap();
+	assert_eq!(res.to_sql(), "title @FUZZY@ 'helo'");
+
+	syn::parse_with("title @FUZZY(3)@ 'helo'".as_bytes(), async |parser, stk| {
+		parser.parse_expr_field(stk).await
+	})
+	.unwrap_err();
+	syn::parse_with("title @PREFIX,PREFIX@ 'helo'".as_bytes(), async |parser, stk| {
+		parser.parse_expr_field(stk).await
+	})
+	.unwrap_err();
+}
@@ -22,6 +22,7 @@ mod put;
 mod replace;
 mod rid;
 mod set;
+mod size;
 mod walk;
 
 mod convert;
@@ -0,0 +1,31 @@
+use std::mem::size_of;
+
+use crate::val::{RecordIdKey, Value};
+
+impl Value {
+	/// Returns an estimate of the number of bytes of memory held by this
+	/// value, including the heap allocations of any nested values
+	pub(crate) fn estimated_size(&self) -> usize {
+		size_of::<Value>()
+			+ match self {
+				Value::String(v) => v.len(),
+				Value::Bytes(v) => v.len(),
+				Value::Table(v) => v.len(),
+				Value::RecordId(v) => v.table.len() + key_size(&v.key),
+				Value::File(v) => v.bucket.len() + v.key.len(),
+				Value::Array(v) => v.iter().map(Value::estimated_size).sum(),
+				Value::Set(v) => v.0.iter().map(Value::estimated_size).sum(),
+				Value::Object(v) => v.iter().map(|(k, v)| k.len() + v.estimated_size()).sum(),
+				_ => 0,
+			}
+	}
+}
+
+fn key_size(key: &RecordIdKey) -> usize {
+	match key {
+		RecordIdKey::String(v) => v.len(),
+		RecordIdKey::Array(v) => v.iter().map(Value::estimated_size).sum(),
+		RecordIdKey::Object(v) => v.iter().map(|(k, v)| k.len() + v.estimated_size()).sum(),
+		_ => 0,
+	}
+}
@@ -125,3 +125,31 @@ async fn test_async_event_retry() -> Result<()> {
 	t.expect_val("20")?;
 	Ok(())
 }
+
+#[tokio::test]
+#[test_log::test]
+async fn test_async_event_capability_overrides() -> Result<()> {
+	let sql = r#"
+		DEFINE DATABASE OVERWRITE test CAPABILITIES { deny_funcs: ['string::uppercase'] };
+		DEFINE EVENT allowed ON TABLE user ASYNC THEN (
+			CREATE activity:allowed SET value = string::lowercase($after.email)
+		);
+		DEFINE EVENT denied ON TABLE user ASYNC THEN (
+			CREATE activity:denied SET value = string::uppercase($after.email)
+		);
+		CREATE user:test SET email = 'Info@surrealdb.com' RETURN NONE;
+	"#;
+
+	let mut t = Test::new(sql).await?;
+	t.expect_size(4)?;
+	t.expect_vals(&["NONE", "NONE", "NONE", "[]"])?;
+
+	// Process the events asynchronously
+	wait_for_events_processing(&t.ds).await?;
+
+	// The events run with the capabilities of the database
+	let mut t = t.new_sql("SELECT * FROM activity;").await?;
+	t.expect_size(1)?;
+	t.expect_val("[{ id: activity:allowed, value: 'info@surrealdb.com' }]")?;
+	Ok(())
+}
@@ -631,3 +631,73 @@ async fn test_changefeed_gc_cycle_kept() -> Result<()> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn test_changefeed_gc_cycle_consumer() -> Result<()> {
+	let (_, db) = new_ds("test-cf-cu", "test-cf-cu", false).await?;
+	let ses = Session::owner().with_ns("test-cf-cu").with_db("test-cf-cu");
+
+	let src = r#"
+		DEFINE TABLE t CHANGEFEED 1ns;
+		DEFINE CONSUMER indexer;
+		DEFINE CONSUMER stalled;
+		CREATE t:1;
+	"#;
+	let res = db.execute(src, &ses, None).await?;
+	for r in res {
+		r.result.unwrap();
+	}
+
+	let src = r#"
+		SHOW CHANGES FOR TABLE t SINCE CONSUMER indexer;
+	"#;
+	let mut res = db.execute(src, &ses, None).await?;
+	let res1 = res.remove(0).result.unwrap();
+	let Value::Array(changesets) = &res1 else {
+		panic!("Expected array of changes");
+	};
+	assert_eq!(changesets.len(), 1);
+	assert_eq!(res1.get(0).get("changes"), syn::value("[{ update: { id: t:1 } }]").unwrap());
+	let Value::Number(vs) = res1.get(0).get("versionstamp") else {
+		panic!("Expected versionstamp number");
+	};
+	let vs = vs.to_int().unwrap();
+
+	// Acknowledged changes are no longer returned to the consumer
+	let src = format!(
+		"
+		ALTER CONSUMER indexer ACK {vs};
+		SHOW CHANGES FOR TABLE t SINCE CONSUMER indexer;
+	"
+	);
+	let mut res = db.execute(&src, &ses, None).await?;
+	res.remove(0).result.unwrap();
+	let res1 = res.remove(0).result.unwrap();
+	assert_eq!(res1, syn::value(r#"[]"#).unwrap());
+
+	// A consumer which has not acknowledged the change does not keep it past
+	// the changefeed expiry
+	let src = r#"
+		SHOW CHANGES FOR TABLE t SINCE CONSUMER stalled;
+	"#;
+	let mut res = db.execute(src, &ses, None).await?;
+	let res1 = res.remove(0).result.unwrap();
+	let Value::Array(changesets) = &res1 else {
+		panic!("Expected array of changes");
+	};
+	assert_eq!(changesets.len(), 1);
+
+	tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+	db.changefeed_process(&std::time::Duration::from_secs(1)).await.unwrap();
+
+	let src = r#"
+		SHOW CHANGES FOR TABLE t SINCE CONSUMER stalled;
+		SHOW CHANGES FOR TABLE t SINCE 0;
+	"#;
+	let mut res = db.execute(src, &ses, None).await?;
+	let res1 = res.remove(0).result.unwrap();
+	assert_eq!(res1, syn::value(r#"[]"#).unwrap());
+	let res2 = res.remove(0).result.unwrap();
+	assert_eq!(res2, syn::value(r#"[]"#).unwrap());
+	Ok(())
+}
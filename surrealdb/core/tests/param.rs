@@ -29,11 +29,14 @@ async fn define_global_param() -> Result<()> {
 			apis: {},
 			buckets: {},
 			configs: {},
+			consumers: {},
 			functions: {},
+			jobs: {},
 			models: {},
 			modules: {},
 			params: { test: 'DEFINE PARAM $test VALUE 12345 PERMISSIONS FULL' },
 			sequences: {},
+			sinks: {},
 			tables: {},
 			users: {},
 		}",
@@ -315,11 +315,14 @@ async fn relate_enforced() -> Result<()> {
 	apis: {},
 	buckets: {},
 	configs: {},
+	consumers: {},
 	functions: {},
+	jobs: {},
 	models: {},
 	modules: {},
 	params: {},
 	sequences: {},
+	sinks: {},
 	tables: {
 		a: 'DEFINE TABLE a TYPE ANY SCHEMALESS PERMISSIONS NONE',
 		edge: 'DEFINE TABLE edge TYPE RELATION ENFORCED SCHEMALESS PERMISSIONS NONE'
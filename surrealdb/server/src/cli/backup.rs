@@ -0,0 +1,178 @@
+use std::pin::Pin;
+
+use anyhow::{Result, bail};
+use bytes::Bytes;
+use clap::Args;
+use futures::{Stream, TryStreamExt};
+use surrealdb_core::dbs::Session;
+use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory, backup};
+use tokio::fs::File;
+use tokio::io::{self, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+
+use crate::cli::abstraction::AuthArguments;
+use crate::cli::abstraction::auth::CredentialsLevel;
+
+type BackupStream = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>;
+
+#[derive(Args, Debug)]
+pub struct BackupCommandArguments {
+	#[arg(
+		help = "Path to the backup file, datastore, or server to back up from. Use dash - to read from stdin."
+	)]
+	#[arg(index = 1)]
+	from: String,
+	#[arg(
+		help = "Path to the backup file, datastore, or server to restore into. Use dash - to write into stdout."
+	)]
+	#[arg(default_value = "-")]
+	#[arg(index = 2)]
+	into: String,
+	#[arg(
+		help = "Back up only the latest version of each key from a datastore which keeps historical versions"
+	)]
+	#[arg(long)]
+	latest_only: bool,
+	#[command(flatten)]
+	auth: AuthArguments,
+}
+
+/// A source or destination of a backup.
+enum Location {
+	/// Standard input or output
+	Std,
+	/// A SurrealDB server, using its `/sync` endpoint
+	Remote(String),
+	/// A datastore opened directly from this process
+	Datastore(String),
+	/// A backup file
+	File(String),
+}
+
+impl Location {
+	fn parse<F: TransactionBuilderFactory>(v: &str) -> Self {
+		match v {
+			"-" => Self::Std,
+			v if v.starts_with("http://") || v.starts_with("https://") => {
+				Self::Remote(format!("{}/sync", v.trim_end_matches('/')))
+			}
+			v if F::path_valid(v).is_ok() => Self::Datastore(v.to_string()),
+			v => Self::File(v.to_string()),
+		}
+	}
+}
+
+pub async fn init<F: TransactionBuilderFactory>(
+	composer: F,
+	BackupCommandArguments {
+		from,
+		into,
+		latest_only,
+		auth: AuthArguments {
+			username,
+			password,
+			token,
+			auth_level,
+		},
+	}: BackupCommandArguments,
+) -> Result<()> {
+	// Backups can only be taken and restored by root users
+	if (username.is_some() || password.is_some()) && !matches!(auth_level, CredentialsLevel::Root) {
+		bail!("Backups require root level authentication");
+	}
+	let from = Location::parse::<F>(&from);
+	let into = Location::parse::<F>(&into);
+	// The composer can only open a single datastore
+	let mut composer = Some(composer);
+	let mut open = async |path: &str| -> Result<Datastore> {
+		let Some(composer) = composer.take() else {
+			bail!("Only one of the backup source and destination can be a datastore");
+		};
+		let ds = Datastore::builder().build_with_factory_path::<F>(path, composer).await?;
+		ds.check_version().await?;
+		Ok(ds)
+	};
+	// Setup the HTTP client for remote servers
+	let client = reqwest::Client::new();
+	let request = |req: reqwest::RequestBuilder| match (&username, &password, &token) {
+		(Some(user), pass, _) => req.basic_auth(user, pass.as_ref()),
+		(_, _, Some(token)) => req.bearer_auth(token),
+		_ => req,
+	};
+	// Historical versions can not be included in a backup
+	let cfg = backup::Config {
+		latest_only,
+	};
+	// Open the backup source
+	debug!("Reading the backup");
+	let stream: BackupStream = match from {
+		Location::Std => Box::pin(ReaderStream::new(io::stdin()).map_err(anyhow::Error::new)),
+		Location::File(path) => {
+			let file = File::open(&path).await?;
+			Box::pin(ReaderStream::new(file).map_err(anyhow::Error::new))
+		}
+		Location::Remote(url) => {
+			let url = format!("{url}?latest_only={latest_only}");
+			let res = request(client.get(url)).send().await?.error_for_status()?;
+			Box::pin(res.bytes_stream().map_err(anyhow::Error::new))
+		}
+		Location::Datastore(path) => {
+			let ds = open(&path).await?;
+			let (chn, out) = surrealdb::channel::bounded::<Result<Bytes>>(1);
+			let (snd, rcv) = surrealdb::channel::bounded(1);
+			// Start the backup task, reporting any failure to the destination
+			let task = ds.backup(&Session::owner(), cfg, snd).await?;
+			let err = chn.clone();
+			tokio::spawn(async move {
+				if let Err(e) = task.await {
+					// The destination has already failed if the error can't be sent
+					if let Err(send) = err.send(Err(e)).await {
+						warn!("Error reporting a failed backup: {:?}", send.0);
+					}
+				}
+				if let Err(e) = ds.shutdown().await {
+					warn!("Error shutting down the datastore: {e}");
+				}
+			});
+			// Process all chunk values
+			tokio::spawn(async move {
+				while let Ok(v) = rcv.recv().await {
+					if chn.send(Ok(Bytes::from(v))).await.is_err() {
+						break;
+					}
+				}
+			});
+			Box::pin(out)
+		}
+	};
+	// Write the backup into the destination
+	debug!("Writing the backup");
+	match into {
+		Location::Std => write(stream, io::stdout()).await?,
+		Location::File(path) => write(stream, File::create(&path).await?).await?,
+		Location::Remote(url) => {
+			let body = reqwest::Body::wrap_stream(stream);
+			request(client.post(url)).body(body).send().await?.error_for_status()?;
+		}
+		Location::Datastore(path) => {
+			let ds = open(&path).await?;
+			ds.restore(&Session::owner(), stream).await?;
+			ds.shutdown().await?;
+		}
+	}
+	info!("The backup was completed successfully");
+	// Everything OK
+	Ok(())
+}
+
+/// Writes a backup stream into a file or stdout.
+async fn write<W>(mut stream: BackupStream, mut w: W) -> Result<()>
+where
+	W: AsyncWriteExt + Unpin,
+{
+	while let Some(bytes) = stream.try_next().await? {
+		w.write_all(&bytes).await?;
+	}
+	w.flush().await?;
+	Ok(())
+}
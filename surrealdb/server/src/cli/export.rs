@@ -64,6 +64,12 @@ struct ExportConfigArguments {
 	/// Whether configs should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	configs: Option<bool>,
+	/// Whether scheduled jobs should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	jobs: Option<bool>,
+	/// Whether changefeed sinks should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	sinks: Option<bool>,
 }
 
 #[derive(Args, Debug)]
@@ -227,5 +233,13 @@ fn apply_config<C: Connection, R>(
 		export = export.configs(value);
 	}
 
+	if let Some(value) = config.jobs {
+		export = export.jobs(value);
+	}
+
+	if let Some(value) = config.sinks {
+		export = export.sinks(value);
+	}
+
 	export
 }
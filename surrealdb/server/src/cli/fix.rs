@@ -1,6 +1,6 @@
-use anyhow::Result;
-use clap::Args;
-use surrealdb_core::kvs::TransactionBuilderFactory;
+use anyhow::{Result, bail};
+use clap::{Args, ValueEnum};
+use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory};
 
 #[derive(Args, Debug)]
 pub struct FixCommandArguments {
@@ -8,14 +8,51 @@ pub struct FixCommandArguments {
 	#[arg(env = "SURREAL_PATH", index = 1)]
 	#[arg(default_value = "memory")]
 	path: String,
+	#[arg(help = "Remove the inconsistent keys which are found")]
+	#[arg(long)]
+	repair: bool,
+	#[arg(help = "The format of the report")]
+	#[arg(long, value_enum)]
+	#[arg(default_value = "text")]
+	format: ReportFormat,
 }
 
-/// Validate the datastore path for the `fix` subcommand.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+	Text,
+	Json,
+}
+
+/// Check the consistency of a datastore, and optionally repair it.
 ///
 /// Only the `TransactionBuilderFactory` bound is required here because this
-/// command does not need to start the HTTP server or build routes.
-pub async fn init<F: TransactionBuilderFactory>(args: FixCommandArguments) -> Result<()> {
+/// command does not need to start the HTTP server or build routes. The
+/// datastore should not be in use by a running server while it is checked.
+pub async fn init<F: TransactionBuilderFactory>(
+	composer: F,
+	FixCommandArguments {
+		path,
+		repair,
+		format,
+	}: FixCommandArguments,
+) -> Result<()> {
+	F::path_valid(&path)?;
+	// Open the datastore
+	let ds = Datastore::builder().build_with_factory_path::<F>(&path, composer).await?;
+	ds.check_version().await?;
+	// Check the datastore
+	let res = ds.fix(repair).await;
+	ds.shutdown().await?;
+	let report = res?;
+	// Output the report
+	match format {
+		ReportFormat::Text => println!("{report}"),
+		ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+	}
+	// Fail when problems were left in place
+	if !repair && !report.problems.is_empty() {
+		bail!("Found {} problems, run with --repair to remove them", report.problems.len());
+	}
 	// All ok
-	F::path_valid(&args.path)?;
-	Err(anyhow::anyhow!("Fix is not implemented"))
+	Ok(())
 }
@@ -1,6 +1,7 @@
 #![allow(deprecated)]
 
 pub(crate) mod abstraction;
+mod backup;
 mod config;
 mod export;
 mod fix;
@@ -9,6 +10,7 @@ mod isready;
 mod ml;
 #[cfg(feature = "surrealism")]
 mod module;
+mod reencrypt;
 #[cfg(feature = "cli")]
 mod sql;
 mod start;
@@ -26,6 +28,7 @@ use std::process::ExitCode;
 use std::time::Duration;
 
 use anyhow::Result;
+use backup::BackupCommandArguments;
 use clap::{Parser, Subcommand, ValueEnum};
 pub use config::{Config, ConfigCheck, ConfigCheckRequirements};
 use export::ExportCommandArguments;
@@ -35,6 +38,7 @@ use isready::IsReadyCommandArguments;
 use ml::MlCommand;
 #[cfg(feature = "surrealism")]
 use module::ModuleCommand;
+use reencrypt::ReencryptCommandArguments;
 use semver::Version;
 #[cfg(feature = "cli")]
 use sql::SqlCommandArguments;
@@ -161,10 +165,8 @@ struct Cli {
 enum Commands {
 	#[command(about = "Start the database server")]
 	Start(StartCommandArguments),
-	/* Not implemented yet
 	#[command(about = "Backup data to or from an existing database")]
 	Backup(BackupCommandArguments),
-	*/
 	#[command(about = "Import a SurrealQL script into an existing database")]
 	Import(ImportCommandArguments),
 	#[command(about = "Export an existing database as a SurrealQL script")]
@@ -188,8 +190,10 @@ enum Commands {
 	IsReady(IsReadyCommandArguments),
 	#[command(about = "Validate SurrealQL query files")]
 	Validate(ValidateCommandArguments),
-	#[command(about = "Fix database storage issues")]
+	#[command(about = "Check and repair the consistency of a datastore")]
 	Fix(FixCommandArguments),
+	#[command(about = "Re-encrypt the data in a datastore with a new encryption key")]
+	Reencrypt(ReencryptCommandArguments),
 	#[command(about = "Run commands in version 2 of the database for backwards compatibility")]
 	V2(V2Commands),
 }
@@ -289,6 +293,7 @@ pub async fn init<
 	// After version warning we can run the respective command
 	let output = match args.command {
 		Commands::Start(args) => start::init::<C>(composer, args).await,
+		Commands::Backup(args) => backup::init::<C>(composer, args).await,
 		Commands::Import(args) => import::init(args).await,
 		Commands::Export(args) => export::init(args).await,
 		Commands::Version(args) => version::init(args).await,
@@ -300,7 +305,8 @@ pub async fn init<
 		Commands::Module(args) => module::init(args).await,
 		Commands::IsReady(args) => isready::init(args).await,
 		Commands::Validate(args) => validate::init(args).await,
-		Commands::Fix(args) => fix::init::<C>(args).await,
+		Commands::Fix(args) => fix::init::<C>(composer, args).await,
+		Commands::Reencrypt(args) => reencrypt::init::<C>(composer, args).await,
 		Commands::V2(args) => v2::init(args).await,
 	};
 	// Save the flamegraph and profile
@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+use surrealdb_core::kvs::encryption::{EncryptionKey, Keyring};
+use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory};
+
+#[derive(Args, Debug)]
+pub struct ReencryptCommandArguments {
+	#[arg(help = "Database path used for storing data")]
+	#[arg(env = "SURREAL_PATH", index = 1)]
+	path: String,
+	#[arg(help = "A file containing the base64 encoded key to encrypt the data with")]
+	#[arg(long)]
+	key_file: PathBuf,
+	#[arg(help = "A file containing a base64 encoded key the data was previously encrypted with")]
+	#[arg(long = "previous-key-file")]
+	previous_key_files: Vec<PathBuf>,
+	#[arg(help = "Whether the datastore contains data stored before encryption was enabled")]
+	#[arg(long)]
+	from_plaintext: bool,
+}
+
+/// Re-encrypt every value in a datastore with a new encryption key.
+///
+/// Only the `TransactionBuilderFactory` bound is required here because this
+/// command does not need to start the HTTP server or build routes. The
+/// datastore should not be in use by a running server while it is
+/// re-encrypted.
+pub async fn init<F: TransactionBuilderFactory>(
+	composer: F,
+	ReencryptCommandArguments {
+		path,
+		key_file,
+		previous_key_files,
+		from_plaintext,
+	}: ReencryptCommandArguments,
+) -> Result<()> {
+	F::path_valid(&path)?;
+	// Load the encryption keys
+	let mut keyring = Keyring::new(EncryptionKey::from_file(key_file)?);
+	for file in previous_key_files {
+		keyring = keyring.with_previous(EncryptionKey::from_file(file)?);
+	}
+	let keyring = keyring.with_plaintext(from_plaintext);
+	// Open the datastore
+	let ds = Datastore::builder()
+		.with_encryption(keyring)
+		.build_with_factory_path::<F>(&path, composer)
+		.await?;
+	ds.check_version().await?;
+	// Re-encrypt the datastore
+	let res = ds.reencrypt().await;
+	ds.shutdown().await?;
+	// Output the report
+	println!("{}", res?);
+	// All ok
+	Ok(())
+}
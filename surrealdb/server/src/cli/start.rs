@@ -72,6 +72,34 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_ASYNC_EVENT_PROCESSING_INTERVAL", long = "async-event-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "5s")]
 	event_processing_interval: Duration,
+	#[arg(
+		help = "The interval at which to remove expired records from tables with a time-to-live",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_TTL_SWEEP_INTERVAL", long = "ttl-sweep-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	ttl_sweep_interval: Duration,
+	#[arg(
+		help = "The interval at which to check whether any scheduled jobs are due",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_JOB_INTERVAL", long = "job-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "10s")]
+	job_interval: Duration,
+	#[arg(
+		help = "The interval at which to deliver changefeeds to webhook and file sinks",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_SINK_INTERVAL", long = "sink-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "5s")]
+	sink_interval: Duration,
+	#[arg(
+		help = "The interval at which to measure the storage size of namespaces and databases with a storage quota",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_QUOTA_STORAGE_INTERVAL", long = "quota-storage-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	quota_storage_interval: Duration,
 	//
 	// Authentication
 	#[arg(
@@ -186,6 +214,10 @@ pub async fn init<
 		changefeed_gc_interval,
 		index_compaction_interval,
 		event_processing_interval,
+		ttl_sweep_interval,
+		job_interval,
+		sink_interval,
+		quota_storage_interval,
 		no_banner,
 		no_identification_headers,
 		allow_origin,
@@ -220,7 +252,11 @@ pub async fn init<
 		.with_node_membership_cleanup_interval(node_membership_cleanup_interval)
 		.with_changefeed_gc_interval(changefeed_gc_interval)
 		.with_index_compaction_interval(index_compaction_interval)
-		.with_event_processing_interval(event_processing_interval);
+		.with_event_processing_interval(event_processing_interval)
+		.with_ttl_sweep_interval(ttl_sweep_interval)
+		.with_job_interval(job_interval)
+		.with_sink_interval(sink_interval)
+		.with_quota_storage_interval(quota_storage_interval);
 	// Configure the config
 	let Some(bind) = listen_addresses.first().copied() else {
 		return Err(anyhow::anyhow!("No listen address provided"));
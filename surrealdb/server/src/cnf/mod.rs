@@ -69,6 +69,9 @@ pub static HTTP_MAX_SIGNIN_BODY_SIZE: LazyLock<usize> =
 pub static HTTP_MAX_IMPORT_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_IMPORT_BODY_SIZE", usize, 4 << 30);
 
+/// Specifies the frequency with which stream sinks are polled for new changes
+pub const SINK_STREAM_POLL_FREQUENCY: Duration = Duration::from_secs(1);
+
 /// Specifies the frequency with which ping messages are sent to the client
 pub const WEBSOCKET_PING_FREQUENCY: Duration = Duration::from_secs(5);
 
@@ -270,7 +270,7 @@ Targets must be in the form of <host>[:<port>], <ipv4|ipv6>[/<mask>]. For exampl
 	deny_http: Option<Targets<RouteTarget>>,
 
 	#[arg(
-		help = "Strategy for the streaming query planner: 'best-effort' (default), 'compute-only', or 'all-read-only'"
+		help = "Strategy for the streaming query planner: 'best-effort' (default), 'compute-only', 'all-read-only', or 'all'"
 	)]
 	#[arg(env = "SURREAL_PLANNER_STRATEGY", long = "planner-strategy")]
 	#[arg(default_value = "best-effort")]
@@ -7,7 +7,8 @@
 //! 2. Validates that the session specifies a namespace and database.
 //! 3. Retrieves (or generates) the GraphQL schema via [`GraphQLSchemaCache`].
 //! 4. Injects the [`Datastore`](surrealdb_core::kvs::Datastore) and [`Session`] into the
-//!    `async_graphql` request context so resolvers can access them.
+//!    `async_graphql` request context so resolvers can access them, along with a [`RecordLoader`]
+//!    which batches the record fetches of the request.
 //! 5. Executes the request -- either as a batch request or as a streaming `multipart/mixed`
 //!    response, depending on the `Accept` header.
 
@@ -29,6 +30,7 @@ use futures_util::future::BoxFuture;
 use http::header::{CONTENT_TYPE, HeaderValue};
 use surrealdb_core::dbs::Session;
 use surrealdb_core::dbs::capabilities::RouteTarget;
+use surrealdb_core::gql::RecordLoader;
 use surrealdb_core::gql::cache::GraphQLSchemaCache;
 use surrealdb_core::gql::error::resolver_error;
 use tower_service::Service;
@@ -117,6 +119,8 @@ where
 			// Clone Arc's before moving req (needed for GraphQL context)
 			let datastore_ctx = datastore.clone();
 			let session_ctx = std::sync::Arc::new(session.clone());
+			let loader_ctx =
+				std::sync::Arc::new(RecordLoader::new(datastore_ctx.clone(), session_ctx.clone()));
 
 			let is_accept_multipart_mixed = req
 				.headers()
@@ -131,7 +135,8 @@ where
 					Ok(r) => r,
 					Err(err) => return Ok(err.into_response()),
 				};
-				let mut req_with_data = gql_req.into_inner().data(datastore_ctx).data(session_ctx);
+				let mut req_with_data =
+					gql_req.into_inner().data(datastore_ctx).data(session_ctx).data(loader_ctx);
 				if request_is_subscription(&mut req_with_data) {
 					let response = async_graphql::Response::from_errors(vec![ServerError::new(
 						"Subscriptions require WebSocket transport on GET /graphql",
@@ -149,7 +154,8 @@ where
 						Ok(r) => r,
 						Err(err) => return Ok(err.into_response()),
 					};
-				let req_with_data = gql_req.into_inner().data(datastore_ctx).data(session_ctx);
+				let req_with_data =
+					gql_req.into_inner().data(datastore_ctx).data(session_ctx).data(loader_ctx);
 				Ok(as_application_json(
 					GraphQLResponse(schema.execute_batch(req_with_data).await).into_response(),
 				))
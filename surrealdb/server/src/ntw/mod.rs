@@ -17,6 +17,7 @@ pub mod rpc;
 mod signals;
 pub mod signin;
 pub mod signup;
+pub mod sink;
 pub mod sql;
 pub mod sync;
 mod tracer;
@@ -139,6 +140,7 @@ impl RouterFactory for CommunityComposer {
 			.merge(signin::router())
 			.merge(signup::router())
 			.merge(key::router())
+			.merge(sink::router())
 			.merge(ml::router())
 			.merge(api::router());
 
@@ -8,7 +8,7 @@ use surrealdb::types::Value;
 use surrealdb_core::dbs::Session;
 use surrealdb_core::dbs::capabilities::RouteTarget;
 use surrealdb_core::iam::Token;
-use surrealdb_core::iam::signin::signin;
+use surrealdb_core::iam::signin::{challenge, signin};
 use surrealdb_core::syn;
 use surrealdb_types::SurrealValue;
 use tower_http::limit::RequestBodyLimitLayer;
@@ -30,10 +30,16 @@ struct Success {
 
 impl Success {
 	fn new(token: Token) -> Success {
+		let details = match token {
+			Token::Challenge {
+				..
+			} => String::from("A second factor is required"),
+			_ => String::from("Authentication succeeded"),
+		};
 		Success {
 			token,
 			code: 200,
-			details: String::from("Authentication succeeded"),
+			details,
 		}
 	}
 }
@@ -44,6 +50,7 @@ where
 {
 	Router::new()
 		.route("/signin", options(|| async {}).post(handler))
+		.route("/signin/challenge", options(|| async {}).post(challenge_handler))
 		.route_layer(DefaultBodyLimit::disable())
 		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_SIGNUP_BODY_SIZE))
 }
@@ -66,48 +73,83 @@ async fn handler(
 	// Parse the provided data as JSON
 	match syn::json(data) {
 		// The provided value was an object
-		Ok(Value::Object(vars)) => {
-			match signin(kvs, &mut session, vars.into()).await {
-				// Authentication was successful
-				Ok(token) => {
-					match accept.as_deref() {
-						// Simple serialization
-						Some(Accept::ApplicationJson) => {
-							let success = Success::new(token).into_value().into_json_value();
-							Ok(Output::json_other(&success))
-						}
-						Some(Accept::ApplicationCbor) => {
-							let success = Success::new(token).into_value();
-							Ok(Output::cbor(success))
-						}
-						// Text serialization
-						// NOTE: Only the token is returned in a plain text response.
-						Some(Accept::TextPlain) => {
-							let token = match token {
-								Token::Access(token) => token,
-								Token::WithRefresh {
-									access: token,
-									..
-								} => token,
-							};
-							Ok(Output::Text(token))
-						}
-						// Internal serialization
-						Some(Accept::ApplicationFlatbuffers) => {
-							let success = Success::new(token).into_value();
-							Ok(Output::flatbuffers(&success))
-						}
-						// Return nothing
-						None => Ok(Output::None),
-						// An incorrect content-type was requested
-						_ => Err(NetError::InvalidType.into()),
-					}
-				}
-				// There was an error with authentication
-				Err(err) => Err(ResponseError(err)),
-			}
-		}
+		Ok(Value::Object(vars)) => match signin(kvs, &mut session, vars.into()).await {
+			// Authentication was successful
+			Ok(token) => output(accept, token),
+			// There was an error with authentication
+			Err(err) => Err(ResponseError(err)),
+		},
 		// The provided value was not an object
 		_ => Err(NetError::Request.into()),
 	}
 }
+
+async fn challenge_handler(
+	Extension(state): Extension<AppState>,
+	Extension(mut session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	body: Bytes,
+) -> Result<Output, ResponseError> {
+	// Get a database reference
+	let kvs = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !kvs.allows_http_route(&RouteTarget::Signin) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Signin);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Signin.to_string()).into());
+	}
+	// Convert the HTTP body into text
+	let data = bytes_to_utf8(&body).context("Non UTF-8 request body").map_err(ResponseError)?;
+	// Parse the provided data as JSON
+	let Ok(Value::Object(vars)) = syn::json(data) else {
+		return Err(NetError::Request.into());
+	};
+	// Extract the challenge and the second factor code
+	let (Some(Value::String(tk)), Some(Value::String(code))) =
+		(vars.get("challenge"), vars.get("code"))
+	else {
+		return Err(NetError::Request.into());
+	};
+	match challenge(kvs, &mut session, tk.clone(), code.clone()).await {
+		// Authentication was successful
+		Ok(token) => output(accept, token),
+		// There was an error with authentication
+		Err(err) => Err(ResponseError(err)),
+	}
+}
+
+/// Serializes the token returned from a signin
+fn output(accept: Option<TypedHeader<Accept>>, token: Token) -> Result<Output, ResponseError> {
+	match accept.as_deref() {
+		// Simple serialization
+		Some(Accept::ApplicationJson) => {
+			let success = Success::new(token).into_value().into_json_value();
+			Ok(Output::json_other(&success))
+		}
+		Some(Accept::ApplicationCbor) => {
+			let success = Success::new(token).into_value();
+			Ok(Output::cbor(success))
+		}
+		// Text serialization
+		// NOTE: Only the token is returned in a plain text response.
+		Some(Accept::TextPlain) => match token {
+			Token::Access(token) => Ok(Output::Text(token)),
+			Token::WithRefresh {
+				access: token,
+				..
+			} => Ok(Output::Text(token)),
+			// A challenge can not be told apart from a token in plain text
+			Token::Challenge {
+				..
+			} => Err(NetError::InvalidType.into()),
+		},
+		// Internal serialization
+		Some(Accept::ApplicationFlatbuffers) => {
+			let success = Success::new(token).into_value();
+			Ok(Output::flatbuffers(&success))
+		}
+		// Return nothing
+		None => Ok(Output::None),
+		// An incorrect content-type was requested
+		_ => Err(NetError::InvalidType.into()),
+	}
+}
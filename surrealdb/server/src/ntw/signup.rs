@@ -87,6 +87,9 @@ async fn handler(
 									access: token,
 									..
 								} => token,
+								Token::Challenge {
+									challenge: token,
+								} => token,
 							};
 							Ok(Output::Text(token))
 						}
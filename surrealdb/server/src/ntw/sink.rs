@@ -0,0 +1,102 @@
+use anyhow::Result;
+use axum::body::Body;
+use axum::extract::Path;
+use axum::response::{IntoResponse, Response};
+use axum::routing::options;
+use axum::{Extension, Router};
+use bytes::Bytes;
+use http::StatusCode;
+use http::header::CONTENT_TYPE;
+use surrealdb_core::dbs::Session;
+use surrealdb_core::dbs::capabilities::RouteTarget;
+
+use super::AppState;
+use super::error::ResponseError;
+use crate::cnf::SINK_STREAM_POLL_FREQUENCY;
+use crate::ntw::error::Error as NetError;
+
+pub fn router<S>() -> Router<S>
+where
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new().route("/sink/{name}", options(|| async {}).get(handler))
+}
+
+/// Streams the changes of a stream sink as NDJSON.
+///
+/// The stream starts after the last acknowledged change of the sink. A batch
+/// of changes is acknowledged once the following batch, or an idle heartbeat,
+/// has been sent to the client, so changes are delivered at least once.
+async fn handler(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	Path(name): Path<String>,
+) -> Result<impl IntoResponse, ResponseError> {
+	// Get the datastore reference
+	let db = state.datastore.clone();
+	// Check if capabilities allow querying the requested HTTP route
+	if !db.allows_http_route(&RouteTarget::Sink) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Sink);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Sink.to_string()).into());
+	}
+	// Read the first batch, which checks the sink exists and can be read
+	let mut batch = db.next_sink_batch(&session, &name, None).await.map_err(ResponseError)?;
+	// Create a chunked response
+	let (chn, body_stream) = surrealdb::channel::bounded::<Result<Bytes>>(1);
+	let body = Body::from_stream(body_stream);
+	// Process all batches until the client disconnects
+	tokio::spawn(async move {
+		// The versionstamp the stream has read up to
+		let mut cursor = None;
+		// The versionstamp of the batch waiting to be acknowledged
+		let mut pending = None;
+		loop {
+			// Send the changes, or a heartbeat while the sink is idle
+			let chunk = match &batch {
+				Some((_, chunk)) if chunk.is_empty() => None,
+				Some((_, chunk)) => Some(chunk.clone()),
+				None => Some(String::from("\n")),
+			};
+			if let Some(chunk) = chunk {
+				if chn.send(Ok(Bytes::from(chunk))).await.is_err() {
+					break;
+				}
+				// The client is still connected, so acknowledge the previous batch
+				if let Some(vs) = pending.take()
+					&& let Err(err) = db.ack_sink_batch(&session, &name, vs).await
+				{
+					// The client may have hung up before the error could be sent
+					if chn.send(Err(err)).await.is_err() {
+						debug!("Sink stream client disconnected before receiving an error");
+					}
+					break;
+				}
+			}
+			// Move the cursor past the changes which were read
+			if let Some((vs, _)) = batch.take() {
+				cursor = Some(vs);
+				pending = Some(vs);
+			}
+			// Read the next batch of changes
+			batch = match db.next_sink_batch(&session, &name, cursor).await {
+				Ok(Some(batch)) => Some(batch),
+				Ok(None) => {
+					tokio::time::sleep(SINK_STREAM_POLL_FREQUENCY).await;
+					None
+				}
+				Err(err) => {
+					// The client may have hung up before the error could be sent
+					if chn.send(Err(err)).await.is_err() {
+						debug!("Sink stream client disconnected before receiving an error");
+					}
+					break;
+				}
+			};
+		}
+	});
+	// Return the chunked body
+	Ok(Response::builder()
+		.status(StatusCode::OK)
+		.header(CONTENT_TYPE, "application/x-ndjson")
+		.body(body)?)
+}
@@ -1,42 +1,110 @@
-use axum::response::IntoResponse;
+use anyhow::Result;
+use axum::body::Body;
+use axum::extract::{DefaultBodyLimit, Request};
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
 use axum::{Extension, Router};
+use axum_extra::extract::Query;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use http::StatusCode;
+use serde::Deserialize;
+use surrealdb_core::dbs::Session;
 use surrealdb_core::dbs::capabilities::RouteTarget;
+use surrealdb_core::iam::Action::Edit;
+use surrealdb_core::iam::ResourceKind::Any;
+use surrealdb_core::kvs::backup;
 
 use super::AppState;
+use super::error::ResponseError;
+use super::output::Output;
 use crate::ntw::error::Error as NetError;
 
+#[derive(Default, Deserialize, Debug, Clone)]
+struct SaveOptions {
+	/// Whether to back up only the latest version of each key, when the
+	/// datastore keeps historical versions
+	#[serde(default)]
+	pub latest_only: bool,
+}
+
 pub fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
 {
-	Router::new().route("/sync", get(save).post(load))
+	// The backup is streamed rather than buffered, and the restore only holds
+	// a bounded number of entries in memory, so the body size is not limited
+	Router::new().route("/sync", get(save).post(load)).route_layer(DefaultBodyLimit::disable())
 }
 
 async fn load(
 	Extension(state): Extension<AppState>,
-) -> Result<impl IntoResponse, impl IntoResponse> {
+	Extension(session): Extension<Session>,
+	request: Request,
+) -> Result<impl IntoResponse, ResponseError> {
 	// Get the datastore reference
 	let db = &state.datastore;
 	// Check if capabilities allow querying the requested HTTP route
 	if !db.allows_http_route(&RouteTarget::Sync) {
 		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Sync);
-		return Err(NetError::ForbiddenRoute(RouteTarget::Sync.to_string()));
+		return Err(NetError::ForbiddenRoute(RouteTarget::Sync.to_string()).into());
 	}
-
-	Ok("Load")
+	// Check the permissions level. A snapshot overwrites the user catalog,
+	// so only root owners may restore one
+	db.check(&session, Edit, Any.on_root()).map_err(ResponseError)?;
+	// Restore the backup from the request body
+	let body_stream = request.into_body().into_data_stream().map_err(anyhow::Error::new);
+	db.restore(&session, body_stream).await.map_err(ResponseError)?;
+	// Return nothing
+	Ok(Output::None)
 }
 
 async fn save(
 	Extension(state): Extension<AppState>,
-) -> Result<impl IntoResponse, impl IntoResponse> {
+	Extension(session): Extension<Session>,
+	Query(query): Query<SaveOptions>,
+) -> Result<impl IntoResponse, ResponseError> {
 	// Get the datastore reference
 	let db = &state.datastore;
 	// Check if capabilities allow querying the requested HTTP route
 	if !db.allows_http_route(&RouteTarget::Sync) {
 		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Sync);
-		return Err(NetError::ForbiddenRoute(RouteTarget::Sync.to_string()));
+		return Err(NetError::ForbiddenRoute(RouteTarget::Sync.to_string()).into());
 	}
-
-	Ok("Save")
+	// Check the permissions level. A snapshot includes the user catalog, so
+	// only root owners may take one
+	db.check(&session, Edit, Any.on_root()).map_err(ResponseError)?;
+	// Create a chunked response
+	let (chn, body_stream) = surrealdb::channel::bounded::<Result<Bytes>>(1);
+	let body = Body::from_stream(body_stream);
+	// Create a new bounded channel
+	let (snd, rcv) = surrealdb::channel::bounded(1);
+	// Start the backup task
+	let cfg = backup::Config {
+		latest_only: query.latest_only,
+	};
+	let task = db.backup(&session, cfg, snd).await.map_err(ResponseError)?;
+	// Spawn a new database backup job, reporting any failure to the client
+	let err = chn.clone();
+	tokio::spawn(async move {
+		if let Err(e) = task.await {
+			// The client has already disconnected if the error can't be sent
+			if let Err(send) = err.send(Err(e)).await {
+				tracing::warn!("Error reporting a failed backup: {:?}", send.0);
+			}
+		}
+	});
+	// Process all chunk values
+	tokio::spawn(async move {
+		while let Ok(v) = rcv.recv().await {
+			if let Err(err) = chn.send(Ok(Bytes::from(v))).await {
+				tracing::warn!("Error sending bytes: {:?}", err);
+			}
+		}
+	});
+	// Return the chunked body
+	Ok(Response::builder()
+		.status(StatusCode::OK)
+		.header(http::header::CONTENT_TYPE, "application/octet-stream")
+		.body(body)?)
 }
@@ -92,4 +92,34 @@ impl RpcProtocol for Http {
 	) -> Result<DbResult, TypesError> {
 		Err(method_not_found(Method::Cancel.to_string()))
 	}
+
+	/// Transactions are not supported on HTTP RPC context
+	async fn savepoint(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		_params: Array,
+	) -> Result<DbResult, TypesError> {
+		Err(method_not_found(Method::Savepoint.to_string()))
+	}
+
+	/// Transactions are not supported on HTTP RPC context
+	async fn rollback_to(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		_params: Array,
+	) -> Result<DbResult, TypesError> {
+		Err(method_not_found(Method::RollbackTo.to_string()))
+	}
+
+	/// Transactions are not supported on HTTP RPC context
+	async fn release(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		_params: Array,
+	) -> Result<DbResult, TypesError> {
+		Err(method_not_found(Method::Release.to_string()))
+	}
 }
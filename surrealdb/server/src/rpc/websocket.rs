@@ -490,6 +490,24 @@ impl Websocket {
 		// Cancel the WebSocket tasks
 		rpc.canceller.cancel();
 	}
+
+	/// Extracts the transaction and the savepoint name from the parameters
+	/// of a savepoint method
+	fn savepoint_params(&self, params: Array) -> Result<(Arc<Transaction>, String), TypesError> {
+		let mut params = params.into_vec().into_iter();
+		let (Some(Value::Uuid(txn_id)), Some(Value::String(name)), None) =
+			(params.next(), params.next(), params.next())
+		else {
+			return Err(surrealdb_core::rpc::invalid_params(
+				"Expected transaction UUID and savepoint name",
+			));
+		};
+		// Retrieve the transaction, keeping it open
+		let Some(tx) = self.transactions.get(&txn_id.into_inner()).map(|tx| tx.clone()) else {
+			return Err(surrealdb_core::rpc::invalid_params("Transaction not found"));
+		};
+		Ok((tx, name))
+	}
 }
 
 impl RpcProtocol for Websocket {
@@ -686,4 +704,46 @@ impl RpcProtocol for Websocket {
 		// Return success
 		Ok(DbResult::Other(Value::None))
 	}
+
+	/// Set a named savepoint in a transaction
+	async fn savepoint(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		params: Array,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		let (tx, name) = self.savepoint_params(params)?;
+		tx.set_named_save_point(&name)
+			.await
+			.map_err(surrealdb_core::rpc::types_error_from_anyhow)?;
+		Ok(DbResult::Other(Value::None))
+	}
+
+	/// Roll a transaction back to a named savepoint
+	async fn rollback_to(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		params: Array,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		let (tx, name) = self.savepoint_params(params)?;
+		tx.rollback_to_named_save_point(&name)
+			.await
+			.map_err(surrealdb_core::rpc::types_error_from_anyhow)?;
+		Ok(DbResult::Other(Value::None))
+	}
+
+	/// Release a named savepoint in a transaction
+	async fn release(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		params: Array,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		let (tx, name) = self.savepoint_params(params)?;
+		tx.release_named_save_point(&name)
+			.await
+			.map_err(surrealdb_core::rpc::types_error_from_anyhow)?;
+		Ok(DbResult::Other(Value::None))
+	}
 }
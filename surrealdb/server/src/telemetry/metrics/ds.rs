@@ -1,29 +1,59 @@
 use std::sync::Arc;
 
 use opentelemetry::global;
-use surrealdb_core::kvs::Datastore;
+use surrealdb_core::kvs::{Datastore, Metrics};
 
 /// Registers datastore-specific metrics with the global OpenTelemetry meter.
 ///
 /// This function checks if the datastore provides any metrics and, if so,
 /// registers them as observable gauges. Observable gauges are useful for
 /// metrics that are updated at a regular interval or on demand, such as
-/// memory usage or cache statistics.
+/// memory usage or cache statistics. The resource quota metrics are
+/// registered for every datastore, along with the GraphQL batching metrics
+/// when GraphQL is enabled.
 pub fn register_datastore_metrics(ds: Arc<Datastore>) {
 	if let Some(metrics) = ds.register_metrics() {
-		let meter = global::meter(metrics.name);
-		for u64_metric in metrics.u64_metrics {
-			let ds = ds.clone();
-			let _ = meter
-				.u64_observable_gauge(u64_metric.name)
-				.with_description(u64_metric.description)
-				.with_callback(move |observer| {
-					if let Some(val) = ds.collect_u64_metric(u64_metric.name) {
-						observer.observe(val, &[]);
-					}
-				})
-				.build();
-		}
+		register_metrics(&ds, metrics);
+	}
+	register_metrics(&ds, ds.register_quota_metrics());
+	#[cfg(feature = "graphql")]
+	register_graphql_metrics();
+}
+
+/// Registers the metrics describing the record batches fetched by GraphQL
+/// requests as observable gauges.
+#[cfg(feature = "graphql")]
+fn register_graphql_metrics() {
+	use surrealdb_core::gql::LoaderMetrics;
+	let metrics = LoaderMetrics::metrics();
+	let meter = global::meter(metrics.name);
+	for u64_metric in metrics.u64_metrics {
+		let _ = meter
+			.u64_observable_gauge(u64_metric.name)
+			.with_description(u64_metric.description)
+			.with_callback(move |observer| {
+				if let Some(val) = LoaderMetrics::collect_u64_metric(u64_metric.name) {
+					observer.observe(val, &[]);
+				}
+			})
+			.build();
+	}
+}
+
+/// Registers a group of metrics as observable gauges.
+fn register_metrics(ds: &Arc<Datastore>, metrics: Metrics) {
+	let meter = global::meter(metrics.name);
+	for u64_metric in metrics.u64_metrics {
+		let ds = ds.clone();
+		let _ = meter
+			.u64_observable_gauge(u64_metric.name)
+			.with_description(u64_metric.description)
+			.with_callback(move |observer| {
+				if let Some(val) = ds.collect_u64_metric(u64_metric.name) {
+					observer.observe(val, &[]);
+				}
+			})
+			.build();
 	}
 }
 
@@ -100,6 +130,9 @@ mod tests {
 			"rocksdb.block_cache_pinned_usage",
 			"rocksdb.estimate_table_readers_mem",
 			"rocksdb.cur_size_all_mem_tables",
+			"quota.active_statements",
+			"quota.rejected_statements",
+			"quota.scanned_records",
 		];
 
 		for metric_name in expected_metrics {
@@ -36,6 +36,18 @@ pub(crate) enum Command {
 	Commit {
 		txn: Uuid,
 	},
+	Savepoint {
+		txn: Uuid,
+		name: String,
+	},
+	RollbackTo {
+		txn: Uuid,
+		name: String,
+	},
+	Release {
+		txn: Uuid,
+		name: String,
+	},
 	Revoke {
 		token: Token,
 	},
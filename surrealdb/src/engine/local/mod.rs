@@ -566,6 +566,22 @@ async fn kill_live_query(
 	Ok(results)
 }
 
+/// The error returned when signing in as a user which requires a second factor
+fn challenge_unsupported() -> crate::Error {
+	TypesError::not_allowed(
+		"The user requires a second factor, which can only be completed with the `challenge` RPC method".to_string(),
+		None,
+	)
+}
+
+/// The error returned when a command refers to a transaction which is not open
+fn transaction_not_found() -> crate::Error {
+	TypesError::not_found(
+		"Transaction not found".to_string(),
+		Some(surrealdb_types::NotFoundError::Transaction),
+	)
+}
+
 async fn router(
 	kvs: &Arc<Datastore>,
 	state: &SessionState,
@@ -603,6 +619,9 @@ async fn router(
 					access: AccessToken(SecureToken(token)),
 					refresh: Some(RefreshToken(SecureToken(refresh))),
 				},
+				iam::Token::Challenge {
+					..
+				} => return Err(challenge_unsupported()),
 			};
 			let result = query_result.finish_with_result(Ok(token.into_value()));
 			Ok(vec![result])
@@ -628,6 +647,9 @@ async fn router(
 					access: AccessToken(SecureToken(access)),
 					refresh: Some(RefreshToken(SecureToken(refresh))),
 				},
+				iam::Token::Challenge {
+					..
+				} => return Err(challenge_unsupported()),
 			};
 			let result = query_result.finish_with_result(Ok(token.into_value()));
 			Ok(vec![result])
@@ -643,6 +665,9 @@ async fn router(
 					access,
 					..
 				} => (access, true),
+				iam::Token::Challenge {
+					challenge,
+				} => (challenge, false),
 			};
 			// Attempt to authenticate with the access token
 			let result = {
@@ -744,6 +769,38 @@ async fn router(
 			}
 			Ok(vec![QueryResultBuilder::instant_none()])
 		}
+		Command::Savepoint {
+			txn,
+			name,
+		} => {
+			let Some(tx) = state.transactions.get(&txn) else {
+				return Err(transaction_not_found());
+			};
+			tx.set_named_save_point(&name).await.map_err(crate::std_error_to_types_error)?;
+			Ok(vec![QueryResultBuilder::instant_none()])
+		}
+		Command::RollbackTo {
+			txn,
+			name,
+		} => {
+			let Some(tx) = state.transactions.get(&txn) else {
+				return Err(transaction_not_found());
+			};
+			tx.rollback_to_named_save_point(&name)
+				.await
+				.map_err(crate::std_error_to_types_error)?;
+			Ok(vec![QueryResultBuilder::instant_none()])
+		}
+		Command::Release {
+			txn,
+			name,
+		} => {
+			let Some(tx) = state.transactions.get(&txn) else {
+				return Err(transaction_not_found());
+			};
+			tx.release_named_save_point(&name).await.map_err(crate::std_error_to_types_error)?;
+			Ok(vec![QueryResultBuilder::instant_none()])
+		}
 		Command::Query {
 			txn,
 			query,
@@ -178,6 +178,9 @@ impl Command {
 						access,
 						..
 					} => access.into_value(),
+					Token::Challenge {
+						challenge,
+					} => challenge.into_value(),
 				}]))),
 				txn: None,
 				session_id,
@@ -225,6 +228,45 @@ impl Command {
 				txn: None,
 				session_id,
 			},
+			Command::Savepoint {
+				txn,
+				name,
+			} => RouterRequest {
+				id,
+				method: "savepoint",
+				params: Some(Value::Array(Array::from(vec![
+					Value::Uuid(Uuid::from(txn)),
+					Value::String(name),
+				]))),
+				txn: None,
+				session_id,
+			},
+			Command::RollbackTo {
+				txn,
+				name,
+			} => RouterRequest {
+				id,
+				method: "rollback_to",
+				params: Some(Value::Array(Array::from(vec![
+					Value::Uuid(Uuid::from(txn)),
+					Value::String(name),
+				]))),
+				txn: None,
+				session_id,
+			},
+			Command::Release {
+				txn,
+				name,
+			} => RouterRequest {
+				id,
+				method: "release",
+				params: Some(Value::Array(Array::from(vec![
+					Value::Uuid(Uuid::from(txn)),
+					Value::String(name),
+				]))),
+				txn: None,
+				session_id,
+			},
 			Command::Revoke {
 				token,
 			} => RouterRequest {
@@ -51,8 +51,12 @@ pub fn init(dbs: Arc<Datastore>, canceller: CancellationToken, opts: &EngineOpti
 	let task3 = spawn_task_node_membership_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task4 = spawn_task_changefeed_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task5 = spawn_task_index_compaction(dbs.clone(), canceller.clone(), opts);
-	let task6 = spawn_task_event_processing(dbs, canceller, opts);
-	Tasks(vec![task1, task2, task3, task4, task5, task6])
+	let task6 = spawn_task_event_processing(dbs.clone(), canceller.clone(), opts);
+	let task7 = spawn_task_ttl_sweep(dbs.clone(), canceller.clone(), opts);
+	let task8 = spawn_task_job_scheduler(dbs.clone(), canceller.clone(), opts);
+	let task9 = spawn_task_sink_delivery(dbs.clone(), canceller.clone(), opts);
+	let task10 = spawn_task_quota_storage(dbs, canceller, opts);
+	Tasks(vec![task1, task2, task3, task4, task5, task6, task7, task8, task9, task10])
 }
 
 fn spawn_task_node_membership_refresh(
@@ -264,6 +268,130 @@ fn spawn_task_event_processing(
 	}))
 }
 
+fn spawn_task_ttl_sweep(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.ttl_sweep_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Removing expired records every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.ttl_sweep(interval).await {
+						error!("Error removing expired records: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Removing expired records");
+	}))
+}
+
+fn spawn_task_job_scheduler(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.job_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running scheduled jobs every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.run_jobs(interval).await {
+						error!("Error running scheduled jobs: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running scheduled jobs");
+	}))
+}
+
+fn spawn_task_sink_delivery(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.sink_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Delivering changefeed sinks every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.run_sinks(interval).await {
+						error!("Error delivering changefeed sinks: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Delivering changefeed sinks");
+	}))
+}
+
+fn spawn_task_quota_storage(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.quota_storage_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Measuring the storage size of quotas every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.refresh_quota_storage().await {
+						error!("Error measuring the storage size of quotas: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Measuring the storage size of quotas");
+	}))
+}
+
 async fn interval_ticker(interval: Duration) -> IntervalStream {
 	#[cfg(not(target_family = "wasm"))]
 	use tokio::{time, time::MissedTickBehavior};
@@ -174,6 +174,22 @@ where
 		}
 		self
 	}
+
+	/// Whether to export scheduled jobs from the database
+	pub fn jobs(mut self, jobs: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.jobs = jobs;
+		}
+		self
+	}
+
+	/// Whether to export changefeed sinks from the database
+	pub fn sinks(mut self, sinks: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.sinks = sinks;
+		}
+		self
+	}
 }
 
 impl<C, R, T> Export<'_, C, R, T>
@@ -29,7 +29,10 @@ mod insert_relation;
 mod invalidate;
 mod merge;
 mod patch;
+mod release;
+mod rollback_to;
 mod run;
+mod savepoint;
 mod select;
 mod set;
 mod signin;
@@ -63,7 +66,10 @@ pub use live::Stream;
 pub use merge::Merge;
 pub use patch::Patch;
 pub use query::{IntoVariables, Query, QueryStream};
+pub use release::Release;
+pub use rollback_to::RollbackTo;
 pub use run::{IntoFn, Run};
+pub use savepoint::Savepoint;
 pub use select::Select;
 pub use set::Set;
 pub use signin::Signin;
@@ -0,0 +1,55 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use uuid::Uuid;
+
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::{Connection, Result, Surreal};
+
+/// Returned by [`Transaction::rollback_to`](crate::method::Transaction::rollback_to);
+/// reverts the changes made in the transaction since a named savepoint was set.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RollbackTo<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) txn: Uuid,
+	pub(super) name: String,
+}
+
+impl<C> RollbackTo<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> RollbackTo<'static, C> {
+		RollbackTo {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for RollbackTo<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			router
+				.execute_unit(
+					self.client.session_id,
+					Command::RollbackTo {
+						txn: self.txn,
+						name: self.name,
+					},
+				)
+				.await
+		})
+	}
+}
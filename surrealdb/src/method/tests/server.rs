@@ -26,6 +26,15 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				| Command::Rollback {
 					..
 				}
+				| Command::Savepoint {
+					..
+				}
+				| Command::RollbackTo {
+					..
+				}
+				| Command::Release {
+					..
+				}
 				| Command::Revoke {
 					..
 				} => query_result,
@@ -2,7 +2,10 @@ use std::borrow::Cow;
 
 use uuid::Uuid;
 
-use crate::method::{Cancel, Commit, Create, Delete, Insert, Query, Select, Update, Upsert};
+use crate::method::{
+	Cancel, Commit, Create, Delete, Insert, Query, Release, RollbackTo, Savepoint, Select, Update,
+	Upsert,
+};
 use crate::opt::{CreateResource, IntoResource};
 use crate::{Connection, Surreal};
 
@@ -36,6 +39,36 @@ where
 		Cancel::from_transaction(self)
 	}
 
+	/// Sets a named savepoint in this transaction, which the transaction can
+	/// later be rolled back to with [`Transaction::rollback_to`].
+	pub fn savepoint(&self, name: impl Into<String>) -> Savepoint<'_, C> {
+		Savepoint {
+			client: Cow::Borrowed(&self.client),
+			txn: self.id,
+			name: name.into(),
+		}
+	}
+
+	/// Reverts the changes made in this transaction since the named savepoint
+	/// was set. The savepoint is kept, so it can be rolled back to again.
+	pub fn rollback_to(&self, name: impl Into<String>) -> RollbackTo<'_, C> {
+		RollbackTo {
+			client: Cow::Borrowed(&self.client),
+			txn: self.id,
+			name: name.into(),
+		}
+	}
+
+	/// Releases the named savepoint, and every savepoint set after it, keeping
+	/// the changes made since it was set.
+	pub fn release(&self, name: impl Into<String>) -> Release<'_, C> {
+		Release {
+			client: Cow::Borrowed(&self.client),
+			txn: self.id,
+			name: name.into(),
+		}
+	}
+
 	/// See [Surreal::query]
 	pub fn query<'client>(&'client self, query: impl Into<Cow<'client, str>>) -> Query<'client, C> {
 		self.client.query(query).with_transaction(self.id)
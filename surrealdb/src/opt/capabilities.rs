@@ -14,14 +14,18 @@ use surrealdb_core::dbs::capabilities::{
 /// Controls how the new streaming planner/executor is used for query processing.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum PlannerStrategy {
-	/// Try the new planner for read-only statements, fall back to compute on unimplemented.
-	/// This is the default.
+	/// Try the new planner for read-only statements and supported write statements, fall back
+	/// to compute on unimplemented. This is the default.
 	BestEffort,
 	/// Skip the new planner entirely; always use the compute executor.
 	ComputeOnly,
 	/// Require the new planner for all read-only statements.
 	/// Unimplemented features become hard errors instead of falling back.
 	AllReadOnly,
+	/// Like `AllReadOnly`, and additionally plan supported write statements
+	/// (`CREATE`, `UPDATE`, `UPSERT`, `DELETE`, `INSERT` and `RELATE`) with the
+	/// streaming executor.
+	All,
 }
 
 /// Not public API
@@ -32,6 +36,7 @@ impl From<PlannerStrategy> for NewPlannerStrategy {
 			PlannerStrategy::BestEffort => NewPlannerStrategy::BestEffortReadOnlyStatements,
 			PlannerStrategy::ComputeOnly => NewPlannerStrategy::ComputeOnly,
 			PlannerStrategy::AllReadOnly => NewPlannerStrategy::AllReadOnlyStatements,
+			PlannerStrategy::All => NewPlannerStrategy::AllStatements,
 		}
 	}
 }
@@ -217,12 +222,15 @@ impl Capabilities {
 	/// Set the planner strategy for query processing.
 	///
 	/// Controls how the streaming query planner/executor is used:
-	/// - [`PlannerStrategy::BestEffort`] (default): Try the new planner for read-only statements,
-	///   fall back to compute on unimplemented features.
+	/// - [`PlannerStrategy::BestEffort`] (default): Try the new planner for read-only statements
+	///   and supported write statements, fall back to compute on unimplemented features.
 	/// - [`PlannerStrategy::ComputeOnly`]: Skip the new planner entirely; always use the compute
 	///   executor.
 	/// - [`PlannerStrategy::AllReadOnly`]: Require the new planner for all read-only statements.
 	///   Unimplemented features become hard errors.
+	/// - [`PlannerStrategy::All`]: As `AllReadOnly`, and additionally stream supported write
+	///   statements (`CREATE`, `UPDATE`, `UPSERT`, `DELETE`, `INSERT` and `RELATE`) through the
+	///   new executor.
 	pub fn with_planner_strategy(self, strategy: PlannerStrategy) -> Self {
 		Self {
 			cap: self.cap.with_planner_strategy(strategy.into()),
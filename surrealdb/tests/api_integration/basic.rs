@@ -1839,6 +1839,36 @@ pub async fn client_side_transactions(_new_db: impl CreateDb) {
 	// Client-side transactions are not supported on HTTP
 }
 
+#[cfg(not(feature = "protocol-http"))]
+pub async fn client_side_savepoints(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let txn = db.begin().await.unwrap();
+	txn.query("CREATE user:john").await.unwrap().check().unwrap();
+	txn.savepoint("before_jane").await.unwrap();
+	txn.query("CREATE user:jane").await.unwrap().check().unwrap();
+	// Revert the creation of Jane, keeping John
+	txn.rollback_to("before_jane").await.unwrap();
+	txn.query("CREATE user:alice").await.unwrap().check().unwrap();
+	txn.release("before_jane").await.unwrap();
+	// A released savepoint can no longer be rolled back to
+	txn.rollback_to("before_jane").await.unwrap_err();
+	let db = txn.commit().await.unwrap();
+
+	let mut response = db.query("SELECT VALUE id FROM user").await.unwrap();
+	let ids: Vec<RecordId> = response.take(0).unwrap();
+	assert_eq!(ids, vec![RecordId::new("user", "alice"), RecordId::new("user", "john")]);
+
+	drop(permit);
+}
+
+#[cfg(feature = "protocol-http")]
+pub async fn client_side_savepoints(_new_db: impl CreateDb) {
+	// Client-side transactions are not supported on HTTP
+}
+
 pub async fn refresh_tokens(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -2012,5 +2042,7 @@ define_include_tests!(basic => {
 	#[test_log::test(tokio::test)]
 	client_side_transactions,
 	#[test_log::test(tokio::test)]
+	client_side_savepoints,
+	#[test_log::test(tokio::test)]
 	refresh_tokens,
 });
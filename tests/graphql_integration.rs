@@ -1404,6 +1404,184 @@ mod graphql_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn batched_record_links_and_relations() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_secs(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Set up: several people sharing company links, and a relation to projects
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO;
+
+					DEFINE TABLE company SCHEMAFUL;
+					DEFINE FIELD name ON company TYPE string;
+
+					DEFINE TABLE project SCHEMAFUL;
+					DEFINE FIELD name ON project TYPE string;
+
+					DEFINE TABLE person SCHEMAFUL;
+					DEFINE FIELD name ON person TYPE string;
+					DEFINE FIELD company ON person TYPE option<record<company>>;
+
+					DEFINE TABLE works_on TYPE RELATION FROM person TO project SCHEMAFUL;
+					DEFINE FIELD in ON works_on TYPE record<person>;
+					DEFINE FIELD out ON works_on TYPE record<project>;
+					DEFINE FIELD role ON works_on TYPE string;
+
+					CREATE company:acme SET name = "Acme";
+					CREATE company:globex SET name = "Globex";
+					CREATE project:p1 SET name = "Rocket";
+					CREATE project:p2 SET name = "Anvil";
+
+					CREATE person:alice SET name = "Alice", company = company:acme;
+					CREATE person:bob SET name = "Bob", company = company:acme;
+					CREATE person:carol SET name = "Carol", company = company:globex;
+					CREATE person:dave SET name = "Dave";
+
+					RELATE person:alice->works_on->project:p1 SET role = "dev";
+					RELATE person:alice->works_on->project:p2 SET role = "lead";
+					RELATE person:bob->works_on->project:p1 SET role = "qa";
+				"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// Links and relations of every listed record resolve to their own values
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query {
+						person(order: {asc: name}) {
+							name
+							company { name }
+							works_on(order: {asc: role}) {
+								role
+								out { name }
+							}
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({
+				"data": {
+					"person": [
+						{
+							"name": "Alice",
+							"company": { "name": "Acme" },
+							"works_on": [
+								{ "role": "dev", "out": { "name": "Rocket" } },
+								{ "role": "lead", "out": { "name": "Anvil" } }
+							]
+						},
+						{
+							"name": "Bob",
+							"company": { "name": "Acme" },
+							"works_on": [
+								{ "role": "qa", "out": { "name": "Rocket" } }
+							]
+						},
+						{
+							"name": "Carol",
+							"company": { "name": "Globex" },
+							"works_on": []
+						},
+						{
+							"name": "Dave",
+							"company": null,
+							"works_on": []
+						}
+					]
+				}
+			});
+			assert_eq!(expected, body)
+		}
+
+		// Relation filters apply to each parent within a batch
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query {
+						person(order: {asc: name}, limit: 2) {
+							name
+							works_on(where: {role: {eq: "dev"}}) { role }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({
+				"data": {
+					"person": [
+						{ "name": "Alice", "works_on": [{ "role": "dev" }] },
+						{ "name": "Bob", "works_on": [] }
+					]
+				}
+			});
+			assert_eq!(expected, body)
+		}
+
+		// Mutations are not hidden by records fetched earlier in the request
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation {
+						first: updatePerson(id: "alice", data: { name: "Alice" }) {
+							company { name }
+						}
+						rename: updateCompany(id: "acme", data: { name: "Acme Corp" }) {
+							name
+						}
+						second: updatePerson(id: "bob", data: { name: "Bob" }) {
+							company { name }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({
+				"data": {
+					"first": { "company": { "name": "Acme" } },
+					"rename": { "name": "Acme Corp" },
+					"second": { "company": { "name": "Acme Corp" } }
+				}
+			});
+			assert_eq!(expected, body)
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn version() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_versioning().await.unwrap();
@@ -1937,6 +2115,239 @@ mod graphql_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn connections_and_aggregates() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_secs(10))
+			.default_headers(headers)
+			.build()?;
+
+		// add schema and data
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+                    DEFINE CONFIG GRAPHQL AUTO;
+                    DEFINE TABLE item SCHEMAFUL;
+                    DEFINE FIELD val ON item TYPE int;
+                    DEFINE FIELD cat ON item TYPE string;
+                    CREATE item:1 SET val = 10, cat = "a";
+                    CREATE item:2 SET val = 20, cat = "a";
+                    CREATE item:3 SET val = 30, cat = "b";
+                    CREATE item:4 SET val = 40, cat = "b";
+                    CREATE item:5 SET val = 50, cat = "b";
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// first page
+		let end_cursor = {
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{_connection_item(first: 2){
+						edges { node { id, val } }
+						pageInfo { hasNextPage, hasPreviousPage, endCursor }
+						totalCount
+					}}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let conn = &body["data"]["_connection_item"];
+			assert_eq!(
+				conn["edges"],
+				json!([
+					{ "node": { "id": "item:1", "val": 10 } },
+					{ "node": { "id": "item:2", "val": 20 } }
+				])
+			);
+			assert_eq!(conn["pageInfo"]["hasNextPage"], true);
+			assert_eq!(conn["pageInfo"]["hasPreviousPage"], false);
+			assert_eq!(conn["totalCount"], 5);
+			conn["pageInfo"]["endCursor"].as_str().unwrap().to_string()
+		};
+
+		// next page, after the end cursor of the first page
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({
+						"query": r#"query($after: String){_connection_item(first: 2, after: $after){
+							edges { node { id } }
+							pageInfo { hasNextPage, hasPreviousPage }
+						}}"#,
+						"variables": { "after": end_cursor }
+					})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({
+				"data": {
+					"_connection_item": {
+						"edges": [
+							{ "node": { "id": "item:3" } },
+							{ "node": { "id": "item:4" } }
+						],
+						"pageInfo": { "hasNextPage": true, "hasPreviousPage": true }
+					}
+				}
+			});
+			assert_eq!(expected, body)
+		}
+
+		// last page, paginating backwards
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{_connection_item(last: 2){
+						edges { node { id } }
+						pageInfo { hasNextPage, hasPreviousPage }
+					}}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({
+				"data": {
+					"_connection_item": {
+						"edges": [
+							{ "node": { "id": "item:4" } },
+							{ "node": { "id": "item:5" } }
+						],
+						"pageInfo": { "hasNextPage": false, "hasPreviousPage": true }
+					}
+				}
+			});
+			assert_eq!(expected, body)
+		}
+
+		// ordering and filtering
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{_connection_item(first: 2, order: {desc: val}, where: {cat: {eq: "b"}}){
+						edges { node { id } }
+						totalCount
+					}}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({
+				"data": {
+					"_connection_item": {
+						"edges": [
+							{ "node": { "id": "item:5" } },
+							{ "node": { "id": "item:4" } }
+						],
+						"totalCount": 3
+					}
+				}
+			});
+			assert_eq!(expected, body)
+		}
+
+		// first and last cannot be combined
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{_connection_item(first: 1, last: 1){ totalCount }}"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			let body = res.json::<serde_json::Value>().await?;
+			let message = body["errors"][0]["message"].as_str().unwrap_or_default();
+			assert!(message.contains("both `first` and `last`"), "body: {body}");
+		}
+
+		// aggregate over the whole table
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{_aggregate_item{
+						group, count, sum { val }, avg { val }, min { val }, max { val }
+					}}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({
+				"data": {
+					"_aggregate_item": [
+						{
+							"group": null,
+							"count": 5,
+							"sum": { "val": 150 },
+							"avg": { "val": 30.0 },
+							"min": { "val": 10 },
+							"max": { "val": 50 }
+						}
+					]
+				}
+			});
+			assert_eq!(expected, body)
+		}
+
+		// aggregate grouped by a field
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{_aggregate_item(groupBy: [cat]){
+						group, count, sum { val }
+					}}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({
+				"data": {
+					"_aggregate_item": [
+						{ "group": { "cat": "a" }, "count": 2, "sum": { "val": 30 } },
+						{ "group": { "cat": "b" }, "count": 3, "sum": { "val": 120 } }
+					]
+				}
+			});
+			assert_eq!(expected, body)
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn nested_objects() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_without_auth().await.unwrap();
@@ -926,6 +926,129 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn signin_challenge_endpoint() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let url = &format!("http://{addr}/signin");
+
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Create a DB user with a second factor
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body(
+					r#"DEFINE USER user_db ON DB PASSWORD 'pass_db' TOTP SECRET 'GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ' RECOVERY [] ROLES VIEWER"#,
+				)
+				.send()
+				.await?;
+			assert!(res.status().is_success(), "body: {}", res.text().await?);
+		}
+
+		let req_body = serde_json::to_string(
+			json!({
+				"ns": ns,
+				"db": db,
+				"user": "user_db",
+				"pass": "pass_db",
+			})
+			.as_object()
+			.unwrap(),
+		)
+		.unwrap();
+
+		// Basic authentication is rejected for a user with a second factor
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth("user_db", Some("pass_db"))
+				.body("INFO FOR DB")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 401, "body: {}", res.text().await?);
+		}
+
+		// A challenge can not be returned as plain text
+		{
+			let res = client
+				.post(url)
+				.header(header::ACCEPT, "text/plain")
+				.body(req_body.clone())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 415, "body: {}", res.text().await?);
+		}
+
+		// Signin returns a challenge, which is completed with the current code
+		let code = {
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("RETURN crypto::totp::code('GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ')")
+				.send()
+				.await?;
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			body[0]["result"].as_str().unwrap().to_string()
+		};
+		let signin = async || -> Result<String, Box<dyn std::error::Error>> {
+			let res = client.post(url).body(req_body.clone()).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(body["details"], "A second factor is required", "body: {body}");
+			Ok(body["token"]["challenge"].as_str().unwrap().to_string())
+		};
+		{
+			let req_body = json!({
+				"challenge": signin().await?,
+				"code": code,
+			});
+			let res = client
+				.post(format!("http://{addr}/signin/challenge"))
+				.body(req_body.to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			let token = body["token"].as_str().unwrap();
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.bearer_auth(token)
+				.body("INFO FOR DB")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// The same code can not be used again
+		{
+			let req_body = json!({
+				"challenge": signin().await?,
+				"code": code,
+			});
+			let res = client
+				.post(format!("http://{addr}/signin/challenge"))
+				.body(req_body.to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 401, "body: {}", res.text().await?);
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn signup_endpoint() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
@@ -1164,19 +1287,50 @@ mod http_integration {
 			.default_headers(headers)
 			.build()?;
 
-		// GET
+		// Create a root user with the Editor role
 		{
-			let res = client.get(url).send().await?;
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("DEFINE USER editor ON ROOT PASSWORD 'editor' ROLES EDITOR")
+				.send()
+				.await?;
 			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
-			let body = res.text().await?;
-			assert_eq!(body, r#"Save"#, "body: {body}");
 		}
-		// POST
+
+		// When no auth is provided, the endpoint returns a 403
 		{
+			let res = client.get(url).send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
 			let res = client.post(url).body("").send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+		}
+
+		// A snapshot includes the user catalog, so only owners may take or restore one
+		{
+			let res = client.get(url).basic_auth("editor", Some("editor")).send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+			let res = client.post(url).basic_auth("editor", Some("editor")).body("").send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+		}
+
+		// An owner can take a snapshot and restore it
+		{
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
 			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
-			let body = res.text().await?;
-			assert_eq!(body, r#"Load"#, "body: {body}");
+			let body = res.bytes().await?;
+			assert!(body.starts_with(b"SURBAKUP"));
+			let res = client.post(url).basic_auth(USER, Some(PASS)).body(body).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// Only the latest version of each key can be requested
+		{
+			let url = format!("{url}?latest_only=true");
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let body = res.bytes().await?;
+			assert!(body.starts_with(b"SURBAKUP"));
 		}
 
 		Ok(())